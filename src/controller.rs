@@ -4,6 +4,7 @@
 //! real-time control of the running load test.
 
 use crate::metrics::SwanlingMetrics;
+use crate::swanling::SwanlingReconfiguration;
 use crate::util;
 use crate::{
     AttackPhase, SwanlingAttack, SwanlingAttackRunState, SwanlingConfiguration, SwanlingError,
@@ -96,6 +97,30 @@ pub enum SwanlingControllerCommand {
     ///
     /// This can be configured when Swanling is idle as well as when a Swanling load test is running.
     RunTime,
+    /// Change the maximum requests per second of a throttled load test.
+    ///
+    /// # Example
+    /// Tells Swanling to allow at most 50 requests per second.
+    /// ```notest
+    /// throttle 50
+    /// ```
+    ///
+    /// Swanling must be running with `--throttle-requests` enabled to process this
+    /// command. In a Regatta, the change is sent to all Workers.
+    Throttle,
+    /// Retrieve the most recent lines of a Worker's request log.
+    ///
+    /// # Example
+    /// Tells the Worker that connected second to send the end of its request log to the
+    /// Manager.
+    /// ```notest
+    /// request-log 2
+    /// ```
+    ///
+    /// Swanling must be running as a Manager, and the Worker must be running with
+    /// `--request-log` enabled. The Manager saves the log to a file the next time the
+    /// Worker pushes metrics.
+    RequestLog,
     /// Display the current [`SwanlingConfiguration`](../struct.SwanlingConfiguration.html)s.
     ///
     /// # Example
@@ -134,6 +159,38 @@ pub enum SwanlingControllerCommand {
     ///
     /// This command can be run at any time.
     MetricsJson,
+    /// Discard the metrics collected so far, starting a clean measurement.
+    ///
+    /// # Example
+    /// Resets the metrics of the running load test, for example after a warmup period.
+    /// ```notest
+    /// reset-metrics
+    /// ```
+    ///
+    /// Swanling must be running to process this command.
+    ResetMetrics,
+    /// Pause all users, which finish the task they are running then wait.
+    ///
+    /// # Example
+    /// Pauses the running load test.
+    /// ```notest
+    /// pause
+    /// ```
+    ///
+    /// Swanling must be running to process this command. In a Regatta, the change is
+    /// sent to all Workers.
+    Pause,
+    /// Resume paused users.
+    ///
+    /// # Example
+    /// Resumes a paused load test.
+    /// ```notest
+    /// resume
+    /// ```
+    ///
+    /// Swanling must be running to process this command. In a Regatta, the change is
+    /// sent to all Workers.
+    Resume,
     /// Displays a list of all commands supported by the Controller.
     ///
     /// # Example
@@ -491,6 +548,21 @@ impl SwanlingControllerState {
                 command: SwanlingControllerCommand::MetricsJson,
                 value: None,
             })
+        } else if matches.matched(SwanlingControllerCommand::ResetMetrics as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::ResetMetrics,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::Pause as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Pause,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::Resume as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Resume,
+                value: None,
+            })
         } else if matches.matched(SwanlingControllerCommand::Host as usize) {
             // Perform a second regex to capture the host value.
             let caps = self.captures[SwanlingControllerCommand::Host as usize]
@@ -539,6 +611,26 @@ impl SwanlingControllerState {
                 command: SwanlingControllerCommand::RunTime,
                 value: Some(run_time.to_string()),
             })
+        } else if matches.matched(SwanlingControllerCommand::Throttle as usize) {
+            // Perform a second regex to capture the throttle value.
+            let caps = self.captures[SwanlingControllerCommand::Throttle as usize]
+                .captures(command_string)
+                .unwrap();
+            let throttle = caps.get(2).map_or("", |m| m.as_str());
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Throttle,
+                value: Some(throttle.to_string()),
+            })
+        } else if matches.matched(SwanlingControllerCommand::RequestLog as usize) {
+            // Perform a second regex to capture the worker id.
+            let caps = self.captures[SwanlingControllerCommand::RequestLog as usize]
+                .captures(command_string)
+                .unwrap();
+            let worker_id = caps.get(2).map_or("", |m| m.as_str());
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::RequestLog,
+                value: Some(worker_id.to_string()),
+            })
         } else {
             Err(())
         }
@@ -628,6 +720,26 @@ impl SwanlingControllerState {
                     Err("failed to configure run_time".to_string())
                 }
             }
+            SwanlingControllerCommand::Throttle => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("throttle configured".to_string())
+                } else {
+                    Err(
+                        "failed to configure throttle, be sure load test is running and throttled"
+                            .to_string(),
+                    )
+                }
+            }
+            SwanlingControllerCommand::RequestLog => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("request log requested".to_string())
+                } else {
+                    Err(
+                        "failed to request log, be sure this is a manager and the worker is connected"
+                            .to_string(),
+                    )
+                }
+            }
             SwanlingControllerCommand::Config => {
                 if let SwanlingControllerResponseMessage::Config(config) = response {
                     Ok(format!("{:#?}", config))
@@ -656,6 +768,27 @@ impl SwanlingControllerState {
                     Err("error loading metrics".to_string())
                 }
             }
+            SwanlingControllerCommand::ResetMetrics => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("metrics reset".to_string())
+                } else {
+                    Err("load test not running, failed to reset metrics".to_string())
+                }
+            }
+            SwanlingControllerCommand::Pause => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("load test paused".to_string())
+                } else {
+                    Err("load test not running, failed to pause".to_string())
+                }
+            }
+            SwanlingControllerCommand::Resume => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("load test resumed".to_string())
+                } else {
+                    Err("load test not running, failed to resume".to_string())
+                }
+            }
             SwanlingControllerCommand::Start => {
                 if let SwanlingControllerResponseMessage::Bool(true) = response {
                     Ok("load test started".to_string())
@@ -989,6 +1122,8 @@ pub(crate) async fn controller_main(
     let hatchrate_regex = r"(?i)^(hatchrate|hatch_rate|hatch-rate) ([0-9]*(\.[0-9]*)?){1}$";
     let runtime_regex =
         r"(?i)^(run|runtime|run_time|run-time|) (\d+|((\d+?)h)?((\d+?)m)?((\d+?)s)?)$";
+    let throttle_regex = r"(?i)^(throttle|throttle_requests|throttle-requests) (\d+)$";
+    let request_log_regex = r"(?i)^(requestlog|request_log|request-log) (\d+)$";
 
     // The following RegexSet is matched against all commands received through the controller.
     // Developer note: The order commands are defined here must match the order in which
@@ -1003,6 +1138,10 @@ pub(crate) async fn controller_main(
         hatchrate_regex,
         // Modify how long the load test will run.
         runtime_regex,
+        // Modify how many requests per second the load test makes.
+        throttle_regex,
+        // Retrieve the request log of a Worker.
+        request_log_regex,
         // Display the current load test configuration.
         r"(?i)^config$",
         // Display the current load test configuration in json.
@@ -1011,6 +1150,12 @@ pub(crate) async fn controller_main(
         r"(?i)^(metrics|stats)$",
         // Display running metrics for the currently active load test in json.
         r"(?i)^(metricsjson|metrics-json|statsjson|stats-json)$",
+        // Discard the metrics collected so far.
+        r"(?i)^(reset|resetmetrics|reset-metrics|reset_metrics)$",
+        // Pause a running load test.
+        r"(?i)^pause$",
+        // Resume a paused load test.
+        r"(?i)^resume$",
         // Provide a list of possible commands.
         r"(?i)^(help|\?)$",
         // Exit/quit the controller connection, does not affect load test.
@@ -1031,6 +1176,8 @@ pub(crate) async fn controller_main(
         Regex::new(users_regex).unwrap(),
         Regex::new(hatchrate_regex).unwrap(),
         Regex::new(runtime_regex).unwrap(),
+        Regex::new(throttle_regex).unwrap(),
+        Regex::new(request_log_regex).unwrap(),
     ];
 
     // Counter increments each time a controller client connects with this protocol.
@@ -1088,10 +1235,15 @@ fn display_help() -> String {
  users INT          set number of simulated users
  hatchrate FLOAT    set per-second rate users hatch
  runtime TIME       set how long to run test, ie 1h30m5s
+ throttle INT       set maximum requests per second of throttled test
+ request-log INT    save end of a worker's request log on the manager
  config             display load test configuration
  config-json        display load test configuration in json format
  metrics            display metrics for current load test
- metrics-json       display metrics for current load test in json format",
+ metrics-json       display metrics for current load test in json format
+ reset-metrics      discard metrics collected so far by running load test
+ pause              pause users of running load test
+ resume             resume users of paused load test",
         env!("CARGO_PKG_NAME"),
         env!("CARGO_PKG_VERSION")
     )
@@ -1153,6 +1305,29 @@ impl SwanlingAttack {
                                 )),
                             );
                         }
+                        // Reset the running metrics, and acknowledge command.
+                        SwanlingControllerCommand::ResetMetrics => {
+                            // We can only reset the metrics of a running load test.
+                            if self.attack_phase == AttackPhase::Running {
+                                // First receive all metrics already sent by users, so
+                                // requests completed before the reset aren't counted after it.
+                                self.sync_metrics(swanling_attack_run_state, true).await?;
+                                // Measure duration from the reset.
+                                self.reset_metrics();
+                                swanling_attack_run_state.running_metrics_timer =
+                                    std::time::Instant::now();
+                                info!("metrics reset by controller");
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(true),
+                                );
+                            } else {
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(false),
+                                );
+                            }
+                        }
                         // Start the load test, and acknowledge command.
                         SwanlingControllerCommand::Start => {
                             // We can only start an idle load test.
@@ -1326,6 +1501,70 @@ impl SwanlingAttack {
                                 );
                             }
                         }
+                        SwanlingControllerCommand::Throttle => {
+                            // The controller uses a regular expression to validate that
+                            // this is a valid integer, so simply use it with further
+                            // validation.
+                            if let Some(throttle_requests) = &message.request.value {
+                                // Use expect() as Controller uses regex to validate this is an integer.
+                                let throttle_requests = usize::from_str(throttle_requests)
+                                    .expect("failed to convert string to usize");
+                                // The throttle can only be changed while it is running.
+                                let configured = self.attack_phase == AttackPhase::Running
+                                    && swanling_attack_run_state.throttle_rate.is_some()
+                                    && throttle_requests > 0
+                                    && throttle_requests <= 1_000_000;
+                                if configured {
+                                    self.reconfigure(
+                                        swanling_attack_run_state,
+                                        &SwanlingReconfiguration {
+                                            throttle_requests: Some(throttle_requests),
+                                            ..Default::default()
+                                        },
+                                    );
+                                }
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(configured),
+                                );
+                            } else {
+                                warn!(
+                                    "Controller didn't provide throttle: {:#?}",
+                                    &message.request
+                                );
+                            }
+                        }
+                        // Only a Manager can retrieve the request log of a Worker.
+                        SwanlingControllerCommand::RequestLog => {
+                            self.reply_to_controller(
+                                message,
+                                SwanlingControllerResponseMessage::Bool(false),
+                            );
+                        }
+                        // Pause or resume the users of a running load test, and acknowledge
+                        // command.
+                        SwanlingControllerCommand::Pause | SwanlingControllerCommand::Resume => {
+                            if self.attack_phase == AttackPhase::Running {
+                                let paused =
+                                    message.request.command == SwanlingControllerCommand::Pause;
+                                self.reconfigure(
+                                    swanling_attack_run_state,
+                                    &SwanlingReconfiguration {
+                                        paused: Some(paused),
+                                        ..Default::default()
+                                    },
+                                );
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(true),
+                                );
+                            } else {
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(false),
+                                );
+                            }
+                        }
                         // These messages shouldn't be received here.
                         SwanlingControllerCommand::Help | SwanlingControllerCommand::Exit => {
                             warn!("Unexpected command: {:?}", &message.request);
@@ -0,0 +1,319 @@
+//! Optionally replays the requests recorded in a HAR file.
+//!
+//! Browser developer tools can export a session as a HAR (HTTP Archive) file. When the
+//! `--har` run-time option is set, the file is parsed into a sequence of requests that
+//! each [`SwanlingUser`](../swanling/struct.SwanlingUser.html) replays in the order they
+//! were recorded, with the same method, headers and body. Entries that aren't HTTP
+//! requests to the host of the first entry, such as `data:` URLs or requests for third
+//! party analytics, are skipped so a load test never sends load to other hosts.
+
+use serde::Deserialize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time;
+use url::Url;
+
+use crate::swanling::{
+    SwanlingMethod, SwanlingTask, SwanlingTaskFunction, SwanlingTaskResult, SwanlingTaskSet,
+    SwanlingUser,
+};
+
+/// Request headers that are set by the client, and not replayed.
+const SKIPPED_HEADERS: [&str; 4] = ["connection", "content-length", "host", "transfer-encoding"];
+
+/// The top level object of a HAR file.
+#[derive(Debug, Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+/// The recorded session.
+#[derive(Debug, Deserialize)]
+struct HarLog {
+    entries: Vec<HarEntry>,
+}
+
+/// One recorded request and its response, of which only the request is replayed.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarEntry {
+    started_date_time: String,
+    request: HarRequest,
+}
+
+/// A recorded request.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    post_data: Option<HarPostData>,
+}
+
+/// A recorded request header.
+#[derive(Debug, Deserialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+/// The body of a recorded request.
+#[derive(Debug, Deserialize)]
+struct HarPostData {
+    #[serde(default)]
+    text: String,
+}
+
+/// A request parsed from a HAR file, replayed by each user.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SwanlingHarRequest {
+    /// The method of the request.
+    pub(crate) method: SwanlingMethod,
+    /// The path and query of the request, relative to the host.
+    pub(crate) path: String,
+    /// The headers of the request, other than those set by the client.
+    pub(crate) headers: Vec<(String, String)>,
+    /// The body of the request, if any.
+    pub(crate) body: Option<String>,
+    /// How many milliseconds after the previous request this request started.
+    pub(crate) delay: u64,
+}
+
+/// The requests parsed from a HAR file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SwanlingHar {
+    /// The scheme, host and port of the first request, which all replayed requests share.
+    pub(crate) host: String,
+    /// The requests to replay, in the order they were recorded.
+    pub(crate) requests: Vec<SwanlingHarRequest>,
+    /// How many entries were skipped.
+    pub(crate) skipped: usize,
+}
+
+/// Parse the contents of a HAR file, returning a description of the problem if it can't
+/// be replayed.
+pub(crate) fn parse_har(contents: &str) -> Result<SwanlingHar, String> {
+    let har: Har =
+        serde_json::from_str(contents).map_err(|e| format!("is not a valid HAR file: {}", e))?;
+
+    let mut host: Option<String> = None;
+    let mut requests = Vec::new();
+    let mut skipped = 0;
+    let mut previous_started: Option<chrono::DateTime<chrono::FixedOffset>> = None;
+    for entry in har.log.entries {
+        let url = match Url::parse(&entry.request.url) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => url,
+            // Skip data: URLs, and anything else that isn't an HTTP request.
+            _ => {
+                debug!("skipping HAR entry: {}", entry.request.url);
+                skipped += 1;
+                continue;
+            }
+        };
+
+        // All requests are made to the host of the first request.
+        let origin = url.origin().ascii_serialization();
+        if host.get_or_insert_with(|| origin.clone()) != &origin {
+            debug!("skipping HAR entry for another host: {}", entry.request.url);
+            skipped += 1;
+            continue;
+        }
+
+        let method = match entry.request.method.to_uppercase().as_str() {
+            "DELETE" => SwanlingMethod::Delete,
+            "GET" => SwanlingMethod::Get,
+            "HEAD" => SwanlingMethod::Head,
+            "PATCH" => SwanlingMethod::Patch,
+            "POST" => SwanlingMethod::Post,
+            "PUT" => SwanlingMethod::Put,
+            _ => {
+                debug!(
+                    "skipping HAR entry with unsupported method: {} {}",
+                    entry.request.method, entry.request.url
+                );
+                skipped += 1;
+                continue;
+            }
+        };
+
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+
+        // HTTP/2 pseudo-headers such as `:authority` are also set by the client.
+        let headers = entry
+            .request
+            .headers
+            .into_iter()
+            .filter(|header| {
+                !header.name.starts_with(':')
+                    && !SKIPPED_HEADERS.contains(&header.name.to_lowercase().as_str())
+            })
+            .map(|header| (header.name, header.value))
+            .collect();
+
+        let body = entry
+            .request
+            .post_data
+            .map(|post_data| post_data.text)
+            .filter(|text| !text.is_empty());
+
+        // Requests are replayed as long after the previous request as they were recorded.
+        let started = chrono::DateTime::parse_from_rfc3339(&entry.started_date_time).ok();
+        let delay = match (previous_started, started) {
+            (Some(previous), Some(started)) => (started - previous).num_milliseconds().max(0),
+            _ => 0,
+        } as u64;
+        if started.is_some() {
+            previous_started = started;
+        }
+
+        requests.push(SwanlingHarRequest {
+            method,
+            path,
+            headers,
+            body,
+            delay,
+        });
+    }
+
+    match host {
+        Some(host) if !requests.is_empty() => Ok(SwanlingHar {
+            host,
+            requests,
+            skipped,
+        }),
+        _ => Err("doesn't contain any HTTP requests.".to_string()),
+    }
+}
+
+/// Build a task set with a task for each request parsed from a HAR file, run in the order
+/// they were recorded, optionally waiting as long before each request as was recorded.
+pub(crate) fn har_task_set(har: SwanlingHar, timing: bool) -> SwanlingTaskSet {
+    let mut task_set = SwanlingTaskSet::new("HAR").set_host(&har.host);
+    for (index, request) in har.requests.into_iter().enumerate() {
+        let request = Arc::new(request);
+        let closure: SwanlingTaskFunction = Arc::new(move |user| {
+            let request = request.clone();
+            Box::pin(async move { replay(user, &request, timing).await })
+        });
+        // Tasks aren't named, so requests are named by their path.
+        task_set = task_set.register_task(SwanlingTask::new(closure).set_sequence(index + 1));
+    }
+
+    task_set
+}
+
+/// Replay a request, first waiting as long as was recorded if timing is enabled.
+async fn replay(
+    user: &SwanlingUser,
+    request: &SwanlingHarRequest,
+    timing: bool,
+) -> SwanlingTaskResult {
+    if timing && request.delay > 0 {
+        tokio::time::sleep(time::Duration::from_millis(request.delay)).await;
+        // Time spent waiting is not a delay for Coordinated Omission Mitigation.
+        user.slept.fetch_add(request.delay, Ordering::SeqCst);
+    }
+
+    let mut request_builder = match request.method {
+        SwanlingMethod::Delete => user.swanling_delete(&request.path).await?,
+        SwanlingMethod::Get => user.swanling_get(&request.path).await?,
+        SwanlingMethod::Head => user.swanling_head(&request.path).await?,
+        SwanlingMethod::Patch => user.swanling_patch(&request.path).await?,
+        SwanlingMethod::Post => user.swanling_post(&request.path).await?,
+        SwanlingMethod::Put => user.swanling_put(&request.path).await?,
+    };
+    for (name, value) in &request.headers {
+        request_builder = request_builder.header(name.as_str(), value.as_str());
+    }
+    if let Some(body) = &request.body {
+        request_builder = request_builder.body(body.to_string());
+    }
+    let _swanling = user.swanling_send(request_builder, None).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let har = parse_har(
+            r#"{"log": {"entries": [
+                {
+                    "startedDateTime": "2021-06-01T10:00:00.000Z",
+                    "request": {
+                        "method": "GET",
+                        "url": "https://example.com/search?q=swan",
+                        "headers": [
+                            {"name": ":authority", "value": "example.com"},
+                            {"name": "Host", "value": "example.com"},
+                            {"name": "Accept", "value": "text/html"}
+                        ]
+                    }
+                },
+                {
+                    "startedDateTime": "2021-06-01T10:00:00.100Z",
+                    "request": {"method": "GET", "url": "data:image/png;base64,AAAA"}
+                },
+                {
+                    "startedDateTime": "2021-06-01T10:00:00.200Z",
+                    "request": {"method": "GET", "url": "https://analytics.example.net/collect"}
+                },
+                {
+                    "startedDateTime": "2021-06-01T10:00:00.300Z",
+                    "request": {"method": "OPTIONS", "url": "https://example.com/login"}
+                },
+                {
+                    "startedDateTime": "2021-06-01T10:00:01.500Z",
+                    "request": {
+                        "method": "post",
+                        "url": "https://example.com/login",
+                        "postData": {"mimeType": "application/json", "text": "{\"user\": \"swan\"}"}
+                    }
+                }
+            ]}}"#,
+        )
+        .unwrap();
+
+        // Skipped entries don't affect the delay between replayed requests.
+        assert_eq!(har.host, "https://example.com");
+        assert_eq!(har.skipped, 3);
+        assert_eq!(
+            har.requests,
+            vec![
+                SwanlingHarRequest {
+                    method: SwanlingMethod::Get,
+                    path: "/search?q=swan".to_string(),
+                    headers: vec![("Accept".to_string(), "text/html".to_string())],
+                    body: None,
+                    delay: 0,
+                },
+                SwanlingHarRequest {
+                    method: SwanlingMethod::Post,
+                    path: "/login".to_string(),
+                    headers: vec![],
+                    body: Some("{\"user\": \"swan\"}".to_string()),
+                    delay: 1_500,
+                },
+            ]
+        );
+
+        // Files without any requests to replay are rejected.
+        assert!(parse_har("{}").is_err());
+        assert!(parse_har(r#"{"log": {"entries": []}}"#).is_err());
+        assert!(parse_har(
+            r#"{"log": {"entries": [{
+                "startedDateTime": "2021-06-01T10:00:00.000Z",
+                "request": {"method": "GET", "url": "data:text/plain,swan"}
+            }]}}"#
+        )
+        .is_err());
+    }
+}
@@ -418,12 +418,14 @@
 extern crate log;
 
 pub mod controller;
+mod har;
 pub mod logger;
 #[cfg(feature = "gaggle")]
 mod manager;
 pub mod metrics;
 pub mod prelude;
 mod report;
+mod sigv4;
 pub mod swanling;
 mod throttle;
 mod user;
@@ -443,6 +445,7 @@ use simplelog::*;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
+use std::net::ToSocketAddrs;
 use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -451,12 +454,22 @@ use std::sync::{
 use std::{fmt, io, time};
 use tokio::fs::File;
 use tokio::runtime::Runtime;
+use tokio::sync::Semaphore;
 
 use crate::controller::{SwanlingControllerProtocol, SwanlingControllerRequest};
 use crate::logger::{SwanlingLogFormat, SwanlingLoggerJoinHandle, SwanlingLoggerTx};
-use crate::metrics::{SwanlingCoordinatedOmissionMitigation, SwanlingMetric, SwanlingMetrics};
+use crate::metrics::{
+    SwanlingAdaptive, SwanlingComparison, SwanlingControlAction, SwanlingCooldownWindow,
+    SwanlingCoordinatedOmissionMitigation, SwanlingIterations, SwanlingMetric, SwanlingMetrics,
+    SwanlingMetricsCallback, SwanlingMetricsCallbackThread, SwanlingMetricsStream,
+    SwanlingMetricsStreamSender, SwanlingRampToFailure, SwanlingRegressionBounds,
+    SwanlingShutdownHandle, SwanlingSmokeResult, SwanlingSpikeWindow, SwanlingSuiteMetrics,
+};
 use crate::swanling::{
-    GaggleUser, SwanlingTask, SwanlingTaskSet, SwanlingUser, SwanlingUserCommand,
+    build_standalone_client, GaggleUser, SwanlingClientCustomizer, SwanlingIterationCounters,
+    SwanlingReconfiguration, SwanlingRequestNamer, SwanlingSession, SwanlingSharedValues,
+    SwanlingTask, SwanlingTaskScheduler, SwanlingTaskSchedulerFactory, SwanlingTaskSet,
+    SwanlingUser, SwanlingUserCommand,
 };
 #[cfg(feature = "gaggle")]
 use crate::worker::{register_shutdown_pipe_handler, GaggleMetrics};
@@ -464,12 +477,34 @@ use crate::worker::{register_shutdown_pipe_handler, GaggleMetrics};
 /// Constant defining Swanling's default port when running a Regatta.
 const DEFAULT_PORT: &str = "5115";
 
+/// Transports the Manager and Workers of a Regatta can communicate over.
+const GAGGLE_TRANSPORTS: [&str; 3] = ["tcp", "inproc", "ipc"];
+
+/// Percent change from the `--baseline` that is a regression, if not configured.
+const DEFAULT_REGRESSION_THRESHOLD: usize = 10;
+
+/// How many milliseconds a Worker waits between pushing metrics to the Manager.
+const DEFAULT_WORKER_METRICS_INTERVAL: usize = 1_000;
+
+/// The most messages nng can buffer on the Manager and Worker sockets.
+const GAGGLE_MAX_SEND_BUFFER: usize = 8_192;
+
+/// The most sockets the Manager can shard Workers across.
+const MAX_MANAGER_SOCKETS: usize = 64;
+
+/// How many seconds to wait for the `--health-check-url` to respond.
+const HEALTH_CHECK_TIMEOUT: u64 = 10;
+
 /// Constant defining Swanling's default telnet Controller port.
 const DEFAULT_TELNET_PORT: &str = "5116";
 
 /// Constant defining Swanling's default WebSocket Controller port.
 const DEFAULT_WEBSOCKET_PORT: &str = "5117";
 
+/// File descriptors set aside for log files, Controllers and other overhead when
+/// estimating how many a load test needs.
+const RESERVED_FILE_DESCRIPTORS: usize = 64;
+
 // WORKER_ID is only used when running a gaggle (a distributed load test).
 lazy_static! {
     static ref WORKER_ID: AtomicUsize = AtomicUsize::new(0);
@@ -532,6 +567,13 @@ pub enum SwanlingError {
         /// An optional explanation of the error.
         detail: String,
     },
+    /// One or more problems were found validating the configuration with `--validate-config`.
+    InvalidConfiguration {
+        /// A description of each problem found.
+        problems: Vec<String>,
+        /// An optional explanation of the error.
+        detail: String,
+    },
     /// Invalid wait time specified.
     InvalidWaitTime {
         // The specified minimum wait time.
@@ -553,6 +595,44 @@ pub enum SwanlingError {
         /// An optional explanation of the error.
         detail: String,
     },
+    /// One or more service level agreements configured with `--sla` were not met.
+    SlaBreach {
+        /// The agreements that were not met.
+        breached: Vec<String>,
+        /// The final metrics of the load test, including the result of checking each
+        /// agreement in `slas`.
+        metrics: Box<SwanlingMetrics>,
+        /// An optional explanation of the error.
+        detail: String,
+    },
+    /// One or more metrics regressed from the baseline configured with `--baseline` by
+    /// more than the `--regression-threshold`.
+    Regression {
+        /// The metrics that regressed.
+        regressed: Vec<String>,
+        /// The final metrics of the load test, including the comparison with the baseline
+        /// in `comparison`.
+        metrics: Box<SwanlingMetrics>,
+        /// An optional explanation of the error.
+        detail: String,
+    },
+    /// One or more tasks failed during a smoke test started with `--smoke`.
+    SmokeFailed {
+        /// The tasks that failed.
+        failed: Vec<String>,
+        /// The metrics of the smoke test, including the result of each task in `smoke`.
+        metrics: Box<SwanlingMetrics>,
+        /// An optional explanation of the error.
+        detail: String,
+    },
+    /// The URL configured with `--health-check-url` didn't return the expected status code
+    /// before the load test started.
+    HealthCheckFailed {
+        /// The URL that was checked.
+        url: String,
+        /// An optional explanation of the error.
+        detail: String,
+    },
 }
 /// Implement a helper to provide a text description of all possible types of errors.
 impl SwanlingError {
@@ -564,9 +644,14 @@ impl SwanlingError {
             SwanlingError::FeatureNotEnabled { .. } => "required compile-time feature not enabled",
             SwanlingError::InvalidHost { .. } => "failed to parse hostname",
             SwanlingError::InvalidOption { .. } => "invalid option or value specified",
+            SwanlingError::InvalidConfiguration { .. } => "invalid configuration",
             SwanlingError::InvalidWaitTime { .. } => "invalid wait_time specified",
             SwanlingError::InvalidWeight { .. } => "invalid weight specified",
             SwanlingError::NoTaskSets { .. } => "no task sets defined",
+            SwanlingError::SlaBreach { .. } => "service level agreement breached",
+            SwanlingError::Regression { .. } => "regressed from baseline",
+            SwanlingError::SmokeFailed { .. } => "smoke test failed",
+            SwanlingError::HealthCheckFailed { .. } => "pre-run health check failed",
         }
     }
 }
@@ -588,6 +673,21 @@ impl fmt::Display for SwanlingError {
             SwanlingError::InvalidHost {
                 ref parse_error, ..
             } => write!(f, "SwanlingError: {} ({})", self.describe(), parse_error),
+            SwanlingError::InvalidConfiguration { ref detail, .. } => {
+                write!(f, "SwanlingError: {} ({})", self.describe(), detail)
+            }
+            SwanlingError::SlaBreach { ref detail, .. } => {
+                write!(f, "SwanlingError: {} ({})", self.describe(), detail)
+            }
+            SwanlingError::Regression { ref detail, .. } => {
+                write!(f, "SwanlingError: {} ({})", self.describe(), detail)
+            }
+            SwanlingError::SmokeFailed { ref detail, .. } => {
+                write!(f, "SwanlingError: {} ({})", self.describe(), detail)
+            }
+            SwanlingError::HealthCheckFailed { ref detail, .. } => {
+                write!(f, "SwanlingError: {} ({})", self.describe(), detail)
+            }
             _ => write!(f, "SwanlingError: {}", self.describe()),
         }
     }
@@ -608,6 +708,23 @@ impl std::error::Error for SwanlingError {
     }
 }
 
+// Describe a problem found while configuring a load test, including the details most errors
+// only expose through `Debug`.
+fn describe_problem(problem: &SwanlingError) -> String {
+    match problem {
+        SwanlingError::InvalidOption {
+            option,
+            value,
+            detail,
+        } => format!("{} = {}: {}", option, value, detail),
+        SwanlingError::InvalidHost { host, detail, .. } => format!("{}: {}", host, detail),
+        SwanlingError::InvalidWaitTime { detail, .. }
+        | SwanlingError::InvalidWeight { detail, .. }
+        | SwanlingError::FeatureNotEnabled { detail, .. } => detail.to_string(),
+        _ => problem.to_string(),
+    }
+}
+
 /// Auto-convert Reqwest errors.
 impl From<reqwest::Error> for SwanlingError {
     fn from(err: reqwest::Error) -> SwanlingError {
@@ -676,18 +793,178 @@ pub enum SwanlingScheduler {
     /// Allocate in a random order.
     Random,
 }
+/// Each user runs its tasks in the order they were allocated by the scheduler.
+impl SwanlingTaskScheduler for SwanlingScheduler {
+    fn next_task(&mut self, user: &SwanlingUser, _task_set: &SwanlingTaskSet) -> Option<usize> {
+        user.weighted_tasks
+            .get(user.position.load(Ordering::SeqCst))
+            .map(|(task_index, _task_name)| *task_index)
+    }
+}
+
+/// A spike of additional users, configured with `--spike`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SwanlingSpike {
+    /// How many times the configured number of users run during the spike.
+    multiplier: usize,
+    /// How many seconds each spike lasts.
+    duration: usize,
+    /// How many seconds from the start of one spike to the start of the next.
+    every: usize,
+}
+impl SwanlingSpike {
+    // Parse a spike in the format `MULTIPLIERx,DURATION,every=INTERVAL`, for example
+    // `10x,30s,every=300s`. Returns `None` if the spike is not valid.
+    fn parse(spike: &str) -> Option<Self> {
+        let mut parts = spike.split(',').map(|part| part.trim());
+        let multiplier: usize = parts.next()?.strip_suffix('x')?.parse().ok()?;
+        let duration = util::parse_timespan(parts.next()?);
+        let every = util::parse_timespan(parts.next()?.strip_prefix("every=")?);
+        // The spike must add users, and end before the next one starts.
+        if parts.next().is_some() || multiplier < 2 || duration == 0 || every <= duration {
+            return None;
+        }
+        Some(SwanlingSpike {
+            multiplier,
+            duration,
+            every,
+        })
+    }
+}
+
+/// A load profile configured with `--load-profile`, the number of users to run at points
+/// in time, interpolated between to trace a curve such as daily traffic.
+#[derive(Clone, Debug, PartialEq)]
+struct SwanlingLoadProfile {
+    /// Each point, as seconds after the load test started and the users to run then, in
+    /// order of increasing offset.
+    points: Vec<(usize, usize)>,
+}
+impl SwanlingLoadProfile {
+    // Parse a load profile with one `OFFSET,USERS` point per line, for example `60,100`
+    // to run 100 users one minute after the load test started. Blank lines and lines
+    // starting with `#` are ignored.
+    fn parse(contents: &str) -> Result<Self, String> {
+        let mut points: Vec<(usize, usize)> = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split(',').map(|part| part.trim());
+            let point = match (parts.next(), parts.next(), parts.next()) {
+                (Some(offset), Some(users), None) => {
+                    match (offset.parse::<usize>(), users.parse::<usize>()) {
+                        (Ok(offset), Ok(users)) => (offset, users),
+                        _ => {
+                            return Err(format!(
+                                "line {}: expected OFFSET,USERS in whole seconds and users, for example 60,100",
+                                index + 1
+                            ))
+                        }
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: expected OFFSET,USERS, for example 60,100",
+                        index + 1
+                    ))
+                }
+            };
+            if let Some((previous, _)) = points.last() {
+                if point.0 <= *previous {
+                    return Err(format!(
+                        "line {}: offset {} must be later than the previous offset {}",
+                        index + 1,
+                        point.0,
+                        previous
+                    ));
+                }
+            }
+            points.push(point);
+        }
+        if points.is_empty() {
+            return Err("contains no OFFSET,USERS points".to_string());
+        }
+        Ok(SwanlingLoadProfile { points })
+    }
+
+    // How many seconds after the load test started the last point is reached.
+    fn duration(&self) -> usize {
+        self.points.last().map_or(0, |(offset, _)| *offset)
+    }
+
+    // The users to run this many seconds after the load test started, interpolating
+    // between the points either side. Before the first point and after the last, the
+    // users of that point are run.
+    fn users_at(&self, elapsed: f32) -> usize {
+        let next = self
+            .points
+            .iter()
+            .position(|(offset, _)| *offset as f32 > elapsed);
+        match next {
+            Some(0) => self.points[0].1,
+            Some(index) => {
+                let (from_offset, from_users) = self.points[index - 1];
+                let (to_offset, to_users) = self.points[index];
+                let progress = (elapsed - from_offset as f32) / (to_offset - from_offset) as f32;
+                (from_users as f32 + (to_users as f32 - from_users as f32) * progress).round()
+                    as usize
+            }
+            None => self.points[self.points.len() - 1].1,
+        }
+    }
+}
+
+/// User sessions saved at the end of a load test, configured with `--session-file`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct SwanlingSessionFile {
+    /// Hash of the load test that saved the sessions, which are only reloaded by the same
+    /// load test.
+    hash: u64,
+    /// The session of each weighted user, in the order users are launched.
+    users: Vec<SwanlingSession>,
+}
 
 /// Optional default values for Swanling run-time options.
 #[derive(Clone, Debug, Default)]
 pub struct SwanlingDefaults {
     /// An optional default host to run this load test against.
     host: Option<String>,
+    /// An optional default path prefixed to all request paths.
+    base_path: Option<String>,
+    /// An optional default URL to check before starting the load test.
+    health_check_url: Option<String>,
+    /// An optional default status code the health check URL must return.
+    health_check_expect: Option<usize>,
     /// An optional default number of users to simulate.
     users: Option<usize>,
     /// An optional default number of clients to start per second.
     hatch_rate: Option<String>,
     /// An optional default number of seconds for the test to run.
     run_time: Option<usize>,
+    /// An optional default number of seconds over which to gradually stop users.
+    stop_time: Option<usize>,
+    /// An optional default number of seconds to keep collecting metrics after stopping users.
+    cooldown: Option<usize>,
+    /// An optional default number of requests after which the test stops.
+    max_requests: Option<usize>,
+    /// An optional default number of complete iterations each user runs before stopping.
+    iterations_per_user: Option<usize>,
+    /// An optional default number of consecutive failed requests after which a user retires.
+    user_max_consecutive_failures: Option<usize>,
+    /// An optional default HAR file to replay.
+    har: Option<String>,
+    /// An optional default for waiting between HAR requests as recorded.
+    har_timing: Option<bool>,
+    /// An optional default for periodically multiplying users.
+    spike: Option<String>,
+    /// An optional default for launching more users until requests fail.
+    ramp_to_failure: Option<bool>,
+    /// An optional default for the percent of failed requests that stops the ramp to failure.
+    error_threshold: Option<usize>,
+    /// An optional default load profile file, adjusting users to trace its curve.
+    load_profile: Option<String>,
     /// An optional default log level.
     log_level: Option<u8>,
     /// An optional default for the swanling log file name.
@@ -702,14 +979,53 @@ pub struct SwanlingDefaults {
     no_metrics: Option<bool>,
     /// An optional default for not tracking task metrics.
     no_task_metrics: Option<bool>,
+    /// An optional default for excluding think time from task metrics.
+    exclude_think_time: Option<bool>,
     /// An optional default for not displaying an error summary.
     no_error_summary: Option<bool>,
+    /// An optional default to bound metrics memory for long soak tests.
+    soak: Option<bool>,
+    /// An optional default maximum number of request names tracked by name.
+    max_request_names: Option<usize>,
+    /// An optional default for service level agreements.
+    sla: Option<String>,
+    /// An optional default slow request threshold in milliseconds.
+    slow_threshold: Option<usize>,
+    /// An optional default number of standard deviations above the mean that makes a
+    /// request an outlier.
+    outlier_factor: Option<String>,
+    /// An optional default minimum number of samples to report percentiles.
+    min_samples: Option<usize>,
+    /// An optional default number of recent distinct errors to display while running.
+    error_tail: Option<usize>,
+    /// An optional default label to group request metrics by.
+    group_by: Option<String>,
+    /// An optional default response header identifying the region that served each request.
+    region_header: Option<String>,
+    /// An optional default baseline JSON report to compare the final metrics against.
+    baseline: Option<String>,
+    /// An optional default percent change from the baseline that is a regression.
+    regression_threshold: Option<usize>,
     /// An optional default for the html-formatted report file name.
     report_file: Option<String>,
+    /// An optional default for the JUnit XML report file name.
+    junit_xml: Option<String>,
+    /// An optional default for the JSON report file name.
+    report_json: Option<String>,
+    /// An optional default for the folded stack task profile file name.
+    task_profile: Option<String>,
+    /// An optional default for the latency chart output directory.
+    chart_output: Option<String>,
     /// An optional default for the requests log file name.
     request_log: Option<String>,
     /// An optional default for the requests log file format.
     request_format: Option<SwanlingLogFormat>,
+    /// An optional default for the names of requests to log.
+    log_only: Option<String>,
+    /// An optional default for the fraction of requests to log.
+    request_log_sample: Option<String>,
+    /// An optional default seed making request log sampling reproducible.
+    request_log_seed: Option<usize>,
     /// An optional default for the tasks log file name.
     task_log: Option<String>,
     /// An optional default for the tasks log file format.
@@ -724,6 +1040,8 @@ pub struct SwanlingDefaults {
     debug_format: Option<SwanlingLogFormat>,
     /// An optional default for not logging response body in debug log.
     no_debug_body: Option<bool>,
+    /// An optional default for capturing the response body of failed requests in the debug log.
+    capture_failure_bodies: Option<bool>,
     /// An optional default for not enabling telnet Controller thread.
     no_telnet: Option<bool>,
     /// An optional default for not enabling WebSocket Controller thread.
@@ -736,6 +1054,50 @@ pub struct SwanlingDefaults {
     status_codes: Option<bool>,
     /// An optional default maximum requests per second.
     throttle_requests: Option<usize>,
+    /// An optional default 95th percentile response time in milliseconds the adaptive throttle keeps within.
+    adaptive_target_p95: Option<usize>,
+    /// An optional default maximum number of concurrent connections.
+    max_connections: Option<usize>,
+    /// An optional default number of idle connections each user keeps open.
+    pool_size: Option<usize>,
+    /// An optional default number of seconds idle connections are kept open.
+    pool_idle_timeout: Option<usize>,
+    /// An optional default number of seconds a connection is idle before TCP keep-alive probes.
+    keepalive_idle_timeout: Option<usize>,
+    /// An optional default for opening each user's connection before it runs any tasks.
+    prewarm_connections: Option<bool>,
+    /// An optional default for the client certificate file name.
+    client_cert: Option<String>,
+    /// An optional default for the client private key file name.
+    client_key: Option<String>,
+    /// An optional default for the custom CA certificate file name.
+    ca_cert: Option<String>,
+    /// An optional default for not verifying TLS certificates.
+    insecure: Option<bool>,
+    /// An optional default for host name to IP address mappings.
+    resolve: Option<String>,
+    /// An optional default for the OAuth2 token endpoint URL.
+    oauth_token_url: Option<String>,
+    /// An optional default for the OAuth2 client id.
+    oauth_client_id: Option<String>,
+    /// An optional default for the OAuth2 client secret.
+    oauth_client_secret: Option<String>,
+    /// An optional default for the AWS region requests are signed for.
+    aws_region: Option<String>,
+    /// An optional default for the AWS service requests are signed for.
+    aws_service: Option<String>,
+    /// An optional default for the AWS access key id requests are signed with.
+    aws_access_key_id: Option<String>,
+    /// An optional default for the AWS secret access key requests are signed with.
+    aws_secret_access_key: Option<String>,
+    /// An optional default for the AWS session token requests are signed with.
+    aws_session_token: Option<String>,
+    /// An optional default for the weighted list of User-Agents file name.
+    user_agents: Option<String>,
+    /// An optional default for the session state file name.
+    session_file: Option<String>,
+    /// An optional default maximum number of redirects to follow.
+    max_redirects: Option<usize>,
     /// An optional default to follows base_url redirect with subsequent request.
     sticky_follow: Option<bool>,
     /// An optional default to enable Manager mode.
@@ -744,6 +1106,17 @@ pub struct SwanlingDefaults {
     expect_workers: Option<u16>,
     /// An optional default for Manager to ignore load test checksum.
     no_hash_check: Option<bool>,
+    /// An optional default number of seconds the Manager aggregates metrics in each window.
+    aggregate_window: Option<usize>,
+    /// An optional default percentage by which a Worker's error rate can exceed its peers'
+    /// before the Manager quarantines it.
+    quarantine_error_threshold: Option<usize>,
+    /// An optional default comma separated list of task sets pinned to Workers.
+    pin_task_set: Option<String>,
+    /// An optional default for the file the Manager appends InfluxDB line protocol to.
+    influx_file: Option<String>,
+    /// An optional default for the host:port the Manager sends InfluxDB line protocol to.
+    influx_udp: Option<String>,
     /// An optional default for host telnet Controller listens on.
     telnet_host: Option<String>,
     /// An optional default for port telnet Controller listens on.
@@ -762,6 +1135,26 @@ pub struct SwanlingDefaults {
     manager_host: Option<String>,
     /// An optional default for port Worker connects to.
     manager_port: Option<u16>,
+    /// An optional default for how the Manager and Workers communicate.
+    gaggle_transport: Option<String>,
+    /// An optional default for the Unix domain socket the Manager and Workers communicate over.
+    gaggle_ipc: Option<String>,
+    /// An optional default number of milliseconds between Worker metrics pushes.
+    worker_metrics_interval: Option<usize>,
+    /// An optional default number of metric samples a Worker buffers before dropping them.
+    worker_metrics_buffer: Option<usize>,
+    /// An optional default number of messages buffered by the Manager and Worker sockets.
+    gaggle_send_buffer: Option<usize>,
+    /// An optional default number of milliseconds Manager and Worker sockets wait to receive a message.
+    gaggle_recv_timeout: Option<usize>,
+    /// An optional default largest message in bytes accepted by Manager and Worker sockets.
+    gaggle_max_msg_size: Option<usize>,
+    /// An optional default number of sockets the Manager shards Workers across.
+    manager_sockets: Option<usize>,
+    /// An optional default window in milliseconds each Worker randomly delays connecting within.
+    worker_startup_jitter: Option<usize>,
+    /// An optional default seed making the Worker startup delay reproducible.
+    worker_startup_seed: Option<usize>,
 }
 
 /// Allows the optional configuration of Swanling's defaults.
@@ -769,12 +1162,40 @@ pub struct SwanlingDefaults {
 pub enum SwanlingDefault {
     /// An optional default host to run this load test against.
     Host,
+    /// An optional default path prefixed to all request paths.
+    BasePath,
+    /// An optional default URL to check before starting the load test.
+    HealthCheckUrl,
+    /// An optional default status code the health check URL must return.
+    HealthCheckExpect,
     /// An optional default number of users to simulate.
     Users,
     /// An optional default number of clients to start per second.
     HatchRate,
     /// An optional default number of seconds for the test to run.
     RunTime,
+    /// An optional default number of seconds over which to gradually stop users.
+    StopTime,
+    /// An optional default number of seconds to keep collecting metrics after stopping users.
+    Cooldown,
+    /// An optional default number of requests after which the test stops.
+    MaxRequests,
+    /// An optional default number of complete iterations each user runs before stopping.
+    IterationsPerUser,
+    /// An optional default number of consecutive failed requests after which a user retires.
+    UserMaxConsecutiveFailures,
+    /// An optional default HAR file to replay.
+    Har,
+    /// An optional default for waiting between HAR requests as recorded.
+    HarTiming,
+    /// An optional default for periodically multiplying users.
+    Spike,
+    /// An optional default for launching more users until requests fail.
+    RampToFailure,
+    /// An optional default for the percent of failed requests that stops the ramp to failure.
+    ErrorThreshold,
+    /// An optional default load profile file, adjusting users to trace its curve.
+    LoadProfile,
     /// An optional default log level.
     LogLevel,
     /// An optional default for the log file name.
@@ -789,14 +1210,53 @@ pub enum SwanlingDefault {
     NoMetrics,
     /// An optional default for not tracking task metrics.
     NoTaskMetrics,
+    /// An optional default for excluding think time from task metrics.
+    ExcludeThinkTime,
     /// An optional default for not displaying an error summary.
     NoErrorSummary,
+    /// An optional default to bound metrics memory for long soak tests.
+    Soak,
+    /// An optional default maximum number of request names tracked by name.
+    MaxRequestNames,
+    /// An optional default for service level agreements.
+    Sla,
+    /// An optional default slow request threshold in milliseconds.
+    SlowThreshold,
+    /// An optional default number of standard deviations above the mean that makes a
+    /// request an outlier.
+    OutlierFactor,
+    /// An optional default minimum number of samples to report percentiles.
+    MinSamples,
+    /// An optional default number of recent distinct errors to display while running.
+    ErrorTail,
+    /// An optional default label to group request metrics by.
+    GroupBy,
+    /// An optional default response header identifying the region that served each request.
+    RegionHeader,
+    /// An optional default baseline JSON report to compare the final metrics against.
+    Baseline,
+    /// An optional default percent change from the baseline that is a regression.
+    RegressionThreshold,
     /// An optional default for the report file name.
     ReportFile,
+    /// An optional default for the JUnit XML report file name.
+    JunitXml,
+    /// An optional default for the JSON report file name.
+    ReportJson,
+    /// An optional default for the folded stack task profile file name.
+    TaskProfile,
+    /// An optional default for the latency chart output directory.
+    ChartOutput,
     /// An optional default for the request log file name.
     RequestLog,
     /// An optional default for the request log file format.
     RequestFormat,
+    /// An optional default for the names of requests to log.
+    LogOnly,
+    /// An optional default for the fraction of requests to log.
+    RequestLogSample,
+    /// An optional default seed making request log sampling reproducible.
+    RequestLogSeed,
     /// An optional default for the task log file name.
     TaskLog,
     /// An optional default for the task log file format.
@@ -811,6 +1271,8 @@ pub enum SwanlingDefault {
     DebugFormat,
     /// An optional default for not logging the response body in the debug log.
     NoDebugBody,
+    /// An optional default for capturing the response body of failed requests in the debug log.
+    CaptureFailureBodies,
     /// An optional default for not enabling telnet Controller thread.
     NoTelnet,
     /// An optional default for not enabling WebSocket Controller thread.
@@ -823,6 +1285,50 @@ pub enum SwanlingDefault {
     StatusCodes,
     /// An optional default maximum requests per second.
     ThrottleRequests,
+    /// An optional default 95th percentile response time in milliseconds the adaptive throttle keeps within.
+    AdaptiveTargetP95,
+    /// An optional default maximum number of concurrent connections.
+    MaxConnections,
+    /// An optional default number of idle connections each user keeps open.
+    PoolSize,
+    /// An optional default number of seconds idle connections are kept open.
+    PoolIdleTimeout,
+    /// An optional default number of seconds a connection is idle before TCP keep-alive probes.
+    KeepaliveIdleTimeout,
+    /// An optional default for opening each user's connection before it runs any tasks.
+    PrewarmConnections,
+    /// An optional default for the client certificate file name.
+    ClientCert,
+    /// An optional default for the client private key file name.
+    ClientKey,
+    /// An optional default for the custom CA certificate file name.
+    CaCert,
+    /// An optional default for not verifying TLS certificates.
+    Insecure,
+    /// An optional default for host name to IP address mappings.
+    Resolve,
+    /// An optional default for the OAuth2 token endpoint URL.
+    OauthTokenUrl,
+    /// An optional default for the OAuth2 client id.
+    OauthClientId,
+    /// An optional default for the OAuth2 client secret.
+    OauthClientSecret,
+    /// An optional default for the AWS region requests are signed for.
+    AwsRegion,
+    /// An optional default for the AWS service requests are signed for.
+    AwsService,
+    /// An optional default for the AWS access key id requests are signed with.
+    AwsAccessKeyId,
+    /// An optional default for the AWS secret access key requests are signed with.
+    AwsSecretAccessKey,
+    /// An optional default for the AWS session token requests are signed with.
+    AwsSessionToken,
+    /// An optional default for the weighted list of User-Agents file name.
+    UserAgents,
+    /// An optional default for the session state file name.
+    SessionFile,
+    /// An optional default maximum number of redirects to follow.
+    MaxRedirects,
     /// An optional default to follows base_url redirect with subsequent request.
     StickyFollow,
     /// An optional default to enable Manager mode.
@@ -831,6 +1337,17 @@ pub enum SwanlingDefault {
     ExpectWorkers,
     /// An optional default for Manager to ignore load test checksum.
     NoHashCheck,
+    /// An optional default number of seconds the Manager aggregates metrics in each window.
+    AggregateWindow,
+    /// An optional default percentage by which a Worker's error rate can exceed its peers'
+    /// before the Manager quarantines it.
+    QuarantineErrorThreshold,
+    /// An optional default comma separated list of task sets pinned to Workers.
+    PinTaskSet,
+    /// An optional default for the file the Manager appends InfluxDB line protocol to.
+    InfluxFile,
+    /// An optional default for the host:port the Manager sends InfluxDB line protocol to.
+    InfluxUdp,
     /// An optional default for host telnet Controller listens on.
     TelnetHost,
     /// An optional default for port telnet Controller listens on.
@@ -849,6 +1366,26 @@ pub enum SwanlingDefault {
     ManagerHost,
     /// An optional default for port Worker connects to.
     ManagerPort,
+    /// An optional default for how the Manager and Workers communicate.
+    GaggleTransport,
+    /// An optional default for the Unix domain socket the Manager and Workers communicate over.
+    GaggleIpc,
+    /// An optional default number of milliseconds between Worker metrics pushes.
+    WorkerMetricsInterval,
+    /// An optional default number of metric samples a Worker buffers before dropping them.
+    WorkerMetricsBuffer,
+    /// An optional default number of messages buffered by the Manager and Worker sockets.
+    GaggleSendBuffer,
+    /// An optional default number of milliseconds Manager and Worker sockets wait to receive a message.
+    GaggleRecvTimeout,
+    /// An optional default largest message in bytes accepted by Manager and Worker sockets.
+    GaggleMaxMsgSize,
+    /// An optional default number of sockets the Manager shards Workers across.
+    ManagerSockets,
+    /// An optional default window in milliseconds each Worker randomly delays connecting within.
+    WorkerStartupJitter,
+    /// An optional default seed making the Worker startup delay reproducible.
+    WorkerStartupSeed,
 }
 
 #[derive(Debug)]
@@ -867,12 +1404,17 @@ struct SwanlingAttackRunState {
     /// the time sleeping to avoid an unintentional drift in events that are supposed to
     /// happen regularly.
     drift_timer: tokio::time::Instant,
-    /// Unbounded sender used by all [`SwanlingUser`](./swanling/struct.SwanlingUser.html)
-    /// threads to send metrics to parent.
+    /// Sender used by all [`SwanlingUser`](./swanling/struct.SwanlingUser.html) threads to
+    /// send metrics to parent, bounded on a Worker if `--worker-metrics-buffer` is set.
     all_threads_metrics_tx: flume::Sender<SwanlingMetric>,
-    /// Unbounded receiver used by Swanling parent to receive metrics from
+    /// Receiver used by Swanling parent to receive metrics from
     /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
     metrics_rx: flume::Receiver<SwanlingMetric>,
+    /// How many metric samples [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
+    /// dropped because the metrics buffer was full, since last pushed to the Manager.
+    dropped_metrics: Arc<AtomicUsize>,
+    /// Counts the iterations run by all [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
+    iterations: Arc<SwanlingIterationCounters>,
     /// Optional unbounded receiver for logger thread, if enabled.
     logger_handle: SwanlingLoggerJoinHandle,
     /// Optional unbounded sender from all [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
@@ -883,6 +1425,12 @@ struct SwanlingAttackRunState {
     throttle_threads_tx: Option<flume::Sender<bool>>,
     /// Optional sender for throttle thread, if enabled.
     parent_to_throttle_tx: Option<flume::Sender<bool>>,
+    /// Optional requests per second allowed by the throttle thread, if enabled, which
+    /// can be changed while the load test is running.
+    throttle_rate: Option<Arc<AtomicUsize>>,
+    /// Optional semaphore shared by all [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
+    /// to cap concurrent connections, if enabled.
+    connections: Option<Arc<Semaphore>>,
     /// Optional channel allowing controller thread to make requests, if not disabled.
     controller_channel_rx: Option<flume::Receiver<SwanlingControllerRequest>>,
     /// Optional unbuffered writer for html-formatted report file, if enabled.
@@ -898,8 +1446,28 @@ struct SwanlingAttackRunState {
     /// All unbounded senders to allow communication with
     /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html) threads.
     user_channels: Vec<flume::Sender<SwanlingUserCommand>>,
+    /// All unbounded senders to allow communication with the additional
+    /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html) threads launched during a spike.
+    spike_channels: Vec<flume::Sender<SwanlingUserCommand>>,
+    /// Timer tracking when the last spike started, or when all users were launched if
+    /// there hasn't been a spike yet.
+    spike_timer: std::time::Instant,
+    /// Timer tracking when the adaptive throttle last adjusted the rate, if enabled.
+    adaptive_timer: std::time::Instant,
+    /// Timer tracking when the error rate was last measured while ramping to failure, if
+    /// enabled.
+    ramp_timer: std::time::Instant,
     /// Timer tracking when to display running metrics, if enabled.
     running_metrics_timer: std::time::Instant,
+    /// Timer tracking when to display the most recent distinct errors, if enabled.
+    error_tail_timer: std::time::Instant,
+    /// The thread running the metrics callback, if one is set.
+    metrics_callback: Option<SwanlingMetricsCallbackThread>,
+    /// Timer tracking when a Worker last pushed metrics to the Manager.
+    worker_metrics_timer: std::time::Instant,
+    /// Boolean flag indicating if a Worker has received metrics not yet pushed to the
+    /// Manager.
+    worker_metrics_pending: bool,
     /// Boolean flag indicating if running metrics should be displayed.
     display_running_metrics: bool,
     /// Boolean flag indicating if all [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
@@ -907,6 +1475,19 @@ struct SwanlingAttackRunState {
     all_users_spawned: bool,
     /// Boolean flag indicating of Swanling should shutdown after stopping a running load test.
     shutdown_after_stop: bool,
+    /// Boolean flag indicating if [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
+    /// are paused.
+    paused: bool,
+    /// How many [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s this process was
+    /// reconfigured to run, launching or stopping users the next time the load test is
+    /// monitored.
+    target_users: Option<usize>,
+    /// Configuration changes applied on a Worker, not yet acknowledged to the Manager.
+    #[cfg(feature = "gaggle")]
+    reconfigured: Vec<String>,
+    /// The end of the request log requested by the Manager, not yet pushed to it.
+    #[cfg(feature = "gaggle")]
+    requested_log: Option<GaggleMetrics>,
     /// Thread-safe boolean flag indicating if the [`SwanlingAttack`](./struct.SwanlingAttack.html)
     /// has been canceled.
     canceled: Arc<AtomicBool>,
@@ -927,12 +1508,40 @@ pub struct SwanlingAttack {
     weighted_users: Vec<SwanlingUser>,
     /// A weighted vector containing a lightweight GaggleUser object that is sent to all Workers if running in Regatta mode.
     weighted_gaggle_users: Vec<GaggleUser>,
+    /// A weighted vector of User-Agents allocated to users, if `--user-agents` is configured.
+    weighted_user_agents: Vec<String>,
+    /// An optional closure customizing the client of every user.
+    client_customizer: Option<SwanlingClientCustomizer>,
+    /// An optional callback invoked with a snapshot of the metrics each interval.
+    metrics_callback: Option<SwanlingMetricsCallback>,
+    /// Optionally sends a snapshot of the metrics to a stream each interval.
+    metrics_stream: Option<SwanlingMetricsStreamSender>,
+    /// Thread-safe boolean flag indicating if the load test has been canceled, shared with
+    /// every shutdown handle.
+    canceled: Arc<AtomicBool>,
+    /// An optional closure creating a custom task scheduler for each user.
+    task_scheduler: Option<SwanlingTaskSchedulerFactory>,
+    /// An optional closure naming requests from their path.
+    request_namer: Option<SwanlingRequestNamer>,
+    /// Values shared by all users, published with `SwanlingUser::set_shared_value`.
+    shared_values: Arc<SwanlingSharedValues>,
     /// Optional default values for Swanling run-time options.
     defaults: SwanlingDefaults,
     /// Configuration object holding options set when launching the load test.
     configuration: SwanlingConfiguration,
     /// How long (in seconds) the load test should run.
     run_time: usize,
+    /// How long (in seconds) to spend gradually stopping users once the load test ends.
+    stop_time: usize,
+    /// How long (in seconds) to keep collecting metrics after users are told to stop.
+    cooldown: usize,
+    /// An optional spike periodically multiplying the number of running users.
+    spike: Option<SwanlingSpike>,
+    /// An optional load profile adjusting the number of running users over time.
+    load_profile: Option<SwanlingLoadProfile>,
+    /// How many requests have been made, used to enforce `--max-requests`. Unlike the
+    /// request metrics, this counter is never reset.
+    request_count: usize,
     /// The load test operates in only one of the following modes: StandAlone, Manager, or Worker.
     attack_mode: AttackMode,
     /// Which phase the load test is currently operating in.
@@ -962,9 +1571,22 @@ impl SwanlingAttack {
             task_sets: Vec::new(),
             weighted_users: Vec::new(),
             weighted_gaggle_users: Vec::new(),
+            weighted_user_agents: Vec::new(),
+            client_customizer: None,
+            metrics_callback: None,
+            metrics_stream: None,
+            canceled: Arc::new(AtomicBool::new(false)),
+            task_scheduler: None,
+            request_namer: None,
+            shared_values: Arc::new(SwanlingSharedValues::default()),
             defaults: SwanlingDefaults::default(),
             configuration: SwanlingConfiguration::parse_args_default_or_exit(),
             run_time: 0,
+            stop_time: 0,
+            cooldown: 0,
+            spike: None,
+            load_profile: None,
+            request_count: 0,
             attack_mode: AttackMode::Undefined,
             attack_phase: AttackPhase::Idle,
             scheduler: SwanlingScheduler::RoundRobin,
@@ -995,9 +1617,22 @@ impl SwanlingAttack {
             task_sets: Vec::new(),
             weighted_users: Vec::new(),
             weighted_gaggle_users: Vec::new(),
+            weighted_user_agents: Vec::new(),
+            client_customizer: None,
+            metrics_callback: None,
+            metrics_stream: None,
+            canceled: Arc::new(AtomicBool::new(false)),
+            task_scheduler: None,
+            request_namer: None,
+            shared_values: Arc::new(SwanlingSharedValues::default()),
             defaults: SwanlingDefaults::default(),
             configuration,
             run_time: 0,
+            stop_time: 0,
+            cooldown: 0,
+            spike: None,
+            load_profile: None,
+            request_count: 0,
             attack_mode: AttackMode::Undefined,
             attack_phase: AttackPhase::Idle,
             scheduler: SwanlingScheduler::RoundRobin,
@@ -1243,6 +1878,360 @@ impl SwanlingAttack {
         self
     }
 
+    /// Optionally customize the
+    /// [`reqwest::ClientBuilder`](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html)
+    /// used to build the client of every [`SwanlingUser`](./swanling/struct.SwanlingUser.html),
+    /// including the users running [`test_start`](#method.test_start) and
+    /// [`test_stop`](#method.test_stop) tasks. This makes it possible to add client-side
+    /// behavior Swanling doesn't otherwise support, such as default headers, proxies, timeouts
+    /// or a custom redirect policy.
+    ///
+    /// The closure is passed the builder after Swanling has configured it, including the
+    /// User-Agent and any `--client-cert`, `--ca-cert`, `--pool-size` and `--resolve` options,
+    /// so anything it sets takes precedence. When running in a distributed Regatta, the closure
+    /// is applied by each Worker, so it must be set in the Worker's load test as well.
+    ///
+    /// Metrics are still recorded around the outermost call: the response time of a request
+    /// includes everything the customized client does, for example following redirects or
+    /// connecting through a proxy.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .set_client_customizer(|builder| {
+    ///             use reqwest::header;
+    ///
+    ///             // Include a custom header with all requests made by all users.
+    ///             let mut headers = header::HeaderMap::new();
+    ///             headers.insert("X-Custom-Header", header::HeaderValue::from_static("custom value"));
+    ///             builder.default_headers(headers)
+    ///         })
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task))
+    ///         );
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_client_customizer<F>(mut self, customizer: F) -> Self
+    where
+        F: Fn(reqwest::ClientBuilder) -> reqwest::ClientBuilder + Send + Sync + 'static,
+    {
+        self.client_customizer = Some(SwanlingClientCustomizer(Arc::new(customizer)));
+        self
+    }
+
+    /// Set a callback invoked with a snapshot of the metrics every `interval` seconds while
+    /// the load test runs, returning a
+    /// [`SwanlingControlAction`](./metrics/enum.SwanlingControlAction.html) to continue,
+    /// abort the load test, or adjust it the same as a controller can. This allows
+    /// implementing custom control logic, for example stopping early once enough errors
+    /// occurred. In a Regatta, the callback is invoked on the Manager with the metrics
+    /// received from all Workers.
+    ///
+    /// The callback runs on its own thread so it can't delay receiving metrics, and an
+    /// interval is skipped if the callback is still running when it ends.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::metrics::SwanlingControlAction;
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .set_metrics_callback(5, |metrics| {
+    ///             // Stop the load test once 100 requests have failed.
+    ///             let failed: usize = metrics.requests.values().map(|r| r.fail_count).sum();
+    ///             if failed >= 100 {
+    ///                 SwanlingControlAction::Abort
+    ///             } else {
+    ///                 SwanlingControlAction::Continue
+    ///             }
+    ///         })?
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task))
+    ///         );
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_metrics_callback<F>(
+        mut self,
+        interval: usize,
+        callback: F,
+    ) -> Result<Self, SwanlingError>
+    where
+        F: Fn(&SwanlingMetrics) -> SwanlingControlAction + Send + Sync + 'static,
+    {
+        if interval == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingAttack.set_metrics_callback".to_string(),
+                value: interval.to_string(),
+                detail: "The metrics callback interval must be at least 1 second.".to_string(),
+            });
+        }
+        self.metrics_callback = Some(SwanlingMetricsCallback {
+            interval,
+            callback: Arc::new(callback),
+        });
+        Ok(self)
+    }
+
+    /// Returns a [`SwanlingMetricsStream`](./metrics/struct.SwanlingMetricsStream.html) of
+    /// snapshots of the metrics every `interval` seconds while the load test runs, the
+    /// async counterpart to
+    /// [`set_metrics_callback`](./struct.SwanlingAttack.html#method.set_metrics_callback)
+    /// for building live dashboards or control loops. The stream also provides a handle
+    /// to gracefully stop the load test. In a Regatta, snapshots are streamed from the
+    /// Manager with the metrics received from all Workers.
+    ///
+    /// Run the load test on its own thread with
+    /// [`execute`](./struct.SwanlingAttack.html#method.execute), which still returns the
+    /// final metrics, while consuming the stream. The stream ends once the load test
+    /// finishes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use swanling::metrics::SwanlingMetricsStream;
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut swanling_attack = SwanlingAttack::initialize()?
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task))
+    ///         );
+    ///     let snapshots = swanling_attack.metrics_stream(1)?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// // Display how many requests were made each second, and stop the load test once 100
+    /// // requests have failed.
+    /// async fn monitor(mut snapshots: SwanlingMetricsStream) {
+    ///     while let Some(metrics) = snapshots.next().await {
+    ///         let made: usize = metrics.requests.values().map(|r| r.success_count).sum();
+    ///         let failed: usize = metrics.requests.values().map(|r| r.fail_count).sum();
+    ///         println!("{} requests, {} failed", made + failed, failed);
+    ///         if failed >= 100 {
+    ///             snapshots.shutdown();
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn metrics_stream(
+        &mut self,
+        interval: usize,
+    ) -> Result<SwanlingMetricsStream, SwanlingError> {
+        if interval == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingAttack.metrics_stream".to_string(),
+                value: interval.to_string(),
+                detail: "The metrics stream interval must be at least 1 second.".to_string(),
+            });
+        }
+        let (snapshot_tx, snapshot_rx) = flume::bounded(1);
+        self.metrics_stream = Some(SwanlingMetricsStreamSender {
+            interval,
+            timer: std::time::Instant::now(),
+            snapshot_tx,
+        });
+        Ok(SwanlingMetricsStream {
+            snapshots: snapshot_rx.into_stream(),
+            shutdown: SwanlingShutdownHandle(self.canceled.clone()),
+        })
+    }
+
+    /// Returns a [`SwanlingShutdownHandle`](./metrics/struct.SwanlingShutdownHandle.html)
+    /// that gracefully stops the load test from another thread or task, for example when
+    /// embedding Swanling in a test harness that bounds the load test by an external
+    /// condition. Stopping the load test with the handle is the same as canceling it with
+    /// ctrl-c: users are stopped, and the metrics collected so far are reported and
+    /// returned by [`execute`](./struct.SwanlingAttack.html#method.execute). In a Regatta,
+    /// the Manager tells all Workers to exit.
+    ///
+    /// Get the handle before calling `execute`, then run the load test on its own thread,
+    /// or with [`tokio::task::spawn_blocking`](https://docs.rs/tokio/*/tokio/task/fn.spawn_blocking.html)
+    /// in an async program.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::metrics::SwanlingShutdownHandle;
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let swanling_attack = SwanlingAttack::initialize()?
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task))
+    ///         );
+    ///     let shutdown = swanling_attack.shutdown_handle();
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// // Stop the load test once the deployment being tested is rolled back.
+    /// async fn watch_deployment(shutdown: SwanlingShutdownHandle) {
+    ///     while !rolled_back().await {
+    ///         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    ///     }
+    ///     shutdown.shutdown();
+    /// }
+    ///
+    /// async fn rolled_back() -> bool {
+    ///     true
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn shutdown_handle(&self) -> SwanlingShutdownHandle {
+        SwanlingShutdownHandle(self.canceled.clone())
+    }
+
+    /// Set a closure creating the
+    /// [`SwanlingTaskScheduler`](./swanling/trait.SwanlingTaskScheduler.html) that decides
+    /// which task each [`SwanlingUser`](./swanling/struct.SwanlingUser.html) runs next,
+    /// instead of running the tasks in the order allocated by the
+    /// [`SwanlingScheduler`](./enum.SwanlingScheduler.html). The closure is invoked once for
+    /// each user with the [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html) it
+    /// runs, so every user gets its own scheduler. The `on_start` and `on_stop` tasks still
+    /// run once each, in the order allocated.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// /// Visitors start on the front page, then read articles until they leave.
+    /// struct Browse {
+    ///     reading: bool,
+    /// }
+    /// impl SwanlingTaskScheduler for Browse {
+    ///     fn next_task(&mut self, _user: &SwanlingUser, _task_set: &SwanlingTaskSet) -> Option<usize> {
+    ///         if !self.reading {
+    ///             self.reading = true;
+    ///             // Task 0 loads the front page.
+    ///             Some(0)
+    ///         } else if rand::random::<f32>() < 0.8 {
+    ///             // Task 1 loads an article.
+    ///             Some(1)
+    ///         } else {
+    ///             // The visitor leaves, and the next visit starts on the front page.
+    ///             self.reading = false;
+    ///             None
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .set_task_scheduler(|_task_set| Box::new(Browse { reading: false }))
+    ///         .register_taskset(taskset!("Visitors")
+    ///             .register_task(task!(front_page))
+    ///             .register_task(task!(article))
+    ///         );
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn front_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/").await?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn article(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/article").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_task_scheduler<F>(mut self, task_scheduler: F) -> Self
+    where
+        F: Fn(&SwanlingTaskSet) -> Box<dyn SwanlingTaskScheduler> + Send + Sync + 'static,
+    {
+        self.task_scheduler = Some(SwanlingTaskSchedulerFactory(Arc::new(task_scheduler)));
+        self
+    }
+
+    /// Set a closure naming every request from its path, replacing the path as the name
+    /// requests are grouped by in the metrics.
+    ///
+    /// APIs with identifiers in their paths, such as `/users/42/orders/1337`, otherwise
+    /// record a separate metric for every identifier. Instead of naming each request with
+    /// [`swanling_send`](./swanling/struct.SwanlingUser.html#method.swanling_send), the closure
+    /// can collapse them all into one name, for example `/users/:id/orders/:id`.
+    ///
+    /// The closure is passed the path of each request, without the `--base-path` or any
+    /// query string. It isn't applied to requests given a name, or made by a named
+    /// [`SwanlingTask`](./swanling/struct.SwanlingTask.html). Requests are named by each
+    /// user before their metrics are aggregated, so in a Regatta the closure is applied by
+    /// each Worker.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .set_request_namer(|path| {
+    ///             // Replace numeric segments with :id.
+    ///             path.split('/')
+    ///                 .map(|segment| {
+    ///                     if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+    ///                         ":id"
+    ///                     } else {
+    ///                         segment
+    ///                     }
+    ///                 })
+    ///                 .collect::<Vec<&str>>()
+    ///                 .join("/")
+    ///         })
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task))
+    ///         );
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     // Recorded as GET /users/:id.
+    ///     let _swanling = user.get("/users/42").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_request_namer<F>(mut self, request_namer: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.request_namer = Some(SwanlingRequestNamer(Arc::new(request_namer)));
+        self
+    }
+
     /// Use configured SwanlingScheduler to build out a properly weighted list of
     /// [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html)s to be assigned to
     /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
@@ -1341,6 +2330,16 @@ impl SwanlingAttack {
         weighted_task_sets
     }
 
+    // Returns the User-Agent allocated to the user at this index, if `--user-agents` is
+    // configured.
+    fn get_user_agent(&self, user_index: usize) -> Option<String> {
+        if self.weighted_user_agents.is_empty() {
+            None
+        } else {
+            Some(self.weighted_user_agents[user_index % self.weighted_user_agents.len()].clone())
+        }
+    }
+
     /// Allocate a vector of weighted [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
     fn weight_task_set_users(&mut self) -> Result<Vec<SwanlingUser>, SwanlingError> {
         trace!("weight_task_set_users");
@@ -1363,14 +2362,23 @@ impl SwanlingAttack {
                     self.task_sets[*task_sets_index].host.clone(),
                     self.defaults.host.clone(),
                 )?;
-                weighted_users.push(SwanlingUser::new(
+                let mut user = SwanlingUser::new(
                     self.task_sets[*task_sets_index].task_sets_index,
                     base_url,
                     self.task_sets[*task_sets_index].min_wait,
                     self.task_sets[*task_sets_index].max_wait,
                     &self.configuration,
                     self.metrics.hash,
-                )?);
+                )?;
+                // Optionally allocate the next User-Agent from the weighted list, and
+                // configure the client for the user's class.
+                let user_agent = self.get_user_agent(user_count);
+                user.configure_client(
+                    user_agent.as_deref(),
+                    Some(&self.task_sets[*task_sets_index]),
+                    self.client_customizer.as_ref(),
+                )?;
+                weighted_users.push(user);
                 user_count += 1;
                 // Users are required here so unwrap() is safe.
                 if user_count >= self.configuration.users.unwrap() {
@@ -1398,14 +2406,19 @@ impl SwanlingAttack {
                     self.task_sets[*task_sets_index].host.clone(),
                     self.defaults.host.clone(),
                 )?;
-                weighted_users.push(GaggleUser::new(
+                let mut user = GaggleUser::new(
                     self.task_sets[*task_sets_index].task_sets_index,
                     base_url,
                     self.task_sets[*task_sets_index].min_wait,
                     self.task_sets[*task_sets_index].max_wait,
                     &self.configuration,
                     self.metrics.hash,
-                ));
+                );
+                // Optionally allocate the next User-Agent from the weighted list, the
+                // Worker configures the user's client with it.
+                user.user_agent = self.get_user_agent(user_count);
+                user.user_class = self.task_sets[*task_sets_index].name.to_string();
+                weighted_users.push(user);
                 user_count += 1;
                 // Users are required here so unwrap() is safe.
                 if user_count >= self.configuration.users.unwrap() {
@@ -1667,621 +2680,3693 @@ impl SwanlingAttack {
         Ok(())
     }
 
-    // Configure how many [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s to hatch.
-    fn set_users(&mut self) -> Result<(), SwanlingError> {
+    // Configure the baseline JSON report to compare the final metrics with.
+    fn set_baseline(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.users";
-        let mut value = 0;
+        let mut key = "--baseline";
 
-        // Check if --users is set.
-        if let Some(users) = self.configuration.users {
-            key = "--users";
-            value = users;
-        // If not, check if a default for users is set.
-        } else if let Some(default_users) = self.defaults.users {
-            // On Worker users comes from the Manager.
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.baseline.is_empty() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_baseline) = self.defaults.baseline.clone() {
+                key = "set_default(SwanlingDefault::Baseline)";
+                self.configuration.baseline = default_baseline;
+            }
+        }
+
+        if !self.configuration.baseline.is_empty() {
+            // The final metrics are compared on the Manager.
             if self.attack_mode == AttackMode::Worker {
-                self.configuration.users = None;
-            // Otherwise use default.
-            } else {
-                key = "set_default(SwanlingDefault::Users)";
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.baseline.clone(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // The baseline is compared with the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.baseline.clone(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Confirm the baseline can be compared before running the load test.
+            let valid = match std::fs::read_to_string(&self.configuration.baseline) {
+                Ok(baseline) => metrics::is_valid_baseline(&baseline),
+                Err(_) => false,
+            };
+            if !valid {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.baseline.clone(),
+                    detail: format!("{} must be a readable JSON report.", key),
+                });
+            }
+
+            info!("baseline = {}", self.configuration.baseline);
+        }
+
+        Ok(())
+    }
+
+    // Configure the percent change from the baseline that is a regression.
+    fn set_regression_threshold(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--regression-threshold";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.regression_threshold.is_none()
+            && self.attack_mode != AttackMode::Worker
+        {
+            if let Some(default_regression_threshold) = self.defaults.regression_threshold {
+                key = "set_default(SwanlingDefault::RegressionThreshold)";
+                self.configuration.regression_threshold = Some(default_regression_threshold);
+            }
+        }
+
+        if let Some(regression_threshold) = self.configuration.regression_threshold {
+            // The threshold is only used to compare with a baseline.
+            if self.configuration.baseline.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: regression_threshold.to_string(),
+                    detail: format!(
+                        "{} can not be set without also setting the --baseline option.",
+                        key
+                    ),
+                });
+            }
+
+            info!("regression_threshold = {}", regression_threshold);
+        }
+
+        Ok(())
+    }
+
+    // Configure how often a Worker pushes metrics to the Manager.
+    fn set_worker_metrics_interval(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--worker-metrics-interval";
+
+        if self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set, otherwise push once a second.
+            if self.configuration.worker_metrics_interval.is_none() {
+                self.configuration.worker_metrics_interval =
+                    if let Some(interval) = self.defaults.worker_metrics_interval {
+                        key = "set_default(SwanlingDefault::WorkerMetricsInterval)";
+                        Some(interval)
+                    } else {
+                        Some(DEFAULT_WORKER_METRICS_INTERVAL)
+                    };
+            }
+
+            if let Some(interval) = self.configuration.worker_metrics_interval {
+                if interval == 0 {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: interval.to_string(),
+                        detail: format!("{} must be set to at least 1 millisecond.", key),
+                    });
+                }
+
+                info!("worker_metrics_interval = {}", interval);
+            }
+        } else if let Some(interval) = self.configuration.worker_metrics_interval {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: interval.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting the --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how many metric samples a Worker buffers before dropping them.
+    fn set_worker_metrics_buffer(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--worker-metrics-buffer";
+
+        if self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set, otherwise the buffer is unbounded.
+            if self.configuration.worker_metrics_buffer.is_none() {
+                if let Some(buffer) = self.defaults.worker_metrics_buffer {
+                    key = "set_default(SwanlingDefault::WorkerMetricsBuffer)";
+                    self.configuration.worker_metrics_buffer = Some(buffer);
+                }
+            }
+
+            if let Some(buffer) = self.configuration.worker_metrics_buffer {
+                if buffer == 0 {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: buffer.to_string(),
+                        detail: format!("{} must be set to at least 1 sample.", key),
+                    });
+                }
+
+                info!("worker_metrics_buffer = {}", buffer);
+            }
+        } else if let Some(buffer) = self.configuration.worker_metrics_buffer {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: buffer.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting the --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure the Unix domain socket the Manager and Workers communicate over.
+    fn set_gaggle_ipc(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--gaggle-ipc";
+
+        if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set.
+            if self.configuration.gaggle_ipc.is_empty() {
+                if let Some(path) = self.defaults.gaggle_ipc.clone() {
+                    key = "set_default(SwanlingDefault::GaggleIpc)";
+                    self.configuration.gaggle_ipc = path;
+                }
+            }
+
+            if !self.configuration.gaggle_ipc.is_empty() {
+                // The socket is only used by the ipc transport, which it selects.
+                if self.configuration.gaggle_transport.is_empty() {
+                    self.configuration.gaggle_transport = "ipc".to_string();
+                } else if self.configuration.gaggle_transport.to_lowercase() != "ipc" {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: self.configuration.gaggle_ipc.clone(),
+                        detail: format!(
+                            "{} can not be set together with --gaggle-transport {}.",
+                            key, self.configuration.gaggle_transport
+                        ),
+                    });
+                }
+
+                info!("gaggle_ipc = {}", self.configuration.gaggle_ipc);
+            }
+        } else if !self.configuration.gaggle_ipc.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.gaggle_ipc.clone(),
+                detail: format!(
+                    "{} can not be set without also setting the --manager or --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how the Manager and Workers communicate.
+    fn set_gaggle_transport(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--gaggle-transport";
+
+        if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set, otherwise communicate over tcp.
+            if self.configuration.gaggle_transport.is_empty() {
+                self.configuration.gaggle_transport =
+                    if let Some(transport) = self.defaults.gaggle_transport.clone() {
+                        key = "set_default(SwanlingDefault::GaggleTransport)";
+                        transport
+                    } else {
+                        GAGGLE_TRANSPORTS[0].to_string()
+                    };
+            }
+
+            let transport = self.configuration.gaggle_transport.to_lowercase();
+            if !GAGGLE_TRANSPORTS.contains(&transport.as_str()) {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.gaggle_transport.clone(),
+                    detail: format!("{} must be set to tcp, inproc or ipc.", key),
+                });
+            }
+            self.configuration.gaggle_transport = transport;
+
+            // The ipc transport communicates over the configured Unix domain socket.
+            if self.configuration.gaggle_transport == "ipc"
+                && self.configuration.gaggle_ipc.is_empty()
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.gaggle_transport.clone(),
+                    detail: format!(
+                        "{} can not be set to ipc without also setting the --gaggle-ipc option.",
+                        key
+                    ),
+                });
+            }
+
+            info!("gaggle_transport = {}", self.configuration.gaggle_transport);
+        } else if !self.configuration.gaggle_transport.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.gaggle_transport.clone(),
+                detail: format!(
+                    "{} can not be set without also setting the --manager or --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how many messages the Manager and Worker sockets buffer.
+    fn set_gaggle_send_buffer(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--gaggle-send-buffer";
+
+        if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set, otherwise keep the nng default.
+            if self.configuration.gaggle_send_buffer.is_none() {
+                if let Some(send_buffer) = self.defaults.gaggle_send_buffer {
+                    key = "set_default(SwanlingDefault::GaggleSendBuffer)";
+                    self.configuration.gaggle_send_buffer = Some(send_buffer);
+                }
+            }
+
+            if let Some(send_buffer) = self.configuration.gaggle_send_buffer {
+                if send_buffer > GAGGLE_MAX_SEND_BUFFER {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: send_buffer.to_string(),
+                        detail: format!(
+                            "{} can not be set to more than {} messages.",
+                            key, GAGGLE_MAX_SEND_BUFFER
+                        ),
+                    });
+                }
+
+                info!("gaggle_send_buffer = {}", send_buffer);
+            }
+        } else if let Some(send_buffer) = self.configuration.gaggle_send_buffer {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: send_buffer.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting the --manager or --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how long the Manager and Worker sockets wait to receive a message.
+    fn set_gaggle_recv_timeout(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--gaggle-recv-timeout";
+
+        if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set, otherwise wait indefinitely.
+            if self.configuration.gaggle_recv_timeout.is_none() {
+                if let Some(recv_timeout) = self.defaults.gaggle_recv_timeout {
+                    key = "set_default(SwanlingDefault::GaggleRecvTimeout)";
+                    self.configuration.gaggle_recv_timeout = Some(recv_timeout);
+                }
+            }
+
+            if let Some(recv_timeout) = self.configuration.gaggle_recv_timeout {
+                if recv_timeout == 0 {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: recv_timeout.to_string(),
+                        detail: format!("{} must be set to at least 1 millisecond.", key),
+                    });
+                }
+
+                info!("gaggle_recv_timeout = {}", recv_timeout);
+            }
+        } else if let Some(recv_timeout) = self.configuration.gaggle_recv_timeout {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: recv_timeout.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting the --manager or --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure the largest message the Manager and Worker sockets accept.
+    fn set_gaggle_max_msg_size(&mut self) -> Result<(), SwanlingError> {
+        let key = "--gaggle-max-msg-size";
+
+        if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set, otherwise keep the nng default. Any
+            // size is valid, 0 removes the limit.
+            if self.configuration.gaggle_max_msg_size.is_none() {
+                self.configuration.gaggle_max_msg_size = self.defaults.gaggle_max_msg_size;
+            }
+
+            if let Some(max_msg_size) = self.configuration.gaggle_max_msg_size {
+                info!("gaggle_max_msg_size = {}", max_msg_size);
+            }
+        } else if let Some(max_msg_size) = self.configuration.gaggle_max_msg_size {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: max_msg_size.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting the --manager or --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how many sockets the Manager shards Workers across, each listening on
+    // the next port after the last.
+    fn set_manager_sockets(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--manager-sockets";
+
+        if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set, otherwise use a single socket.
+            if self.configuration.manager_sockets.is_none() {
+                if let Some(sockets) = self.defaults.manager_sockets {
+                    key = "set_default(SwanlingDefault::ManagerSockets)";
+                    self.configuration.manager_sockets = Some(sockets);
+                }
+            }
+
+            if let Some(sockets) = self.configuration.manager_sockets {
+                if sockets == 0 || sockets > MAX_MANAGER_SOCKETS {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: sockets.to_string(),
+                        detail: format!(
+                            "{} must be set to between 1 and {} sockets.",
+                            key, MAX_MANAGER_SOCKETS
+                        ),
+                    });
+                }
+
+                // The sockets listen on consecutive ports, the last must be valid.
+                let port = if self.attack_mode == AttackMode::Manager {
+                    self.configuration.manager_bind_port
+                } else {
+                    self.configuration.manager_port
+                };
+                if port as usize + sockets - 1 > u16::MAX as usize {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: sockets.to_string(),
+                        detail: format!(
+                            "{} sockets starting at port {} exceed the highest port.",
+                            key, port
+                        ),
+                    });
+                }
+
+                info!("manager_sockets = {}", sockets);
+            }
+        } else if let Some(sockets) = self.configuration.manager_sockets {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: sockets.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting the --manager or --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure the random delay before a Worker connects to the Manager, staggering
+    // Workers that start at the same time.
+    fn set_worker_startup_jitter(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--worker-startup-jitter";
+
+        if self.attack_mode == AttackMode::Worker {
+            // Use defaults if run-time options not set, otherwise connect without delay.
+            if self.configuration.worker_startup_jitter.is_none() {
+                if let Some(jitter) = self.defaults.worker_startup_jitter {
+                    key = "set_default(SwanlingDefault::WorkerStartupJitter)";
+                    self.configuration.worker_startup_jitter = Some(jitter);
+                }
+            }
+            if self.configuration.worker_startup_seed.is_none() {
+                self.configuration.worker_startup_seed = self.defaults.worker_startup_seed;
+            }
+
+            if let Some(jitter) = self.configuration.worker_startup_jitter {
+                info!("worker_startup_jitter = {}", jitter);
+            }
+        } else if let Some(jitter) = self.configuration.worker_startup_jitter {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: jitter.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting the --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        // The seed only affects the delay.
+        if let Some(seed) = self.configuration.worker_startup_seed {
+            if self.configuration.worker_startup_jitter.is_none() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--worker-startup-seed".to_string(),
+                    value: seed.to_string(),
+                    detail: format!(
+                        "--worker-startup-seed can not be set without also setting {}.",
+                        key
+                    ),
+                });
+            }
+
+            info!("worker_startup_seed = {}", seed);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s to hatch.
+    fn set_users(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.users";
+        let mut value = 0;
+
+        // Check if --users is set.
+        if let Some(users) = self.configuration.users {
+            key = "--users";
+            value = users;
+        // If not, check if a default for users is set.
+        } else if let Some(default_users) = self.defaults.users {
+            // On Worker users comes from the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                self.configuration.users = None;
+            // Otherwise use default.
+            } else {
+                key = "set_default(SwanlingDefault::Users)";
                 value = default_users;
 
-                self.configuration.users = Some(default_users);
+                self.configuration.users = Some(default_users);
+            }
+        // If not and if not running on Worker, default to 1.
+        } else if self.attack_mode != AttackMode::Worker {
+            // This should not be able to fail, but setting up debug in case the number
+            // of cpus library returns an invalid number.
+            key = "num_cpus::get()";
+            value = num_cpus::get();
+
+            info!("concurrent users defaulted to {} (number of CPUs)", value);
+
+            self.configuration.users = Some(value);
+        }
+
+        // Perform bounds checking.
+        if let Some(users) = self.configuration.users {
+            // Setting --users with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Setting users to 0 is not allowed.
+            if users == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: "0".to_string(),
+                    detail: "The --users option must be set to at least 1.".to_string(),
+                });
+            }
+
+            // Debug output.
+            info!("users = {}", users);
+        }
+
+        Ok(())
+    }
+
+    // Configure maximum run time if specified, otherwise run until canceled.
+    fn set_run_time(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.run_time";
+        let mut value = 0;
+
+        // Use --run-time if set, don't allow on Worker.
+        self.run_time = if !self.configuration.run_time.is_empty() {
+            key = "--run-time";
+            value = util::parse_timespan(&self.configuration.run_time);
+            value
+        // Otherwise, use default if set, but not on Worker.
+        } else if let Some(default_run_time) = self.defaults.run_time {
+            if self.attack_mode == AttackMode::Worker {
+                0
+            } else {
+                key = "set_default(SwanlingDefault::RunTime)";
+                value = default_run_time;
+                default_run_time
+            }
+        }
+        // Otherwise the test runs until canceled.
+        else {
+            0
+        };
+
+        if self.run_time > 0 {
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Debug output.
+            info!("run_time = {}", self.run_time);
+        }
+
+        Ok(())
+    }
+
+    // Configure how long to spend gradually stopping users, if specified.
+    fn set_stop_time(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.stop_time";
+        let mut value = 0;
+
+        // Use --stop-time if set, don't allow on Worker.
+        self.stop_time = if !self.configuration.stop_time.is_empty() {
+            key = "--stop-time";
+            value = util::parse_timespan(&self.configuration.stop_time);
+            value
+        // Otherwise, use default if set, but not on Worker.
+        } else if let Some(default_stop_time) = self.defaults.stop_time {
+            if self.attack_mode == AttackMode::Worker {
+                0
+            } else {
+                key = "set_default(SwanlingDefault::StopTime)";
+                value = default_stop_time;
+                default_stop_time
+            }
+        }
+        // Otherwise all users are stopped at once.
+        else {
+            0
+        };
+
+        if self.stop_time > 0 {
+            // In Gaggles, the Manager tells the Workers how long to spend stopping users.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Debug output.
+            info!("stop_time = {}", self.stop_time);
+        }
+
+        Ok(())
+    }
+
+    // Configure how long to keep collecting metrics after stopping users, if specified.
+    fn set_cooldown(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.cooldown";
+        let mut value = 0;
+
+        // Use --cooldown if set, don't allow on Worker.
+        self.cooldown = if !self.configuration.cooldown.is_empty() {
+            key = "--cooldown";
+            value = util::parse_timespan(&self.configuration.cooldown);
+            value
+        // Otherwise, use default if set, but not on Worker.
+        } else if let Some(default_cooldown) = self.defaults.cooldown {
+            if self.attack_mode == AttackMode::Worker {
+                0
+            } else {
+                key = "set_default(SwanlingDefault::Cooldown)";
+                value = default_cooldown;
+                default_cooldown
+            }
+        }
+        // Otherwise the load test ends as soon as all users stop.
+        else {
+            0
+        };
+
+        if self.cooldown > 0 {
+            // In Gaggles, the Manager tells the Workers how long to cool down.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Debug output.
+            info!("cooldown = {}", self.cooldown);
+        }
+
+        Ok(())
+    }
+
+    // Configure the maximum number of requests to make, if specified.
+    fn set_max_requests(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.max_requests";
+        let mut value = 0;
+
+        if self.configuration.max_requests > 0 {
+            key = "--max-requests";
+            value = self.configuration.max_requests;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_max_requests) = self.defaults.max_requests {
+                key = "set_default(SwanlingDefault::MaxRequests)";
+                value = default_max_requests;
+
+                self.configuration.max_requests = default_max_requests;
+            }
+        }
+
+        if self.configuration.max_requests > 0 {
+            // In Gaggles, the Manager counts requests and tells the Workers when to stop.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Requests are counted from the metrics sent by each SwanlingUser.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Debug output.
+            info!("max_requests = {}", self.configuration.max_requests);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many complete iterations each user runs before stopping.
+    fn set_iterations_per_user(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.iterations_per_user";
+        let mut value = 0;
+
+        if let Some(iterations_per_user) = self.configuration.iterations_per_user {
+            key = "--iterations-per-user";
+            value = iterations_per_user;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_iterations_per_user) = self.defaults.iterations_per_user {
+                key = "set_default(SwanlingDefault::IterationsPerUser)";
+                value = default_iterations_per_user;
+
+                self.configuration.iterations_per_user = Some(default_iterations_per_user);
+            }
+        }
+
+        if let Some(iterations_per_user) = self.configuration.iterations_per_user {
+            // In Gaggles, the Manager sends the limit to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            if iterations_per_user == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} must be set to at least 1.", key),
+                });
+            }
+
+            info!("iterations_per_user = {}", iterations_per_user);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many consecutive failed requests retire a user.
+    fn set_user_max_consecutive_failures(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.user_max_consecutive_failures";
+        let mut value = 0;
+
+        if let Some(max_failures) = self.configuration.user_max_consecutive_failures {
+            key = "--user-max-consecutive-failures";
+            value = max_failures;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_max_failures) = self.defaults.user_max_consecutive_failures {
+                key = "set_default(SwanlingDefault::UserMaxConsecutiveFailures)";
+                value = default_max_failures;
+
+                self.configuration.user_max_consecutive_failures = Some(default_max_failures);
+            }
+        }
+
+        if let Some(max_failures) = self.configuration.user_max_consecutive_failures {
+            // In Gaggles, the Manager sends the limit to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Failures are counted as request metrics are collected.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            if max_failures == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} must be set to at least 1.", key),
+                });
+            }
+
+            info!("user_max_consecutive_failures = {}", max_failures);
+        }
+
+        Ok(())
+    }
+
+    // Configure the HAR file to replay, and whether to wait between requests as recorded,
+    // adding a task set that replays its requests. Called before the task sets are
+    // checked, so a HAR file can be replayed without defining any other task sets.
+    fn set_har(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.har";
+        let mut value = "".to_string();
+
+        if !self.configuration.har.is_empty() {
+            key = "--har";
+            value = self.configuration.har.to_string();
+        // Optionally set default.
+        } else if let Some(default_har) = self.defaults.har.clone() {
+            key = "set_default(SwanlingDefault::Har)";
+            value = default_har.to_string();
+
+            self.configuration.har = default_har;
+        }
+
+        // Optionally set default for waiting between requests.
+        if !self.configuration.har_timing {
+            if let Some(default_har_timing) = self.defaults.har_timing {
+                self.configuration.har_timing = default_har_timing;
+            }
+        }
+
+        if self.configuration.har.is_empty() {
+            if self.configuration.har_timing {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--har-timing".to_string(),
+                    value: true.to_string(),
+                    detail: "--har-timing can not be set without --har.".to_string(),
+                });
+            }
+
+            return Ok(());
+        }
+
+        let contents = match std::fs::read_to_string(&self.configuration.har) {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!("{} could not be read: {}", key, e),
+                });
+            }
+        };
+        let har = match har::parse_har(&contents) {
+            Ok(h) => h,
+            Err(e) => {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!("{} {}", key, e),
+                });
+            }
+        };
+
+        if har.skipped > 0 {
+            warn!(
+                "skipped {} HAR entries that aren't HTTP requests to {}",
+                har.skipped, har.host
+            );
+        }
+        info!(
+            "har = {} ({} requests, har_timing = {})",
+            value,
+            har.requests.len(),
+            self.configuration.har_timing
+        );
+
+        let mut task_set = har::har_task_set(har, self.configuration.har_timing);
+        task_set.task_sets_index = self.task_sets.len();
+        self.task_sets.push(task_set);
+
+        Ok(())
+    }
+
+    // Returns true if `--max-requests` is enabled and that many requests have been made.
+    pub(crate) fn max_requests_reached(&self) -> bool {
+        self.configuration.max_requests > 0 && self.request_count >= self.configuration.max_requests
+    }
+
+    // Returns true once every user has run all of its iterations, if limited with
+    // `--iterations-per-user`.
+    fn iterations_completed(&self, swanling_attack_run_state: &SwanlingAttackRunState) -> bool {
+        self.configuration.iterations_per_user.is_some()
+            && !swanling_attack_run_state.users.is_empty()
+            && swanling_attack_run_state
+                .users
+                .iter()
+                .all(|user| user.is_finished())
+    }
+
+    // Configure how quickly to hatch [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
+    fn set_hatch_rate(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.hatch_rate";
+        let mut value = "".to_string();
+
+        // Check if --hash-rate is set.
+        if let Some(hatch_rate) = &self.configuration.hatch_rate {
+            key = "--hatch_rate";
+            value = hatch_rate.to_string();
+        // If not, check if a default hatch_rate is set.
+        } else if let Some(default_hatch_rate) = &self.defaults.hatch_rate {
+            // On Worker hatch_rate comes from the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                self.configuration.hatch_rate = None;
+            // Otherwise use default.
+            } else {
+                key = "set_default(SwanlingDefault::HatchRate)";
+                value = default_hatch_rate.to_string();
+                self.configuration.hatch_rate = Some(default_hatch_rate.to_string());
+            }
+        // If not and if not running on Worker, default to 1.
+        } else if self.attack_mode != AttackMode::Worker {
+            // This should not be able to fail, but setting up debug in case a later
+            // change introduces the potential for failure.
+            key = "Swanling default";
+            value = "1".to_string();
+            self.configuration.hatch_rate = Some(value.to_string());
+        }
+
+        // Verbose output.
+        if let Some(hatch_rate) = &self.configuration.hatch_rate {
+            // Setting --hatch-rate with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Setting --hatch-rate of 0 is not allowed.
+            if hatch_rate.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!("{} must be set to at least 1.", key),
+                });
+            }
+
+            // Debug output.
+            info!("hatch_rate = {}", hatch_rate);
+        }
+
+        Ok(())
+    }
+
+    // Configure the coordinated omission mitigation strategy.
+    fn set_coordinated_omission(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.coordinated_omission";
+        let mut value = Some(SwanlingCoordinatedOmissionMitigation::Disabled);
+
+        if self.configuration.co_mitigation.is_some() {
+            key = "--co-mitigation";
+            value = self.configuration.co_mitigation.clone();
+        }
+
+        // Use default for co_mitigation if set and not on Worker.
+        if self.configuration.co_mitigation.is_none() {
+            if let Some(default_co_mitigation) = self.defaults.co_mitigation.as_ref() {
+                // In Gaggles, co_mitigation is only set on Manager.
+                if self.attack_mode != AttackMode::Worker {
+                    key = "set_default(SwanlingDefault::CoordinatedOmissionMitigation)";
+                    value = Some(default_co_mitigation.clone());
+
+                    self.configuration.co_mitigation = Some(default_co_mitigation.clone());
+                }
+            }
+        }
+
+        // Otherwise default to SwanlingCoordinaatedOmissionMitigation::Average.
+        if self.configuration.co_mitigation.is_none() && self.attack_mode != AttackMode::Worker {
+            self.configuration.co_mitigation = value.clone();
+        }
+
+        if let Some(co_mitigation) = self.configuration.co_mitigation.as_ref() {
+            // Setting --co-mitigation with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", value),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Setting --co-mitigation with --no-metrics is not allowed.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            if co_mitigation != &SwanlingCoordinatedOmissionMitigation::Disabled
+                && self.scheduler == SwanlingScheduler::Random
+            {
+                // Coordinated Omission Mitigation is not possible together with the random scheduler,
+                // as it's impossible to calculate an accurate request cadence.
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "{} can not be set together with SwanlingScheduler::Random.",
+                        key
+                    ),
+                });
+            }
+
+            info!(
+                "co_mitigation = {:?}",
+                self.configuration.co_mitigation.as_ref().unwrap()
+            );
+        }
+
+        Ok(())
+    }
+
+    // Configure maximum requests per second if throttle enabled.
+    fn set_throttle_requests(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.throttle_requests";
+        let mut value = 0;
+
+        if self.configuration.throttle_requests > 0 {
+            key = "--throttle-requests";
+            value = self.configuration.throttle_requests;
+        }
+
+        // Use default for throttle_requests if set and not on Worker.
+        if self.configuration.throttle_requests == 0 {
+            if let Some(default_throttle_requests) = self.defaults.throttle_requests {
+                // In Gaggles, throttle_requests is only set on Worker.
+                if self.attack_mode != AttackMode::Manager {
+                    key = "set_default(SwanlingDefault::ThrottleRequests)";
+                    value = default_throttle_requests;
+
+                    self.configuration.throttle_requests = default_throttle_requests;
+                }
+            }
+        }
+
+        if self.configuration.throttle_requests > 0 {
+            // Setting --throttle-requests with --worker is not allowed.
+            if self.attack_mode == AttackMode::Manager {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --manager flag.", key),
+                });
+            }
+
+            // Be sure throttle_requests is in allowed range.
+            if self.configuration.throttle_requests == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} must be set to at least 1 request per second.", key),
+                });
+            } else if self.configuration.throttle_requests > 1_000_000 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set to more than 1,000,000 requests per second.",
+                        key
+                    ),
+                });
+            }
+
+            info!(
+                "throttle_requests = {}",
+                self.configuration.throttle_requests
+            );
+        }
+
+        Ok(())
+    }
+
+    // Configure the adaptive throttle if enabled.
+    fn set_adaptive_target_p95(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--adaptive-target-p95";
+
+        if self.configuration.adaptive_target_p95.is_none() {
+            if let Some(default_target) = self.defaults.adaptive_target_p95 {
+                // The adaptive throttle is only supported in StandAlone mode.
+                if self.attack_mode == AttackMode::StandAlone {
+                    key = "set_default(SwanlingDefault::AdaptiveTargetP95)";
+                    self.configuration.adaptive_target_p95 = Some(default_target);
+                }
+            }
+        }
+
+        if let Some(target) = self.configuration.adaptive_target_p95 {
+            // The throttle is adjusted from the metrics of a single process.
+            if self.attack_mode != AttackMode::StandAlone {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: target.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --manager or --worker flags.",
+                        key
+                    ),
+                });
+            }
+
+            if target == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: target.to_string(),
+                    detail: format!("{} must be set to at least 1 millisecond.", key),
+                });
+            }
+
+            // The throttle sets the rate the adaptive throttle starts from.
+            if self.configuration.throttle_requests == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: target.to_string(),
+                    detail: format!(
+                        "{} can not be set without also setting --throttle-requests.",
+                        key
+                    ),
+                });
+            }
+
+            // The throttle is adapted from the response times.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: target.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            info!("adaptive_target_p95 = {}", target);
+        }
+
+        Ok(())
+    }
+
+    // Configure maximum concurrent connections if enabled.
+    fn set_max_connections(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--max-connections";
+
+        // Use default for max_connections if set and not on Manager.
+        if self.configuration.max_connections == 0 {
+            if let Some(default_max_connections) = self.defaults.max_connections {
+                // In Gaggles, max_connections is only set on Worker.
+                if self.attack_mode != AttackMode::Manager {
+                    key = "set_default(SwanlingDefault::MaxConnections)";
+                    self.configuration.max_connections = default_max_connections;
+                }
+            }
+        }
+
+        if self.configuration.max_connections > 0 {
+            // Connections are opened by the process running the users.
+            if self.attack_mode == AttackMode::Manager {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.max_connections.to_string(),
+                    detail: format!("{} can not be set together with the --manager flag.", key),
+                });
+            }
+
+            info!("max_connections = {}", self.configuration.max_connections);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many idle connections each user keeps open.
+    fn set_pool_size(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--pool-size";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.pool_size.is_none() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_pool_size) = self.defaults.pool_size {
+                key = "set_default(SwanlingDefault::PoolSize)";
+                self.configuration.pool_size = Some(default_pool_size);
+            }
+        }
+
+        if let Some(pool_size) = self.configuration.pool_size {
+            // The pool size is configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: pool_size.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            info!("pool_size = {}", pool_size);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many seconds each user keeps idle connections open.
+    fn set_pool_idle_timeout(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--pool-idle-timeout";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.pool_idle_timeout.is_none() && self.attack_mode != AttackMode::Worker
+        {
+            if let Some(default_pool_idle_timeout) = self.defaults.pool_idle_timeout {
+                key = "set_default(SwanlingDefault::PoolIdleTimeout)";
+                self.configuration.pool_idle_timeout = Some(default_pool_idle_timeout);
+            }
+        }
+
+        if let Some(pool_idle_timeout) = self.configuration.pool_idle_timeout {
+            // The timeout is configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: pool_idle_timeout.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Use --pool-size 0 to close connections as soon as they're idle.
+            if pool_idle_timeout == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: pool_idle_timeout.to_string(),
+                    detail: format!(
+                        "{} must be set to at least 1 second, use --pool-size 0 to not keep idle connections open.",
+                        key
+                    ),
+                });
+            }
+
+            // Users don't keep idle connections open without a pool.
+            if self.configuration.pool_size == Some(0) {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: pool_idle_timeout.to_string(),
+                    detail: format!("{} can not be set together with --pool-size 0.", key),
+                });
+            }
+
+            info!("pool_idle_timeout = {}", pool_idle_timeout);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many seconds a connection is idle before TCP keep-alive probes are sent.
+    fn set_keepalive_idle_timeout(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--keepalive-idle-timeout";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.keepalive_idle_timeout.is_none()
+            && self.attack_mode != AttackMode::Worker
+        {
+            if let Some(default_keepalive_idle_timeout) = self.defaults.keepalive_idle_timeout {
+                key = "set_default(SwanlingDefault::KeepaliveIdleTimeout)";
+                self.configuration.keepalive_idle_timeout = Some(default_keepalive_idle_timeout);
+            }
+        }
+
+        if let Some(keepalive_idle_timeout) = self.configuration.keepalive_idle_timeout {
+            // The timeout is configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: keepalive_idle_timeout.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Probing connections the moment they're idle isn't supported by the socket.
+            if keepalive_idle_timeout == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: keepalive_idle_timeout.to_string(),
+                    detail: format!("{} must be set to at least 1 second.", key),
+                });
+            }
+
+            info!("keepalive_idle_timeout = {}", keepalive_idle_timeout);
+        }
+
+        Ok(())
+    }
+
+    // Determine if `--prewarm-connections` flag is enabled.
+    fn set_prewarm_connections(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.prewarm_connections";
+        let mut value = false;
+
+        if self.configuration.prewarm_connections {
+            key = "--prewarm-connections";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_prewarm_connections) = self.defaults.prewarm_connections {
+                key = "set_default(SwanlingDefault::PrewarmConnections)";
+                value = default_prewarm_connections;
+
+                self.configuration.prewarm_connections = default_prewarm_connections;
+            }
+        }
+
+        if self.configuration.prewarm_connections {
+            // Connections are prewarmed by users configured on the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Users don't keep a connection open between requests without a pool.
+            if self.configuration.pool_size == Some(0) {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with --pool-size 0.", key),
+                });
+            }
+
+            info!("prewarm_connections = {}", value);
+        }
+
+        Ok(())
+    }
+
+    // Warn if the users are likely to need more file descriptors than this process is
+    // allowed to open, which otherwise causes confusing connection errors mid-test.
+    pub(crate) fn check_open_file_limit(&self, users: usize, pool_size: Option<usize>) {
+        let open_file_limit = match util::open_file_limit() {
+            Some(open_file_limit) => open_file_limit,
+            None => return,
+        };
+
+        // Unless the pool is disabled, each user keeps a connection open between requests.
+        let idle_connections = if pool_size == Some(0) { 0 } else { users };
+        // Requests in flight are capped by --max-connections.
+        let active_connections = if self.configuration.max_connections > 0 {
+            self.configuration.max_connections.min(users)
+        } else {
+            users
+        };
+        let file_descriptors = idle_connections.max(active_connections) + RESERVED_FILE_DESCRIPTORS;
+
+        if file_descriptors > open_file_limit {
+            warn!(
+                "{} users may need about {} file descriptors but the open file limit is {}: raise it with `ulimit -n`, or cap connections with --max-connections and --pool-size 0",
+                users, file_descriptors, open_file_limit
+            );
+        }
+    }
+
+    // Determine if `no_reset_statics` is enabled.
+    fn set_no_reset_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_reset_metrics";
+        let mut value = false;
+
+        if self.configuration.no_reset_metrics {
+            key = "--no-reset-metrics";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_no_reset_metrics) = self.defaults.no_reset_metrics {
+                key = "set_default(SwanlingDefault::NoResetMetrics)";
+                value = default_no_reset_metrics;
+
+                // Optionally set default.
+                self.configuration.no_reset_metrics = default_no_reset_metrics;
+            }
+        }
+
+        // Setting --no-reset-metrics with --worker is not allowed.
+        if self.configuration.no_reset_metrics && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--status-codes` flag is enabled.
+    fn set_status_codes(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.status_codes";
+        let mut value = false;
+
+        if self.configuration.status_codes {
+            key = "--status-codes";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_status_codes) = self.defaults.status_codes {
+                key = "set_default(SwanlingDefault::StatusCodes)";
+                value = default_status_codes;
+
+                // Optionally set default.
+                self.configuration.status_codes = default_status_codes;
+            }
+        }
+
+        // Setting --status-codes with --worker is not allowed.
+        if self.configuration.status_codes && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--running-metrics` flag is enabled.
+    fn set_running_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.running_metrics";
+        let mut value = 0;
+
+        if let Some(running_metrics) = self.configuration.running_metrics {
+            key = "--running-metrics";
+            value = running_metrics;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_running_metrics) = self.defaults.running_metrics {
+                key = "set_default(SwanlingDefault::RunningMetrics)";
+                value = default_running_metrics;
+
+                self.configuration.running_metrics = Some(default_running_metrics);
+            }
+        }
+
+        // Setting --running-metrics with --worker is not allowed.
+        if let Some(running_metrics) = self.configuration.running_metrics {
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            if running_metrics > 0 {
+                info!("running_metrics = {}", running_metrics);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--no-task-metrics` flag is enabled.
+    fn set_no_task_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_task_metrics";
+        let mut value = false;
+
+        if self.configuration.no_task_metrics {
+            key = "--no-task-metrics";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_task_metrics) = self.defaults.no_task_metrics {
+                key = "set_default(SwanlingDefault::NoTaskMetrics)";
+                value = default_no_task_metrics;
+
+                self.configuration.no_task_metrics = default_no_task_metrics;
+            }
+        }
+
+        // Setting --no-task-metrics with --worker is not allowed.
+        if self.configuration.no_task_metrics && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--exclude-think-time` flag is enabled.
+    fn set_exclude_think_time(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.exclude_think_time";
+
+        if self.configuration.exclude_think_time {
+            key = "--exclude-think-time";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_exclude_think_time) = self.defaults.exclude_think_time {
+                key = "set_default(SwanlingDefault::ExcludeThinkTime)";
+
+                self.configuration.exclude_think_time = default_exclude_think_time;
+            }
+        }
+
+        if self.configuration.exclude_think_time {
+            // Task metrics are configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: true.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Think time can't be excluded from task metrics that aren't tracked.
+            if self.configuration.no_metrics || self.configuration.no_task_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: true.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics or --no-task-metrics flags.",
+                        key
+                    ),
+                });
+            }
+
+            info!("exclude_think_time = true");
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--no-error-summary` flag is enabled.
+    fn set_no_error_summary(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_error_summary";
+        let mut value = false;
+
+        if self.configuration.no_error_summary {
+            key = "--no-error-summary";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_error_summary) = self.defaults.no_error_summary {
+                key = "set_default(SwanlingDefault::NoErrorSummary)";
+                value = default_no_error_summary;
+
+                self.configuration.no_error_summary = default_no_error_summary;
+            }
+        }
+
+        // Setting --no-error-summary with --worker is not allowed.
+        if self.configuration.no_error_summary && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how many of the most recent distinct errors are displayed while running.
+    fn set_error_tail(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--error-tail";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.error_tail.is_none() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_error_tail) = self.defaults.error_tail {
+                key = "set_default(SwanlingDefault::ErrorTail)";
+                self.configuration.error_tail = Some(default_error_tail);
+            }
+        }
+
+        if let Some(error_tail) = self.configuration.error_tail {
+            // Errors are displayed by the Manager as they're received from the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: error_tail.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Errors are collected with the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: error_tail.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Otherwise there's nothing to display.
+            if error_tail == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: error_tail.to_string(),
+                    detail: format!("{} must be set to at least 1 error.", key),
+                });
+            }
+
+            info!("error_tail = {}", error_tail);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many request names are tracked by name.
+    fn set_max_request_names(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--max-request-names";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.max_request_names.is_none() && self.attack_mode != AttackMode::Worker
+        {
+            if let Some(default_max_request_names) = self.defaults.max_request_names {
+                key = "set_default(SwanlingDefault::MaxRequestNames)";
+                self.configuration.max_request_names = Some(default_max_request_names);
+            }
+        }
+
+        if let Some(max_request_names) = self.configuration.max_request_names {
+            // Workers inherit the limit from the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: max_request_names.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Request names are only tracked with the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: max_request_names.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // At least one request name must be tracked.
+            if max_request_names == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: max_request_names.to_string(),
+                    detail: format!("{} must be set to at least 1 request name.", key),
+                });
+            }
+
+            info!("max_request_names = {}", max_request_names);
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--soak` flag is enabled.
+    fn set_soak(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.soak";
+        let mut value = false;
+
+        if self.configuration.soak {
+            key = "--soak";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_soak) = self.defaults.soak {
+                key = "set_default(SwanlingDefault::Soak)";
+                value = default_soak;
+
+                self.configuration.soak = default_soak;
+            }
+        }
+
+        // Setting --soak with --worker is not allowed.
+        if self.configuration.soak && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--no-metrics` flag is enabled.
+    fn set_no_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_metrics";
+        let mut value = false;
+
+        if self.configuration.no_metrics {
+            key = "--no-metrics";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_metrics) = self.defaults.no_metrics {
+                key = "set_default(SwanlingDefault::NoMetrics)";
+                value = default_no_metrics;
+
+                self.configuration.no_metrics = default_no_metrics;
+            }
+        }
+
+        // Setting --no-metrics with --worker is not allowed.
+        if self.configuration.no_metrics && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        // Don't allow overhead of collecting metrics unless we're printing them.
+        if self.configuration.no_metrics {
+            if self.configuration.status_codes {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --status-codes flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Don't allow overhead of collecting metrics unless we're printing them.
+            if self.configuration.running_metrics.is_some() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --running_metrics option.",
+                        key
+                    ),
+                });
+            }
+
+            // There is nothing to log if metrics are disabled.
+            if !self.configuration.request_log.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --requests-file option.",
+                        key
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure the client certificate, client key and custom CA certificate used to
+    // establish TLS connections.
+    fn set_client_tls(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.client_cert";
+        let mut value = self.configuration.client_cert.to_string();
+
+        if !self.configuration.client_cert.is_empty() {
+            key = "--client-cert";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_client_cert) = self.defaults.client_cert.clone() {
+                key = "set_default(SwanlingDefault::ClientCert)";
+                value = default_client_cert.to_string();
+
+                self.configuration.client_cert = default_client_cert;
+            }
+        }
+
+        if !self.configuration.client_key.is_empty() {
+            if self.configuration.client_cert.is_empty() {
+                key = "--client-key";
+                value = self.configuration.client_key.to_string();
+            }
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_client_key) = self.defaults.client_key.clone() {
+                if self.configuration.client_cert.is_empty() {
+                    key = "set_default(SwanlingDefault::ClientKey)";
+                    value = default_client_key.to_string();
+                }
+
+                self.configuration.client_key = default_client_key;
+            }
+        }
+
+        if self.configuration.ca_cert.is_empty() && self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_ca_cert) = self.defaults.ca_cert.clone() {
+                self.configuration.ca_cert = default_ca_cert;
+            }
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            // TLS options are configured on the Manager and sent to the Workers.
+            if !self.configuration.client_cert.is_empty()
+                || !self.configuration.client_key.is_empty()
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+            if !self.configuration.ca_cert.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--ca-cert".to_string(),
+                    value: self.configuration.ca_cert.to_string(),
+                    detail: "--ca-cert can not be set together with the --worker flag.".to_string(),
+                });
+            }
+        }
+
+        // A client certificate is useless without the matching private key.
+        if self.configuration.client_cert.is_empty() != self.configuration.client_key.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value,
+                detail: "--client-cert and --client-key must be set together.".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure whether to skip verifying TLS certificates.
+    fn set_insecure(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.insecure";
+
+        if self.configuration.insecure {
+            key = "--insecure";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_insecure) = self.defaults.insecure {
+                key = "set_default(SwanlingDefault::Insecure)";
+
+                self.configuration.insecure = default_insecure;
+            }
+        }
+
+        if self.configuration.insecure {
+            // TLS options are configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: true.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Make it impossible to miss that certificates aren't verified.
+            warn!("******************************************************************");
+            warn!("{} is set: TLS certificates will NOT be verified!", key);
+            warn!("Any server can impersonate the load tested host. Only use this");
+            warn!("against test environments with self-signed or internal certificates.");
+            warn!("******************************************************************");
+        }
+
+        Ok(())
+    }
+
+    // Configure the OAuth2 client-credentials grant used to obtain bearer tokens.
+    fn set_oauth(&mut self) -> Result<(), SwanlingError> {
+        if self.attack_mode == AttackMode::Worker {
+            // OAuth2 options are configured on the Manager and sent to the Workers.
+            for (key, value) in &[
+                ("--oauth-token-url", &self.configuration.oauth_token_url),
+                ("--oauth-client-id", &self.configuration.oauth_client_id),
+                (
+                    "--oauth-client-secret",
+                    &self.configuration.oauth_client_secret,
+                ),
+            ] {
+                if !value.is_empty() {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: value.to_string(),
+                        detail: format!("{} can not be set together with the --worker flag.", key),
+                    });
+                }
+            }
+            return Ok(());
+        }
+
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--oauth-token-url";
+        if self.configuration.oauth_token_url.is_empty() {
+            if let Some(default_oauth_token_url) = self.defaults.oauth_token_url.clone() {
+                key = "set_default(SwanlingDefault::OauthTokenUrl)";
+                self.configuration.oauth_token_url = default_oauth_token_url;
+            }
+        }
+        if self.configuration.oauth_client_id.is_empty() {
+            if let Some(default_oauth_client_id) = self.defaults.oauth_client_id.clone() {
+                self.configuration.oauth_client_id = default_oauth_client_id;
+            }
+        }
+        if self.configuration.oauth_client_secret.is_empty() {
+            if let Some(default_oauth_client_secret) = self.defaults.oauth_client_secret.clone() {
+                self.configuration.oauth_client_secret = default_oauth_client_secret;
+            }
+        }
+
+        if self.configuration.oauth_token_url.is_empty() {
+            // A client id or secret is useless without a token endpoint.
+            if !self.configuration.oauth_client_id.is_empty()
+                || !self.configuration.oauth_client_secret.is_empty()
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--oauth-token-url".to_string(),
+                    value: "".to_string(),
+                    detail: "--oauth-token-url must be set together with --oauth-client-id and --oauth-client-secret.".to_string(),
+                });
+            }
+            return Ok(());
+        }
+
+        let value = self.configuration.oauth_token_url.to_string();
+        if util::is_valid_host(&value).is_err() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value,
+                detail: format!("{} must be a valid URL.", key),
+            });
+        }
+
+        if self.configuration.oauth_client_id.is_empty()
+            || self.configuration.oauth_client_secret.is_empty()
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value,
+                detail: "--oauth-token-url must be set together with --oauth-client-id and --oauth-client-secret.".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure the region, service and credentials requests are signed with by AWS SigV4.
+    fn set_aws_sigv4(&mut self) -> Result<(), SwanlingError> {
+        if self.attack_mode == AttackMode::Worker {
+            // AWS SigV4 options are configured on the Manager and sent to the Workers.
+            for (key, value) in &[
+                ("--aws-region", &self.configuration.aws_region),
+                ("--aws-service", &self.configuration.aws_service),
+                ("--aws-access-key-id", &self.configuration.aws_access_key_id),
+                (
+                    "--aws-secret-access-key",
+                    &self.configuration.aws_secret_access_key,
+                ),
+                ("--aws-session-token", &self.configuration.aws_session_token),
+            ] {
+                if !value.is_empty() {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: value.to_string(),
+                        detail: format!("{} can not be set together with the --worker flag.", key),
+                    });
+                }
+            }
+            return Ok(());
+        }
+
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--aws-region";
+        if self.configuration.aws_region.is_empty() {
+            if let Some(default_aws_region) = self.defaults.aws_region.clone() {
+                key = "set_default(SwanlingDefault::AwsRegion)";
+                self.configuration.aws_region = default_aws_region;
+            }
+        }
+        for (value, default) in [
+            (
+                &mut self.configuration.aws_service,
+                &self.defaults.aws_service,
+            ),
+            (
+                &mut self.configuration.aws_access_key_id,
+                &self.defaults.aws_access_key_id,
+            ),
+            (
+                &mut self.configuration.aws_secret_access_key,
+                &self.defaults.aws_secret_access_key,
+            ),
+            (
+                &mut self.configuration.aws_session_token,
+                &self.defaults.aws_session_token,
+            ),
+        ] {
+            if value.is_empty() {
+                if let Some(default) = default.clone() {
+                    *value = default;
+                }
+            }
+        }
+
+        if self.configuration.aws_region.is_empty() {
+            // A service or credentials are useless without a region.
+            if !self.configuration.aws_service.is_empty()
+                || !self.configuration.aws_access_key_id.is_empty()
+                || !self.configuration.aws_secret_access_key.is_empty()
+                || !self.configuration.aws_session_token.is_empty()
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--aws-region".to_string(),
+                    value: "".to_string(),
+                    detail: "--aws-region must be set together with --aws-service.".to_string(),
+                });
+            }
+            return Ok(());
+        }
+
+        let value = self.configuration.aws_region.to_string();
+        if self.configuration.aws_service.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value,
+                detail: "--aws-region must be set together with --aws-service.".to_string(),
+            });
+        }
+
+        // Credentials that aren't configured are read from the environment, so they're
+        // sent to any Workers together with the rest of the configuration.
+        for (value, variable) in [
+            (
+                &mut self.configuration.aws_access_key_id,
+                sigv4::ACCESS_KEY_ID_ENV,
+            ),
+            (
+                &mut self.configuration.aws_secret_access_key,
+                sigv4::SECRET_ACCESS_KEY_ENV,
+            ),
+            (
+                &mut self.configuration.aws_session_token,
+                sigv4::SESSION_TOKEN_ENV,
+            ),
+        ] {
+            if value.is_empty() {
+                if let Ok(env_value) = std::env::var(variable) {
+                    *value = env_value;
+                }
+            }
+        }
+        if self.configuration.aws_access_key_id.is_empty()
+            || self.configuration.aws_secret_access_key.is_empty()
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value,
+                detail: format!(
+                    "{} requires --aws-access-key-id and --aws-secret-access-key, or the {} and {} environment variables.",
+                    key,
+                    sigv4::ACCESS_KEY_ID_ENV,
+                    sigv4::SECRET_ACCESS_KEY_ENV
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure the weighted list of User-Agents allocated to users, if enabled.
+    fn set_user_agents(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--user-agents";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.user_agents.is_empty() && self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_user_agents) = &self.defaults.user_agents {
+                key = "set_default(SwanlingDefault::UserAgents)";
+
+                self.configuration.user_agents = default_user_agents.to_string();
+            }
+        }
+
+        if !self.configuration.user_agents.is_empty() {
+            // User-Agents are allocated on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.user_agents.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            let weighted_user_agents = std::fs::read_to_string(&self.configuration.user_agents)
+                .map_err(|e| e.to_string())
+                .and_then(|contents| util::weight_user_agents(&contents));
+            match weighted_user_agents {
+                Ok(weighted_user_agents) => self.weighted_user_agents = weighted_user_agents,
+                Err(detail) => {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: self.configuration.user_agents.to_string(),
+                        detail,
+                    });
+                }
+            }
+
+            // Debug output.
+            info!(
+                "user_agents = {} ({} weighted)",
+                self.configuration.user_agents,
+                self.weighted_user_agents.len()
+            );
+        }
+
+        Ok(())
+    }
+
+    // Configure the file user sessions are saved to and reloaded from, if enabled.
+    fn set_session_file(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--session-file";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.session_file.is_empty() && self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_session_file) = &self.defaults.session_file {
+                key = "set_default(SwanlingDefault::SessionFile)";
+
+                self.configuration.session_file = default_session_file.to_string();
+            }
+        }
+
+        if !self.configuration.session_file.is_empty() {
+            // Sessions belong to users in a single process.
+            if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.session_file.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --manager or --worker flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Debug output.
+            info!("session_file = {}", self.configuration.session_file);
+        }
+
+        Ok(())
+    }
+
+    // Configure spikes of additional users.
+    fn set_spike(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--spike";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.spike.is_empty() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_spike) = self.defaults.spike.clone() {
+                key = "set_default(SwanlingDefault::Spike)";
+                self.configuration.spike = default_spike;
+            }
+        }
+
+        if !self.configuration.spike.is_empty() {
+            // Spikes are launched by the process running the users.
+            if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.spike.to_string(),
+                    detail: format!("{} is not supported in Regatta mode.", key),
+                });
+            }
+
+            self.spike = SwanlingSpike::parse(&self.configuration.spike);
+            if self.spike.is_none() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.spike.to_string(),
+                    detail: "Expected MULTIPLIERx,DURATION,every=INTERVAL with a multiplier of at least 2 and a duration shorter than the interval, for example 10x,30s,every=300s.".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure ramping up users until requests fail.
+    fn set_ramp_to_failure(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.ramp_to_failure";
+        let mut value = false;
+
+        if self.configuration.ramp_to_failure {
+            key = "--ramp-to-failure";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_ramp_to_failure) = self.defaults.ramp_to_failure {
+                key = "set_default(SwanlingDefault::RampToFailure)";
+                value = default_ramp_to_failure;
+
+                self.configuration.ramp_to_failure = default_ramp_to_failure;
+            }
+        }
+
+        if self.configuration.ramp_to_failure {
+            // Users are launched by the process measuring the error rate.
+            if self.attack_mode != AttackMode::StandAlone {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --manager or --worker flags.",
+                        key
+                    ),
+                });
+            }
+
+            // The error rate is measured from the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Both would launch additional users.
+            if !self.configuration.spike.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --spike option.", key),
+                });
+            }
+        }
+
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--error-threshold";
+
+        // If not otherwise set and ramping to failure, check if there's a default.
+        if self.configuration.error_threshold.is_none() && self.configuration.ramp_to_failure {
+            self.configuration.error_threshold =
+                if let Some(default_error_threshold) = self.defaults.error_threshold {
+                    key = "set_default(SwanlingDefault::ErrorThreshold)";
+                    Some(default_error_threshold)
+                } else {
+                    Some(metrics::RAMP_ERROR_THRESHOLD)
+                };
+        }
+
+        if let Some(error_threshold) = self.configuration.error_threshold {
+            // The threshold is only used to stop ramping to failure.
+            if !self.configuration.ramp_to_failure {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: error_threshold.to_string(),
+                    detail: format!(
+                        "{} can not be set without also setting the --ramp-to-failure flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Otherwise the target could never be considered broken.
+            if error_threshold >= 100 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: error_threshold.to_string(),
+                    detail: format!("{} must be set to less than 100 percent.", key),
+                });
+            }
+
+            info!("ramp_to_failure, error_threshold = {}", error_threshold);
+        }
+
+        Ok(())
+    }
+
+    // Configure the load profile adjusting users over time. When no run time is set, the
+    // load test runs until the last point of the profile.
+    fn set_load_profile(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--load-profile";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.load_profile.is_empty() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_load_profile) = self.defaults.load_profile.clone() {
+                key = "set_default(SwanlingDefault::LoadProfile)";
+                self.configuration.load_profile = default_load_profile;
+            }
+        }
+
+        if self.configuration.load_profile.is_empty() {
+            return Ok(());
+        }
+
+        // The Manager follows the profile, reconfiguring the users on all Workers.
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.load_profile.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        // All would launch additional users.
+        for (conflict, enabled) in [
+            ("--spike", !self.configuration.spike.is_empty()),
+            ("--ramp-to-failure", self.configuration.ramp_to_failure),
+        ] {
+            if enabled {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.load_profile.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the {} option.",
+                        key, conflict
+                    ),
+                });
+            }
+        }
+
+        let contents = match std::fs::read_to_string(&self.configuration.load_profile) {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.load_profile.to_string(),
+                    detail: format!("{} could not be read: {}", key, e),
+                });
+            }
+        };
+        let load_profile = match SwanlingLoadProfile::parse(&contents) {
+            Ok(l) => l,
+            Err(e) => {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.load_profile.to_string(),
+                    detail: format!("{} {}", key, e),
+                });
+            }
+        };
+
+        if self.run_time == 0 {
+            self.run_time = load_profile.duration();
+        }
+        info!(
+            "load_profile = {} ({} points over {} seconds)",
+            self.configuration.load_profile,
+            load_profile.points.len(),
+            load_profile.duration()
+        );
+        self.load_profile = Some(load_profile);
+
+        Ok(())
+    }
+
+    // Configure service level agreements.
+    fn set_sla(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--sla";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.sla.is_empty() && self.attack_mode != AttackMode::Worker {
+            // Optionally set default, which can contain a comma separated list of agreements.
+            if let Some(default_sla) = self.defaults.sla.clone() {
+                key = "set_default(SwanlingDefault::Sla)";
+
+                self.configuration.sla = default_sla
+                    .split(',')
+                    .map(|sla| sla.trim().to_string())
+                    .collect();
+            }
+        }
+
+        if !self.configuration.sla.is_empty() {
+            // Agreements are checked against the final metrics on the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.sla.join(","),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Agreements are checked against metrics, so they must be enabled.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.sla.join(","),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Validate all agreements before starting the load test.
+            for sla in &self.configuration.sla {
+                if metrics::parse_sla(sla).is_none() {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: sla.to_string(),
+                        detail: "Expected NAME:THRESHOLD where THRESHOLD is pNN=MS, max=MS or errors=PERCENT, for example /search:p95=200.".to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure the slow request threshold.
+    fn set_slow_threshold(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--slow-threshold";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.slow_threshold.is_none() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_slow_threshold) = self.defaults.slow_threshold {
+                key = "set_default(SwanlingDefault::SlowThreshold)";
+                self.configuration.slow_threshold = Some(default_slow_threshold);
+            }
+        }
+
+        if let Some(slow_threshold) = self.configuration.slow_threshold {
+            // The threshold is configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: slow_threshold.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Slow requests are counted in the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: slow_threshold.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Otherwise every request would be slow.
+            if slow_threshold == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: slow_threshold.to_string(),
+                    detail: format!("{} must be set to at least 1 millisecond.", key),
+                });
+            }
+
+            info!("slow_threshold = {}", slow_threshold);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many standard deviations above average make a request an outlier.
+    fn set_outlier_factor(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--outlier-factor";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.outlier_factor.is_none() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_outlier_factor) = self.defaults.outlier_factor.as_ref() {
+                key = "set_default(SwanlingDefault::OutlierFactor)";
+                match default_outlier_factor.parse::<f32>() {
+                    Ok(factor) => self.configuration.outlier_factor = Some(factor),
+                    Err(e) => {
+                        return Err(SwanlingError::InvalidOption {
+                            option: key.to_string(),
+                            value: default_outlier_factor.to_string(),
+                            detail: format!("{} must be set to a number: {}", key, e),
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(outlier_factor) = self.configuration.outlier_factor {
+            // The factor is configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: outlier_factor.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Outliers are found in the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: outlier_factor.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Otherwise every request slower than average would be an outlier.
+            if !outlier_factor.is_finite() || outlier_factor <= 0.0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: outlier_factor.to_string(),
+                    detail: format!("{} must be set to a number greater than 0.", key),
+                });
+            }
+
+            info!("outlier_factor = {}", outlier_factor);
+        }
+
+        Ok(())
+    }
+
+    // Configure the minimum number of samples to report percentiles.
+    fn set_min_samples(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--min-samples";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.min_samples.is_none() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_min_samples) = self.defaults.min_samples {
+                key = "set_default(SwanlingDefault::MinSamples)";
+                self.configuration.min_samples = Some(default_min_samples);
+            }
+        }
+
+        if let Some(min_samples) = self.configuration.min_samples {
+            // Percentiles are reported by the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: min_samples.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Percentiles are only reported with the metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: min_samples.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Every request has at least one sample.
+            if min_samples == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: min_samples.to_string(),
+                    detail: format!("{} must be set to at least 1 sample.", key),
+                });
+            }
+
+            info!("min_samples = {}", min_samples);
+        }
+
+        Ok(())
+    }
+
+    // Configure the label to group request metrics by.
+    fn set_group_by(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--group-by";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.group_by.is_empty() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_group_by) = &self.defaults.group_by {
+                key = "set_default(SwanlingDefault::GroupBy)";
+                self.configuration.group_by = default_group_by.to_string();
             }
-        // If not and if not running on Worker, default to 1.
-        } else if self.attack_mode != AttackMode::Worker {
-            // This should not be able to fail, but setting up debug in case the number
-            // of cpus library returns an invalid number.
-            key = "num_cpus::get()";
-            value = num_cpus::get();
+        }
 
-            info!("concurrent users defaulted to {} (number of CPUs)", value);
+        if !self.configuration.group_by.is_empty() {
+            // The label is configured on the Manager and sent to the Workers.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.group_by.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
 
-            self.configuration.users = Some(value);
+            // Groups are aggregated from the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.group_by.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Labels are recorded in lower case.
+            self.configuration.group_by = self.configuration.group_by.to_lowercase();
+
+            info!("group_by = {}", self.configuration.group_by);
         }
 
-        // Perform bounds checking.
-        if let Some(users) = self.configuration.users {
-            // Setting --users with --worker is not allowed.
+        Ok(())
+    }
+
+    // Configure the response header identifying the region that served each request.
+    fn set_region_header(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--region-header";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.region_header.is_empty() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_region_header) = &self.defaults.region_header {
+                key = "set_default(SwanlingDefault::RegionHeader)";
+                self.configuration.region_header = default_region_header.to_string();
+            }
+        }
+
+        if !self.configuration.region_header.is_empty() {
+            // The header is configured on the Manager and sent to the Workers.
             if self.attack_mode == AttackMode::Worker {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
+                    value: self.configuration.region_header.to_string(),
                     detail: format!("{} can not be set together with the --worker flag.", key),
                 });
             }
 
-            // Setting users to 0 is not allowed.
-            if users == 0 {
+            // Regions are grouped from the request metrics.
+            if self.configuration.no_metrics {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: "0".to_string(),
-                    detail: "The --users option must be set to at least 1.".to_string(),
+                    value: self.configuration.region_header.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
                 });
             }
 
-            // Debug output.
-            info!("users = {}", users);
+            if reqwest::header::HeaderName::from_bytes(self.configuration.region_header.as_bytes())
+                .is_err()
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.region_header.to_string(),
+                    detail: format!("{} must be a valid header name.", key),
+                });
+            }
+
+            // Requests are grouped by the region label, so they can't also be grouped by
+            // another label.
+            if !self.configuration.group_by.is_empty()
+                && self.configuration.group_by != swanling::REGION_LABEL
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.region_header.to_string(),
+                    detail: format!(
+                        "{} can not be set together with --group-by {}.",
+                        key, self.configuration.group_by
+                    ),
+                });
+            }
+            self.configuration.group_by = swanling::REGION_LABEL.to_string();
+
+            // Header names are case insensitive.
+            self.configuration.region_header = self.configuration.region_header.to_lowercase();
+
+            info!("region_header = {}", self.configuration.region_header);
         }
 
         Ok(())
     }
 
-    // Configure maximum run time if specified, otherwise run until canceled.
-    fn set_run_time(&mut self) -> Result<(), SwanlingError> {
+    // Expand environment variables referenced in the hosts, so they can be configured
+    // without appearing in shell history or process listings.
+    fn set_host(&mut self) -> Result<(), SwanlingError> {
+        if !self.configuration.host.is_empty() {
+            self.configuration.host =
+                util::expand_env_vars(&self.configuration.host).map_err(|detail| {
+                    SwanlingError::InvalidOption {
+                        option: "--host".to_string(),
+                        value: self.configuration.host.to_string(),
+                        detail,
+                    }
+                })?;
+        }
+
+        if let Some(default_host) = self.defaults.host.clone() {
+            self.defaults.host = Some(util::expand_env_vars(&default_host).map_err(|detail| {
+                SwanlingError::InvalidOption {
+                    option: "set_default(SwanlingDefault::Host)".to_string(),
+                    value: default_host.to_string(),
+                    detail,
+                }
+            })?);
+        }
+
+        for task_set in &mut self.task_sets {
+            if let Some(host) = task_set.host.clone() {
+                task_set.host = Some(util::expand_env_vars(&host).map_err(|detail| {
+                    SwanlingError::InvalidOption {
+                        option: "SwanlingTaskSet.set_host()".to_string(),
+                        value: host.to_string(),
+                        detail,
+                    }
+                })?);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure the path prefixed to all relative request paths.
+    fn set_base_path(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.run_time";
-        let mut value = 0;
+        let mut key = "--base-path";
 
-        // Use --run-time if set, don't allow on Worker.
-        self.run_time = if !self.configuration.run_time.is_empty() {
-            key = "--run-time";
-            value = util::parse_timespan(&self.configuration.run_time);
-            value
-        // Otherwise, use default if set, but not on Worker.
-        } else if let Some(default_run_time) = self.defaults.run_time {
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.base_path.is_empty() && self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_base_path) = &self.defaults.base_path {
+                key = "set_default(SwanlingDefault::BasePath)";
+
+                self.configuration.base_path = default_base_path.to_string();
+            }
+        }
+
+        if !self.configuration.base_path.is_empty() {
+            // The base path is configured on the Manager and sent to the Workers.
             if self.attack_mode == AttackMode::Worker {
-                0
-            } else {
-                key = "set_default(SwanlingDefault::RunTime)";
-                value = default_run_time;
-                default_run_time
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.base_path.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Only a path can be prefixed, not a host, query or fragment.
+            if self
+                .configuration
+                .base_path
+                .contains(|c: char| c == '?' || c == '#' || c == ':' || c.is_whitespace())
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.base_path.to_string(),
+                    detail: format!("{} must be a path, for example /api/v2.", key),
+                });
             }
+
+            // Normalize to a leading slash without a trailing slash, so `/` prefixes nothing.
+            self.configuration.base_path =
+                format!("/{}", self.configuration.base_path.trim_matches('/'))
+                    .trim_end_matches('/')
+                    .to_string();
+
+            info!("base_path = {}", self.configuration.base_path);
         }
-        // Otherwise the test runs until canceled.
-        else {
-            0
-        };
 
-        if self.run_time > 0 {
+        Ok(())
+    }
+
+    // Configure the URL checked before starting the load test, and the status code it must
+    // return.
+    fn set_health_check(&mut self) -> Result<(), SwanlingError> {
+        // Track how values get set so we can return a meaningful error if necessary.
+        let mut key = "--health-check-url";
+        let mut expect_key = "--health-check-expect";
+
+        // If not otherwise set and not Worker, check if there are defaults.
+        if self.attack_mode != AttackMode::Worker {
+            if self.configuration.health_check_url.is_empty() {
+                // Optionally set default.
+                if let Some(default_health_check_url) = &self.defaults.health_check_url {
+                    key = "set_default(SwanlingDefault::HealthCheckUrl)";
+
+                    self.configuration.health_check_url = default_health_check_url.to_string();
+                }
+            }
+            if self.configuration.health_check_expect.is_none() {
+                // Optionally set default.
+                if let Some(default_health_check_expect) = self.defaults.health_check_expect {
+                    expect_key = "set_default(SwanlingDefault::HealthCheckExpect)";
+
+                    self.configuration.health_check_expect = Some(default_health_check_expect);
+                }
+            }
+        }
+
+        if let Some(health_check_expect) = self.configuration.health_check_expect {
+            // The expected status code only applies to a health check.
+            if self.configuration.health_check_url.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: expect_key.to_string(),
+                    value: health_check_expect.to_string(),
+                    detail: format!("{} requires --health-check-url.", expect_key),
+                });
+            }
+
+            if !(100..=599).contains(&health_check_expect) {
+                return Err(SwanlingError::InvalidOption {
+                    option: expect_key.to_string(),
+                    value: health_check_expect.to_string(),
+                    detail: format!("{} must be a status code from 100 to 599.", expect_key),
+                });
+            }
+        }
+
+        if !self.configuration.health_check_url.is_empty() {
+            // The health check runs once on the Manager, before any Workers start.
             if self.attack_mode == AttackMode::Worker {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
+                    value: self.configuration.health_check_url.to_string(),
                     detail: format!("{} can not be set together with the --worker flag.", key),
                 });
             }
 
-            // Debug output.
-            info!("run_time = {}", self.run_time);
+            // Only a full URL can be checked.
+            if util::is_valid_host(&self.configuration.health_check_url).is_err() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.health_check_url.to_string(),
+                    detail: format!(
+                        "{} must be a full URL, for example http://localhost/health.",
+                        key
+                    ),
+                });
+            }
+
+            info!("health_check_url = {}", self.configuration.health_check_url);
         }
 
         Ok(())
     }
 
-    // Configure how quickly to hatch [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
-    fn set_hatch_rate(&mut self) -> Result<(), SwanlingError> {
+    // Request the `--health-check-url`, failing unless it returns the expected status code,
+    // or any 2xx status code if none is expected.
+    async fn run_health_check(&self) -> Result<(), SwanlingError> {
+        let url = &self.configuration.health_check_url;
+        let expect = self.configuration.health_check_expect;
+        let client = build_standalone_client(&self.configuration)?;
+        let detail = match client
+            .get(url)
+            .timeout(time::Duration::from_secs(HEALTH_CHECK_TIMEOUT))
+            .send()
+            .await
+        {
+            Ok(response) => {
+                let status = response.status();
+                let passed = match expect {
+                    Some(expect) => status.as_u16() as usize == expect,
+                    None => status.is_success(),
+                };
+                if passed {
+                    info!("health check passed: {} returned {}", url, status);
+                    return Ok(());
+                }
+                match expect {
+                    Some(expect) => format!("{} returned {}, expected {}.", url, status, expect),
+                    None => format!("{} returned {}, expected a 2xx status code.", url, status),
+                }
+            }
+            Err(e) => format!("{} failed: {}.", url, e),
+        };
+
+        error!("health check failed: {}", detail);
+        Err(SwanlingError::HealthCheckFailed {
+            url: url.to_string(),
+            detail,
+        })
+    }
+
+    // Configure host name to IP address mappings.
+    fn set_resolve(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.hatch_rate";
-        let mut value = "".to_string();
+        let mut key = "--resolve";
 
-        // Check if --hash-rate is set.
-        if let Some(hatch_rate) = &self.configuration.hatch_rate {
-            key = "--hatch_rate";
-            value = hatch_rate.to_string();
-        // If not, check if a default hatch_rate is set.
-        } else if let Some(default_hatch_rate) = &self.defaults.hatch_rate {
-            // On Worker hatch_rate comes from the Manager.
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.resolve.is_empty() && self.attack_mode != AttackMode::Worker {
+            // Optionally set default, which can contain a comma separated list of mappings.
+            if let Some(default_resolve) = self.defaults.resolve.clone() {
+                key = "set_default(SwanlingDefault::Resolve)";
+
+                self.configuration.resolve = default_resolve
+                    .split(',')
+                    .map(|mapping| mapping.trim().to_string())
+                    .collect();
+            }
+        }
+
+        if !self.configuration.resolve.is_empty() {
+            // Host mappings are configured on the Manager and sent to the Workers.
             if self.attack_mode == AttackMode::Worker {
-                self.configuration.hatch_rate = None;
-            // Otherwise use default.
-            } else {
-                key = "set_default(SwanlingDefault::HatchRate)";
-                value = default_hatch_rate.to_string();
-                self.configuration.hatch_rate = Some(default_hatch_rate.to_string());
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.resolve.join(","),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Validate all mappings before starting the load test. Mappings apply to the
+            // host name, so a host name can't be mapped more than one way.
+            let mut mapped: BTreeMap<String, &str> = BTreeMap::new();
+            for mapping in &self.configuration.resolve {
+                let host = match util::parse_resolve(mapping) {
+                    Some((host, _)) => host,
+                    None => {
+                        return Err(SwanlingError::InvalidOption {
+                            option: key.to_string(),
+                            value: mapping.to_string(),
+                            detail:
+                                "Expected HOST:PORT:ADDR, for example api.example.com:443:10.0.0.42."
+                                    .to_string(),
+                        });
+                    }
+                };
+                if let Some(previous) = mapped.insert(host.clone(), mapping) {
+                    if previous != mapping {
+                        return Err(SwanlingError::InvalidOption {
+                            option: key.to_string(),
+                            value: mapping.to_string(),
+                            detail: format!(
+                                "{} is already mapped by {}, connections always use the port from the request URL.",
+                                host, previous
+                            ),
+                        });
+                    }
+                }
             }
-        // If not and if not running on Worker, default to 1.
-        } else if self.attack_mode != AttackMode::Worker {
-            // This should not be able to fail, but setting up debug in case a later
-            // change introduces the potential for failure.
-            key = "Swanling default";
-            value = "1".to_string();
-            self.configuration.hatch_rate = Some(value.to_string());
         }
 
-        // Verbose output.
-        if let Some(hatch_rate) = &self.configuration.hatch_rate {
-            // Setting --hatch-rate with --worker is not allowed.
+        Ok(())
+    }
+
+    // Configure how many redirects each request follows.
+    fn set_max_redirects(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--max-redirects";
+
+        // If not otherwise set and not Worker, check if there's a default.
+        if self.configuration.max_redirects.is_none() && self.attack_mode != AttackMode::Worker {
+            if let Some(default_max_redirects) = self.defaults.max_redirects {
+                key = "set_default(SwanlingDefault::MaxRedirects)";
+                self.configuration.max_redirects = Some(default_max_redirects);
+            }
+        }
+
+        if let Some(max_redirects) = self.configuration.max_redirects {
+            // The redirect limit is configured on the Manager and sent to the Workers.
             if self.attack_mode == AttackMode::Worker {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value,
+                    value: max_redirects.to_string(),
                     detail: format!("{} can not be set together with the --worker flag.", key),
                 });
             }
 
-            // Setting --hatch-rate of 0 is not allowed.
-            if hatch_rate.is_empty() {
+            // There's nothing to follow if redirects are disabled.
+            if max_redirects == 0 && self.configuration.sticky_follow {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value,
-                    detail: format!("{} must be set to at least 1.", key),
+                    value: max_redirects.to_string(),
+                    detail: format!(
+                        "{} can not be set to 0 together with the --sticky-follow flag.",
+                        key
+                    ),
                 });
             }
 
-            // Debug output.
-            info!("hatch_rate = {}", hatch_rate);
+            info!("max_redirects = {}", max_redirects);
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--sticky-follow` flag is enabled.
+    fn set_sticky_follow(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.sticky_follow";
+        let mut value = false;
+
+        if self.configuration.sticky_follow {
+            key = "--sticky-follow";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_sticky_follow) = self.defaults.sticky_follow {
+                key = "set_default(SwanlingDefault::StickyFollow)";
+                value = default_sticky_follow;
+
+                self.configuration.sticky_follow = default_sticky_follow;
+            }
+        }
+
+        if self.configuration.sticky_follow && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
         }
 
         Ok(())
     }
 
-    // Configure the coordinated omission mitigation strategy.
-    fn set_coordinated_omission(&mut self) -> Result<(), SwanlingError> {
+    #[cfg(feature = "gaggle")]
+    // Determine if `--no-hash-check` flag is enabled.
+    fn set_no_hash_check(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.coordinated_omission";
-        let mut value = Some(SwanlingCoordinatedOmissionMitigation::Disabled);
-
-        if self.configuration.co_mitigation.is_some() {
-            key = "--co-mitigation";
-            value = self.configuration.co_mitigation.clone();
-        }
+        let mut key = "configuration.no_hash_check";
+        let mut value = false;
 
-        // Use default for co_mitigation if set and not on Worker.
-        if self.configuration.co_mitigation.is_none() {
-            if let Some(default_co_mitigation) = self.defaults.co_mitigation.as_ref() {
-                // In Gaggles, co_mitigation is only set on Manager.
-                if self.attack_mode != AttackMode::Worker {
-                    key = "set_default(SwanlingDefault::CoordinatedOmissionMitigation)";
-                    value = Some(default_co_mitigation.clone());
+        if self.configuration.no_hash_check {
+            key = "--no-hash-check";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_hash_check) = self.defaults.no_hash_check {
+                key = "set_default(SwanlingDefault::NoHashCheck)";
+                value = default_no_hash_check;
 
-                    self.configuration.co_mitigation = Some(default_co_mitigation.clone());
-                }
+                self.configuration.no_hash_check = default_no_hash_check;
             }
         }
 
-        // Otherwise default to SwanlingCoordinaatedOmissionMitigation::Average.
-        if self.configuration.co_mitigation.is_none() && self.attack_mode != AttackMode::Worker {
-            self.configuration.co_mitigation = value.clone();
+        if self.configuration.no_hash_check && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
         }
 
-        if let Some(co_mitigation) = self.configuration.co_mitigation.as_ref() {
-            // Setting --co-mitigation with --worker is not allowed.
-            if self.attack_mode == AttackMode::Worker {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: format!("{} can not be set together with the --worker flag.", key),
-                });
+        Ok(())
+    }
+
+    // Determine how many seconds the Manager aggregates metrics in each window.
+    fn set_aggregate_window(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.aggregate_window";
+        let mut value = 0;
+
+        if let Some(aggregate_window) = self.configuration.aggregate_window {
+            key = "--aggregate-window";
+            value = aggregate_window;
+        // If not otherwise set and Manager, check if there's a default.
+        } else if self.attack_mode == AttackMode::Manager {
+            // Optionally set default.
+            if let Some(default_aggregate_window) = self.defaults.aggregate_window {
+                key = "set_default(SwanlingDefault::AggregateWindow)";
+                value = default_aggregate_window;
+
+                self.configuration.aggregate_window = Some(default_aggregate_window);
             }
+        }
 
-            // Setting --co-mitigation with --no-metrics is not allowed.
-            if self.configuration.no_metrics {
+        if let Some(aggregate_window) = self.configuration.aggregate_window {
+            // Only the Manager aggregates metrics from Workers.
+            if self.attack_mode != AttackMode::Manager {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: format!("{:?}", value),
+                    value: value.to_string(),
                     detail: format!(
-                        "{} can not be set together with the --no-metrics flag.",
+                        "{} can not be set without also setting the --manager flag.",
                         key
                     ),
                 });
             }
 
-            if co_mitigation != &SwanlingCoordinatedOmissionMitigation::Disabled
-                && self.scheduler == SwanlingScheduler::Random
-            {
-                // Coordinated Omission Mitigation is not possible together with the random scheduler,
-                // as it's impossible to calculate an accurate request cadence.
+            if aggregate_window == 0 {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: format!(
-                        "{} can not be set together with SwanlingScheduler::Random.",
-                        key
-                    ),
+                    value: value.to_string(),
+                    detail: format!("{} must be set to at least 1.", key),
                 });
             }
 
-            info!(
-                "co_mitigation = {:?}",
-                self.configuration.co_mitigation.as_ref().unwrap()
-            );
+            info!("aggregate_window = {}", aggregate_window);
         }
 
         Ok(())
     }
 
-    // Configure maximum requests per second if throttle enabled.
-    fn set_throttle_requests(&mut self) -> Result<(), SwanlingError> {
+    // Configure how much higher a Worker's error rate can be than its peers' before the
+    // Manager quarantines it.
+    fn set_quarantine_error_threshold(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.throttle_requests";
+        let mut key = "configuration.quarantine_error_threshold";
         let mut value = 0;
 
-        if self.configuration.throttle_requests > 0 {
-            key = "--throttle-requests";
-            value = self.configuration.throttle_requests;
-        }
-
-        // Use default for throttle_requests if set and not on Worker.
-        if self.configuration.throttle_requests == 0 {
-            if let Some(default_throttle_requests) = self.defaults.throttle_requests {
-                // In Gaggles, throttle_requests is only set on Worker.
-                if self.attack_mode != AttackMode::Manager {
-                    key = "set_default(SwanlingDefault::ThrottleRequests)";
-                    value = default_throttle_requests;
+        if let Some(quarantine_error_threshold) = self.configuration.quarantine_error_threshold {
+            key = "--quarantine-error-threshold";
+            value = quarantine_error_threshold;
+        // If not otherwise set and Manager, check if there's a default.
+        } else if self.attack_mode == AttackMode::Manager {
+            // Optionally set default.
+            if let Some(default_quarantine_error_threshold) =
+                self.defaults.quarantine_error_threshold
+            {
+                key = "set_default(SwanlingDefault::QuarantineErrorThreshold)";
+                value = default_quarantine_error_threshold;
 
-                    self.configuration.throttle_requests = default_throttle_requests;
-                }
+                self.configuration.quarantine_error_threshold =
+                    Some(default_quarantine_error_threshold);
             }
         }
 
-        if self.configuration.throttle_requests > 0 {
-            // Setting --throttle-requests with --worker is not allowed.
-            if self.attack_mode == AttackMode::Manager {
+        if let Some(quarantine_error_threshold) = self.configuration.quarantine_error_threshold {
+            // Only the Manager compares Workers with each other.
+            if self.attack_mode != AttackMode::Manager {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
                     value: value.to_string(),
-                    detail: format!("{} can not be set together with the --manager flag.", key),
+                    detail: format!(
+                        "{} can not be set without also setting the --manager flag.",
+                        key
+                    ),
                 });
             }
 
-            // Be sure throttle_requests is in allowed range.
-            if self.configuration.throttle_requests == 0 {
+            if quarantine_error_threshold == 0 || quarantine_error_threshold > 100 {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
                     value: value.to_string(),
-                    detail: format!("{} must be set to at least 1 request per second.", key),
+                    detail: format!("{} must be set to a percentage from 1 to 100.", key),
                 });
-            } else if self.configuration.throttle_requests > 1_000_000 {
+            }
+
+            info!(
+                "quarantine_error_threshold = {}",
+                quarantine_error_threshold
+            );
+        }
+
+        Ok(())
+    }
+
+    // Configure which Workers task sets are pinned to.
+    fn set_pin_task_set(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--pin-task-set";
+
+        // If not otherwise set and Manager, check if there's a default.
+        if self.configuration.pin_task_set.is_empty() && self.attack_mode == AttackMode::Manager {
+            // Optionally set default, which can contain a comma separated list of pins.
+            if let Some(default_pin_task_set) = self.defaults.pin_task_set.clone() {
+                key = "set_default(SwanlingDefault::PinTaskSet)";
+
+                self.configuration.pin_task_set = default_pin_task_set
+                    .split(',')
+                    .map(|pin| pin.trim().to_string())
+                    .collect();
+            }
+        }
+
+        if !self.configuration.pin_task_set.is_empty() {
+            // Only the Manager allocates users to Workers.
+            if self.attack_mode != AttackMode::Manager {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
+                    value: self.configuration.pin_task_set.join(","),
                     detail: format!(
-                        "{} can not be set to more than 1,000,000 requests per second.",
+                        "{} can not be set without also setting the --manager flag.",
                         key
                     ),
                 });
             }
 
+            // Validate all pins before starting the load test.
+            let expect_workers = self.configuration.expect_workers.unwrap_or(0) as usize;
+            for pin in &self.configuration.pin_task_set {
+                let (task_set, worker) = match util::parse_pin_task_set(pin) {
+                    Some(parsed) => parsed,
+                    None => {
+                        return Err(SwanlingError::InvalidOption {
+                            option: key.to_string(),
+                            value: pin.to_string(),
+                            detail: "Expected NAME:N, where N is a Worker numbered from 1, for example WriteHeavy:1.".to_string(),
+                        });
+                    }
+                };
+                if !self.task_sets.iter().any(|t| t.name == task_set) {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: pin.to_string(),
+                        detail: format!("No task set is named {}.", task_set),
+                    });
+                }
+                if worker > expect_workers {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: pin.to_string(),
+                        detail: format!(
+                            "Worker {} does not exist, only {} Workers are expected.",
+                            worker, expect_workers
+                        ),
+                    });
+                }
+            }
+
             info!(
-                "throttle_requests = {}",
-                self.configuration.throttle_requests
+                "pin_task_set = {}",
+                self.configuration.pin_task_set.join(",")
             );
         }
 
         Ok(())
     }
 
-    // Determine if `no_reset_statics` is enabled.
-    fn set_no_reset_metrics(&mut self) -> Result<(), SwanlingError> {
+    // Determine the file the Manager appends InfluxDB line protocol to, if any.
+    fn set_influx_file(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_reset_metrics";
-        let mut value = false;
-
-        if self.configuration.no_reset_metrics {
-            key = "--no-reset-metrics";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            if let Some(default_no_reset_metrics) = self.defaults.no_reset_metrics {
-                key = "set_default(SwanlingDefault::NoResetMetrics)";
-                value = default_no_reset_metrics;
+        let mut key = "--influx-file";
 
-                // Optionally set default.
-                self.configuration.no_reset_metrics = default_no_reset_metrics;
+        // If not otherwise set and Manager, check if there's a default.
+        if self.configuration.influx_file.is_empty() && self.attack_mode == AttackMode::Manager {
+            if let Some(default_influx_file) = &self.defaults.influx_file {
+                key = "set_default(SwanlingDefault::InfluxFile)";
+                self.configuration.influx_file = default_influx_file.to_string();
             }
         }
 
-        // Setting --no-reset-metrics with --worker is not allowed.
-        if self.configuration.no_reset_metrics && self.attack_mode == AttackMode::Worker {
-            return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
-            });
+        if !self.configuration.influx_file.is_empty() {
+            self.validate_influx_option(key, &self.configuration.influx_file)?;
+
+            info!("influx_file = {}", self.configuration.influx_file);
         }
 
         Ok(())
     }
 
-    // Determine if the `--status-codes` flag is enabled.
-    fn set_status_codes(&mut self) -> Result<(), SwanlingError> {
+    // Determine the host:port the Manager sends InfluxDB line protocol to, if any.
+    fn set_influx_udp(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.status_codes";
-        let mut value = false;
+        let mut key = "--influx-udp";
 
-        if self.configuration.status_codes {
-            key = "--status-codes";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            if let Some(default_status_codes) = self.defaults.status_codes {
-                key = "set_default(SwanlingDefault::StatusCodes)";
-                value = default_status_codes;
+        // If not otherwise set and Manager, check if there's a default.
+        if self.configuration.influx_udp.is_empty() && self.attack_mode == AttackMode::Manager {
+            if let Some(default_influx_udp) = &self.defaults.influx_udp {
+                key = "set_default(SwanlingDefault::InfluxUdp)";
+                self.configuration.influx_udp = default_influx_udp.to_string();
+            }
+        }
 
-                // Optionally set default.
-                self.configuration.status_codes = default_status_codes;
+        if !self.configuration.influx_udp.is_empty() {
+            self.validate_influx_option(key, &self.configuration.influx_udp)?;
+
+            // Resolve the address now, so a typo is reported before the load test starts.
+            let resolved = self
+                .configuration
+                .influx_udp
+                .to_socket_addrs()
+                .map(|mut addresses| addresses.next().is_some())
+                .unwrap_or(false);
+            if !resolved {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.influx_udp.to_string(),
+                    detail: format!("{} must be set to a valid host:port.", key),
+                });
             }
+
+            info!("influx_udp = {}", self.configuration.influx_udp);
         }
 
-        // Setting --status-codes with --worker is not allowed.
-        if self.configuration.status_codes && self.attack_mode == AttackMode::Worker {
+        Ok(())
+    }
+
+    // InfluxDB line protocol is written by the Manager at the end of each aggregation
+    // window.
+    fn validate_influx_option(&self, key: &str, value: &str) -> Result<(), SwanlingError> {
+        if self.attack_mode != AttackMode::Manager {
             return Err(SwanlingError::InvalidOption {
                 option: key.to_string(),
                 value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                detail: format!(
+                    "{} can not be set without also setting the --manager flag.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.aggregate_window.is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!(
+                    "{} can not be set without also setting --aggregate-window.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.no_metrics {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!(
+                    "{} can not be set together with the --no-metrics flag.",
+                    key
+                ),
             });
         }
 
         Ok(())
     }
 
-    // Determine if the `--running-metrics` flag is enabled.
-    fn set_running_metrics(&mut self) -> Result<(), SwanlingError> {
+    // If enabled, returns the path of the report_file, otherwise returns None.
+    fn get_report_file_path(&mut self) -> Option<String> {
+        // If metrics are disabled, or running in Manager mode, there is no
+        // report file, exit immediately.
+        if self.configuration.no_metrics || self.attack_mode == AttackMode::Manager {
+            return None;
+        }
+
+        // If --report-file is set, return it.
+        if !self.configuration.report_file.is_empty() {
+            return Some(self.configuration.report_file.to_string());
+        }
+
+        // If SwanlingDefault::ReportFile is set, return it.
+        if let Some(default_report_file) = &self.defaults.report_file {
+            return Some(default_report_file.to_string());
+        }
+
+        // Otherwise there is no report file.
+        None
+    }
+
+    // Configure the directory to write latency distribution charts to, if enabled.
+    fn set_chart_output(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.running_metrics";
-        let mut value = 0;
+        let mut key = "--chart-output";
 
-        if let Some(running_metrics) = self.configuration.running_metrics {
-            key = "--running-metrics";
-            value = running_metrics;
         // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_running_metrics) = self.defaults.running_metrics {
-                key = "set_default(SwanlingDefault::RunningMetrics)";
-                value = default_running_metrics;
+        if self.configuration.chart_output.is_empty() && self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_chart_output) = &self.defaults.chart_output {
+                key = "set_default(SwanlingDefault::ChartOutput)";
 
-                self.configuration.running_metrics = Some(default_running_metrics);
+                self.configuration.chart_output = default_chart_output.to_string();
             }
         }
 
-        // Setting --running-metrics with --worker is not allowed.
-        if let Some(running_metrics) = self.configuration.running_metrics {
+        if !self.configuration.chart_output.is_empty() {
+            // In Gaggles, the Manager renders the charts from the merged metrics.
             if self.attack_mode == AttackMode::Worker {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
+                    value: self.configuration.chart_output.to_string(),
                     detail: format!("{} can not be set together with the --worker flag.", key),
                 });
             }
 
-            if running_metrics > 0 {
-                info!("running_metrics = {}", running_metrics);
+            // Charts are built from the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.chart_output.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
             }
+
+            // Debug output.
+            info!("chart_output = {}", self.configuration.chart_output);
         }
 
         Ok(())
     }
 
-    // Determine if the `--no-task-metrics` flag is enabled.
-    fn set_no_task_metrics(&mut self) -> Result<(), SwanlingError> {
+    // Configure the JUnit XML report, if enabled.
+    fn set_junit_xml(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_task_metrics";
-        let mut value = false;
+        let mut key = "--junit-xml";
 
-        if self.configuration.no_task_metrics {
-            key = "--no-task-metrics";
-            value = true;
         // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
+        if self.configuration.junit_xml.is_empty() && self.attack_mode != AttackMode::Worker {
             // Optionally set default.
-            if let Some(default_no_task_metrics) = self.defaults.no_task_metrics {
-                key = "set_default(SwanlingDefault::NoTaskMetrics)";
-                value = default_no_task_metrics;
+            if let Some(default_junit_xml) = &self.defaults.junit_xml {
+                key = "set_default(SwanlingDefault::JunitXml)";
 
-                self.configuration.no_task_metrics = default_no_task_metrics;
+                self.configuration.junit_xml = default_junit_xml.to_string();
             }
         }
 
-        // Setting --no-task-metrics with --worker is not allowed.
-        if self.configuration.no_task_metrics && self.attack_mode == AttackMode::Worker {
-            return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
-            });
-        }
-
-        Ok(())
-    }
-
-    // Determine if the `--no-error-summary` flag is enabled.
-    fn set_no_error_summary(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_error_summary";
-        let mut value = false;
+        if !self.configuration.junit_xml.is_empty() {
+            // In Gaggles, the Manager writes the report from the merged metrics.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.junit_xml.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
 
-        if self.configuration.no_error_summary {
-            key = "--no-error-summary";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_no_error_summary) = self.defaults.no_error_summary {
-                key = "set_default(SwanlingDefault::NoErrorSummary)";
-                value = default_no_error_summary;
+            // Testcases are built from the request metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.junit_xml.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
 
-                self.configuration.no_error_summary = default_no_error_summary;
+            // A smoke test reports its own results instead of metrics.
+            if self.configuration.smoke {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.junit_xml.to_string(),
+                    detail: format!("{} can not be set together with the --smoke flag.", key),
+                });
             }
-        }
 
-        // Setting --no-error-summary with --worker is not allowed.
-        if self.configuration.no_error_summary && self.attack_mode == AttackMode::Worker {
-            return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
-            });
+            // Debug output.
+            info!("junit_xml = {}", self.configuration.junit_xml);
         }
 
         Ok(())
     }
 
-    // Determine if the `--no-metrics` flag is enabled.
-    fn set_no_metrics(&mut self) -> Result<(), SwanlingError> {
+    // Configure the JSON report, if enabled.
+    fn set_report_json(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_metrics";
-        let mut value = false;
+        let mut key = "--report-json";
 
-        if self.configuration.no_metrics {
-            key = "--no-metrics";
-            value = true;
         // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
+        if self.configuration.report_json.is_empty() && self.attack_mode != AttackMode::Worker {
             // Optionally set default.
-            if let Some(default_no_metrics) = self.defaults.no_metrics {
-                key = "set_default(SwanlingDefault::NoMetrics)";
-                value = default_no_metrics;
+            if let Some(default_report_json) = &self.defaults.report_json {
+                key = "set_default(SwanlingDefault::ReportJson)";
 
-                self.configuration.no_metrics = default_no_metrics;
+                self.configuration.report_json = default_report_json.to_string();
             }
         }
 
-        // Setting --no-metrics with --worker is not allowed.
-        if self.configuration.no_metrics && self.attack_mode == AttackMode::Worker {
-            return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
-            });
-        }
-
-        // Don't allow overhead of collecting metrics unless we're printing them.
-        if self.configuration.no_metrics {
-            if self.configuration.status_codes {
+        if !self.configuration.report_json.is_empty() {
+            // In Gaggles, the Manager writes the report from the merged metrics.
+            if self.attack_mode == AttackMode::Worker {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!(
-                        "{} can not be set together with the --status-codes flag.",
-                        key
-                    ),
+                    value: self.configuration.report_json.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
                 });
             }
 
-            // Don't allow overhead of collecting metrics unless we're printing them.
-            if self.configuration.running_metrics.is_some() {
+            // There's no report without metrics.
+            if self.configuration.no_metrics {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
+                    value: self.configuration.report_json.to_string(),
                     detail: format!(
-                        "{} can not be set together with the --running_metrics option.",
+                        "{} can not be set together with the --no-metrics flag.",
                         key
                     ),
                 });
             }
 
-            // There is nothing to log if metrics are disabled.
-            if !self.configuration.request_log.is_empty() {
+            // A smoke test reports its own results instead of metrics.
+            if self.configuration.smoke {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!(
-                        "{} can not be set together with the --requests-file option.",
-                        key
-                    ),
+                    value: self.configuration.report_json.to_string(),
+                    detail: format!("{} can not be set together with the --smoke flag.", key),
                 });
             }
+
+            // Debug output.
+            info!("report_json = {}", self.configuration.report_json);
         }
 
         Ok(())
     }
 
-    // Determine if the `--sticky-follow` flag is enabled.
-    fn set_sticky_follow(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.sticky_follow";
-        let mut value = false;
-
-        if self.configuration.sticky_follow {
-            key = "--sticky-follow";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_sticky_follow) = self.defaults.sticky_follow {
-                key = "set_default(SwanlingDefault::StickyFollow)";
-                value = default_sticky_follow;
-
-                self.configuration.sticky_follow = default_sticky_follow;
-            }
+    // Write the final metrics as a JSON report, if enabled. The report can be used as a
+    // `--baseline` for later load tests.
+    fn write_report_json(&self) -> Result<(), SwanlingError> {
+        if self.configuration.report_json.is_empty() {
+            return Ok(());
         }
 
-        if self.configuration.sticky_follow && self.attack_mode == AttackMode::Worker {
+        let json = serde_json::to_string_pretty(&self.metrics).map_err(|e| {
+            SwanlingError::InvalidOption {
+                option: "--report-json".to_string(),
+                value: self.configuration.report_json.to_string(),
+                detail: format!("Failed to serialize JSON report: {}", e),
+            }
+        })?;
+        if let Err(e) = std::fs::write(&self.configuration.report_json, json) {
             return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                option: "--report-json".to_string(),
+                value: self.configuration.report_json.to_string(),
+                detail: format!("Failed to write JSON report: {}", e),
             });
         }
 
+        info!("wrote JSON report to: {}", self.configuration.report_json);
+
         Ok(())
     }
 
-    #[cfg(feature = "gaggle")]
-    // Determine if `--no-hash-check` flag is enabled.
-    fn set_no_hash_check(&mut self) -> Result<(), SwanlingError> {
+    // Configure the folded stack task profile, if enabled.
+    fn set_task_profile(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_hash_check";
-        let mut value = false;
+        let mut key = "--task-profile";
 
-        if self.configuration.no_hash_check {
-            key = "--no-hash-check";
-            value = true;
         // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
+        if self.configuration.task_profile.is_empty() && self.attack_mode != AttackMode::Worker {
             // Optionally set default.
-            if let Some(default_no_hash_check) = self.defaults.no_hash_check {
-                key = "set_default(SwanlingDefault::NoHashCheck)";
-                value = default_no_hash_check;
+            if let Some(default_task_profile) = &self.defaults.task_profile {
+                key = "set_default(SwanlingDefault::TaskProfile)";
 
-                self.configuration.no_hash_check = default_no_hash_check;
+                self.configuration.task_profile = default_task_profile.to_string();
             }
         }
 
-        if self.configuration.no_hash_check && self.attack_mode == AttackMode::Worker {
+        if !self.configuration.task_profile.is_empty() {
+            // In Gaggles, the Manager writes the profile from the merged metrics.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.task_profile.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // The profile is built from the task metrics.
+            if self.configuration.no_metrics || self.configuration.no_task_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.task_profile.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics or --no-task-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Debug output.
+            info!("task_profile = {}", self.configuration.task_profile);
+        }
+
+        Ok(())
+    }
+
+    // Write where the task sets spent their time as a folded stack profile, if enabled.
+    fn write_task_profile(&self) -> Result<(), SwanlingError> {
+        if self.configuration.task_profile.is_empty() || self.attack_mode == AttackMode::Worker {
+            return Ok(());
+        }
+
+        if let Err(e) = std::fs::write(
+            &self.configuration.task_profile,
+            self.metrics.folded_task_profile(),
+        ) {
             return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                option: "--task-profile".to_string(),
+                value: self.configuration.task_profile.to_string(),
+                detail: format!("Failed to write task profile: {}", e),
             });
         }
 
+        info!("wrote task profile to: {}", self.configuration.task_profile);
+
         Ok(())
     }
 
-    // If enabled, returns the path of the report_file, otherwise returns None.
-    fn get_report_file_path(&mut self) -> Option<String> {
-        // If metrics are disabled, or running in Manager mode, there is no
-        // report file, exit immediately.
-        if self.configuration.no_metrics || self.attack_mode == AttackMode::Manager {
-            return None;
+    // Write the final metrics as a JUnit XML report, if enabled.
+    fn write_junit_xml(&self) -> Result<(), SwanlingError> {
+        if self.configuration.junit_xml.is_empty() {
+            return Ok(());
         }
 
-        // If --report-file is set, return it.
-        if !self.configuration.report_file.is_empty() {
-            return Some(self.configuration.report_file.to_string());
+        let xml = report::build_junit_xml(
+            "swanling",
+            self.metrics.duration,
+            &self.metrics.junit_test_cases(),
+        );
+        if let Err(e) = std::fs::write(&self.configuration.junit_xml, xml) {
+            return Err(SwanlingError::InvalidOption {
+                option: "--junit-xml".to_string(),
+                value: self.configuration.junit_xml.to_string(),
+                detail: format!("Failed to write JUnit XML report: {}", e),
+            });
         }
 
-        // If SwanlingDefault::ReportFile is set, return it.
-        if let Some(default_report_file) = &self.defaults.report_file {
-            return Some(default_report_file.to_string());
-        }
+        info!(
+            "wrote JUnit XML report to: {}",
+            self.configuration.junit_xml
+        );
 
-        // Otherwise there is no report file.
-        None
+        Ok(())
     }
 
     // Configure requests log format.
@@ -2319,14 +6404,95 @@ impl SwanlingAttack {
                     detail: "The --no-metrics flag can not be set together with the --requests-format option.".to_string(),
                 });
             }
-            // Log format isn't relevant if log not enabled.
-            else if self.configuration.request_log.is_empty() {
+            // Log format isn't relevant if log not enabled.
+            else if self.configuration.request_log.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", value),
+                    detail: "The --requests-file option must be set together with the --requests-format option.".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure the fraction of requests written to the request log.
+    fn set_request_log_sample(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "--request-log-sample";
+
+        // In Gaggles, the request log is only written by Workers.
+        if self.configuration.request_log_sample.is_none()
+            && self.attack_mode != AttackMode::Manager
+        {
+            if let Some(default_request_log_sample) = self.defaults.request_log_sample.as_ref() {
+                key = "set_default(SwanlingDefault::RequestLogSample)";
+                match default_request_log_sample.parse::<f32>() {
+                    Ok(rate) => self.configuration.request_log_sample = Some(rate),
+                    Err(e) => {
+                        return Err(SwanlingError::InvalidOption {
+                            option: key.to_string(),
+                            value: default_request_log_sample.to_string(),
+                            detail: format!("{} must be set to a number: {}", key, e),
+                        });
+                    }
+                }
+            }
+        }
+        if self.configuration.request_log_seed.is_none() && self.attack_mode != AttackMode::Manager
+        {
+            self.configuration.request_log_seed = self.defaults.request_log_seed;
+        }
+
+        if let Some(rate) = self.configuration.request_log_sample {
+            // The request log is written by Workers.
+            if self.attack_mode == AttackMode::Manager {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: rate.to_string(),
+                    detail: format!("{} can not be set together with the --manager flag.", key),
+                });
+            }
+
+            // Only requests written to the request log are sampled.
+            if self.configuration.request_log.is_empty() && self.defaults.request_log.is_none() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: rate.to_string(),
+                    detail: format!(
+                        "{} can not be set without also setting the --request-log option.",
+                        key
+                    ),
+                });
+            }
+
+            // The rate is a fraction of all requests.
+            if !(0.0..=1.0).contains(&rate) {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: "The --requests-file option must be set together with the --requests-format option.".to_string(),
+                    value: rate.to_string(),
+                    detail: format!("{} must be set to a value from 0.0 to 1.0.", key),
+                });
+            }
+
+            info!("request_log_sample = {}", rate);
+        }
+
+        // The seed only affects sampling.
+        if let Some(seed) = self.configuration.request_log_seed {
+            if self.configuration.request_log_sample.is_none() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--request-log-seed".to_string(),
+                    value: seed.to_string(),
+                    detail: format!(
+                        "--request-log-seed can not be set without also setting {}.",
+                        key
+                    ),
                 });
             }
+
+            info!("request_log_seed = {}", seed);
         }
 
         Ok(())
@@ -2468,10 +6634,34 @@ impl SwanlingAttack {
         Ok(())
     }
 
-    // Configure whether or not to enable the telnet Controller. Always disable when in Regatta mode.
+    // Determine if a smoke test should run instead of a load test.
+    fn set_smoke(&mut self) -> Result<(), SwanlingError> {
+        if !self.configuration.smoke {
+            return Ok(());
+        }
+
+        // A smoke test runs in a single process.
+        let key = "--smoke";
+        if self.attack_mode == AttackMode::Manager || self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: true.to_string(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flag.",
+                    key
+                ),
+            });
+        }
+
+        info!("smoke = {}", self.configuration.smoke);
+
+        Ok(())
+    }
+
+    // Configure whether or not to enable the telnet Controller. Always disable on Workers.
     fn set_no_telnet(&mut self) {
-        // Currently Gaggles are not Controller-aware, force disable.
-        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+        // Workers are controlled through the Manager, force disable.
+        if self.attack_mode == AttackMode::Worker {
             self.configuration.no_telnet = true;
         // Otherwise, if --no-telnet flag not set, respect default if configured.
         } else if !self.configuration.no_telnet {
@@ -2481,10 +6671,10 @@ impl SwanlingAttack {
         }
     }
 
-    // Configure whether or not to enable the WebSocket Controller. Always disable when in Regatta mode.
+    // Configure whether or not to enable the WebSocket Controller. Always disable on Workers.
     fn set_no_websocket(&mut self) {
-        // Currently Gaggles are not Controller-aware, force disable.
-        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+        // Workers are controlled through the Manager, force disable.
+        if self.attack_mode == AttackMode::Worker {
             self.configuration.no_websocket = true;
         // Otherwise, if --no-websocket flag not set, respect default if configured.
         } else if !self.configuration.no_websocket {
@@ -2569,14 +6759,84 @@ impl SwanlingAttack {
         Ok(())
     }
 
+    // Configure whether to capture the response body of failed requests.
+    fn set_capture_failure_bodies(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.capture_failure_bodies";
+        let mut value = false;
+
+        if self.configuration.capture_failure_bodies {
+            key = "--capture-failure-bodies";
+            value = true;
+        // If not otherwise set and not Manager, check if there's a default.
+        } else if self.attack_mode != AttackMode::Manager {
+            // Optionally set default.
+            if let Some(default_capture_failure_bodies) = self.defaults.capture_failure_bodies {
+                key = "set_default(SwanlingDefault::CaptureFailureBodies)";
+                value = default_capture_failure_bodies;
+
+                self.configuration.capture_failure_bodies = default_capture_failure_bodies;
+            }
+        }
+
+        if self.configuration.capture_failure_bodies {
+            // The debug log is written by Workers.
+            if self.attack_mode == AttackMode::Manager {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --manager flag.", key),
+                });
+            }
+
+            // Bodies are captured in the debug log.
+            if self.configuration.debug_log.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set without also setting the --debug-log option.",
+                        key
+                    ),
+                });
+            }
+
+            // There would be no body to capture.
+            if self.configuration.no_debug_body {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-debug-body flag.",
+                        key
+                    ),
+                });
+            }
+
+            info!("capture_failure_bodies = {}", value);
+        }
+
+        Ok(())
+    }
+
     /// Execute the [`SwanlingAttack`](./struct.SwanlingAttack.html) load test.
     ///
+    /// Returns the final, fully aggregated
+    /// [`SwanlingMetrics`](./metrics/struct.SwanlingMetrics.html). The final metrics are
+    /// not printed, making it possible to embed Swanling in a larger program or test
+    /// suite and inspect the `requests`, `tasks` and `errors` programmatically. Call
+    /// [`print()`](./metrics/struct.SwanlingMetrics.html#method.print) to display them.
+    ///
+    /// If a service level agreement configured with `--sla` is breached,
+    /// [`SwanlingError::SlaBreach`](./enum.SwanlingError.html#variant.SlaBreach) is returned
+    /// instead, also carrying the final metrics.
+    ///
     /// # Example
     /// ```rust
     /// use swanling::prelude::*;
     ///
     /// fn main() -> Result<(), SwanlingError> {
-    ///     let _swanling_metrics = SwanlingAttack::initialize()?
+    ///     let swanling_metrics = SwanlingAttack::initialize()?
     ///         .register_taskset(taskset!("ExampleTasks")
     ///             .register_task(task!(example_task).set_weight(2)?)
     ///             .register_task(task!(another_example_task).set_weight(3)?)
@@ -2587,6 +6847,14 @@ impl SwanlingAttack {
     ///         .set_default(SwanlingDefault::RunTime, 1)?
     ///         .execute()?;
     ///
+    ///     // Inspect the final metrics, for example to count failed requests.
+    ///     let failed_requests: usize = swanling_metrics
+    ///         .requests
+    ///         .values()
+    ///         .map(|request| request.fail_count)
+    ///         .sum();
+    ///     println!("{} requests failed", failed_requests);
+    ///
     ///     Ok(())
     /// }
     ///
@@ -2609,33 +6877,177 @@ impl SwanlingAttack {
             std::process::exit(0);
         }
 
+        // Configure loggers.
+        self.configuration.configure_loggers(&self.defaults);
+
+        // Initialize logger.
+        self.initialize_logger();
+
+        // Replay requests from a HAR file, if configured.
+        let har_problem = self.set_har().err();
+
         // At least one task set is required.
         if self.task_sets.is_empty() {
-            return Err(SwanlingError::NoTaskSets {
+            return Err(har_problem.unwrap_or(SwanlingError::NoTaskSets {
                 detail: "No task sets are defined.".to_string(),
-            });
+            }));
         }
 
         // Display task sets and tasks, then exit.
         if self.configuration.list {
-            println!("Available tasks:");
-            for task_set in self.task_sets {
-                println!(" - {} (weight: {})", task_set.name, task_set.weight);
-                for task in task_set.tasks {
-                    println!("    o {} (weight: {})", task.name, task.weight);
-                }
-            }
+            print!("{}", task_list(&self.task_sets));
             std::process::exit(0);
         }
 
-        // Configure loggers.
-        self.configuration.configure_loggers(&self.defaults);
+        // Configure all run-time options and defaults.
+        let problems: Vec<SwanlingError> =
+            har_problem.into_iter().chain(self.configure()).collect();
 
-        // Initialize logger.
-        self.initialize_logger();
+        // Report any problems with the configuration, then exit without starting the load
+        // test.
+        if self.configuration.validate_config {
+            return self.report_configuration(problems);
+        }
+
+        // Otherwise stop at the first problem.
+        if let Some(problem) = problems.into_iter().next() {
+            return Err(problem);
+        }
+
+        // Calculate a unique hash for the current load test.
+        self.metrics.hash = self.load_test_hash();
+        debug!("hash: {}", self.metrics.hash);
+
+        // Refuse to start if the target doesn't pass the health check.
+        if !self.configuration.health_check_url.is_empty() {
+            let rt = Runtime::new().unwrap();
+            rt.block_on(self.run_health_check())?;
+        }
+
+        // Run each task once and exit, instead of starting the load test.
+        if self.configuration.smoke {
+            let rt = Runtime::new().unwrap();
+            return rt.block_on(self.run_smoke_test());
+        }
+
+        // Start swanling in manager mode.
+        if self.attack_mode == AttackMode::Manager {
+            #[cfg(feature = "gaggle")]
+            {
+                let rt = Runtime::new().unwrap();
+                self = rt.block_on(manager::manager_main(self));
+            }
+
+            #[cfg(not(feature = "gaggle"))]
+            {
+                return Err(SwanlingError::FeatureNotEnabled {
+                    feature: "gaggle".to_string(), detail: "Load test must be recompiled with `--features gaggle` to start in manager mode.".to_string()
+                });
+            }
+        }
+        // Start swanling in worker mode.
+        else if self.attack_mode == AttackMode::Worker {
+            #[cfg(feature = "gaggle")]
+            {
+                let rt = Runtime::new().unwrap();
+                self = rt.block_on(worker::worker_main(&self));
+            }
+
+            #[cfg(not(feature = "gaggle"))]
+            {
+                return Err(SwanlingError::FeatureNotEnabled {
+                    feature: "gaggle".to_string(),
+                    detail: "Load test must be recompiled with `--features gaggle` to start in worker mode.".to_string(),
+                });
+            }
+        }
+        // Start swanling in single-process mode.
+        else {
+            self.check_open_file_limit(
+                self.configuration.users.unwrap_or(0),
+                self.configuration.pool_size,
+            );
+            let rt = Runtime::new().unwrap();
+            self = rt.block_on(self.start_attack(None))?;
+        }
+
+        // Compare the final metrics with the baseline, the result is included in the metrics
+        // even if service level agreements are also breached.
+        let mut passed_baseline = true;
+        if !self.configuration.baseline.is_empty() && self.attack_mode != AttackMode::Worker {
+            let baseline = std::fs::read_to_string(&self.configuration.baseline)?;
+            let bounds = SwanlingRegressionBounds::new(
+                self.configuration
+                    .regression_threshold
+                    .unwrap_or(DEFAULT_REGRESSION_THRESHOLD) as f32,
+            );
+            passed_baseline = self
+                .metrics
+                .check_baseline(&baseline, &bounds)
+                .map_err(|e| SwanlingError::InvalidOption {
+                    option: "--baseline".to_string(),
+                    value: self.configuration.baseline.clone(),
+                    detail: format!("failed to parse baseline: {}", e),
+                })?;
+        }
+
+        // Check service level agreements against the final metrics.
+        let passed_slas = self.configuration.sla.is_empty()
+            || self.attack_mode == AttackMode::Worker
+            || self.metrics.check_slas(&self.configuration.sla);
+
+        // Write the JUnit XML and JSON reports, if enabled, before failing for any breached
+        // agreements.
+        self.write_junit_xml()?;
+        self.write_report_json()?;
+        self.write_task_profile()?;
+
+        if !passed_slas {
+            let breached: Vec<String> = self
+                .metrics
+                .slas
+                .iter()
+                .filter(|sla| !sla.passed)
+                .map(|sla| format!("{}: {}", sla.name, sla.threshold))
+                .collect();
+            return Err(SwanlingError::SlaBreach {
+                detail: format!("Breached: {}.", breached.join(", ")),
+                breached,
+                metrics: Box::new(self.metrics),
+            });
+        }
+
+        // Fail if the final metrics regressed from the baseline.
+        if !passed_baseline {
+            let regressed = self
+                .metrics
+                .comparison
+                .as_ref()
+                .map(SwanlingComparison::regressions)
+                .unwrap_or_default();
+            return Err(SwanlingError::Regression {
+                detail: format!("Regressed: {}.", regressed.join(", ")),
+                regressed,
+                metrics: Box::new(self.metrics),
+            });
+        }
+
+        Ok(self.metrics)
+    }
+
+    // Configure all run-time options and defaults, returning every problem found so they can
+    // be reported together. Configuring stops at the first problem with the attack mode or
+    // users, as other options depend on them.
+    fn configure(&mut self) -> Vec<SwanlingError> {
+        let mut problems = Vec::new();
 
         // Configure run mode (StandAlone, Worker, Manager).
-        self.set_attack_mode()?;
+        if let Err(e) = self.set_attack_mode() {
+            return vec![e];
+        }
+
+        // Determine whether or not to run a smoke test.
+        problems.extend(self.set_smoke().err());
 
         // Determine whether or not to enable the telnet Controller.
         self.set_no_telnet();
@@ -2644,68 +7056,237 @@ impl SwanlingAttack {
         self.set_no_websocket();
 
         // Determine whether or not to autostart load test.
-        self.set_no_autostart()?;
+        problems.extend(self.set_no_autostart().err());
 
         // Configure number of users to simulate.
-        self.set_users()?;
+        if let Err(e) = self.set_users() {
+            problems.push(e);
+            return problems;
+        }
 
         // Configure expect_workers if running in Manager attack mode.
-        self.set_expect_workers()?;
+        problems.extend(self.set_expect_workers().err());
 
         // Configure host and ports if running in a Regatta distributed load test.
-        self.set_gaggle_host_and_port()?;
+        problems.extend(self.set_gaggle_host_and_port().err());
+
+        // Configure the Unix domain socket the Manager and Workers communicate over.
+        problems.extend(self.set_gaggle_ipc().err());
+
+        // Configure how the Manager and Workers communicate, must happen after gaggle_ipc
+        // is configured.
+        problems.extend(self.set_gaggle_transport().err());
+
+        // Configure how often Workers push metrics to the Manager.
+        problems.extend(self.set_worker_metrics_interval().err());
+        problems.extend(self.set_worker_metrics_buffer().err());
+
+        // Configure the sockets the Manager and Workers communicate over.
+        problems.extend(self.set_gaggle_send_buffer().err());
+        problems.extend(self.set_gaggle_recv_timeout().err());
+        problems.extend(self.set_gaggle_max_msg_size().err());
+
+        // Configure how many sockets the Manager shards Workers across.
+        problems.extend(self.set_manager_sockets().err());
+
+        // Configure how long Workers randomly delay connecting to the Manager.
+        problems.extend(self.set_worker_startup_jitter().err());
 
         // Configure how long to run.
-        self.set_run_time()?;
+        problems.extend(self.set_run_time().err());
+
+        // Configure how long to spend stopping users.
+        problems.extend(self.set_stop_time().err());
+
+        // Configure how long to cool down after stopping users.
+        problems.extend(self.set_cooldown().err());
 
         // Configure how many users to hatch per second.
-        self.set_hatch_rate()?;
+        problems.extend(self.set_hatch_rate().err());
 
         // Configure the requests log format.
-        self.set_request_format()?;
+        problems.extend(self.set_request_format().err());
+
+        // Configure the fraction of requests written to the request log.
+        problems.extend(self.set_request_log_sample().err());
 
         // Configure the tasks log format.
-        self.set_task_format()?;
+        problems.extend(self.set_task_format().err());
 
         // Configure the tasks log format.
-        self.set_error_format()?;
+        problems.extend(self.set_error_format().err());
 
         // Configure the debug log format.
-        self.set_debug_format()?;
+        problems.extend(self.set_debug_format().err());
 
         // Determine whether or not to log response body.
-        self.set_no_debug_body()?;
+        problems.extend(self.set_no_debug_body().err());
+
+        // Determine whether or not to capture the response body of failed requests.
+        problems.extend(self.set_capture_failure_bodies().err());
 
         // Configure coordinated ommission mitigation strategy.
-        self.set_coordinated_omission()?;
+        problems.extend(self.set_coordinated_omission().err());
 
         // Configure throttle if enabled.
-        self.set_throttle_requests()?;
+        problems.extend(self.set_throttle_requests().err());
+
+        // Configure maximum concurrent connections if enabled.
+        problems.extend(self.set_max_connections().err());
+
+        // Configure how many idle connections each user keeps open.
+        problems.extend(self.set_pool_size().err());
+        problems.extend(self.set_prewarm_connections().err());
+
+        // Configure how long idle connections are kept open and probed.
+        problems.extend(self.set_pool_idle_timeout().err());
+        problems.extend(self.set_keepalive_idle_timeout().err());
 
         // Configure status_codes flag.
-        self.set_status_codes()?;
+        problems.extend(self.set_status_codes().err());
 
         // Configure running_metrics flag.
-        self.set_running_metrics()?;
+        problems.extend(self.set_running_metrics().err());
 
         // Configure no_reset_metrics flag.
-        self.set_no_reset_metrics()?;
+        problems.extend(self.set_no_reset_metrics().err());
 
         // Configure no_task_metrics flag.
-        self.set_no_task_metrics()?;
+        problems.extend(self.set_no_task_metrics().err());
+
+        // Configure exclude_think_time flag.
+        problems.extend(self.set_exclude_think_time().err());
 
         // Configure no_error_summary flag.
-        self.set_no_error_summary()?;
+        problems.extend(self.set_no_error_summary().err());
+
+        // Configure soak flag.
+        problems.extend(self.set_soak().err());
 
         // Configure no_metrics flag.
-        self.set_no_metrics()?;
+        problems.extend(self.set_no_metrics().err());
+
+        // Configure service level agreements, must happen after no_metrics is configured.
+        problems.extend(self.set_sla().err());
+
+        // Configure maximum number of requests, must happen after no_metrics is configured.
+        problems.extend(self.set_max_requests().err());
+
+        // Configure how many iterations each user runs.
+        problems.extend(self.set_iterations_per_user().err());
+
+        // Configure when users retire after failing, must happen after no_metrics is
+        // configured.
+        problems.extend(self.set_user_max_consecutive_failures().err());
+
+        // Configure the adaptive throttle, must happen after throttle_requests and no_metrics
+        // are configured.
+        problems.extend(self.set_adaptive_target_p95().err());
+
+        // Configure the slow request threshold, must happen after no_metrics is configured.
+        problems.extend(self.set_slow_threshold().err());
+
+        // Configure how far above average a request must be to be reported as an outlier.
+        problems.extend(self.set_outlier_factor().err());
+
+        // Configure the minimum number of samples to report percentiles.
+        problems.extend(self.set_min_samples().err());
+
+        // Configure how many recent errors are displayed, must happen after no_metrics is
+        // configured.
+        problems.extend(self.set_error_tail().err());
+
+        // Configure how many request names are tracked, must happen after no_metrics is
+        // configured.
+        problems.extend(self.set_max_request_names().err());
+
+        // Configure the label to group request metrics by, must happen after no_metrics is
+        // configured.
+        problems.extend(self.set_group_by().err());
+        problems.extend(self.set_region_header().err());
+
+        // Configure the baseline to compare with, must happen after no_metrics is configured.
+        problems.extend(self.set_baseline().err());
+
+        // Configure the regression threshold, must happen after the baseline is configured.
+        problems.extend(self.set_regression_threshold().err());
+
+        // Configure latency chart output, must happen after no_metrics is configured.
+        problems.extend(self.set_chart_output().err());
+
+        // Configure the JUnit XML report, must happen after no_metrics is configured.
+        problems.extend(self.set_junit_xml().err());
+
+        // Configure the JSON report, must happen after no_metrics is configured.
+        problems.extend(self.set_report_json().err());
+        problems.extend(self.set_task_profile().err());
+
+        // Configure spikes of additional users.
+        problems.extend(self.set_spike().err());
+
+        // Configure ramping to failure, must happen after no_metrics and spike are configured.
+        problems.extend(self.set_ramp_to_failure().err());
+
+        // Configure the load profile, must happen after run_time, spike and ramp_to_failure
+        // are configured.
+        problems.extend(self.set_load_profile().err());
 
         // Configure sticky_follow flag.
-        self.set_sticky_follow()?;
+        problems.extend(self.set_sticky_follow().err());
+
+        // Configure the redirect limit, must happen after sticky_follow is configured.
+        problems.extend(self.set_max_redirects().err());
+
+        // Configure client_cert, client_key and ca_cert.
+        problems.extend(self.set_client_tls().err());
+
+        // Configure insecure flag.
+        problems.extend(self.set_insecure().err());
+
+        // Configure host name to IP address mappings.
+        problems.extend(self.set_resolve().err());
+
+        // Configure the path prefixed to all relative request paths.
+        problems.extend(self.set_base_path().err());
+
+        // Configure the health check run before the load test starts.
+        problems.extend(self.set_health_check().err());
+
+        // Configure oauth_token_url, oauth_client_id and oauth_client_secret.
+        problems.extend(self.set_oauth().err());
+
+        // Configure aws_region, aws_service and the credentials requests are signed with.
+        problems.extend(self.set_aws_sigv4().err());
+
+        // Configure the weighted list of User-Agents.
+        problems.extend(self.set_user_agents().err());
+
+        // Configure the file user sessions are saved to and reloaded from.
+        problems.extend(self.set_session_file().err());
+
+        // Expand environment variables referenced in the host.
+        problems.extend(self.set_host().err());
 
         // Configure no_hash_check flag.
         #[cfg(feature = "gaggle")]
-        self.set_no_hash_check()?;
+        problems.extend(self.set_no_hash_check().err());
+
+        // Configure how long the Manager aggregates metrics in each window.
+        problems.extend(self.set_aggregate_window().err());
+
+        // Configure when the Manager quarantines Workers with too many errors.
+        problems.extend(self.set_quarantine_error_threshold().err());
+
+        // Configure which Workers task sets are pinned to, must happen after expect_workers
+        // is configured.
+        problems.extend(self.set_pin_task_set().err());
+
+        // Configure InfluxDB line protocol output.
+        problems.extend(self.set_influx_file().err());
+        problems.extend(self.set_influx_udp().err());
+
+        // Confirm task transitions lead to tasks that exist.
+        problems.extend(self.validate_transitions().err());
 
         // Confirm there's either a global host, or each task set has a host defined.
         if let Err(e) = self.validate_host() {
@@ -2713,57 +7294,36 @@ impl SwanlingAttack {
                 info!("host must be configured via Controller before starting load test");
             } else {
                 // If auto-starting, host must be valid.
-                return Err(e);
+                problems.push(e);
             }
-        } else {
+        } else if problems.is_empty() {
             info!("global host configured: {}", self.configuration.host);
-            self.prepare_load_test()?;
+            problems.extend(self.prepare_load_test().err());
         }
 
-        // Calculate a unique hash for the current load test.
-        let mut s = DefaultHasher::new();
-        self.task_sets.hash(&mut s);
-        self.metrics.hash = s.finish();
-        debug!("hash: {}", self.metrics.hash);
-
-        // Start swanling in manager mode.
-        if self.attack_mode == AttackMode::Manager {
-            #[cfg(feature = "gaggle")]
-            {
-                let rt = Runtime::new().unwrap();
-                self = rt.block_on(manager::manager_main(self));
-            }
+        problems
+    }
 
-            #[cfg(not(feature = "gaggle"))]
-            {
-                return Err(SwanlingError::FeatureNotEnabled {
-                    feature: "gaggle".to_string(), detail: "Load test must be recompiled with `--features gaggle` to start in manager mode.".to_string()
-                });
-            }
+    // Display the result of validating the configuration with `--validate-config`, returning
+    // an error if there were any problems.
+    fn report_configuration(
+        self,
+        problems: Vec<SwanlingError>,
+    ) -> Result<SwanlingMetrics, SwanlingError> {
+        if problems.is_empty() {
+            println!("configuration is valid");
+            return Ok(self.metrics);
         }
-        // Start swanling in worker mode.
-        else if self.attack_mode == AttackMode::Worker {
-            #[cfg(feature = "gaggle")]
-            {
-                let rt = Runtime::new().unwrap();
-                self = rt.block_on(worker::worker_main(&self));
-            }
 
-            #[cfg(not(feature = "gaggle"))]
-            {
-                return Err(SwanlingError::FeatureNotEnabled {
-                    feature: "gaggle".to_string(),
-                    detail: "Load test must be recompiled with `--features gaggle` to start in worker mode.".to_string(),
-                });
-            }
+        let problems: Vec<String> = problems.iter().map(describe_problem).collect();
+        println!("configuration has {} problem(s):", problems.len());
+        for problem in &problems {
+            println!(" - {}", problem);
         }
-        // Start swanling in single-process mode.
-        else {
-            let rt = Runtime::new().unwrap();
-            self = rt.block_on(self.start_attack(None))?;
-        }
-
-        Ok(self.metrics)
+        Err(SwanlingError::InvalidConfiguration {
+            detail: problems.join(" "),
+            problems,
+        })
     }
 
     // Returns OK(()) if there's a valid host, SwanlingError with details if not.
@@ -2798,6 +7358,32 @@ impl SwanlingAttack {
         Ok(())
     }
 
+    // Returns Ok(()) if every task transition leads to a normal task in the same task set,
+    // SwanlingError with details if not.
+    fn validate_transitions(&self) -> Result<(), SwanlingError> {
+        for task_set in &self.task_sets {
+            for task in &task_set.tasks {
+                for to in task.transitions.iter().filter_map(|(to, _)| to.as_ref()) {
+                    if !task_set
+                        .tasks
+                        .iter()
+                        .any(|t| &t.name == to && !t.on_start && !t.on_stop)
+                    {
+                        return Err(SwanlingError::InvalidOption {
+                            option: "SwanlingTask::set_transition".to_string(),
+                            value: to.to_string(),
+                            detail: format!(
+                                "Task {} in {} transitions to {}, which isn't a normal task in the same task set.",
+                                task.name, task_set.name, to
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     // Create and schedule SwanlingUsers. This requires that the host that will be load tested
     // has been configured.
     fn prepare_load_test(&mut self) -> Result<(), SwanlingError> {
@@ -2826,6 +7412,12 @@ impl SwanlingAttack {
             // Stand-alone and Manager processes can display metrics.
             if !self.configuration.no_metrics {
                 self.metrics.display_metrics = true;
+                self.metrics.slow_threshold = self.configuration.slow_threshold;
+                self.metrics.outlier_factor = self.configuration.outlier_factor;
+                self.metrics.min_samples = self.configuration.min_samples;
+                if !self.configuration.group_by.is_empty() {
+                    self.metrics.group_by = Some(self.configuration.group_by.to_string());
+                }
             }
 
             if self.attack_mode == AttackMode::StandAlone {
@@ -2859,10 +7451,12 @@ impl SwanlingAttack {
         Option<flume::Sender<bool>>,
         // A channel used by parent to tell throttle the load test is complete.
         Option<flume::Sender<bool>>,
+        // The requests per second allowed by the throttle, which can be changed while it runs.
+        Option<Arc<AtomicUsize>>,
     ) {
         // If the throttle isn't enabled, return immediately.
         if self.configuration.throttle_requests == 0 {
-            return (None, None);
+            return (None, None, None);
         }
 
         // Create a bounded channel allowing single-sender multi-receiver to throttle
@@ -2877,9 +7471,11 @@ impl SwanlingAttack {
         // oneshot channel as we don't want to block waiting for a message.
         let (parent_to_throttle_tx, throttle_rx) = flume::bounded(1);
 
+        let throttle_rate = Arc::new(AtomicUsize::new(self.configuration.throttle_requests));
+
         // Launch a new thread for throttling, no need to rejoin it.
         let _ = Some(tokio::spawn(throttle::throttle_main(
-            self.configuration.throttle_requests,
+            throttle_rate.clone(),
             throttle_receiver,
             throttle_rx,
         )));
@@ -2895,7 +7491,91 @@ impl SwanlingAttack {
             let _ = sender.send_async(true).await;
         }
 
-        (Some(all_threads_throttle), Some(parent_to_throttle_tx))
+        (
+            Some(all_threads_throttle),
+            Some(parent_to_throttle_tx),
+            Some(throttle_rate),
+        )
+    }
+
+    // Apply changes to the configuration of a running load test, returning a description
+    // of each change. Used by the Controller, and by Workers when the Manager sends
+    // [`SwanlingUserCommand::Reconfigure`](./swanling/enum.SwanlingUserCommand.html#variant.Reconfigure).
+    fn reconfigure(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+        reconfiguration: &SwanlingReconfiguration,
+    ) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if let Some(throttle_requests) = reconfiguration.throttle_requests {
+            // The throttle can only be adjusted, not enabled, while the load test runs.
+            if let Some(throttle_rate) = swanling_attack_run_state.throttle_rate.as_ref() {
+                if throttle_requests > 0 && throttle_requests <= 1_000_000 {
+                    changes.push(format!(
+                        "throttle_requests changed from {} to {}",
+                        self.configuration.throttle_requests, throttle_requests
+                    ));
+                    throttle_rate.store(throttle_requests, Ordering::SeqCst);
+                    self.configuration.throttle_requests = throttle_requests;
+                    self.metrics.throttle_requests = throttle_requests;
+                } else {
+                    changes.push(format!(
+                        "throttle_requests not changed, {} is not between 1 and 1,000,000",
+                        throttle_requests
+                    ));
+                }
+            } else {
+                changes
+                    .push("throttle_requests not changed, the throttle is not enabled".to_string());
+            }
+        }
+
+        if let Some(paused) = reconfiguration.paused {
+            if paused != swanling_attack_run_state.paused {
+                swanling_attack_run_state.paused = paused;
+                let command = if paused {
+                    SwanlingUserCommand::Wait
+                } else {
+                    SwanlingUserCommand::Run
+                };
+                for send_to_user in swanling_attack_run_state
+                    .user_channels
+                    .iter()
+                    .chain(swanling_attack_run_state.spike_channels.iter())
+                {
+                    let _ = send_to_user.send(command.clone());
+                }
+                changes.push(if paused {
+                    "users paused".to_string()
+                } else {
+                    "users resumed".to_string()
+                });
+            }
+        }
+
+        if let Some(users) = reconfiguration.users {
+            // Each Worker runs its share of the users, in proportion to the users the
+            // Manager allocated it.
+            let users = match self.configuration.users {
+                Some(configured) if self.attack_mode == AttackMode::Worker && configured > 0 => {
+                    (users * self.weighted_users.len() + configured / 2) / configured
+                }
+                _ => users,
+            };
+            let running = swanling_attack_run_state
+                .target_users
+                .unwrap_or(swanling_attack_run_state.user_channels.len());
+            if users != running {
+                changes.push(format!("users changed from {} to {}", running, users));
+                swanling_attack_run_state.target_users = Some(users);
+            }
+        }
+
+        for change in &changes {
+            info!("reconfigured: {}", change);
+        }
+        changes
     }
 
     // Helper to optionally spawn a telnet and/or WebSocket Controller thread. The Controller
@@ -3003,7 +7683,9 @@ impl SwanlingAttack {
                         None,
                         self.defaults.host.clone(),
                     )?;
-                    let user = SwanlingUser::single(base_url, &self.configuration)?;
+                    let mut user = SwanlingUser::single(base_url, &self.configuration)?;
+                    user.configure_client(None, None, self.client_customizer.as_ref())?;
+                    user.shared_values = self.shared_values.clone();
                     let function = &t.function;
                     let _ = function(&user).await;
                 }
@@ -3015,6 +7697,108 @@ impl SwanlingAttack {
         Ok(())
     }
 
+    // Run each task of every task set once, in the order a user would run them, with a
+    // single user per task set. A task fails if it returns an error or any request it made
+    // failed. Sends no sustained load, only one pass through the tasks.
+    async fn run_smoke_test(mut self) -> Result<SwanlingMetrics, SwanlingError> {
+        self.run_test_start().await?;
+
+        for task_set in &self.task_sets {
+            info!("smoke testing {}", task_set.name);
+            let base_url = swanling::get_base_url(
+                self.get_configuration_host(),
+                task_set.host.clone(),
+                self.defaults.host.clone(),
+            )?;
+            let mut user = SwanlingUser::single(base_url, &self.configuration)?;
+            user.task_sets_index = task_set.task_sets_index;
+            user.load_test_hash = self.metrics.hash;
+            user.configure_client(None, Some(task_set), self.client_customizer.as_ref())?;
+            user.shared_values = self.shared_values.clone();
+            user.request_namer = self.request_namer.clone();
+            // Collect the requests made by each task.
+            let (requests_tx, requests_rx) = flume::unbounded();
+            user.channel_to_parent = Some(requests_tx);
+
+            for task in smoke_test_tasks(task_set) {
+                // Requests are named after the running task, as during a load test.
+                user.weighted_tasks = vec![(task.tasks_index, task.name.to_string())];
+
+                let started = time::Instant::now();
+                let function = &task.function;
+                let mut failures = Vec::new();
+                if let Err(e) = function(&user).await {
+                    failures.push(e.to_string());
+                }
+                let time = started.elapsed().as_millis() as u64;
+
+                // Later copies of a request, sent by set_success or set_failure, replace the
+                // original.
+                let mut requests: Vec<metrics::SwanlingRequestMetric> = Vec::new();
+                for metric in requests_rx.try_iter() {
+                    if let SwanlingMetric::Request(request) = metric {
+                        match requests.iter_mut().find(|r| {
+                            request.update && r.elapsed == request.elapsed && r.url == request.url
+                        }) {
+                            Some(original) => *original = request,
+                            None => requests.push(request),
+                        }
+                    }
+                }
+                for request in requests.iter().filter(|r| !r.success) {
+                    failures.push(format!(
+                        "{} {}: {}",
+                        request.method, request.name, request.error
+                    ));
+                }
+
+                self.metrics.smoke.push(SwanlingSmokeResult {
+                    task_set: task_set.name.to_string(),
+                    task_index: task.tasks_index,
+                    task_name: task.name.to_string(),
+                    time,
+                    passed: failures.is_empty(),
+                    detail: if failures.is_empty() {
+                        None
+                    } else {
+                        Some(failures.join(", "))
+                    },
+                });
+            }
+        }
+
+        self.run_test_stop().await?;
+
+        // Always display the result of the smoke test.
+        self.metrics.final_metrics = true;
+        self.metrics.display_metrics = true;
+        println!("{}", self.metrics);
+
+        let failed: Vec<String> = self
+            .metrics
+            .smoke
+            .iter()
+            .filter(|result| !result.passed)
+            .map(|result| {
+                format!(
+                    "{}: {}: {}",
+                    result.task_set,
+                    result.task_index + 1,
+                    result.task_name
+                )
+            })
+            .collect();
+        if !failed.is_empty() {
+            return Err(SwanlingError::SmokeFailed {
+                detail: format!("Failed: {}.", failed.join(", ")),
+                failed,
+                metrics: Box::new(self.metrics),
+            });
+        }
+
+        Ok(self.metrics)
+    }
+
     // Invoke `test_stop` tasks if existing.
     async fn run_test_stop(&self) -> Result<(), SwanlingError> {
         // Initialize per-user states.
@@ -3029,7 +7813,9 @@ impl SwanlingAttack {
                         None,
                         self.defaults.host.clone(),
                     )?;
-                    let user = SwanlingUser::single(base_url, &self.configuration)?;
+                    let mut user = SwanlingUser::single(base_url, &self.configuration)?;
+                    user.configure_client(None, None, self.client_customizer.as_ref())?;
+                    user.shared_values = self.shared_values.clone();
                     let function = &t.function;
                     let _ = function(&user).await;
                 }
@@ -3050,11 +7836,15 @@ impl SwanlingAttack {
         trace!("initialize_attack");
 
         // Create a single channel used to send metrics from SwanlingUser threads
-        // to parent thread.
+        // to parent thread. A Worker can bound it, dropping samples rather than
+        // buffering them without limit if it can't keep up.
         let (all_threads_metrics_tx, metrics_rx): (
             flume::Sender<SwanlingMetric>,
             flume::Receiver<SwanlingMetric>,
-        ) = flume::unbounded();
+        ) = match self.configuration.worker_metrics_buffer {
+            Some(buffer) if self.attack_mode == AttackMode::Worker => flume::bounded(buffer),
+            _ => flume::unbounded(),
+        };
 
         // Optionally spawn a telnet and/or Websocket Controller thread.
         let controller_channel_rx = self.setup_controllers().await;
@@ -3070,21 +7860,47 @@ impl SwanlingAttack {
             drift_timer: tokio::time::Instant::now(),
             all_threads_metrics_tx,
             metrics_rx,
+            dropped_metrics: Arc::new(AtomicUsize::new(0)),
+            iterations: Arc::new(SwanlingIterationCounters::default()),
             logger_handle: None,
             all_threads_logger_tx: None,
             throttle_threads_tx: None,
             parent_to_throttle_tx: None,
+            throttle_rate: None,
+            connections: None,
             controller_channel_rx,
             report_file: None,
             metrics_header_displayed: false,
             idle_status_displayed: false,
             users: Vec::new(),
             user_channels: Vec::new(),
+            spike_channels: Vec::new(),
+            spike_timer: std_now,
+            adaptive_timer: std_now,
+            ramp_timer: std_now,
             running_metrics_timer: std_now,
+            error_tail_timer: std_now,
+            // Workers don't have the metrics of the whole load test, so the callback
+            // only runs on the Manager.
+            metrics_callback: if self.attack_mode == AttackMode::Worker {
+                None
+            } else {
+                self.metrics_callback
+                    .as_ref()
+                    .map(SwanlingMetricsCallbackThread::spawn)
+            },
+            worker_metrics_timer: std_now,
+            worker_metrics_pending: false,
             display_running_metrics: false,
             all_users_spawned: false,
             shutdown_after_stop: !self.configuration.no_autostart,
-            canceled: Arc::new(AtomicBool::new(false)),
+            paused: false,
+            target_users: None,
+            #[cfg(feature = "gaggle")]
+            reconfigured: Vec::new(),
+            #[cfg(feature = "gaggle")]
+            requested_log: None,
+            canceled: self.canceled.clone(),
             socket,
         };
 
@@ -3103,9 +7919,11 @@ impl SwanlingAttack {
         &mut self,
         swanling_attack_run_state: &mut SwanlingAttackRunState,
     ) -> Result<(), SwanlingError> {
-        // If the run_timer has expired, stop spawning user threads and start stopping them
-        // instead. Unwrap is safe here because load test had to start to get here.
-        if util::timer_expired(self.started.unwrap(), self.run_time) {
+        // If the run_timer has expired or the maximum number of requests have been made, stop
+        // spawning user threads and start stopping them instead. Unwrap is safe here because
+        // load test had to start to get here.
+        if util::timer_expired(self.started.unwrap(), self.run_time) || self.max_requests_reached()
+        {
             self.set_attack_phase(swanling_attack_run_state, AttackPhase::Stopping);
             return Ok(());
         }
@@ -3136,73 +7954,14 @@ impl SwanlingAttack {
             }
 
             // Spawn next scheduled SwanlingUser.
-            let mut thread_user =
+            let thread_user =
                 self.weighted_users[swanling_attack_run_state.spawn_user_counter].clone();
-            swanling_attack_run_state.spawn_user_counter += 1;
-
-            // Copy weighted tasks and weighted on start tasks into the user thread.
-            thread_user.weighted_tasks = self.task_sets[thread_user.task_sets_index]
-                .weighted_tasks
-                .clone();
-            thread_user.weighted_on_start_tasks = self.task_sets[thread_user.task_sets_index]
-                .weighted_on_start_tasks
-                .clone();
-            thread_user.weighted_on_stop_tasks = self.task_sets[thread_user.task_sets_index]
-                .weighted_on_stop_tasks
-                .clone();
-            // Remember which task group this user is using.
-            thread_user.weighted_users_index = self.metrics.users;
-
-            // Create a per-thread channel allowing parent thread to control child threads.
-            let (parent_sender, thread_receiver): (
-                flume::Sender<SwanlingUserCommand>,
-                flume::Receiver<SwanlingUserCommand>,
-            ) = flume::unbounded();
+            self.metrics
+                .record_task_set_user(thread_user.task_sets_index);
+            let parent_sender =
+                self.launch_user(swanling_attack_run_state, thread_user, self.metrics.users);
             swanling_attack_run_state.user_channels.push(parent_sender);
-
-            // Clone the logger_tx if enabled, otherwise is None.
-            thread_user.logger = swanling_attack_run_state.all_threads_logger_tx.clone();
-
-            // Copy the SwanlingUser-throttle receiver channel, used by all threads.
-            thread_user.throttle = if self.configuration.throttle_requests > 0 {
-                Some(
-                    swanling_attack_run_state
-                        .throttle_threads_tx
-                        .clone()
-                        .unwrap(),
-                )
-            } else {
-                None
-            };
-
-            // Copy the SwanlingUser-to-parent sender channel, used by all threads.
-            thread_user.channel_to_parent =
-                Some(swanling_attack_run_state.all_threads_metrics_tx.clone());
-
-            // Copy the appropriate task_set into the thread.
-            let thread_task_set = self.task_sets[thread_user.task_sets_index].clone();
-
-            // We number threads from 1 as they're human-visible (in the logs),
-            // whereas metrics.users starts at 0.
-            let thread_number = self.metrics.users + 1;
-
-            let is_worker = self.attack_mode == AttackMode::Worker;
-
-            // If running on Worker, use Worker configuration in SwanlingUser.
-            if is_worker {
-                thread_user.config = self.configuration.clone();
-            }
-
-            // Launch a new user.
-            let user = tokio::spawn(user::user_main(
-                thread_number,
-                thread_task_set,
-                thread_user,
-                thread_receiver,
-                is_worker,
-            ));
-
-            swanling_attack_run_state.users.push(user);
+            swanling_attack_run_state.spawn_user_counter += 1;
             self.metrics.users += 1;
 
             if let Some(running_metrics) = self.configuration.running_metrics {
@@ -3254,23 +8013,435 @@ impl SwanlingAttack {
                 info!("launched {} users...", self.metrics.users);
             }
 
-            self.reset_metrics(swanling_attack_run_state).await?;
+            self.reset_metrics_after_launch(swanling_attack_run_state)
+                .await?;
+            // The first spike starts one interval after all users are launched.
+            swanling_attack_run_state.spike_timer = std::time::Instant::now();
+            // The adaptive throttle only measures requests made once all users are launched.
+            swanling_attack_run_state.adaptive_timer = std::time::Instant::now();
+            if let Some(adaptive) = self.metrics.adaptive.as_mut() {
+                adaptive.start_interval();
+            }
+            // Likewise, the ramp to failure starts from the configured users.
+            swanling_attack_run_state.ramp_timer = std::time::Instant::now();
+            if let Some(ramp) = self.metrics.ramp_to_failure.as_mut() {
+                ramp.start_interval();
+            }
             self.set_attack_phase(swanling_attack_run_state, AttackPhase::Running);
         }
 
         Ok(())
     }
 
+    // Launch a SwanlingUser in its own thread, identified by `user_index`. Returns a channel
+    // allowing the parent thread to control it.
+    fn launch_user(
+        &self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+        mut thread_user: SwanlingUser,
+        user_index: usize,
+    ) -> flume::Sender<SwanlingUserCommand> {
+        // Copy weighted tasks and weighted on start tasks into the user thread.
+        thread_user.weighted_tasks = self.task_sets[thread_user.task_sets_index]
+            .weighted_tasks
+            .clone();
+        thread_user.weighted_on_start_tasks = self.task_sets[thread_user.task_sets_index]
+            .weighted_on_start_tasks
+            .clone();
+        thread_user.weighted_on_stop_tasks = self.task_sets[thread_user.task_sets_index]
+            .weighted_on_stop_tasks
+            .clone();
+        // Remember which task group this user is using.
+        thread_user.weighted_users_index = user_index;
+
+        // Create a per-thread channel allowing parent thread to control child threads.
+        let (parent_sender, thread_receiver): (
+            flume::Sender<SwanlingUserCommand>,
+            flume::Receiver<SwanlingUserCommand>,
+        ) = flume::unbounded();
+
+        // Users launched while the load test is paused start out paused.
+        if swanling_attack_run_state.paused {
+            let _ = parent_sender.send(SwanlingUserCommand::Wait);
+        }
+
+        // Clone the logger_tx if enabled, otherwise is None.
+        thread_user.logger = swanling_attack_run_state.all_threads_logger_tx.clone();
+
+        // Copy the SwanlingUser-throttle receiver channel, used by all threads.
+        thread_user.throttle = if self.configuration.throttle_requests > 0 {
+            Some(
+                swanling_attack_run_state
+                    .throttle_threads_tx
+                    .clone()
+                    .unwrap(),
+            )
+        } else {
+            None
+        };
+
+        // Share the connection cap, if enabled, with all threads.
+        thread_user.connections = swanling_attack_run_state.connections.clone();
+
+        // Share published values with all threads.
+        thread_user.shared_values = self.shared_values.clone();
+
+        // Name requests with the request namer, if set.
+        thread_user.request_namer = self.request_namer.clone();
+
+        // Copy the SwanlingUser-to-parent sender channel, used by all threads.
+        thread_user.channel_to_parent =
+            Some(swanling_attack_run_state.all_threads_metrics_tx.clone());
+        thread_user.dropped_metrics = swanling_attack_run_state.dropped_metrics.clone();
+        thread_user.iterations = swanling_attack_run_state.iterations.clone();
+
+        // Copy the appropriate task_set into the thread.
+        let thread_task_set = self.task_sets[thread_user.task_sets_index].clone();
+
+        // We number threads from 1 as they're human-visible (in the logs),
+        // whereas user_index starts at 0.
+        let thread_number = user_index + 1;
+
+        let is_worker = self.attack_mode == AttackMode::Worker;
+
+        // If running on Worker, use Worker configuration in SwanlingUser.
+        if is_worker {
+            thread_user.config = self.configuration.clone();
+        }
+
+        // If enabled, trace everything the user does in a span identifying the user.
+        #[cfg(feature = "tracing")]
+        let user_span = tracing::info_span!(
+            "user",
+            worker_id = get_worker_id(),
+            user = thread_number,
+            task_set = %thread_task_set.name
+        );
+
+        // Each user gets its own task scheduler.
+        let thread_scheduler: Box<dyn SwanlingTaskScheduler> = match self.task_scheduler.as_ref() {
+            Some(task_scheduler) => (task_scheduler.0)(&thread_task_set),
+            None => Box::new(self.scheduler.clone()),
+        };
+
+        // Launch a new user.
+        let user_main = user::user_main(
+            thread_number,
+            thread_task_set,
+            thread_scheduler,
+            thread_user,
+            thread_receiver,
+            is_worker,
+        );
+        #[cfg(feature = "tracing")]
+        let user_main = tracing::Instrument::instrument(user_main, user_span);
+        let user = tokio::spawn(user_main);
+
+        swanling_attack_run_state.users.push(user);
+
+        parent_sender
+    }
+
+    // Build an additional user identified by `user_index`, allocated the same way as the
+    // configured users. Each gets its own client, rather than sharing one with the
+    // configured user it was copied from.
+    async fn build_additional_user(
+        &self,
+        user_index: usize,
+    ) -> Result<SwanlingUser, SwanlingError> {
+        let weighted_user = &self.weighted_users[user_index % self.weighted_users.len()];
+        let base_url = weighted_user.base_url.read().await.clone();
+        let mut thread_user = SwanlingUser::new(
+            weighted_user.task_sets_index,
+            base_url,
+            weighted_user.min_wait,
+            weighted_user.max_wait,
+            &self.configuration,
+            self.metrics.hash,
+        )?;
+        // Additional users send the same User-Agent as the user they were copied from, and
+        // belong to the same class.
+        thread_user.configure_client(
+            weighted_user.user_agent.as_deref(),
+            Some(&self.task_sets[weighted_user.task_sets_index]),
+            self.client_customizer.as_ref(),
+        )?;
+        Ok(thread_user)
+    }
+
+    // If a spike is configured, launch additional users each time one is due and stop
+    // them again when it is over.
+    async fn update_spike(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+    ) -> Result<(), SwanlingError> {
+        let spike = match self.spike {
+            Some(s) => s,
+            None => return Ok(()),
+        };
+        // Spike windows are recorded relative to when the load test started.
+        let elapsed = self.started.unwrap().elapsed().as_secs() as usize;
+
+        if swanling_attack_run_state.spike_channels.is_empty() {
+            if util::timer_expired(swanling_attack_run_state.spike_timer, spike.every) {
+                swanling_attack_run_state.spike_timer = std::time::Instant::now();
+
+                // Spike users are numbered after the configured users.
+                let users = self.weighted_users.len() * (spike.multiplier - 1);
+                info!(
+                    "spiking to {}x users, launching {} additional users for {} seconds...",
+                    spike.multiplier, users, spike.duration
+                );
+                for index in 0..users {
+                    let user_index = self.weighted_users.len() + index;
+                    let thread_user = self.build_additional_user(user_index).await?;
+                    let parent_sender =
+                        self.launch_user(swanling_attack_run_state, thread_user, user_index);
+                    swanling_attack_run_state.spike_channels.push(parent_sender);
+                }
+
+                self.metrics.spikes.push(SwanlingSpikeWindow {
+                    started: elapsed,
+                    stopped: None,
+                    users,
+                });
+                // During a soak test, only keep the most recent spikes.
+                if self.configuration.soak && self.metrics.spikes.len() > metrics::SOAK_MAX_WINDOWS
+                {
+                    self.metrics.spikes.remove(0);
+                }
+            }
+        } else if util::timer_expired(swanling_attack_run_state.spike_timer, spike.duration) {
+            info!(
+                "spike is over, stopping {} additional users...",
+                swanling_attack_run_state.spike_channels.len()
+            );
+            // Users that have exited are joined when the load test stops.
+            for send_to_user in swanling_attack_run_state.spike_channels.drain(..) {
+                let _ = send_to_user.send(SwanlingUserCommand::Exit);
+            }
+
+            if let Some(spike_window) = self.metrics.spikes.last_mut() {
+                spike_window.stopped = Some(elapsed);
+            }
+        }
+
+        Ok(())
+    }
+
+    // If the adaptive throttle is enabled, raise or lower the throttle each time an
+    // interval ends, depending on the response times and errors during the interval.
+    fn update_adaptive_throttle(&mut self, swanling_attack_run_state: &mut SwanlingAttackRunState) {
+        if self.metrics.adaptive.is_none()
+            || !util::timer_expired(
+                swanling_attack_run_state.adaptive_timer,
+                metrics::ADAPTIVE_INTERVAL,
+            )
+        {
+            return;
+        }
+        let interval = swanling_attack_run_state
+            .adaptive_timer
+            .elapsed()
+            .as_secs_f32();
+        swanling_attack_run_state.adaptive_timer = std::time::Instant::now();
+
+        // Steps are recorded relative to when the load test started.
+        let elapsed = self.started.unwrap().elapsed().as_secs() as usize;
+        let throttle_requests = self.configuration.throttle_requests;
+        let adjusted =
+            self.metrics
+                .adaptive
+                .as_mut()
+                .unwrap()
+                .adjust(elapsed, interval, throttle_requests);
+        if adjusted != throttle_requests {
+            let reconfiguration = SwanlingReconfiguration {
+                throttle_requests: Some(adjusted),
+                ..Default::default()
+            };
+            for change in self.reconfigure(swanling_attack_run_state, &reconfiguration) {
+                info!("adaptive throttle: {}", change);
+            }
+        }
+    }
+
+    // If ramping to failure, measure the error rate each time an interval ends. Launch as
+    // many additional users as were configured while it stays within the threshold, and
+    // stop the load test once it has exceeded it for long enough.
+    async fn update_ramp_to_failure(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+    ) -> Result<(), SwanlingError> {
+        if self.metrics.ramp_to_failure.is_none()
+            || !util::timer_expired(swanling_attack_run_state.ramp_timer, metrics::RAMP_INTERVAL)
+        {
+            return Ok(());
+        }
+        let interval = swanling_attack_run_state.ramp_timer.elapsed().as_secs_f32();
+        swanling_attack_run_state.ramp_timer = std::time::Instant::now();
+
+        // Steps are recorded relative to when the load test started.
+        let elapsed = self.started.unwrap().elapsed().as_secs() as usize;
+        let users = self.metrics.users;
+        let ramp = self.metrics.ramp_to_failure.as_mut().unwrap();
+        if ramp.measure(elapsed, interval, users) {
+            let breaking_point = ramp.breaking_point.as_ref().unwrap();
+            info!(
+                "ramp to failure: error rate exceeded {}% with {} users at {:.2} requests per second, stopping load test...",
+                ramp.error_threshold, breaking_point.users, breaking_point.rps
+            );
+            self.set_attack_phase(swanling_attack_run_state, AttackPhase::Stopping);
+        } else if !ramp.is_failing() {
+            // Hold the load steady while confirming the error rate is sustained.
+            let additional_users = self.weighted_users.len();
+            info!(
+                "ramp to failure: launching {} additional users...",
+                additional_users
+            );
+            for _ in 0..additional_users {
+                let user_index = self.metrics.users;
+                let thread_user = self.build_additional_user(user_index).await?;
+                self.metrics
+                    .record_task_set_user(thread_user.task_sets_index);
+                let parent_sender =
+                    self.launch_user(swanling_attack_run_state, thread_user, user_index);
+                swanling_attack_run_state.user_channels.push(parent_sender);
+                self.metrics.users += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    // If a load profile is configured, reconfigure the users to run each time the profile
+    // interpolates a different number. In a Regatta the Manager follows the profile
+    // instead, reconfiguring the users on all Workers.
+    fn update_load_profile(&mut self, swanling_attack_run_state: &mut SwanlingAttackRunState) {
+        let users = match self.load_profile.as_ref() {
+            Some(load_profile) => {
+                load_profile.users_at(self.started.unwrap().elapsed().as_secs_f32())
+            }
+            None => return,
+        };
+        let reconfiguration = SwanlingReconfiguration {
+            users: Some(users),
+            ..Default::default()
+        };
+        for change in self.reconfigure(swanling_attack_run_state, &reconfiguration) {
+            info!("load profile: {}", change);
+        }
+    }
+
+    // If reconfigured to run a different number of users, launch additional users or stop
+    // the most recently launched users until that many are running.
+    async fn update_users(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+    ) -> Result<(), SwanlingError> {
+        let users = match swanling_attack_run_state.target_users.take() {
+            Some(users) => users,
+            None => return Ok(()),
+        };
+        while swanling_attack_run_state.user_channels.len() < users {
+            // Users are numbered after all users launched so far.
+            let user_index = self.metrics.users;
+            let thread_user = self.build_additional_user(user_index).await?;
+            self.metrics
+                .record_task_set_user(thread_user.task_sets_index);
+            let parent_sender =
+                self.launch_user(swanling_attack_run_state, thread_user, user_index);
+            // Users launched while paused wait to be resumed with the others.
+            if swanling_attack_run_state.paused {
+                let _ = parent_sender.send(SwanlingUserCommand::Wait);
+            }
+            swanling_attack_run_state.user_channels.push(parent_sender);
+            self.metrics.users += 1;
+        }
+        while swanling_attack_run_state.user_channels.len() > users {
+            // Users that have exited are joined when the load test stops.
+            if let Some(send_to_user) = swanling_attack_run_state.user_channels.pop() {
+                let _ = send_to_user.send(SwanlingUserCommand::Exit);
+            }
+        }
+
+        Ok(())
+    }
+
+    // If a metrics stream is returned, send it a snapshot of the metrics each time an
+    // interval ends. Workers don't have the metrics of the whole load test, so snapshots
+    // are only streamed from the Manager.
+    fn update_metrics_stream(&mut self) {
+        if self.attack_mode == AttackMode::Worker {
+            return;
+        }
+        if let Some(metrics_stream) = self.metrics_stream.as_mut() {
+            self.metrics.duration = self.started.unwrap().elapsed().as_secs() as usize;
+            metrics_stream.poll(&self.metrics);
+        }
+    }
+
+    // If a metrics callback is set, send it a snapshot of the metrics each time an interval
+    // ends, and apply the action it returns.
+    fn update_metrics_callback(&mut self, swanling_attack_run_state: &mut SwanlingAttackRunState) {
+        let metrics_callback = match swanling_attack_run_state.metrics_callback.as_mut() {
+            Some(metrics_callback) => metrics_callback,
+            None => return,
+        };
+        self.metrics.duration = self.started.unwrap().elapsed().as_secs() as usize;
+        match metrics_callback.poll(&self.metrics) {
+            Some(SwanlingControlAction::Abort) => {
+                info!(
+                    "metrics callback aborted load test after {} seconds...",
+                    self.metrics.duration
+                );
+                self.set_attack_phase(swanling_attack_run_state, AttackPhase::Stopping);
+            }
+            Some(SwanlingControlAction::Adjust(reconfiguration)) => {
+                for change in self.reconfigure(swanling_attack_run_state, &reconfiguration) {
+                    info!("metrics callback: {}", change);
+                }
+            }
+            Some(SwanlingControlAction::Continue) | None => (),
+        }
+    }
+
     // Let the [`SwanlingAttack`](./struct.SwanlingAttack.html) run until the timer expires
     // (or the test is canceled), and then trigger a shut down.
     async fn monitor_attack(
         &mut self,
         swanling_attack_run_state: &mut SwanlingAttackRunState,
     ) -> Result<(), SwanlingError> {
-        // Exit if run_time timer expires.
-        if util::timer_expired(self.started.unwrap(), self.run_time) {
+        // Exit if run_time timer expires, if the maximum number of requests have been made,
+        // or if all users have run all of their iterations.
+        if util::timer_expired(self.started.unwrap(), self.run_time)
+            || self.max_requests_reached()
+            || self.iterations_completed(swanling_attack_run_state)
+        {
             self.set_attack_phase(swanling_attack_run_state, AttackPhase::Stopping);
         } else {
+            // Start or stop a spike of additional users, if configured.
+            self.update_spike(swanling_attack_run_state).await?;
+
+            // Adjust the throttle to the response times, if configured.
+            self.update_adaptive_throttle(swanling_attack_run_state);
+
+            // Launch more users or stop the load test, if ramping to failure.
+            self.update_ramp_to_failure(swanling_attack_run_state)
+                .await?;
+
+            // Follow the load profile, if configured.
+            self.update_load_profile(swanling_attack_run_state);
+
+            // Invoke the metrics callback, if set, and apply the action it returned.
+            self.update_metrics_callback(swanling_attack_run_state);
+
+            // Stream a snapshot of the metrics, if requested.
+            self.update_metrics_stream();
+
+            // Launch or stop users, if reconfigured to run a different number.
+            self.update_users(swanling_attack_run_state).await?;
+
             // Subtract the time spent doing other things, running the main parent loop twice
             // per second.
             swanling_attack_run_state.drift_timer = util::sleep_minus_drift(
@@ -3304,7 +8475,44 @@ impl SwanlingAttack {
         } else {
             info!("stopping after {} seconds...", self.metrics.duration);
         }
+        // Users launched by a spike that is still running are stopped right away.
+        for (index, send_to_user) in swanling_attack_run_state.spike_channels.iter().enumerate() {
+            match send_to_user.send(SwanlingUserCommand::Exit) {
+                Ok(_) => {
+                    debug!("telling spike user {} to exit", index);
+                }
+                Err(e) => {
+                    info!("failed to tell spike user {} to exit: {}", index, e);
+                }
+            }
+        }
+        // If --stop-time is set, spread stopping users evenly across it, mirroring how they
+        // were hatched. Each Worker stops its own users over the same time, so the Regatta
+        // as a whole winds down together.
+        let stop_user_in_ms =
+            if self.stop_time > 0 && !swanling_attack_run_state.user_channels.is_empty() {
+                info!(
+                    "gradually stopping {} users over {} seconds...",
+                    swanling_attack_run_state.user_channels.len(),
+                    self.stop_time
+                );
+                swanling_attack_run_state.drift_timer = tokio::time::Instant::now();
+                self.stop_time * 1_000 / swanling_attack_run_state.user_channels.len()
+            } else {
+                0
+            };
         for (index, send_to_user) in swanling_attack_run_state.user_channels.iter().enumerate() {
+            // Stop the remaining users at once if the load test is canceled while stopping.
+            if stop_user_in_ms > 0
+                && index > 0
+                && !swanling_attack_run_state.canceled.load(Ordering::SeqCst)
+            {
+                swanling_attack_run_state.drift_timer = util::sleep_minus_drift(
+                    time::Duration::from_millis(stop_user_in_ms as u64),
+                    swanling_attack_run_state.drift_timer,
+                )
+                .await;
+            }
             match send_to_user.send(SwanlingUserCommand::Exit) {
                 Ok(_) => {
                     debug!("telling user {} to exit", index);
@@ -3325,12 +8533,47 @@ impl SwanlingAttack {
             let _ = throttle_tx.send(false);
         }
 
+        // If --cooldown is set, keep collecting metrics while in-flight requests finish
+        // and the target recovers, without counting the time in the load test duration.
+        if self.cooldown > 0 && !swanling_attack_run_state.canceled.load(Ordering::SeqCst) {
+            let duration = self.metrics.duration;
+            info!("cooling down for {} seconds...", self.cooldown);
+            let cooldown_timer = time::Instant::now();
+            swanling_attack_run_state.drift_timer = tokio::time::Instant::now();
+            while !util::timer_expired(cooldown_timer, self.cooldown)
+                && !swanling_attack_run_state.canceled.load(Ordering::SeqCst)
+            {
+                self.sync_metrics(swanling_attack_run_state, false).await?;
+                swanling_attack_run_state.drift_timer = util::sleep_minus_drift(
+                    time::Duration::from_millis(500),
+                    swanling_attack_run_state.drift_timer,
+                )
+                .await;
+            }
+            // Displaying running metrics updates the duration, restore it.
+            self.metrics.duration = duration;
+            self.metrics.cooldown = Some(SwanlingCooldownWindow {
+                started: duration,
+                stopped: duration + cooldown_timer.elapsed().as_secs() as usize,
+            });
+        }
+
         // Take the users vector out of the SwanlingAttackRunState object so it can be
         // consumed by futures::future::join_all().
         let users = std::mem::take(&mut swanling_attack_run_state.users);
         futures::future::join_all(users).await;
         debug!("all users exited");
 
+        // Report the iterations users ran, if limited.
+        if let Some(per_user) = self.configuration.iterations_per_user {
+            let iterations = &swanling_attack_run_state.iterations;
+            self.metrics.iterations = Some(SwanlingIterations {
+                per_user,
+                completed: iterations.completed.load(Ordering::SeqCst),
+                in_progress: iterations.in_progress.load(Ordering::SeqCst),
+            });
+        }
+
         // If the logger thread is enabled, tell it to flush and exit.
         if swanling_attack_run_state.logger_handle.is_some() {
             if let Err(e) = swanling_attack_run_state
@@ -3358,21 +8601,120 @@ impl SwanlingAttack {
 
         #[cfg(feature = "gaggle")]
         {
-            // As worker, push metrics up to manager.
+            // As worker, push metrics up to manager. Without metrics, the push only tells
+            // the manager this worker is done.
             if self.attack_mode == AttackMode::Worker {
-                worker::push_metrics_to_manager(
-                    &swanling_attack_run_state.socket.clone().unwrap(),
-                    vec![
+                let mut gaggle_metrics = Vec::new();
+                if !self.configuration.no_metrics {
+                    gaggle_metrics = vec![
+                        worker::clock_metric(),
                         GaggleMetrics::Requests(self.metrics.requests.clone()),
+                        GaggleMetrics::Groups(self.metrics.groups.clone()),
                         GaggleMetrics::Errors(self.metrics.errors.clone()),
                         GaggleMetrics::Tasks(self.metrics.tasks.clone()),
-                    ],
+                        GaggleMetrics::Custom(self.metrics.custom.clone()),
+                        GaggleMetrics::Dropped(
+                            swanling_attack_run_state
+                                .dropped_metrics
+                                .swap(0, Ordering::SeqCst),
+                        ),
+                    ];
+                }
+                // Report the iterations the Worker's users ran, if limited.
+                if let Some(iterations) = self.metrics.iterations {
+                    gaggle_metrics.push(GaggleMetrics::Iterations(iterations));
+                }
+                worker::push_metrics_to_manager(
+                    &swanling_attack_run_state.socket.clone().unwrap(),
+                    gaggle_metrics,
                     true,
+                    self.configuration.gaggle_max_msg_size.unwrap_or(0),
+                );
+                // No need to reset local metrics, the worker is exiting. A push still
+                // waiting for the interval is covered by this one, don't send it again
+                // when metrics are flushed.
+                swanling_attack_run_state.worker_metrics_pending = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Calculate a unique hash for the current load test, from its task sets.
+    fn load_test_hash(&self) -> u64 {
+        let mut s = DefaultHasher::new();
+        self.task_sets.hash(&mut s);
+        s.finish()
+    }
+
+    // Reload the sessions saved by an earlier run of the same load test, if enabled.
+    async fn load_sessions(&self) {
+        if self.configuration.session_file.is_empty() {
+            return;
+        }
+
+        let contents = match std::fs::read_to_string(&self.configuration.session_file) {
+            Ok(contents) => contents,
+            Err(_) => {
+                info!(
+                    "no sessions saved in {}, users start without sessions",
+                    self.configuration.session_file
+                );
+                return;
+            }
+        };
+        let session_file: SwanlingSessionFile = match serde_json::from_str(&contents) {
+            Ok(session_file) => session_file,
+            Err(e) => {
+                warn!(
+                    "ignoring sessions in {}: {}",
+                    self.configuration.session_file, e
                 );
-                // No need to reset local metrics, the worker is exiting.
+                return;
             }
+        };
+        if session_file.hash != self.load_test_hash() {
+            warn!(
+                "ignoring sessions in {}, saved by a different load test",
+                self.configuration.session_file
+            );
+            return;
+        }
+
+        for (user, session) in self.weighted_users.iter().zip(&session_file.users) {
+            user.restore_session(session).await;
+        }
+        info!(
+            "reloaded {} sessions from {}",
+            session_file.users.len().min(self.weighted_users.len()),
+            self.configuration.session_file
+        );
+        warn!("reloaded cookies and tokens may have expired since they were saved");
+    }
+
+    // Save the session of each user, to be reloaded by the next run, if enabled.
+    async fn save_sessions(&self) -> Result<(), SwanlingError> {
+        if self.configuration.session_file.is_empty() {
+            return Ok(());
         }
 
+        let mut session_file = SwanlingSessionFile {
+            hash: self.load_test_hash(),
+            users: Vec::new(),
+        };
+        for user in &self.weighted_users {
+            session_file.users.push(user.save_session().await);
+        }
+        std::fs::write(
+            &self.configuration.session_file,
+            serde_json::to_string_pretty(&session_file).expect("unexpected serde failure"),
+        )?;
+        info!(
+            "saved {} sessions to {}",
+            session_file.users.len(),
+            self.configuration.session_file
+        );
+
         Ok(())
     }
 
@@ -3381,6 +8723,9 @@ impl SwanlingAttack {
         // Run any configured test_stop() functions.
         self.run_test_stop().await?;
 
+        // Optionally save user sessions for the next run.
+        self.save_sessions().await?;
+
         // Percentile and errors are only displayed when the load test is finished.
         self.metrics.final_metrics = true;
 
@@ -3396,14 +8741,41 @@ impl SwanlingAttack {
         // Run any configured test_start() functions.
         self.run_test_start().await.unwrap();
 
+        // Optionally reload user sessions saved by an earlier run.
+        self.load_sessions().await;
+
         // Prepare to collect metrics, if enabled.
         self.metrics = SwanlingMetrics::default();
         if !self.configuration.no_metrics {
             self.metrics
                 .initialize_task_metrics(&self.task_sets, &self.configuration);
+            self.metrics.initialize_task_set_users(&self.task_sets);
             self.metrics.display_metrics = true;
             // Only display status codes if enabled.
             self.metrics.display_status_codes = self.configuration.status_codes;
+            // Only display slow requests if a threshold is set.
+            self.metrics.slow_threshold = self.configuration.slow_threshold;
+            // Only display outliers if a factor is set.
+            self.metrics.outlier_factor = self.configuration.outlier_factor;
+            // Only compare the rate of requests with the throttle if it's set.
+            self.metrics.throttle_requests = self.configuration.throttle_requests;
+            self.metrics.max_connections = self.configuration.max_connections;
+            // Only report percentiles of requests with enough samples, if set.
+            self.metrics.min_samples = self.configuration.min_samples;
+            // Only group request metrics if a label is set.
+            if !self.configuration.group_by.is_empty() {
+                self.metrics.group_by = Some(self.configuration.group_by.to_string());
+            }
+            // Only adapt the throttle if a target is set.
+            if let Some(target_p95) = self.configuration.adaptive_target_p95 {
+                self.metrics.adaptive = Some(SwanlingAdaptive::new(target_p95));
+            }
+            // Only ramp to failure if enabled.
+            if self.configuration.ramp_to_failure {
+                self.metrics.ramp_to_failure = Some(SwanlingRampToFailure::new(
+                    self.configuration.error_threshold.unwrap(),
+                ));
+            }
         }
 
         // Reset the run state.
@@ -3416,10 +8788,25 @@ impl SwanlingAttack {
         swanling_attack_run_state.idle_status_displayed = false;
         swanling_attack_run_state.users = Vec::new();
         swanling_attack_run_state.user_channels = Vec::new();
+        swanling_attack_run_state.spike_channels = Vec::new();
+        swanling_attack_run_state.spike_timer = std_now;
+        swanling_attack_run_state.adaptive_timer = std_now;
+        swanling_attack_run_state.ramp_timer = std_now;
         swanling_attack_run_state.running_metrics_timer = std_now;
+        swanling_attack_run_state.error_tail_timer = std_now;
+        if let Some(metrics_callback) = swanling_attack_run_state.metrics_callback.as_mut() {
+            metrics_callback.timer = std_now;
+        }
+        if let Some(metrics_stream) = self.metrics_stream.as_mut() {
+            metrics_stream.timer = std_now;
+        }
+        swanling_attack_run_state.worker_metrics_timer = std_now;
+        swanling_attack_run_state.worker_metrics_pending = false;
         swanling_attack_run_state.display_running_metrics = false;
         swanling_attack_run_state.shutdown_after_stop = !self.configuration.no_autostart;
         swanling_attack_run_state.all_users_spawned = false;
+        swanling_attack_run_state.paused = false;
+        swanling_attack_run_state.target_users = None;
 
         // If enabled, spawn a logger thread.
         let (logger_handle, all_threads_logger_tx) =
@@ -3428,9 +8815,17 @@ impl SwanlingAttack {
         swanling_attack_run_state.all_threads_logger_tx = all_threads_logger_tx;
 
         // If enabled, spawn a throttle thread.
-        let (throttle_threads_tx, parent_to_throttle_tx) = self.setup_throttle().await;
+        let (throttle_threads_tx, parent_to_throttle_tx, throttle_rate) =
+            self.setup_throttle().await;
         swanling_attack_run_state.throttle_threads_tx = throttle_threads_tx;
         swanling_attack_run_state.parent_to_throttle_tx = parent_to_throttle_tx;
+        swanling_attack_run_state.throttle_rate = throttle_rate;
+
+        // If enabled, cap how many connections all users can use at once.
+        if self.configuration.max_connections > 0 {
+            swanling_attack_run_state.connections =
+                Some(Arc::new(Semaphore::new(self.configuration.max_connections)));
+        }
 
         // If enabled, create an report file and confirm access.
         swanling_attack_run_state.report_file = match self.prepare_report_file().await {
@@ -3527,6 +8922,8 @@ impl SwanlingAttack {
                     // Write an html report, if enabled.
                     self.write_html_report(&mut swanling_attack_run_state)
                         .await?;
+                    // Write latency distribution charts, if enabled.
+                    self.write_chart_output().await?;
                     // Shutdown Swanling or go into an idle waiting state.
                     if swanling_attack_run_state.shutdown_after_stop {
                         self.set_attack_phase(
@@ -3573,6 +8970,99 @@ impl SwanlingAttack {
     }
 }
 
+/// Runs several named [`SwanlingAttack`](./struct.SwanlingAttack.html)s one after another
+/// in the same process.
+///
+/// Each attack is configured independently with its own task sets, defaults and metrics,
+/// and starts only after the previous attack has stopped. The metrics of each attack are
+/// returned under the name it was registered with. If an attack fails, or is canceled with
+/// ctrl-c, the remaining attacks don't run.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// fn main() -> Result<(), SwanlingError> {
+///     let _swanling_suite = SwanlingSuite::new()
+///         .register_attack(
+///             "browse",
+///             SwanlingAttack::initialize()?
+///                 .register_taskset(taskset!("Browse").register_task(task!(browse)))
+///                 .set_default(SwanlingDefault::Users, 10)?,
+///         )
+///         .register_attack(
+///             "checkout",
+///             SwanlingAttack::initialize()?
+///                 .register_taskset(taskset!("Checkout").register_task(task!(checkout)))
+///                 .set_default(SwanlingDefault::Users, 2)?,
+///         );
+///
+///     Ok(())
+/// }
+///
+/// async fn browse(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let _swanling = user.get("/").await?;
+///
+///     Ok(())
+/// }
+///
+/// async fn checkout(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let _swanling = user.post("/checkout", "").await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct SwanlingSuite {
+    /// The name of each attack, and the attack, in the order they run.
+    attacks: Vec<(String, Box<SwanlingAttack>)>,
+}
+impl SwanlingSuite {
+    /// Create an empty [`SwanlingSuite`](./struct.SwanlingSuite.html).
+    pub fn new() -> Self {
+        SwanlingSuite::default()
+    }
+
+    /// Register an attack to run after those already registered. Each attack must have a
+    /// unique name.
+    ///
+    /// The attack can be passed as returned by `set_default`, without unboxing it.
+    pub fn register_attack<A: Into<Box<SwanlingAttack>>>(mut self, name: &str, attack: A) -> Self {
+        self.attacks.push((name.to_string(), attack.into()));
+        self
+    }
+
+    /// Execute each attack in the order it was registered, returning the final metrics of
+    /// every attack that ran.
+    pub fn execute(self) -> Result<SwanlingSuiteMetrics, SwanlingError> {
+        // Attack metrics are keyed by name, so names can't be reused.
+        for (index, (name, _)) in self.attacks.iter().enumerate() {
+            if self.attacks[..index].iter().any(|(other, _)| other == name) {
+                return Err(SwanlingError::InvalidOption {
+                    option: "SwanlingSuite::register_attack".to_string(),
+                    value: name.to_string(),
+                    detail: format!("More than one attack is named {}.", name),
+                });
+            }
+        }
+
+        let mut suite_metrics = SwanlingSuiteMetrics::default();
+        for (name, attack) in self.attacks {
+            info!("executing attack {}...", name);
+            let metrics = attack.execute()?;
+            suite_metrics.attacks.push((name, metrics));
+
+            // Don't start another attack if ctrl-c was caught.
+            if util::ctrlc_caught() {
+                info!("suite canceled, skipping remaining attacks");
+                break;
+            }
+        }
+
+        Ok(suite_metrics)
+    }
+}
+
 /// All run-time options can optionally be configured with custom defaults.
 ///
 /// For example, you can optionally configure a default host for the load test. This is
@@ -3609,6 +9099,41 @@ impl SwanlingAttack {
 ///  - [SwanlingDefault::WebSocketHost](../swanling/enum.SwanlingDefault.html#variant.WebSocketHost)
 ///  - [SwanlingDefault::ManagerBindHost](../swanling/enum.SwanlingDefault.html#variant.ManagerBindHost)
 ///  - [SwanlingDefault::ManagerHost](../swanling/enum.SwanlingDefault.html#variant.ManagerHost)
+///  - [SwanlingDefault::ClientCert](../swanling/enum.SwanlingDefault.html#variant.ClientCert)
+///  - [SwanlingDefault::ClientKey](../swanling/enum.SwanlingDefault.html#variant.ClientKey)
+///  - [SwanlingDefault::CaCert](../swanling/enum.SwanlingDefault.html#variant.CaCert)
+///  - [SwanlingDefault::Resolve](../swanling/enum.SwanlingDefault.html#variant.Resolve)
+///  - [SwanlingDefault::Sla](../swanling/enum.SwanlingDefault.html#variant.Sla)
+///  - [SwanlingDefault::Spike](../swanling/enum.SwanlingDefault.html#variant.Spike)
+///  - [SwanlingDefault::OauthTokenUrl](../swanling/enum.SwanlingDefault.html#variant.OauthTokenUrl)
+///  - [SwanlingDefault::OauthClientId](../swanling/enum.SwanlingDefault.html#variant.OauthClientId)
+///  - [SwanlingDefault::OauthClientSecret](../swanling/enum.SwanlingDefault.html#variant.OauthClientSecret)
+///  - [SwanlingDefault::AwsRegion](../swanling/enum.SwanlingDefault.html#variant.AwsRegion)
+///  - [SwanlingDefault::AwsService](../swanling/enum.SwanlingDefault.html#variant.AwsService)
+///  - [SwanlingDefault::AwsAccessKeyId](../swanling/enum.SwanlingDefault.html#variant.AwsAccessKeyId)
+///  - [SwanlingDefault::AwsSecretAccessKey](../swanling/enum.SwanlingDefault.html#variant.AwsSecretAccessKey)
+///  - [SwanlingDefault::AwsSessionToken](../swanling/enum.SwanlingDefault.html#variant.AwsSessionToken)
+///  - [SwanlingDefault::ChartOutput](../swanling/enum.SwanlingDefault.html#variant.ChartOutput)
+///  - [SwanlingDefault::LogOnly](../swanling/enum.SwanlingDefault.html#variant.LogOnly)
+///  - [SwanlingDefault::UserAgents](../swanling/enum.SwanlingDefault.html#variant.UserAgents)
+///  - [SwanlingDefault::GaggleTransport](../swanling/enum.SwanlingDefault.html#variant.GaggleTransport)
+///  - [SwanlingDefault::Baseline](../swanling/enum.SwanlingDefault.html#variant.Baseline)
+///  - [SwanlingDefault::SessionFile](../swanling/enum.SwanlingDefault.html#variant.SessionFile)
+///  - [SwanlingDefault::GroupBy](../swanling/enum.SwanlingDefault.html#variant.GroupBy)
+///  - [SwanlingDefault::JunitXml](../swanling/enum.SwanlingDefault.html#variant.JunitXml)
+///  - [SwanlingDefault::ReportJson](../swanling/enum.SwanlingDefault.html#variant.ReportJson)
+///  - [SwanlingDefault::TaskProfile](../swanling/enum.SwanlingDefault.html#variant.TaskProfile)
+///  - [SwanlingDefault::BasePath](../swanling/enum.SwanlingDefault.html#variant.BasePath)
+///  - [SwanlingDefault::HealthCheckUrl](../swanling/enum.SwanlingDefault.html#variant.HealthCheckUrl)
+///  - [SwanlingDefault::PinTaskSet](../swanling/enum.SwanlingDefault.html#variant.PinTaskSet)
+///  - [SwanlingDefault::OutlierFactor](../swanling/enum.SwanlingDefault.html#variant.OutlierFactor)
+///  - [SwanlingDefault::RequestLogSample](../swanling/enum.SwanlingDefault.html#variant.RequestLogSample)
+///  - [SwanlingDefault::GaggleIpc](../swanling/enum.SwanlingDefault.html#variant.GaggleIpc)
+///  - [SwanlingDefault::RegionHeader](../swanling/enum.SwanlingDefault.html#variant.RegionHeader)
+///  - [SwanlingDefault::InfluxFile](../swanling/enum.SwanlingDefault.html#variant.InfluxFile)
+///  - [SwanlingDefault::InfluxUdp](../swanling/enum.SwanlingDefault.html#variant.InfluxUdp)
+///  - [SwanlingDefault::Har](../swanling/enum.SwanlingDefault.html#variant.Har)
+///  - [SwanlingDefault::LoadProfile](../swanling/enum.SwanlingDefault.html#variant.LoadProfile)
 ///
 /// The following run-time options can be configured with a custom default using a
 /// `usize` integer:
@@ -3624,12 +9149,42 @@ impl SwanlingAttack {
 ///  - [SwanlingDefault::WebSocketPort](../swanling/enum.SwanlingDefault.html#variant.WebSocketPort)
 ///  - [SwanlingDefault::ManagerBindPort](../swanling/enum.SwanlingDefault.html#variant.ManagerBindPort)
 ///  - [SwanlingDefault::ManagerPort](../swanling/enum.SwanlingDefault.html#variant.ManagerPort)
+///  - [SwanlingDefault::MaxRequests](../swanling/enum.SwanlingDefault.html#variant.MaxRequests)
+///  - [SwanlingDefault::AggregateWindow](../swanling/enum.SwanlingDefault.html#variant.AggregateWindow)
+///  - [SwanlingDefault::QuarantineErrorThreshold](../swanling/enum.SwanlingDefault.html#variant.QuarantineErrorThreshold)
+///  - [SwanlingDefault::MaxConnections](../swanling/enum.SwanlingDefault.html#variant.MaxConnections)
+///  - [SwanlingDefault::PoolSize](../swanling/enum.SwanlingDefault.html#variant.PoolSize)
+///  - [SwanlingDefault::SlowThreshold](../swanling/enum.SwanlingDefault.html#variant.SlowThreshold)
+///  - [SwanlingDefault::MinSamples](../swanling/enum.SwanlingDefault.html#variant.MinSamples)
+///  - [SwanlingDefault::WorkerMetricsInterval](../swanling/enum.SwanlingDefault.html#variant.WorkerMetricsInterval)
+///  - [SwanlingDefault::RegressionThreshold](../swanling/enum.SwanlingDefault.html#variant.RegressionThreshold)
+///  - [SwanlingDefault::StopTime](../swanling/enum.SwanlingDefault.html#variant.StopTime)
+///  - [SwanlingDefault::Cooldown](../swanling/enum.SwanlingDefault.html#variant.Cooldown)
+///  - [SwanlingDefault::GaggleSendBuffer](../swanling/enum.SwanlingDefault.html#variant.GaggleSendBuffer)
+///  - [SwanlingDefault::GaggleRecvTimeout](../swanling/enum.SwanlingDefault.html#variant.GaggleRecvTimeout)
+///  - [SwanlingDefault::GaggleMaxMsgSize](../swanling/enum.SwanlingDefault.html#variant.GaggleMaxMsgSize)
+///  - [SwanlingDefault::ManagerSockets](../swanling/enum.SwanlingDefault.html#variant.ManagerSockets)
+///  - [SwanlingDefault::WorkerStartupJitter](../swanling/enum.SwanlingDefault.html#variant.WorkerStartupJitter)
+///  - [SwanlingDefault::WorkerStartupSeed](../swanling/enum.SwanlingDefault.html#variant.WorkerStartupSeed)
+///  - [SwanlingDefault::AdaptiveTargetP95](../swanling/enum.SwanlingDefault.html#variant.AdaptiveTargetP95)
+///  - [SwanlingDefault::MaxRedirects](../swanling/enum.SwanlingDefault.html#variant.MaxRedirects)
+///  - [SwanlingDefault::ErrorTail](../swanling/enum.SwanlingDefault.html#variant.ErrorTail)
+///  - [SwanlingDefault::MaxRequestNames](../swanling/enum.SwanlingDefault.html#variant.MaxRequestNames)
+///  - [SwanlingDefault::RequestLogSeed](../swanling/enum.SwanlingDefault.html#variant.RequestLogSeed)
+///  - [SwanlingDefault::ErrorThreshold](../swanling/enum.SwanlingDefault.html#variant.ErrorThreshold)
+///  - [SwanlingDefault::WorkerMetricsBuffer](../swanling/enum.SwanlingDefault.html#variant.WorkerMetricsBuffer)
+///  - [SwanlingDefault::IterationsPerUser](../swanling/enum.SwanlingDefault.html#variant.IterationsPerUser)
+///  - [SwanlingDefault::UserMaxConsecutiveFailures](../swanling/enum.SwanlingDefault.html#variant.UserMaxConsecutiveFailures)
+///  - [SwanlingDefault::HealthCheckExpect](../swanling/enum.SwanlingDefault.html#variant.HealthCheckExpect)
+///  - [SwanlingDefault::PoolIdleTimeout](../swanling/enum.SwanlingDefault.html#variant.PoolIdleTimeout)
+///  - [SwanlingDefault::KeepaliveIdleTimeout](../swanling/enum.SwanlingDefault.html#variant.KeepaliveIdleTimeout)
 ///
 /// The following run-time flags can be configured with a custom default using a
 /// `bool` (and otherwise default to `false`).
 ///  - [SwanlingDefault::NoResetMetrics](../swanling/enum.SwanlingDefault.html#variant.NoResetMetrics)
 ///  - [SwanlingDefault::NoMetrics](../swanling/enum.SwanlingDefault.html#variant.NoMetrics)
 ///  - [SwanlingDefault::NoTaskMetrics](../swanling/enum.SwanlingDefault.html#variant.NoTaskMetrics)
+///  - [SwanlingDefault::ExcludeThinkTime](../swanling/enum.SwanlingDefault.html#variant.ExcludeThinkTime)
 ///  - [SwanlingDefault::NoErrorSummary](../swanling/enum.SwanlingDefault.html#variant.NoErrorSummary)
 ///  - [SwanlingDefault::NoDebugBody](../swanling/enum.SwanlingDefault.html#variant.NoDebugBody)
 ///  - [SwanlingDefault::NoTelnet](../swanling/enum.SwanlingDefault.html#variant.NoTelnet)
@@ -3640,6 +9195,12 @@ impl SwanlingAttack {
 ///  - [SwanlingDefault::Manager](../swanling/enum.SwanlingDefault.html#variant.Manager)
 ///  - [SwanlingDefault::NoHashCheck](../swanling/enum.SwanlingDefault.html#variant.NoHashCheck)
 ///  - [SwanlingDefault::Worker](../swanling/enum.SwanlingDefault.html#variant.Worker)
+///  - [SwanlingDefault::Soak](../swanling/enum.SwanlingDefault.html#variant.Soak)
+///  - [SwanlingDefault::CaptureFailureBodies](../swanling/enum.SwanlingDefault.html#variant.CaptureFailureBodies)
+///  - [SwanlingDefault::RampToFailure](../swanling/enum.SwanlingDefault.html#variant.RampToFailure)
+///  - [SwanlingDefault::PrewarmConnections](../swanling/enum.SwanlingDefault.html#variant.PrewarmConnections)
+///  - [SwanlingDefault::HarTiming](../swanling/enum.SwanlingDefault.html#variant.HarTiming)
+///  - [SwanlingDefault::Insecure](../swanling/enum.SwanlingDefault.html#variant.Insecure)
 ///
 /// The following run-time flags can be configured with a custom default using a
 /// `SwanlingLogFormat`.
@@ -3689,6 +9250,61 @@ impl SwanlingDefaultType<&str> for SwanlingAttack {
                 self.defaults.manager_bind_host = Some(value.to_string())
             }
             SwanlingDefault::ManagerHost => self.defaults.manager_host = Some(value.to_string()),
+            SwanlingDefault::ClientCert => self.defaults.client_cert = Some(value.to_string()),
+            SwanlingDefault::ClientKey => self.defaults.client_key = Some(value.to_string()),
+            SwanlingDefault::CaCert => self.defaults.ca_cert = Some(value.to_string()),
+            SwanlingDefault::Resolve => self.defaults.resolve = Some(value.to_string()),
+            SwanlingDefault::Sla => self.defaults.sla = Some(value.to_string()),
+            SwanlingDefault::PinTaskSet => self.defaults.pin_task_set = Some(value.to_string()),
+            SwanlingDefault::OutlierFactor => {
+                self.defaults.outlier_factor = Some(value.to_string())
+            }
+            SwanlingDefault::Spike => self.defaults.spike = Some(value.to_string()),
+            SwanlingDefault::OauthTokenUrl => {
+                self.defaults.oauth_token_url = Some(value.to_string())
+            }
+            SwanlingDefault::OauthClientId => {
+                self.defaults.oauth_client_id = Some(value.to_string())
+            }
+            SwanlingDefault::OauthClientSecret => {
+                self.defaults.oauth_client_secret = Some(value.to_string())
+            }
+            SwanlingDefault::AwsRegion => self.defaults.aws_region = Some(value.to_string()),
+            SwanlingDefault::AwsService => self.defaults.aws_service = Some(value.to_string()),
+            SwanlingDefault::AwsAccessKeyId => {
+                self.defaults.aws_access_key_id = Some(value.to_string())
+            }
+            SwanlingDefault::AwsSecretAccessKey => {
+                self.defaults.aws_secret_access_key = Some(value.to_string())
+            }
+            SwanlingDefault::AwsSessionToken => {
+                self.defaults.aws_session_token = Some(value.to_string())
+            }
+            SwanlingDefault::ChartOutput => self.defaults.chart_output = Some(value.to_string()),
+            SwanlingDefault::LogOnly => self.defaults.log_only = Some(value.to_string()),
+            SwanlingDefault::UserAgents => self.defaults.user_agents = Some(value.to_string()),
+            SwanlingDefault::GaggleTransport => {
+                self.defaults.gaggle_transport = Some(value.to_string())
+            }
+            SwanlingDefault::Baseline => self.defaults.baseline = Some(value.to_string()),
+            SwanlingDefault::SessionFile => self.defaults.session_file = Some(value.to_string()),
+            SwanlingDefault::GroupBy => self.defaults.group_by = Some(value.to_string()),
+            SwanlingDefault::JunitXml => self.defaults.junit_xml = Some(value.to_string()),
+            SwanlingDefault::ReportJson => self.defaults.report_json = Some(value.to_string()),
+            SwanlingDefault::TaskProfile => self.defaults.task_profile = Some(value.to_string()),
+            SwanlingDefault::BasePath => self.defaults.base_path = Some(value.to_string()),
+            SwanlingDefault::HealthCheckUrl => {
+                self.defaults.health_check_url = Some(value.to_string())
+            }
+            SwanlingDefault::RequestLogSample => {
+                self.defaults.request_log_sample = Some(value.to_string())
+            }
+            SwanlingDefault::GaggleIpc => self.defaults.gaggle_ipc = Some(value.to_string()),
+            SwanlingDefault::RegionHeader => self.defaults.region_header = Some(value.to_string()),
+            SwanlingDefault::InfluxFile => self.defaults.influx_file = Some(value.to_string()),
+            SwanlingDefault::InfluxUdp => self.defaults.influx_udp = Some(value.to_string()),
+            SwanlingDefault::Har => self.defaults.har = Some(value.to_string()),
+            SwanlingDefault::LoadProfile => self.defaults.load_profile = Some(value.to_string()),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::Users
             | SwanlingDefault::RunTime
@@ -3699,7 +9315,36 @@ impl SwanlingDefaultType<&str> for SwanlingAttack {
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
-            | SwanlingDefault::ManagerPort => {
+            | SwanlingDefault::ManagerPort
+            | SwanlingDefault::MaxRequests
+            | SwanlingDefault::AggregateWindow
+            | SwanlingDefault::QuarantineErrorThreshold
+            | SwanlingDefault::MaxConnections
+            | SwanlingDefault::PoolSize
+            | SwanlingDefault::SlowThreshold
+            | SwanlingDefault::MinSamples
+            | SwanlingDefault::WorkerMetricsInterval
+            | SwanlingDefault::RegressionThreshold
+            | SwanlingDefault::StopTime
+            | SwanlingDefault::Cooldown
+            | SwanlingDefault::GaggleSendBuffer
+            | SwanlingDefault::GaggleRecvTimeout
+            | SwanlingDefault::GaggleMaxMsgSize
+            | SwanlingDefault::ManagerSockets
+            | SwanlingDefault::WorkerStartupJitter
+            | SwanlingDefault::WorkerStartupSeed
+            | SwanlingDefault::AdaptiveTargetP95
+            | SwanlingDefault::MaxRedirects
+            | SwanlingDefault::ErrorTail
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::RequestLogSeed
+            | SwanlingDefault::ErrorThreshold
+            | SwanlingDefault::WorkerMetricsBuffer
+            | SwanlingDefault::IterationsPerUser
+            | SwanlingDefault::UserMaxConsecutiveFailures
+            | SwanlingDefault::HealthCheckExpect
+            | SwanlingDefault::PoolIdleTimeout
+            | SwanlingDefault::KeepaliveIdleTimeout => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: value.to_string(),
@@ -3722,7 +9367,14 @@ impl SwanlingDefaultType<&str> for SwanlingAttack {
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
-            | SwanlingDefault::Worker => {
+            | SwanlingDefault::Worker
+            | SwanlingDefault::Soak
+            | SwanlingDefault::CaptureFailureBodies
+            | SwanlingDefault::RampToFailure
+            | SwanlingDefault::PrewarmConnections
+            | SwanlingDefault::HarTiming
+            | SwanlingDefault::Insecure
+            | SwanlingDefault::ExcludeThinkTime => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: value.to_string(),
@@ -3779,6 +9431,49 @@ impl SwanlingDefaultType<usize> for SwanlingAttack {
                 self.defaults.manager_bind_port = Some(value as u16)
             }
             SwanlingDefault::ManagerPort => self.defaults.manager_port = Some(value as u16),
+            SwanlingDefault::MaxRequests => self.defaults.max_requests = Some(value),
+            SwanlingDefault::AggregateWindow => self.defaults.aggregate_window = Some(value),
+            SwanlingDefault::QuarantineErrorThreshold => {
+                self.defaults.quarantine_error_threshold = Some(value)
+            }
+            SwanlingDefault::MaxConnections => self.defaults.max_connections = Some(value),
+            SwanlingDefault::PoolSize => self.defaults.pool_size = Some(value),
+            SwanlingDefault::PoolIdleTimeout => self.defaults.pool_idle_timeout = Some(value),
+            SwanlingDefault::KeepaliveIdleTimeout => {
+                self.defaults.keepalive_idle_timeout = Some(value)
+            }
+            SwanlingDefault::SlowThreshold => self.defaults.slow_threshold = Some(value),
+            SwanlingDefault::MinSamples => self.defaults.min_samples = Some(value),
+            SwanlingDefault::WorkerMetricsInterval => {
+                self.defaults.worker_metrics_interval = Some(value)
+            }
+            SwanlingDefault::RegressionThreshold => {
+                self.defaults.regression_threshold = Some(value)
+            }
+            SwanlingDefault::StopTime => self.defaults.stop_time = Some(value),
+            SwanlingDefault::Cooldown => self.defaults.cooldown = Some(value),
+            SwanlingDefault::GaggleSendBuffer => self.defaults.gaggle_send_buffer = Some(value),
+            SwanlingDefault::GaggleRecvTimeout => self.defaults.gaggle_recv_timeout = Some(value),
+            SwanlingDefault::GaggleMaxMsgSize => self.defaults.gaggle_max_msg_size = Some(value),
+            SwanlingDefault::ManagerSockets => self.defaults.manager_sockets = Some(value),
+            SwanlingDefault::WorkerStartupJitter => {
+                self.defaults.worker_startup_jitter = Some(value)
+            }
+            SwanlingDefault::WorkerStartupSeed => self.defaults.worker_startup_seed = Some(value),
+            SwanlingDefault::AdaptiveTargetP95 => self.defaults.adaptive_target_p95 = Some(value),
+            SwanlingDefault::MaxRedirects => self.defaults.max_redirects = Some(value),
+            SwanlingDefault::ErrorTail => self.defaults.error_tail = Some(value),
+            SwanlingDefault::MaxRequestNames => self.defaults.max_request_names = Some(value),
+            SwanlingDefault::RequestLogSeed => self.defaults.request_log_seed = Some(value),
+            SwanlingDefault::ErrorThreshold => self.defaults.error_threshold = Some(value),
+            SwanlingDefault::WorkerMetricsBuffer => {
+                self.defaults.worker_metrics_buffer = Some(value)
+            }
+            SwanlingDefault::IterationsPerUser => self.defaults.iterations_per_user = Some(value),
+            SwanlingDefault::UserMaxConsecutiveFailures => {
+                self.defaults.user_max_consecutive_failures = Some(value)
+            }
+            SwanlingDefault::HealthCheckExpect => self.defaults.health_check_expect = Some(value),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::Host
             | SwanlingDefault::HatchRate
@@ -3791,7 +9486,42 @@ impl SwanlingDefaultType<usize> for SwanlingAttack {
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
-            | SwanlingDefault::ManagerHost => {
+            | SwanlingDefault::ManagerHost
+            | SwanlingDefault::ClientCert
+            | SwanlingDefault::ClientKey
+            | SwanlingDefault::CaCert
+            | SwanlingDefault::Resolve
+            | SwanlingDefault::Sla
+            | SwanlingDefault::PinTaskSet
+            | SwanlingDefault::OutlierFactor
+            | SwanlingDefault::Spike
+            | SwanlingDefault::OauthTokenUrl
+            | SwanlingDefault::OauthClientId
+            | SwanlingDefault::OauthClientSecret
+            | SwanlingDefault::AwsRegion
+            | SwanlingDefault::AwsService
+            | SwanlingDefault::AwsAccessKeyId
+            | SwanlingDefault::AwsSecretAccessKey
+            | SwanlingDefault::AwsSessionToken
+            | SwanlingDefault::ChartOutput
+            | SwanlingDefault::LogOnly
+            | SwanlingDefault::UserAgents
+            | SwanlingDefault::GaggleTransport
+            | SwanlingDefault::Baseline
+            | SwanlingDefault::SessionFile
+            | SwanlingDefault::GroupBy
+            | SwanlingDefault::JunitXml
+            | SwanlingDefault::ReportJson
+            | SwanlingDefault::TaskProfile
+            | SwanlingDefault::BasePath
+            | SwanlingDefault::HealthCheckUrl
+            | SwanlingDefault::RequestLogSample
+            | SwanlingDefault::GaggleIpc
+            | SwanlingDefault::RegionHeader
+            | SwanlingDefault::InfluxFile
+            | SwanlingDefault::InfluxUdp
+            | SwanlingDefault::Har
+            | SwanlingDefault::LoadProfile => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{}", value),
@@ -3813,7 +9543,14 @@ impl SwanlingDefaultType<usize> for SwanlingAttack {
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
-            | SwanlingDefault::Worker => {
+            | SwanlingDefault::Worker
+            | SwanlingDefault::Soak
+            | SwanlingDefault::CaptureFailureBodies
+            | SwanlingDefault::RampToFailure
+            | SwanlingDefault::PrewarmConnections
+            | SwanlingDefault::HarTiming
+            | SwanlingDefault::Insecure
+            | SwanlingDefault::ExcludeThinkTime => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{}", value),
@@ -3860,6 +9597,7 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             SwanlingDefault::NoResetMetrics => self.defaults.no_reset_metrics = Some(value),
             SwanlingDefault::NoMetrics => self.defaults.no_metrics = Some(value),
             SwanlingDefault::NoTaskMetrics => self.defaults.no_task_metrics = Some(value),
+            SwanlingDefault::ExcludeThinkTime => self.defaults.exclude_think_time = Some(value),
             SwanlingDefault::NoErrorSummary => self.defaults.no_error_summary = Some(value),
             SwanlingDefault::NoDebugBody => self.defaults.no_debug_body = Some(value),
             SwanlingDefault::NoTelnet => self.defaults.no_telnet = Some(value),
@@ -3870,6 +9608,14 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             SwanlingDefault::Manager => self.defaults.manager = Some(value),
             SwanlingDefault::NoHashCheck => self.defaults.no_hash_check = Some(value),
             SwanlingDefault::Worker => self.defaults.worker = Some(value),
+            SwanlingDefault::Soak => self.defaults.soak = Some(value),
+            SwanlingDefault::CaptureFailureBodies => {
+                self.defaults.capture_failure_bodies = Some(value)
+            }
+            SwanlingDefault::RampToFailure => self.defaults.ramp_to_failure = Some(value),
+            SwanlingDefault::PrewarmConnections => self.defaults.prewarm_connections = Some(value),
+            SwanlingDefault::HarTiming => self.defaults.har_timing = Some(value),
+            SwanlingDefault::Insecure => self.defaults.insecure = Some(value),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::Host
             | SwanlingDefault::SwanlingLog
@@ -3882,7 +9628,42 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
-            | SwanlingDefault::ManagerHost => {
+            | SwanlingDefault::ManagerHost
+            | SwanlingDefault::ClientCert
+            | SwanlingDefault::ClientKey
+            | SwanlingDefault::CaCert
+            | SwanlingDefault::Resolve
+            | SwanlingDefault::Sla
+            | SwanlingDefault::PinTaskSet
+            | SwanlingDefault::OutlierFactor
+            | SwanlingDefault::Spike
+            | SwanlingDefault::OauthTokenUrl
+            | SwanlingDefault::OauthClientId
+            | SwanlingDefault::OauthClientSecret
+            | SwanlingDefault::AwsRegion
+            | SwanlingDefault::AwsService
+            | SwanlingDefault::AwsAccessKeyId
+            | SwanlingDefault::AwsSecretAccessKey
+            | SwanlingDefault::AwsSessionToken
+            | SwanlingDefault::ChartOutput
+            | SwanlingDefault::LogOnly
+            | SwanlingDefault::UserAgents
+            | SwanlingDefault::GaggleTransport
+            | SwanlingDefault::Baseline
+            | SwanlingDefault::SessionFile
+            | SwanlingDefault::GroupBy
+            | SwanlingDefault::JunitXml
+            | SwanlingDefault::ReportJson
+            | SwanlingDefault::TaskProfile
+            | SwanlingDefault::BasePath
+            | SwanlingDefault::HealthCheckUrl
+            | SwanlingDefault::RequestLogSample
+            | SwanlingDefault::GaggleIpc
+            | SwanlingDefault::RegionHeader
+            | SwanlingDefault::InfluxFile
+            | SwanlingDefault::InfluxUdp
+            | SwanlingDefault::Har
+            | SwanlingDefault::LoadProfile => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{}", value),
@@ -3902,7 +9683,36 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
-            | SwanlingDefault::ManagerPort => {
+            | SwanlingDefault::ManagerPort
+            | SwanlingDefault::MaxRequests
+            | SwanlingDefault::AggregateWindow
+            | SwanlingDefault::QuarantineErrorThreshold
+            | SwanlingDefault::MaxConnections
+            | SwanlingDefault::PoolSize
+            | SwanlingDefault::SlowThreshold
+            | SwanlingDefault::MinSamples
+            | SwanlingDefault::WorkerMetricsInterval
+            | SwanlingDefault::RegressionThreshold
+            | SwanlingDefault::StopTime
+            | SwanlingDefault::Cooldown
+            | SwanlingDefault::GaggleSendBuffer
+            | SwanlingDefault::GaggleRecvTimeout
+            | SwanlingDefault::GaggleMaxMsgSize
+            | SwanlingDefault::ManagerSockets
+            | SwanlingDefault::WorkerStartupJitter
+            | SwanlingDefault::WorkerStartupSeed
+            | SwanlingDefault::AdaptiveTargetP95
+            | SwanlingDefault::MaxRedirects
+            | SwanlingDefault::ErrorTail
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::RequestLogSeed
+            | SwanlingDefault::ErrorThreshold
+            | SwanlingDefault::WorkerMetricsBuffer
+            | SwanlingDefault::IterationsPerUser
+            | SwanlingDefault::UserMaxConsecutiveFailures
+            | SwanlingDefault::HealthCheckExpect
+            | SwanlingDefault::PoolIdleTimeout
+            | SwanlingDefault::KeepaliveIdleTimeout => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{}", value),
@@ -3960,7 +9770,14 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
-            | SwanlingDefault::Worker => {
+            | SwanlingDefault::Worker
+            | SwanlingDefault::Soak
+            | SwanlingDefault::CaptureFailureBodies
+            | SwanlingDefault::RampToFailure
+            | SwanlingDefault::PrewarmConnections
+            | SwanlingDefault::HarTiming
+            | SwanlingDefault::Insecure
+            | SwanlingDefault::ExcludeThinkTime => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -3981,7 +9798,42 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
-            | SwanlingDefault::ManagerHost => {
+            | SwanlingDefault::ManagerHost
+            | SwanlingDefault::ClientCert
+            | SwanlingDefault::ClientKey
+            | SwanlingDefault::CaCert
+            | SwanlingDefault::Resolve
+            | SwanlingDefault::Sla
+            | SwanlingDefault::PinTaskSet
+            | SwanlingDefault::OutlierFactor
+            | SwanlingDefault::Spike
+            | SwanlingDefault::OauthTokenUrl
+            | SwanlingDefault::OauthClientId
+            | SwanlingDefault::OauthClientSecret
+            | SwanlingDefault::AwsRegion
+            | SwanlingDefault::AwsService
+            | SwanlingDefault::AwsAccessKeyId
+            | SwanlingDefault::AwsSecretAccessKey
+            | SwanlingDefault::AwsSessionToken
+            | SwanlingDefault::ChartOutput
+            | SwanlingDefault::LogOnly
+            | SwanlingDefault::UserAgents
+            | SwanlingDefault::GaggleTransport
+            | SwanlingDefault::Baseline
+            | SwanlingDefault::SessionFile
+            | SwanlingDefault::GroupBy
+            | SwanlingDefault::JunitXml
+            | SwanlingDefault::ReportJson
+            | SwanlingDefault::TaskProfile
+            | SwanlingDefault::BasePath
+            | SwanlingDefault::HealthCheckUrl
+            | SwanlingDefault::RequestLogSample
+            | SwanlingDefault::GaggleIpc
+            | SwanlingDefault::RegionHeader
+            | SwanlingDefault::InfluxFile
+            | SwanlingDefault::InfluxUdp
+            | SwanlingDefault::Har
+            | SwanlingDefault::LoadProfile => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -4001,7 +9853,36 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
-            | SwanlingDefault::ManagerPort => {
+            | SwanlingDefault::ManagerPort
+            | SwanlingDefault::MaxRequests
+            | SwanlingDefault::AggregateWindow
+            | SwanlingDefault::QuarantineErrorThreshold
+            | SwanlingDefault::MaxConnections
+            | SwanlingDefault::PoolSize
+            | SwanlingDefault::SlowThreshold
+            | SwanlingDefault::MinSamples
+            | SwanlingDefault::WorkerMetricsInterval
+            | SwanlingDefault::RegressionThreshold
+            | SwanlingDefault::StopTime
+            | SwanlingDefault::Cooldown
+            | SwanlingDefault::GaggleSendBuffer
+            | SwanlingDefault::GaggleRecvTimeout
+            | SwanlingDefault::GaggleMaxMsgSize
+            | SwanlingDefault::ManagerSockets
+            | SwanlingDefault::WorkerStartupJitter
+            | SwanlingDefault::WorkerStartupSeed
+            | SwanlingDefault::AdaptiveTargetP95
+            | SwanlingDefault::MaxRedirects
+            | SwanlingDefault::ErrorTail
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::RequestLogSeed
+            | SwanlingDefault::ErrorThreshold
+            | SwanlingDefault::WorkerMetricsBuffer
+            | SwanlingDefault::IterationsPerUser
+            | SwanlingDefault::UserMaxConsecutiveFailures
+            | SwanlingDefault::HealthCheckExpect
+            | SwanlingDefault::PoolIdleTimeout
+            | SwanlingDefault::KeepaliveIdleTimeout => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -4052,7 +9933,14 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
-            | SwanlingDefault::Worker => {
+            | SwanlingDefault::Worker
+            | SwanlingDefault::Soak
+            | SwanlingDefault::CaptureFailureBodies
+            | SwanlingDefault::RampToFailure
+            | SwanlingDefault::PrewarmConnections
+            | SwanlingDefault::HarTiming
+            | SwanlingDefault::Insecure
+            | SwanlingDefault::ExcludeThinkTime => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -4073,7 +9961,42 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
-            | SwanlingDefault::ManagerHost => {
+            | SwanlingDefault::ManagerHost
+            | SwanlingDefault::ClientCert
+            | SwanlingDefault::ClientKey
+            | SwanlingDefault::CaCert
+            | SwanlingDefault::Resolve
+            | SwanlingDefault::Sla
+            | SwanlingDefault::PinTaskSet
+            | SwanlingDefault::OutlierFactor
+            | SwanlingDefault::Spike
+            | SwanlingDefault::OauthTokenUrl
+            | SwanlingDefault::OauthClientId
+            | SwanlingDefault::OauthClientSecret
+            | SwanlingDefault::AwsRegion
+            | SwanlingDefault::AwsService
+            | SwanlingDefault::AwsAccessKeyId
+            | SwanlingDefault::AwsSecretAccessKey
+            | SwanlingDefault::AwsSessionToken
+            | SwanlingDefault::ChartOutput
+            | SwanlingDefault::LogOnly
+            | SwanlingDefault::UserAgents
+            | SwanlingDefault::GaggleTransport
+            | SwanlingDefault::Baseline
+            | SwanlingDefault::SessionFile
+            | SwanlingDefault::GroupBy
+            | SwanlingDefault::JunitXml
+            | SwanlingDefault::ReportJson
+            | SwanlingDefault::TaskProfile
+            | SwanlingDefault::BasePath
+            | SwanlingDefault::HealthCheckUrl
+            | SwanlingDefault::RequestLogSample
+            | SwanlingDefault::GaggleIpc
+            | SwanlingDefault::RegionHeader
+            | SwanlingDefault::InfluxFile
+            | SwanlingDefault::InfluxUdp
+            | SwanlingDefault::Har
+            | SwanlingDefault::LoadProfile => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -4093,7 +10016,36 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
-            | SwanlingDefault::ManagerPort => {
+            | SwanlingDefault::ManagerPort
+            | SwanlingDefault::MaxRequests
+            | SwanlingDefault::AggregateWindow
+            | SwanlingDefault::QuarantineErrorThreshold
+            | SwanlingDefault::MaxConnections
+            | SwanlingDefault::PoolSize
+            | SwanlingDefault::SlowThreshold
+            | SwanlingDefault::MinSamples
+            | SwanlingDefault::WorkerMetricsInterval
+            | SwanlingDefault::RegressionThreshold
+            | SwanlingDefault::StopTime
+            | SwanlingDefault::Cooldown
+            | SwanlingDefault::GaggleSendBuffer
+            | SwanlingDefault::GaggleRecvTimeout
+            | SwanlingDefault::GaggleMaxMsgSize
+            | SwanlingDefault::ManagerSockets
+            | SwanlingDefault::WorkerStartupJitter
+            | SwanlingDefault::WorkerStartupSeed
+            | SwanlingDefault::AdaptiveTargetP95
+            | SwanlingDefault::MaxRedirects
+            | SwanlingDefault::ErrorTail
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::RequestLogSeed
+            | SwanlingDefault::ErrorThreshold
+            | SwanlingDefault::WorkerMetricsBuffer
+            | SwanlingDefault::IterationsPerUser
+            | SwanlingDefault::UserMaxConsecutiveFailures
+            | SwanlingDefault::HealthCheckExpect
+            | SwanlingDefault::PoolIdleTimeout
+            | SwanlingDefault::KeepaliveIdleTimeout => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -4120,7 +10072,11 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
 }
 
 /// Options available when launching a Swanling load test.
+///
+/// When deserialized, for example by a Worker from its Manager, options missing from
+/// the configuration take their default value and unknown options are ignored.
 #[derive(Options, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SwanlingConfiguration {
     /// Displays this help
     #[options(short = "h")]
@@ -4128,13 +10084,28 @@ pub struct SwanlingConfiguration {
     /// Prints version information
     #[options(short = "V")]
     pub version: bool,
-    // Add a blank line after this option
-    #[options(short = "l", help = "Lists all tasks and exits\n")]
+    /// Lists all tasks and exits
+    #[options(short = "l")]
     pub list: bool,
+    /// Validates configuration and exits
+    #[options(no_short)]
+    pub validate_config: bool,
+    // Add a blank line after this option
+    #[options(no_short, help = "Runs each task once and exits\n")]
+    pub smoke: bool,
 
     /// Defines host to load test (ie http://10.21.32.33)
     #[options(short = "H")]
     pub host: String,
+    /// Prepends path to all relative request paths (ie /api/v2)
+    #[options(no_short, meta = "PATH")]
+    pub base_path: String,
+    /// Refuses to start unless this URL responds
+    #[options(no_short, meta = "URL")]
+    pub health_check_url: String,
+    /// Sets status code health check expects (default: any 2xx)
+    #[options(no_short, meta = "STATUS")]
+    pub health_check_expect: Option<usize>,
     /// Sets concurrent users (default: number of CPUs)
     #[options(short = "u")]
     pub users: Option<usize>,
@@ -4144,6 +10115,39 @@ pub struct SwanlingConfiguration {
     /// Stops after (30s, 20m, 3h, 1h30m, etc)
     #[options(short = "t", meta = "TIME")]
     pub run_time: String,
+    /// Gradually stops users over (30s, 20m, 3h, 1h30m, etc)
+    #[options(no_short, meta = "TIME")]
+    pub stop_time: String,
+    /// Keeps collecting metrics after stopping users for (30s, 20m, etc)
+    #[options(no_short, meta = "TIME")]
+    pub cooldown: String,
+    /// Stops after making this many requests
+    #[options(no_short, meta = "VALUE")]
+    pub max_requests: usize,
+    /// Stops each user after this many iterations
+    #[options(no_short, meta = "N")]
+    pub iterations_per_user: Option<usize>,
+    /// Retires a user after this many consecutive failed requests
+    #[options(no_short, meta = "N")]
+    pub user_max_consecutive_failures: Option<usize>,
+    /// Replays requests from a HAR file
+    #[options(no_short, meta = "NAME")]
+    pub har: String,
+    /// Waits between HAR requests as recorded
+    #[options(no_short)]
+    pub har_timing: bool,
+    /// Periodically multiplies users (ie 10x,30s,every=300s)
+    #[options(no_short, meta = "SPIKE")]
+    pub spike: String,
+    /// Adds users until requests fail, then stops
+    #[options(no_short)]
+    pub ramp_to_failure: bool,
+    /// Sets percent of failed requests that stops ramp (default: 5)
+    #[options(no_short, meta = "PERCENT")]
+    pub error_threshold: Option<usize>,
+    /// Adjusts users to trace a load profile from file
+    #[options(no_short, meta = "NAME")]
+    pub load_profile: String,
     /// Enables Swanling log file and sets name
     #[options(short = "G", meta = "NAME")]
     pub swanling_log: String,
@@ -4170,18 +10174,75 @@ pub struct SwanlingConfiguration {
     /// Doesn't track task metrics
     #[options(no_short)]
     pub no_task_metrics: bool,
+    /// Excludes think time from task metrics
+    #[options(no_short)]
+    pub exclude_think_time: bool,
     /// Doesn't display an error summary
     #[options(no_short)]
     pub no_error_summary: bool,
+    /// Prints most recent distinct errors while running
+    #[options(no_short, meta = "VALUE")]
+    pub error_tail: Option<usize>,
+    /// Bounds metrics memory for long soak tests
+    #[options(no_short)]
+    pub soak: bool,
+    /// Combines request names beyond this many
+    #[options(no_short, meta = "VALUE")]
+    pub max_request_names: Option<usize>,
+    /// Checks service level agreement, can be repeated
+    #[options(no_short, meta = "NAME:THRESHOLD")]
+    pub sla: Vec<String>,
+    /// Tracks requests slower than this many milliseconds
+    #[options(no_short, meta = "TIME")]
+    pub slow_threshold: Option<usize>,
+    /// Reports outliers this many std deviations above average
+    #[options(no_short, meta = "FACTOR")]
+    pub outlier_factor: Option<f32>,
+    /// Hides percentiles of requests with fewer samples
+    #[options(no_short, meta = "COUNT")]
+    pub min_samples: Option<usize>,
+    /// Groups request metrics by the value of a label
+    #[options(no_short, meta = "LABEL")]
+    pub group_by: String,
+    /// Groups request metrics by region from a header
+    #[options(no_short, meta = "NAME")]
+    pub region_header: String,
+    /// Compares final metrics against a baseline JSON report
+    #[options(no_short, meta = "NAME")]
+    pub baseline: String,
+    /// Sets percent change from baseline that regresses (default: 10)
+    #[options(no_short, meta = "PERCENT")]
+    pub regression_threshold: Option<usize>,
     /// Create an html-formatted report
     #[options(no_short, meta = "NAME")]
     pub report_file: String,
+    /// Writes a JUnit XML report of requests or SLAs
+    #[options(no_short, meta = "NAME")]
+    pub junit_xml: String,
+    /// Writes the final metrics as a JSON report
+    #[options(no_short, meta = "NAME")]
+    pub report_json: String,
+    /// Writes a folded stack profile of task and request time
+    #[options(no_short, meta = "NAME")]
+    pub task_profile: String,
+    /// Writes latency distribution charts to a directory
+    #[options(no_short, meta = "DIR")]
+    pub chart_output: String,
     /// Sets request log file name
     #[options(short = "R", meta = "NAME")]
     pub request_log: String,
     /// Sets request log format (csv, json, raw)
     #[options(no_short, meta = "FORMAT")]
     pub request_format: Option<SwanlingLogFormat>,
+    /// Only logs requests with these names, comma separated
+    #[options(no_short, meta = "NAMES")]
+    pub log_only: String,
+    /// Only logs this fraction of requests (0.0-1.0)
+    #[options(no_short, meta = "RATE")]
+    pub request_log_sample: Option<f32>,
+    /// Makes request log sampling reproducible
+    #[options(no_short, meta = "NUMBER")]
+    pub request_log_seed: Option<usize>,
     /// Sets task log file name
     #[options(short = "T", meta = "NAME")]
     pub task_log: String,
@@ -4203,6 +10264,9 @@ pub struct SwanlingConfiguration {
     /// Do not include the response body in the debug log
     #[options(no_short)]
     pub no_debug_body: bool,
+    /// Writes the response body of failed requests to the debug log
+    #[options(no_short)]
+    pub capture_failure_bodies: bool,
     // Add a blank line and then an Advanced: header after this option
     #[options(no_short, help = "Tracks additional status code metrics\n\nAdvanced:")]
     pub status_codes: bool,
@@ -4234,6 +10298,72 @@ pub struct SwanlingConfiguration {
     /// Sets maximum requests per second
     #[options(no_short, meta = "VALUE")]
     pub throttle_requests: usize,
+    /// Adapts throttle to keep 95% of responses within MS
+    #[options(no_short, meta = "MS")]
+    pub adaptive_target_p95: Option<usize>,
+    /// Sets maximum concurrent connections
+    #[options(no_short, meta = "VALUE")]
+    pub max_connections: usize,
+    /// Sets idle connections each user keeps open
+    #[options(no_short, meta = "VALUE")]
+    pub pool_size: Option<usize>,
+    /// Sets seconds idle connections are kept open
+    #[options(no_short, meta = "TIME")]
+    pub pool_idle_timeout: Option<usize>,
+    /// Sets seconds connections idle before keep-alive probes
+    #[options(no_short, meta = "TIME")]
+    pub keepalive_idle_timeout: Option<usize>,
+    /// Opens each user's connection before it runs tasks
+    #[options(no_short)]
+    pub prewarm_connections: bool,
+    /// Sets client certificate file for mutual TLS (PEM)
+    #[options(no_short, meta = "NAME")]
+    pub client_cert: String,
+    /// Sets client private key file for mutual TLS (PEM)
+    #[options(no_short, meta = "NAME")]
+    pub client_key: String,
+    /// Adds custom CA certificates to trust (PEM)
+    #[options(no_short, meta = "NAME")]
+    pub ca_cert: String,
+    /// Disables TLS certificate verification (unsafe)
+    #[options(no_short)]
+    pub insecure: bool,
+    /// Resolves host and port to address, can be repeated
+    #[options(no_short, meta = "HOST:PORT:ADDR")]
+    pub resolve: Vec<String>,
+    /// Sets OAuth2 token endpoint for bearer tokens
+    #[options(no_short, meta = "URL")]
+    pub oauth_token_url: String,
+    /// Sets OAuth2 client id
+    #[options(no_short, meta = "ID")]
+    pub oauth_client_id: String,
+    /// Sets OAuth2 client secret
+    #[options(no_short, meta = "SECRET")]
+    pub oauth_client_secret: String,
+    /// Signs requests with AWS SigV4 for region
+    #[options(no_short, meta = "REGION")]
+    pub aws_region: String,
+    /// Sets AWS service to sign requests for
+    #[options(no_short, meta = "SERVICE")]
+    pub aws_service: String,
+    /// Sets AWS access key id (default: $AWS_ACCESS_KEY_ID)
+    #[options(no_short, meta = "ID")]
+    pub aws_access_key_id: String,
+    /// Sets AWS secret access key (default: $AWS_SECRET_ACCESS_KEY)
+    #[options(no_short, meta = "SECRET")]
+    pub aws_secret_access_key: String,
+    /// Sets AWS session token (default: $AWS_SESSION_TOKEN)
+    #[options(no_short, meta = "TOKEN")]
+    pub aws_session_token: String,
+    /// Allocates User-Agents from a weighted list
+    #[options(no_short, meta = "NAME")]
+    pub user_agents: String,
+    /// Saves and reloads user cookies and sessions
+    #[options(no_short, meta = "NAME")]
+    pub session_file: String,
+    /// Sets maximum redirects to follow, 0 disables (default: 10)
+    #[options(no_short, meta = "VALUE")]
+    pub max_redirects: Option<usize>,
     #[options(
         no_short,
         help = "Follows base_url redirect with subsequent requests\n\nGaggle:"
@@ -4249,6 +10379,21 @@ pub struct SwanlingConfiguration {
     /// Tells Manager to ignore load test checksum
     #[options(no_short)]
     pub no_hash_check: bool,
+    /// Sets how many seconds each Manager metrics window lasts
+    #[options(no_short, meta = "TIME")]
+    pub aggregate_window: Option<usize>,
+    /// Quarantines Workers erroring this % more than peers
+    #[options(no_short, meta = "PERCENT")]
+    pub quarantine_error_threshold: Option<usize>,
+    /// Pins a task set to a Worker, can be repeated
+    #[options(no_short, meta = "NAME:N")]
+    pub pin_task_set: Vec<String>,
+    /// Appends InfluxDB line protocol to file
+    #[options(no_short, meta = "NAME")]
+    pub influx_file: String,
+    /// Sends InfluxDB line protocol over UDP
+    #[options(no_short, meta = "HOST:PORT")]
+    pub influx_udp: String,
     /// Sets host Manager listens on (default: 0.0.0.0)
     #[options(no_short, meta = "HOST")]
     pub manager_bind_host: String,
@@ -4264,6 +10409,146 @@ pub struct SwanlingConfiguration {
     /// Sets port Worker connects to (default: 5115)
     #[options(no_short, meta = "PORT")]
     pub manager_port: u16,
+    /// Sets how Manager and Workers communicate (tcp, inproc, ipc)
+    #[options(no_short, meta = "TRANSPORT")]
+    pub gaggle_transport: String,
+    /// Communicates over a Unix domain socket at PATH
+    #[options(no_short, meta = "PATH")]
+    pub gaggle_ipc: String,
+    /// Sets how often Worker pushes metrics in ms (default: 1000)
+    #[options(no_short, meta = "TIME")]
+    pub worker_metrics_interval: Option<usize>,
+    /// Drops metric samples beyond this many buffered on Worker
+    #[options(no_short, meta = "SAMPLES")]
+    pub worker_metrics_buffer: Option<usize>,
+    /// Sets how many messages Manager and Worker sockets buffer
+    #[options(no_short, meta = "MESSAGES")]
+    pub gaggle_send_buffer: Option<usize>,
+    /// Sets how long Manager and Worker sockets wait to receive in ms
+    #[options(no_short, meta = "TIMEOUT")]
+    pub gaggle_recv_timeout: Option<usize>,
+    /// Sets largest message Manager and Worker sockets accept in bytes
+    #[options(no_short, meta = "BYTES")]
+    pub gaggle_max_msg_size: Option<usize>,
+    /// Sets how many sockets Manager shards Workers across (default: 1)
+    #[options(no_short, meta = "VALUE")]
+    pub manager_sockets: Option<usize>,
+    /// Randomly delays Worker connecting to Manager by up to this many ms
+    #[options(no_short, meta = "TIME")]
+    pub worker_startup_jitter: Option<usize>,
+    /// Seeds the Worker startup delay so it is reproducible
+    #[options(no_short, meta = "NUMBER")]
+    pub worker_startup_seed: Option<usize>,
+}
+/// The configuration of a load test launched without any run-time options.
+impl Default for SwanlingConfiguration {
+    fn default() -> Self {
+        // No run-time options are required, so parsing none can't fail.
+        SwanlingConfiguration::parse_args_default::<&str>(&[]).unwrap()
+    }
+}
+
+#[cfg(feature = "gaggle")]
+/// Returns the address the Manager listens on and Workers connect to for a shard, each
+/// listening on the next port after the last. Over the inproc transport the Manager and
+/// Workers must run in the same process, and the host is ignored. Over the ipc transport
+/// they must run on the same host, and communicate over the `--gaggle-ipc` Unix domain
+/// socket, with a suffix naming each shard after the first.
+fn gaggle_address(
+    configuration: &SwanlingConfiguration,
+    host: &str,
+    port: u16,
+    shard: usize,
+) -> String {
+    match configuration.gaggle_transport.as_str() {
+        "inproc" => format!("inproc://swanling-{}", port + shard as u16),
+        "ipc" if shard == 0 => format!("ipc://{}", configuration.gaggle_ipc),
+        "ipc" => format!("ipc://{}.{}", configuration.gaggle_ipc, shard),
+        _ => format!("tcp://{}:{}", host, port + shard as u16),
+    }
+}
+
+#[cfg(feature = "gaggle")]
+/// Applies the configured socket options to the socket the Manager listens on or a Worker
+/// connects with. Options that aren't configured keep the nng defaults.
+fn configure_gaggle_socket(
+    socket: &Socket,
+    configuration: &SwanlingConfiguration,
+) -> Result<(), nng::Error> {
+    use nng::options::{Options, RecvMaxSize, RecvTimeout, SendBufferSize};
+
+    if let Some(send_buffer) = configuration.gaggle_send_buffer {
+        socket.set_opt::<SendBufferSize>(send_buffer as i32)?;
+    }
+    if let Some(recv_timeout) = configuration.gaggle_recv_timeout {
+        socket
+            .set_opt::<RecvTimeout>(Some(std::time::Duration::from_millis(recv_timeout as u64)))?;
+    }
+    // The Manager checks the size of each batch of metrics itself, as nng drops an oversized
+    // message and closes the connection without telling the Worker why.
+    if let (false, Some(max_msg_size)) = (configuration.manager, configuration.gaggle_max_msg_size)
+    {
+        socket.set_opt::<RecvMaxSize>(max_msg_size)?;
+    }
+    Ok(())
+}
+
+/// Describes each task set with its weight, and each of its tasks with its weight and when
+/// it runs, as listed by the `--list` flag. Task sets and tasks are numbered the same as in
+/// the metrics.
+fn task_list(task_sets: &[SwanlingTaskSet]) -> String {
+    let mut list = "Available tasks:\n".to_string();
+    for (task_set_index, task_set) in task_sets.iter().enumerate() {
+        list.push_str(&format!(
+            " - {}: {} (weight: {})\n",
+            task_set_index + 1,
+            task_set.name,
+            task_set.weight
+        ));
+        for (task_index, task) in task_set.tasks.iter().enumerate() {
+            let mut details = vec![format!("weight: {}", task.weight)];
+            if task.sequence > 0 {
+                details.push(format!("sequence: {}", task.sequence));
+            }
+            if task.on_start {
+                details.push("on_start".to_string());
+            }
+            if task.on_stop {
+                details.push("on_stop".to_string());
+            }
+            // Unnamed tasks are only listed by number.
+            let name = if task.name.is_empty() {
+                (task_index + 1).to_string()
+            } else {
+                format!("{}: {}", task_index + 1, task.name)
+            };
+            list.push_str(&format!("    o {} ({})\n", name, details.join(", ")));
+        }
+    }
+    list
+}
+
+/// Returns the tasks of a [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html) in the
+/// order a smoke test runs them: first the `on_start` tasks, then the normal tasks, and
+/// finally the `on_stop` tasks. Within each group sequenced tasks run first, in order of
+/// their sequence, followed by unsequenced tasks in the order they were registered.
+fn smoke_test_tasks(task_set: &SwanlingTaskSet) -> Vec<&SwanlingTask> {
+    let ordered = |filter: &dyn Fn(&SwanlingTask) -> bool| {
+        let mut tasks: Vec<&SwanlingTask> = task_set.tasks.iter().filter(|t| filter(t)).collect();
+        // A stable sort keeps unsequenced tasks in the order they were registered.
+        tasks.sort_by_key(|task| {
+            if task.sequence > 0 {
+                task.sequence
+            } else {
+                usize::MAX
+            }
+        });
+        tasks
+    };
+    let mut tasks = ordered(&|task| task.on_start);
+    tasks.extend(ordered(&|task| !task.on_start && !task.on_stop));
+    tasks.extend(ordered(&|task| task.on_stop));
+    tasks
 }
 
 /// Use the configured SwanlingScheduler to allocate all [`SwanlingTask`](./swanling/struct.SwanlingTask.html)s
@@ -4551,6 +10836,61 @@ mod test {
         let manager_bind_port: usize = 1221;
         let manager_host = "127.0.0.1".to_string();
         let manager_port: usize = 1221;
+        let client_cert = "custom-swanling-client.pem".to_string();
+        let client_key = "custom-swanling-client.key".to_string();
+        let ca_cert = "custom-swanling-ca.pem".to_string();
+        let max_requests: usize = 100;
+        let resolve = "example.com:443:127.0.0.1".to_string();
+        let sla = "/search:p95=200".to_string();
+        let pin_task_set = "WriteHeavy:1".to_string();
+        let spike = "10x,30s,every=300s".to_string();
+        let oauth_token_url = "https://auth.example.com/token".to_string();
+        let oauth_client_id = "swanling".to_string();
+        let oauth_client_secret = "secret".to_string();
+        let aws_region = "us-east-1".to_string();
+        let aws_service = "execute-api".to_string();
+        let aws_access_key_id = "AKIDEXAMPLE".to_string();
+        let aws_secret_access_key = "secret".to_string();
+        let aws_session_token = "token".to_string();
+        let chart_output = "custom-swanling-charts".to_string();
+        let log_only = "/checkout,/search".to_string();
+        let user_agents = "custom-user-agents.txt".to_string();
+        let gaggle_transport = "inproc".to_string();
+        let worker_metrics_interval: usize = 250;
+        let baseline = "baseline.json".to_string();
+        let regression_threshold: usize = 5;
+        let stop_time: usize = 5;
+        let cooldown: usize = 10;
+        let gaggle_send_buffer: usize = 64;
+        let gaggle_recv_timeout: usize = 30_000;
+        let gaggle_max_msg_size: usize = 4_194_304;
+        let session_file = "custom-sessions.json".to_string();
+        let manager_sockets: usize = 4;
+        let worker_startup_jitter: usize = 500;
+        let worker_startup_seed: usize = 42;
+        let adaptive_target_p95: usize = 250;
+        let group_by = "region".to_string();
+        let junit_xml = "junit.xml".to_string();
+        let report_json = "report.json".to_string();
+        let task_profile = "profile.folded".to_string();
+        let base_path = "/api/v2".to_string();
+        let health_check_url = "http://example.com/health".to_string();
+        let health_check_expect: usize = 204;
+        let max_redirects: usize = 3;
+        let error_tail: usize = 5;
+        let max_request_names: usize = 500;
+        let request_log_sample = "0.01".to_string();
+        let request_log_seed: usize = 7;
+        let error_threshold: usize = 5;
+        let gaggle_ipc = "/tmp/swanling.ipc".to_string();
+        let region_header = "cf-ray".to_string();
+        let worker_metrics_buffer: usize = 10_000;
+        let influx_file = "swanling-influx.txt".to_string();
+        let influx_udp = "127.0.0.1:8089".to_string();
+        let iterations_per_user: usize = 3;
+        let user_max_consecutive_failures: usize = 5;
+        let har = "recording.har".to_string();
+        let load_profile = "daily.csv".to_string();
 
         let swanling_attack = SwanlingAttack::initialize()
             .unwrap()
@@ -4576,6 +10916,8 @@ mod test {
             .unwrap()
             .set_default(SwanlingDefault::NoTaskMetrics, true)
             .unwrap()
+            .set_default(SwanlingDefault::ExcludeThinkTime, true)
+            .unwrap()
             .set_default(SwanlingDefault::NoErrorSummary, true)
             .unwrap()
             .set_default(SwanlingDefault::NoTelnet, true)
@@ -4630,6 +10972,161 @@ mod test {
             .set_default(SwanlingDefault::ManagerHost, manager_host.as_str())
             .unwrap()
             .set_default(SwanlingDefault::ManagerPort, manager_port)
+            .unwrap()
+            .set_default(SwanlingDefault::ClientCert, client_cert.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::ClientKey, client_key.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::CaCert, ca_cert.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::MaxRequests, max_requests)
+            .unwrap()
+            .set_default(SwanlingDefault::Resolve, resolve.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::Sla, sla.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::Spike, spike.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::AggregateWindow, 30)
+            .unwrap()
+            .set_default(SwanlingDefault::QuarantineErrorThreshold, 25)
+            .unwrap()
+            .set_default(SwanlingDefault::PinTaskSet, pin_task_set.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::OutlierFactor, "2.5")
+            .unwrap()
+            .set_default(SwanlingDefault::OauthTokenUrl, oauth_token_url.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::OauthClientId, oauth_client_id.as_str())
+            .unwrap()
+            .set_default(
+                SwanlingDefault::OauthClientSecret,
+                oauth_client_secret.as_str(),
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::AwsRegion, aws_region.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::AwsService, aws_service.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::AwsAccessKeyId, aws_access_key_id.as_str())
+            .unwrap()
+            .set_default(
+                SwanlingDefault::AwsSecretAccessKey,
+                aws_secret_access_key.as_str(),
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::AwsSessionToken, aws_session_token.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::MaxConnections, 200)
+            .unwrap()
+            .set_default(SwanlingDefault::PoolSize, 0)
+            .unwrap()
+            .set_default(SwanlingDefault::PoolIdleTimeout, 30)
+            .unwrap()
+            .set_default(SwanlingDefault::KeepaliveIdleTimeout, 60)
+            .unwrap()
+            .set_default(SwanlingDefault::Soak, true)
+            .unwrap()
+            .set_default(SwanlingDefault::ChartOutput, chart_output.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::LogOnly, log_only.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::UserAgents, user_agents.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::SlowThreshold, 1000)
+            .unwrap()
+            .set_default(SwanlingDefault::MinSamples, 20)
+            .unwrap()
+            .set_default(SwanlingDefault::GaggleTransport, gaggle_transport.as_str())
+            .unwrap()
+            .set_default(
+                SwanlingDefault::WorkerMetricsInterval,
+                worker_metrics_interval,
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::CaptureFailureBodies, true)
+            .unwrap()
+            .set_default(SwanlingDefault::Baseline, baseline.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::RegressionThreshold, regression_threshold)
+            .unwrap()
+            .set_default(SwanlingDefault::StopTime, stop_time)
+            .unwrap()
+            .set_default(SwanlingDefault::Cooldown, cooldown)
+            .unwrap()
+            .set_default(SwanlingDefault::GaggleSendBuffer, gaggle_send_buffer)
+            .unwrap()
+            .set_default(SwanlingDefault::GaggleRecvTimeout, gaggle_recv_timeout)
+            .unwrap()
+            .set_default(SwanlingDefault::GaggleMaxMsgSize, gaggle_max_msg_size)
+            .unwrap()
+            .set_default(SwanlingDefault::SessionFile, session_file.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::ManagerSockets, manager_sockets)
+            .unwrap()
+            .set_default(SwanlingDefault::WorkerStartupJitter, worker_startup_jitter)
+            .unwrap()
+            .set_default(SwanlingDefault::WorkerStartupSeed, worker_startup_seed)
+            .unwrap()
+            .set_default(SwanlingDefault::AdaptiveTargetP95, adaptive_target_p95)
+            .unwrap()
+            .set_default(SwanlingDefault::GroupBy, group_by.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::JunitXml, junit_xml.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::ReportJson, report_json.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::TaskProfile, task_profile.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::BasePath, base_path.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::HealthCheckUrl, health_check_url.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::HealthCheckExpect, health_check_expect)
+            .unwrap()
+            .set_default(SwanlingDefault::MaxRedirects, max_redirects)
+            .unwrap()
+            .set_default(SwanlingDefault::ErrorTail, error_tail)
+            .unwrap()
+            .set_default(SwanlingDefault::MaxRequestNames, max_request_names)
+            .unwrap()
+            .set_default(
+                SwanlingDefault::RequestLogSample,
+                request_log_sample.as_str(),
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::RequestLogSeed, request_log_seed)
+            .unwrap()
+            .set_default(SwanlingDefault::RampToFailure, true)
+            .unwrap()
+            .set_default(SwanlingDefault::ErrorThreshold, error_threshold)
+            .unwrap()
+            .set_default(SwanlingDefault::GaggleIpc, gaggle_ipc.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::RegionHeader, region_header.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::WorkerMetricsBuffer, worker_metrics_buffer)
+            .unwrap()
+            .set_default(SwanlingDefault::PrewarmConnections, true)
+            .unwrap()
+            .set_default(SwanlingDefault::InfluxFile, influx_file.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::InfluxUdp, influx_udp.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::IterationsPerUser, iterations_per_user)
+            .unwrap()
+            .set_default(
+                SwanlingDefault::UserMaxConsecutiveFailures,
+                user_max_consecutive_failures,
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::Har, har.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::HarTiming, true)
+            .unwrap()
+            .set_default(SwanlingDefault::Insecure, true)
+            .unwrap()
+            .set_default(SwanlingDefault::LoadProfile, load_profile.as_str())
             .unwrap();
 
         assert!(swanling_attack.defaults.host == Some(host));
@@ -4644,6 +11141,7 @@ mod test {
         assert!(swanling_attack.defaults.no_reset_metrics == Some(true));
         assert!(swanling_attack.defaults.no_metrics == Some(true));
         assert!(swanling_attack.defaults.no_task_metrics == Some(true));
+        assert!(swanling_attack.defaults.exclude_think_time == Some(true));
         assert!(swanling_attack.defaults.no_error_summary == Some(true));
         assert!(swanling_attack.defaults.no_telnet == Some(true));
         assert!(swanling_attack.defaults.no_websocket == Some(true));
@@ -4670,5 +11168,184 @@ mod test {
         assert!(swanling_attack.defaults.worker == Some(true));
         assert!(swanling_attack.defaults.manager_host == Some(manager_host));
         assert!(swanling_attack.defaults.manager_port == Some(manager_port as u16));
+        assert!(swanling_attack.defaults.client_cert == Some(client_cert));
+        assert!(swanling_attack.defaults.client_key == Some(client_key));
+        assert!(swanling_attack.defaults.ca_cert == Some(ca_cert));
+        assert!(swanling_attack.defaults.max_requests == Some(max_requests));
+        assert!(swanling_attack.defaults.resolve == Some(resolve));
+        assert!(swanling_attack.defaults.sla == Some(sla));
+        assert!(swanling_attack.defaults.spike == Some(spike));
+        assert!(swanling_attack.defaults.aggregate_window == Some(30));
+        assert!(swanling_attack.defaults.quarantine_error_threshold == Some(25));
+        assert!(swanling_attack.defaults.pin_task_set == Some(pin_task_set));
+        assert!(swanling_attack.defaults.outlier_factor == Some("2.5".to_string()));
+        assert!(swanling_attack.defaults.oauth_token_url == Some(oauth_token_url));
+        assert!(swanling_attack.defaults.oauth_client_id == Some(oauth_client_id));
+        assert!(swanling_attack.defaults.oauth_client_secret == Some(oauth_client_secret));
+        assert!(swanling_attack.defaults.aws_region == Some(aws_region));
+        assert!(swanling_attack.defaults.aws_service == Some(aws_service));
+        assert!(swanling_attack.defaults.aws_access_key_id == Some(aws_access_key_id));
+        assert!(swanling_attack.defaults.aws_secret_access_key == Some(aws_secret_access_key));
+        assert!(swanling_attack.defaults.aws_session_token == Some(aws_session_token));
+        assert!(swanling_attack.defaults.max_connections == Some(200));
+        assert!(swanling_attack.defaults.pool_size == Some(0));
+        assert!(swanling_attack.defaults.pool_idle_timeout == Some(30));
+        assert!(swanling_attack.defaults.keepalive_idle_timeout == Some(60));
+        assert!(swanling_attack.defaults.soak == Some(true));
+        assert!(swanling_attack.defaults.chart_output == Some(chart_output));
+        assert!(swanling_attack.defaults.log_only == Some(log_only));
+        assert!(swanling_attack.defaults.user_agents == Some(user_agents));
+        assert!(swanling_attack.defaults.slow_threshold == Some(1000));
+        assert!(swanling_attack.defaults.min_samples == Some(20));
+        assert!(swanling_attack.defaults.gaggle_transport == Some(gaggle_transport));
+        assert!(swanling_attack.defaults.worker_metrics_interval == Some(worker_metrics_interval));
+        assert!(swanling_attack.defaults.capture_failure_bodies == Some(true));
+        assert!(swanling_attack.defaults.baseline == Some(baseline));
+        assert!(swanling_attack.defaults.regression_threshold == Some(regression_threshold));
+        assert!(swanling_attack.defaults.stop_time == Some(stop_time));
+        assert!(swanling_attack.defaults.cooldown == Some(cooldown));
+        assert!(swanling_attack.defaults.gaggle_send_buffer == Some(gaggle_send_buffer));
+        assert!(swanling_attack.defaults.gaggle_recv_timeout == Some(gaggle_recv_timeout));
+        assert!(swanling_attack.defaults.gaggle_max_msg_size == Some(gaggle_max_msg_size));
+        assert!(swanling_attack.defaults.session_file == Some(session_file));
+        assert!(swanling_attack.defaults.manager_sockets == Some(manager_sockets));
+        assert!(swanling_attack.defaults.worker_startup_jitter == Some(worker_startup_jitter));
+        assert!(swanling_attack.defaults.worker_startup_seed == Some(worker_startup_seed));
+        assert!(swanling_attack.defaults.adaptive_target_p95 == Some(adaptive_target_p95));
+        assert!(swanling_attack.defaults.group_by == Some(group_by));
+        assert!(swanling_attack.defaults.junit_xml == Some(junit_xml));
+        assert!(swanling_attack.defaults.report_json == Some(report_json));
+        assert!(swanling_attack.defaults.task_profile == Some(task_profile));
+        assert!(swanling_attack.defaults.base_path == Some(base_path));
+        assert!(swanling_attack.defaults.health_check_url == Some(health_check_url));
+        assert!(swanling_attack.defaults.health_check_expect == Some(health_check_expect));
+        assert!(swanling_attack.defaults.max_redirects == Some(max_redirects));
+        assert!(swanling_attack.defaults.error_tail == Some(error_tail));
+        assert!(swanling_attack.defaults.max_request_names == Some(max_request_names));
+        assert!(swanling_attack.defaults.request_log_sample == Some(request_log_sample));
+        assert!(swanling_attack.defaults.request_log_seed == Some(request_log_seed));
+        assert!(swanling_attack.defaults.ramp_to_failure == Some(true));
+        assert!(swanling_attack.defaults.error_threshold == Some(error_threshold));
+        assert!(swanling_attack.defaults.gaggle_ipc == Some(gaggle_ipc));
+        assert!(swanling_attack.defaults.region_header == Some(region_header));
+        assert!(swanling_attack.defaults.worker_metrics_buffer == Some(worker_metrics_buffer));
+        assert!(swanling_attack.defaults.prewarm_connections == Some(true));
+        assert!(swanling_attack.defaults.influx_file == Some(influx_file.to_string()));
+        assert!(swanling_attack.defaults.influx_udp == Some(influx_udp.to_string()));
+        assert!(swanling_attack.defaults.iterations_per_user == Some(iterations_per_user));
+        assert!(
+            swanling_attack.defaults.user_max_consecutive_failures
+                == Some(user_max_consecutive_failures)
+        );
+        assert!(swanling_attack.defaults.har == Some(har.to_string()));
+        assert!(swanling_attack.defaults.load_profile == Some(load_profile));
+        assert!(swanling_attack.defaults.har_timing == Some(true));
+        assert!(swanling_attack.defaults.insecure == Some(true));
+    }
+
+    #[test]
+    fn list_tasks() {
+        async fn task(_user: &SwanlingUser) -> swanling::SwanlingTaskResult {
+            Ok(())
+        }
+
+        let task_sets = vec![
+            taskset!("Users")
+                .set_weight(3)
+                .unwrap()
+                .register_task(task!(task).set_name("login").set_on_start())
+                .register_task(task!(task).set_name("browse").set_weight(2).unwrap())
+                .register_task(task!(task).set_sequence(1)),
+            taskset!("Admins").register_task(task!(task).set_name("logout").set_on_stop()),
+        ];
+        assert_eq!(
+            task_list(&task_sets),
+            concat!(
+                "Available tasks:\n",
+                " - 1: Users (weight: 3)\n",
+                "    o 1: login (weight: 1, on_start)\n",
+                "    o 2: browse (weight: 2)\n",
+                "    o 3 (weight: 1, sequence: 1)\n",
+                " - 2: Admins (weight: 1)\n",
+                "    o 1: logout (weight: 1, on_stop)\n",
+            )
+        );
+    }
+
+    #[test]
+    fn deserialize_configuration() {
+        let configuration =
+            SwanlingConfiguration::parse_args_default(&["--users", "5", "--host", "http://x/"])
+                .unwrap();
+        let mut value = serde_json::to_value(&configuration).unwrap();
+        let options = value.as_object_mut().unwrap();
+
+        // Options unknown to this version are ignored, and missing options take their
+        // default value.
+        options.insert("added".to_string(), serde_json::json!(true));
+        options.remove("host");
+        let configuration: SwanlingConfiguration = serde_json::from_value(value).unwrap();
+        assert_eq!(configuration.users, Some(5));
+        assert_eq!(configuration.host, SwanlingConfiguration::default().host);
+    }
+
+    #[test]
+    fn spike() {
+        assert_eq!(
+            SwanlingSpike::parse("10x,30s,every=300s"),
+            Some(SwanlingSpike {
+                multiplier: 10,
+                duration: 30,
+                every: 300,
+            })
+        );
+        assert_eq!(
+            SwanlingSpike::parse("2x, 1m, every=1h"),
+            Some(SwanlingSpike {
+                multiplier: 2,
+                duration: 60,
+                every: 3600,
+            })
+        );
+        // A multiplier of 1 doesn't add any users.
+        assert_eq!(SwanlingSpike::parse("1x,30s,every=300s"), None);
+        // The spike must end before the next one starts.
+        assert_eq!(SwanlingSpike::parse("10x,300s,every=300s"), None);
+        assert_eq!(SwanlingSpike::parse("10x,0s,every=300s"), None);
+        assert_eq!(SwanlingSpike::parse("10,30s,every=300s"), None);
+        assert_eq!(SwanlingSpike::parse("10x,30s,300s"), None);
+        assert_eq!(SwanlingSpike::parse("10x,30s"), None);
+        assert_eq!(SwanlingSpike::parse("10x,30s,every=300s,5"), None);
+    }
+
+    #[test]
+    fn load_profile() {
+        let load_profile =
+            SwanlingLoadProfile::parse("# offset,users\n0,10\n\n60, 40\n120,20\n").unwrap();
+        assert_eq!(load_profile.points, vec![(0, 10), (60, 40), (120, 20)]);
+        assert_eq!(load_profile.duration(), 120);
+
+        // Users are interpolated between points.
+        assert_eq!(load_profile.users_at(0.0), 10);
+        assert_eq!(load_profile.users_at(30.0), 25);
+        assert_eq!(load_profile.users_at(60.0), 40);
+        assert_eq!(load_profile.users_at(90.0), 30);
+        // After the last point its users keep running.
+        assert_eq!(load_profile.users_at(300.0), 20);
+
+        // Before the first point its users run.
+        let load_profile = SwanlingLoadProfile::parse("30,5\n60,0").unwrap();
+        assert_eq!(load_profile.users_at(10.0), 5);
+        assert_eq!(load_profile.users_at(45.0), 3);
+        assert_eq!(load_profile.users_at(60.0), 0);
+
+        assert!(SwanlingLoadProfile::parse("").is_err());
+        assert!(SwanlingLoadProfile::parse("# no points").is_err());
+        assert!(SwanlingLoadProfile::parse("0,10\n60").is_err());
+        assert!(SwanlingLoadProfile::parse("0,10\n60,20,30").is_err());
+        assert!(SwanlingLoadProfile::parse("0,10\n1m,20").is_err());
+        assert!(SwanlingLoadProfile::parse("0,10\n60,-5").is_err());
+        // Offsets must increase.
+        assert!(SwanlingLoadProfile::parse("0,10\n60,20\n60,30").is_err());
     }
 }
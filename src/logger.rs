@@ -28,7 +28,11 @@
 //! configuration option.
 //!
 //! Each [`SwanlingRequestMetric`] object generated by all [`SwanlingUser`](../swanling/struct.SwanlingUser.html)
-//! threads during a load test is written to this log file.
+//! threads during a load test is written to this log file. To keep the log focused, it can be
+//! limited to a comma separated list of request names with the `--log-only` command-line option,
+//! or the [`SwanlingDefault::LogOnly`](../enum.SwanlingDefault.html#variant.LogOnly) default
+//! configuration option. Metrics are still collected for all requests, and requests slower
+//! than the `--slow-threshold` command-line option are always logged.
 //!
 //! ## Task File logger
 //! The Swanling tasks logger is enabled with the `--task-log` command-line option, or the
@@ -195,7 +199,10 @@ impl FromStr for SwanlingLogFormat {
 // @TODO this should be automatically derived from the structure.
 fn debug_csv_header() -> String {
     // No quotes needed in header.
-    format!("{},{},{},{}", "tag", "request", "header", "body")
+    format!(
+        "{},{},{},{},{}",
+        "tag", "request", "header", "body", "fields"
+    )
 }
 
 // @TODO this should be automatically derived from the structure.
@@ -220,7 +227,7 @@ fn error_csv_header() -> String {
 fn requests_csv_header() -> String {
     // No quotes needed in header.
     format!(
-        "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
         "elapsed",
         "method",
         "name",
@@ -235,6 +242,10 @@ fn requests_csv_header() -> String {
         "error",
         "coordinated_omission_elapsed",
         "user_cadence",
+        "bytes_sent",
+        "redirects",
+        "throttle_wait",
+        "dns_time",
     )
 }
 
@@ -285,8 +296,12 @@ impl SwanlingLogger<SwanlingDebug> for SwanlingConfiguration {
         // Put quotes around all fields, as they are all strings.
         // @TODO: properly handle Option<>; also, escape inner quotes etc.
         format!(
-            "\"{}\",\"{:?}\",\"{:?}\",\"{:?}\"",
-            debug.tag, debug.request, debug.header, debug.body
+            "\"{}\",\"{:?}\",\"{:?}\",\"{:?}\",\"{:?}\"",
+            debug.tag,
+            debug.request,
+            debug.header,
+            debug.body,
+            debug.fields.as_ref().map(|fields| fields.to_string())
         )
     }
 }
@@ -350,7 +365,7 @@ impl SwanlingLogger<SwanlingRequestMetric> for SwanlingConfiguration {
     fn prepare_csv(&self, request: &SwanlingRequestMetric) -> String {
         format!(
             // Put quotes around name, url and final_url as they are strings.
-            "{},{},\"{}\",\"{}\",\"{}\",{},{},{},{},{},{},{},{},{}",
+            "{},{},\"{}\",\"{}\",\"{}\",{},{},{},{},{},{},{},{},{},{},{},{},{}",
             request.elapsed,
             request.method,
             request.name,
@@ -365,6 +380,13 @@ impl SwanlingLogger<SwanlingRequestMetric> for SwanlingConfiguration {
             request.error,
             request.coordinated_omission_elapsed,
             request.user_cadence,
+            request.bytes_sent,
+            request.redirects,
+            request.throttle_wait,
+            // Empty if no host name was resolved.
+            request
+                .dns_time
+                .map_or_else(String::new, |dns_time| dns_time.to_string()),
         )
     }
 }
@@ -434,6 +456,13 @@ impl SwanlingConfiguration {
                 self.request_log = default_request_log;
             }
         }
+        // Configure which requests are logged, if limited.
+        if self.log_only.is_empty() {
+            // Set default, if configured.
+            if let Some(default_log_only) = defaults.log_only.clone() {
+                self.log_only = default_log_only;
+            }
+        }
         // Configure task_log path if enabled.
         if self.task_log.is_empty() {
             // Set default, if configured.
@@ -443,6 +472,18 @@ impl SwanlingConfiguration {
         }
     }
 
+    /// Returns true if this request is written to the request log, which is all requests
+    /// unless limited with `--log-only`. Requests slower than `--slow-threshold` are always
+    /// written.
+    pub(crate) fn log_request(&self, request: &SwanlingRequestMetric) -> bool {
+        self.log_only.is_empty()
+            || self
+                .log_only
+                .split(',')
+                .any(|log_only| log_only.trim() == request.name)
+            || request.is_slow(self.slow_threshold)
+    }
+
     /// Spawns the logger thread if one or more loggers are enabled.
     pub(crate) async fn setup_loggers(
         &mut self,
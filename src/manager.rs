@@ -1,25 +1,67 @@
 use lazy_static::lazy_static;
 use nng::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::BufWriter;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{thread, time};
 
+use crate::controller::{
+    SwanlingControllerCommand, SwanlingControllerRequest, SwanlingControllerResponseMessage,
+};
 use crate::metrics::{
-    self, SwanlingErrorMetricAggregate, SwanlingErrorMetrics, SwanlingRequestMetricAggregate,
-    SwanlingRequestMetrics, SwanlingTaskMetricAggregate, SwanlingTaskMetrics,
+    self, SwanlingControlAction, SwanlingCooldownWindow, SwanlingCustomMetrics,
+    SwanlingErrorMetricAggregate, SwanlingErrorMetrics, SwanlingInfluxWriter, SwanlingIterations,
+    SwanlingMetricsCallbackThread, SwanlingMetricsWindow, SwanlingQuarantinedWorker,
+    SwanlingRequestMetricAggregate, SwanlingRequestMetricTimingData, SwanlingRequestMetrics,
+    SwanlingTaskMetricAggregate, SwanlingTaskMetrics,
 };
+use crate::swanling::SwanlingReconfiguration;
 use crate::util;
-use crate::worker::GaggleMetrics;
-use crate::{SwanlingAttack, SwanlingConfiguration, SwanlingUserCommand};
+use crate::worker::{oversized_metrics_error, GaggleMetrics};
+use crate::{
+    configure_gaggle_socket, gaggle_address, SwanlingAttack, SwanlingConfiguration,
+    SwanlingUserCommand,
+};
 
 /// How long the manager will wait for all workers to stop after the load test ends.
 const GRACEFUL_SHUTDOWN_TIMEOUT: usize = 30;
 
-/// All elements required to initialize a user in a worker process.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How many milliseconds a Worker's clock can differ from the Manager's before warning.
+const WORKER_CLOCK_OFFSET_WARNING: i64 = 1_000;
+
+/// How many requests a Worker, and its peers, must make before their error rates are
+/// compared to decide whether to quarantine it.
+const QUARANTINE_MIN_REQUESTS: usize = 100;
+
+/// All elements required to initialize a worker process.
+///
+/// Sent to each worker one time, so the configuration shared by all users is only
+/// serialized once rather than once per user. Fields missing from the message take their
+/// default value and unknown fields are ignored, so a Worker can still be initialized by a
+/// Manager running a slightly older or newer version of the load test.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SwanlingWorkerInitializer {
+    /// A copy of the global SwanlingConfiguration, shared by all users on the worker.
+    pub config: SwanlingConfiguration,
+    /// How long the load test should run, in seconds.
+    pub run_time: usize,
+    /// How long to spend gradually stopping users, in seconds.
+    pub stop_time: usize,
+    /// How long to keep collecting metrics after stopping users, in seconds.
+    pub cooldown: usize,
+    /// Numerical identifier for worker.
+    pub worker_id: usize,
+    /// The users to start on the worker.
+    pub users: Vec<SwanlingUserInitializer>,
+}
+
+/// All elements required to initialize a user in a worker process. Like the
+/// [`SwanlingWorkerInitializer`] it's sent in, missing fields take their default value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SwanlingUserInitializer {
     /// An index into the internal `SwanlingTest.task_sets` vector, indicating which SwanlingTaskSet is running.
     pub task_sets_index: usize,
@@ -29,12 +71,29 @@ pub struct SwanlingUserInitializer {
     pub min_wait: usize,
     /// Maximum amount of time to sleep after running a task.
     pub max_wait: usize,
-    /// A local copy of the global SwanlingConfiguration.
-    pub config: SwanlingConfiguration,
-    /// How long the load test should run, in seconds.
-    pub run_time: usize,
-    /// Numerical identifier for worker.
-    pub worker_id: usize,
+    /// The User-Agent allocated to this user, if `--user-agents` is configured.
+    pub user_agent: Option<String>,
+    /// The name of the task set, or class of users, this user belongs to.
+    pub user_class: String,
+}
+
+/// One of the sockets the Manager listens on, served by its own thread.
+struct ManagerShard {
+    /// The socket the Workers in this shard are connected to.
+    server: Socket,
+    /// Tells the thread serving this shard that a reply was sent, so it can receive the
+    /// next message.
+    replied_tx: flume::Sender<()>,
+}
+
+/// A message from a Worker, deserialized by the thread serving its shard.
+struct WorkerMessage {
+    /// Which shard received the message, and must send the reply.
+    shard: usize,
+    /// The pipe identifying the Worker.
+    pipe: Pipe,
+    /// The metrics pushed by the Worker, or why they couldn't be read.
+    gaggle_metrics: std::result::Result<Vec<GaggleMetrics>, String>,
 }
 
 // Mutable singleton globally tracking how many workers are currently being managed.
@@ -59,6 +118,84 @@ fn distribute_users(swanling_attack: &SwanlingAttack) -> (usize, usize) {
     (users_per_worker, users_remainder)
 }
 
+// Map each pinned task set, by index, to the Workers (numbered from 1) it is pinned to.
+fn pinned_task_sets(swanling_attack: &SwanlingAttack) -> HashMap<usize, Vec<usize>> {
+    let mut pins: HashMap<usize, Vec<usize>> = HashMap::new();
+    for pin in &swanling_attack.configuration.pin_task_set {
+        // Pins are validated when the load test is configured, so they always parse.
+        if let Some((name, worker)) = util::parse_pin_task_set(pin) {
+            if let Some(index) = swanling_attack
+                .task_sets
+                .iter()
+                .position(|t| t.name == name)
+            {
+                let workers = pins.entry(index).or_default();
+                if !workers.contains(&worker) {
+                    workers.push(worker);
+                }
+            }
+        }
+    }
+    pins
+}
+
+// Plan which users each Worker starts, returning for each Worker the indexes of its users in
+// the order they are sent. Users of pinned task sets are placed first, each on whichever of
+// its Workers has the most room left. The remaining users then fill each Worker in turn, so
+// without any pins every Worker gets the same contiguous batch of users it always has.
+fn allocate_users(
+    task_sets_indexes: &[usize],
+    pins: &HashMap<usize, Vec<usize>>,
+    users_per_worker: usize,
+    users_remainder: usize,
+    expect_workers: usize,
+) -> Vec<Vec<usize>> {
+    let quotas: Vec<usize> = (0..expect_workers)
+        .map(|worker| users_per_worker + if worker < users_remainder { 1 } else { 0 })
+        .collect();
+    let mut batches: Vec<Vec<usize>> = vec![Vec::new(); expect_workers];
+
+    // Users are handed out from the end of the list.
+    let mut unpinned = Vec::new();
+    for (user, task_sets_index) in task_sets_indexes.iter().enumerate().rev() {
+        let workers = match pins.get(task_sets_index) {
+            Some(workers) => workers,
+            None => {
+                unpinned.push(user);
+                continue;
+            }
+        };
+        // Prefer the pinned Worker with the most room left, overfilling the least loaded
+        // pinned Worker once they are all full.
+        let worker = workers
+            .iter()
+            .map(|worker| worker - 1)
+            .max_by_key(|&worker| {
+                (
+                    quotas[worker].saturating_sub(batches[worker].len()),
+                    std::cmp::Reverse(batches[worker].len()),
+                    std::cmp::Reverse(worker),
+                )
+            })
+            .unwrap();
+        batches[worker].push(user);
+    }
+
+    for user in unpinned {
+        let worker =
+            match (0..expect_workers).find(|&worker| batches[worker].len() < quotas[worker]) {
+                Some(worker) => worker,
+                // Pinned users overfilled a Worker, so there's no room left anywhere.
+                None => (0..expect_workers)
+                    .min_by_key(|&worker| batches[worker].len())
+                    .unwrap(),
+            };
+        batches[worker].push(user);
+    }
+
+    batches
+}
+
 fn pipe_closed(_pipe: Pipe, event: PipeEvent) {
     match event {
         PipeEvent::AddPost => {
@@ -94,6 +231,13 @@ fn merge_tasks_from_worker(
     merged_task.success_count += &user_task.success_count;
     // Increment total fail counter.
     merged_task.fail_count += &user_task.fail_count;
+    // Add time spent in each request made by the task, for the task profile.
+    for (request_key, request_time) in &user_task.request_times {
+        *merged_task
+            .request_times
+            .entry(request_key.to_string())
+            .or_insert(0) += request_time;
+    }
     merged_task
 }
 
@@ -124,10 +268,61 @@ fn merge_requests_from_worker(
         merged_request.raw_data.maximum_time,
         user_request.raw_data.maximum_time,
     );
+    // Add up how much the request counts toward the request mix, if either was weighted.
+    if merged_request.mix_count.is_some() || user_request.mix_count.is_some() {
+        merged_request.mix_count =
+            Some(merged_request.counted_in_mix() + user_request.counted_in_mix());
+    }
     // Increment total success counter.
     merged_request.success_count += &user_request.success_count;
     // Increment total fail counter.
     merged_request.fail_count += &user_request.fail_count;
+    // Increment total bytes sent counter.
+    merged_request.bytes_sent += &user_request.bytes_sent;
+    // Increment total redirects counter.
+    merged_request.redirect_count += &user_request.redirect_count;
+    // Merge time spent waiting in the throttle.
+    if let Some(user_throttle_wait) = user_request.throttle_wait_data.as_ref() {
+        let throttle_wait = merged_request
+            .throttle_wait_data
+            .get_or_insert_with(|| SwanlingRequestMetricTimingData::new(None));
+        throttle_wait.times = metrics::merge_times(
+            throttle_wait.times.clone(),
+            user_throttle_wait.times.clone(),
+        );
+        throttle_wait.total_time += user_throttle_wait.total_time;
+        throttle_wait.counter += user_throttle_wait.counter;
+        throttle_wait.minimum_time =
+            metrics::update_min_time(throttle_wait.minimum_time, user_throttle_wait.minimum_time);
+        throttle_wait.maximum_time =
+            metrics::update_max_time(throttle_wait.maximum_time, user_throttle_wait.maximum_time);
+    }
+    // Merge time spent resolving host names.
+    if let Some(user_dns_time) = user_request.dns_time_data.as_ref() {
+        let dns_time = merged_request
+            .dns_time_data
+            .get_or_insert_with(|| SwanlingRequestMetricTimingData::new(None));
+        dns_time.times = metrics::merge_times(dns_time.times.clone(), user_dns_time.times.clone());
+        dns_time.total_time += user_dns_time.total_time;
+        dns_time.counter += user_dns_time.counter;
+        dns_time.minimum_time =
+            metrics::update_min_time(dns_time.minimum_time, user_dns_time.minimum_time);
+        dns_time.maximum_time =
+            metrics::update_max_time(dns_time.maximum_time, user_dns_time.maximum_time);
+    }
+    // Increment slow request counter, keeping the slowest examples from both.
+    merged_request.slow_count += &user_request.slow_count;
+    for slow_request in &user_request.slow_requests {
+        merged_request.sample_slow_request(slow_request.clone());
+    }
+    // Increment outlier counter, keeping the slowest examples from both.
+    merged_request.total_squared_time = merged_request
+        .total_squared_time
+        .saturating_add(user_request.total_squared_time);
+    merged_request.outlier_count += &user_request.outlier_count;
+    for outlier_request in &user_request.outlier_requests {
+        merged_request.sample_outlier_request(outlier_request.clone());
+    }
     // Only accrue overhead of merging status_code_counts if we're going to display the results
     if status_codes {
         for (status_code, count) in &user_request.status_code_counts {
@@ -164,18 +359,22 @@ fn merge_errors_from_worker(
 }
 
 /// Helper to send SwanlingUserCommand::Exit command to worker.
-fn tell_worker_to_exit(server: &Socket) -> bool {
+fn tell_worker_to_exit(shard: &ManagerShard) -> bool {
     let mut message = Message::new();
     serde_cbor::to_writer(&mut message, &SwanlingUserCommand::Exit)
         .map_err(|error| eprintln!("{:?}", error))
         .expect("failed to serialize user command");
-    send_message_to_worker(server, message)
+    send_message_to_worker(shard, message)
 }
 
 /// Helper to send message to worker.
-fn send_message_to_worker(server: &Socket, message: Message) -> bool {
+fn send_message_to_worker(shard: &ManagerShard, message: Message) -> bool {
+    let sent = shard.server.try_send(message);
+    // The shard can now receive the next message.
+    let _ = shard.replied_tx.send(());
+
     // If there's an error, handle it.
-    if let Err((_, e)) = server.try_send(message) {
+    if let Err((_, e)) = sent {
         match e {
             Error::TryAgain => {
                 if ACTIVE_WORKERS.load(Ordering::SeqCst) == 0 {
@@ -191,33 +390,296 @@ fn send_message_to_worker(server: &Socket, message: Message) -> bool {
     true
 }
 
-/// Helper to merge in request metrics from Worker.
-fn merge_request_metrics(swanling_attack: &mut SwanlingAttack, requests: SwanlingRequestMetrics) {
+/// Create a Rep0 reply socket listening for Workers in a shard. Each shard listens on
+/// the next port after the last.
+fn listen_for_workers(configuration: &SwanlingConfiguration, shard: usize) -> Socket {
+    // Creates a TCP, inproc or ipc address.
+    let address = gaggle_address(
+        configuration,
+        &configuration.manager_bind_host,
+        configuration.manager_bind_port,
+        shard,
+    );
+    debug!("preparing to listen for workers at: {}", &address);
+
+    // Create a Rep0 reply socket.
+    let server = Socket::new(Protocol::Rep0)
+        .map_err(|error| eprintln!("{:?}", error))
+        .expect("failed to create socket");
+
+    // Apply any configured socket options.
+    configure_gaggle_socket(&server, configuration)
+        .map_err(|error| eprintln!("{:?}", error))
+        .expect("failed to configure socket");
+
+    // Set up callback function to receive pipe event notifications.
+    server
+        .pipe_notify(pipe_closed)
+        .map_err(|error| eprintln!("{:?}", error))
+        .expect("failed to set up pipe handler");
+
+    // Listen for connections.
+    server
+        .listen(&address)
+        .map_err(|error| eprintln!("{:?} (address = {})", error, address))
+        .expect("failed to bind to socket");
+    info!("manager listening on {}", &address);
+
+    server
+}
+
+/// Receive messages from the Workers in a shard, deserializing them in parallel with the
+/// other shards before the Manager merges them. Runs until the Manager stops listening.
+fn receive_from_workers(
+    shard: usize,
+    server: Socket,
+    max_msg_size: usize,
+    messages_tx: flume::Sender<WorkerMessage>,
+    replied_rx: flume::Receiver<()>,
+) {
+    loop {
+        match server.try_recv() {
+            Ok(mut msg) => {
+                // Message received, grab the pipe to determine which worker it is.
+                let pipe = msg.pipe().expect("fatal error getting worker pipe");
+
+                // Workers always send a vector of SwanlingMetric objects. Batches that are
+                // too large or don't parse are passed on as errors, so the Worker is told to
+                // exit rather than left waiting for a reply.
+                let gaggle_metrics = if max_msg_size > 0 && msg.len() > max_msg_size {
+                    Err(oversized_metrics_error(msg.len(), max_msg_size))
+                } else {
+                    serde_cbor::from_reader(msg.as_slice())
+                        .map_err(|e| format!("invalid metrics batch of {} bytes: {}", msg.len(), e))
+                };
+
+                if messages_tx
+                    .send(WorkerMessage {
+                        shard,
+                        pipe,
+                        gaggle_metrics,
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+
+                // A Rep0 socket only receives the next message after replying to the last.
+                if replied_rx.recv().is_err() {
+                    break;
+                }
+            }
+            Err(Error::TryAgain) => {
+                if replied_rx.is_disconnected() {
+                    break;
+                }
+                // Sleep a tenth of a second then check for messages again.
+                thread::sleep(time::Duration::from_millis(100));
+            }
+            // The Manager stopped listening.
+            Err(Error::Closed) => break,
+            Err(e) => {
+                error!("error receiving message from workers: {}", e);
+                thread::sleep(time::Duration::from_millis(100));
+            }
+        }
+    }
+}
+
+/// Helper to merge one request aggregate from a Worker into a set of request metrics.
+fn merge_request(
+    requests: &mut SwanlingRequestMetrics,
+    request_key: &str,
+    request: &SwanlingRequestMetricAggregate,
+    status_codes: bool,
+) {
+    let merged_request;
+    if let Some(parent_request) = requests.get(request_key) {
+        merged_request = merge_requests_from_worker(parent_request, request, status_codes);
+    } else {
+        // First time seeing this request, simply insert it.
+        merged_request = request.clone();
+    }
+    requests.insert(request_key.to_string(), merged_request);
+}
+
+/// Helper to estimate how many milliseconds a Worker's clock is ahead of the Manager's,
+/// from when the Worker pushed its metrics and when they were received. Half of the
+/// round trip of the previous push is assumed to be spent in transit.
+fn worker_clock_offset(sent: u64, round_trip: u64, received: u64) -> i64 {
+    sent as i64 - (received as i64 - (round_trip / 2) as i64)
+}
+
+/// Helper to find the aggregation window that was open when metrics were pushed, as
+/// metrics pushed just before a window ended can arrive after the next one started.
+fn window_at(
+    windows: &mut [SwanlingMetricsWindow],
+    pushed: usize,
+) -> Option<&mut SwanlingMetricsWindow> {
+    windows
+        .iter_mut()
+        .rev()
+        .find(|window| window.started <= pushed)
+}
+
+/// Returns the error rates of a Worker and of its peers, as percentages, if the Worker's
+/// is more than `threshold` percentage points higher. Each is a tuple of how many requests
+/// were made and how many of them failed. Error rates are only compared once the Worker and
+/// its peers have each made at least `QUARANTINE_MIN_REQUESTS` requests.
+fn out_of_line_error_rate(
+    worker: (usize, usize),
+    peers: (usize, usize),
+    threshold: usize,
+) -> Option<(f32, f32)> {
+    if worker.0 < QUARANTINE_MIN_REQUESTS || peers.0 < QUARANTINE_MIN_REQUESTS {
+        return None;
+    }
+    let error_rate = worker.1 as f32 / worker.0 as f32 * 100.0;
+    let peer_error_rate = peers.1 as f32 / peers.0 as f32 * 100.0;
+    if error_rate - peer_error_rate > threshold as f32 {
+        Some((error_rate, peer_error_rate))
+    } else {
+        None
+    }
+}
+
+/// Helper to count the requests pushed by a Worker, quarantining it if its error rate is
+/// out of line with the Workers that aren't quarantined. Returns the index of the Worker
+/// in `SwanlingMetrics::quarantined` if it was quarantined.
+fn quarantine_worker(
+    swanling_attack: &mut SwanlingAttack,
+    worker_requests: &mut HashMap<usize, (usize, usize)>,
+    worker_id: usize,
+    requests: &SwanlingRequestMetrics,
+    threshold: usize,
+    pushed: usize,
+) -> Option<usize> {
+    let counts = worker_requests.entry(worker_id).or_default();
+    for request in requests.values() {
+        counts.0 += request.success_count + request.fail_count;
+        counts.1 += request.fail_count;
+    }
+    let worker = *counts;
+
+    let quarantined = &swanling_attack.metrics.quarantined;
+    let peers = worker_requests
+        .iter()
+        .filter(|(id, _)| **id != worker_id && !quarantined.iter().any(|q| q.worker_id == **id))
+        .fold((0, 0), |peers, (_, counts)| {
+            (peers.0 + counts.0, peers.1 + counts.1)
+        });
+    let (error_rate, peer_error_rate) = out_of_line_error_rate(worker, peers, threshold)?;
+
+    warn!(
+        "quarantining worker {}: {:.2}% of its requests failed, compared to {:.2}% on other workers",
+        worker_id, error_rate, peer_error_rate
+    );
+    swanling_attack
+        .metrics
+        .quarantined
+        .push(SwanlingQuarantinedWorker {
+            worker_id,
+            quarantined: pushed,
+            error_rate,
+            peer_error_rate,
+            ignored_requests: 0,
+        });
+    Some(swanling_attack.metrics.quarantined.len() - 1)
+}
+
+/// Helper to merge in request metrics from Worker, pushed the given number of seconds
+/// after the load test started.
+fn merge_request_metrics(
+    swanling_attack: &mut SwanlingAttack,
+    requests: SwanlingRequestMetrics,
+    pushed: usize,
+) {
     if !requests.is_empty() {
         debug!("requests metrics received: {:?}", requests.len());
-        for (request_key, request) in requests {
+        let status_codes = swanling_attack.configuration.status_codes;
+        for (mut request_key, mut request) in requests {
             trace!("request_key: {}", request_key);
-            let merged_request;
-            if let Some(parent_request) = swanling_attack.metrics.requests.get(&request_key) {
-                merged_request = merge_requests_from_worker(
-                    parent_request,
-                    &request,
-                    swanling_attack.configuration.status_codes,
-                );
-            } else {
-                // First time seeing this request, simply insert it.
-                merged_request = request.clone();
+            // Combine new request names into a single entry once the maximum number are
+            // tracked.
+            if let Some(max_request_names) =
+                metrics::max_request_names(&swanling_attack.configuration)
+            {
+                if let Some(overflow_key) = metrics::request_overflow_key(
+                    &swanling_attack.metrics.requests,
+                    &request_key,
+                    &request.method,
+                    max_request_names,
+                ) {
+                    if !swanling_attack.metrics.requests.contains_key(&overflow_key) {
+                        metrics::warn_request_names_overflow(max_request_names, &overflow_key);
+                    }
+                    request_key = overflow_key;
+                    request.path = metrics::METRICS_OVERFLOW.to_string();
+                }
             }
+            // Count requests actually made, ignoring Coordinated Omission Mitigation.
+            swanling_attack.request_count += request.raw_data.counter;
             swanling_attack
                 .metrics
-                .requests
-                .insert(request_key.to_string(), merged_request);
+                .record_throughput(pushed, request.raw_data.counter);
+            merge_request(
+                &mut swanling_attack.metrics.requests,
+                &request_key,
+                &request,
+                status_codes,
+            );
+            // Also merge into the aggregation window it was pushed in, if enabled.
+            if let Some(window) = window_at(&mut swanling_attack.metrics.windows, pushed) {
+                merge_request(&mut window.requests, &request_key, &request, status_codes);
+            }
         }
     }
 }
 
-/// Helper to merge in task metrics from Worker.
-fn merge_task_metrics(swanling_attack: &mut SwanlingAttack, tasks: SwanlingTaskMetrics) {
+/// Helper to write the request metrics from each Worker since the last write as InfluxDB
+/// line protocol.
+fn write_influx_metrics(
+    influx_writer: &mut SwanlingInfluxWriter,
+    influx_requests: &mut HashMap<usize, SwanlingRequestMetrics>,
+) {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    let mut worker_ids: Vec<usize> = influx_requests.keys().copied().collect();
+    worker_ids.sort_unstable();
+    for worker_id in worker_ids {
+        lines.extend(metrics::influx_lines(
+            &influx_requests[&worker_id],
+            worker_id,
+            timestamp,
+        ));
+    }
+    influx_requests.clear();
+    influx_writer.write(&lines);
+}
+
+/// Helper to merge in request metrics grouped by label from Worker.
+fn merge_group_metrics(swanling_attack: &mut SwanlingAttack, groups: SwanlingRequestMetrics) {
+    let status_codes = swanling_attack.configuration.status_codes;
+    for (value, group) in groups {
+        merge_request(
+            &mut swanling_attack.metrics.groups,
+            &value,
+            &group,
+            status_codes,
+        );
+    }
+}
+
+/// Helper to merge in task metrics from Worker, pushed the given number of seconds after
+/// the load test started.
+fn merge_task_metrics(
+    swanling_attack: &mut SwanlingAttack,
+    tasks: SwanlingTaskMetrics,
+    pushed: usize,
+) {
     for task_set in tasks {
         for task in task_set {
             let merged_task = merge_tasks_from_worker(
@@ -225,16 +687,56 @@ fn merge_task_metrics(swanling_attack: &mut SwanlingAttack, tasks: SwanlingTaskM
                 &task,
             );
             swanling_attack.metrics.tasks[task.taskset_index][task.task_index] = merged_task;
+            // Also merge into the aggregation window it was pushed in, if enabled.
+            if let Some(window) = window_at(&mut swanling_attack.metrics.windows, pushed) {
+                let merged_task = merge_tasks_from_worker(
+                    &window.tasks[task.taskset_index][task.task_index],
+                    &task,
+                );
+                window.tasks[task.taskset_index][task.task_index] = merged_task;
+            }
         }
     }
 }
 
+/// Helper to merge in custom counters and gauges from the Worker.
+fn merge_custom_metrics(swanling_attack: &mut SwanlingAttack, custom: SwanlingCustomMetrics) {
+    for (name, aggregate) in custom {
+        metrics::merge_custom_metric(&mut swanling_attack.metrics.custom, &name, aggregate);
+    }
+}
+
 /// Helper to merge in errors from the Worker.
 fn merge_error_metrics(swanling_attack: &mut SwanlingAttack, errors: SwanlingErrorMetrics) {
     if !errors.is_empty() {
         debug!("errors received: {:?}", errors.len());
-        for (error_key, error) in errors {
+        for (mut error_key, mut error) in errors {
             trace!("error_key: {}", error_key);
+            // During a soak test, combine new errors into a single entry once the maximum
+            // number are tracked.
+            if swanling_attack.configuration.soak {
+                if let Some(overflow_key) = metrics::error_overflow_key(
+                    &swanling_attack.metrics.errors,
+                    &error_key,
+                    &error.method,
+                ) {
+                    error_key = overflow_key;
+                    error.name = metrics::METRICS_OVERFLOW.to_string();
+                    error.error = metrics::METRICS_OVERFLOW.to_string();
+                }
+            }
+            // If enabled, track the most recent distinct errors while the load test runs.
+            if let Some(error_tail) = swanling_attack.configuration.error_tail {
+                let last_seen = swanling_attack
+                    .started
+                    .map_or(0, |started| started.elapsed().as_secs() as usize);
+                swanling_attack.metrics.record_recent_error(
+                    error_tail,
+                    &error,
+                    error.occurrences,
+                    last_seen,
+                );
+            }
             let merged_error;
             if let Some(parent_error) = swanling_attack.metrics.errors.get(&error_key) {
                 merged_error = merge_errors_from_worker(parent_error, &error);
@@ -250,65 +752,259 @@ fn merge_error_metrics(swanling_attack: &mut SwanlingAttack, errors: SwanlingErr
     }
 }
 
-/// Main manager loop.
-pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> SwanlingAttack {
-    // Creates a TCP address.
-    let address = format!(
-        "tcp://{}:{}",
-        swanling_attack.configuration.manager_bind_host,
-        swanling_attack.configuration.manager_bind_port
-    );
-    debug!("preparing to listen for workers at: {}", &address);
-
-    // Create a Rep0 reply socket.
-    let server = Socket::new(Protocol::Rep0)
-        .map_err(|error| eprintln!("{:?}", error))
-        .expect("failed to create socket");
+/// Save the end of a Worker's request log in the Manager's working directory, prefixing
+/// the file name with the Worker's id.
+fn save_request_log(worker_id: usize, name: &str, lines: &[String]) {
+    if name.is_empty() {
+        warn!(
+            "worker {} has no request log, start it with --request-log to retrieve one",
+            worker_id
+        );
+        return;
+    }
+    let path = format!("worker-{}-{}", worker_id, name);
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    match std::fs::write(&path, contents) {
+        Ok(_) => info!(
+            "saved last {} lines of worker {} request log to {}",
+            lines.len(),
+            worker_id,
+            path
+        ),
+        Err(e) => error!(
+            "failed to save worker {} request log to {}: {}",
+            worker_id, path, e
+        ),
+    }
+}
 
-    // Set up callback function to receive pipe event notifications.
-    server
-        .pipe_notify(pipe_closed)
-        .map_err(|error| eprintln!("{:?}", error))
-        .expect("failed to set up pipe handler");
+/// Handle a Controller request received by the Manager, returning any configuration
+/// changes to send to all Workers. Only the commands that apply to a running Regatta are
+/// supported, all others fail. Workers asked for their request log are added to
+/// `request_logs`, by the id assigned as they connected.
+fn handle_controller_request(
+    swanling_attack: &mut SwanlingAttack,
+    request: SwanlingControllerRequest,
+    load_test_running: bool,
+    request_logs: &mut HashSet<usize>,
+    connected_workers: usize,
+) -> Option<SwanlingReconfiguration> {
+    info!(
+        "request from controller client {}: {:?}",
+        request.client_id, request.request
+    );
+    let mut reconfiguration = None;
+    let response = match request.request.command {
+        // Send back a copy of the running configuration.
+        SwanlingControllerCommand::Config | SwanlingControllerCommand::ConfigJson => {
+            SwanlingControllerResponseMessage::Config(Box::new(
+                swanling_attack.configuration.clone(),
+            ))
+        }
+        // Send back a copy of the running metrics.
+        SwanlingControllerCommand::Metrics | SwanlingControllerCommand::MetricsJson => {
+            SwanlingControllerResponseMessage::Metrics(Box::new(swanling_attack.metrics.clone()))
+        }
+        // Send the new throttle to all Workers, which validate it is enabled.
+        SwanlingControllerCommand::Throttle if load_test_running => {
+            // The controller uses a regular expression to validate that this is a valid
+            // integer.
+            match request
+                .request
+                .value
+                .as_ref()
+                .and_then(|value| usize::from_str(value).ok())
+            {
+                Some(throttle_requests)
+                    if throttle_requests > 0 && throttle_requests <= 1_000_000 =>
+                {
+                    reconfiguration = Some(SwanlingReconfiguration {
+                        throttle_requests: Some(throttle_requests),
+                        ..Default::default()
+                    });
+                    SwanlingControllerResponseMessage::Bool(true)
+                }
+                _ => SwanlingControllerResponseMessage::Bool(false),
+            }
+        }
+        // Ask a connected Worker for its request log the next time it pushes metrics.
+        SwanlingControllerCommand::RequestLog if load_test_running => {
+            // The controller uses a regular expression to validate that this is a valid
+            // integer.
+            match request
+                .request
+                .value
+                .as_ref()
+                .and_then(|value| usize::from_str(value).ok())
+            {
+                Some(worker_id) if worker_id > 0 && worker_id <= connected_workers => {
+                    request_logs.insert(worker_id);
+                    SwanlingControllerResponseMessage::Bool(true)
+                }
+                _ => SwanlingControllerResponseMessage::Bool(false),
+            }
+        }
+        // Pause or resume the users on all Workers.
+        SwanlingControllerCommand::Pause | SwanlingControllerCommand::Resume
+            if load_test_running =>
+        {
+            reconfiguration = Some(SwanlingReconfiguration {
+                paused: Some(request.request.command == SwanlingControllerCommand::Pause),
+                ..Default::default()
+            });
+            SwanlingControllerResponseMessage::Bool(true)
+        }
+        // Reset the metrics, telling all Workers to discard the metrics they haven't
+        // pushed yet.
+        SwanlingControllerCommand::ResetMetrics if load_test_running => {
+            swanling_attack.reset_metrics();
+            info!("metrics reset by controller");
+            reconfiguration = Some(SwanlingReconfiguration {
+                reset_metrics: true,
+                ..Default::default()
+            });
+            SwanlingControllerResponseMessage::Bool(true)
+        }
+        _ => SwanlingControllerResponseMessage::Bool(false),
+    };
+    swanling_attack.reply_to_controller(request, response);
+    reconfiguration
+}
 
-    // Listen for connections.
-    server
-        .listen(&address)
-        .map_err(|error| eprintln!("{:?} (address = {})", error, address))
-        .expect("failed to bind to socket");
+/// Main manager loop.
+pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> SwanlingAttack {
+    // Listen for Workers on one or more sockets, each served by its own thread so
+    // messages from Workers in different shards are received in parallel.
+    let (messages_tx, messages_rx) = flume::unbounded();
+    let mut shards = Vec::new();
+    let mut receivers = Vec::new();
+    for shard in 0..swanling_attack.configuration.manager_sockets.unwrap_or(1) {
+        let server = listen_for_workers(&swanling_attack.configuration, shard);
+        let (replied_tx, replied_rx) = flume::unbounded();
+        let shard_server = server.clone();
+        let max_msg_size = swanling_attack
+            .configuration
+            .gaggle_max_msg_size
+            .unwrap_or(0);
+        let shard_messages_tx = messages_tx.clone();
+        receivers.push(thread::spawn(move || {
+            receive_from_workers(
+                shard,
+                shard_server,
+                max_msg_size,
+                shard_messages_tx,
+                replied_rx,
+            )
+        }));
+        shards.push(ManagerShard { server, replied_tx });
+    }
 
     // Expect workers is reqiured so unwrap() is safe.
     info!(
-        "manager listening on {}, waiting for {} workers",
-        &address,
+        "manager listening on {} sockets, waiting for {} workers",
+        shards.len(),
         swanling_attack.configuration.expect_workers.unwrap(),
     );
 
     // Calculate how many users each worker will be responsible for.
-    let (users_per_worker, mut users_remainder) = distribute_users(&swanling_attack);
+    let (users_per_worker, users_remainder) = distribute_users(&swanling_attack);
+
+    // Plan which users are sent to each worker, honoring --pin-task-set.
+    let task_sets_indexes: Vec<usize> = swanling_attack
+        .weighted_gaggle_users
+        .iter()
+        .map(|user| user.task_sets_index)
+        .collect();
+    let mut user_batches = allocate_users(
+        &task_sets_indexes,
+        &pinned_task_sets(&swanling_attack),
+        users_per_worker,
+        users_remainder,
+        swanling_attack.configuration.expect_workers.unwrap() as usize,
+    )
+    .into_iter();
 
     // A mutable bucket of users to be assigned to workers.
-    let mut available_users = swanling_attack.weighted_gaggle_users.clone();
+    let mut available_users: Vec<_> = swanling_attack
+        .weighted_gaggle_users
+        .iter()
+        .cloned()
+        .map(Some)
+        .collect();
 
     // Track how many workers we've seen.
     let mut workers: HashSet<Pipe> = HashSet::new();
 
+    // The id assigned to each worker, tagging the metrics it sends.
+    let mut worker_ids: HashMap<Pipe, usize> = HashMap::new();
+
+    // Optionally write request metrics as InfluxDB line protocol at the end of each
+    // aggregation window, tracking each worker's requests since the last write.
+    let mut influx_writer = SwanlingInfluxWriter::new(&swanling_attack.configuration)
+        .unwrap_or_else(|e| {
+            error!("failed to enable InfluxDB line protocol: {}", e);
+            None
+        });
+    let mut influx_requests: HashMap<usize, SwanlingRequestMetrics> = HashMap::new();
+
+    // Optionally spawn a telnet and/or WebSocket Controller thread.
+    let controller_channel_rx = swanling_attack.setup_controllers().await;
+
+    // Configuration changes to send to all workers, and the workers that haven't been
+    // sent the latest changes yet.
+    let mut reconfiguration = SwanlingReconfiguration::default();
+    let mut reconfigure_workers: HashSet<Pipe> = HashSet::new();
+
+    // The workers whose metrics were collected before the last reset, and whether they've
+    // been told to discard them. Their metrics are discarded until they acknowledge it.
+    let mut reset_workers: HashMap<Pipe, bool> = HashMap::new();
+
+    // The version of the shared values last sent to each worker.
+    let mut shared_values_sent: HashMap<Pipe, usize> = HashMap::new();
+
+    // How many milliseconds each worker's clock is estimated to be ahead of ours.
+    let mut worker_clock_offsets: HashMap<Pipe, i64> = HashMap::new();
+
+    // How many requests each worker made and how many of them failed, compared to
+    // quarantine workers with far more errors than their peers.
+    let mut worker_requests: HashMap<usize, (usize, usize)> = HashMap::new();
+
+    // The workers asked to send the end of their request log, by id.
+    let mut request_logs: HashSet<usize> = HashSet::new();
+
     // Track start time, we'll reset this when the test actually starts.
     let mut started = time::Instant::now();
     swanling_attack.started = Some(started);
     let mut running_metrics_timer = time::Instant::now();
+    let mut error_tail_timer = time::Instant::now();
+    let mut aggregate_window_timer = time::Instant::now();
     let mut exit_timer = time::Instant::now();
     let mut load_test_running = false;
     let mut load_test_finished = false;
+    // How long Workers keep collecting metrics after stopping their users, skipped if
+    // the load test doesn't end normally.
+    let mut cooldown = swanling_attack.cooldown;
 
     // Catch ctrl-c to allow clean shutdown to display metrics.
-    let canceled = Arc::new(AtomicBool::new(false));
+    let canceled = swanling_attack.canceled.clone();
     util::setup_ctrlc_handler(&canceled);
 
+    // Optionally invoke the metrics callback with the metrics from all Workers, which can
+    // abort the load test.
+    let mut metrics_callback = swanling_attack
+        .metrics_callback
+        .as_ref()
+        .map(SwanlingMetricsCallbackThread::spawn);
+    let mut aborted = false;
+
     // Initialize the optional task metrics.
     swanling_attack
         .metrics
         .initialize_task_metrics(&swanling_attack.task_sets, &swanling_attack.configuration);
+    swanling_attack
+        .metrics
+        .initialize_task_set_users(&swanling_attack.task_sets);
 
     // Update metrics, which doesn't happen automatically on the Master as we don't
     // invoke start_attack. Hatch rate is required here so unwrap() is safe.
@@ -332,8 +1028,12 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         "worker went away, stopping gracefully after {} seconds...",
                         started.elapsed().as_secs()
                     );
+                    swanling_attack
+                        .metrics
+                        .close_window(started.elapsed().as_secs() as usize);
                     load_test_finished = true;
                     exit_timer = time::Instant::now();
+                    cooldown = 0;
                 }
                 // If a worker goes away during start up, exit immediately.
                 else {
@@ -344,20 +1044,64 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
         }
         if load_test_running {
             if !load_test_finished {
-                // Test ran to completion or was canceled with ctrl-c.
+                // Test ran to completion, made the maximum number of requests, was canceled
+                // with ctrl-c, or was aborted by the metrics callback.
                 if util::timer_expired(started, swanling_attack.run_time)
+                    || swanling_attack.max_requests_reached()
                     || canceled.load(Ordering::SeqCst)
+                    || aborted
                 {
                     info!("stopping after {} seconds...", started.elapsed().as_secs());
                     swanling_attack.metrics.duration =
                         swanling_attack.started.unwrap().elapsed().as_secs() as usize;
+                    // Metrics still to arrive from Workers are merged into the last window.
+                    swanling_attack
+                        .metrics
+                        .close_window(swanling_attack.metrics.duration);
                     load_test_finished = true;
                     exit_timer = time::Instant::now();
+                    // Workers keep collecting metrics during the cooldown, unless the load
+                    // test was canceled or aborted.
+                    if canceled.load(Ordering::SeqCst) || aborted {
+                        cooldown = 0;
+                    } else if cooldown > 0 {
+                        info!("cooling down for {} seconds...", cooldown);
+                        swanling_attack.metrics.cooldown = Some(SwanlingCooldownWindow {
+                            started: swanling_attack.metrics.duration,
+                            stopped: swanling_attack.metrics.duration + cooldown,
+                        });
+                    }
+                }
+                // Start a new aggregation window each time the window timer expires.
+                else if let Some(aggregate_window) =
+                    swanling_attack.configuration.aggregate_window
+                {
+                    if util::timer_expired(aggregate_window_timer, aggregate_window) {
+                        aggregate_window_timer = time::Instant::now();
+                        if let Some(influx_writer) = influx_writer.as_mut() {
+                            write_influx_metrics(influx_writer, &mut influx_requests);
+                        }
+                        swanling_attack
+                            .metrics
+                            .start_window(started.elapsed().as_secs() as usize);
+                        // During a soak test, only keep the most recent windows.
+                        if swanling_attack.configuration.soak
+                            && swanling_attack.metrics.windows.len() > metrics::SOAK_MAX_WINDOWS
+                        {
+                            swanling_attack.metrics.windows.remove(0);
+                        }
+                    }
                 }
             }
 
-            // Aborting graceful shutdown, workers took too long to shut down.
-            if load_test_finished && util::timer_expired(exit_timer, GRACEFUL_SHUTDOWN_TIMEOUT) {
+            // Aborting graceful shutdown, workers took too long to shut down. Workers first
+            // spend stop_time gradually stopping their users.
+            if load_test_finished
+                && util::timer_expired(
+                    exit_timer,
+                    GRACEFUL_SHUTDOWN_TIMEOUT + swanling_attack.stop_time + cooldown,
+                )
+            {
                 warn!("graceful shutdown timer expired, exiting...");
                 break;
             }
@@ -367,25 +1111,140 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                 if util::timer_expired(running_metrics_timer, running_metrics) {
                     // Reset timer each time we display metrics.
                     running_metrics_timer = time::Instant::now();
-                    swanling_attack.metrics.duration =
-                        swanling_attack.started.unwrap().elapsed().as_secs() as usize;
+                    // The cooldown isn't included in the duration.
+                    if !load_test_finished {
+                        swanling_attack.metrics.duration =
+                            swanling_attack.started.unwrap().elapsed().as_secs() as usize;
+                    }
                     swanling_attack.metrics.print_running();
                 }
             }
+
+            // Display the most recent distinct errors received from the Workers.
+            if swanling_attack.configuration.error_tail.is_some()
+                && util::timer_expired(error_tail_timer, metrics::ERROR_TAIL_INTERVAL)
+            {
+                error_tail_timer = time::Instant::now();
+                swanling_attack.metrics.print_error_tail();
+            }
+
+            // Invoke the metrics callback, if set, and apply the action it returned.
+            if let Some(metrics_callback) =
+                metrics_callback.as_mut().filter(|_| !load_test_finished)
+            {
+                swanling_attack.metrics.duration =
+                    swanling_attack.started.unwrap().elapsed().as_secs() as usize;
+                match metrics_callback.poll(&swanling_attack.metrics) {
+                    Some(SwanlingControlAction::Abort) => {
+                        info!("metrics callback aborted load test");
+                        aborted = true;
+                    }
+                    Some(SwanlingControlAction::Adjust(changes)) => {
+                        // Send the combined changes to all workers the next time they push
+                        // metrics.
+                        reconfiguration.merge(&changes);
+                        reconfigure_workers = workers.clone();
+                    }
+                    Some(SwanlingControlAction::Continue) | None => (),
+                }
+            }
+
+            // Stream a snapshot of the metrics from all Workers, if requested.
+            if let Some(metrics_stream) = swanling_attack
+                .metrics_stream
+                .as_mut()
+                .filter(|_| !load_test_finished)
+            {
+                swanling_attack.metrics.duration =
+                    swanling_attack.started.unwrap().elapsed().as_secs() as usize;
+                metrics_stream.poll(&swanling_attack.metrics);
+            }
+
+            // Follow the load profile, if configured, reconfiguring the users on all
+            // workers each time it interpolates a different number.
+            if let Some(load_profile) = swanling_attack
+                .load_profile
+                .as_ref()
+                .filter(|_| !load_test_finished)
+            {
+                let users = load_profile.users_at(started.elapsed().as_secs_f32());
+                if reconfiguration.users != Some(users) {
+                    info!("load profile: users changed to {}", users);
+                    reconfiguration.merge(&SwanlingReconfiguration {
+                        users: Some(users),
+                        ..Default::default()
+                    });
+                    reconfigure_workers = workers.clone();
+                    swanling_attack.metrics.users = swanling_attack.metrics.users.max(users);
+                }
+            }
         } else if canceled.load(Ordering::SeqCst) {
             info!("load test canceled, exiting");
             std::process::exit(1);
         }
 
-        // Check for messages from workers.
-        match server.try_recv() {
-            Ok(mut msg) => {
-                // Message received, grab the pipe to determine which worker it is.
-                let pipe = msg.pipe().expect("fatal error getting worker pipe");
+        // Check for requests from the Controllers, if enabled.
+        if let Some(request) = controller_channel_rx
+            .as_ref()
+            .and_then(|controller| controller.try_recv().ok())
+        {
+            if let Some(changes) = handle_controller_request(
+                &mut swanling_attack,
+                request,
+                load_test_running && !load_test_finished,
+                &mut request_logs,
+                workers.len(),
+            ) {
+                // The metrics were reset, measure everything from now.
+                if changes.reset_metrics {
+                    started = swanling_attack.started.unwrap();
+                    running_metrics_timer = started;
+                    if let Some(metrics_callback) = metrics_callback.as_mut() {
+                        metrics_callback.timer = started;
+                    }
+                    if let Some(metrics_stream) = swanling_attack.metrics_stream.as_mut() {
+                        metrics_stream.timer = started;
+                    }
+                    influx_requests.clear();
+                    if swanling_attack.configuration.aggregate_window.is_some() {
+                        aggregate_window_timer = started;
+                        swanling_attack.metrics.start_window(0);
+                    }
+                    reset_workers = workers.iter().map(|pipe| (*pipe, false)).collect();
+                }
+                // Send the combined changes to all workers the next time they push metrics.
+                reconfiguration.merge(&changes);
+                reconfigure_workers = workers.clone();
+            }
+        }
 
-                // Workers always send a vector of SwanlingMetric objects.
-                let mut gaggle_metrics: Vec<GaggleMetrics> =
-                    serde_cbor::from_reader(msg.as_slice()).unwrap();
+        // Check for messages from workers. Metrics from all shards are merged together
+        // here, so they are aggregated the same as with a single socket.
+        match messages_rx.try_recv() {
+            Ok(WorkerMessage {
+                shard,
+                pipe,
+                gaggle_metrics,
+            }) => {
+                // Replies are sent from the shard that received the message.
+                let server = &shards[shard];
+
+                // Metrics that can't be read are lost, stop the Worker that sent them.
+                let mut gaggle_metrics = match gaggle_metrics {
+                    Ok(gaggle_metrics) => gaggle_metrics,
+                    Err(e) => {
+                        error!(
+                            "telling worker {} to exit: {}",
+                            worker_ids.get(&pipe).copied().unwrap_or(0),
+                            e
+                        );
+                        if !tell_worker_to_exit(server) {
+                            // All workers have exited, shut down the load test.
+                            break;
+                        }
+                        continue;
+                    }
+                };
 
                 // Check if we're seeing this worker for the first time.
                 if !workers.contains(&pipe) {
@@ -401,7 +1260,7 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         );
                         // We already have enough workers, tell this extra one to
                         // SwanlingUserCommand::Exit.
-                        if !tell_worker_to_exit(&server) {
+                        if !tell_worker_to_exit(server) {
                             // All workers have exited, shut down the
                             // load test.
                             break;
@@ -414,7 +1273,7 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         if gaggle_metrics.len() != 1 {
                             warn!("invalid message from Worker, exiting load test");
                             // Invalid message, tell worker to SwanlingUserCommand::Exit.
-                            if !tell_worker_to_exit(&server) {
+                            if !tell_worker_to_exit(server) {
                                 // All workers have exited, shut down the
                                 // load test.
                                 break;
@@ -434,7 +1293,7 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                             // Unexpected object received, tell the worker
                             // to SwanlingUserCommand::Exit.
                             warn!("invalid object from Worker, exiting load test");
-                            if !tell_worker_to_exit(&server) {
+                            if !tell_worker_to_exit(server) {
                                 // All workers have exited, shut down the
                                 // load test.
                                 break;
@@ -442,6 +1301,7 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         }
 
                         workers.insert(pipe);
+                        worker_ids.insert(pipe, workers.len());
                         // Expect workers is required so unwrap() is safe.
                         info!(
                             "worker {} of {} connected",
@@ -449,47 +1309,57 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                             swanling_attack.configuration.expect_workers.unwrap(),
                         );
 
-                        // Send new worker a batch of users.
-                        let mut user_batch = users_per_worker;
-                        // If remainder, put extra user in this batch.
-                        if users_remainder > 0 {
-                            users_remainder -= 1;
-                            user_batch += 1;
-                        }
+                        // Send new worker its planned batch of users.
+                        let user_batch = user_batches.next().unwrap_or_default();
                         let mut users = Vec::new();
 
-                        // Pop users from available_users vector and build worker initializer.
-                        debug!("sending {} users to worker", user_batch);
-                        for _ in 1..=user_batch {
-                            let user = match available_users.pop() {
+                        // Take users from available_users vector and build worker initializer.
+                        debug!("sending {} users to worker", user_batch.len());
+                        for index in user_batch {
+                            let user = match available_users[index].take() {
                                 Some(u) => u,
                                 None => {
                                     panic!("not enough available users!?");
                                 }
                             };
+                            // Report how users were actually allocated to task sets.
+                            swanling_attack
+                                .metrics
+                                .record_task_set_user(user.task_sets_index);
                             // Build a vector of SwanlingUser initializers for next worker.
                             users.push(SwanlingUserInitializer {
                                 task_sets_index: user.task_sets_index,
                                 base_url: user.base_url.read().await.to_string(),
                                 min_wait: user.min_wait,
                                 max_wait: user.max_wait,
-                                config: user.config.clone(),
-                                run_time: swanling_attack.run_time,
-                                worker_id: workers.len(),
+                                user_agent: user.user_agent,
+                                user_class: user.user_class,
                             });
                         }
+                        let user_count = users.len();
+
+                        // The configuration, run_time and stop_time are shared by all users,
+                        // so they are only sent to the worker once.
+                        let worker_initializer = SwanlingWorkerInitializer {
+                            config: swanling_attack.configuration.clone(),
+                            run_time: swanling_attack.run_time,
+                            stop_time: swanling_attack.stop_time,
+                            cooldown: swanling_attack.cooldown,
+                            worker_id: workers.len(),
+                            users,
+                        };
 
-                        // Prepare to serialize the list of users to send to the Worker.
+                        // Prepare to serialize the worker initializer to send to the Worker.
                         let mut message = BufWriter::new(Message::new());
 
                         info!("serializing users with serde_cbor...");
-                        serde_cbor::to_writer(&mut message, &users)
+                        serde_cbor::to_writer(&mut message, &worker_initializer)
                             .map_err(|error| eprintln!("{:?}", error))
-                            .expect("failed to serialize user initializers");
+                            .expect("failed to serialize worker initializer");
 
-                        info!("sending {} users to worker {}", users.len(), workers.len());
+                        info!("sending {} users to worker {}", user_count, workers.len());
                         if !send_message_to_worker(
-                            &server,
+                            server,
                             message
                                 .into_inner()
                                 .expect("failed to extract nng message from buffer"),
@@ -508,8 +1378,20 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                             started = time::Instant::now();
                             swanling_attack.started = Some(started);
                             running_metrics_timer = time::Instant::now();
+                            if let Some(metrics_callback) = metrics_callback.as_mut() {
+                                metrics_callback.timer = started;
+                            }
+                            if let Some(metrics_stream) = swanling_attack.metrics_stream.as_mut() {
+                                metrics_stream.timer = started;
+                            }
                             load_test_running = true;
 
+                            // Start the first aggregation window, if enabled.
+                            if swanling_attack.configuration.aggregate_window.is_some() {
+                                aggregate_window_timer = time::Instant::now();
+                                swanling_attack.metrics.start_window(0);
+                            }
+
                             // Run any configured test_start() functions.
                             swanling_attack.run_test_start().await.unwrap();
                         }
@@ -527,67 +1409,267 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         serde_cbor::to_writer(&mut message, &SwanlingUserCommand::Wait)
                             .map_err(|error| eprintln!("{:?}", error))
                             .expect("failed to serialize user command");
-                        if !send_message_to_worker(&server, message) {
+                        if !send_message_to_worker(server, message) {
                             // All workers have exited, shut down the load test.
                             break;
                         }
                         continue;
                     }
 
+                    // When the metrics were pushed on our timeline, in seconds since the
+                    // load test started. Refined by the round trip sent with the Worker's
+                    // clock, which is sent first.
+                    let mut pushed = started.elapsed().as_secs() as usize;
+
+                    // Whether the worker was quarantined, by its index in the metrics.
+                    let worker_id = worker_ids.get(&pipe).copied().unwrap_or(0);
+                    let mut quarantined = swanling_attack
+                        .metrics
+                        .quarantined
+                        .iter()
+                        .position(|worker| worker.worker_id == worker_id);
+
+                    // Discard metrics collected before the metrics were reset, until the
+                    // Worker acknowledges discarding the rest. Workers send the
+                    // acknowledgement before any metrics collected after the reset.
+                    let acknowledged = gaggle_metrics
+                        .iter()
+                        .any(|metric| matches!(metric, GaggleMetrics::Reconfigured(_)));
+                    if reset_workers.get(&pipe) == Some(&true) && acknowledged {
+                        reset_workers.remove(&pipe);
+                    } else if reset_workers.contains_key(&pipe) {
+                        gaggle_metrics.retain(|metric| {
+                            !matches!(
+                                metric,
+                                GaggleMetrics::Requests(_)
+                                    | GaggleMetrics::Groups(_)
+                                    | GaggleMetrics::Tasks(_)
+                                    | GaggleMetrics::Errors(_)
+                                    | GaggleMetrics::Custom(_)
+                                    | GaggleMetrics::Dropped(_)
+                            )
+                        });
+                    }
+
                     for metric in gaggle_metrics {
                         match metric {
+                            // Place the Worker's metrics on our timeline, warning if its
+                            // clock differs too much from ours.
+                            GaggleMetrics::Clock { sent, round_trip } => {
+                                let received = std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .map(|duration| duration.as_millis() as u64)
+                                    .unwrap_or(0);
+                                let offset = worker_clock_offset(sent, round_trip, received);
+                                // Only warn when the offset first exceeds the threshold.
+                                let warned = worker_clock_offsets
+                                    .insert(pipe, offset)
+                                    .filter(|previous| previous.abs() > WORKER_CLOCK_OFFSET_WARNING)
+                                    .is_some();
+                                if offset.abs() > WORKER_CLOCK_OFFSET_WARNING && !warned {
+                                    warn!(
+                                        "worker {} clock differs from the manager's by {} ms, is it synchronized with NTP?",
+                                        worker_id, offset
+                                    );
+                                }
+                                // Normalized to our clock, the metrics were pushed half a
+                                // round trip before they were received. The offset is only
+                                // diagnostic, it's estimated from this same arrival time so
+                                // `sent - offset` would give the same result.
+                                pushed = (started.elapsed().as_millis() as u64)
+                                    .saturating_sub(round_trip / 2)
+                                    as usize
+                                    / 1_000;
+                            }
                             // Merge in request metrics from Worker.
                             GaggleMetrics::Requests(requests) => {
-                                merge_request_metrics(&mut swanling_attack, requests)
+                                // Compare the Worker's error rate with its peers', if enabled.
+                                if let (None, Some(threshold)) = (
+                                    quarantined,
+                                    swanling_attack.configuration.quarantine_error_threshold,
+                                ) {
+                                    quarantined = quarantine_worker(
+                                        &mut swanling_attack,
+                                        &mut worker_requests,
+                                        worker_id,
+                                        &requests,
+                                        threshold,
+                                        pushed,
+                                    );
+                                    // Retrieve the request log of a newly quarantined
+                                    // Worker, to help explain its errors.
+                                    if quarantined.is_some() {
+                                        request_logs.insert(worker_id);
+                                    }
+                                }
+                                // Don't count requests from a quarantined Worker.
+                                if let Some(index) = quarantined {
+                                    swanling_attack.metrics.quarantined[index].ignored_requests +=
+                                        requests
+                                            .values()
+                                            .map(|request| {
+                                                request.success_count + request.fail_count
+                                            })
+                                            .sum::<usize>();
+                                    continue;
+                                }
+                                // Also track the Worker's requests for InfluxDB.
+                                if influx_writer.is_some() {
+                                    let worker_requests =
+                                        influx_requests.entry(worker_id).or_default();
+                                    for (request_key, request) in &requests {
+                                        merge_request(
+                                            worker_requests,
+                                            request_key,
+                                            request,
+                                            swanling_attack.configuration.status_codes,
+                                        );
+                                    }
+                                }
+                                merge_request_metrics(&mut swanling_attack, requests, pushed)
+                            }
+                            // Don't count any other metrics from a quarantined Worker.
+                            GaggleMetrics::Groups(_)
+                            | GaggleMetrics::Tasks(_)
+                            | GaggleMetrics::Errors(_)
+                            | GaggleMetrics::Custom(_)
+                                if quarantined.is_some() => {}
+                            // Merge in request metrics grouped by label from Worker.
+                            GaggleMetrics::Groups(groups) => {
+                                merge_group_metrics(&mut swanling_attack, groups)
                             }
                             // Merge in task metrics from Worker.
                             GaggleMetrics::Tasks(tasks) => {
-                                merge_task_metrics(&mut swanling_attack, tasks)
+                                merge_task_metrics(&mut swanling_attack, tasks, pushed)
                             }
                             // Merge in error metrics from Worker.
                             GaggleMetrics::Errors(errors) => {
                                 merge_error_metrics(&mut swanling_attack, errors)
                             }
+                            // Merge in custom metrics from Worker.
+                            GaggleMetrics::Custom(custom) => {
+                                merge_custom_metrics(&mut swanling_attack, custom)
+                            }
+                            // Log the configuration changes applied by the Worker.
+                            GaggleMetrics::Reconfigured(changes) => {
+                                info!("worker reconfigured: {}", changes.join(", "))
+                            }
+                            // Save the end of the Worker's request log.
+                            GaggleMetrics::RequestLog { name, lines } => {
+                                save_request_log(worker_id, &name, &lines)
+                            }
+                            // Store values published on the Worker, to send to all Workers.
+                            GaggleMetrics::SharedValues(values) => {
+                                swanling_attack.shared_values.merge(values)
+                            }
+                            // Count metric samples the Worker couldn't buffer.
+                            GaggleMetrics::Dropped(dropped) => {
+                                swanling_attack.metrics.dropped_samples += dropped
+                            }
+                            // Add up the iterations run by the users on each Worker.
+                            GaggleMetrics::Iterations(iterations) => {
+                                let total = swanling_attack.metrics.iterations.get_or_insert(
+                                    SwanlingIterations {
+                                        per_user: iterations.per_user,
+                                        ..Default::default()
+                                    },
+                                );
+                                total.completed += iterations.completed;
+                                total.in_progress += iterations.in_progress;
+                            }
                             // Ignore Worker heartbeats.
                             GaggleMetrics::WorkerInit(_) => (),
                         }
                     }
 
-                    if load_test_finished {
+                    if load_test_finished
+                        && (cooldown == 0 || util::timer_expired(exit_timer, cooldown))
+                    {
                         debug!("telling worker to exit");
                         serde_cbor::to_writer(&mut message, &SwanlingUserCommand::Exit)
                             .map_err(|error| eprintln!("{:?}", error))
                             .expect("failed to serialize user command");
                     }
+                    // Send the worker configuration changes it hasn't applied yet.
+                    else if reconfigure_workers.remove(&pipe) {
+                        debug!("telling worker to reconfigure");
+                        serde_cbor::to_writer(
+                            &mut message,
+                            &SwanlingUserCommand::Reconfigure(SwanlingReconfiguration {
+                                reset_metrics: reset_workers
+                                    .get_mut(&pipe)
+                                    .map(|told| !std::mem::replace(told, true))
+                                    == Some(true),
+                                ..reconfiguration.clone()
+                            }),
+                        )
+                        .map_err(|error| eprintln!("{:?}", error))
+                        .expect("failed to serialize user command");
+                    }
+                    // Ask the worker for the end of its request log.
+                    else if request_logs.remove(&worker_id) {
+                        debug!("asking worker {} for its request log", worker_id);
+                        serde_cbor::to_writer(&mut message, &SwanlingUserCommand::SendRequestLog)
+                            .map_err(|error| eprintln!("{:?}", error))
+                            .expect("failed to serialize user command");
+                    }
+                    // Send the worker shared values it hasn't received yet.
+                    else if swanling_attack.shared_values.version() > 0
+                        && shared_values_sent.get(&pipe)
+                            != Some(&swanling_attack.shared_values.version())
+                    {
+                        let (version, values) = swanling_attack.shared_values.snapshot();
+                        debug!("sending worker {} shared values", values.len());
+                        shared_values_sent.insert(pipe, version);
+                        serde_cbor::to_writer(
+                            &mut message,
+                            &SwanlingUserCommand::SharedValues(values),
+                        )
+                        .map_err(|error| eprintln!("{:?}", error))
+                        .expect("failed to serialize user command");
+                    }
                     // Notify the worker that the load test is still running.
                     else {
                         serde_cbor::to_writer(&mut message, &SwanlingUserCommand::Run)
                             .map_err(|error| eprintln!("{:?}", error))
                             .expect("failed to serialize user command");
                     }
-                    if !send_message_to_worker(&server, message) {
+                    if !send_message_to_worker(server, message) {
                         // All workers have exited, shut down the load
                         // test.
                         break;
                     }
                 }
             }
-            Err(e) => {
-                if e == Error::TryAgain {
-                    if !workers.is_empty() && ACTIVE_WORKERS.load(Ordering::SeqCst) == 0 {
-                        info!("all workers have exited");
-                        break;
-                    }
-                    if !load_test_finished {
-                        // Sleep a tenth of a second then return to the loop.
-                        thread::sleep(time::Duration::from_millis(100));
-                    }
-                } else {
-                    panic!("error receiving user message: {}", e);
+            Err(_) => {
+                if !workers.is_empty() && ACTIVE_WORKERS.load(Ordering::SeqCst) == 0 {
+                    info!("all workers have exited");
+                    break;
+                }
+                if !load_test_finished {
+                    // Sleep a tenth of a second then return to the loop.
+                    thread::sleep(time::Duration::from_millis(100));
                 }
             }
         }
     }
+
+    // Write request metrics that arrived since the last aggregation window ended.
+    if let Some(influx_writer) = influx_writer.as_mut() {
+        write_influx_metrics(influx_writer, &mut influx_requests);
+    }
+
+    // Stop listening for Workers, waiting for each shard's thread to exit.
+    drop(shards);
+    drop(messages_rx);
+    for receiver in receivers {
+        let _ = receiver.join();
+    }
+    // Write latency distribution charts from the merged metrics, if enabled.
+    if let Err(e) = swanling_attack.write_chart_output().await {
+        error!("{}", e);
+    }
+
     // Run any configured test_stop() functions.
     swanling_attack.run_test_stop().await.unwrap();
 
@@ -625,4 +1707,192 @@ mod tests {
         assert_eq!(users_per_process, 4);
         assert_eq!(users_remainder, 16);
     }
+
+    #[test]
+    fn test_allocate_users() {
+        // Without pins each worker gets a contiguous batch, taken from the end.
+        let task_sets_indexes = vec![0, 1, 0, 1, 0];
+        let batches = allocate_users(&task_sets_indexes, &HashMap::new(), 2, 1, 2);
+        assert_eq!(batches, vec![vec![4, 3, 2], vec![1, 0]]);
+
+        // Task set 1 is pinned to the second worker.
+        let mut pins = HashMap::new();
+        pins.insert(1, vec![2]);
+        let batches = allocate_users(&task_sets_indexes, &pins, 2, 1, 2);
+        assert_eq!(batches, vec![vec![4, 2, 0], vec![3, 1]]);
+
+        // Pinned users overfill their worker rather than run elsewhere.
+        let task_sets_indexes = vec![1, 1, 1, 0];
+        let batches = allocate_users(&task_sets_indexes, &pins, 2, 0, 2);
+        assert_eq!(batches, vec![vec![3], vec![2, 1, 0]]);
+
+        // Users pinned to several workers are spread across them.
+        let task_sets_indexes = vec![1, 1, 1, 1, 0, 0];
+        pins.insert(1, vec![1, 3]);
+        let batches = allocate_users(&task_sets_indexes, &pins, 2, 0, 3);
+        assert_eq!(batches, vec![vec![3, 1], vec![5, 4], vec![2, 0]]);
+    }
+
+    #[test]
+    fn test_worker_clock_offset() {
+        // Half of the 40 ms round trip is spent in transit.
+        assert_eq!(worker_clock_offset(1_000, 40, 1_020), 0);
+        // The worker's clock is 5 seconds ahead of the manager's.
+        assert_eq!(worker_clock_offset(6_000, 40, 1_020), 5_000);
+        // The worker's clock is 5 seconds behind the manager's.
+        assert_eq!(worker_clock_offset(1_000, 0, 6_000), -5_000);
+    }
+
+    #[test]
+    fn test_out_of_line_error_rate() {
+        // Workers aren't compared until they and their peers made enough requests.
+        assert_eq!(out_of_line_error_rate((50, 50), (1_000, 0), 10), None);
+        assert_eq!(out_of_line_error_rate((1_000, 1_000), (50, 0), 10), None);
+        // An error rate within the threshold of the peers' is fine.
+        assert_eq!(out_of_line_error_rate((200, 30), (1_000, 100), 10), None);
+        // An error rate more than the threshold above the peers' is out of line.
+        assert_eq!(
+            out_of_line_error_rate((200, 50), (1_000, 100), 10),
+            Some((25.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn test_window_at() {
+        let mut windows: Vec<SwanlingMetricsWindow> = [0, 10, 20]
+            .iter()
+            .map(|started| SwanlingMetricsWindow {
+                started: *started,
+                stopped: *started + 10,
+                requests: HashMap::new(),
+                tasks: Vec::new(),
+            })
+            .collect();
+        assert_eq!(window_at(&mut windows, 0).unwrap().started, 0);
+        assert_eq!(window_at(&mut windows, 19).unwrap().started, 10);
+        // Metrics pushed after the last window started are merged into it.
+        assert_eq!(window_at(&mut windows, 35).unwrap().started, 20);
+        assert!(window_at(&mut Vec::new(), 5).is_none());
+    }
+
+    #[test]
+    fn test_decode_initializer() {
+        use crate::worker::decode_initializer;
+        use serde_cbor::Value;
+        use std::collections::BTreeMap;
+
+        let user = SwanlingUserInitializer {
+            base_url: "http://localhost/".to_string(),
+            user_class: "LoadTest".to_string(),
+            ..Default::default()
+        };
+        let initializer = SwanlingWorkerInitializer {
+            run_time: 60,
+            users: vec![user.clone(), user],
+            ..Default::default()
+        };
+        let text = |key: &str| Value::Text(key.to_string());
+        let mut fields = match serde_cbor::value::to_value(&initializer).unwrap() {
+            Value::Map(fields) => fields,
+            _ => unreachable!(),
+        };
+        let encode = |fields: &BTreeMap<Value, Value>| {
+            serde_cbor::to_vec(&Value::Map(fields.clone())).unwrap()
+        };
+
+        // A field added by a newer Manager is ignored, and one it removed takes its default.
+        fields.insert(text("added"), Value::Bool(true));
+        fields.remove(&text("stop_time"));
+        let decoded = decode_initializer(&encode(&fields)).unwrap();
+        assert_eq!(decoded.run_time, 60);
+        assert_eq!(decoded.stop_time, 0);
+        assert_eq!(decoded.users.len(), 2);
+
+        // A user that doesn't parse is skipped.
+        if let Some(Value::Array(users)) = fields.get_mut(&text("users")) {
+            users[0] = text("not a user");
+        }
+        let decoded = decode_initializer(&encode(&fields)).unwrap();
+        assert_eq!(decoded.users.len(), 1);
+        assert_eq!(decoded.users[0].user_class, "LoadTest");
+
+        // Fields and options that don't parse are named.
+        fields.insert(text("run_time"), text("soon"));
+        if let Some(Value::Map(options)) = fields.get_mut(&text("config")) {
+            options.insert(text("users"), text("many"));
+        }
+        let error = decode_initializer(&encode(&fields)).unwrap_err();
+        assert!(error.contains("config.users ("));
+        assert!(error.contains("run_time ("));
+        assert!(!error.contains("cooldown"));
+
+        // A message that isn't an initializer at all.
+        assert!(decode_initializer(b"garbage").is_err());
+    }
+
+    #[test]
+    fn test_split_metrics() {
+        use crate::swanling::SwanlingMethod;
+        use crate::worker::split_metrics;
+
+        let size = |batch: &Vec<GaggleMetrics>| serde_cbor::to_vec(batch).unwrap().len();
+        let mut errors = SwanlingErrorMetrics::new();
+        for index in 0..20 {
+            let mut error = SwanlingErrorMetricAggregate::new(
+                SwanlingMethod::Get,
+                format!("/{}", index),
+                "404 Not Found".to_string(),
+            );
+            error.occurrences = 2;
+            errors.insert(format!("GET /{}", index), error);
+        }
+        let tasks: SwanlingTaskMetrics = vec![(0..20)
+            .map(|index| {
+                let mut task = SwanlingTaskMetricAggregate::new(0, "LoadTest", index, "task");
+                task.counter = 3;
+                task
+            })
+            .collect()];
+        let metrics = vec![GaggleMetrics::Errors(errors), GaggleMetrics::Tasks(tasks)];
+
+        // Without a limit, or within it, the batch is sent as is.
+        assert_eq!(split_metrics(metrics.clone(), 0).len(), 1);
+        let max_msg_size = size(&metrics);
+        assert_eq!(split_metrics(metrics.clone(), max_msg_size).len(), 1);
+
+        // Errors and the tasks of a task set are split, and none are lost.
+        let max_msg_size = max_msg_size / 4;
+        let batches = split_metrics(metrics, max_msg_size);
+        assert!(batches.len() > 4);
+        let (mut occurrences, mut counter) = (0, 0);
+        for batch in &batches {
+            assert!(size(batch) <= max_msg_size);
+            for metric in batch {
+                match metric {
+                    GaggleMetrics::Errors(errors) => {
+                        occurrences += errors.values().map(|e| e.occurrences).sum::<usize>()
+                    }
+                    GaggleMetrics::Tasks(tasks) => {
+                        counter += tasks.iter().flatten().map(|t| t.counter).sum::<usize>()
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+        assert_eq!(occurrences, 40);
+        assert_eq!(counter, 60);
+
+        // A single entry that doesn't fit is dropped, and counted as dropped samples.
+        let mut errors = SwanlingErrorMetrics::new();
+        let mut error = SwanlingErrorMetricAggregate::new(
+            SwanlingMethod::Get,
+            "/".to_string(),
+            "x".repeat(1_000),
+        );
+        error.occurrences = 7;
+        errors.insert("GET /".to_string(), error);
+        let batches = split_metrics(vec![GaggleMetrics::Errors(errors)], 100);
+        assert_eq!(batches.len(), 1);
+        assert!(matches!(batches[0][..], [GaggleMetrics::Dropped(7)]));
+    }
 }
@@ -5,10 +5,11 @@
 //! it returns a [`SwanlingMetrics`] object.
 //!
 //! When the [`SwanlingMetrics`] object is viewed with [`std::fmt::Display`], the
-//! contained [`SwanlingTaskMetrics`], [`SwanlingRequestMetrics`], and
-//! [`SwanlingErrorMetrics`] are displayed in tables.
+//! contained [`SwanlingTaskMetrics`], [`SwanlingRequestMetrics`], [`SwanlingErrorMetrics`],
+//! and [`SwanlingCustomMetrics`] are displayed in tables.
 
 use chrono::prelude::*;
+use futures::Stream;
 use http::StatusCode;
 use itertools::Itertools;
 use num_format::{Locale, ToFormattedString};
@@ -17,13 +18,24 @@ use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize, Serializer};
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
+use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::{f32, fmt};
 use tokio::io::AsyncWriteExt;
 
+#[cfg(feature = "gaggle")]
+use crate::get_worker_id;
 use crate::logger::SwanlingLog;
 use crate::report;
-use crate::swanling::{SwanlingMethod, SwanlingTaskSet};
+#[cfg(feature = "gaggle")]
+use crate::swanling::SwanlingUserCommand;
+use crate::swanling::{
+    SwanlingMethod, SwanlingReconfiguration, SwanlingTaskSet, CONNECTION_ERROR, SIGV4_ERROR,
+    TIMEOUT_ERROR, TLS_HANDSHAKE_ERROR,
+};
 use crate::util;
 #[cfg(feature = "gaggle")]
 use crate::worker::{self, GaggleMetrics};
@@ -48,6 +60,7 @@ use crate::{
 pub enum SwanlingMetric {
     Request(SwanlingRequestMetric),
     Task(SwanlingTaskMetric),
+    Custom(SwanlingCustomMetric),
 }
 
 /// THIS IS IN EXPERIMENTAL FEATURE, DISABLED BY DEFAULT. Optionally mitigate the loss of data
@@ -248,10 +261,100 @@ pub type SwanlingTaskMetrics = Vec<Vec<SwanlingTaskMetricAggregate>>;
 /// ------------------------------------------------------------------------------
 /// 924           GET (Auth) front page: 503 Service Unavailable: /
 /// 715           POST (Auth) front page: 503 Service Unavailable: /user
-/// 36            GET (Anon) front page: error sending request for url (http://example.com/): connection closed before message completed
+/// 36            GET (Anon) front page: Connection failed: Connection refused (os error 111)
+/// ------------------------------------------------------------------------------
+/// 36            Connection failed
 /// ```
+///
+/// Failures to complete a TLS handshake, to connect, and requests that time out, are each
+/// recorded as their own [`SwanlingErrorKind`](./enum.SwanlingErrorKind.html) with the
+/// underlying reason, and counted by kind below the table.
 pub type SwanlingErrorMetrics = BTreeMap<String, SwanlingErrorMetricAggregate>;
 
+/// Name of the entries that combine all request names, and all errors, beyond the limits
+/// of a soak test enabled with the `--soak` run-time option, or beyond the number of
+/// request names set with `--max-request-names`.
+pub const METRICS_OVERFLOW: &str = "(overflow)";
+
+/// Maximum number of request names tracked by name during a soak test.
+pub(crate) const SOAK_MAX_REQUESTS: usize = 1_000;
+
+/// Maximum number of distinct errors tracked during a soak test.
+pub(crate) const SOAK_MAX_ERRORS: usize = 1_000;
+
+/// Maximum number of spike and aggregation windows kept during a soak test.
+pub(crate) const SOAK_MAX_WINDOWS: usize = 100;
+
+/// How many of the slowest requests are kept as examples when `--slow-threshold` is set.
+pub const SLOW_REQUEST_SAMPLES: usize = 5;
+
+/// How many of the slowest outliers are kept as examples when `--outlier-factor` is set.
+pub const OUTLIER_REQUEST_SAMPLES: usize = 5;
+
+/// How many requests with the same name must be made before slower ones can be outliers, so
+/// the average and standard deviation they're compared to are meaningful.
+pub const OUTLIER_MIN_REQUESTS: usize = 100;
+
+/// How many seconds the adaptive throttle configured with `--adaptive-target-p95` measures
+/// response times before each adjustment.
+pub const ADAPTIVE_INTERVAL: usize = 3;
+
+/// The highest percentage of requests that can fail during an interval for the adaptive
+/// throttle to still increase the rate.
+pub const ADAPTIVE_MAX_ERROR_RATE: f32 = 1.0;
+
+/// How many seconds `--ramp-to-failure` measures the error rate before launching more users.
+pub const RAMP_INTERVAL: usize = 5;
+
+/// The percentage of requests that can fail before `--ramp-to-failure` considers the target
+/// broken, if `--error-threshold` isn't set.
+pub const RAMP_ERROR_THRESHOLD: usize = 5;
+
+/// How many consecutive intervals the error rate must exceed the `--error-threshold` before
+/// `--ramp-to-failure` stops the load test.
+pub const RAMP_SUSTAINED_INTERVALS: usize = 2;
+
+/// The percentage of the `--throttle-requests` rate below which a load test fell short of
+/// it, and the cause of the shortfall is reported.
+pub const THROTTLE_EFFICIENCY_SHORTFALL: f32 = 90.0;
+
+/// The percentage of their time requests must spend waiting in the throttle for the throttle
+/// to be the cause of falling short of its rate.
+pub const THROTTLE_WAIT_BOTTLENECK: f32 = 50.0;
+
+/// The percentage of a limit of concurrent requests that must be in flight on average for
+/// the limit to be the cause of falling short of the `--throttle-requests` rate.
+pub const IN_FLIGHT_BOTTLENECK: f32 = 75.0;
+
+/// How often, in seconds, the most recent distinct errors are displayed if any occurred,
+/// when `--error-tail` is set.
+pub const ERROR_TAIL_INTERVAL: usize = 5;
+
+/// Displayed instead of the percentiles of requests with fewer samples than `--min-samples`.
+pub const INSUFFICIENT_SAMPLES: &str = "n/a (insufficient samples)";
+
+/// All custom counters and gauges recorded during a load test.
+///
+/// Load tests can record domain-specific measurements that Swanling doesn't otherwise know
+/// about, such as cache hits reported in a response header or items added to a cart, with
+/// [`SwanlingUser::increment_counter`](../swanling/struct.SwanlingUser.html#method.increment_counter)
+/// and
+/// [`SwanlingUser::observe_gauge`](../swanling/struct.SwanlingUser.html#method.observe_gauge).
+/// Each is stored as a [`SwanlingCustomMetricAggregate`], keyed by name.
+///
+/// # Example
+/// When viewed with [`std::fmt::Display`], [`SwanlingCustomMetrics`] are displayed in
+/// a table:
+/// ```text
+///  === CUSTOM METRICS ===
+/// ------------------------------------------------------------------------------
+/// Name                     | Type    | Total / Mean |        Min |        Max
+/// ------------------------------------------------------------------------------
+/// cache hits               | counter |        4,209 |            |
+/// cart size                | gauge   |         3.27 |       1.00 |      12.00
+/// ```
+pub type SwanlingCustomMetrics = BTreeMap<String, SwanlingCustomMetricAggregate>;
+
 /// For tracking and counting requests made during a load test.
 ///
 /// The request that Swanling is making. User threads send this data to the parent thread
@@ -293,6 +396,23 @@ pub struct SwanlingRequestMetric {
     /// [`SwanlingTask`](../swanling/struct.SwanlingTask.html)s by this
     /// [`SwanlingUser`](../swanling/struct.SwanlingUser.html).
     pub user_cadence: u64,
+    /// How many bytes of request body were sent.
+    pub bytes_sent: u64,
+    /// How many redirects were followed to reach the `final_url`.
+    pub redirects: u64,
+    /// How many milliseconds the request waited in the `--throttle-requests` throttle before
+    /// it was made. This time isn't included in the `response_time`.
+    pub throttle_wait: u64,
+    /// How many milliseconds were spent resolving host names for the request, or `None` if
+    /// no host name was resolved, for example because an open connection was reused. This
+    /// time is included in the `response_time`.
+    pub dns_time: Option<u64>,
+    /// Labels attached to the request with
+    /// [`label`](../swanling/trait.SwanlingRequestLabel.html#tymethod.label).
+    pub labels: BTreeMap<String, String>,
+    /// How many times the request counts toward the request mix, set with
+    /// [`mix_weight`](../swanling/trait.SwanlingRequestMixWeight.html#tymethod.mix_weight).
+    pub mix_weight: usize,
 }
 impl SwanlingRequestMetric {
     pub(crate) fn new(
@@ -317,6 +437,12 @@ impl SwanlingRequestMetric {
             error: "".to_string(),
             coordinated_omission_elapsed: 0,
             user_cadence: 0,
+            bytes_sent: 0,
+            redirects: 0,
+            throttle_wait: 0,
+            dns_time: None,
+            labels: BTreeMap::new(),
+            mix_weight: 1,
         }
     }
 
@@ -340,6 +466,81 @@ impl SwanlingRequestMetric {
             None => 0,
         };
     }
+
+    // Whether the request took longer than the `--slow-threshold`. Response times generated
+    // by Coordinated Omission Mitigation are never slow, as no request was made.
+    pub(crate) fn is_slow(&self, slow_threshold: Option<usize>) -> bool {
+        match slow_threshold {
+            Some(slow_threshold) => {
+                self.coordinated_omission_elapsed == 0 && self.response_time > slow_threshold as u64
+            }
+            None => false,
+        }
+    }
+}
+
+/// What percentage of the time between deciding to make requests and receiving their
+/// responses was spent waiting in the throttle.
+pub(crate) fn throttle_wait_percent(wait_total_time: usize, response_total_time: usize) -> f32 {
+    let total_time = wait_total_time + response_total_time;
+    if total_time == 0 {
+        0.0
+    } else {
+        wait_total_time as f32 / total_time as f32 * 100.0
+    }
+}
+
+/// The name of a request, from its request_key of "{method} {name}".
+fn request_name(request_key: &str) -> &str {
+    request_key
+        .split_once(' ')
+        .map_or(request_key, |(_, name)| name)
+}
+
+/// What percentage of requests were slower than the `--slow-threshold`.
+fn slow_percent(slow_count: usize, counter: usize) -> f32 {
+    if counter == 0 {
+        0.0
+    } else {
+        slow_count as f32 / counter as f32 * 100.0
+    }
+}
+
+/// Format how many requests failed, with the percentage of all requests that failed.
+fn format_fails(fail_count: usize, total_count: usize) -> String {
+    let fail_percent = if fail_count > 0 {
+        fail_count as f32 / total_count as f32 * 100.0
+    } else {
+        0.0
+    };
+    // Compress 100.0 and 0.0 to 100 and 0 respectively to save width.
+    if fail_percent as usize == 100 || fail_percent as usize == 0 {
+        format!("{} ({}%)", format_number(fail_count), fail_percent as usize)
+    } else {
+        format!("{} ({:.1}%)", format_number(fail_count), fail_percent)
+    }
+}
+
+/// A request that took longer than the `--slow-threshold`, kept as an example of a slow
+/// operation.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SwanlingSlowRequest {
+    /// The full URL that was requested.
+    pub url: String,
+    /// How many milliseconds the request took.
+    pub response_time: u64,
+}
+
+/// A request that took more than `--outlier-factor` standard deviations longer than the
+/// average of the requests with the same name before it, kept as an example of an outlier.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SwanlingOutlierRequest {
+    /// The full URL that was requested.
+    pub url: String,
+    /// How many milliseconds the request took.
+    pub response_time: u64,
+    /// How many milliseconds a request had to take to be an outlier when it was made.
+    pub threshold: u64,
 }
 
 /// Metrics collected about a method-path pair, (for example `GET /index`).
@@ -371,6 +572,34 @@ pub struct SwanlingRequestMetricAggregate {
     ///
     /// A count of how many requests resulted in a non-2xx status code.
     pub fail_count: usize,
+    /// Total number of bytes of request body sent for this path-method request.
+    pub bytes_sent: usize,
+    /// Total number of redirects followed by this path-method request.
+    pub redirect_count: usize,
+    /// How long requests waited in the `--throttle-requests` throttle before they were made,
+    /// only collected when the throttle is enabled.
+    pub throttle_wait_data: Option<SwanlingRequestMetricTimingData>,
+    /// How long requests spent resolving host names, only collected for requests that
+    /// resolved a host name rather than reusing an open connection.
+    pub dns_time_data: Option<SwanlingRequestMetricTimingData>,
+    /// Total number of times this path-method request took longer than the `--slow-threshold`.
+    pub slow_count: usize,
+    /// The slowest requests that took longer than the `--slow-threshold`, slowest first. Only
+    /// [`SLOW_REQUEST_SAMPLES`] are kept.
+    pub slow_requests: Vec<SwanlingSlowRequest>,
+    /// Total of each raw response time squared, used with the `raw_data` to calculate the
+    /// standard deviation of response times.
+    pub total_squared_time: u64,
+    /// Total number of times this path-method request was an outlier, more than the
+    /// `--outlier-factor` standard deviations slower than average.
+    pub outlier_count: usize,
+    /// The slowest outliers, slowest first. Only [`OUTLIER_REQUEST_SAMPLES`] are kept.
+    pub outlier_requests: Vec<SwanlingOutlierRequest>,
+    /// How much this path-method request counts toward the request mix, adding up the
+    /// [`mix_weight`](struct.SwanlingRequestMetric.html#structfield.mix_weight) of each
+    /// request. `None` until a request is given a weight other than `1`, as until then
+    /// each request counts once.
+    pub mix_count: Option<usize>,
     /// Load test hash.
     ///
     /// The hash is primarily used when running a distributed Regatta, allowing the Manager to confirm
@@ -379,7 +608,11 @@ pub struct SwanlingRequestMetricAggregate {
 }
 impl SwanlingRequestMetricAggregate {
     /// Create a new SwanlingRequestMetricAggregate object.
-    pub(crate) fn new(path: &str, method: SwanlingMethod, load_test_hash: u64) -> Self {
+    ///
+    /// Along with [`record_time`](#method.record_time) and
+    /// [`set_status_code`](#method.set_status_code) this can be used to build known
+    /// request metrics, for example to test how reports are formatted.
+    pub fn new(path: &str, method: SwanlingMethod, load_test_hash: u64) -> Self {
         trace!("new request");
         SwanlingRequestMetricAggregate {
             path: path.to_string(),
@@ -389,15 +622,71 @@ impl SwanlingRequestMetricAggregate {
             status_code_counts: HashMap::new(),
             success_count: 0,
             fail_count: 0,
+            bytes_sent: 0,
+            redirect_count: 0,
+            throttle_wait_data: None,
+            dns_time_data: None,
+            slow_count: 0,
+            slow_requests: Vec::new(),
+            total_squared_time: 0,
+            outlier_count: 0,
+            outlier_requests: Vec::new(),
+            mix_count: None,
             load_test_hash,
         }
     }
 
-    pub(crate) fn record_time(&mut self, time_elapsed: u64, coordinated_omission_mitigation: bool) {
+    /// Keep this request as an example if it's among the slowest seen so far.
+    pub(crate) fn sample_slow_request(&mut self, slow_request: SwanlingSlowRequest) {
+        let index = self
+            .slow_requests
+            .iter()
+            .position(|sample| sample.response_time < slow_request.response_time)
+            .unwrap_or(self.slow_requests.len());
+        if index < SLOW_REQUEST_SAMPLES {
+            self.slow_requests.insert(index, slow_request);
+            self.slow_requests.truncate(SLOW_REQUEST_SAMPLES);
+        }
+    }
+
+    /// Keep this outlier as an example if it's among the slowest seen so far.
+    pub(crate) fn sample_outlier_request(&mut self, outlier_request: SwanlingOutlierRequest) {
+        let index = self
+            .outlier_requests
+            .iter()
+            .position(|sample| sample.response_time < outlier_request.response_time)
+            .unwrap_or(self.outlier_requests.len());
+        if index < OUTLIER_REQUEST_SAMPLES {
+            self.outlier_requests.insert(index, outlier_request);
+            self.outlier_requests.truncate(OUTLIER_REQUEST_SAMPLES);
+        }
+    }
+
+    /// How many milliseconds a request must take to be an outlier, more than `outlier_factor`
+    /// standard deviations slower than the average raw response time. `None` until at least
+    /// [`OUTLIER_MIN_REQUESTS`] requests have been recorded.
+    pub(crate) fn outlier_threshold(&self, outlier_factor: f32) -> Option<f64> {
+        let counter = self.raw_data.counter;
+        if counter < OUTLIER_MIN_REQUESTS {
+            return None;
+        }
+        let average = self.raw_data.total_time as f64 / counter as f64;
+        // Rounding can make the variance of nearly identical times slightly negative.
+        let variance =
+            (self.total_squared_time as f64 / counter as f64 - average * average).max(0.0);
+        Some(average + outlier_factor as f64 * variance.sqrt())
+    }
+
+    /// Record how many milliseconds a request took. Times generated by Coordinated Omission
+    /// Mitigation are only added to the `coordinated_omission_data`.
+    pub fn record_time(&mut self, time_elapsed: u64, coordinated_omission_mitigation: bool) {
         // Only add time_elapsed to raw_data if the time wasn't generated by Coordinated
         // Omission Mitigation.
         if !coordinated_omission_mitigation {
             self.raw_data.record_time(time_elapsed);
+            self.total_squared_time = self
+                .total_squared_time
+                .saturating_add(time_elapsed.saturating_mul(time_elapsed));
         }
 
         // A Coordinated Omission data object already exists, add a new time into the data.
@@ -414,8 +703,41 @@ impl SwanlingRequestMetricAggregate {
         }
     }
 
+    /// How many requests this path-method request counts as in the request mix.
+    pub fn counted_in_mix(&self) -> usize {
+        self.mix_count
+            .unwrap_or(self.success_count + self.fail_count)
+    }
+
+    /// Count a newly recorded request toward the request mix, once the success or fail
+    /// counter has been incremented.
+    pub(crate) fn record_mix_weight(&mut self, mix_weight: usize) {
+        match self.mix_count.as_mut() {
+            Some(mix_count) => *mix_count += mix_weight,
+            // Until now, each earlier request counted once.
+            None if mix_weight != 1 => {
+                self.mix_count = Some(self.success_count + self.fail_count - 1 + mix_weight)
+            }
+            None => (),
+        }
+    }
+
+    /// Record how long a request waited in the throttle before it was made.
+    pub(crate) fn record_throttle_wait(&mut self, throttle_wait: u64) {
+        self.throttle_wait_data
+            .get_or_insert_with(|| SwanlingRequestMetricTimingData::new(None))
+            .record_time(throttle_wait);
+    }
+
+    /// Record how long a request spent resolving host names.
+    pub(crate) fn record_dns_time(&mut self, dns_time: u64) {
+        self.dns_time_data
+            .get_or_insert_with(|| SwanlingRequestMetricTimingData::new(None))
+            .record_time(dns_time);
+    }
+
     /// Increment counter for status code, creating new counter if first time seeing status code.
-    pub(crate) fn set_status_code(&mut self, status_code: u16) {
+    pub fn set_status_code(&mut self, status_code: u16) {
         let counter = match self.status_code_counts.get(&status_code) {
             // We've seen this status code before, increment counter.
             Some(c) => {
@@ -566,6 +888,10 @@ pub struct SwanlingTaskMetric {
     pub success: bool,
     /// Which SwanlingUser thread processed the request.
     pub user: usize,
+    /// The name and response time of each request the task made, only collected for the
+    /// `--task-profile`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_times: Vec<(String, u64)>,
 }
 impl SwanlingTaskMetric {
     /// Create a new SwanlingTaskMetric metric.
@@ -584,6 +910,7 @@ impl SwanlingTaskMetric {
             run_time: 0,
             success: true,
             user,
+            request_times: Vec::new(),
         }
     }
 
@@ -625,10 +952,13 @@ pub struct SwanlingTaskMetricAggregate {
     pub success_count: usize,
     /// Total number of times task has failed.
     pub fail_count: usize,
+    /// Total milliseconds spent in each request this task made, by request name (for example
+    /// `GET /`). Only collected for the `--task-profile`.
+    pub request_times: BTreeMap<String, usize>,
 }
 impl SwanlingTaskMetricAggregate {
     /// Create a new SwanlingTaskMetricAggregate.
-    pub(crate) fn new(
+    pub fn new(
         taskset_index: usize,
         taskset_name: &str,
         task_index: usize,
@@ -646,11 +976,22 @@ impl SwanlingTaskMetricAggregate {
             counter: 0,
             success_count: 0,
             fail_count: 0,
+            request_times: BTreeMap::new(),
+        }
+    }
+
+    /// Add the time spent in each request made by one run of this task.
+    pub(crate) fn record_request_times(&mut self, request_times: &[(String, u64)]) {
+        for (request_key, response_time) in request_times {
+            *self
+                .request_times
+                .entry(request_key.to_string())
+                .or_insert(0) += *response_time as usize;
         }
     }
 
     /// Track task function elapsed time in milliseconds.
-    pub(crate) fn set_time(&mut self, time: u64, success: bool) {
+    pub fn set_time(&mut self, time: u64, success: bool) {
         // Perform this conversion only once, then re-use throughout this function.
         let time_usize = time as usize;
 
@@ -684,9 +1025,9 @@ impl SwanlingTaskMetricAggregate {
             // Round to nearest 10 for times 100-500 ms.
             101..=500 => ((time as f64 / 10.0).round() * 10.0) as usize,
             // Round to nearest 100 for times 500-1000 ms.
-            501..=1000 => ((time as f64 / 100.0).round() * 10.0) as usize,
+            501..=1000 => ((time as f64 / 100.0).round() * 100.0) as usize,
             // Round to nearest 1000 for larger times.
-            _ => ((time as f64 / 1000.0).round() * 10.0) as usize,
+            _ => ((time as f64 / 1000.0).round() * 1000.0) as usize,
         };
 
         let counter = match self.times.get(&rounded_time) {
@@ -698,6 +1039,52 @@ impl SwanlingTaskMetricAggregate {
         self.times.insert(rounded_time, counter);
         debug!("incremented {} counter: {}", rounded_time, counter);
     }
+
+    /// Get the run-time in milliseconds that the given percent of task runs completed
+    /// within, for example `0.95` for the 95th percentile.
+    pub fn percentile(&self, percent: f32) -> usize {
+        response_time_percentile(
+            &self.times,
+            self.counter,
+            self.min_time,
+            self.max_time,
+            percent,
+        )
+    }
+}
+
+/// Per-task run-time percentiles, included when serializing [`SwanlingMetrics`].
+///
+/// Task run-times measure the entire task function, including every request it makes,
+/// so they are the closest approximation of what a real user experiences.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SwanlingTaskPercentiles {
+    /// An index into [`SwanlingAttack`](../struct.SwanlingAttack.html)`.task_sets`,
+    /// indicating which task set this is.
+    pub taskset_index: usize,
+    /// An index into [`SwanlingTaskSet`](../swanling/struct.SwanlingTaskSet.html)`.task`,
+    /// indicating which task this is.
+    pub task_index: usize,
+    /// An optional name for the task.
+    pub task_name: String,
+    /// Half of all task runs completed within this many milliseconds.
+    pub percentile_50: usize,
+    /// 95% of all task runs completed within this many milliseconds.
+    pub percentile_95: usize,
+    /// 99% of all task runs completed within this many milliseconds.
+    pub percentile_99: usize,
+}
+impl From<&SwanlingTaskMetricAggregate> for SwanlingTaskPercentiles {
+    fn from(task: &SwanlingTaskMetricAggregate) -> Self {
+        SwanlingTaskPercentiles {
+            taskset_index: task.taskset_index,
+            task_index: task.task_index,
+            task_name: task.task_name.to_string(),
+            percentile_50: task.percentile(0.5),
+            percentile_95: task.percentile(0.95),
+            percentile_99: task.percentile(0.99),
+        }
+    }
 }
 
 /// All metrics optionally collected during a Swanling load test.
@@ -736,6 +1123,13 @@ impl SwanlingTaskMetricAggregate {
 ///         ),
 ///         duration: 1,
 ///         users: 1,
+///         task_set_users: [
+///             SwanlingTaskSetUsers {
+///                 name: "ExampleUsers",
+///                 weight: 1,
+///                 users: 1,
+///             },
+///         ],
 ///         requests: {
 ///             "GET /": SwanlingRequestMetricAggregate {
 ///                 path: "/",
@@ -753,6 +1147,16 @@ impl SwanlingTaskMetricAggregate {
 ///                 status_code_counts: {},
 ///                 success_count: 0,
 ///                 fail_count: 221,
+///                 bytes_sent: 0,
+///                 redirect_count: 0,
+///                 throttle_wait_data: None,
+///                 dns_time_data: None,
+///                 slow_count: 0,
+///                 slow_requests: [],
+///                 total_squared_time: 0,
+///                 outlier_count: 0,
+///                 outlier_requests: [],
+///                 mix_count: None,
 ///                 load_test_hash: 0,
 ///             },
 ///         },
@@ -775,6 +1179,7 @@ impl SwanlingTaskMetricAggregate {
 ///                     counter: 221,
 ///                     success_count: 221,
 ///                     fail_count: 0,
+///                     request_times: {},
 ///                 },
 ///             ],
 ///         ],
@@ -786,9 +1191,17 @@ impl SwanlingTaskMetricAggregate {
 ///                 occurrences: 221,
 ///             },
 ///         },
+///         custom: {},
+///         slas: [],
+///         spikes: [],
+///         windows: [],
 ///         final_metrics: true,
 ///         display_status_codes: false,
 ///         display_metrics: true,
+///         slow_threshold: None,
+///         outlier_factor: None,
+///         throttle_requests: 0,
+///         max_connections: 0,
 ///     }
 ///     **/
 ///
@@ -815,6 +1228,10 @@ pub struct SwanlingMetrics {
     /// This value may be smaller than what was configured at start time if the test
     /// didn't run long enough for all configured users to start.
     pub users: usize,
+    /// How many users were actually allocated to each task set, indexed by task set,
+    /// compared with the weight it was configured with. Rounding, and splitting users
+    /// between Workers in a Regatta, can make this differ from the configured weights.
+    pub task_set_users: Vec<SwanlingTaskSetUsers>,
     /// Tracks details about each request made during the load test.
     ///
     /// Can be disabled with the `--no-metrics` run-time option, or with
@@ -834,6 +1251,66 @@ pub struct SwanlingMetrics {
     /// [SwanlingDefault::NoErrorSummary](../enum.SwanlingDefault.html#variant.NoErrorSummary) or
     /// [SwanlingDefault::NoMetrics](../enum.SwanlingDefault.html#variant.NoMetrics).
     pub errors: SwanlingErrorMetrics,
+    /// Tracks custom counters and gauges recorded by the load test.
+    ///
+    /// Disabled with the `--no-metrics` run-time option, or with
+    /// [SwanlingDefault::NoMetrics](../enum.SwanlingDefault.html#variant.NoMetrics).
+    pub custom: SwanlingCustomMetrics,
+    /// Results of checking each service level agreement configured with the `--sla`
+    /// run-time option against the final request metrics.
+    pub slas: Vec<SwanlingSlaResult>,
+    /// Windows of time during which a spike configured with the `--spike` run-time option
+    /// multiplied the number of running users.
+    pub spikes: Vec<SwanlingSpikeWindow>,
+    /// The window of time after users were stopped during which metrics were still
+    /// collected, configured with the `--cooldown` run-time option. This time isn't
+    /// included in the `duration`.
+    pub cooldown: Option<SwanlingCooldownWindow>,
+    /// Request and task metrics aggregated by the Manager over consecutive windows of
+    /// time, configured with the `--aggregate-window` run-time option. The lifetime
+    /// totals are still tracked in `requests` and `tasks`.
+    pub windows: Vec<SwanlingMetricsWindow>,
+    /// The comparison of the final metrics with the baseline JSON report configured with
+    /// the `--baseline` run-time option.
+    pub comparison: Option<SwanlingComparison>,
+    /// The result of running each task once during a smoke test started with the `--smoke`
+    /// run-time flag.
+    pub smoke: Vec<SwanlingSmokeResult>,
+    /// Each adjustment made by the adaptive throttle configured with the
+    /// `--adaptive-target-p95` run-time option, and the highest rate it found to be safe.
+    pub adaptive: Option<SwanlingAdaptive>,
+    /// Each interval measured while ramping up users with the `--ramp-to-failure` run-time
+    /// option, and the load at which the error rate exceeded the `--error-threshold`.
+    pub ramp_to_failure: Option<SwanlingRampToFailure>,
+    /// Requests grouped by the value of the label configured with the `--group-by`
+    /// run-time option, with the label value as key. Requests without the label aren't
+    /// grouped.
+    pub groups: SwanlingRequestMetrics,
+    /// The most recent distinct errors, most recent last, tracked while the load test runs
+    /// with the `--error-tail` run-time option.
+    pub error_tail: Vec<SwanlingRecentError>,
+    /// How many metric samples Workers dropped because their `--worker-metrics-buffer`
+    /// was full, or because they were too large to send with `--gaggle-max-msg-size`.
+    /// These samples are missing from all other metrics.
+    pub dropped_samples: usize,
+    /// Workers the Manager quarantined because their error rate was out of line with
+    /// their peers, configured with the `--quarantine-error-threshold` run-time option.
+    /// Metrics pushed by a Worker after it was quarantined are missing from all other
+    /// metrics.
+    pub quarantined: Vec<SwanlingQuarantinedWorker>,
+    /// How many iterations users completed, and how many they were still running when the
+    /// load test stopped, if limited with the `--iterations-per-user` run-time option.
+    pub iterations: Option<SwanlingIterations>,
+    /// How many requests completed during each second since the load test started, or
+    /// since the metrics were last reset. In a Regatta, requests are counted in the second
+    /// each Worker pushed them to the Manager.
+    pub throughput: Vec<usize>,
+    /// The second of `throughput` from which all users were running, where the steady
+    /// state of the load test starts.
+    pub steady_state_started: usize,
+    /// Flag indicating whether or not errors were added to the `error_tail` since it was
+    /// last displayed.
+    pub(crate) error_tail_changed: bool,
     /// Flag indicating whether or not these are the final metrics, used to determine
     /// which metrics should be displayed. Defaults to false.
     pub(crate) final_metrics: bool,
@@ -842,8 +1319,240 @@ pub struct SwanlingMetrics {
     /// Flag indicating whether or not to display metrics. This defaults to false on
     /// Workers, otherwise true.
     pub(crate) display_metrics: bool,
+    /// The `--slow-threshold` in milliseconds, if slow requests are tracked.
+    pub(crate) slow_threshold: Option<usize>,
+    /// The `--outlier-factor`, if outliers are tracked.
+    pub(crate) outlier_factor: Option<f32>,
+    /// The `--throttle-requests` rate, if requests are throttled.
+    pub(crate) throttle_requests: usize,
+    /// The `--max-connections` limit, if connections are limited.
+    pub(crate) max_connections: usize,
+    /// The `--min-samples` below which percentiles aren't reported, if set.
+    pub(crate) min_samples: Option<usize>,
+    /// The `--group-by` label, if request metrics are grouped.
+    pub(crate) group_by: Option<String>,
+    /// How many requests had been made and had failed when the running metrics were last
+    /// displayed, used to measure the next interval.
+    pub(crate) running_totals: SwanlingRunningInterval,
+    /// The requests made and failed since the running metrics were previously displayed.
+    pub(crate) running_interval: Option<SwanlingRunningInterval>,
 }
 impl SwanlingMetrics {
+    /// Set whether or not to display these metrics when formatted with `{}`.
+    ///
+    /// Together with the public `requests`, `tasks` and `errors` this makes it possible to
+    /// build a known set of metrics, for example to test how reports are formatted:
+    ///
+    /// ```rust
+    /// use swanling::metrics::{SwanlingMetrics, SwanlingRequestMetricAggregate};
+    /// use swanling::swanling::SwanlingMethod;
+    ///
+    /// let mut request = SwanlingRequestMetricAggregate::new("/", SwanlingMethod::Get, 0);
+    /// request.record_time(12, false);
+    /// request.set_status_code(200);
+    /// request.success_count += 1;
+    ///
+    /// let mut metrics = SwanlingMetrics::default();
+    /// metrics.requests.insert("GET /".to_string(), request);
+    /// metrics.set_display_metrics(true);
+    /// metrics.set_final_metrics(true);
+    ///
+    /// assert!(metrics.to_string().contains("PER REQUEST METRICS"));
+    /// assert!(metrics.html_report("http://example.com").contains("http://example.com"));
+    /// ```
+    pub fn set_display_metrics(&mut self, display_metrics: bool) {
+        self.display_metrics = display_metrics;
+    }
+
+    /// Set whether or not these are the final metrics, which display more detail.
+    pub fn set_final_metrics(&mut self, final_metrics: bool) {
+        self.final_metrics = final_metrics;
+    }
+
+    /// Set whether or not to display how often each status code was returned.
+    pub fn set_display_status_codes(&mut self, display_status_codes: bool) {
+        self.display_status_codes = display_status_codes;
+    }
+
+    /// Move an error to the end of the `error_tail`, dropping the oldest errors so at most
+    /// `size` distinct errors are kept.
+    pub(crate) fn record_recent_error(
+        &mut self,
+        size: usize,
+        error: &SwanlingErrorMetricAggregate,
+        occurrences: usize,
+        last_seen: usize,
+    ) {
+        let mut recent_error = match self.error_tail.iter().position(|recent_error| {
+            recent_error.method == error.method
+                && recent_error.name == error.name
+                && recent_error.error == error.error
+        }) {
+            Some(index) => self.error_tail.remove(index),
+            None => SwanlingRecentError {
+                method: error.method.clone(),
+                name: error.name.clone(),
+                error: error.error.clone(),
+                occurrences: 0,
+                last_seen,
+            },
+        };
+        recent_error.occurrences += occurrences;
+        recent_error.last_seen = last_seen;
+        self.error_tail.push(recent_error);
+        if self.error_tail.len() > size {
+            self.error_tail.drain(..self.error_tail.len() - size);
+        }
+        self.error_tail_changed = true;
+    }
+
+    /// Display the most recent distinct errors, if any occurred since they were last
+    /// displayed.
+    pub(crate) fn print_error_tail(&mut self) {
+        if !self.error_tail_changed {
+            return;
+        }
+        self.error_tail_changed = false;
+
+        let mut table = format!(
+            " === RECENT ERRORS ===\n {:-<78}\n {:<9} | {:<11} | Error\n {:-<78}\n",
+            "", "Last seen", "Count", ""
+        );
+        // Display the most recent error first.
+        for recent_error in self.error_tail.iter().rev() {
+            table.push_str(&format!(
+                " {:<9} | {:<11} | {} {}: {}\n",
+                format!("{}s", recent_error.last_seen),
+                format_number(recent_error.occurrences),
+                recent_error.method,
+                recent_error.name,
+                recent_error.error
+            ));
+        }
+        table.push_str(&format!(" {:-<78}\n", ""));
+        println!("{}", table);
+    }
+
+    /// Check each configured service level agreement against the final request metrics,
+    /// storing the results for display. Returns `true` if all agreements were met.
+    pub(crate) fn check_slas(&mut self, slas: &[String]) -> bool {
+        self.slas = Vec::new();
+        for sla in slas {
+            // Agreements are validated when the load test starts.
+            let (name, threshold) = match parse_sla(sla) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            // Combine all requests with this name, regardless of method.
+            let mut times: BTreeMap<usize, usize> = BTreeMap::new();
+            let mut counter: usize = 0;
+            let mut min_time: usize = 0;
+            let mut max_time: usize = 0;
+            let mut success_count: usize = 0;
+            let mut fail_count: usize = 0;
+            for (request_key, request) in &self.requests {
+                if request.path != name && request_key != &name {
+                    continue;
+                }
+                times = merge_times(times, request.raw_data.times.clone());
+                counter += request.raw_data.counter;
+                min_time = update_min_time(min_time, request.raw_data.minimum_time);
+                max_time = update_max_time(max_time, request.raw_data.maximum_time);
+                success_count += request.success_count;
+                fail_count += request.fail_count;
+            }
+
+            // An agreement for a request that was never made can't be met.
+            let observed = if success_count + fail_count == 0 {
+                None
+            } else {
+                Some(match threshold {
+                    SwanlingSlaThreshold::Percentile { percent, .. } => response_time_percentile(
+                        &times,
+                        counter,
+                        min_time,
+                        max_time,
+                        percent / 100.0,
+                    )
+                        as f32,
+                    SwanlingSlaThreshold::Maximum(_) => max_time as f32,
+                    SwanlingSlaThreshold::ErrorRate(_) => {
+                        fail_count as f32 * 100.0 / (success_count + fail_count) as f32
+                    }
+                })
+            };
+
+            let passed = match observed {
+                Some(value) => value <= threshold.limit(),
+                None => false,
+            };
+            if !passed {
+                warn!("service level agreement breached: {}", sla);
+            }
+
+            self.slas.push(SwanlingSlaResult {
+                name,
+                threshold,
+                observed,
+                passed,
+            });
+        }
+
+        self.slas.iter().all(|sla| sla.passed)
+    }
+
+    /// Map the final metrics to JUnit testcases: one for each service level agreement if
+    /// any were checked, otherwise one for each request, failing if any request failed.
+    pub(crate) fn junit_test_cases(&self) -> Vec<report::JunitTestCase> {
+        if !self.slas.is_empty() {
+            return self
+                .slas
+                .iter()
+                .map(|sla| report::JunitTestCase {
+                    classname: "sla".to_string(),
+                    name: format!("{}: {}", sla.name, sla.threshold),
+                    time: 0.0,
+                    failure: if sla.passed {
+                        None
+                    } else {
+                        Some(format!(
+                            "observed {}, breaching {}",
+                            sla.observed_string(),
+                            sla.threshold
+                        ))
+                    },
+                })
+                .collect();
+        }
+
+        self.requests
+            .iter()
+            .sorted()
+            .map(|(request_key, request)| {
+                let total = request.success_count + request.fail_count;
+                report::JunitTestCase {
+                    classname: "requests".to_string(),
+                    name: request_key.to_string(),
+                    // Average response time, in seconds.
+                    time: request.raw_data.total_time as f32
+                        / request.raw_data.counter.max(1) as f32
+                        / 1000.0,
+                    failure: if request.fail_count == 0 {
+                        None
+                    } else {
+                        Some(format!(
+                            "{} of {} requests failed ({:.2}%)",
+                            format_number(request.fail_count),
+                            format_number(total),
+                            request.fail_count as f32 * 100.0 / total as f32
+                        ))
+                    },
+                }
+            })
+            .collect()
+    }
+
     /// Initialize the task_metrics vector.
     pub(crate) fn initialize_task_metrics(
         &mut self,
@@ -867,6 +1576,220 @@ impl SwanlingMetrics {
         }
     }
 
+    /// Start tracking how many users are allocated to each task set.
+    pub(crate) fn initialize_task_set_users(&mut self, task_sets: &[SwanlingTaskSet]) {
+        self.task_set_users = task_sets
+            .iter()
+            .map(|task_set| SwanlingTaskSetUsers {
+                name: task_set.name.to_string(),
+                weight: task_set.weight,
+                users: 0,
+            })
+            .collect();
+    }
+
+    /// Count a user allocated to the task set at `task_sets_index`.
+    pub(crate) fn record_task_set_user(&mut self, task_sets_index: usize) {
+        if let Some(task_set_users) = self.task_set_users.get_mut(task_sets_index) {
+            task_set_users.users += 1;
+        }
+    }
+
+    #[cfg(feature = "gaggle")]
+    /// Close the current aggregation window, if any, and start a new one `started`
+    /// seconds after the load test started.
+    pub(crate) fn start_window(&mut self, started: usize) {
+        self.close_window(started);
+
+        // Each window tracks the same tasks as the lifetime metrics.
+        let tasks = self.empty_task_metrics();
+
+        self.windows.push(SwanlingMetricsWindow {
+            started,
+            stopped: started,
+            requests: HashMap::new(),
+            tasks,
+        });
+    }
+
+    /// Discard all request, task, error and custom metrics collected so far, starting
+    /// a clean measurement. Task metrics are zeroed, but still track the same tasks.
+    ///
+    /// Swanling does this automatically after all users have started unless the
+    /// `--no-reset-metrics` run-time option is enabled, and when the `reset-metrics`
+    /// Controller command is received.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::metrics::SwanlingMetrics;
+    ///
+    /// let mut swanling_metrics = SwanlingMetrics::default();
+    /// swanling_metrics.reset_metrics();
+    /// assert!(swanling_metrics.requests.is_empty());
+    /// ```
+    pub fn reset_metrics(&mut self) {
+        self.requests = HashMap::new();
+        self.groups = HashMap::new();
+        self.tasks = self.empty_task_metrics();
+        self.errors = BTreeMap::new();
+        self.custom = BTreeMap::new();
+        self.throughput = Vec::new();
+        self.steady_state_started = 0;
+        self.running_totals = SwanlingRunningInterval::default();
+        self.running_interval = None;
+        self.windows = Vec::new();
+    }
+
+    /// Count requests that completed `second` seconds after the load test started.
+    pub(crate) fn record_throughput(&mut self, second: usize, requests: usize) {
+        if self.throughput.len() <= second {
+            self.throughput.resize(second + 1, 0);
+        }
+        self.throughput[second] += requests;
+    }
+
+    /// Returns how stable the number of requests per second was during the steady state,
+    /// from when all users were running until the load test stopped. Returns `None` if
+    /// the steady state lasted less than two seconds, or no requests were made.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::metrics::SwanlingMetrics;
+    ///
+    /// let mut metrics = SwanlingMetrics::default();
+    /// metrics.duration = 4;
+    /// metrics.throughput = vec![10, 12, 8, 10];
+    ///
+    /// let stability = metrics.throughput_stability().unwrap();
+    /// assert_eq!(stability.seconds, 4);
+    /// assert_eq!(stability.mean, 10.0);
+    /// assert!((stability.coefficient_of_variation - 0.1414).abs() < 0.001);
+    /// ```
+    pub fn throughput_stability(&self) -> Option<SwanlingThroughputStability> {
+        // Seconds without any requests are part of the timeline, even at the end.
+        let seconds = self.duration.checked_sub(self.steady_state_started)?;
+        if seconds < 2 {
+            return None;
+        }
+        let timeline: Vec<usize> = (self.steady_state_started..self.duration)
+            .map(|second| self.throughput.get(second).copied().unwrap_or(0))
+            .collect();
+        let mean = timeline.iter().sum::<usize>() as f32 / seconds as f32;
+        if mean == 0.0 {
+            return None;
+        }
+        let variance = timeline
+            .iter()
+            .map(|requests| (*requests as f32 - mean).powi(2))
+            .sum::<f32>()
+            / seconds as f32;
+        let std_dev = variance.sqrt();
+        Some(SwanlingThroughputStability {
+            seconds,
+            mean,
+            std_dev,
+            min: *timeline.iter().min().unwrap_or(&0),
+            max: *timeline.iter().max().unwrap_or(&0),
+            coefficient_of_variation: std_dev / mean,
+        })
+    }
+
+    /// Returns how close the steady state of the load test came to the rate of requests
+    /// configured with `--throttle-requests`, and what limited it if it fell short. Not
+    /// available unless requests are throttled, or with the adaptive throttle.
+    ///
+    /// When the rate falls short, the time requests spent waiting in the throttle and the
+    /// average number of requests in flight tell whether the load generator or the target
+    /// was the bottleneck.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::metrics::{SwanlingBottleneck, SwanlingMetrics};
+    ///
+    /// let metrics = SwanlingMetrics::default();
+    ///
+    /// // Without a throttle there's no intended rate to compare with.
+    /// assert!(metrics.throttle_efficiency().is_none());
+    /// ```
+    pub fn throttle_efficiency(&self) -> Option<SwanlingThrottleEfficiency> {
+        if self.throttle_requests == 0 || self.adaptive.is_some() {
+            return None;
+        }
+        let achieved = self.throughput_stability()?.mean;
+
+        let mut wait_total_time = 0;
+        let mut response_total_time = 0;
+        let mut response_counter = 0;
+        for request in self.requests.values() {
+            if let Some(throttle_wait) = request.throttle_wait_data.as_ref() {
+                wait_total_time += throttle_wait.total_time;
+            }
+            response_total_time += request.raw_data.total_time;
+            response_counter += request.raw_data.counter;
+        }
+        let response_average = match response_counter {
+            0 => 0.0,
+            _ => response_total_time as f32 / response_counter as f32,
+        };
+        let throttle_wait = throttle_wait_percent(wait_total_time, response_total_time);
+        // By Little's law, requests in flight are the rate of requests multiplied by how
+        // long each takes.
+        let in_flight = achieved * response_average / 1_000.0;
+        let efficiency = achieved / self.throttle_requests as f32 * 100.0;
+
+        let bottleneck = if efficiency >= THROTTLE_EFFICIENCY_SHORTFALL {
+            None
+        } else if throttle_wait >= THROTTLE_WAIT_BOTTLENECK {
+            Some(SwanlingBottleneck::Throttle)
+        } else if self.max_connections > 0
+            && in_flight >= self.max_connections as f32 * IN_FLIGHT_BOTTLENECK / 100.0
+        {
+            Some(SwanlingBottleneck::MaxConnections)
+        } else if in_flight >= self.users as f32 * IN_FLIGHT_BOTTLENECK / 100.0 {
+            Some(SwanlingBottleneck::Target)
+        } else {
+            Some(SwanlingBottleneck::Users)
+        };
+
+        Some(SwanlingThrottleEfficiency {
+            intended: self.throttle_requests,
+            achieved,
+            efficiency,
+            throttle_wait,
+            in_flight,
+            bottleneck,
+        })
+    }
+
+    /// Returns task metrics for the same tasks as `tasks`, with all counters zeroed.
+    fn empty_task_metrics(&self) -> SwanlingTaskMetrics {
+        self.tasks
+            .iter()
+            .map(|task_set| {
+                task_set
+                    .iter()
+                    .map(|task| {
+                        SwanlingTaskMetricAggregate::new(
+                            task.taskset_index,
+                            &task.taskset_name,
+                            task.task_index,
+                            &task.task_name,
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "gaggle")]
+    /// Record when the current aggregation window stopped, `stopped` seconds after the
+    /// load test started.
+    pub(crate) fn close_window(&mut self, stopped: usize) {
+        if let Some(window) = self.windows.last_mut() {
+            window.stopped = stopped;
+        }
+    }
+
     /// Consumes and display all enabled metrics from a completed load test.
     ///
     /// # Example
@@ -907,18 +1830,66 @@ impl SwanlingMetrics {
     /// started, unless the `--no-reset-metrics` run-time option is enabled. It
     /// is invoked at regular intervals if the `--running-metrics` run-time
     /// option is enabled.
-    pub(crate) fn print_running(&self) {
+    pub(crate) fn print_running(&mut self) {
         if self.display_metrics {
             info!(
                 "printing running metrics after {} seconds...",
                 self.duration
             );
 
+            self.measure_running_interval();
+
             // Include a blank line after printing running metrics.
             println!("{}", self);
         }
     }
 
+    /// Measure how many requests were made and failed since the running metrics were
+    /// previously displayed.
+    fn measure_running_interval(&mut self) {
+        let totals: BTreeMap<String, (usize, usize)> = self
+            .requests
+            .iter()
+            .map(|(request_key, request)| {
+                (
+                    request_key.to_string(),
+                    (
+                        request.success_count + request.fail_count,
+                        request.fail_count,
+                    ),
+                )
+            })
+            .collect();
+        let requests = totals
+            .iter()
+            .map(|(request_key, (total_count, fail_count))| {
+                let (previous_total, previous_fail) = self
+                    .running_totals
+                    .requests
+                    .get(request_key)
+                    .copied()
+                    .unwrap_or((0, 0));
+                (
+                    request_key.to_string(),
+                    (
+                        total_count.saturating_sub(previous_total),
+                        fail_count.saturating_sub(previous_fail),
+                    ),
+                )
+            })
+            .collect();
+        self.running_interval = Some(SwanlingRunningInterval {
+            started: self.running_totals.stopped.min(self.duration),
+            stopped: self.duration,
+            requests,
+        });
+        self.running_totals = SwanlingRunningInterval {
+            started: 0,
+            stopped: self.duration,
+            requests: totals,
+        };
+    }
+
     /// Optionally prepares a table of requests and fails.
     ///
     /// This function is invoked by `SwanlingMetrics::print()` and
@@ -1046,51 +2017,170 @@ impl SwanlingMetrics {
         Ok(())
     }
 
-    /// Optionally prepares a table of tasks.
+    /// Optionally prepares a table of the requests made and failed since the running
+    /// metrics were previously displayed, so a rising error rate is visible while the
+    /// load test runs.
     ///
-    /// This function is invoked by `SwanlingMetrics::print()` and
-    /// `SwanlingMetrics::print_running()`.
-    pub(crate) fn fmt_tasks(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // If there's nothing to display, exit immediately.
-        if self.tasks.is_empty() || !self.display_metrics {
+    /// This function is invoked by `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_running_interval(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the interval when displaying running metrics.
+        if self.final_metrics {
+            return Ok(());
+        }
+
+        let interval = match self.running_interval.as_ref() {
+            Some(i) => i,
+            None => return Ok(()),
+        };
+        if interval.requests.is_empty() {
             return Ok(());
         }
 
-        // Display metrics from tasks Vector
         writeln!(
             fmt,
-            "\n === PER TASK METRICS ===\n ------------------------------------------------------------------------------"
+            "\n === LAST INTERVAL ({}s to {}s) ===\n ------------------------------------------------------------------------------",
+            interval.started, interval.stopped,
         )?;
         writeln!(
             fmt,
             " {:<24} | {:>13} | {:>14} | {:>8} | {:>7}",
-            "Name", "# times run", "# fails", "task/s", "fail/s"
+            "Name", "# reqs", "# fails", "req/s", "fail/s"
         )?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
-        let mut aggregate_fail_count = 0;
+        let seconds = interval.stopped - interval.started;
         let mut aggregate_total_count = 0;
-        let mut task_count = 0;
-        for task_set in &self.tasks {
-            let mut displayed_task_set = false;
-            for task in task_set {
-                task_count += 1;
-                let total_count = task.success_count + task.fail_count;
-                let fail_percent = if task.fail_count > 0 {
-                    task.fail_count as f32 / total_count as f32 * 100.0
-                } else {
-                    0.0
-                };
-                let (runs, fails) =
-                    per_second_calculations(self.duration, total_count, task.fail_count);
-                let runs_precision = determine_precision(runs);
-                let fails_precision = determine_precision(fails);
-
-                // First time through display name of task set.
-                if !displayed_task_set {
-                    writeln!(
+        let mut aggregate_fail_count = 0;
+        for (request_key, (total_count, fail_count)) in &interval.requests {
+            let (reqs, fails) = per_second_calculations(seconds, *total_count, *fail_count);
+            writeln!(
+                fmt,
+                " {:<24} | {:>13} | {:>14} | {:>8.reqs_p$} | {:>7.fails_p$}",
+                util::truncate_string(request_key, 24),
+                format_number(*total_count),
+                format_fails(*fail_count, *total_count),
+                reqs,
+                fails,
+                reqs_p = determine_precision(reqs),
+                fails_p = determine_precision(fails),
+            )?;
+            aggregate_total_count += total_count;
+            aggregate_fail_count += fail_count;
+        }
+        if interval.requests.len() > 1 {
+            writeln!(
+                fmt,
+                " -------------------------+---------------+----------------+----------+--------"
+            )?;
+            let (reqs, fails) =
+                per_second_calculations(seconds, aggregate_total_count, aggregate_fail_count);
+            writeln!(
+                fmt,
+                " {:<24} | {:>13} | {:>14} | {:>8.reqs_p$} | {:>7.fails_p$}",
+                "Aggregated",
+                format_number(aggregate_total_count),
+                format_fails(aggregate_fail_count, aggregate_total_count),
+                reqs,
+                fails,
+                reqs_p = determine_precision(reqs),
+                fails_p = determine_precision(fails),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Prepares a profile of where the task sets spend their time in the folded stack format
+    /// read by flamegraph tools, one `task set;task;request milliseconds` line per request
+    /// made by each task, and one `task set;task milliseconds` line for the rest of the time
+    /// spent in each task.
+    ///
+    /// This function is invoked when writing the `--task-profile`.
+    pub(crate) fn folded_task_profile(&self) -> String {
+        // Semicolons separate the frames of a stack, so they can't be in a frame's name.
+        let frame = |index: usize, name: &str| {
+            format!("{}: {}", index + 1, name)
+                .trim_end()
+                .replace(';', ",")
+        };
+
+        let mut profile = String::new();
+        for task in self.tasks.iter().flatten() {
+            if task.counter == 0 {
+                continue;
+            }
+            let stack = format!(
+                "{};{}",
+                frame(task.taskset_index, &task.taskset_name),
+                frame(task.task_index, &task.task_name)
+            );
+            // Requests made at the same time can add up to more than the task's run time.
+            let request_time: usize = task.request_times.values().sum();
+            let task_time = task.total_time.saturating_sub(request_time);
+            if task_time > 0 {
+                profile.push_str(&format!("{} {}\n", stack, task_time));
+            }
+            for (request_key, request_time) in &task.request_times {
+                if *request_time > 0 {
+                    profile.push_str(&format!(
+                        "{};{} {}\n",
+                        stack,
+                        request_key.replace(';', ","),
+                        request_time
+                    ));
+                }
+            }
+        }
+        profile
+    }
+
+    /// Optionally prepares a table of tasks.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_tasks(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if self.tasks.is_empty() || !self.display_metrics {
+            return Ok(());
+        }
+
+        // Display metrics from tasks Vector
+        writeln!(
+            fmt,
+            "\n === PER TASK METRICS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>13} | {:>14} | {:>8} | {:>7}",
+            "Name", "# times run", "# fails", "task/s", "fail/s"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut aggregate_fail_count = 0;
+        let mut aggregate_total_count = 0;
+        let mut task_count = 0;
+        for task_set in &self.tasks {
+            let mut displayed_task_set = false;
+            for task in task_set {
+                task_count += 1;
+                let total_count = task.success_count + task.fail_count;
+                let fail_percent = if task.fail_count > 0 {
+                    task.fail_count as f32 / total_count as f32 * 100.0
+                } else {
+                    0.0
+                };
+                let (runs, fails) =
+                    per_second_calculations(self.duration, total_count, task.fail_count);
+                let runs_precision = determine_precision(runs);
+                let fails_precision = determine_precision(fails);
+
+                // First time through display name of task set.
+                if !displayed_task_set {
+                    writeln!(
                         fmt,
                         " {:24 } |",
                         util::truncate_string(
@@ -1316,6 +2406,120 @@ impl SwanlingMetrics {
         Ok(())
     }
 
+    /// Optionally prepares a table of slowest task times within several percentiles.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_task_percentiles(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include percentiles when displaying the final metrics report.
+        if self.tasks.is_empty() || !self.display_metrics || !self.final_metrics {
+            return Ok(());
+        }
+
+        let mut aggregate_task_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut aggregate_task_time_counter: usize = 0;
+        let mut aggregate_min_task_time: usize = 0;
+        let mut aggregate_max_task_time: usize = 0;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " Slowest task run within specified percentile of task runs (in ms):"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15}",
+            "Name", "50%", "95%", "99%"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut task_count = 0;
+        for task_set in &self.tasks {
+            let mut displayed_task_set = false;
+            for task in task_set {
+                task_count += 1;
+                // First time through display name of task set.
+                if !displayed_task_set {
+                    writeln!(
+                        fmt,
+                        " {:24 } |",
+                        util::truncate_string(
+                            &format!("{}: {}", task.taskset_index + 1, &task.taskset_name),
+                            60
+                        ),
+                    )?;
+                    displayed_task_set = true;
+                }
+
+                // Iterate over user task times, and merge into global task times.
+                aggregate_task_times = merge_times(aggregate_task_times, task.times.clone());
+
+                // Increment counter tracking individual task times seen.
+                aggregate_task_time_counter += &task.counter;
+
+                // If user had new fastest task time, update global fastest task time.
+                aggregate_min_task_time = update_min_time(aggregate_min_task_time, task.min_time);
+
+                // If user had new slowest task time, update global slowest task time.
+                aggregate_max_task_time = update_max_time(aggregate_max_task_time, task.max_time);
+
+                writeln!(
+                    fmt,
+                    " {:<24} | {:>15} | {:>15} | {:>15}",
+                    util::truncate_string(
+                        &format!("  {}: {}", task.task_index + 1, task.task_name),
+                        24
+                    ),
+                    format_number(task.percentile(0.5)),
+                    format_number(task.percentile(0.95)),
+                    format_number(task.percentile(0.99)),
+                )?;
+            }
+        }
+        if task_count > 1 {
+            writeln!(
+                fmt,
+                " -------------------------+-----------------+-----------------+-----------------"
+            )?;
+            writeln!(
+                fmt,
+                " {:<24} | {:>15} | {:>15} | {:>15}",
+                "Aggregated",
+                calculate_response_time_percentile(
+                    &aggregate_task_times,
+                    aggregate_task_time_counter,
+                    aggregate_min_task_time,
+                    aggregate_max_task_time,
+                    0.5
+                ),
+                calculate_response_time_percentile(
+                    &aggregate_task_times,
+                    aggregate_task_time_counter,
+                    aggregate_min_task_time,
+                    aggregate_max_task_time,
+                    0.95
+                ),
+                calculate_response_time_percentile(
+                    &aggregate_task_times,
+                    aggregate_task_time_counter,
+                    aggregate_min_task_time,
+                    aggregate_max_task_time,
+                    0.99
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Optionally prepares a table of response times.
     ///
     /// This function is invoked by `SwanlingMetrics::print()` and
@@ -1550,6 +2754,37 @@ impl SwanlingMetrics {
         Ok(())
     }
 
+    /// Returns whether enough requests were sampled to report their percentiles, if
+    /// `--min-samples` is set.
+    pub(crate) fn has_min_samples(&self, samples: usize) -> bool {
+        samples >= self.min_samples.unwrap_or(0)
+    }
+
+    /// Prepares the percentiles of a request for the html report, or marks them as
+    /// unavailable if too few requests were sampled.
+    fn response_metric(
+        &self,
+        method: &str,
+        name: &str,
+        response_times: &BTreeMap<usize, usize>,
+        samples: usize,
+        response_time_minimum: usize,
+        response_time_maximum: usize,
+    ) -> report::ResponseMetric {
+        if self.has_min_samples(samples) {
+            report::get_response_metric(
+                method,
+                name,
+                response_times,
+                samples,
+                response_time_minimum,
+                response_time_maximum,
+            )
+        } else {
+            report::insufficient_samples_response_metric(method, name, INSUFFICIENT_SAMPLES)
+        }
+    }
+
     /// Optionally prepares a table of slowest response times within several percentiles.
     ///
     /// This function is invoked by `SwanlingMetrics::print()` and
@@ -1614,6 +2849,16 @@ impl SwanlingMetrics {
                 raw_aggregate_max_response_time,
                 request.raw_data.maximum_time,
             );
+            // Don't report percentiles calculated from too few samples.
+            if !self.has_min_samples(request.raw_data.counter) {
+                writeln!(
+                    fmt,
+                    " {:<24} | {}",
+                    util::truncate_string(request_key, 24),
+                    INSUFFICIENT_SAMPLES
+                )?;
+                continue;
+            }
             // Sort response times so we can calculate a mean.
             writeln!(
                 fmt,
@@ -1668,6 +2913,10 @@ impl SwanlingMetrics {
                 fmt,
                 " -------------------------+--------+--------+--------+--------+--------+-------"
             )?;
+        }
+        if self.requests.len() > 1 && !self.has_min_samples(raw_aggregate_response_time_counter) {
+            writeln!(fmt, " {:<24} | {}", "Aggregated", INSUFFICIENT_SAMPLES)?;
+        } else if self.requests.len() > 1 {
             writeln!(
                 fmt,
                 " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
@@ -1772,6 +3021,16 @@ impl SwanlingMetrics {
                     coordinated_omission_data.maximum_time,
                 );
 
+                // Don't report percentiles calculated from too few samples.
+                if !self.has_min_samples(coordinated_omission_data.counter) {
+                    writeln!(
+                        fmt,
+                        " {:<24} | {}",
+                        util::truncate_string(request_key, 24),
+                        INSUFFICIENT_SAMPLES
+                    )?;
+                    continue;
+                }
                 // Sort response times so we can calculate a mean.
                 writeln!(
                     fmt,
@@ -1839,6 +3098,10 @@ impl SwanlingMetrics {
                 fmt,
                 " -------------------------+--------+--------+--------+--------+--------+-------"
             )?;
+        }
+        if self.requests.len() > 1 && !self.has_min_samples(co_aggregate_response_time_counter) {
+            writeln!(fmt, " {:<24} | {}", "Aggregated", INSUFFICIENT_SAMPLES)?;
+        } else if self.requests.len() > 1 {
             writeln!(
                 fmt,
                 " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
@@ -1934,174 +3197,3032 @@ impl SwanlingMetrics {
         Ok(())
     }
 
-    /// Optionally prepares a table of errors.
+    /// Optionally prepares a table of how long requests waited in the `--throttle-requests`
+    /// throttle before they were made, alongside their response times.
     ///
-    /// This function is invoked by `SwanlingMetrics::print()` and
-    /// `SwanlingMetrics::print_running()`.
-    pub(crate) fn fmt_errors(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Only include errors when displaying the final metrics report, and if there are
-        // errors to display.
-        if !self.final_metrics || self.errors.is_empty() {
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_throttle_wait(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Throttle wait is only collected when the throttle is enabled.
+        if self
+            .requests
+            .values()
+            .all(|request| request.throttle_wait_data.is_none())
+        {
             return Ok(());
         }
 
-        // Write the errors into a vector which can then be sorted by occurrences.
-        let mut errors: Vec<(usize, String)> = Vec::new();
-        for error in self.errors.values() {
-            errors.push((
-                error.occurrences,
-                format!("{} {}: {}", error.method, error.name, error.error),
-            ));
-        }
-
         writeln!(
             fmt,
-            "\n === ERRORS ===\n ------------------------------------------------------------------------------"
+            "\n === THROTTLE WAIT ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>11} | {:>10} | {:>11} | {:>10}",
+            "Name", "Wait (ms)", "Max", "Resp (ms)", "% waiting"
         )?;
-        writeln!(fmt, " {:<11} | Error", "Count")?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
+        let mut aggregate_wait = SwanlingRequestMetricTimingData::new(None);
+        let mut aggregate_response_time = 0;
+        for (request_key, request) in self.requests.iter().sorted() {
+            let throttle_wait = match request.throttle_wait_data.as_ref() {
+                Some(throttle_wait) => throttle_wait,
+                None => continue,
+            };
+            aggregate_wait.total_time += throttle_wait.total_time;
+            aggregate_wait.counter += throttle_wait.counter;
+            aggregate_wait.maximum_time =
+                update_max_time(aggregate_wait.maximum_time, throttle_wait.maximum_time);
+            aggregate_response_time += request.raw_data.total_time;
+            self.fmt_throttle_wait_row(
+                fmt,
+                request_key,
+                throttle_wait,
+                request.raw_data.total_time,
+                request.raw_data.counter,
+            )?;
+        }
 
-        // Reverse sort errors to display the error occuring the most first.
-        for (occurrences, error) in errors.iter().sorted().rev() {
-            writeln!(fmt, " {:<12}  {}", format_number(*occurrences), error)?;
+        // Display aggregated data if there was more than one request.
+        if self.requests.len() > 1 {
+            writeln!(
+                fmt,
+                " -------------------------+-------------+------------+-------------+-----------"
+            )?;
+            let counter = aggregate_wait.counter;
+            self.fmt_throttle_wait_row(
+                fmt,
+                "Aggregated",
+                &aggregate_wait,
+                aggregate_response_time,
+                counter,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Display one row of the throttle wait table.
+    fn fmt_throttle_wait_row(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        name: &str,
+        throttle_wait: &SwanlingRequestMetricTimingData,
+        response_total_time: usize,
+        response_counter: usize,
+    ) -> fmt::Result {
+        let wait_average = match throttle_wait.counter {
+            0 => 0.0,
+            _ => throttle_wait.total_time as f32 / throttle_wait.counter as f32,
+        };
+        let response_average = match response_counter {
+            0 => 0.0,
+            _ => response_total_time as f32 / response_counter as f32,
+        };
+        writeln!(
+            fmt,
+            " {:<24} | {:>11.wait_precision$} | {:>10} | {:>11.response_precision$} | {:>9.2}%",
+            util::truncate_string(name, 24),
+            wait_average,
+            format_number(throttle_wait.maximum_time),
+            response_average,
+            throttle_wait_percent(throttle_wait.total_time, response_total_time),
+            wait_precision = determine_precision(wait_average),
+            response_precision = determine_precision(response_average),
+        )
+    }
+
+    /// Optionally prepares a table of how long requests spent resolving host names,
+    /// alongside their response times.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_dns_time(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // DNS time is only collected for requests that resolved a host name.
+        if self
+            .requests
+            .values()
+            .all(|request| request.dns_time_data.is_none())
+        {
+            return Ok(());
         }
 
+        writeln!(
+            fmt,
+            "\n === DNS RESOLUTION ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>11} | {:>10} | {:>10} | {:>11}",
+            "Name", "# lookups", "Avg (ms)", "Max", "Resp (ms)"
+        )?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
+        let mut aggregate_dns = SwanlingRequestMetricTimingData::new(None);
+        let mut aggregate_response = SwanlingRequestMetricTimingData::new(None);
+        for (request_key, request) in self.requests.iter().sorted() {
+            let dns_time = match request.dns_time_data.as_ref() {
+                Some(dns_time) => dns_time,
+                None => continue,
+            };
+            aggregate_dns.total_time += dns_time.total_time;
+            aggregate_dns.counter += dns_time.counter;
+            aggregate_dns.maximum_time =
+                update_max_time(aggregate_dns.maximum_time, dns_time.maximum_time);
+            aggregate_response.total_time += request.raw_data.total_time;
+            aggregate_response.counter += request.raw_data.counter;
+            self.fmt_dns_time_row(fmt, request_key, dns_time, &request.raw_data)?;
+        }
 
-        Ok(())
+        // Display aggregated data if there was more than one request.
+        if self.requests.len() > 1 {
+            writeln!(
+                fmt,
+                " -------------------------+-------------+------------+------------+------------"
+            )?;
+            self.fmt_dns_time_row(fmt, "Aggregated", &aggregate_dns, &aggregate_response)?;
+        }
+
+        Ok(())
+    }
+
+    // Display one row of the DNS resolution table.
+    fn fmt_dns_time_row(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        name: &str,
+        dns_time: &SwanlingRequestMetricTimingData,
+        response_time: &SwanlingRequestMetricTimingData,
+    ) -> fmt::Result {
+        let dns_average = match dns_time.counter {
+            0 => 0.0,
+            _ => dns_time.total_time as f32 / dns_time.counter as f32,
+        };
+        let response_average = match response_time.counter {
+            0 => 0.0,
+            _ => response_time.total_time as f32 / response_time.counter as f32,
+        };
+        writeln!(
+            fmt,
+            " {:<24} | {:>11} | {:>10.dns_precision$} | {:>10} | {:>11.response_precision$}",
+            util::truncate_string(name, 24),
+            format_number(dns_time.counter),
+            dns_average,
+            format_number(dns_time.maximum_time),
+            response_average,
+            dns_precision = determine_precision(dns_average),
+            response_precision = determine_precision(response_average),
+        )
+    }
+
+    /// Optionally prepares a table of requests slower than the `--slow-threshold`, with
+    /// examples of the slowest URLs.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_slow_requests(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include slow requests when displaying the final metrics report.
+        let slow_threshold = match self.slow_threshold {
+            Some(slow_threshold) if self.final_metrics && self.display_metrics => slow_threshold,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === SLOW REQUESTS (over {} ms) ===\n ------------------------------------------------------------------------------",
+            format_number(slow_threshold)
+        )?;
+        writeln!(fmt, " {:<24} | {:>13} | {:>13}", "Name", "# slow", "% slow")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut slow_count = 0;
+        let mut counter = 0;
+        for (request_key, request) in self.requests.iter().sorted() {
+            slow_count += request.slow_count;
+            counter += request.raw_data.counter;
+            if request.slow_count == 0 {
+                continue;
+            }
+            writeln!(
+                fmt,
+                " {:<24} | {:>13} | {:>12.2}%",
+                util::truncate_string(request_key, 24),
+                format_number(request.slow_count),
+                slow_percent(request.slow_count, request.raw_data.counter),
+            )?;
+            // List the slowest requests as examples.
+            for slow_request in &request.slow_requests {
+                writeln!(
+                    fmt,
+                    "   {:>10} ms  {}",
+                    format_number(slow_request.response_time as usize),
+                    slow_request.url
+                )?;
+            }
+        }
+        writeln!(
+            fmt,
+            " -------------------------+---------------+---------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>13} | {:>12.2}%",
+            "Aggregated",
+            format_number(slow_count),
+            slow_percent(slow_count, counter),
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of requests that were outliers, more than the
+    /// `--outlier-factor` standard deviations slower than average, with examples of the
+    /// slowest URLs.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_outlier_requests(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include outliers when displaying the final metrics report.
+        let outlier_factor = match self.outlier_factor {
+            Some(outlier_factor) if self.final_metrics && self.display_metrics => outlier_factor,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === OUTLIERS (over {} standard deviations slower than average) ===\n ------------------------------------------------------------------------------",
+            outlier_factor
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>13} | {:>13}",
+            "Name", "# outliers", "% outliers"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut outlier_count = 0;
+        let mut counter = 0;
+        for (request_key, request) in self.requests.iter().sorted() {
+            outlier_count += request.outlier_count;
+            counter += request.raw_data.counter;
+            if request.outlier_count == 0 {
+                continue;
+            }
+            writeln!(
+                fmt,
+                " {:<24} | {:>13} | {:>12.2}%",
+                util::truncate_string(request_key, 24),
+                format_number(request.outlier_count),
+                slow_percent(request.outlier_count, request.raw_data.counter),
+            )?;
+            // List the slowest outliers as examples, with how slow they had to be.
+            for outlier_request in &request.outlier_requests {
+                writeln!(
+                    fmt,
+                    "   {:>10} ms  {} (over {} ms)",
+                    format_number(outlier_request.response_time as usize),
+                    outlier_request.url,
+                    format_number(outlier_request.threshold as usize),
+                )?;
+            }
+        }
+        writeln!(
+            fmt,
+            " -------------------------+---------------+---------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>13} | {:>12.2}%",
+            "Aggregated",
+            format_number(outlier_count),
+            slow_percent(outlier_count, counter),
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of requests grouped by the value of a label.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_groups(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include groups when displaying the final metrics report.
+        let group_by = match self.group_by.as_ref() {
+            Some(group_by) if self.final_metrics && self.display_metrics => group_by,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === PER LABEL METRICS (grouped by {}) ===\n ------------------------------------------------------------------------------",
+            group_by
+        )?;
+        writeln!(
+            fmt,
+            " {:<16} | {:>9} | {:>9} | {:>8} | {:>6} | {:>6} | {:>6}",
+            "Value", "# reqs", "# fails", "Avg (ms)", "50%", "95%", "Max"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        if self.groups.is_empty() {
+            writeln!(fmt, " No requests were labeled with {}.", group_by)?;
+        }
+        for (value, group) in self.groups.iter().sorted() {
+            writeln!(
+                fmt,
+                " {:<16} | {:>9} | {:>9} | {:>8.2} | {:>6} | {:>6} | {:>6}",
+                util::truncate_string(value, 16),
+                format_number(group.raw_data.counter),
+                format_number(group.fail_count),
+                group.raw_data.total_time as f32 / group.raw_data.counter.max(1) as f32,
+                calculate_response_time_percentile(
+                    &group.raw_data.times,
+                    group.raw_data.counter,
+                    group.raw_data.minimum_time,
+                    group.raw_data.maximum_time,
+                    0.5
+                ),
+                calculate_response_time_percentile(
+                    &group.raw_data.times,
+                    group.raw_data.counter,
+                    group.raw_data.minimum_time,
+                    group.raw_data.maximum_time,
+                    0.95
+                ),
+                format_number(group.raw_data.maximum_time),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of custom counters and gauges.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_custom(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if self.custom.is_empty() || !self.display_metrics {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === CUSTOM METRICS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:<7} | {:>12} | {:>10} | {:>10}",
+            "Name", "Type", "Total / Mean", "Min", "Max"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (name, custom) in &self.custom {
+            match custom {
+                SwanlingCustomMetricAggregate::Counter(total) => {
+                    writeln!(
+                        fmt,
+                        " {:<24} | {:<7} | {:>12} | {:>10} | {:>10}",
+                        util::truncate_string(name, 24),
+                        "counter",
+                        format_number(*total),
+                        "",
+                        "",
+                    )?;
+                }
+                SwanlingCustomMetricAggregate::Gauge {
+                    minimum, maximum, ..
+                } => {
+                    writeln!(
+                        fmt,
+                        " {:<24} | {:<7} | {:>12.2} | {:>10.2} | {:>10.2}",
+                        util::truncate_string(name, 24),
+                        "gauge",
+                        custom.mean().unwrap_or(0.0),
+                        minimum,
+                        maximum,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of how many users were allocated to each task set,
+    /// compared with the configured weights.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_task_set_users(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include task set users when displaying the final metrics report.
+        if !self.final_metrics || self.task_set_users.is_empty() || !self.display_metrics {
+            return Ok(());
+        }
+
+        let total_weight: usize = self.task_set_users.iter().map(|t| t.weight).sum();
+        let total_users: usize = self.task_set_users.iter().map(|t| t.users).sum();
+        let percent = |value: usize, total: usize| {
+            if total == 0 {
+                0.0
+            } else {
+                value as f32 / total as f32 * 100.0
+            }
+        };
+
+        writeln!(
+            fmt,
+            "\n === USERS PER TASK SET ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>10} | {:>10} | {:>10} | {:>10}",
+            "Name", "Weight", "Weight %", "Users", "Users %"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (index, task_set_users) in self.task_set_users.iter().enumerate() {
+            writeln!(
+                fmt,
+                " {:<24} | {:>10} | {:>9.2}% | {:>10} | {:>9.2}%",
+                util::truncate_string(&format!("{}: {}", index + 1, task_set_users.name), 24),
+                format_number(task_set_users.weight),
+                percent(task_set_users.weight, total_weight),
+                format_number(task_set_users.users),
+                percent(task_set_users.users, total_users),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of the share of all requests each request makes up,
+    /// counting requests by their
+    /// [`mix_weight`](../swanling/trait.SwanlingRequestMixWeight.html#tymethod.mix_weight).
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_request_mix(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the request mix when displaying the final metrics report, and some
+        // requests were weighted.
+        if !self.final_metrics
+            || !self.display_metrics
+            || self.requests.values().all(|r| r.mix_count.is_none())
+        {
+            return Ok(());
+        }
+
+        let total_requests: usize = self
+            .requests
+            .values()
+            .map(|r| r.success_count + r.fail_count)
+            .sum();
+        let total_mix: usize = self.requests.values().map(|r| r.counted_in_mix()).sum();
+        let percent = |value: usize, total: usize| {
+            if total == 0 {
+                0.0
+            } else {
+                value as f32 / total as f32 * 100.0
+            }
+        };
+
+        writeln!(
+            fmt,
+            "\n === REQUEST MIX ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>10} | {:>10} | {:>10} | {:>10}",
+            "Name", "# reqs", "Reqs %", "Mix", "Mix %"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (request_key, request) in self.requests.iter().sorted() {
+            let total_count = request.success_count + request.fail_count;
+            writeln!(
+                fmt,
+                " {:<24} | {:>10} | {:>9.2}% | {:>10} | {:>9.2}%",
+                util::truncate_string(request_key, 24),
+                format_number(total_count),
+                percent(total_count, total_requests),
+                format_number(request.counted_in_mix()),
+                percent(request.counted_in_mix(), total_mix),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of iterations run by users.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_iterations(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include iterations when displaying the final metrics report.
+        let iterations = match self.iterations.as_ref() {
+            Some(iterations) if self.final_metrics && self.display_metrics => iterations,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === ITERATIONS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>14} | {:>14}",
+            "Per user", "Completed", "In progress"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>14} | {:>14}",
+            format_number(iterations.per_user),
+            format_number(iterations.completed),
+            format_number(iterations.in_progress),
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of spike windows.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_spikes(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include spikes when displaying the final metrics report.
+        if !self.final_metrics || self.spikes.is_empty() || !self.display_metrics {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === SPIKES ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<10} | {:>12} | {:>12} | {:>16}",
+            "Spike", "Started (s)", "Stopped (s)", "Additional users"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (index, spike) in self.spikes.iter().enumerate() {
+            writeln!(
+                fmt,
+                " {:<10} | {:>12} | {:>12} | {:>16}",
+                index + 1,
+                spike.started,
+                match spike.stopped {
+                    Some(stopped) => stopped.to_string(),
+                    None => "-".to_string(),
+                },
+                format_number(spike.users),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a summary of the cooldown after users were stopped.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_cooldown(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the cooldown when displaying the final metrics report.
+        let cooldown = match self.cooldown {
+            Some(cooldown) if self.final_metrics && self.display_metrics => cooldown,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === COOLDOWN ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " Users stopped at {}s, metrics were collected until {}s.",
+            cooldown.started, cooldown.stopped
+        )?;
+        writeln!(
+            fmt,
+            " Metrics above include requests that finished during the cooldown."
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a summary of how stable the number of requests per second was
+    /// during the steady state.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_throughput_stability(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include throughput stability when displaying the final metrics report.
+        if !self.final_metrics || !self.display_metrics {
+            return Ok(());
+        }
+        let stability = match self.throughput_stability() {
+            Some(stability) => stability,
+            None => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === THROUGHPUT STABILITY ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<9} | {:>10} | {:>10} | {:>10} | {:>10} | {:>10}",
+            "Seconds", "Mean req/s", "Std dev", "Min", "Max", "CV"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<9} | {:>10.2} | {:>10.2} | {:>10} | {:>10} | {:>9.1}%",
+            stability.seconds,
+            stability.mean,
+            stability.std_dev,
+            format_number(stability.min),
+            format_number(stability.max),
+            stability.coefficient_of_variation * 100.0,
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a comparison of the rate of requests made during the steady state
+    /// with the `--throttle-requests` rate, and what limited it if it fell short.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_throttle_efficiency(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include throttle efficiency when displaying the final metrics report.
+        if !self.final_metrics || !self.display_metrics {
+            return Ok(());
+        }
+        let efficiency = match self.throttle_efficiency() {
+            Some(efficiency) => efficiency,
+            None => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === THROTTLE EFFICIENCY ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<14} | {:>14} | {:>10} | {:>13} | {:>10}",
+            "Intended req/s", "Achieved req/s", "Efficiency", "Throttle wait", "In flight"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<14} | {:>14.2} | {:>9.1}% | {:>12.2}% | {:>10.2}",
+            format_number(efficiency.intended),
+            efficiency.achieved,
+            efficiency.efficiency,
+            efficiency.throttle_wait,
+            efficiency.in_flight,
+        )?;
+        match efficiency.bottleneck {
+            Some(bottleneck) => writeln!(fmt, " Bottleneck: {}.", bottleneck)?,
+            None => writeln!(fmt, " The intended rate was reached.")?,
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of requests made during the last complete aggregation
+    /// window, reflecting steady-state performance rather than the lifetime totals.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_last_window(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the last window when displaying the final metrics report.
+        if !self.final_metrics || !self.display_metrics {
+            return Ok(());
+        }
+
+        let window = match self.last_complete_window() {
+            Some(w) => w,
+            None => return Ok(()),
+        };
+        if window.requests.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === LAST WINDOW ({}s to {}s) ===\n ------------------------------------------------------------------------------",
+            window.started, window.stopped,
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>8} | {:>8} | {:>8} | {:>8} | {:>7}",
+            "Name", "# reqs", "# fails", "req/s", "Avg (ms)", "95%"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (request_key, request) in window.requests.iter().sorted() {
+            let total_count = request.success_count + request.fail_count;
+            let (reqs, _) = per_second_calculations(
+                window.stopped - window.started,
+                total_count,
+                request.fail_count,
+            );
+            let average = if request.raw_data.counter > 0 {
+                request.raw_data.total_time as f32 / request.raw_data.counter as f32
+            } else {
+                0.0
+            };
+            writeln!(
+                fmt,
+                " {:<24} | {:>8} | {:>8} | {:>8.reqs_p$} | {:>8.2} | {:>7}",
+                util::truncate_string(request_key, 24),
+                format_number(total_count),
+                format_number(request.fail_count),
+                reqs,
+                average,
+                calculate_response_time_percentile(
+                    &request.raw_data.times,
+                    request.raw_data.counter,
+                    request.raw_data.minimum_time,
+                    request.raw_data.maximum_time,
+                    0.95,
+                ),
+                reqs_p = determine_precision(reqs),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the most recent aggregation window that ran for the full configured
+    /// length, ignoring a final window cut short when the load test stopped.
+    pub fn last_complete_window(&self) -> Option<&SwanlingMetricsWindow> {
+        let longest = self.windows.iter().map(|w| w.stopped - w.started).max()?;
+        self.windows
+            .iter()
+            .rev()
+            .find(|w| w.stopped - w.started == longest)
+    }
+
+    /// Optionally prepares a table of service level agreements.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_slas(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include agreements when displaying the final metrics report.
+        if !self.final_metrics || self.slas.is_empty() || !self.display_metrics {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === SERVICE LEVEL AGREEMENTS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:<18} | {:>12} | {:>6}",
+            "Name", "Threshold", "Observed", "Result"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for sla in &self.slas {
+            writeln!(
+                fmt,
+                " {:<24} | {:<18} | {:>12} | {:>6}",
+                util::truncate_string(&sla.name, 24),
+                sla.threshold.to_string(),
+                sla.observed_string(),
+                if sla.passed { "PASS" } else { "FAIL" },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares the result of each task run by a smoke test.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_smoke(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the smoke test when displaying the final metrics report.
+        if !self.final_metrics || self.smoke.is_empty() || !self.display_metrics {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === SMOKE TEST ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, " {:<24} | {:>12} | {:>6}", "Task", "Time", "Result")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut task_set = None;
+        for result in &self.smoke {
+            // Display the name of each task set before its tasks.
+            if task_set != Some(&result.task_set) {
+                writeln!(
+                    fmt,
+                    " {:<24} |",
+                    util::truncate_string(&result.task_set, 60)
+                )?;
+                task_set = Some(&result.task_set);
+            }
+            writeln!(
+                fmt,
+                " {:<24} | {:>12} | {:>6}",
+                util::truncate_string(
+                    &format!("  {}: {}", result.task_index + 1, result.task_name),
+                    24
+                ),
+                format!("{} ms", format_number(result.time as usize)),
+                if result.passed { "PASS" } else { "FAIL" },
+            )?;
+            if let Some(detail) = &result.detail {
+                writeln!(fmt, "     {}", detail)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares each adjustment made by the adaptive throttle, and the highest
+    /// rate found to be safe.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_adaptive(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the adaptive throttle when displaying the final metrics report.
+        let adaptive = match self.adaptive.as_ref() {
+            Some(adaptive) if self.final_metrics && self.display_metrics => adaptive,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === ADAPTIVE THROTTLE (target p95: {} ms) ===\n ------------------------------------------------------------------------------",
+            format_number(adaptive.target_p95)
+        )?;
+        writeln!(
+            fmt,
+            " {:<11} | {:>10} | {:>10} | {:>10} | {:>8} | {:>6}",
+            "Elapsed (s)", "Throttle", "Req/s", "p95 (ms)", "Fail %", "Result"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for step in &adaptive.steps {
+            writeln!(
+                fmt,
+                " {:<11} | {:>10} | {:>10.2} | {:>10} | {:>8.2} | {:>6}",
+                step.elapsed,
+                format_number(step.throttle_requests),
+                step.rps,
+                format_number(step.p95),
+                step.error_rate,
+                if step.within_bounds { "UP" } else { "DOWN" },
+            )?;
+        }
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        match adaptive.max_safe_rps {
+            Some(max_safe_rps) => writeln!(fmt, " Max safe req/s: {:.2}", max_safe_rps)?,
+            None => writeln!(
+                fmt,
+                " Max safe req/s: not found, no interval stayed within bounds"
+            )?,
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares each interval measured while ramping to failure, and the load
+    /// at which the error rate exceeded the threshold.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_ramp_to_failure(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the ramp when displaying the final metrics report.
+        let ramp = match self.ramp_to_failure.as_ref() {
+            Some(ramp) if self.final_metrics && self.display_metrics => ramp,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === RAMP TO FAILURE (error threshold: {}%) ===\n ------------------------------------------------------------------------------",
+            ramp.error_threshold
+        )?;
+        writeln!(
+            fmt,
+            " {:<11} | {:>10} | {:>10} | {:>8} | {:>6}",
+            "Elapsed (s)", "Users", "Req/s", "Fail %", "Result"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for step in &ramp.steps {
+            writeln!(
+                fmt,
+                " {:<11} | {:>10} | {:>10.2} | {:>8.2} | {:>6}",
+                step.elapsed,
+                format_number(step.users),
+                step.rps,
+                step.error_rate,
+                if step.over_threshold { "FAIL" } else { "OK" },
+            )?;
+        }
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        match ramp.breaking_point.as_ref() {
+            Some(breaking_point) => writeln!(
+                fmt,
+                " Breaking point: {} users, {:.2} req/s",
+                format_number(breaking_point.users),
+                breaking_point.rps
+            )?,
+            None => writeln!(
+                fmt,
+                " Breaking point: not found, the error rate stayed within the threshold"
+            )?,
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a comparison with the baseline.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_comparison(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include the comparison when displaying the final metrics report.
+        if !self.final_metrics || !self.display_metrics {
+            return Ok(());
+        }
+
+        match self.comparison.as_ref() {
+            Some(comparison) => write!(fmt, "\n{}", comparison),
+            None => Ok(()),
+        }
+    }
+
+    /// Compare the final request metrics with a baseline JSON report, storing the result
+    /// for display. Returns `true` if nothing regressed.
+    pub(crate) fn check_baseline(
+        &mut self,
+        baseline: &str,
+        bounds: &SwanlingRegressionBounds,
+    ) -> Result<bool, serde_json::Error> {
+        let baseline: SwanlingReportSummary = serde_json::from_str(baseline)?;
+        let current = SwanlingReportSummary::from(&*self);
+        let comparison = SwanlingComparison::new(&baseline, &current, bounds);
+        let regressed = comparison.regressed;
+        self.comparison = Some(comparison);
+        Ok(!regressed)
+    }
+
+    /// Optionally warns that the metrics are incomplete because Workers dropped samples.
+    ///
+    /// This is displayed whenever Workers have dropped samples, configured with the
+    /// `--worker-metrics-buffer` and `--gaggle-max-msg-size` run-time options.
+    pub(crate) fn fmt_dropped_samples(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.dropped_samples == 0 {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n {} samples dropped by Workers, metrics are incomplete.",
+            format_number(self.dropped_samples)
+        )
+    }
+
+    /// Optionally prepares a table of the Workers quarantined by the Manager.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_quarantined(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.quarantined.is_empty() || !self.display_metrics {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === QUARANTINED WORKERS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<8} | {:>16} | {:>10} | {:>10} | {:>16}",
+            "Worker", "Quarantined (s)", "Errors", "Peers", "Ignored requests"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for worker in &self.quarantined {
+            writeln!(
+                fmt,
+                " {:<8} | {:>16} | {:>9.2}% | {:>9.2}% | {:>16}",
+                worker.worker_id,
+                worker.quarantined,
+                worker.error_rate,
+                worker.peer_error_rate,
+                format_number(worker.ignored_requests),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " Metrics pushed by quarantined Workers after they were quarantined are not included."
+        )
+    }
+
+    /// Optionally prepares a table of errors.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_errors(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include errors when displaying the final metrics report, and if there are
+        // errors to display.
+        if !self.final_metrics || self.errors.is_empty() {
+            return Ok(());
+        }
+
+        // Write the errors into a vector which can then be sorted by occurrences.
+        let mut errors: Vec<(usize, String)> = Vec::new();
+        for error in self.errors.values() {
+            errors.push((
+                error.occurrences,
+                format!("{} {}: {}", error.method, error.name, error.error),
+            ));
+        }
+
+        writeln!(
+            fmt,
+            "\n === ERRORS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, " {:<11} | Error", "Count")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+
+        // Reverse sort errors to display the error occuring the most first.
+        for (occurrences, error) in errors.iter().sorted().rev() {
+            writeln!(fmt, " {:<12}  {}", format_number(*occurrences), error)?;
+        }
+
+        // Count connection level and signing failures by kind, as they point at different
+        // problems.
+        let mut kinds: BTreeMap<SwanlingErrorKind, usize> = BTreeMap::new();
+        for error in self.errors.values() {
+            if error.kind != SwanlingErrorKind::Other {
+                *kinds.entry(error.kind).or_insert(0) += error.occurrences;
+            }
+        }
+        if !kinds.is_empty() {
+            writeln!(
+                fmt,
+                " ------------------------------------------------------------------------------"
+            )?;
+            for (kind, occurrences) in kinds {
+                writeln!(fmt, " {:<12}  {}", format_number(occurrences), kind)?;
+            }
+        }
+
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+
+        Ok(())
+    }
+
+    /// Build an HTML-formatted report of these metrics, as written to the `--report-file`.
+    ///
+    /// The `host` is displayed in the report summary. Task metrics are included if any
+    /// were collected, and status codes if `display_status_codes` is set.
+    pub fn html_report(&self, host: &str) -> String {
+        // Prepare report summary variables.
+        let started = self.started.unwrap_or_else(Local::now);
+        let start_time = started.format("%Y-%m-%d %H:%M:%S").to_string();
+        let end_time = (started + chrono::Duration::seconds(self.duration as i64))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        // Prepare requests and responses variables.
+        let mut raw_request_metrics = Vec::new();
+        let mut co_request_metrics = Vec::new();
+        let mut raw_response_metrics = Vec::new();
+        let mut co_response_metrics = Vec::new();
+        let mut raw_aggregate_total_count = 0;
+        let mut co_aggregate_total_count = 0;
+        let mut raw_aggregate_fail_count = 0;
+        let mut raw_aggregate_response_time_counter: usize = 0;
+        let mut raw_aggregate_response_time_minimum: usize = 0;
+        let mut raw_aggregate_response_time_maximum: usize = 0;
+        let mut raw_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut co_aggregate_response_time_counter: usize = 0;
+        let mut co_aggregate_response_time_maximum: usize = 0;
+        let mut co_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut co_data = false;
+        for (request_key, request) in self.requests.iter().sorted() {
+            // Determine whether or not to include Coordinated Omission data.
+            if !co_data && request.coordinated_omission_data.is_some() {
+                co_data = true;
+            }
+            let method = format!("{}", request.method);
+            let name = request_name(request_key);
+            let total_request_count = request.success_count + request.fail_count;
+            let (requests_per_second, failures_per_second) =
+                per_second_calculations(self.duration, total_request_count, request.fail_count);
+            // Prepare per-request metrics.
+            raw_request_metrics.push(report::RequestMetric {
+                method: method.to_string(),
+                name: name.to_string(),
+                number_of_requests: total_request_count,
+                number_of_failures: request.fail_count,
+                response_time_average: format!(
+                    "{:.2}",
+                    request.raw_data.total_time as f32 / request.raw_data.counter as f32
+                ),
+                response_time_minimum: request.raw_data.minimum_time,
+                response_time_maximum: request.raw_data.maximum_time,
+                requests_per_second: format!("{:.2}", requests_per_second),
+                failures_per_second: format!("{:.2}", failures_per_second),
+            });
+
+            // Prepare per-response metrics.
+            raw_response_metrics.push(self.response_metric(
+                &method,
+                name,
+                &request.raw_data.times,
+                request.raw_data.counter,
+                request.raw_data.minimum_time,
+                request.raw_data.maximum_time,
+            ));
+
+            // Collect aggregated request and response metrics.
+            raw_aggregate_total_count += total_request_count;
+            raw_aggregate_fail_count += request.fail_count;
+            raw_aggregate_response_time_counter += request.raw_data.total_time;
+            raw_aggregate_response_time_minimum = update_min_time(
+                raw_aggregate_response_time_minimum,
+                request.raw_data.minimum_time,
+            );
+            raw_aggregate_response_time_maximum = update_max_time(
+                raw_aggregate_response_time_maximum,
+                request.raw_data.maximum_time,
+            );
+            raw_aggregate_response_times =
+                merge_times(raw_aggregate_response_times, request.raw_data.times.clone());
+        }
+
+        // Prepare aggregate per-request metrics.
+        let (raw_aggregate_requests_per_second, raw_aggregate_failures_per_second) =
+            per_second_calculations(
+                self.duration,
+                raw_aggregate_total_count,
+                raw_aggregate_fail_count,
+            );
+        raw_request_metrics.push(report::RequestMetric {
+            method: "".to_string(),
+            name: "Aggregated".to_string(),
+            number_of_requests: raw_aggregate_total_count,
+            number_of_failures: raw_aggregate_fail_count,
+            response_time_average: format!(
+                "{:.2}",
+                raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32
+            ),
+            response_time_minimum: raw_aggregate_response_time_minimum,
+            response_time_maximum: raw_aggregate_response_time_maximum,
+            requests_per_second: format!("{:.2}", raw_aggregate_requests_per_second),
+            failures_per_second: format!("{:.2}", raw_aggregate_failures_per_second),
+        });
+
+        // Prepare aggregate per-response metrics.
+        raw_response_metrics.push(self.response_metric(
+            "",
+            "Aggregated",
+            &raw_aggregate_response_times,
+            raw_aggregate_total_count,
+            raw_aggregate_response_time_minimum,
+            raw_aggregate_response_time_maximum,
+        ));
+
+        // Compile the request metrics template.
+        let mut raw_requests_rows = Vec::new();
+        for metric in raw_request_metrics {
+            raw_requests_rows.push(report::raw_request_metrics_row(metric));
+        }
+
+        // Compile the response metrics template.
+        let mut raw_responses_rows = Vec::new();
+        for metric in raw_response_metrics {
+            raw_responses_rows.push(report::response_metrics_row(metric));
+        }
+
+        let co_requests_template: String;
+        let co_responses_template: String;
+        if co_data {
+            for (request_key, request) in self.requests.iter().sorted() {
+                if let Some(coordinated_omission_data) = request.coordinated_omission_data.as_ref()
+                {
+                    let method = format!("{}", request.method);
+                    let name = request_name(request_key);
+                    let raw_average =
+                        request.raw_data.total_time as f32 / request.raw_data.counter as f32;
+                    let co_average = coordinated_omission_data.total_time as f32
+                        / coordinated_omission_data.counter as f32;
+                    // Prepare per-request metrics.
+                    co_request_metrics.push(report::CORequestMetric {
+                        method: method.to_string(),
+                        name: name.to_string(),
+                        response_time_average: format!("{:.2}", co_average),
+                        response_time_standard_deviation: format!(
+                            "{:.2}",
+                            util::standard_deviation(raw_average, co_average)
+                        ),
+                        response_time_maximum: coordinated_omission_data.maximum_time,
+                    });
+
+                    // Prepare per-response metrics.
+                    co_response_metrics.push(self.response_metric(
+                        &method,
+                        name,
+                        &coordinated_omission_data.times,
+                        coordinated_omission_data.counter,
+                        coordinated_omission_data.minimum_time,
+                        coordinated_omission_data.maximum_time,
+                    ));
+
+                    // Collect aggregated request and response metrics.
+                    co_aggregate_response_time_counter += coordinated_omission_data.total_time;
+                    co_aggregate_response_time_maximum = update_max_time(
+                        co_aggregate_response_time_maximum,
+                        coordinated_omission_data.maximum_time,
+                    );
+                    co_aggregate_response_times = merge_times(
+                        co_aggregate_response_times,
+                        coordinated_omission_data.times.clone(),
+                    );
+                }
+                let total_request_count = request.success_count + request.fail_count;
+                co_aggregate_total_count += total_request_count;
+            }
+            let co_average =
+                co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32;
+            let raw_average =
+                raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32;
+            co_request_metrics.push(report::CORequestMetric {
+                method: "".to_string(),
+                name: "Aggregated".to_string(),
+                response_time_average: format!(
+                    "{:.2}",
+                    co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32
+                ),
+                response_time_standard_deviation: format!(
+                    "{:.2}",
+                    util::standard_deviation(raw_average, co_average),
+                ),
+                response_time_maximum: co_aggregate_response_time_maximum,
+            });
+
+            // Prepare aggregate per-response metrics.
+            co_response_metrics.push(self.response_metric(
+                "",
+                "Aggregated",
+                &co_aggregate_response_times,
+                co_aggregate_total_count,
+                raw_aggregate_response_time_minimum,
+                co_aggregate_response_time_maximum,
+            ));
+
+            // Compile the co_request metrics rows.
+            let mut co_request_rows = Vec::new();
+            for metric in co_request_metrics {
+                co_request_rows.push(report::coordinated_omission_request_metrics_row(metric));
+            }
+
+            // Compile the status_code metrics template.
+            co_requests_template =
+                report::coordinated_omission_request_metrics_template(&co_request_rows.join("\n"));
+
+            // Compile the co_request metrics rows.
+            let mut co_response_rows = Vec::new();
+            for metric in co_response_metrics {
+                co_response_rows.push(report::coordinated_omission_response_metrics_row(metric));
+            }
+
+            // Compile the status_code metrics template.
+            co_responses_template = report::coordinated_omission_response_metrics_template(
+                &co_response_rows.join("\n"),
+            );
+        } else {
+            // If --status-codes is not enabled, return an empty template.
+            co_requests_template = "".to_string();
+            co_responses_template = "".to_string();
+        }
+
+        // Only build the tasks template if --no-task-metrics isn't enabled.
+        let tasks_template: String;
+        if !self.tasks.is_empty() {
+            let mut task_metrics = Vec::new();
+            let mut aggregate_total_count = 0;
+            let mut aggregate_fail_count = 0;
+            let mut aggregate_task_time_counter: usize = 0;
+            let mut aggregate_task_time_minimum: usize = 0;
+            let mut aggregate_task_time_maximum: usize = 0;
+            let mut aggregate_task_times: BTreeMap<usize, usize> = BTreeMap::new();
+            for (task_set_counter, task_set) in self.tasks.iter().enumerate() {
+                for (task_counter, task) in task_set.iter().enumerate() {
+                    if task_counter == 0 {
+                        // Only the taskset_name is used for task sets.
+                        task_metrics.push(report::TaskMetric {
+                            is_task_set: true,
+                            task: "".to_string(),
+                            name: task.taskset_name.to_string(),
+                            number_of_requests: 0,
+                            number_of_failures: 0,
+                            response_time_average: "".to_string(),
+                            response_time_minimum: 0,
+                            response_time_maximum: 0,
+                            requests_per_second: "".to_string(),
+                            failures_per_second: "".to_string(),
+                            percentile_50: "".to_string(),
+                            percentile_95: "".to_string(),
+                            percentile_99: "".to_string(),
+                        });
+                    }
+                    let total_run_count = task.success_count + task.fail_count;
+                    let (requests_per_second, failures_per_second) =
+                        per_second_calculations(self.duration, total_run_count, task.fail_count);
+                    let average = match task.counter {
+                        0 => 0.00,
+                        _ => task.total_time as f32 / task.counter as f32,
+                    };
+                    task_metrics.push(report::TaskMetric {
+                        is_task_set: false,
+                        task: format!("{}.{}", task_set_counter, task_counter),
+                        name: task.task_name.to_string(),
+                        number_of_requests: total_run_count,
+                        number_of_failures: task.fail_count,
+                        response_time_average: format!("{:.2}", average),
+                        response_time_minimum: task.min_time,
+                        response_time_maximum: task.max_time,
+                        requests_per_second: format!("{:.2}", requests_per_second),
+                        failures_per_second: format!("{:.2}", failures_per_second),
+                        percentile_50: format_number(task.percentile(0.5)),
+                        percentile_95: format_number(task.percentile(0.95)),
+                        percentile_99: format_number(task.percentile(0.99)),
+                    });
+
+                    aggregate_total_count += total_run_count;
+                    aggregate_fail_count += task.fail_count;
+                    aggregate_task_times = merge_times(aggregate_task_times, task.times.clone());
+                    aggregate_task_time_counter += &task.counter;
+                    aggregate_task_time_minimum =
+                        update_min_time(aggregate_task_time_minimum, task.min_time);
+                    aggregate_task_time_maximum =
+                        update_max_time(aggregate_task_time_maximum, task.max_time);
+                }
+            }
+
+            let (aggregate_requests_per_second, aggregate_failures_per_second) =
+                per_second_calculations(self.duration, aggregate_total_count, aggregate_fail_count);
+            task_metrics.push(report::TaskMetric {
+                is_task_set: false,
+                task: "".to_string(),
+                name: "Aggregated".to_string(),
+                number_of_requests: aggregate_total_count,
+                number_of_failures: aggregate_fail_count,
+                response_time_average: format!(
+                    "{:.2}",
+                    raw_aggregate_response_time_counter as f32 / aggregate_total_count as f32
+                ),
+                response_time_minimum: aggregate_task_time_minimum,
+                response_time_maximum: aggregate_task_time_maximum,
+                requests_per_second: format!("{:.2}", aggregate_requests_per_second),
+                failures_per_second: format!("{:.2}", aggregate_failures_per_second),
+                percentile_50: calculate_response_time_percentile(
+                    &aggregate_task_times,
+                    aggregate_task_time_counter,
+                    aggregate_task_time_minimum,
+                    aggregate_task_time_maximum,
+                    0.5,
+                ),
+                percentile_95: calculate_response_time_percentile(
+                    &aggregate_task_times,
+                    aggregate_task_time_counter,
+                    aggregate_task_time_minimum,
+                    aggregate_task_time_maximum,
+                    0.95,
+                ),
+                percentile_99: calculate_response_time_percentile(
+                    &aggregate_task_times,
+                    aggregate_task_time_counter,
+                    aggregate_task_time_minimum,
+                    aggregate_task_time_maximum,
+                    0.99,
+                ),
+            });
+            let mut tasks_rows = Vec::new();
+            // Compile the task metrics template.
+            for metric in task_metrics {
+                tasks_rows.push(report::task_metrics_row(metric));
+            }
+
+            tasks_template = report::task_metrics_template(&tasks_rows.join("\n"));
+        } else {
+            tasks_template = "".to_string();
+        }
+
+        // Only build the throttle wait template if the throttle was enabled.
+        let mut throttle_wait_rows = Vec::new();
+        for (request_key, request) in self.requests.iter().sorted() {
+            if let Some(throttle_wait) = request.throttle_wait_data.as_ref() {
+                let method = format!("{}", request.method);
+                let name = request_name(request_key).to_string();
+                let wait_average = match throttle_wait.counter {
+                    0 => 0.0,
+                    _ => throttle_wait.total_time as f32 / throttle_wait.counter as f32,
+                };
+                let response_time_average = match request.raw_data.counter {
+                    0 => 0.0,
+                    _ => request.raw_data.total_time as f32 / request.raw_data.counter as f32,
+                };
+                throttle_wait_rows.push(report::throttle_wait_metrics_row(
+                    report::ThrottleWaitMetric {
+                        method,
+                        name,
+                        wait_average: format!("{:.2}", wait_average),
+                        wait_maximum: throttle_wait.maximum_time,
+                        response_time_average: format!("{:.2}", response_time_average),
+                        waiting_percent: format!(
+                            "{:.2}%",
+                            throttle_wait_percent(
+                                throttle_wait.total_time,
+                                request.raw_data.total_time
+                            )
+                        ),
+                    },
+                ));
+            }
+        }
+        let throttle_wait_template = if !throttle_wait_rows.is_empty() {
+            report::throttle_wait_metrics_template(&throttle_wait_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
+
+        // Only build the DNS resolution template if host names were resolved.
+        let mut dns_time_rows = Vec::new();
+        for (request_key, request) in self.requests.iter().sorted() {
+            if let Some(dns_time) = request.dns_time_data.as_ref() {
+                let method = format!("{}", request.method);
+                let name = request_name(request_key).to_string();
+                let dns_average = match dns_time.counter {
+                    0 => 0.0,
+                    _ => dns_time.total_time as f32 / dns_time.counter as f32,
+                };
+                let response_time_average = match request.raw_data.counter {
+                    0 => 0.0,
+                    _ => request.raw_data.total_time as f32 / request.raw_data.counter as f32,
+                };
+                dns_time_rows.push(report::dns_time_metrics_row(report::DnsTimeMetric {
+                    method,
+                    name,
+                    lookups: dns_time.counter,
+                    dns_average: format!("{:.2}", dns_average),
+                    dns_maximum: dns_time.maximum_time,
+                    response_time_average: format!("{:.2}", response_time_average),
+                }));
+            }
+        }
+        let dns_time_template = if !dns_time_rows.is_empty() {
+            report::dns_time_metrics_template(&dns_time_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
+
+        // Only build the throughput stability template if there was a steady state.
+        let throughput_stability_template = match self.throughput_stability() {
+            Some(stability) => report::throughput_stability_template(&stability),
+            None => "".to_string(),
+        };
+
+        // Only build the throttle efficiency template if requests were throttled.
+        let throttle_efficiency_template = match self.throttle_efficiency() {
+            Some(efficiency) => report::throttle_efficiency_template(&efficiency),
+            None => "".to_string(),
+        };
+
+        // Only build the custom template if custom metrics were recorded.
+        let custom_template = if !self.custom.is_empty() {
+            let mut custom_rows = Vec::new();
+            for (name, custom) in &self.custom {
+                custom_rows.push(report::custom_metrics_row(name, custom));
+            }
+            report::custom_metrics_template(&custom_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
+
+        // Only build the tasks template if --no-task-metrics isn't enabled.
+        let errors_template: String;
+        if !self.errors.is_empty() {
+            let mut error_rows = Vec::new();
+            for error in self.errors.values() {
+                error_rows.push(report::error_row(error));
+            }
+            errors_template = report::errors_template(&error_rows.join("\n"));
+        } else {
+            errors_template = "".to_string();
+        }
+
+        // Only build the status_code template if --status-codes is enabled.
+        let status_code_template: String;
+        if self.display_status_codes {
+            let mut status_code_metrics = Vec::new();
+            let mut aggregated_status_code_counts: HashMap<u16, usize> = HashMap::new();
+            for (request_key, request) in self.requests.iter().sorted() {
+                let method = format!("{}", request.method);
+                let name = request_name(request_key).to_string();
+
+                // Build a list of status codes, and update the aggregate record.
+                let codes = prepare_status_codes(
+                    &request.status_code_counts,
+                    &mut Some(&mut aggregated_status_code_counts),
+                );
+
+                // Add a row of data for the status code table.
+                status_code_metrics.push(report::StatusCodeMetric {
+                    method,
+                    name,
+                    status_codes: codes,
+                });
+            }
+
+            // Build a list of aggregate status codes.
+            let aggregated_codes = prepare_status_codes(&aggregated_status_code_counts, &mut None);
+
+            // Add a final row of aggregate data for the status code table.
+            status_code_metrics.push(report::StatusCodeMetric {
+                method: "".to_string(),
+                name: "Aggregated".to_string(),
+                status_codes: aggregated_codes,
+            });
+
+            // Compile the status_code metrics rows.
+            let mut status_code_rows = Vec::new();
+            for metric in status_code_metrics {
+                status_code_rows.push(report::status_code_metrics_row(metric));
+            }
+
+            // Compile the status_code metrics template.
+            status_code_template =
+                report::status_code_metrics_template(&status_code_rows.join("\n"));
+        } else {
+            // If --status-codes is not enabled, return an empty template.
+            status_code_template = "".to_string();
+        }
+
+        // Compile the report template.
+        report::build_report(
+            &start_time,
+            &end_time,
+            host,
+            report::SwanlingReportTemplates {
+                raw_requests_template: &raw_requests_rows.join("\n"),
+                raw_responses_template: &raw_responses_rows.join("\n"),
+                co_requests_template: &co_requests_template,
+                co_responses_template: &co_responses_template,
+                throttle_wait_template: &throttle_wait_template,
+                dns_time_template: &dns_time_template,
+                throughput_stability_template: &throughput_stability_template,
+                throttle_efficiency_template: &throttle_efficiency_template,
+                tasks_template: &tasks_template,
+                status_codes_template: &status_code_template,
+                custom_template: &custom_template,
+                errors_template: &errors_template,
+            },
+        )
+    }
+}
+impl Serialize for SwanlingMetrics {
+    // SwanlingMetrics serialization can't be derived because of the started field.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("SwanlingMetrics", 32)?;
+        s.serialize_field("hash", &self.hash)?;
+        // Convert started field to a unix timestamp.
+        let timestamp;
+        if let Some(started) = self.started {
+            timestamp = started.timestamp();
+        } else {
+            timestamp = 0;
+        }
+        s.serialize_field("started", &timestamp)?;
+        s.serialize_field("duration", &self.duration)?;
+        s.serialize_field("users", &self.users)?;
+        s.serialize_field("task_set_users", &self.task_set_users)?;
+        s.serialize_field("requests", &self.requests)?;
+        s.serialize_field("tasks", &self.tasks)?;
+        // Task percentiles are calculated from the aggregated task run-times.
+        let task_percentiles: Vec<Vec<SwanlingTaskPercentiles>> = self
+            .tasks
+            .iter()
+            .map(|task_set| task_set.iter().map(SwanlingTaskPercentiles::from).collect())
+            .collect();
+        s.serialize_field("task_percentiles", &task_percentiles)?;
+        s.serialize_field("errors", &self.errors)?;
+        s.serialize_field("custom", &self.custom)?;
+        s.serialize_field("slas", &self.slas)?;
+        s.serialize_field("spikes", &self.spikes)?;
+        s.serialize_field("cooldown", &self.cooldown)?;
+        s.serialize_field("windows", &self.windows)?;
+        s.serialize_field("comparison", &self.comparison)?;
+        s.serialize_field("smoke", &self.smoke)?;
+        s.serialize_field("adaptive", &self.adaptive)?;
+        s.serialize_field("ramp_to_failure", &self.ramp_to_failure)?;
+        s.serialize_field("groups", &self.groups)?;
+        s.serialize_field("error_tail", &self.error_tail)?;
+        s.serialize_field("dropped_samples", &self.dropped_samples)?;
+        s.serialize_field("quarantined", &self.quarantined)?;
+        s.serialize_field("iterations", &self.iterations)?;
+        s.serialize_field("throughput", &self.throughput)?;
+        s.serialize_field("steady_state_started", &self.steady_state_started)?;
+        s.serialize_field("throughput_stability", &self.throughput_stability())?;
+        s.serialize_field("throttle_efficiency", &self.throttle_efficiency())?;
+        s.serialize_field("final_metrics", &self.final_metrics)?;
+        s.serialize_field("display_status_codes", &self.display_status_codes)?;
+        s.serialize_field("display_metrics", &self.display_metrics)?;
+        s.serialize_field("slow_threshold", &self.slow_threshold)?;
+        s.serialize_field("outlier_factor", &self.outlier_factor)?;
+        s.serialize_field("min_samples", &self.min_samples)?;
+        s.serialize_field("group_by", &self.group_by)?;
+        s.end()
+    }
+}
+
+/// Implement format trait to allow displaying metrics.
+impl fmt::Display for SwanlingMetrics {
+    // Implement display of metrics with `{}` marker.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        // Formats from zero to eleven tables of data, depending on what data is contained
+        // and which contained flags are set.
+        self.fmt_tasks(fmt)?;
+        self.fmt_task_times(fmt)?;
+        self.fmt_task_percentiles(fmt)?;
+        self.fmt_requests(fmt)?;
+        self.fmt_running_interval(fmt)?;
+        self.fmt_response_times(fmt)?;
+        self.fmt_throttle_wait(fmt)?;
+        self.fmt_dns_time(fmt)?;
+        self.fmt_percentiles(fmt)?;
+        self.fmt_status_codes(fmt)?;
+        self.fmt_slow_requests(fmt)?;
+        self.fmt_outlier_requests(fmt)?;
+        self.fmt_groups(fmt)?;
+        self.fmt_custom(fmt)?;
+        self.fmt_errors(fmt)?;
+        self.fmt_dropped_samples(fmt)?;
+        self.fmt_quarantined(fmt)?;
+        self.fmt_task_set_users(fmt)?;
+        self.fmt_request_mix(fmt)?;
+        self.fmt_iterations(fmt)?;
+        self.fmt_throughput_stability(fmt)?;
+        self.fmt_throttle_efficiency(fmt)?;
+        self.fmt_last_window(fmt)?;
+        self.fmt_spikes(fmt)?;
+        self.fmt_cooldown(fmt)?;
+        self.fmt_slas(fmt)?;
+        self.fmt_comparison(fmt)?;
+        self.fmt_smoke(fmt)?;
+        self.fmt_adaptive(fmt)?;
+        self.fmt_ramp_to_failure(fmt)
+    }
+}
+
+/// The metrics of each [`SwanlingAttack`](../struct.SwanlingAttack.html) run in sequence
+/// by a [`SwanlingSuite`](../struct.SwanlingSuite.html), under the name it was registered
+/// with.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SwanlingSuiteMetrics {
+    /// The name and metrics of each attack, in the order they ran.
+    pub attacks: Vec<(String, SwanlingMetrics)>,
+}
+impl SwanlingSuiteMetrics {
+    /// Returns the metrics of the attack registered with this name.
+    pub fn get(&self, name: &str) -> Option<&SwanlingMetrics> {
+        self.attacks
+            .iter()
+            .find(|(attack, _)| attack == name)
+            .map(|(_, metrics)| metrics)
+    }
+
+    /// Display the final metrics of each attack, followed by a summary of the suite.
+    pub fn print(&self) {
+        info!(
+            "printing final metrics of {} attacks...",
+            self.attacks.len()
+        );
+        print!("{}", self);
+    }
+
+    /// Optionally prepares a table summarizing the requests made by each attack.
+    ///
+    /// This is displayed after the metrics of every attack.
+    pub(crate) fn fmt_summary(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only summarize attacks when their metrics are displayed.
+        if !self
+            .attacks
+            .iter()
+            .any(|(_, metrics)| metrics.display_metrics)
+        {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === SUITE ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>12} | {:>13} | {:>10} | {:>8}",
+            "Attack", "Duration (s)", "# reqs", "# fails", "Avg (ms)"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (name, metrics) in &self.attacks {
+            let mut total_count = 0;
+            let mut fail_count = 0;
+            let mut total_time = 0;
+            for request in metrics.requests.values() {
+                total_count += request.success_count + request.fail_count;
+                fail_count += request.fail_count;
+                total_time += request.raw_data.total_time;
+            }
+            let average = if total_count > 0 {
+                total_time as f32 / total_count as f32
+            } else {
+                0.0
+            };
+            writeln!(
+                fmt,
+                " {:<24} | {:>12} | {:>13} | {:>10} | {:>8.2}",
+                util::truncate_string(name, 24),
+                metrics.duration,
+                total_count.to_formatted_string(&Locale::en),
+                fail_count.to_formatted_string(&Locale::en),
+                average,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for SwanlingSuiteMetrics {
+    // Implement display of suite metrics with `{}` marker.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        // Each attack is displayed in its own section, under the name it was registered with.
+        for (name, metrics) in &self.attacks {
+            if metrics.display_metrics {
+                writeln!(
+                    fmt,
+                    "\n === ATTACK: {} ===\n ==============================================================================",
+                    name
+                )?;
+                write!(fmt, "{}", metrics)?;
+            }
+        }
+        self.fmt_summary(fmt)
+    }
+}
+
+/// For tracking and counting requests made during a load test.
+///
+/// The request that Swanling is making. User threads send this data to the parent thread
+/// when metrics are enabled. This request object must be provided to calls to
+/// [`set_success`](https://docs.rs/swanling/*/swanling/swanling/struct.SwanlingUser.html#method.set_success)
+/// or
+/// [`set_failure`](https://docs.rs/swanling/*/swanling/swanling/struct.SwanlingUser.html#method.set_failure)
+/// so Swanling knows which request is being updated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwanlingErrorMetric {
+    /// How many milliseconds the load test has been running.
+    pub elapsed: u64,
+    /// The method that was used (ie, Get, Post, etc).
+    pub method: SwanlingMethod,
+    /// The optional name of the request.
+    pub name: String,
+    /// The full URL that was requested.
+    pub url: String,
+    /// The final full URL that was requested, after redirects.
+    pub final_url: String,
+    /// Whether or not the request was redirected.
+    pub redirected: bool,
+    /// How many milliseconds the request took.
+    pub response_time: u64,
+    /// The HTTP response code (optional).
+    pub status_code: u16,
+    /// Which SwanlingUser thread processed the request.
+    pub user: usize,
+    /// The error caused by this request.
+    pub error: String,
+}
+
+/// For tracking and counting errors detected during a load test.
+///
+/// When a load test completes, by default it will include a summary of all errors that
+/// were detected during the load test. Multiple errors that share the same request method,
+/// the same request name, and the same error text are contained within a single
+/// SwanlingErrorMetric object, with `occurrences` indicating how many times this error was
+/// seen.
+///
+/// Individual `SwanlingErrorMetric`s are stored within a
+/// [`SwanlingErrorMetrics`](./type.SwanlingErrorMetrics.html) `BTreeMap` with a string key of
+/// `error.method.name`. The `BTreeMap` is found in the `errors` field of what is returned
+/// by [`SwanlingAttack::execute()`](../struct.SwanlingAttack.html#method.execute) when a load
+/// test finishes.
+///
+/// Can be disabled with the `--no-error-summary` run-time option, or with
+/// [SwanlingDefault::NoErrorSummary](../enum.SwanlingDefault.html#variant.NoErrorSummary).
+///
+/// # Example
+/// In this example, requests to load the front page are failing:
+/// ```text
+/// SwanlingErrorMetric {
+///     method: Get,
+///     name: "(Anon) front page",
+///     error: "503 Service Unavailable: /",
+///     occurrences: 4588,
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SwanlingErrorMetricAggregate {
+    /// The method that resulted in an error.
+    pub method: SwanlingMethod,
+    /// The optional name of the request.
+    pub name: String,
+    /// The error string.
+    pub error: String,
+    /// What kind of failure caused the error.
+    #[serde(default)]
+    pub kind: SwanlingErrorKind,
+    /// A counter reflecting how many times this error occurred.
+    pub occurrences: usize,
+}
+impl SwanlingErrorMetricAggregate {
+    /// Create a new SwanlingErrorMetricAggregate, determining its kind from the error.
+    pub fn new(method: SwanlingMethod, name: String, error: String) -> Self {
+        let kind = SwanlingErrorKind::from_error(&error);
+        SwanlingErrorMetricAggregate {
+            method,
+            name,
+            error,
+            kind,
+            occurrences: 0,
+        }
+    }
+}
+
+/// The kind of failure that caused an error, so failures that point at different problems
+/// can be told apart in the error summary.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub enum SwanlingErrorKind {
+    /// The TLS handshake failed, for example because a certificate was rejected.
+    TlsHandshake,
+    /// A connection couldn't be made, for example because it was refused.
+    Connection,
+    /// The request timed out.
+    Timeout,
+    /// The request couldn't be signed with AWS SigV4, or AWS rejected its signature.
+    Signing,
+    /// Any other error, such as an unexpected response.
+    #[default]
+    Other,
+}
+impl SwanlingErrorKind {
+    /// Determine the kind of failure from the error recorded for a request.
+    pub(crate) fn from_error(error: &str) -> Self {
+        if error.starts_with(TLS_HANDSHAKE_ERROR) {
+            SwanlingErrorKind::TlsHandshake
+        } else if error.starts_with(CONNECTION_ERROR) {
+            SwanlingErrorKind::Connection
+        } else if error.starts_with(TIMEOUT_ERROR) {
+            SwanlingErrorKind::Timeout
+        } else if error.starts_with(SIGV4_ERROR) {
+            SwanlingErrorKind::Signing
+        } else {
+            SwanlingErrorKind::Other
+        }
+    }
+}
+impl fmt::Display for SwanlingErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingErrorKind::TlsHandshake => write!(f, "{}", TLS_HANDSHAKE_ERROR),
+            SwanlingErrorKind::Connection => write!(f, "{}", CONNECTION_ERROR),
+            SwanlingErrorKind::Timeout => write!(f, "{}", TIMEOUT_ERROR),
+            SwanlingErrorKind::Signing => write!(f, "{}", SIGV4_ERROR),
+            SwanlingErrorKind::Other => write!(f, "Other"),
+        }
+    }
+}
+
+/// A distinct error seen recently, tracked with the `--error-tail` run-time option.
+///
+/// Errors that share the same request method, request name and error text are tracked
+/// together. Each time the error occurs again it becomes the most recent error.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SwanlingRecentError {
+    /// The method that resulted in an error.
+    pub method: SwanlingMethod,
+    /// The optional name of the request.
+    pub name: String,
+    /// The error string.
+    pub error: String,
+    /// How many times this error occurred while it was among the most recent errors.
+    pub occurrences: usize,
+    /// How many seconds after the load test started this error last occurred.
+    pub last_seen: usize,
+}
+
+/// A custom measurement recorded by a load test.
+///
+/// [`SwanlingUser`](../swanling/struct.SwanlingUser.html) threads send these to the parent
+/// process when
+/// [`increment_counter`](../swanling/struct.SwanlingUser.html#method.increment_counter) or
+/// [`observe_gauge`](../swanling/struct.SwanlingUser.html#method.observe_gauge) are invoked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SwanlingCustomMetric {
+    /// Increment the named counter by the specified amount.
+    Counter { name: String, value: usize },
+    /// Record an observation of the named gauge.
+    Gauge { name: String, value: f64 },
+}
+
+/// For tracking custom counters and gauges recorded during a load test.
+///
+/// Counters are summed together, while gauges track the minimum, maximum and mean of all
+/// observed values. When running in Regatta mode, aggregates from each Worker are merged
+/// the same way on the Manager.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SwanlingCustomMetricAggregate {
+    /// The total of all increments to a counter.
+    Counter(usize),
+    /// A summary of all observations of a gauge.
+    Gauge {
+        /// How many times the gauge was observed.
+        observations: usize,
+        /// The sum of all observed values, used to calculate the mean.
+        total: f64,
+        /// The smallest observed value.
+        minimum: f64,
+        /// The largest observed value.
+        maximum: f64,
+    },
+}
+impl SwanlingCustomMetricAggregate {
+    /// Create a new aggregate from a single custom metric.
+    pub(crate) fn new(metric: &SwanlingCustomMetric) -> Self {
+        match metric {
+            SwanlingCustomMetric::Counter { value, .. } => {
+                SwanlingCustomMetricAggregate::Counter(*value)
+            }
+            SwanlingCustomMetric::Gauge { value, .. } => SwanlingCustomMetricAggregate::Gauge {
+                observations: 1,
+                total: *value,
+                minimum: *value,
+                maximum: *value,
+            },
+        }
+    }
+
+    /// Merge another aggregate of the same type into this one, returning false if the
+    /// types don't match.
+    pub(crate) fn merge(&mut self, other: &SwanlingCustomMetricAggregate) -> bool {
+        match (self, other) {
+            (
+                SwanlingCustomMetricAggregate::Counter(total),
+                SwanlingCustomMetricAggregate::Counter(value),
+            ) => {
+                *total += value;
+                true
+            }
+            (
+                SwanlingCustomMetricAggregate::Gauge {
+                    observations,
+                    total,
+                    minimum,
+                    maximum,
+                },
+                SwanlingCustomMetricAggregate::Gauge {
+                    observations: other_observations,
+                    total: other_total,
+                    minimum: other_minimum,
+                    maximum: other_maximum,
+                },
+            ) => {
+                *observations += other_observations;
+                *total += other_total;
+                *minimum = minimum.min(*other_minimum);
+                *maximum = maximum.max(*other_maximum);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The mean of all observed gauge values, or `None` for counters.
+    pub fn mean(&self) -> Option<f64> {
+        match self {
+            SwanlingCustomMetricAggregate::Counter(_) => None,
+            SwanlingCustomMetricAggregate::Gauge {
+                observations,
+                total,
+                ..
+            } => match observations {
+                0 => Some(0.0),
+                _ => Some(total / *observations as f64),
+            },
+        }
+    }
+}
+
+/// Merge a custom metric aggregate into a collection of custom metrics.
+pub(crate) fn merge_custom_metric(
+    custom_metrics: &mut SwanlingCustomMetrics,
+    name: &str,
+    aggregate: SwanlingCustomMetricAggregate,
+) {
+    if let Some(existing) = custom_metrics.get_mut(name) {
+        if !existing.merge(&aggregate) {
+            warn!(
+                "custom metric {} can't be both a counter and a gauge, ignoring value",
+                name
+            );
+        }
+    } else {
+        custom_metrics.insert(name.to_string(), aggregate);
+    }
+}
+
+/// During a soak test, round times of 100 seconds or more to two significant digits.
+///
+/// Shorter times are already rounded when recorded, so this bounds how many distinct
+/// times are stored no matter how slow responses get.
+pub(crate) fn soak_time(time: u64) -> u64 {
+    if time < 100_000 {
+        return time;
+    }
+    let magnitude = 10_u64.pow((time as f64).log10().floor() as u32 - 1);
+    (time + magnitude / 2) / magnitude * magnitude
+}
+
+/// The maximum number of request names tracked by name, if limited with
+/// `--max-request-names` or by a soak test.
+pub(crate) fn max_request_names(configuration: &SwanlingConfiguration) -> Option<usize> {
+    match (configuration.max_request_names, configuration.soak) {
+        (Some(max_request_names), true) => Some(max_request_names.min(SOAK_MAX_REQUESTS)),
+        (Some(max_request_names), false) => Some(max_request_names),
+        (None, true) => Some(SOAK_MAX_REQUESTS),
+        (None, false) => None,
+    }
+}
+
+/// Warn that new request names are being combined into an overflow entry, which usually
+/// means requests to URLs with unique ids aren't named.
+pub(crate) fn warn_request_names_overflow(max_request_names: usize, overflow_key: &str) {
+    warn!(
+        "more than {} request names, combining new names into {}: name requests to URLs \
+        with unique ids with set_name() or the _named() request methods",
+        max_request_names, overflow_key
+    );
+}
+
+/// Once the maximum number of request names are tracked, returns the key to combine a
+/// request into, or `None` if the request can be tracked by name.
+pub(crate) fn request_overflow_key(
+    requests: &SwanlingRequestMetrics,
+    request_key: &str,
+    method: &SwanlingMethod,
+    max_request_names: usize,
+) -> Option<String> {
+    if requests.contains_key(request_key) || requests.len() < max_request_names {
+        None
+    } else {
+        Some(format!("{} {}", method, METRICS_OVERFLOW))
+    }
+}
+
+/// During a soak test, returns the key to combine an error into once the maximum number
+/// of distinct errors are tracked, or `None` if the error can be tracked on its own.
+pub(crate) fn error_overflow_key(
+    errors: &SwanlingErrorMetrics,
+    error_key: &str,
+    method: &SwanlingMethod,
+) -> Option<String> {
+    if errors.contains_key(error_key) || errors.len() < SOAK_MAX_ERRORS {
+        None
+    } else {
+        Some(format!(
+            "{}.{}.{}",
+            METRICS_OVERFLOW, method, METRICS_OVERFLOW
+        ))
+    }
+}
+
+/// How many users were allocated to a task set during a load test.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingTaskSetUsers {
+    /// The name of the task set.
+    pub name: String,
+    /// The weight the task set was configured with.
+    pub weight: usize,
+    /// How many users were allocated to the task set. Users launched by a spike are not
+    /// included.
+    pub users: usize,
+}
+
+/// Iterations run by users limited with the `--iterations-per-user` run-time option. An
+/// iteration is one pass through the tasks chosen by the scheduler, including a pass a
+/// task ended early with `SwanlingTaskControl::SkipRest`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingIterations {
+    /// How many iterations each user runs before stopping.
+    pub per_user: usize,
+    /// How many iterations users completed.
+    pub completed: usize,
+    /// How many iterations users had started but not completed when they stopped, either
+    /// because the load test stopped or a task aborted the user. These are not included
+    /// in `completed`.
+    pub in_progress: usize,
+}
+
+/// A window of time during which a spike configured with the `--spike` run-time option
+/// multiplied the number of running users.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingSpikeWindow {
+    /// How many seconds after the load test started the spike began.
+    pub started: usize,
+    /// How many seconds after the load test started the spike ended, or `None` if the
+    /// load test stopped during the spike.
+    pub stopped: Option<usize>,
+    /// How many users were launched in addition to the configured users.
+    pub users: usize,
+}
+
+/// A window of time after users were stopped during which metrics were still collected,
+/// configured with the `--cooldown` run-time option.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingCooldownWindow {
+    /// How many seconds after the load test started the cooldown began.
+    pub started: usize,
+    /// How many seconds after the load test started the cooldown ended.
+    pub stopped: usize,
+}
+
+/// A Worker whose error rate was out of line with its peers, quarantined by the Manager
+/// with the `--quarantine-error-threshold` run-time option.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingQuarantinedWorker {
+    /// The id of the quarantined Worker.
+    pub worker_id: usize,
+    /// How many seconds after the load test started the Worker was quarantined.
+    pub quarantined: usize,
+    /// The percentage of the Worker's requests that had failed when it was quarantined.
+    pub error_rate: f32,
+    /// The percentage of the requests made by the other Workers that had failed.
+    pub peer_error_rate: f32,
+    /// How many requests the Worker made after it was quarantined, which aren't included
+    /// in the metrics.
+    pub ignored_requests: usize,
+}
+
+/// How stable the number of requests per second was during the steady state of a load test,
+/// returned by
+/// [`SwanlingMetrics::throughput_stability`](./struct.SwanlingMetrics.html#method.throughput_stability).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingThroughputStability {
+    /// How many seconds of steady state were measured.
+    pub seconds: usize,
+    /// The average number of requests per second.
+    pub mean: f32,
+    /// The standard deviation of the number of requests per second.
+    pub std_dev: f32,
+    /// The fewest requests made in one second.
+    pub min: usize,
+    /// The most requests made in one second.
+    pub max: usize,
+    /// The standard deviation divided by the average. Throughput was steady when this is
+    /// close to zero, and bursty when it is high.
+    pub coefficient_of_variation: f32,
+}
+
+/// What limited the rate of requests when a load test fell short of its `--throttle-requests`
+/// rate, reported in
+/// [`SwanlingThrottleEfficiency`](./struct.SwanlingThrottleEfficiency.html).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SwanlingBottleneck {
+    /// The load generator: requests spent most of their time waiting in the throttle, which
+    /// couldn't release them as fast as configured.
+    Throttle,
+    /// The load generator: the requests in flight filled the `--max-connections` limit.
+    MaxConnections,
+    /// The load generator: users spent most of their time between requests, for example in
+    /// their wait time or their own code, rather than waiting for responses.
+    Users,
+    /// The target: users spent most of their time waiting for its responses.
+    Target,
+}
+impl fmt::Display for SwanlingBottleneck {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingBottleneck::Throttle => write!(
+                fmt,
+                "load generator, requests waited in the throttle longer than for responses"
+            ),
+            SwanlingBottleneck::MaxConnections => write!(
+                fmt,
+                "load generator, requests in flight filled --max-connections"
+            ),
+            SwanlingBottleneck::Users => write!(
+                fmt,
+                "load generator, users spent their time between requests"
+            ),
+            SwanlingBottleneck::Target => {
+                write!(fmt, "target, users spent their time waiting for responses")
+            }
+        }
     }
 }
-impl Serialize for SwanlingMetrics {
-    // SwanlingMetrics serialization can't be derived because of the started field.
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_struct("SwanlingMetrics", 10)?;
-        s.serialize_field("hash", &self.hash)?;
-        // Convert started field to a unix timestamp.
-        let timestamp;
-        if let Some(started) = self.started {
-            timestamp = started.timestamp();
+
+/// How close the steady state of a load test came to the rate of requests configured with
+/// `--throttle-requests`, returned by
+/// [`SwanlingMetrics::throttle_efficiency`](./struct.SwanlingMetrics.html#method.throttle_efficiency).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingThrottleEfficiency {
+    /// The requests per second allowed by the throttle.
+    pub intended: usize,
+    /// The average requests per second made during the steady state.
+    pub achieved: f32,
+    /// The achieved rate as a percentage of the intended rate.
+    pub efficiency: f32,
+    /// The percentage of the time between deciding to make requests and receiving their
+    /// responses that was spent waiting in the throttle.
+    pub throttle_wait: f32,
+    /// The average number of requests in flight, the achieved rate multiplied by the
+    /// average response time.
+    pub in_flight: f32,
+    /// What limited the rate of requests, if it fell short of the intended rate.
+    pub bottleneck: Option<SwanlingBottleneck>,
+}
+
+/// How many requests were made and how many failed, by request name, during an interval
+/// between two displays of the running metrics.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SwanlingRunningInterval {
+    /// How many seconds after the load test started the interval began.
+    pub(crate) started: usize,
+    /// How many seconds after the load test started the interval ended.
+    pub(crate) stopped: usize,
+    /// How many requests were made and how many failed, by request name.
+    pub(crate) requests: BTreeMap<String, (usize, usize)>,
+}
+
+/// Request and task metrics aggregated by the Manager during one window of time,
+/// configured with the `--aggregate-window` run-time option.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwanlingMetricsWindow {
+    /// How many seconds after the load test started the window began.
+    pub started: usize,
+    /// How many seconds after the load test started the window ended.
+    pub stopped: usize,
+    /// Requests made during the window.
+    pub requests: SwanlingRequestMetrics,
+    /// Tasks run during the window.
+    pub tasks: SwanlingTaskMetrics,
+}
+
+/// A service level agreement threshold for one request name, configured with the
+/// `--sla` run-time option.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SwanlingSlaThreshold {
+    /// The response time percentile, for example `95.0`, must not be slower than
+    /// `milliseconds`. Configured as `p95=200`.
+    Percentile {
+        /// The percentile to check, between 0 and 100.
+        percent: f32,
+        /// The slowest allowed response time, in milliseconds.
+        milliseconds: usize,
+    },
+    /// The slowest response must not be slower than this many milliseconds. Configured
+    /// as `max=5000`.
+    Maximum(usize),
+    /// The percentage of failed requests must not be higher than this. Configured as
+    /// `errors=1`.
+    ErrorRate(f32),
+}
+impl SwanlingSlaThreshold {
+    /// The limit observed values are compared against.
+    fn limit(&self) -> f32 {
+        match self {
+            SwanlingSlaThreshold::Percentile { milliseconds, .. } => *milliseconds as f32,
+            SwanlingSlaThreshold::Maximum(milliseconds) => *milliseconds as f32,
+            SwanlingSlaThreshold::ErrorRate(percent) => *percent,
+        }
+    }
+}
+/// Implement format trait to allow displaying thresholds.
+impl fmt::Display for SwanlingSlaThreshold {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingSlaThreshold::Percentile {
+                percent,
+                milliseconds,
+            } => write!(fmt, "p{} <= {} ms", percent, milliseconds),
+            SwanlingSlaThreshold::Maximum(milliseconds) => {
+                write!(fmt, "max <= {} ms", milliseconds)
+            }
+            SwanlingSlaThreshold::ErrorRate(percent) => write!(fmt, "errors <= {}%", percent),
+        }
+    }
+}
+
+/// The result of running one task during a smoke test started with the `--smoke`
+/// run-time flag.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingSmokeResult {
+    /// The name of the task set the task belongs to.
+    pub task_set: String,
+    /// The index of the task within its task set.
+    pub task_index: usize,
+    /// The name of the task.
+    pub task_name: String,
+    /// How long the task took to run, in milliseconds.
+    pub time: u64,
+    /// Whether or not the task and every request it made succeeded.
+    pub passed: bool,
+    /// Why the task failed, if it did.
+    pub detail: Option<String>,
+}
+
+/// One adjustment of the throttle made by the adaptive throttle configured with the
+/// `--adaptive-target-p95` run-time option.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingAdaptiveStep {
+    /// How many seconds after the load test started the adjustment was made.
+    pub elapsed: usize,
+    /// The maximum requests per second allowed during the interval before the adjustment.
+    pub throttle_requests: usize,
+    /// The requests per second actually made during the interval.
+    pub rps: f32,
+    /// The 95th percentile response time during the interval, in milliseconds.
+    pub p95: usize,
+    /// The percentage of requests that failed during the interval.
+    pub error_rate: f32,
+    /// Whether or not the response time and error rate stayed within bounds, increasing
+    /// the rate, rather than backing off.
+    pub within_bounds: bool,
+}
+
+/// Adaptive throttling configured with the `--adaptive-target-p95` run-time option,
+/// increasing the throttle while the 95th percentile response time stays within the target
+/// and backing off when it doesn't, to find the maximum sustainable throughput.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingAdaptive {
+    /// The 95th percentile response time to stay within, in milliseconds.
+    pub target_p95: usize,
+    /// Each adjustment made to the throttle.
+    pub steps: Vec<SwanlingAdaptiveStep>,
+    /// The highest requests per second made during an interval that stayed within bounds,
+    /// or `None` if no interval did.
+    pub max_safe_rps: Option<f32>,
+    /// Response times of requests made since the last adjustment.
+    #[serde(skip)]
+    times: BTreeMap<usize, usize>,
+    /// How many requests were made since the last adjustment.
+    #[serde(skip)]
+    requests: usize,
+    /// How many requests failed since the last adjustment.
+    #[serde(skip)]
+    failures: usize,
+}
+impl SwanlingAdaptive {
+    /// Start adaptive throttling, keeping the 95th percentile response time within the
+    /// target in milliseconds.
+    pub(crate) fn new(target_p95: usize) -> Self {
+        SwanlingAdaptive {
+            target_p95,
+            ..Default::default()
+        }
+    }
+
+    /// Record a request made during the current interval, or an update marking a
+    /// previous request as failed or successful.
+    pub(crate) fn record(&mut self, request_metric: &SwanlingRequestMetric) {
+        if request_metric.update {
+            if request_metric.success {
+                self.failures = self.failures.saturating_sub(1);
+            } else {
+                self.failures += 1;
+            }
+            return;
+        }
+        *self
+            .times
+            .entry(request_metric.response_time as usize)
+            .or_insert(0) += 1;
+        self.requests += 1;
+        if !request_metric.success {
+            self.failures += 1;
+        }
+    }
+
+    /// Discard requests recorded so far, starting a new interval.
+    pub(crate) fn start_interval(&mut self) {
+        self.times = BTreeMap::new();
+        self.requests = 0;
+        self.failures = 0;
+    }
+
+    /// Measure the interval of this many seconds that just ended, and return the
+    /// requests per second to allow during the next. The rate grows by a quarter while
+    /// within bounds, and shrinks by a quarter otherwise.
+    pub(crate) fn adjust(
+        &mut self,
+        elapsed: usize,
+        interval: f32,
+        throttle_requests: usize,
+    ) -> usize {
+        // Without requests there's nothing to measure, keep the current rate.
+        if self.requests == 0 {
+            return throttle_requests;
+        }
+
+        let min = *self.times.keys().next().unwrap();
+        let max = *self.times.keys().next_back().unwrap();
+        let p95 = response_time_percentile(&self.times, self.requests, min, max, 0.95);
+        let error_rate = self.failures as f32 / self.requests as f32 * 100.0;
+        let rps = self.requests as f32 / interval;
+        let within_bounds = p95 <= self.target_p95 && error_rate <= ADAPTIVE_MAX_ERROR_RATE;
+        self.steps.push(SwanlingAdaptiveStep {
+            elapsed,
+            throttle_requests,
+            rps,
+            p95,
+            error_rate,
+            within_bounds,
+        });
+        self.start_interval();
+
+        if within_bounds {
+            if !matches!(self.max_safe_rps, Some(max_safe_rps) if max_safe_rps >= rps) {
+                self.max_safe_rps = Some(rps);
+            }
+            (throttle_requests + (throttle_requests / 4).max(1)).min(1_000_000)
         } else {
-            timestamp = 0;
+            (throttle_requests - throttle_requests / 4).max(1)
         }
-        s.serialize_field("started", &timestamp)?;
-        s.serialize_field("duration", &self.duration)?;
-        s.serialize_field("users", &self.users)?;
-        s.serialize_field("requests", &self.requests)?;
-        s.serialize_field("tasks", &self.tasks)?;
-        s.serialize_field("errors", &self.errors)?;
-        s.serialize_field("final_metrics", &self.final_metrics)?;
-        s.serialize_field("display_status_codes", &self.display_status_codes)?;
-        s.serialize_field("display_metrics", &self.display_metrics)?;
-        s.end()
     }
 }
 
-/// Implement format trait to allow displaying metrics.
-impl fmt::Display for SwanlingMetrics {
-    // Implement display of metrics with `{}` marker.
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        // Formats from zero to six tables of data, depending on what data is contained
-        // and which contained flags are set.
-        self.fmt_tasks(fmt)?;
-        self.fmt_task_times(fmt)?;
-        self.fmt_requests(fmt)?;
-        self.fmt_response_times(fmt)?;
-        self.fmt_percentiles(fmt)?;
-        self.fmt_status_codes(fmt)?;
-        self.fmt_errors(fmt)
+/// One interval measured while ramping up users with the `--ramp-to-failure` run-time
+/// option.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingRampStep {
+    /// How many seconds after the load test started the interval ended.
+    pub elapsed: usize,
+    /// How many users were running during the interval.
+    pub users: usize,
+    /// The requests per second made during the interval.
+    pub rps: f32,
+    /// The percentage of requests that failed during the interval.
+    pub error_rate: f32,
+    /// Whether or not the error rate exceeded the `--error-threshold`.
+    pub over_threshold: bool,
+}
+
+/// Ramping to failure configured with the `--ramp-to-failure` run-time option, launching
+/// more users each interval until the error rate exceeds the `--error-threshold` for
+/// [`RAMP_SUSTAINED_INTERVALS`] consecutive intervals, to find the load at which the
+/// target breaks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingRampToFailure {
+    /// The percentage of requests that can fail before the target is considered broken.
+    pub error_threshold: usize,
+    /// Each interval measured while ramping up.
+    pub steps: Vec<SwanlingRampStep>,
+    /// The first of the consecutive intervals during which the error rate exceeded the
+    /// threshold, or `None` if the load test ended first.
+    pub breaking_point: Option<SwanlingRampStep>,
+    /// How many requests were made since the last interval ended.
+    #[serde(skip)]
+    requests: usize,
+    /// How many requests failed since the last interval ended.
+    #[serde(skip)]
+    failures: usize,
+    /// How many consecutive intervals have exceeded the threshold.
+    #[serde(skip)]
+    failing: usize,
+}
+impl SwanlingRampToFailure {
+    /// Start ramping to failure, stopping once more than this percentage of requests fail.
+    pub(crate) fn new(error_threshold: usize) -> Self {
+        SwanlingRampToFailure {
+            error_threshold,
+            ..Default::default()
+        }
+    }
+
+    /// Record a request made during the current interval, or an update marking a
+    /// previous request as failed or successful.
+    pub(crate) fn record(&mut self, request_metric: &SwanlingRequestMetric) {
+        if request_metric.update {
+            if request_metric.success {
+                self.failures = self.failures.saturating_sub(1);
+            } else {
+                self.failures += 1;
+            }
+            return;
+        }
+        self.requests += 1;
+        if !request_metric.success {
+            self.failures += 1;
+        }
+    }
+
+    /// Discard requests recorded so far, starting a new interval.
+    pub(crate) fn start_interval(&mut self) {
+        self.requests = 0;
+        self.failures = 0;
+    }
+
+    /// Returns true if the most recent interval exceeded the threshold.
+    pub(crate) fn is_failing(&self) -> bool {
+        self.failing > 0
+    }
+
+    /// Measure the interval of this many seconds that just ended with this many users
+    /// running. Returns true once the error rate has exceeded the threshold for
+    /// [`RAMP_SUSTAINED_INTERVALS`] consecutive intervals, recording the breaking point.
+    pub(crate) fn measure(&mut self, elapsed: usize, interval: f32, users: usize) -> bool {
+        // Without requests there's nothing to measure.
+        if self.requests == 0 {
+            return false;
+        }
+
+        let error_rate = self.failures as f32 / self.requests as f32 * 100.0;
+        let step = SwanlingRampStep {
+            elapsed,
+            users,
+            rps: self.requests as f32 / interval,
+            error_rate,
+            over_threshold: error_rate > self.error_threshold as f32,
+        };
+        self.start_interval();
+
+        if step.over_threshold {
+            self.failing += 1;
+        } else {
+            self.failing = 0;
+        }
+        self.steps.push(step);
+
+        if self.failing >= RAMP_SUSTAINED_INTERVALS {
+            self.breaking_point = Some(self.steps[self.steps.len() - self.failing].clone());
+            true
+        } else {
+            false
+        }
     }
 }
 
-/// For tracking and counting requests made during a load test.
-///
-/// The request that Swanling is making. User threads send this data to the parent thread
-/// when metrics are enabled. This request object must be provided to calls to
-/// [`set_success`](https://docs.rs/swanling/*/swanling/swanling/struct.SwanlingUser.html#method.set_success)
-/// or
-/// [`set_failure`](https://docs.rs/swanling/*/swanling/swanling/struct.SwanlingUser.html#method.set_failure)
-/// so Swanling knows which request is being updated.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SwanlingErrorMetric {
-    /// How many milliseconds the load test has been running.
-    pub elapsed: u64,
-    /// The method that was used (ie, Get, Post, etc).
-    pub method: SwanlingMethod,
-    /// The optional name of the request.
+/// The result of checking one service level agreement against the final request metrics.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingSlaResult {
+    /// The request name, or method and name, the agreement applies to.
     pub name: String,
-    /// The full URL that was requested.
-    pub url: String,
-    /// The final full URL that was requested, after redirects.
-    pub final_url: String,
-    /// Whether or not the request was redirected.
-    pub redirected: bool,
-    /// How many milliseconds the request took.
-    pub response_time: u64,
-    /// The HTTP response code (optional).
-    pub status_code: u16,
-    /// Which SwanlingUser thread processed the request.
-    pub user: usize,
-    /// The error caused by this request.
-    pub error: String,
+    /// The threshold that was checked.
+    pub threshold: SwanlingSlaThreshold,
+    /// The observed value, in milliseconds or percent of failed requests, or `None` if
+    /// no matching requests were made.
+    pub observed: Option<f32>,
+    /// Whether or not the agreement was met.
+    pub passed: bool,
+}
+impl SwanlingSlaResult {
+    /// Format the observed value with the same units as the threshold.
+    fn observed_string(&self) -> String {
+        match (self.observed, self.threshold) {
+            (None, _) => "no requests".to_string(),
+            (Some(value), SwanlingSlaThreshold::ErrorRate(_)) => format!("{:.2}%", value),
+            (Some(value), _) => format!("{} ms", format_number(value as usize)),
+        }
+    }
 }
 
-/// For tracking and counting errors detected during a load test.
-///
-/// When a load test completes, by default it will include a summary of all errors that
-/// were detected during the load test. Multiple errors that share the same request method,
-/// the same request name, and the same error text are contained within a single
-/// SwanlingErrorMetric object, with `occurrences` indicating how many times this error was
-/// seen.
+/// Parse a service level agreement in the format `NAME:THRESHOLD`, where `THRESHOLD`
+/// is one of `pNN=MILLISECONDS`, `max=MILLISECONDS` or `errors=PERCENT`.
 ///
-/// Individual `SwanlingErrorMetric`s are stored within a
-/// [`SwanlingErrorMetrics`](./type.SwanlingErrorMetrics.html) `BTreeMap` with a string key of
-/// `error.method.name`. The `BTreeMap` is found in the `errors` field of what is returned
-/// by [`SwanlingAttack::execute()`](../struct.SwanlingAttack.html#method.execute) when a load
-/// test finishes.
+/// The name is everything before the last colon, so it may itself contain colons.
+/// Returns `None` if the agreement is not valid.
+pub(crate) fn parse_sla(sla: &str) -> Option<(String, SwanlingSlaThreshold)> {
+    let (name, threshold) = sla.rsplit_once(':')?;
+    let (kind, limit) = threshold.split_once('=')?;
+    if name.is_empty() {
+        return None;
+    }
+
+    let threshold = match kind {
+        "max" => SwanlingSlaThreshold::Maximum(limit.parse().ok()?),
+        "errors" => {
+            let percent: f32 = limit.trim_end_matches('%').parse().ok()?;
+            if !(0.0..=100.0).contains(&percent) {
+                return None;
+            }
+            SwanlingSlaThreshold::ErrorRate(percent)
+        }
+        _ => {
+            let percent: f32 = kind.strip_prefix('p')?.parse().ok()?;
+            if percent <= 0.0 || percent > 100.0 {
+                return None;
+            }
+            SwanlingSlaThreshold::Percentile {
+                percent,
+                milliseconds: limit.parse().ok()?,
+            }
+        }
+    };
+
+    Some((name.to_string(), threshold))
+}
+
+/// How much the metrics of a load test may change from a baseline before they are
+/// considered to have regressed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingRegressionBounds {
+    /// How many percent slower the average response time of a request may become.
+    pub response_time_percent: f32,
+    /// How many percentage points the error rate of a request may rise.
+    pub error_rate_points: f32,
+    /// How many percent lower the total requests per second may become.
+    pub throughput_percent: f32,
+}
+impl SwanlingRegressionBounds {
+    /// Apply the same percent to response times, error rates and throughput, as with the
+    /// `--regression-threshold` run-time option.
+    pub fn new(percent: f32) -> Self {
+        SwanlingRegressionBounds {
+            response_time_percent: percent,
+            error_rate_points: percent,
+            throughput_percent: percent,
+        }
+    }
+}
+
+/// The change of one request from the baseline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingRequestComparison {
+    /// The method and name of the request.
+    pub name: String,
+    /// The average response time in the baseline, or `None` if the request wasn't made.
+    pub baseline_average: Option<f32>,
+    /// The average response time in this load test, or `None` if the request wasn't made.
+    pub current_average: Option<f32>,
+    /// The percent change of the average response time, positive when slower.
+    pub response_time_change: Option<f32>,
+    /// The percent of requests that failed in the baseline.
+    pub baseline_error_rate: f32,
+    /// The percent of requests that failed in this load test.
+    pub current_error_rate: f32,
+    /// Whether the request got faster by more than the bounds allow it to get slower.
+    pub improved: bool,
+    /// Whether the request got slower, or failed more often, than the bounds allow.
+    pub regressed: bool,
+}
+impl SwanlingRequestComparison {
+    /// Summarize the result of comparing this request.
+    fn result(&self) -> &str {
+        if self.regressed {
+            "FAIL"
+        } else if self.baseline_average.is_none() {
+            "NEW"
+        } else if self.current_average.is_none() {
+            "GONE"
+        } else if self.improved {
+            "FASTER"
+        } else {
+            "PASS"
+        }
+    }
+}
+
+/// The result of comparing the metrics of a load test with a baseline JSON report, as
+/// produced by serializing [`SwanlingMetrics`](./struct.SwanlingMetrics.html).
 ///
-/// Can be disabled with the `--no-error-summary` run-time option, or with
-/// [SwanlingDefault::NoErrorSummary](../enum.SwanlingDefault.html#variant.NoErrorSummary).
+/// It is displayed as a human-readable table, and can be serialized for machines.
 ///
 /// # Example
-/// In this example, requests to load the front page are failing:
-/// ```text
-/// SwanlingErrorMetric {
-///     method: Get,
-///     name: "(Anon) front page",
-///     error: "503 Service Unavailable: /",
-///     occurrences: 4588,
-/// }
+/// ```rust
+/// use swanling::metrics::{SwanlingComparison, SwanlingRegressionBounds};
+///
+/// let baseline = r#"{"duration": 10, "requests": {}}"#;
+/// let current = r#"{"duration": 10, "requests": {}}"#;
+/// let comparison =
+///     SwanlingComparison::from_json(baseline, current, &SwanlingRegressionBounds::new(10.0))
+///         .unwrap();
+/// assert!(!comparison.regressed);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
-pub struct SwanlingErrorMetricAggregate {
-    /// The method that resulted in an error.
-    pub method: SwanlingMethod,
-    /// The optional name of the request.
-    pub name: String,
-    /// The error string.
-    pub error: String,
-    /// A counter reflecting how many times this error occurred.
-    pub occurrences: usize,
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwanlingComparison {
+    /// The bounds the metrics were compared with.
+    pub bounds: SwanlingRegressionBounds,
+    /// The change of each request made in either load test, sorted by name.
+    pub requests: Vec<SwanlingRequestComparison>,
+    /// Total requests per second in the baseline.
+    pub baseline_throughput: f32,
+    /// Total requests per second in this load test.
+    pub current_throughput: f32,
+    /// The percent change of the total requests per second, positive when higher.
+    pub throughput_change: Option<f32>,
+    /// Whether any request, or the throughput, regressed beyond the bounds.
+    pub regressed: bool,
+}
+impl SwanlingComparison {
+    /// Compare two JSON reports, each produced by serializing
+    /// [`SwanlingMetrics`](./struct.SwanlingMetrics.html).
+    pub fn from_json(
+        baseline: &str,
+        current: &str,
+        bounds: &SwanlingRegressionBounds,
+    ) -> Result<Self, serde_json::Error> {
+        let baseline: SwanlingReportSummary = serde_json::from_str(baseline)?;
+        let current: SwanlingReportSummary = serde_json::from_str(current)?;
+        Ok(SwanlingComparison::new(&baseline, &current, bounds))
+    }
+
+    fn new(
+        baseline: &SwanlingReportSummary,
+        current: &SwanlingReportSummary,
+        bounds: &SwanlingRegressionBounds,
+    ) -> Self {
+        let mut requests = Vec::new();
+        let names: std::collections::BTreeSet<&String> = baseline
+            .requests
+            .keys()
+            .chain(current.requests.keys())
+            .collect();
+        for name in names {
+            let before = baseline.requests.get(name);
+            let after = current.requests.get(name);
+            let baseline_average = before.and_then(SwanlingRequestSummary::average);
+            let current_average = after.and_then(SwanlingRequestSummary::average);
+            let response_time_change = match (baseline_average, current_average) {
+                (Some(b), Some(c)) if b > 0.0 => Some((c - b) * 100.0 / b),
+                _ => None,
+            };
+            let baseline_error_rate = before.map_or(0.0, SwanlingRequestSummary::error_rate);
+            let current_error_rate = after.map_or(0.0, SwanlingRequestSummary::error_rate);
+            let slower = matches!(response_time_change, Some(change) if change > bounds.response_time_percent);
+            let failing = before.is_some()
+                && current_error_rate - baseline_error_rate > bounds.error_rate_points;
+            requests.push(SwanlingRequestComparison {
+                name: name.to_string(),
+                baseline_average,
+                current_average,
+                response_time_change,
+                baseline_error_rate,
+                current_error_rate,
+                improved: matches!(response_time_change, Some(change) if -change > bounds.response_time_percent),
+                regressed: slower || failing,
+            });
+        }
+
+        let baseline_throughput = baseline.throughput();
+        let current_throughput = current.throughput();
+        let throughput_change = if baseline_throughput > 0.0 {
+            Some((current_throughput - baseline_throughput) * 100.0 / baseline_throughput)
+        } else {
+            None
+        };
+        let regressed = requests.iter().any(|request| request.regressed)
+            || matches!(throughput_change, Some(change) if -change > bounds.throughput_percent);
+
+        SwanlingComparison {
+            bounds: *bounds,
+            requests,
+            baseline_throughput,
+            current_throughput,
+            throughput_change,
+            regressed,
+        }
+    }
+
+    /// A description of each metric that regressed.
+    pub fn regressions(&self) -> Vec<String> {
+        let mut regressions: Vec<String> = self
+            .requests
+            .iter()
+            .filter(|request| request.regressed)
+            .map(|request| request.name.to_string())
+            .collect();
+        if matches!(self.throughput_change, Some(change) if -change > self.bounds.throughput_percent)
+        {
+            regressions.push("throughput".to_string());
+        }
+        regressions
+    }
+}
+
+/// Returns `true` if the contents of a baseline can be compared with the metrics of a
+/// load test.
+pub(crate) fn is_valid_baseline(baseline: &str) -> bool {
+    serde_json::from_str::<SwanlingReportSummary>(baseline).is_ok()
+}
+
+// Format an optional average response time.
+fn format_average(value: Option<f32>) -> String {
+    match value {
+        Some(value) => format!("{:.prec$}", value, prec = determine_precision(value)),
+        None => "-".to_string(),
+    }
+}
+
+// Format an optional percent change, with its sign.
+fn format_change(value: Option<f32>) -> String {
+    match value {
+        Some(value) => format!("{:+.1}%", value),
+        None => "-".to_string(),
+    }
+}
+
+/// Implement format trait to allow displaying a comparison.
+impl fmt::Display for SwanlingComparison {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            fmt,
+            " === COMPARISON WITH BASELINE ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<18} | {:>9} | {:>9} | {:>8} | {:>12} | {:>6}",
+            "Name", "Base (ms)", "Now (ms)", "Change", "Errors", "Result"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for request in &self.requests {
+            writeln!(
+                fmt,
+                " {:<18} | {:>9} | {:>9} | {:>8} | {:>12} | {:>6}",
+                util::truncate_string(&request.name, 18),
+                format_average(request.baseline_average),
+                format_average(request.current_average),
+                format_change(request.response_time_change),
+                format!(
+                    "{:.1}->{:.1}%",
+                    request.baseline_error_rate, request.current_error_rate
+                ),
+                request.result(),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " Throughput: {:.2} -> {:.2} req/s ({})",
+            self.baseline_throughput,
+            self.current_throughput,
+            format_change(self.throughput_change),
+        )?;
+        writeln!(
+            fmt,
+            " Regressed beyond {}% slower, {} points more errors or {}% less throughput: {}",
+            self.bounds.response_time_percent,
+            self.bounds.error_rate_points,
+            self.bounds.throughput_percent,
+            if self.regressed { "FAIL" } else { "PASS" },
+        )
+    }
 }
-impl SwanlingErrorMetricAggregate {
-    pub(crate) fn new(method: SwanlingMethod, name: String, error: String) -> Self {
-        SwanlingErrorMetricAggregate {
-            method,
-            name,
-            error,
-            occurrences: 0,
+
+// The parts of a JSON report needed to compare it with another.
+#[derive(Debug, Deserialize)]
+struct SwanlingReportSummary {
+    #[serde(default)]
+    duration: usize,
+    #[serde(default)]
+    requests: HashMap<String, SwanlingRequestSummary>,
+}
+impl SwanlingReportSummary {
+    // Total requests per second.
+    fn throughput(&self) -> f32 {
+        let total: usize = self
+            .requests
+            .values()
+            .map(|request| request.success_count + request.fail_count)
+            .sum();
+        total as f32 / self.duration.max(1) as f32
+    }
+}
+impl From<&SwanlingMetrics> for SwanlingReportSummary {
+    fn from(metrics: &SwanlingMetrics) -> Self {
+        SwanlingReportSummary {
+            duration: metrics.duration,
+            requests: metrics
+                .requests
+                .iter()
+                .map(|(name, request)| {
+                    (
+                        name.to_string(),
+                        SwanlingRequestSummary {
+                            raw_data: SwanlingRequestTimingSummary {
+                                total_time: request.raw_data.total_time,
+                                counter: request.raw_data.counter,
+                            },
+                            success_count: request.success_count,
+                            fail_count: request.fail_count,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+// The parts of a request in a JSON report needed to compare it with another.
+#[derive(Debug, Deserialize)]
+struct SwanlingRequestSummary {
+    raw_data: SwanlingRequestTimingSummary,
+    #[serde(default)]
+    success_count: usize,
+    #[serde(default)]
+    fail_count: usize,
+}
+impl SwanlingRequestSummary {
+    // The average response time, if any requests were timed.
+    fn average(&self) -> Option<f32> {
+        if self.raw_data.counter == 0 {
+            None
+        } else {
+            Some(self.raw_data.total_time as f32 / self.raw_data.counter as f32)
+        }
+    }
+
+    // The percent of requests that failed.
+    fn error_rate(&self) -> f32 {
+        let total = self.success_count + self.fail_count;
+        if total == 0 {
+            0.0
+        } else {
+            self.fail_count as f32 * 100.0 / total as f32
         }
     }
 }
 
+// The timing of a request in a JSON report needed to compare it with another.
+#[derive(Debug, Deserialize)]
+struct SwanlingRequestTimingSummary {
+    #[serde(default)]
+    total_time: usize,
+    #[serde(default)]
+    counter: usize,
+}
+
 impl SwanlingAttack {
     // If metrics are enabled, synchronize metrics from child threads to the parent. If
     // flush is true all metrics will be received regardless of how long it takes. If
@@ -2112,6 +6233,7 @@ impl SwanlingAttack {
         swanling_attack_run_state: &mut SwanlingAttackRunState,
         flush: bool,
     ) -> Result<(), SwanlingError> {
+        let mut received_message = false;
         if !self.configuration.no_metrics {
             // Check if we're displaying running metrics.
             if let Some(running_metrics) = self.configuration.running_metrics {
@@ -2127,30 +6249,131 @@ impl SwanlingAttack {
             }
 
             // Load messages from user threads until the receiver queue is empty.
-            let received_message = self
+            received_message = self
                 .receive_metrics(swanling_attack_run_state, flush)
                 .await?;
+        }
+
+        // As worker, push metrics up to manager when flushing, or once the configured
+        // interval passes. Without metrics only control messages are exchanged, so the
+        // worker still learns when to exit or reconfigure.
+        if self.attack_mode == AttackMode::Worker {
+            // Also push if values were published or users are waiting for one, so
+            // they are exchanged with the manager. Without metrics, push every interval
+            // so commands from the manager are still received.
+            if received_message || self.shared_values.needs_sync() || self.configuration.no_metrics
+            {
+                swanling_attack_run_state.worker_metrics_pending = true;
+            }
+            let worker_metrics_interval = self
+                .configuration
+                .worker_metrics_interval
+                .unwrap_or(crate::DEFAULT_WORKER_METRICS_INTERVAL);
+            if swanling_attack_run_state.worker_metrics_pending
+                && (flush
+                    || util::ms_timer_expired(
+                        swanling_attack_run_state.worker_metrics_timer,
+                        worker_metrics_interval,
+                    ))
+            {
+                swanling_attack_run_state.worker_metrics_timer = std::time::Instant::now();
+                swanling_attack_run_state.worker_metrics_pending = false;
 
-            // As worker, push metrics up to manager.
-            if self.attack_mode == AttackMode::Worker && received_message {
                 #[cfg(feature = "gaggle")]
                 {
-                    // Push metrics to manager process.
-                    if !worker::push_metrics_to_manager(
-                        &swanling_attack_run_state.socket.clone().unwrap(),
-                        vec![
+                    // The clock is sent first, so the Manager knows when the metrics
+                    // were pushed before merging them. Without metrics, only control
+                    // messages are sent.
+                    let mut gaggle_metrics = Vec::new();
+                    if !self.configuration.no_metrics {
+                        gaggle_metrics.push(worker::clock_metric());
+                    }
+                    // Acknowledge configuration changes since the last push, before the
+                    // metrics so the Manager knows they were collected after a reset.
+                    if !swanling_attack_run_state.reconfigured.is_empty() {
+                        gaggle_metrics.push(GaggleMetrics::Reconfigured(std::mem::take(
+                            &mut swanling_attack_run_state.reconfigured,
+                        )));
+                    }
+                    if !self.configuration.no_metrics {
+                        gaggle_metrics.extend(vec![
                             GaggleMetrics::Requests(self.metrics.requests.clone()),
+                            GaggleMetrics::Groups(self.metrics.groups.clone()),
+                            GaggleMetrics::Errors(self.metrics.errors.clone()),
                             GaggleMetrics::Tasks(self.metrics.tasks.clone()),
-                        ],
+                            GaggleMetrics::Custom(self.metrics.custom.clone()),
+                        ]);
+                    }
+                    // Send the end of the request log, if the manager asked for it.
+                    if let Some(requested_log) = swanling_attack_run_state.requested_log.take() {
+                        gaggle_metrics.push(requested_log);
+                    }
+                    // Send values published since the last push.
+                    let unsent = self.shared_values.take_unsent();
+                    if !unsent.is_empty() {
+                        gaggle_metrics.push(GaggleMetrics::SharedValues(unsent));
+                    }
+                    // Report metric samples dropped since the last push.
+                    let dropped = swanling_attack_run_state
+                        .dropped_metrics
+                        .swap(0, std::sync::atomic::Ordering::SeqCst);
+                    if dropped > 0 {
+                        gaggle_metrics.push(GaggleMetrics::Dropped(dropped));
+                    }
+                    // Push metrics to manager process.
+                    match worker::push_metrics_to_manager(
+                        &swanling_attack_run_state.socket.clone().unwrap(),
+                        gaggle_metrics,
                         true,
+                        self.configuration.gaggle_max_msg_size.unwrap_or(0),
                     ) {
                         // SwanlingUserCommand::Exit received, cancel.
-                        swanling_attack_run_state
-                            .canceled
-                            .store(true, std::sync::atomic::Ordering::SeqCst);
+                        Some(SwanlingUserCommand::Exit) => {
+                            swanling_attack_run_state
+                                .canceled
+                                .store(true, std::sync::atomic::Ordering::SeqCst);
+                        }
+                        // Apply the changes, acknowledging them with the next push.
+                        Some(SwanlingUserCommand::Reconfigure(reconfiguration)) => {
+                            info!(
+                                    "[{}] received SwanlingUserCommand::Reconfigure command from manager",
+                                    get_worker_id()
+                                );
+                            let mut changes =
+                                self.reconfigure(swanling_attack_run_state, &reconfiguration);
+                            // Receive the metrics users sent since this push, so they're
+                            // discarded with the pushed metrics below.
+                            if reconfiguration.reset_metrics {
+                                self.receive_metrics(swanling_attack_run_state, true)
+                                    .await?;
+                                swanling_attack_run_state
+                                    .dropped_metrics
+                                    .store(0, std::sync::atomic::Ordering::SeqCst);
+                                changes.push("metrics reset".to_string());
+                            }
+                            swanling_attack_run_state.reconfigured.extend(changes);
+                        }
+                        // Store the values published by all workers.
+                        Some(SwanlingUserCommand::SharedValues(values)) => {
+                            self.shared_values.merge(values);
+                        }
+                        // Read the end of the request log, sending it with the next push.
+                        Some(SwanlingUserCommand::SendRequestLog) => {
+                            info!(
+                                "[{}] received SwanlingUserCommand::SendRequestLog command from manager",
+                                get_worker_id()
+                            );
+                            swanling_attack_run_state.requested_log =
+                                Some(worker::request_log_metric(&self.configuration.request_log));
+                            swanling_attack_run_state.worker_metrics_pending = true;
+                        }
+                        _ => (),
                     }
                     // The manager has all our metrics, reset locally.
                     self.metrics.requests = HashMap::new();
+                    self.metrics.groups = HashMap::new();
+                    self.metrics.errors = BTreeMap::new();
+                    self.metrics.custom = BTreeMap::new();
                     self.metrics
                         .initialize_task_metrics(&self.task_sets, &self.configuration);
                 }
@@ -2164,12 +6387,49 @@ impl SwanlingAttack {
             self.metrics.print_running();
         }
 
+        // If enabled, display the most recent distinct errors after sync.
+        if self.configuration.error_tail.is_some()
+            && self.attack_mode != AttackMode::Worker
+            && util::timer_expired(
+                swanling_attack_run_state.error_tail_timer,
+                ERROR_TAIL_INTERVAL,
+            )
+        {
+            swanling_attack_run_state.error_tail_timer = std::time::Instant::now();
+            self.metrics.print_error_tail();
+        }
+
         Ok(())
     }
 
+    /// Discard all metrics collected so far, and measure the duration of the load test
+    /// from now, starting a clean measurement. See
+    /// [`SwanlingMetrics::reset_metrics`](./metrics/struct.SwanlingMetrics.html#method.reset_metrics).
+    ///
+    /// Swanling does this automatically after all users have started unless the
+    /// `--no-reset-metrics` run-time option is enabled, and when the `reset-metrics`
+    /// Controller command is received. In a Regatta the Manager also tells all Workers to
+    /// discard the metrics they haven't pushed yet.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut swanling_attack = SwanlingAttack::initialize()?;
+    ///     swanling_attack.reset_metrics();
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset_metrics();
+        self.started = Some(std::time::Instant::now());
+    }
+
     // When the [`SwanlingAttack`](./struct.SwanlingAttack.html) goes from the `Starting`
     // phase to the `Running` phase, optionally flush metrics.
-    pub(crate) async fn reset_metrics(
+    pub(crate) async fn reset_metrics_after_launch(
         &mut self,
         swanling_attack_run_state: &mut SwanlingAttackRunState,
     ) -> Result<(), SwanlingError> {
@@ -2200,11 +6460,8 @@ impl SwanlingAttack {
                     }
                 }
 
-                self.metrics.requests = HashMap::new();
-                self.metrics
-                    .initialize_task_metrics(&self.task_sets, &self.configuration);
                 // Restart the timer now that all threads are launched.
-                self.started = Some(std::time::Instant::now());
+                self.reset_metrics();
             } else if self.metrics.users < users {
                 println!(
                     "{} of {} users hatched, timer expired.\n",
@@ -2213,6 +6470,15 @@ impl SwanlingAttack {
             } else {
                 println!("All {} users hatched.\n", self.metrics.users);
             }
+
+            // Without resetting the metrics, the steady state starts with the first full
+            // second all users are running.
+            if self.configuration.no_reset_metrics {
+                if let Some(started) = self.started {
+                    self.metrics.steady_state_started =
+                        started.elapsed().as_secs_f32().ceil() as usize;
+                }
+            }
         }
 
         Ok(())
@@ -2222,16 +6488,53 @@ impl SwanlingAttack {
     // `SwanlingMetrics.requests` `HashMap`, merging if already existing, or creating new.
     // Also writes it to the request_file if enabled.
     async fn record_request_metric(&mut self, request_metric: &SwanlingRequestMetric) {
-        let key = format!("{} {}", request_metric.method, request_metric.name);
+        let mut key = format!("{} {}", request_metric.method, request_metric.name);
+        let mut name = request_metric.name.as_str();
+        // Combine new request names into a single entry once the maximum number are
+        // tracked, so memory and the metrics pushed to the Manager don't grow without bound.
+        if let Some(max_request_names) = max_request_names(&self.configuration) {
+            if let Some(overflow_key) = request_overflow_key(
+                &self.metrics.requests,
+                &key,
+                &request_metric.method,
+                max_request_names,
+            ) {
+                if !self.metrics.requests.contains_key(&overflow_key) {
+                    warn_request_names_overflow(max_request_names, &overflow_key);
+                }
+                key = overflow_key;
+                name = METRICS_OVERFLOW;
+            }
+        }
         let mut merge_request = match self.metrics.requests.get(&key) {
             Some(m) => m.clone(),
-            None => SwanlingRequestMetricAggregate::new(
-                &request_metric.name,
-                request_metric.method.clone(),
-                0,
-            ),
+            None => SwanlingRequestMetricAggregate::new(name, request_metric.method.clone(), 0),
         };
+        self.merge_request_metric(&mut merge_request, request_metric);
+        self.metrics.requests.insert(key, merge_request);
+
+        // Also group the request by the value of its label, if enabled.
+        if self.configuration.group_by.is_empty() {
+            return;
+        }
+        if let Some(value) = request_metric.labels.get(&self.configuration.group_by) {
+            let mut merge_group = match self.metrics.groups.get(value) {
+                Some(m) => m.clone(),
+                None => {
+                    SwanlingRequestMetricAggregate::new(value, request_metric.method.clone(), 0)
+                }
+            };
+            self.merge_request_metric(&mut merge_group, request_metric);
+            self.metrics.groups.insert(value.to_string(), merge_group);
+        }
+    }
 
+    // Merge a `SwanlingRequestMetric` into a `SwanlingRequestMetricAggregate`.
+    fn merge_request_metric(
+        &self,
+        merge_request: &mut SwanlingRequestMetricAggregate,
+        request_metric: &SwanlingRequestMetric,
+    ) {
         // Handle a metrics update.
         if request_metric.update {
             if request_metric.success {
@@ -2244,8 +6547,30 @@ impl SwanlingAttack {
         }
         // Store a new metric.
         else {
+            // Count requests far slower than the requests before them, keeping the slowest
+            // URLs. The threshold is found before this request is recorded so it can't skew
+            // its own threshold.
+            if let Some(outlier_factor) = self.configuration.outlier_factor {
+                if request_metric.coordinated_omission_elapsed == 0 {
+                    if let Some(threshold) = merge_request.outlier_threshold(outlier_factor) {
+                        if request_metric.response_time as f64 > threshold {
+                            merge_request.outlier_count += 1;
+                            merge_request.sample_outlier_request(SwanlingOutlierRequest {
+                                url: request_metric.url.to_string(),
+                                response_time: request_metric.response_time,
+                                threshold: threshold.ceil() as u64,
+                            });
+                        }
+                    }
+                }
+            }
+            let response_time = if self.configuration.soak {
+                soak_time(request_metric.response_time)
+            } else {
+                request_metric.response_time
+            };
             merge_request.record_time(
-                request_metric.response_time,
+                response_time,
                 request_metric.coordinated_omission_elapsed > 0,
             );
             if self.configuration.status_codes {
@@ -2256,9 +6581,29 @@ impl SwanlingAttack {
             } else {
                 merge_request.fail_count += 1;
             }
+            merge_request.record_mix_weight(request_metric.mix_weight);
+            // Statistically generated Coordinated Omission requests didn't send anything.
+            if request_metric.coordinated_omission_elapsed == 0 {
+                merge_request.bytes_sent += request_metric.bytes_sent as usize;
+                merge_request.redirect_count += request_metric.redirects as usize;
+                // Track time spent waiting in the throttle separately from response times.
+                if self.configuration.throttle_requests > 0 {
+                    merge_request.record_throttle_wait(request_metric.throttle_wait);
+                }
+                // Track time spent resolving host names for requests that resolved one.
+                if let Some(dns_time) = request_metric.dns_time {
+                    merge_request.record_dns_time(dns_time);
+                }
+            }
+            // Count requests slower than the --slow-threshold, keeping the slowest URLs.
+            if request_metric.is_slow(self.configuration.slow_threshold) {
+                merge_request.slow_count += 1;
+                merge_request.sample_slow_request(SwanlingSlowRequest {
+                    url: request_metric.url.to_string(),
+                    response_time: request_metric.response_time,
+                });
+            }
         }
-
-        self.metrics.requests.insert(key, merge_request);
     }
 
     // Receive metrics from [`SwanlingUser`](./swanling/struct.SwanlingUser.html) threads. If flush
@@ -2281,6 +6626,24 @@ impl SwanlingAttack {
             received_message = true;
             match message.unwrap() {
                 SwanlingMetric::Request(request_metric) => {
+                    // Measure response times for the adaptive throttle, if enabled.
+                    if let Some(adaptive) = self.metrics.adaptive.as_mut() {
+                        adaptive.record(&request_metric);
+                    }
+                    // Measure errors while ramping to failure, if enabled.
+                    if let Some(ramp) = self.metrics.ramp_to_failure.as_mut() {
+                        ramp.record(&request_metric);
+                    }
+
+                    // Count each request one time, ignoring updates to a previous request.
+                    if !request_metric.update {
+                        self.request_count += 1;
+                        if let Some(started) = self.started {
+                            let second = started.elapsed().as_secs() as usize;
+                            self.metrics.record_throughput(second, 1);
+                        }
+                    }
+
                     // If there was an error, store it.
                     if !request_metric.error.is_empty() {
                         self.record_error(&request_metric, swanling_attack_run_state);
@@ -2319,9 +6682,26 @@ impl SwanlingAttack {
                     }
                 }
                 SwanlingMetric::Task(raw_task) => {
+                    let run_time = if self.configuration.soak {
+                        soak_time(raw_task.run_time)
+                    } else {
+                        raw_task.run_time
+                    };
                     // Store a new metric.
-                    self.metrics.tasks[raw_task.taskset_index][raw_task.task_index]
-                        .set_time(raw_task.run_time, raw_task.success);
+                    let task = &mut self.metrics.tasks[raw_task.taskset_index][raw_task.task_index];
+                    task.set_time(run_time, raw_task.success);
+                    task.record_request_times(&raw_task.request_times);
+                }
+                SwanlingMetric::Custom(custom_metric) => {
+                    let name = match &custom_metric {
+                        SwanlingCustomMetric::Counter { name, .. } => name,
+                        SwanlingCustomMetric::Gauge { name, .. } => name,
+                    };
+                    merge_custom_metric(
+                        &mut self.metrics.custom,
+                        name,
+                        SwanlingCustomMetricAggregate::new(&custom_metric),
+                    );
                 }
             }
             // Unless flushing all metrics, break out of receive loop after timeout.
@@ -2361,16 +6741,53 @@ impl SwanlingAttack {
             }
         }
 
+        // If enabled, track the most recent distinct errors while the load test runs. In a
+        // Regatta they are tracked by the Manager.
+        if let (Some(error_tail), false) = (
+            self.configuration.error_tail,
+            self.attack_mode == AttackMode::Worker,
+        ) {
+            let last_seen = self
+                .started
+                .map_or(0, |started| started.elapsed().as_secs() as usize);
+            let error = SwanlingErrorMetricAggregate::new(
+                raw_request.method.clone(),
+                raw_request.name.clone(),
+                raw_request.error.clone(),
+            );
+            self.metrics
+                .record_recent_error(error_tail, &error, 1, last_seen);
+        }
+
         // If the error summary is disabled, return without collecting errors.
         if self.configuration.no_error_summary {
             return;
         }
 
         // Create a string to uniquely identify errors for tracking metrics.
-        let error_string = format!(
+        let mut error_string = format!(
             "{}.{}.{}",
             raw_request.error, raw_request.method, raw_request.name
         );
+        let mut name = raw_request.name.as_str();
+        let mut error = raw_request.error.as_str();
+        // During a soak test, combine new errors into a single entry once the maximum
+        // number are tracked, so memory doesn't grow without bound.
+        if self.configuration.soak {
+            if let Some(overflow_key) =
+                error_overflow_key(&self.metrics.errors, &error_string, &raw_request.method)
+            {
+                if !self.metrics.errors.contains_key(&overflow_key) {
+                    warn!(
+                        "more than {} distinct errors, combining new errors into {}",
+                        SOAK_MAX_ERRORS, METRICS_OVERFLOW
+                    );
+                }
+                error_string = overflow_key;
+                name = METRICS_OVERFLOW;
+                error = METRICS_OVERFLOW;
+            }
+        }
 
         let mut error_metrics = match self.metrics.errors.get(&error_string) {
             // We've seen this error before.
@@ -2378,8 +6795,8 @@ impl SwanlingAttack {
             // First time we've seen this error.
             None => SwanlingErrorMetricAggregate::new(
                 raw_request.method.clone(),
-                raw_request.name.to_string(),
-                raw_request.error.to_string(),
+                name.to_string(),
+                error.to_string(),
             ),
         };
         error_metrics.occurrences += 1;
@@ -2402,421 +6819,11 @@ impl SwanlingAttack {
     ) -> Result<(), SwanlingError> {
         // Only write the report if enabled.
         if let Some(report_file) = swanling_attack_run_state.report_file.as_mut() {
-            // Prepare report summary variables.
-            let started = self.metrics.started.unwrap();
-            let start_time = started.format("%Y-%m-%d %H:%M:%S").to_string();
-            let end_time = (started + chrono::Duration::seconds(self.metrics.duration as i64))
-                .format("%Y-%m-%d %H:%M:%S")
-                .to_string();
             let host = match self.get_configuration_host() {
                 Some(h) => h.to_string(),
                 None => "".to_string(),
             };
-
-            // Prepare requests and responses variables.
-            let mut raw_request_metrics = Vec::new();
-            let mut co_request_metrics = Vec::new();
-            let mut raw_response_metrics = Vec::new();
-            let mut co_response_metrics = Vec::new();
-            let mut raw_aggregate_total_count = 0;
-            let mut co_aggregate_total_count = 0;
-            let mut raw_aggregate_fail_count = 0;
-            let mut raw_aggregate_response_time_counter: usize = 0;
-            let mut raw_aggregate_response_time_minimum: usize = 0;
-            let mut raw_aggregate_response_time_maximum: usize = 0;
-            let mut raw_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-            let mut co_aggregate_response_time_counter: usize = 0;
-            let mut co_aggregate_response_time_maximum: usize = 0;
-            let mut co_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-            let mut co_data = false;
-            for (request_key, request) in self.metrics.requests.iter().sorted() {
-                // Determine whether or not to include Coordinated Omission data.
-                if !co_data && request.coordinated_omission_data.is_some() {
-                    co_data = true;
-                }
-                let method = format!("{}", request.method);
-                // The request_key is "{method} {name}", so by stripping the "{method} "
-                // prefix we get the name.
-                let name = request_key
-                    .strip_prefix(&format!("{} ", request.method))
-                    .unwrap()
-                    .to_string();
-                let total_request_count = request.success_count + request.fail_count;
-                let (requests_per_second, failures_per_second) = per_second_calculations(
-                    self.metrics.duration,
-                    total_request_count,
-                    request.fail_count,
-                );
-                // Prepare per-request metrics.
-                raw_request_metrics.push(report::RequestMetric {
-                    method: method.to_string(),
-                    name: name.to_string(),
-                    number_of_requests: total_request_count,
-                    number_of_failures: request.fail_count,
-                    response_time_average: format!(
-                        "{:.2}",
-                        request.raw_data.total_time as f32 / request.raw_data.counter as f32
-                    ),
-                    response_time_minimum: request.raw_data.minimum_time,
-                    response_time_maximum: request.raw_data.maximum_time,
-                    requests_per_second: format!("{:.2}", requests_per_second),
-                    failures_per_second: format!("{:.2}", failures_per_second),
-                });
-
-                // Prepare per-response metrics.
-                raw_response_metrics.push(report::get_response_metric(
-                    &method,
-                    &name,
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                ));
-
-                // Collect aggregated request and response metrics.
-                raw_aggregate_total_count += total_request_count;
-                raw_aggregate_fail_count += request.fail_count;
-                raw_aggregate_response_time_counter += request.raw_data.total_time;
-                raw_aggregate_response_time_minimum = update_min_time(
-                    raw_aggregate_response_time_minimum,
-                    request.raw_data.minimum_time,
-                );
-                raw_aggregate_response_time_maximum = update_max_time(
-                    raw_aggregate_response_time_maximum,
-                    request.raw_data.maximum_time,
-                );
-                raw_aggregate_response_times =
-                    merge_times(raw_aggregate_response_times, request.raw_data.times.clone());
-            }
-
-            // Prepare aggregate per-request metrics.
-            let (raw_aggregate_requests_per_second, raw_aggregate_failures_per_second) =
-                per_second_calculations(
-                    self.metrics.duration,
-                    raw_aggregate_total_count,
-                    raw_aggregate_fail_count,
-                );
-            raw_request_metrics.push(report::RequestMetric {
-                method: "".to_string(),
-                name: "Aggregated".to_string(),
-                number_of_requests: raw_aggregate_total_count,
-                number_of_failures: raw_aggregate_fail_count,
-                response_time_average: format!(
-                    "{:.2}",
-                    raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32
-                ),
-                response_time_minimum: raw_aggregate_response_time_minimum,
-                response_time_maximum: raw_aggregate_response_time_maximum,
-                requests_per_second: format!("{:.2}", raw_aggregate_requests_per_second),
-                failures_per_second: format!("{:.2}", raw_aggregate_failures_per_second),
-            });
-
-            // Prepare aggregate per-response metrics.
-            raw_response_metrics.push(report::get_response_metric(
-                "",
-                "Aggregated",
-                &raw_aggregate_response_times,
-                raw_aggregate_total_count,
-                raw_aggregate_response_time_minimum,
-                raw_aggregate_response_time_maximum,
-            ));
-
-            // Compile the request metrics template.
-            let mut raw_requests_rows = Vec::new();
-            for metric in raw_request_metrics {
-                raw_requests_rows.push(report::raw_request_metrics_row(metric));
-            }
-
-            // Compile the response metrics template.
-            let mut raw_responses_rows = Vec::new();
-            for metric in raw_response_metrics {
-                raw_responses_rows.push(report::response_metrics_row(metric));
-            }
-
-            let co_requests_template: String;
-            let co_responses_template: String;
-            if co_data {
-                for (request_key, request) in self.metrics.requests.iter().sorted() {
-                    if let Some(coordinated_omission_data) =
-                        request.coordinated_omission_data.as_ref()
-                    {
-                        let method = format!("{}", request.method);
-                        // The request_key is "{method} {name}", so by stripping the "{method} "
-                        // prefix we get the name.
-                        let name = request_key
-                            .strip_prefix(&format!("{} ", request.method))
-                            .unwrap()
-                            .to_string();
-                        let raw_average =
-                            request.raw_data.total_time as f32 / request.raw_data.counter as f32;
-                        let co_average = coordinated_omission_data.total_time as f32
-                            / coordinated_omission_data.counter as f32;
-                        // Prepare per-request metrics.
-                        co_request_metrics.push(report::CORequestMetric {
-                            method: method.to_string(),
-                            name: name.to_string(),
-                            response_time_average: format!("{:.2}", co_average),
-                            response_time_standard_deviation: format!(
-                                "{:.2}",
-                                util::standard_deviation(raw_average, co_average)
-                            ),
-                            response_time_maximum: coordinated_omission_data.maximum_time,
-                        });
-
-                        // Prepare per-response metrics.
-                        co_response_metrics.push(report::get_response_metric(
-                            &method,
-                            &name,
-                            &coordinated_omission_data.times,
-                            coordinated_omission_data.counter,
-                            coordinated_omission_data.minimum_time,
-                            coordinated_omission_data.maximum_time,
-                        ));
-
-                        // Collect aggregated request and response metrics.
-                        co_aggregate_response_time_counter += coordinated_omission_data.total_time;
-                        co_aggregate_response_time_maximum = update_max_time(
-                            co_aggregate_response_time_maximum,
-                            coordinated_omission_data.maximum_time,
-                        );
-                        co_aggregate_response_times = merge_times(
-                            co_aggregate_response_times,
-                            coordinated_omission_data.times.clone(),
-                        );
-                    }
-                    let total_request_count = request.success_count + request.fail_count;
-                    co_aggregate_total_count += total_request_count;
-                }
-                let co_average =
-                    co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32;
-                let raw_average =
-                    raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32;
-                co_request_metrics.push(report::CORequestMetric {
-                    method: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    response_time_average: format!(
-                        "{:.2}",
-                        co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32
-                    ),
-                    response_time_standard_deviation: format!(
-                        "{:.2}",
-                        util::standard_deviation(raw_average, co_average),
-                    ),
-                    response_time_maximum: co_aggregate_response_time_maximum,
-                });
-
-                // Prepare aggregate per-response metrics.
-                co_response_metrics.push(report::get_response_metric(
-                    "",
-                    "Aggregated",
-                    &co_aggregate_response_times,
-                    co_aggregate_total_count,
-                    raw_aggregate_response_time_minimum,
-                    co_aggregate_response_time_maximum,
-                ));
-
-                // Compile the co_request metrics rows.
-                let mut co_request_rows = Vec::new();
-                for metric in co_request_metrics {
-                    co_request_rows.push(report::coordinated_omission_request_metrics_row(metric));
-                }
-
-                // Compile the status_code metrics template.
-                co_requests_template = report::coordinated_omission_request_metrics_template(
-                    &co_request_rows.join("\n"),
-                );
-
-                // Compile the co_request metrics rows.
-                let mut co_response_rows = Vec::new();
-                for metric in co_response_metrics {
-                    co_response_rows
-                        .push(report::coordinated_omission_response_metrics_row(metric));
-                }
-
-                // Compile the status_code metrics template.
-                co_responses_template = report::coordinated_omission_response_metrics_template(
-                    &co_response_rows.join("\n"),
-                );
-            } else {
-                // If --status-codes is not enabled, return an empty template.
-                co_requests_template = "".to_string();
-                co_responses_template = "".to_string();
-            }
-
-            // Only build the tasks template if --no-task-metrics isn't enabled.
-            let tasks_template: String;
-            if !self.configuration.no_task_metrics {
-                let mut task_metrics = Vec::new();
-                let mut aggregate_total_count = 0;
-                let mut aggregate_fail_count = 0;
-                let mut aggregate_task_time_counter: usize = 0;
-                let mut aggregate_task_time_minimum: usize = 0;
-                let mut aggregate_task_time_maximum: usize = 0;
-                let mut aggregate_task_times: BTreeMap<usize, usize> = BTreeMap::new();
-                for (task_set_counter, task_set) in self.metrics.tasks.iter().enumerate() {
-                    for (task_counter, task) in task_set.iter().enumerate() {
-                        if task_counter == 0 {
-                            // Only the taskset_name is used for task sets.
-                            task_metrics.push(report::TaskMetric {
-                                is_task_set: true,
-                                task: "".to_string(),
-                                name: task.taskset_name.to_string(),
-                                number_of_requests: 0,
-                                number_of_failures: 0,
-                                response_time_average: "".to_string(),
-                                response_time_minimum: 0,
-                                response_time_maximum: 0,
-                                requests_per_second: "".to_string(),
-                                failures_per_second: "".to_string(),
-                            });
-                        }
-                        let total_run_count = task.success_count + task.fail_count;
-                        let (requests_per_second, failures_per_second) = per_second_calculations(
-                            self.metrics.duration,
-                            total_run_count,
-                            task.fail_count,
-                        );
-                        let average = match task.counter {
-                            0 => 0.00,
-                            _ => task.total_time as f32 / task.counter as f32,
-                        };
-                        task_metrics.push(report::TaskMetric {
-                            is_task_set: false,
-                            task: format!("{}.{}", task_set_counter, task_counter),
-                            name: task.task_name.to_string(),
-                            number_of_requests: total_run_count,
-                            number_of_failures: task.fail_count,
-                            response_time_average: format!("{:.2}", average),
-                            response_time_minimum: task.min_time,
-                            response_time_maximum: task.max_time,
-                            requests_per_second: format!("{:.2}", requests_per_second),
-                            failures_per_second: format!("{:.2}", failures_per_second),
-                        });
-
-                        aggregate_total_count += total_run_count;
-                        aggregate_fail_count += task.fail_count;
-                        aggregate_task_times =
-                            merge_times(aggregate_task_times, task.times.clone());
-                        aggregate_task_time_counter += &task.counter;
-                        aggregate_task_time_minimum =
-                            update_min_time(aggregate_task_time_minimum, task.min_time);
-                        aggregate_task_time_maximum =
-                            update_max_time(aggregate_task_time_maximum, task.max_time);
-                    }
-                }
-
-                let (aggregate_requests_per_second, aggregate_failures_per_second) =
-                    per_second_calculations(
-                        self.metrics.duration,
-                        aggregate_total_count,
-                        aggregate_fail_count,
-                    );
-                task_metrics.push(report::TaskMetric {
-                    is_task_set: false,
-                    task: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    number_of_requests: aggregate_total_count,
-                    number_of_failures: aggregate_fail_count,
-                    response_time_average: format!(
-                        "{:.2}",
-                        raw_aggregate_response_time_counter as f32 / aggregate_total_count as f32
-                    ),
-                    response_time_minimum: aggregate_task_time_minimum,
-                    response_time_maximum: aggregate_task_time_maximum,
-                    requests_per_second: format!("{:.2}", aggregate_requests_per_second),
-                    failures_per_second: format!("{:.2}", aggregate_failures_per_second),
-                });
-                let mut tasks_rows = Vec::new();
-                // Compile the task metrics template.
-                for metric in task_metrics {
-                    tasks_rows.push(report::task_metrics_row(metric));
-                }
-
-                tasks_template = report::task_metrics_template(&tasks_rows.join("\n"));
-            } else {
-                tasks_template = "".to_string();
-            }
-
-            // Only build the tasks template if --no-task-metrics isn't enabled.
-            let errors_template: String;
-            if !self.metrics.errors.is_empty() {
-                let mut error_rows = Vec::new();
-                for error in self.metrics.errors.values() {
-                    error_rows.push(report::error_row(error));
-                }
-                errors_template = report::errors_template(&error_rows.join("\n"));
-            } else {
-                errors_template = "".to_string();
-            }
-
-            // Only build the status_code template if --status-codes is enabled.
-            let status_code_template: String;
-            if self.configuration.status_codes {
-                let mut status_code_metrics = Vec::new();
-                let mut aggregated_status_code_counts: HashMap<u16, usize> = HashMap::new();
-                for (request_key, request) in self.metrics.requests.iter().sorted() {
-                    let method = format!("{}", request.method);
-                    // The request_key is "{method} {name}", so by stripping the "{method} "
-                    // prefix we get the name.
-                    let name = request_key
-                        .strip_prefix(&format!("{} ", request.method))
-                        .unwrap()
-                        .to_string();
-
-                    // Build a list of status codes, and update the aggregate record.
-                    let codes = prepare_status_codes(
-                        &request.status_code_counts,
-                        &mut Some(&mut aggregated_status_code_counts),
-                    );
-
-                    // Add a row of data for the status code table.
-                    status_code_metrics.push(report::StatusCodeMetric {
-                        method,
-                        name,
-                        status_codes: codes,
-                    });
-                }
-
-                // Build a list of aggregate status codes.
-                let aggregated_codes =
-                    prepare_status_codes(&aggregated_status_code_counts, &mut None);
-
-                // Add a final row of aggregate data for the status code table.
-                status_code_metrics.push(report::StatusCodeMetric {
-                    method: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    status_codes: aggregated_codes,
-                });
-
-                // Compile the status_code metrics rows.
-                let mut status_code_rows = Vec::new();
-                for metric in status_code_metrics {
-                    status_code_rows.push(report::status_code_metrics_row(metric));
-                }
-
-                // Compile the status_code metrics template.
-                status_code_template =
-                    report::status_code_metrics_template(&status_code_rows.join("\n"));
-            } else {
-                // If --status-codes is not enabled, return an empty template.
-                status_code_template = "".to_string();
-            }
-
-            // Compile the report template.
-            let report = report::build_report(
-                &start_time,
-                &end_time,
-                &host,
-                report::SwanlingReportTemplates {
-                    raw_requests_template: &raw_requests_rows.join("\n"),
-                    raw_responses_template: &raw_responses_rows.join("\n"),
-                    co_requests_template: &co_requests_template,
-                    co_responses_template: &co_responses_template,
-                    tasks_template: &tasks_template,
-                    status_codes_template: &status_code_template,
-                    errors_template: &errors_template,
-                },
-            );
+            let report = self.metrics.html_report(&host);
 
             // Write the report to file.
             if let Err(e) = report_file.write(report.as_ref()).await {
@@ -2837,6 +6844,86 @@ impl SwanlingAttack {
 
         Ok(())
     }
+
+    // Write latency distribution charts, if enabled.
+    pub(crate) async fn write_chart_output(&self) -> Result<(), SwanlingError> {
+        if self.configuration.chart_output.is_empty() {
+            return Ok(());
+        }
+
+        // The Manager doesn't track when the load test started, so derive it from the
+        // duration.
+        let started = self.metrics.started.unwrap_or_else(|| {
+            Local::now() - chrono::Duration::seconds(self.metrics.duration as i64)
+        });
+        let start_time = started.format("%Y-%m-%d %H:%M:%S").to_string();
+        let end_time = (started + chrono::Duration::seconds(self.metrics.duration as i64))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let host = match self.get_configuration_host() {
+            Some(h) => h,
+            None => "".to_string(),
+        };
+
+        // Chart each request, followed by all requests together.
+        let mut names = Vec::new();
+        let mut charts = Vec::new();
+        let mut data = Vec::new();
+        let mut aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
+        for (request_key, request) in self.metrics.requests.iter().sorted() {
+            charts.push(report::latency_chart(request_key, &request.raw_data.times));
+            data.push(report::latency_data(request_key, &request.raw_data.times));
+            names.push(request_key.to_string());
+            aggregate_response_times =
+                merge_times(aggregate_response_times, request.raw_data.times.clone());
+        }
+        if names.len() > 1 {
+            charts.push(report::latency_chart(
+                "Aggregated",
+                &aggregate_response_times,
+            ));
+            data.push(report::latency_data(
+                "Aggregated",
+                &aggregate_response_times,
+            ));
+            names.push("Aggregated".to_string());
+        }
+
+        // Gnuplot expects two blank lines between each block of data.
+        let files = [
+            (
+                "latency.html",
+                report::build_chart_report(&start_time, &end_time, &host, &charts.join("\n")),
+            ),
+            ("latency.dat", data.join("\n\n")),
+            (
+                "latency.gnuplot",
+                report::latency_gnuplot_script("latency.dat", &names),
+            ),
+        ];
+
+        let chart_output = std::path::Path::new(&self.configuration.chart_output);
+        let mut result = tokio::fs::create_dir_all(chart_output).await;
+        for (file_name, contents) in &files {
+            if result.is_ok() {
+                result = tokio::fs::write(chart_output.join(file_name), contents).await;
+            }
+        }
+        if let Err(e) = result {
+            return Err(SwanlingError::InvalidOption {
+                option: "--chart-output".to_string(),
+                value: self.configuration.chart_output.to_string(),
+                detail: format!("Failed to write charts: {}", e),
+            });
+        }
+
+        info!(
+            "wrote latency distribution charts to: {}",
+            self.configuration.chart_output
+        );
+
+        Ok(())
+    }
 }
 
 /// Helper to calculate requests and fails per seconds.
@@ -2903,14 +6990,31 @@ pub(crate) fn update_max_time(mut global_max: usize, max: usize) -> usize {
     global_max
 }
 
-/// Get the response time that a certain number of percent of the requests finished within.
-pub(crate) fn calculate_response_time_percentile(
+/// Get the response time that a certain number of percent of the requests finished within.
+pub(crate) fn calculate_response_time_percentile(
+    response_times: &BTreeMap<usize, usize>,
+    total_requests: usize,
+    min: usize,
+    max: usize,
+    percent: f32,
+) -> String {
+    format_number(response_time_percentile(
+        response_times,
+        total_requests,
+        min,
+        max,
+        percent,
+    ))
+}
+
+/// Get the unformatted time that a certain number of percent of the requests finished within.
+pub(crate) fn response_time_percentile(
     response_times: &BTreeMap<usize, usize>,
     total_requests: usize,
     min: usize,
     max: usize,
     percent: f32,
-) -> String {
+) -> usize {
     let percentile_request = (total_requests as f32 * percent).round() as usize;
     debug!(
         "percentile: {}, request {} of total {}",
@@ -2923,15 +7027,154 @@ pub(crate) fn calculate_response_time_percentile(
         total_count += counter;
         if total_count >= percentile_request {
             if *value < min {
-                return format_number(min);
+                return min;
             } else if *value > max {
-                return format_number(max);
+                return max;
             } else {
-                return format_number(*value);
+                return *value;
+            }
+        }
+    }
+    0
+}
+
+#[cfg(feature = "gaggle")]
+/// The largest UDP payload sent to an InfluxDB line protocol listener, small enough to
+/// avoid fragmentation on most networks.
+const INFLUX_UDP_PAYLOAD: usize = 1_400;
+
+#[cfg(feature = "gaggle")]
+/// Escape a tag value for InfluxDB line protocol, where commas, equals signs and spaces
+/// are delimiters.
+fn influx_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == ',' || c == '=' || c == ' ' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(feature = "gaggle")]
+/// Format request metrics from one Worker as InfluxDB line protocol, one line per
+/// request, timestamped with `timestamp` nanoseconds since the Unix epoch.
+pub(crate) fn influx_lines(
+    requests: &SwanlingRequestMetrics,
+    worker_id: usize,
+    timestamp: u128,
+) -> Vec<String> {
+    requests
+        .values()
+        .sorted()
+        .map(|request| {
+            let data = &request.raw_data;
+            let percentile = |percent| {
+                response_time_percentile(
+                    &data.times,
+                    data.counter,
+                    data.minimum_time,
+                    data.maximum_time,
+                    percent,
+                )
+            };
+            format!(
+                "swanling_requests,method={},name={},worker_id={} count={}i,failures={}i,p50={}i,p95={}i,p99={}i {}",
+                request.method,
+                influx_escape(&request.path),
+                worker_id,
+                request.success_count + request.fail_count,
+                request.fail_count,
+                percentile(0.5),
+                percentile(0.95),
+                percentile(0.99),
+                timestamp,
+            )
+        })
+        .collect()
+}
+
+#[cfg(feature = "gaggle")]
+/// Writes InfluxDB line protocol, configured with the `--influx-file` and `--influx-udp`
+/// run-time options.
+pub(crate) struct SwanlingInfluxWriter {
+    /// The file lines are appended to, if enabled.
+    file: Option<std::fs::File>,
+    /// The non-blocking socket lines are sent from, if enabled.
+    socket: Option<std::net::UdpSocket>,
+}
+#[cfg(feature = "gaggle")]
+impl SwanlingInfluxWriter {
+    /// Open the configured file and socket, returning `None` if neither is enabled.
+    pub(crate) fn new(configuration: &SwanlingConfiguration) -> std::io::Result<Option<Self>> {
+        if configuration.influx_file.is_empty() && configuration.influx_udp.is_empty() {
+            return Ok(None);
+        }
+
+        let file = if configuration.influx_file.is_empty() {
+            None
+        } else {
+            Some(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&configuration.influx_file)?,
+            )
+        };
+
+        let socket = if configuration.influx_udp.is_empty() {
+            None
+        } else {
+            let socket = std::net::UdpSocket::bind("0.0.0.0:0")?;
+            socket.connect(&configuration.influx_udp)?;
+            // Never wait for the network, so sending can't stall aggregation.
+            socket.set_nonblocking(true)?;
+            Some(socket)
+        };
+
+        Ok(Some(SwanlingInfluxWriter { file, socket }))
+    }
+
+    /// Append lines to the file and send them over UDP, logging any failure.
+    pub(crate) fn write(&mut self, lines: &[String]) {
+        if lines.is_empty() {
+            return;
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            let mut buffer = lines.join("\n");
+            buffer.push('\n');
+            if let Err(e) = std::io::Write::write_all(file, buffer.as_bytes()) {
+                warn!("failed to write InfluxDB line protocol: {}", e);
+            }
+        }
+
+        if let Some(socket) = self.socket.as_ref() {
+            // Batch as many lines as fit in each datagram.
+            let mut datagram = String::new();
+            for line in lines {
+                if !datagram.is_empty() && datagram.len() + line.len() + 1 > INFLUX_UDP_PAYLOAD {
+                    Self::send(socket, &datagram);
+                    datagram.clear();
+                }
+                datagram.push_str(line);
+                datagram.push('\n');
+            }
+            Self::send(socket, &datagram);
+        }
+    }
+
+    /// Send one datagram, dropping it if the socket isn't ready.
+    fn send(socket: &std::net::UdpSocket, datagram: &str) {
+        match socket.send(datagram.as_bytes()) {
+            Ok(_) => (),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                debug!("InfluxDB line protocol socket not ready, dropping datagram");
             }
+            Err(e) => warn!("failed to send InfluxDB line protocol: {}", e),
         }
     }
-    format_number(0)
 }
 
 /// Helper to count and aggregate seen status codes.
@@ -2969,6 +7212,157 @@ pub(crate) fn prepare_status_codes(
     codes
 }
 
+/// What the load test does next, returned by the callback set with
+/// [`SwanlingAttack::set_metrics_callback`](../struct.SwanlingAttack.html#method.set_metrics_callback).
+#[derive(Debug, Clone, PartialEq)]
+pub enum SwanlingControlAction {
+    /// Keep running the load test unchanged.
+    Continue,
+    /// Stop the load test, as if the run time expired.
+    Abort,
+    /// Apply changes to the running load test, as if made by a controller.
+    Adjust(SwanlingReconfiguration),
+}
+
+/// A callback invoked with a snapshot of the metrics each interval, set with
+/// [`SwanlingAttack::set_metrics_callback`](../struct.SwanlingAttack.html#method.set_metrics_callback).
+#[derive(Clone)]
+pub(crate) struct SwanlingMetricsCallback {
+    /// How often, in seconds, the callback is invoked.
+    pub(crate) interval: usize,
+    /// The callback.
+    pub(crate) callback: Arc<dyn Fn(&SwanlingMetrics) -> SwanlingControlAction + Send + Sync>,
+}
+impl fmt::Debug for SwanlingMetricsCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SwanlingMetricsCallback({})", self.interval)
+    }
+}
+
+/// Runs the metrics callback on its own thread, so a slow callback can't delay receiving
+/// metrics. If the callback is still running when the next interval ends, that snapshot
+/// is skipped.
+#[derive(Debug)]
+pub(crate) struct SwanlingMetricsCallbackThread {
+    /// How often, in seconds, a snapshot is sent to the callback.
+    interval: usize,
+    /// Timer tracking when a snapshot was last sent to the callback.
+    pub(crate) timer: std::time::Instant,
+    /// Sends snapshots to the callback thread, holding at most one.
+    snapshot_tx: flume::Sender<SwanlingMetrics>,
+    /// Receives the actions returned by the callback.
+    action_rx: flume::Receiver<SwanlingControlAction>,
+}
+impl SwanlingMetricsCallbackThread {
+    /// Spawn a thread invoking the callback, which exits when this is dropped.
+    pub(crate) fn spawn(metrics_callback: &SwanlingMetricsCallback) -> Self {
+        let (snapshot_tx, snapshot_rx) = flume::bounded::<SwanlingMetrics>(1);
+        let (action_tx, action_rx) = flume::unbounded();
+        let callback = metrics_callback.callback.clone();
+        std::thread::spawn(move || {
+            for snapshot in snapshot_rx.iter() {
+                if action_tx.send(callback(&snapshot)).is_err() {
+                    break;
+                }
+            }
+        });
+        SwanlingMetricsCallbackThread {
+            interval: metrics_callback.interval,
+            timer: std::time::Instant::now(),
+            snapshot_tx,
+            action_rx,
+        }
+    }
+
+    /// Send a snapshot of the metrics to the callback if the interval ended, returning the
+    /// action from an earlier snapshot if the callback has returned one.
+    pub(crate) fn poll(&mut self, metrics: &SwanlingMetrics) -> Option<SwanlingControlAction> {
+        if util::timer_expired(self.timer, self.interval) {
+            self.timer = std::time::Instant::now();
+            if self.snapshot_tx.try_send(metrics.clone()).is_err() {
+                debug!("metrics callback still running, skipping snapshot");
+            }
+        }
+        self.action_rx.try_recv().ok()
+    }
+}
+
+/// A handle to stop a running load test from another thread, as if it was canceled with
+/// ctrl-c, returned by
+/// [`SwanlingAttack::shutdown_handle`](../struct.SwanlingAttack.html#method.shutdown_handle)
+/// and
+/// [`SwanlingMetricsStream::shutdown_handle`](./struct.SwanlingMetricsStream.html#method.shutdown_handle).
+#[derive(Debug, Clone)]
+pub struct SwanlingShutdownHandle(pub(crate) Arc<AtomicBool>);
+impl SwanlingShutdownHandle {
+    /// Gracefully stop the load test, still returning the metrics it collected.
+    pub fn shutdown(&self) {
+        self.0.store(true, atomic::Ordering::SeqCst);
+    }
+}
+
+/// A [`Stream`](https://docs.rs/futures/*/futures/stream/trait.Stream.html) of snapshots
+/// of the metrics while the load test runs, created with
+/// [`SwanlingAttack::metrics_stream`](../struct.SwanlingAttack.html#method.metrics_stream).
+///
+/// A snapshot is skipped if the previous one hasn't been received yet when the next
+/// interval ends, so a slow consumer always receives recent metrics. The stream ends once
+/// the load test finishes, and the final metrics are returned by
+/// [`SwanlingAttack::execute`](../struct.SwanlingAttack.html#method.execute).
+pub struct SwanlingMetricsStream {
+    /// Receives snapshots of the metrics.
+    pub(crate) snapshots: flume::r#async::RecvStream<'static, SwanlingMetrics>,
+    /// Stops the load test.
+    pub(crate) shutdown: SwanlingShutdownHandle,
+}
+impl SwanlingMetricsStream {
+    /// Returns a handle that stops the load test, which can be moved to another task or
+    /// thread.
+    pub fn shutdown_handle(&self) -> SwanlingShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Gracefully stop the load test. The stream ends once it has stopped.
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown();
+    }
+}
+impl Stream for SwanlingMetricsStream {
+    type Item = SwanlingMetrics;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.snapshots).poll_next(cx)
+    }
+}
+impl fmt::Debug for SwanlingMetricsStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SwanlingMetricsStream")
+    }
+}
+
+/// Sends snapshots of the metrics to a
+/// [`SwanlingMetricsStream`](./struct.SwanlingMetricsStream.html) every interval.
+#[derive(Debug, Clone)]
+pub(crate) struct SwanlingMetricsStreamSender {
+    /// How often, in seconds, a snapshot is sent.
+    pub(crate) interval: usize,
+    /// Timer tracking when a snapshot was last sent.
+    pub(crate) timer: std::time::Instant,
+    /// Sends snapshots to the stream, holding at most one.
+    pub(crate) snapshot_tx: flume::Sender<SwanlingMetrics>,
+}
+impl SwanlingMetricsStreamSender {
+    /// Send a snapshot of the metrics to the stream if the interval ended.
+    pub(crate) fn poll(&mut self, metrics: &SwanlingMetrics) {
+        if util::timer_expired(self.timer, self.interval) {
+            self.timer = std::time::Instant::now();
+            if self.snapshot_tx.try_send(metrics.clone()).is_err() {
+                debug!("metrics stream not yet received, skipping snapshot");
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2998,6 +7392,804 @@ mod test {
         assert_eq!(min_response_time, 9);
     }
 
+    #[test]
+    fn task_percentiles() {
+        let mut task = SwanlingTaskMetricAggregate::new(0, "LoadTestTasks", 0, "task");
+        // Ten task runs, with run times of 10 ms through 100 ms.
+        for time in 1..=10 {
+            task.set_time(time * 10, true);
+        }
+        assert_eq!(task.percentile(0.5), 50);
+        assert_eq!(task.percentile(0.95), 100);
+        assert_eq!(task.percentile(0.99), 100);
+
+        // Times over 500 ms are rounded to the nearest 100 ms.
+        task.set_time(749, true);
+        assert!(task.times.contains_key(&700));
+        // Times over 1000 ms are rounded to the nearest 1000 ms.
+        task.set_time(2400, false);
+        assert!(task.times.contains_key(&2000));
+        assert_eq!(task.percentile(0.99), 2000);
+
+        let percentiles = SwanlingTaskPercentiles::from(&task);
+        assert_eq!(percentiles.task_name, "task");
+        assert_eq!(percentiles.percentile_50, 60);
+        assert_eq!(percentiles.percentile_99, 2000);
+    }
+
+    #[test]
+    fn custom_metrics() {
+        let mut custom_metrics: SwanlingCustomMetrics = BTreeMap::new();
+        let counter = SwanlingCustomMetric::Counter {
+            name: "hits".to_string(),
+            value: 2,
+        };
+        let gauge = SwanlingCustomMetric::Gauge {
+            name: "size".to_string(),
+            value: 3.0,
+        };
+
+        // Counters are summed together.
+        merge_custom_metric(
+            &mut custom_metrics,
+            "hits",
+            SwanlingCustomMetricAggregate::new(&counter),
+        );
+        merge_custom_metric(
+            &mut custom_metrics,
+            "hits",
+            SwanlingCustomMetricAggregate::Counter(5),
+        );
+        assert_eq!(
+            custom_metrics["hits"],
+            SwanlingCustomMetricAggregate::Counter(7)
+        );
+        assert_eq!(custom_metrics["hits"].mean(), None);
+
+        // Gauges track the minimum, maximum and mean.
+        merge_custom_metric(
+            &mut custom_metrics,
+            "size",
+            SwanlingCustomMetricAggregate::new(&gauge),
+        );
+        merge_custom_metric(
+            &mut custom_metrics,
+            "size",
+            SwanlingCustomMetricAggregate::Gauge {
+                observations: 2,
+                total: 9.0,
+                minimum: 1.0,
+                maximum: 8.0,
+            },
+        );
+        assert_eq!(
+            custom_metrics["size"],
+            SwanlingCustomMetricAggregate::Gauge {
+                observations: 3,
+                total: 12.0,
+                minimum: 1.0,
+                maximum: 8.0,
+            }
+        );
+        assert_eq!(custom_metrics["size"].mean(), Some(4.0));
+
+        // A counter can't be merged into a gauge.
+        merge_custom_metric(
+            &mut custom_metrics,
+            "size",
+            SwanlingCustomMetricAggregate::Counter(1),
+        );
+        assert_eq!(custom_metrics["size"].mean(), Some(4.0));
+    }
+
+    #[test]
+    fn slas() {
+        // Valid agreements.
+        assert_eq!(
+            parse_sla("/search:p95=200"),
+            Some((
+                "/search".to_string(),
+                SwanlingSlaThreshold::Percentile {
+                    percent: 95.0,
+                    milliseconds: 200
+                }
+            ))
+        );
+        assert_eq!(
+            parse_sla("GET /report:max=5000"),
+            Some((
+                "GET /report".to_string(),
+                SwanlingSlaThreshold::Maximum(5000)
+            ))
+        );
+        assert_eq!(
+            parse_sla("http://example.com/:errors=1.5%"),
+            Some((
+                "http://example.com/".to_string(),
+                SwanlingSlaThreshold::ErrorRate(1.5)
+            ))
+        );
+
+        // Invalid agreements.
+        assert_eq!(parse_sla("/search"), None);
+        assert_eq!(parse_sla(":p95=200"), None);
+        assert_eq!(parse_sla("/search:p95"), None);
+        assert_eq!(parse_sla("/search:p101=200"), None);
+        assert_eq!(parse_sla("/search:avg=200"), None);
+        assert_eq!(parse_sla("/search:errors=200"), None);
+
+        // Agreements are checked against requests matching the name.
+        let mut metrics = SwanlingMetrics::default();
+        let mut search = SwanlingRequestMetricAggregate::new("/search", SwanlingMethod::Get, 0);
+        for response_time in 1..=100 {
+            search.record_time(response_time, false);
+            search.success_count += 1;
+        }
+        search.fail_count = 1;
+        metrics.requests.insert("GET /search".to_string(), search);
+
+        assert!(metrics.check_slas(&["/search:p95=100".to_string()]));
+        assert_eq!(metrics.slas[0].observed, Some(95.0));
+        assert!(metrics.check_slas(&[
+            "GET /search:max=100".to_string(),
+            "/search:errors=1".to_string(),
+        ]));
+        assert!(!metrics.check_slas(&[
+            "/search:p95=90".to_string(),
+            "/search:errors=0".to_string(),
+            "/report:max=5000".to_string(),
+        ]));
+        assert!(metrics.slas.iter().all(|sla| !sla.passed));
+        assert_eq!(metrics.slas[2].observed, None);
+    }
+
+    #[test]
+    fn slow_requests() {
+        // Only real requests over the threshold are slow.
+        let mut request_metric = SwanlingRequestMetric::new(SwanlingMethod::Get, "/", "/", 0, 0);
+        request_metric.set_response_time(100);
+        assert!(!request_metric.is_slow(None));
+        assert!(!request_metric.is_slow(Some(100)));
+        assert!(request_metric.is_slow(Some(99)));
+        request_metric.coordinated_omission_elapsed = 1;
+        assert!(!request_metric.is_slow(Some(99)));
+
+        // Only the slowest requests are kept, slowest first.
+        let mut search = SwanlingRequestMetricAggregate::new("/search", SwanlingMethod::Get, 0);
+        for response_time in &[1200, 1100, 1500, 1050, 1900, 1300, 1000] {
+            search.record_time(*response_time, false);
+            search.slow_count += 1;
+            search.sample_slow_request(SwanlingSlowRequest {
+                url: format!("http://example.com/search?q={}", response_time),
+                response_time: *response_time,
+            });
+        }
+        let response_times: Vec<u64> = search
+            .slow_requests
+            .iter()
+            .map(|slow_request| slow_request.response_time)
+            .collect();
+        assert_eq!(response_times, vec![1900, 1500, 1300, 1200, 1100]);
+        for _ in 0..193 {
+            search.record_time(10, false);
+        }
+
+        // Slow requests are only displayed in the final report, when a threshold is set.
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /search".to_string(), search);
+        assert!(!metrics.to_string().contains("SLOW REQUESTS"));
+        metrics.final_metrics = true;
+        assert!(!metrics.to_string().contains("SLOW REQUESTS"));
+        metrics.slow_threshold = Some(999);
+        let report = metrics.to_string();
+        assert!(report.contains("SLOW REQUESTS (over 999 ms)"));
+        assert!(report.contains("3.50%"));
+        assert!(report.contains("1,900 ms  http://example.com/search?q=1900"));
+        assert!(!report.contains("http://example.com/search?q=1050"));
+    }
+
+    #[test]
+    fn folded_task_profile() {
+        let mut index = SwanlingTaskMetricAggregate::new(0, "Anonymous", 0, "index");
+        index.set_time(120, true);
+        index.record_request_times(&[("GET /".to_string(), 80), ("GET /logo.png".to_string(), 30)]);
+        index.set_time(100, true);
+        index.record_request_times(&[("GET /".to_string(), 90)]);
+        // Tasks that haven't run aren't profiled, and unnamed tasks are numbered.
+        let unused = SwanlingTaskMetricAggregate::new(0, "Anonymous", 1, "unused");
+        let mut unnamed = SwanlingTaskMetricAggregate::new(1, "Checkout;Cart", 0, "");
+        unnamed.set_time(10, true);
+        unnamed.record_request_times(&[("POST /cart".to_string(), 15)]);
+
+        let metrics = SwanlingMetrics {
+            tasks: vec![vec![index, unused], vec![unnamed]],
+            ..Default::default()
+        };
+        assert_eq!(
+            metrics.folded_task_profile(),
+            "1: Anonymous;1: index 20\n\
+             1: Anonymous;1: index;GET / 170\n\
+             1: Anonymous;1: index;GET /logo.png 30\n\
+             2: Checkout,Cart;1:;POST /cart 15\n"
+        );
+    }
+
+    #[test]
+    fn outlier_requests() {
+        // There's no threshold until enough requests have been made.
+        let mut search = SwanlingRequestMetricAggregate::new("/search", SwanlingMethod::Get, 0);
+        for _ in 0..OUTLIER_MIN_REQUESTS / 2 - 1 {
+            search.record_time(90, false);
+            search.record_time(110, false);
+        }
+        search.record_time(100, false);
+        assert_eq!(search.outlier_threshold(3.0), None);
+        search.record_time(100, false);
+
+        // The average is 100 ms and the standard deviation nearly 10 ms.
+        let threshold = search.outlier_threshold(3.0).unwrap();
+        assert!(threshold > 129.0 && threshold < 130.0);
+        assert!(search.outlier_threshold(1.0).unwrap() < threshold);
+
+        // Only the slowest outliers are kept, slowest first.
+        for response_time in &[400, 300, 700, 200, 500, 600] {
+            search.outlier_count += 1;
+            search.sample_outlier_request(SwanlingOutlierRequest {
+                url: format!("http://example.com/search?q={}", response_time),
+                response_time: *response_time,
+                threshold: 130,
+            });
+        }
+        let response_times: Vec<u64> = search
+            .outlier_requests
+            .iter()
+            .map(|outlier_request| outlier_request.response_time)
+            .collect();
+        assert_eq!(response_times, vec![700, 600, 500, 400, 300]);
+
+        // Outliers are only displayed in the final report, when a factor is set.
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /search".to_string(), search);
+        assert!(!metrics.to_string().contains("OUTLIERS"));
+        metrics.outlier_factor = Some(3.0);
+        let report = metrics.to_string();
+        assert!(report.contains("OUTLIERS (over 3 standard deviations slower than average)"));
+        assert!(report.contains("700 ms  http://example.com/search?q=700 (over 130 ms)"));
+        assert!(!report.contains("http://example.com/search?q=200"));
+    }
+
+    #[test]
+    fn min_samples() {
+        let mut index = SwanlingRequestMetricAggregate::new("/", SwanlingMethod::Get, 0);
+        for response_time in 1..=50 {
+            index.record_time(response_time, false);
+        }
+        let mut about = SwanlingRequestMetricAggregate::new("/about", SwanlingMethod::Get, 0);
+        for response_time in &[10, 20, 30, 40, 900] {
+            about.record_time(*response_time, false);
+        }
+
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.requests.insert("GET /about".to_string(), about);
+        assert!(!metrics.to_string().contains(INSUFFICIENT_SAMPLES));
+
+        // Percentiles of requests with fewer samples aren't reported.
+        metrics.min_samples = Some(20);
+        let report = metrics.to_string();
+        assert!(report.contains(" GET /about               | n/a (insufficient samples)"));
+        assert!(!report.contains(" GET /                    | n/a (insufficient samples)"));
+        assert!(!report.contains(" Aggregated               | n/a (insufficient samples)"));
+
+        // Not even combined.
+        metrics.min_samples = Some(100);
+        let report = metrics.to_string();
+        assert!(report.contains(" GET /                    | n/a (insufficient samples)"));
+        assert!(report.contains(" Aggregated               | n/a (insufficient samples)"));
+    }
+
+    #[test]
+    fn request_mix() {
+        let mut cart = SwanlingRequestMetricAggregate::new("/cart", SwanlingMethod::Get, 0);
+        let mut health = SwanlingRequestMetricAggregate::new("/health", SwanlingMethod::Get, 0);
+        for _ in 0..3 {
+            cart.success_count += 1;
+            cart.record_mix_weight(1);
+            health.success_count += 1;
+            health.record_mix_weight(1);
+        }
+        assert_eq!(cart.mix_count, None);
+        assert_eq!(health.counted_in_mix(), 3);
+
+        // The request mix is only displayed once some requests are weighted.
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        metrics
+            .requests
+            .insert("GET /cart".to_string(), cart.clone());
+        metrics.requests.insert("GET /health".to_string(), health);
+        assert!(!metrics.to_string().contains("REQUEST MIX"));
+
+        // Health checks are left out of the mix, after the first one.
+        let mut health = SwanlingRequestMetricAggregate::new("/health", SwanlingMethod::Get, 0);
+        for mix_weight in &[1, 0, 0] {
+            health.success_count += 1;
+            health.record_mix_weight(*mix_weight);
+        }
+        assert_eq!(health.mix_count, Some(1));
+        metrics.requests.insert("GET /health".to_string(), health);
+        let report = metrics.to_string();
+        assert!(report.contains("REQUEST MIX"));
+        assert!(report.contains(
+            " GET /cart                |          3 |     50.00% |          3 |     75.00%"
+        ));
+        assert!(report.contains(
+            " GET /health              |          3 |     50.00% |          1 |     25.00%"
+        ));
+    }
+
+    #[test]
+    fn running_interval() {
+        let mut index = SwanlingRequestMetricAggregate::new("/", SwanlingMethod::Get, 0);
+        index.success_count = 90;
+        index.fail_count = 10;
+        let mut metrics = SwanlingMetrics {
+            duration: 10,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /".to_string(), index.clone());
+        metrics.measure_running_interval();
+        assert_eq!(
+            metrics.running_interval.as_ref().unwrap().requests["GET /"],
+            (100, 10)
+        );
+
+        // Only the requests made since the previous interval are counted.
+        index.success_count = 120;
+        index.fail_count = 30;
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.duration = 20;
+        metrics.measure_running_interval();
+        let interval = metrics.running_interval.as_ref().unwrap();
+        assert_eq!((interval.started, interval.stopped), (10, 20));
+        assert_eq!(interval.requests["GET /"], (50, 20));
+
+        // The interval is displayed with the running metrics, not the final metrics.
+        metrics.display_metrics = true;
+        let report = metrics.to_string();
+        assert!(report.contains("LAST INTERVAL (10s to 20s)"));
+        assert!(report.contains(
+            " GET /                    |            50 |     20 (40.0%) |     5.00 |    2.00"
+        ));
+        metrics.final_metrics = true;
+        assert!(!metrics.to_string().contains("LAST INTERVAL"));
+
+        // Resetting the metrics starts over.
+        metrics.reset_metrics();
+        assert!(metrics.running_interval.is_none());
+        assert!(metrics.running_totals.requests.is_empty());
+    }
+
+    #[test]
+    fn groups() {
+        let mut eu = SwanlingRequestMetricAggregate::new("eu", SwanlingMethod::Get, 0);
+        for response_time in &[10, 20, 30, 40] {
+            eu.record_time(*response_time, false);
+        }
+        eu.success_count = 3;
+        eu.fail_count = 1;
+
+        // Groups are only displayed in the final report, when a label is set.
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        metrics.groups.insert("eu".to_string(), eu);
+        assert!(!metrics.to_string().contains("PER LABEL METRICS"));
+        metrics.group_by = Some("region".to_string());
+        let report = metrics.to_string();
+        assert!(report.contains("PER LABEL METRICS (grouped by region)"));
+        assert!(report.contains(
+            " eu               |         4 |         1 |    25.00 |     20 |     40 |     40"
+        ));
+    }
+
+    #[test]
+    fn dropped_samples() {
+        // Dropped samples are only mentioned if there were any.
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        assert!(!metrics.to_string().contains("dropped"));
+        metrics.dropped_samples = 1234;
+        assert!(metrics
+            .to_string()
+            .contains("1,234 samples dropped by Workers, metrics are incomplete."));
+    }
+
+    #[test]
+    fn quarantined_workers() {
+        // Quarantined Workers are only listed if there were any.
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        assert!(!metrics.to_string().contains("QUARANTINED WORKERS"));
+        metrics.quarantined.push(SwanlingQuarantinedWorker {
+            worker_id: 3,
+            quarantined: 42,
+            error_rate: 87.5,
+            peer_error_rate: 1.25,
+            ignored_requests: 12345,
+        });
+        let report = metrics.to_string();
+        assert!(report.contains("QUARANTINED WORKERS"));
+        assert!(report
+            .contains(" 3        |               42 |     87.50% |      1.25% |           12,345"));
+    }
+
+    #[test]
+    fn synthetic_metrics() {
+        // Build a known set of request, task and error metrics.
+        let mut request = SwanlingRequestMetricAggregate::new("/about", SwanlingMethod::Get, 0);
+        for response_time in &[10, 20, 30] {
+            request.record_time(*response_time, false);
+            request.set_status_code(200);
+        }
+        request.record_time(40, false);
+        request.set_status_code(503);
+        request.success_count = 3;
+        request.fail_count = 1;
+        let mut task = SwanlingTaskMetricAggregate::new(0, "WebsiteUser", 0, "about");
+        task.set_time(25, true);
+        task.set_time(45, false);
+        let mut error = SwanlingErrorMetricAggregate::new(
+            SwanlingMethod::Get,
+            "/about".to_string(),
+            "503 Service Unavailable: /about".to_string(),
+        );
+        error.occurrences = 1;
+
+        let mut metrics = SwanlingMetrics {
+            duration: 2,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /about".to_string(), request);
+        metrics.tasks = vec![vec![task]];
+        metrics.errors.insert("503.GET./about".to_string(), error);
+
+        metrics.set_display_metrics(true);
+        metrics.set_final_metrics(true);
+        let text = metrics.to_string();
+        assert!(text.contains(
+            "   1: about               |             2 |      1 (50.0%) |     1.00 |    0.50"
+        ));
+        assert!(text.contains(
+            " GET /about               |             4 |      1 (25.0%) |     2.00 |    0.50"
+        ));
+        assert!(text.contains(" 1             GET /about: 503 Service Unavailable: /about"));
+        assert!(!text.contains("STATUS CODE METRICS"));
+        metrics.set_display_status_codes(true);
+        // Status codes are listed in no particular order.
+        let text = metrics.to_string();
+        assert!(text.contains("3 [200], 1 [503]") || text.contains("1 [503], 3 [200]"));
+
+        let json = serde_json::to_string(&metrics).unwrap();
+        assert!(json.contains(r#""success_count":3,"fail_count":1"#));
+        assert!(json.contains(r#""error":"503 Service Unavailable: /about""#));
+
+        let html = metrics.html_report("http://example.com");
+        assert!(html.contains("http://example.com"));
+        assert!(html.contains("<h2>Task Metrics</h2>"));
+        assert!(html.contains("<h2>Status Code Metrics</h2>"));
+        assert!(html.contains("503 Service Unavailable: /about"));
+        metrics.tasks = Vec::new();
+        metrics.set_display_status_codes(false);
+        let html = metrics.html_report("http://example.com");
+        assert!(!html.contains("<h2>Task Metrics</h2>"));
+        assert!(!html.contains("<h2>Status Code Metrics</h2>"));
+    }
+
+    #[test]
+    fn error_tail() {
+        let not_found = SwanlingErrorMetricAggregate::new(
+            SwanlingMethod::Get,
+            "/missing".to_string(),
+            "404 Not Found: /missing".to_string(),
+        );
+        let unavailable = SwanlingErrorMetricAggregate::new(
+            SwanlingMethod::Get,
+            "/".to_string(),
+            "503 Service Unavailable: /".to_string(),
+        );
+        let timeout = SwanlingErrorMetricAggregate::new(
+            SwanlingMethod::Post,
+            "/".to_string(),
+            "operation timed out".to_string(),
+        );
+
+        let mut metrics = SwanlingMetrics::default();
+        metrics.record_recent_error(2, &not_found, 1, 1);
+        metrics.record_recent_error(2, &unavailable, 3, 2);
+        assert_eq!(metrics.error_tail.len(), 2);
+        assert!(metrics.error_tail_changed);
+
+        // An error that occurs again becomes the most recent error.
+        metrics.record_recent_error(2, &not_found, 1, 4);
+        assert_eq!(metrics.error_tail[1].name, "/missing");
+        assert_eq!(metrics.error_tail[1].occurrences, 2);
+        assert_eq!(metrics.error_tail[1].last_seen, 4);
+
+        // Only the most recent distinct errors are kept.
+        metrics.record_recent_error(2, &timeout, 1, 5);
+        assert_eq!(metrics.error_tail.len(), 2);
+        assert_eq!(metrics.error_tail[0].error, "404 Not Found: /missing");
+        assert_eq!(metrics.error_tail[1].error, "operation timed out");
+
+        // The tail is only displayed again after new errors.
+        metrics.print_error_tail();
+        assert!(!metrics.error_tail_changed);
+    }
+
+    #[test]
+    fn error_kinds() {
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        for (error, kind, occurrences) in [
+            (
+                "TLS handshake failed: invalid peer certificate: UnknownIssuer",
+                SwanlingErrorKind::TlsHandshake,
+                3,
+            ),
+            (
+                "Connection failed: Connection refused (os error 111)",
+                SwanlingErrorKind::Connection,
+                2,
+            ),
+            (
+                "Request timed out: operation timed out",
+                SwanlingErrorKind::Timeout,
+                1,
+            ),
+            (
+                "AWS SigV4 signing failed: 403 Forbidden InvalidSignatureException: /",
+                SwanlingErrorKind::Signing,
+                5,
+            ),
+            ("503 Service Unavailable: /", SwanlingErrorKind::Other, 4),
+        ] {
+            let mut aggregate = SwanlingErrorMetricAggregate::new(
+                SwanlingMethod::Get,
+                "/".to_string(),
+                error.to_string(),
+            );
+            assert_eq!(aggregate.kind, kind);
+            aggregate.occurrences = occurrences;
+            metrics.errors.insert(error.to_string(), aggregate);
+        }
+
+        // Connection level failures are counted by kind, other errors are not.
+        let report = metrics.to_string();
+        assert!(report.contains(" 3             TLS handshake failed\n"));
+        assert!(report.contains(" 2             Connection failed\n"));
+        assert!(report.contains(" 1             Request timed out\n"));
+        assert!(report.contains(" 5             AWS SigV4 signing failed\n"));
+        assert!(!report.contains("Other"));
+    }
+
+    #[test]
+    fn task_set_users() {
+        let mut metrics = SwanlingMetrics {
+            display_metrics: true,
+            final_metrics: true,
+            ..Default::default()
+        };
+        metrics.initialize_task_set_users(&[
+            SwanlingTaskSet::new("TaskSet1").set_weight(2).unwrap(),
+            SwanlingTaskSet::new("TaskSet2"),
+        ]);
+        for task_sets_index in &[0, 1, 0, 1, 0] {
+            metrics.record_task_set_user(*task_sets_index);
+        }
+        // Unknown task sets are ignored.
+        metrics.record_task_set_user(2);
+
+        assert_eq!(metrics.task_set_users[0].weight, 2);
+        assert_eq!(metrics.task_set_users[0].users, 3);
+        assert_eq!(metrics.task_set_users[1].weight, 1);
+        assert_eq!(metrics.task_set_users[1].users, 2);
+        let report = metrics.to_string();
+        assert!(report.contains("USERS PER TASK SET"));
+        assert!(report.contains(
+            " 1: TaskSet1              |          2 |     66.67% |          3 |     60.00%"
+        ));
+    }
+
+    #[test]
+    fn reset_metrics() {
+        let mut metrics = SwanlingMetrics::default();
+        metrics.tasks.push(vec![SwanlingTaskMetricAggregate::new(
+            0, "Users", 0, "index",
+        )]);
+        metrics.tasks[0][0].set_time(10, true);
+        let mut index = SwanlingRequestMetricAggregate::new("/", SwanlingMethod::Get, 0);
+        index.record_time(10, false);
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.errors.insert(
+            "503.GET./".to_string(),
+            SwanlingErrorMetricAggregate::new(
+                SwanlingMethod::Get,
+                "/".to_string(),
+                "503 Service Unavailable: /".to_string(),
+            ),
+        );
+
+        // All metrics are discarded, but the same tasks are still tracked.
+        metrics.reset_metrics();
+        assert!(metrics.requests.is_empty());
+        assert!(metrics.errors.is_empty());
+        assert_eq!(metrics.tasks[0].len(), 1);
+        assert_eq!(metrics.tasks[0][0].task_name, "index");
+        assert_eq!(metrics.tasks[0][0].counter, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "gaggle")]
+    fn windows() {
+        let mut metrics = SwanlingMetrics::default();
+        metrics.tasks.push(vec![SwanlingTaskMetricAggregate::new(
+            0, "Users", 0, "index",
+        )]);
+        assert!(metrics.last_complete_window().is_none());
+
+        // Each window starts with empty task metrics, leaving lifetime metrics untouched.
+        metrics.tasks[0][0].set_time(10, true);
+        metrics.start_window(0);
+        assert_eq!(metrics.windows[0].tasks[0][0].counter, 0);
+        assert_eq!(metrics.tasks[0][0].counter, 1);
+
+        // Starting a new window closes the previous one.
+        metrics.start_window(30);
+        metrics.start_window(60);
+        assert_eq!(metrics.windows.len(), 3);
+        assert_eq!(metrics.windows[1].started, 30);
+        assert_eq!(metrics.windows[1].stopped, 60);
+
+        // A final window cut short when the load test stops is not complete.
+        metrics.close_window(65);
+        assert_eq!(metrics.last_complete_window().unwrap().started, 30);
+        metrics.close_window(90);
+        assert_eq!(metrics.last_complete_window().unwrap().started, 60);
+    }
+
+    #[test]
+    #[cfg(feature = "gaggle")]
+    fn influx() {
+        use gumdrop::Options;
+
+        let mut requests = SwanlingRequestMetrics::new();
+        let mut search = SwanlingRequestMetricAggregate::new("/search a,b", SwanlingMethod::Get, 0);
+        for response_time in 1..=100 {
+            search.record_time(response_time, false);
+            search.success_count += 1;
+        }
+        search.fail_count = 2;
+        requests.insert("GET /search a,b".to_string(), search);
+
+        // Tags are escaped, counts are integers and percentiles are in milliseconds.
+        let lines = influx_lines(&requests, 3, 1_000);
+        assert_eq!(
+            lines,
+            vec![
+                "swanling_requests,method=GET,name=/search\\ a\\,b,worker_id=3 count=102i,failures=2i,p50=50i,p95=95i,p99=99i 1000"
+                    .to_string()
+            ]
+        );
+
+        // Lines are appended to the file and sent over UDP.
+        let listener = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let file = std::env::temp_dir().join(format!("swanling-influx-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&file);
+        let empty_args: Vec<&str> = vec![];
+        let mut configuration = SwanlingConfiguration::parse_args_default(&empty_args).unwrap();
+        configuration.influx_file = file.to_str().unwrap().to_string();
+        configuration.influx_udp = listener.local_addr().unwrap().to_string();
+        let mut writer = SwanlingInfluxWriter::new(&configuration).unwrap().unwrap();
+        writer.write(&lines);
+        writer.write(&lines);
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            format!("{}\n{}\n", lines[0], lines[0])
+        );
+        let mut buffer = [0; 2_048];
+        let received = listener.recv(&mut buffer).unwrap();
+        assert_eq!(&buffer[..received], format!("{}\n", lines[0]).as_bytes());
+        std::fs::remove_file(&file).unwrap();
+
+        // Nothing is written unless enabled.
+        configuration.influx_file = "".to_string();
+        configuration.influx_udp = "".to_string();
+        assert!(SwanlingInfluxWriter::new(&configuration).unwrap().is_none());
+    }
+
+    #[test]
+    fn soak() {
+        // Shorter times are not changed, longer times keep two significant digits.
+        assert_eq!(soak_time(99_999), 99_999);
+        assert_eq!(soak_time(123_456), 120_000);
+        assert_eq!(soak_time(987_654_321), 990_000_000);
+
+        // Requests and errors are tracked on their own until the maximum is reached.
+        let mut requests = SwanlingRequestMetrics::new();
+        let mut errors = SwanlingErrorMetrics::new();
+        for index in 0..SOAK_MAX_REQUESTS {
+            let key = format!("GET /{}", index);
+            assert_eq!(
+                request_overflow_key(&requests, &key, &SwanlingMethod::Get, SOAK_MAX_REQUESTS),
+                None
+            );
+            requests.insert(
+                key.to_string(),
+                SwanlingRequestMetricAggregate::new(&key, SwanlingMethod::Get, 0),
+            );
+        }
+        for index in 0..SOAK_MAX_ERRORS {
+            let key = format!("{}.GET./", index);
+            assert_eq!(
+                error_overflow_key(&errors, &key, &SwanlingMethod::Get),
+                None
+            );
+            errors.insert(
+                key.to_string(),
+                SwanlingErrorMetricAggregate::new(SwanlingMethod::Get, "/".to_string(), key),
+            );
+        }
+
+        // Known keys are still tracked on their own, new keys are combined.
+        assert_eq!(
+            request_overflow_key(&requests, "GET /0", &SwanlingMethod::Get, SOAK_MAX_REQUESTS),
+            None
+        );
+        assert_eq!(
+            request_overflow_key(
+                &requests,
+                "POST /new",
+                &SwanlingMethod::Post,
+                SOAK_MAX_REQUESTS
+            ),
+            Some("POST (overflow)".to_string())
+        );
+        assert_eq!(
+            error_overflow_key(&errors, "0.GET./", &SwanlingMethod::Get),
+            None
+        );
+        assert_eq!(
+            error_overflow_key(&errors, "new.GET./", &SwanlingMethod::Get),
+            Some("(overflow).GET.(overflow)".to_string())
+        );
+    }
+
     #[test]
     fn response_time_merge() {
         let mut global_response_times: BTreeMap<usize, usize> = BTreeMap::new();
@@ -3041,6 +8233,114 @@ mod test {
         assert!(calculate_response_time_percentile(&response_times, 115, 1, 200, 0.999) == "200");
     }
 
+    #[test]
+    fn throughput_stability() {
+        // Less than two seconds isn't enough to measure stability.
+        let mut metrics = SwanlingMetrics {
+            duration: 1,
+            ..Default::default()
+        };
+        metrics.record_throughput(0, 10);
+        assert_eq!(metrics.throughput_stability(), None);
+
+        // Seconds before the steady state are ignored, and seconds without requests count.
+        metrics.duration = 5;
+        metrics.record_throughput(0, 90);
+        metrics.record_throughput(2, 20);
+        metrics.record_throughput(3, 20);
+        metrics.steady_state_started = 1;
+        assert_eq!(metrics.throughput, vec![100, 0, 20, 20]);
+        let stability = metrics.throughput_stability().unwrap();
+        assert_eq!(stability.seconds, 4);
+        assert_eq!(stability.mean, 10.0);
+        assert_eq!(stability.std_dev, 10.0);
+        assert_eq!(stability.min, 0);
+        assert_eq!(stability.max, 20);
+        assert_eq!(stability.coefficient_of_variation, 1.0);
+
+        // Perfectly steady throughput doesn't vary.
+        metrics.throughput = vec![100, 10, 10, 10, 10];
+        assert_eq!(
+            metrics
+                .throughput_stability()
+                .unwrap()
+                .coefficient_of_variation,
+            0.0
+        );
+
+        // Without requests there's nothing to measure.
+        metrics.throughput = Vec::new();
+        assert_eq!(metrics.throughput_stability(), None);
+
+        // Resetting the metrics restarts the timeline.
+        metrics.throughput = vec![1, 2, 3];
+        metrics.reset_metrics();
+        assert!(metrics.throughput.is_empty());
+        assert_eq!(metrics.steady_state_started, 0);
+    }
+
+    #[test]
+    fn throttle_efficiency() {
+        let mut metrics = SwanlingMetrics {
+            duration: 4,
+            throughput: vec![10, 10, 10, 10],
+            users: 2,
+            ..Default::default()
+        };
+
+        // Without a throttle there's nothing to compare with.
+        assert_eq!(metrics.throttle_efficiency(), None);
+
+        // Each request takes 200 ms, so two users can make 10 requests per second.
+        let mut request = SwanlingRequestMetricAggregate::new("/", SwanlingMethod::Get, 0);
+        for _ in 0..40 {
+            request.record_time(200, false);
+        }
+        metrics.requests.insert("GET /".to_string(), request);
+
+        // The intended rate was reached.
+        metrics.throttle_requests = 10;
+        let efficiency = metrics.throttle_efficiency().unwrap();
+        assert_eq!(efficiency.achieved, 10.0);
+        assert_eq!(efficiency.efficiency, 100.0);
+        assert_eq!(efficiency.in_flight, 2.0);
+        assert_eq!(efficiency.bottleneck, None);
+
+        // Both users were always waiting for responses, so the target is the bottleneck.
+        metrics.throttle_requests = 100;
+        let efficiency = metrics.throttle_efficiency().unwrap();
+        assert_eq!(efficiency.efficiency, 10.0);
+        assert_eq!(efficiency.bottleneck, Some(SwanlingBottleneck::Target));
+
+        // With more users, they weren't making requests most of the time.
+        metrics.users = 10;
+        assert_eq!(
+            metrics.throttle_efficiency().unwrap().bottleneck,
+            Some(SwanlingBottleneck::Users)
+        );
+
+        // Unless they were held back by the limit on connections.
+        metrics.max_connections = 2;
+        assert_eq!(
+            metrics.throttle_efficiency().unwrap().bottleneck,
+            Some(SwanlingBottleneck::MaxConnections)
+        );
+
+        // Requests that mostly waited in the throttle were held back by the throttle.
+        for request in metrics.requests.values_mut() {
+            for _ in 0..40 {
+                request.record_throttle_wait(400);
+            }
+        }
+        let efficiency = metrics.throttle_efficiency().unwrap();
+        assert!(efficiency.throttle_wait > THROTTLE_WAIT_BOTTLENECK);
+        assert_eq!(efficiency.bottleneck, Some(SwanlingBottleneck::Throttle));
+
+        // The adaptive throttle has no single intended rate.
+        metrics.adaptive = Some(SwanlingAdaptive::new(100));
+        assert_eq!(metrics.throttle_efficiency(), None);
+    }
+
     #[test]
     fn calculate_per_second() {
         // With duration of 0, requests and fails per second is always 0.
@@ -3304,4 +8604,65 @@ mod test {
         assert_eq!(request.raw_data.total_time, 987657045);
         assert_eq!(request.raw_data.counter, 8);
     }
+
+    #[test]
+    fn baseline_comparison() {
+        // Build metrics with a request taking the given time, failing some requests.
+        let build_metrics = |name: &str, response_time: usize, fail_count: usize| {
+            let mut metrics = SwanlingMetrics {
+                duration: 10,
+                ..Default::default()
+            };
+            let mut request = SwanlingRequestMetricAggregate::new(name, SwanlingMethod::Get, 0);
+            for _ in 0..100 {
+                request.record_time(response_time as u64, false);
+            }
+            request.success_count = 100 - fail_count;
+            request.fail_count = fail_count;
+            metrics.requests.insert(format!("GET {}", name), request);
+            metrics
+        };
+        let bounds = SwanlingRegressionBounds::new(10.0);
+        let baseline = serde_json::to_string(&build_metrics("/", 100, 0)).unwrap();
+
+        // Within the bounds nothing regressed.
+        let mut metrics = build_metrics("/", 105, 0);
+        assert!(metrics.check_baseline(&baseline, &bounds).unwrap());
+        let comparison = metrics.comparison.as_ref().unwrap();
+        assert_eq!(comparison.requests[0].response_time_change, Some(5.0));
+        assert_eq!(comparison.throughput_change, Some(0.0));
+
+        // Much faster is an improvement.
+        let mut metrics = build_metrics("/", 50, 0);
+        assert!(metrics.check_baseline(&baseline, &bounds).unwrap());
+        assert!(metrics.comparison.as_ref().unwrap().requests[0].improved);
+
+        // Slower, or failing more often, beyond the bounds is a regression.
+        let mut metrics = build_metrics("/", 120, 0);
+        assert!(!metrics.check_baseline(&baseline, &bounds).unwrap());
+        assert_eq!(
+            metrics.comparison.as_ref().unwrap().regressions(),
+            vec!["GET /".to_string()]
+        );
+        let mut metrics = build_metrics("/", 100, 11);
+        assert!(!metrics.check_baseline(&baseline, &bounds).unwrap());
+
+        // New and removed requests aren't regressions, but lower throughput is.
+        let current = serde_json::to_string(&build_metrics("/new", 100, 0)).unwrap();
+        let comparison = SwanlingComparison::from_json(&baseline, &current, &bounds).unwrap();
+        assert!(!comparison.regressed);
+        let results: Vec<&str> = comparison.requests.iter().map(|r| r.result()).collect();
+        assert_eq!(results, vec!["GONE", "NEW"]);
+        let mut metrics = build_metrics("/", 100, 0);
+        metrics.duration = 20;
+        assert!(!metrics.check_baseline(&baseline, &bounds).unwrap());
+        assert_eq!(
+            metrics.comparison.as_ref().unwrap().regressions(),
+            vec!["throughput".to_string()]
+        );
+
+        // Only JSON reports can be compared.
+        assert!(is_valid_baseline(&baseline));
+        assert!(!is_valid_baseline("not json"));
+    }
 }
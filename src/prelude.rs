@@ -7,12 +7,17 @@
 //! use swanling::prelude::*;
 //! ```
 
-pub use crate::metrics::{SwanlingCoordinatedOmissionMitigation, SwanlingMetrics};
+pub use crate::metrics::{
+    SwanlingCoordinatedOmissionMitigation, SwanlingMetrics, SwanlingSuiteMetrics,
+};
 pub use crate::swanling::{
-    SwanlingTask, SwanlingTaskError, SwanlingTaskFunction, SwanlingTaskResult, SwanlingTaskSet,
-    SwanlingUser,
+    SwanlingExtractor, SwanlingHeaderCheck, SwanlingHeaderOutcome, SwanlingMarkovScheduler,
+    SwanlingRequestExpectContentType, SwanlingRequestExpectSize, SwanlingRequestExpectStatus,
+    SwanlingRequestLabel, SwanlingRequestMixWeight, SwanlingTask, SwanlingTaskControl,
+    SwanlingTaskError, SwanlingTaskFunction, SwanlingTaskResult, SwanlingTaskScheduler,
+    SwanlingTaskSet, SwanlingUser,
 };
 pub use crate::{
     task, taskset, SwanlingAttack, SwanlingDefault, SwanlingDefaultType, SwanlingError,
-    SwanlingScheduler,
+    SwanlingScheduler, SwanlingSuite,
 };
@@ -1,4 +1,5 @@
-//! Optionally writes an html-formatted summary report after running a load test.
+//! Optionally writes an html-formatted summary report after running a load test,
+//! latency distribution charts, and a JUnit XML report.
 
 use crate::metrics;
 
@@ -14,11 +15,22 @@ pub struct SwanlingReportTemplates<'a> {
     pub raw_responses_template: &'a str,
     pub co_requests_template: &'a str,
     pub co_responses_template: &'a str,
+    pub throttle_wait_template: &'a str,
+    pub dns_time_template: &'a str,
+    pub throughput_stability_template: &'a str,
+    pub throttle_efficiency_template: &'a str,
     pub tasks_template: &'a str,
     pub status_codes_template: &'a str,
+    pub custom_template: &'a str,
     pub errors_template: &'a str,
 }
 
+// Dimensions of each latency distribution chart, in pixels.
+const CHART_WIDTH: f32 = 960.0;
+const CHART_HEIGHT: f32 = 300.0;
+// Space around the plotted area for axis labels.
+const CHART_MARGIN: f32 = 40.0;
+
 /// Defines the metrics reported about requests.
 #[derive(Debug, Clone, Serialize)]
 pub struct RequestMetric {
@@ -58,6 +70,28 @@ pub struct ResponseMetric {
     pub percentile_100: String,
 }
 
+/// Defines the metrics reported about time spent waiting in the throttle.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThrottleWaitMetric {
+    pub method: String,
+    pub name: String,
+    pub wait_average: String,
+    pub wait_maximum: usize,
+    pub response_time_average: String,
+    pub waiting_percent: String,
+}
+
+/// Defines the metrics reported about time spent resolving host names.
+#[derive(Debug, Clone, Serialize)]
+pub struct DnsTimeMetric {
+    pub method: String,
+    pub name: String,
+    pub lookups: usize,
+    pub dns_average: String,
+    pub dns_maximum: usize,
+    pub response_time_average: String,
+}
+
 /// Defines the metrics reported about tasks.
 #[derive(Debug, Clone, Serialize)]
 pub struct TaskMetric {
@@ -71,6 +105,9 @@ pub struct TaskMetric {
     pub response_time_maximum: usize,
     pub requests_per_second: String,
     pub failures_per_second: String,
+    pub percentile_50: String,
+    pub percentile_95: String,
+    pub percentile_99: String,
 }
 
 /// Defines the metrics reported about status codes.
@@ -80,6 +117,17 @@ pub struct StatusCodeMetric {
     pub status_codes: String,
 }
 
+/// Defines a testcase in the JUnit XML report.
+#[derive(Debug, Clone)]
+pub struct JunitTestCase {
+    pub classname: String,
+    pub name: String,
+    /// How long the testcase took, in seconds.
+    pub time: f32,
+    /// Why the testcase failed, or `None` if it passed.
+    pub failure: Option<String>,
+}
+
 /// Helper to generate a single response metric.
 pub fn get_response_metric(
     method: &str,
@@ -116,6 +164,27 @@ pub fn get_response_metric(
     }
 }
 
+/// Helper to generate a response metric for a request with too few samples to calculate
+/// meaningful percentiles, displaying `unavailable` instead.
+pub fn insufficient_samples_response_metric(
+    method: &str,
+    name: &str,
+    unavailable: &str,
+) -> ResponseMetric {
+    ResponseMetric {
+        method: method.to_string(),
+        name: name.to_string(),
+        percentile_50: unavailable.to_string(),
+        percentile_60: unavailable.to_string(),
+        percentile_70: unavailable.to_string(),
+        percentile_80: unavailable.to_string(),
+        percentile_90: unavailable.to_string(),
+        percentile_95: unavailable.to_string(),
+        percentile_99: unavailable.to_string(),
+        percentile_100: unavailable.to_string(),
+    }
+}
+
 /// Build an individual row of raw request metrics in the html report.
 pub fn raw_request_metrics_row(metric: RequestMetric) -> String {
     format!(
@@ -273,6 +342,176 @@ pub fn coordinated_omission_response_metrics_row(metric: ResponseMetric) -> Stri
     )
 }
 
+/// If the throttle is enabled, add a table of how long requests waited in the throttle
+/// to the html report.
+pub fn throttle_wait_metrics_template(throttle_wait_rows: &str) -> String {
+    format!(
+        r#"<div class="throttle-wait">
+        <h2>Throttle Wait Metrics</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Method</th>
+                    <th>Name</th>
+                    <th>Average wait (ms)</th>
+                    <th>Max wait (ms)</th>
+                    <th>Average response (ms)</th>
+                    <th>% waiting</th>
+                </tr>
+            </thead>
+            <tbody>
+                {throttle_wait_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        throttle_wait_rows = throttle_wait_rows,
+    )
+}
+
+/// Build an individual row of throttle wait metrics in the html report.
+pub fn throttle_wait_metrics_row(metric: ThrottleWaitMetric) -> String {
+    format!(
+        r#"<tr>
+            <td>{method}</td>
+            <td>{name}</td>
+            <td>{wait_average}</td>
+            <td>{wait_maximum}</td>
+            <td>{response_time_average}</td>
+            <td>{waiting_percent}</td>
+        </tr>"#,
+        method = metric.method,
+        name = metric.name,
+        wait_average = metric.wait_average,
+        wait_maximum = metric.wait_maximum,
+        response_time_average = metric.response_time_average,
+        waiting_percent = metric.waiting_percent,
+    )
+}
+
+/// If host names were resolved, add a table of how long it took to the html report.
+pub fn dns_time_metrics_template(dns_time_rows: &str) -> String {
+    format!(
+        r#"<div class="dns-time">
+        <h2>DNS Resolution Metrics</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Method</th>
+                    <th>Name</th>
+                    <th># Lookups</th>
+                    <th>Average lookup (ms)</th>
+                    <th>Max lookup (ms)</th>
+                    <th>Average response (ms)</th>
+                </tr>
+            </thead>
+            <tbody>
+                {dns_time_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        dns_time_rows = dns_time_rows,
+    )
+}
+
+/// Build an individual row of DNS resolution metrics in the html report.
+pub fn dns_time_metrics_row(metric: DnsTimeMetric) -> String {
+    format!(
+        r#"<tr>
+            <td>{method}</td>
+            <td>{name}</td>
+            <td>{lookups}</td>
+            <td>{dns_average}</td>
+            <td>{dns_maximum}</td>
+            <td>{response_time_average}</td>
+        </tr>"#,
+        method = metric.method,
+        name = metric.name,
+        lookups = metric.lookups,
+        dns_average = metric.dns_average,
+        dns_maximum = metric.dns_maximum,
+        response_time_average = metric.response_time_average,
+    )
+}
+
+/// If the steady state lasted long enough, add a summary of how stable the number of
+/// requests per second was to the html report.
+pub fn throughput_stability_template(stability: &metrics::SwanlingThroughputStability) -> String {
+    format!(
+        r#"<div class="throughput-stability">
+        <h2>Throughput Stability</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Seconds</th>
+                    <th>Mean req/s</th>
+                    <th>Std dev</th>
+                    <th>Min req/s</th>
+                    <th>Max req/s</th>
+                    <th>Coefficient of variation</th>
+                </tr>
+            </thead>
+            <tbody>
+                <tr>
+                    <td>{seconds}</td>
+                    <td>{mean:.2}</td>
+                    <td>{std_dev:.2}</td>
+                    <td>{min}</td>
+                    <td>{max}</td>
+                    <td>{cv:.1}%</td>
+                </tr>
+            </tbody>
+        </table>
+    </div>"#,
+        seconds = stability.seconds,
+        mean = stability.mean,
+        std_dev = stability.std_dev,
+        min = stability.min,
+        max = stability.max,
+        cv = stability.coefficient_of_variation * 100.0,
+    )
+}
+
+/// If requests were throttled, add a comparison of the rate of requests made with the
+/// throttled rate to the html report.
+pub fn throttle_efficiency_template(efficiency: &metrics::SwanlingThrottleEfficiency) -> String {
+    let bottleneck = match efficiency.bottleneck {
+        Some(bottleneck) => format!("Bottleneck: {}.", bottleneck),
+        None => "The intended rate was reached.".to_string(),
+    };
+    format!(
+        r#"<div class="throttle-efficiency">
+        <h2>Throttle Efficiency</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Intended req/s</th>
+                    <th>Achieved req/s</th>
+                    <th>Efficiency</th>
+                    <th>Throttle wait</th>
+                    <th>In flight</th>
+                </tr>
+            </thead>
+            <tbody>
+                <tr>
+                    <td>{intended}</td>
+                    <td>{achieved:.2}</td>
+                    <td>{efficiency:.1}%</td>
+                    <td>{throttle_wait:.2}%</td>
+                    <td>{in_flight:.2}</td>
+                </tr>
+            </tbody>
+        </table>
+        <p>{bottleneck}</p>
+    </div>"#,
+        intended = efficiency.intended,
+        achieved = efficiency.achieved,
+        efficiency = efficiency.efficiency,
+        throttle_wait = efficiency.throttle_wait,
+        in_flight = efficiency.in_flight,
+        bottleneck = bottleneck,
+    )
+}
+
 /// If status code metrics are enabled, add a status code metrics table to the
 /// html report.
 pub fn status_code_metrics_template(status_code_rows: &str) -> String {
@@ -326,6 +565,9 @@ pub fn task_metrics_template(task_rows: &str) -> String {
                     <th>Max (ms)</th>
                     <th>RPS</th>
                     <th>Failures/s</th>
+                    <th>50%ile (ms)</th>
+                    <th>95%ile (ms)</th>
+                    <th>99%ile (ms)</th>
                 </tr>
             </thead>
             <tbody>
@@ -342,7 +584,7 @@ pub fn task_metrics_row(metric: TaskMetric) -> String {
     if metric.is_task_set {
         format!(
             r#"<tr>
-            <td colspan="10" align="left"><strong>{name}</strong></td>
+            <td colspan="13" align="left"><strong>{name}</strong></td>
         </tr>"#,
             name = metric.name,
         )
@@ -357,6 +599,9 @@ pub fn task_metrics_row(metric: TaskMetric) -> String {
             <td>{response_time_maximum}</td>
             <td>{requests_per_second}</td>
             <td>{failures_per_second}</td>
+            <td>{percentile_50}</td>
+            <td>{percentile_95}</td>
+            <td>{percentile_99}</td>
         </tr>"#,
             task = metric.task,
             name = metric.name,
@@ -367,10 +612,69 @@ pub fn task_metrics_row(metric: TaskMetric) -> String {
             response_time_maximum = metric.response_time_maximum,
             requests_per_second = metric.requests_per_second,
             failures_per_second = metric.failures_per_second,
+            percentile_50 = metric.percentile_50,
+            percentile_95 = metric.percentile_95,
+            percentile_99 = metric.percentile_99,
         )
     }
 }
 
+/// If there are custom metrics, add a custom metrics table to the html report.
+pub fn custom_metrics_template(custom_rows: &str) -> String {
+    format!(
+        r#"<div class="custom">
+        <h2>Custom Metrics</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th colspan="2">Name</th>
+                    <th>Type</th>
+                    <th>Total / Mean</th>
+                    <th>Min</th>
+                    <th>Max</th>
+                </tr>
+            </thead>
+            <tbody>
+                {custom_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        custom_rows = custom_rows,
+    )
+}
+
+/// Build an individual row of custom metrics in the html report.
+pub fn custom_metrics_row(name: &str, custom: &metrics::SwanlingCustomMetricAggregate) -> String {
+    match custom {
+        metrics::SwanlingCustomMetricAggregate::Counter(total) => format!(
+            r#"<tr>
+            <td colspan="2">{name}</td>
+            <td>counter</td>
+            <td>{total}</td>
+            <td></td>
+            <td></td>
+        </tr>"#,
+            name = name,
+            total = metrics::format_number(*total),
+        ),
+        metrics::SwanlingCustomMetricAggregate::Gauge {
+            minimum, maximum, ..
+        } => format!(
+            r#"<tr>
+            <td colspan="2">{name}</td>
+            <td>gauge</td>
+            <td>{mean:.2}</td>
+            <td>{minimum:.2}</td>
+            <td>{maximum:.2}</td>
+        </tr>"#,
+            name = name,
+            mean = custom.mean().unwrap_or(0.0),
+            minimum = minimum,
+            maximum = maximum,
+        ),
+    }
+}
+
 /// If there are errors, add an errors table to the html report.
 pub fn errors_template(error_rows: &str) -> String {
     format!(
@@ -529,10 +833,20 @@ pub fn build_report(
 
         {co_responses_template}
 
+        {throttle_wait_template}
+
+        {dns_time_template}
+
+        {throughput_stability_template}
+
+        {throttle_efficiency_template}
+
         {status_codes_template}
 
         {tasks_template}
 
+        {custom_template}
+
         {errors_template}
 
     </div>
@@ -545,8 +859,257 @@ pub fn build_report(
         raw_responses_template = templates.raw_responses_template,
         co_requests_template = templates.co_requests_template,
         co_responses_template = templates.co_responses_template,
+        throttle_wait_template = templates.throttle_wait_template,
+        dns_time_template = templates.dns_time_template,
+        throughput_stability_template = templates.throughput_stability_template,
+        throttle_efficiency_template = templates.throttle_efficiency_template,
         tasks_template = templates.tasks_template,
         status_codes_template = templates.status_codes_template,
+        custom_template = templates.custom_template,
         errors_template = templates.errors_template,
     )
 }
+
+/// Build the latency distribution chart of a single request, as an inline svg image.
+///
+/// Response times are plotted on a logarithmic scale with one bar per recorded response
+/// time, so distributions with more than one peak (for example cache hits and misses)
+/// are easy to spot.
+pub fn latency_chart(name: &str, response_times: &BTreeMap<usize, usize>) -> String {
+    let total: usize = response_times.values().sum();
+    let (minimum, maximum) = match (response_times.keys().next(), response_times.keys().last()) {
+        (Some(minimum), Some(maximum)) if total > 0 => (*minimum, *maximum),
+        // Nothing to chart.
+        _ => {
+            return format!(
+                r#"<div class="chart"><h3>{name}</h3><p>No responses.</p></div>"#,
+                name = name
+            )
+        }
+    };
+    let largest = *response_times.values().max().unwrap();
+
+    // Span whole powers of ten, treating response times of 0 ms as 1 ms.
+    let log_minimum = (minimum.max(1) as f32).log10().floor();
+    let log_maximum = (maximum.max(1) as f32)
+        .log10()
+        .ceil()
+        .max(log_minimum + 1.0);
+    let plot_width = CHART_WIDTH - 2.0 * CHART_MARGIN;
+    let plot_height = CHART_HEIGHT - 2.0 * CHART_MARGIN;
+    let bottom = CHART_HEIGHT - CHART_MARGIN;
+    let x = |time: usize| {
+        CHART_MARGIN
+            + ((time.max(1) as f32).log10() - log_minimum) / (log_maximum - log_minimum)
+                * plot_width
+    };
+
+    let mut elements = Vec::new();
+    // Label each power of ten on the x axis.
+    for exponent in log_minimum as u32..=log_maximum as u32 {
+        let position = x(10_usize.pow(exponent));
+        elements.push(format!(
+            r#"<line x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="{bottom}" class="grid" /><text x="{x:.1}" y="{label}" text-anchor="middle">{time} ms</text>"#,
+            x = position,
+            top = CHART_MARGIN,
+            bottom = bottom,
+            label = bottom + 20.0,
+            time = 10_usize.pow(exponent),
+        ));
+    }
+    // Label the y axis with the most responses at a single response time.
+    elements.push(format!(
+        r#"<line x1="{left}" y1="{bottom}" x2="{right}" y2="{bottom}" class="axis" /><text x="{left}" y="{top}">{largest} responses</text>"#,
+        left = CHART_MARGIN,
+        right = CHART_WIDTH - CHART_MARGIN,
+        bottom = bottom,
+        top = CHART_MARGIN - 10.0,
+        largest = largest,
+    ));
+    // Add a bar for each response time.
+    for (time, count) in response_times {
+        let height = *count as f32 / largest as f32 * plot_height;
+        elements.push(format!(
+            r#"<rect x="{x:.1}" y="{y:.1}" width="3" height="{height:.1}"><title>{time} ms: {count} ({percent:.2}%)</title></rect>"#,
+            x = x(*time) - 1.5,
+            y = bottom - height,
+            height = height,
+            time = time,
+            count = count,
+            percent = *count as f32 / total as f32 * 100.0,
+        ));
+    }
+
+    format!(
+        r#"<div class="chart">
+            <h3>{name}</h3>
+            <svg width="{width}" height="{height}" viewBox="0 0 {width} {height}">
+                {elements}
+            </svg>
+        </div>"#,
+        name = name,
+        width = CHART_WIDTH,
+        height = CHART_HEIGHT,
+        elements = elements.join("\n                "),
+    )
+}
+
+/// Build the Gnuplot-ready latency distribution data of a single request.
+///
+/// Each line contains a response time in milliseconds, how many responses took that
+/// long, and what percent of all responses that is.
+pub fn latency_data(name: &str, response_times: &BTreeMap<usize, usize>) -> String {
+    let total: usize = response_times.values().sum();
+    let mut data = format!("# {}\n", name);
+    for (time, count) in response_times {
+        data.push_str(&format!(
+            "{} {} {:.4}\n",
+            time,
+            count,
+            *count as f32 / total as f32 * 100.0
+        ));
+    }
+    data
+}
+
+/// Build a Gnuplot script charting the latency distribution data of each request.
+///
+/// The data of each request is expected in its own block of `data_file`, in the same
+/// order as `names`.
+pub fn latency_gnuplot_script(data_file: &str, names: &[String]) -> String {
+    let plots = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            format!(
+                "'{}' index {} using 1:3 with impulses title '{}'",
+                data_file,
+                index,
+                // Single quotes are escaped by doubling them.
+                name.replace('\'', "''")
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", \\\n    ");
+
+    format!(
+        r#"set terminal pngcairo size 1000,400
+set output 'latency.png'
+set title 'Swanling Latency Distribution'
+set xlabel 'Response time (ms)'
+set ylabel 'Responses (%)'
+set logscale x
+plot {plots}
+"#,
+        plots = plots
+    )
+}
+
+/// Build the html latency distribution chart report.
+pub fn build_chart_report(start_time: &str, end_time: &str, host: &str, charts: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <title>Swanling Latency Distribution</title>
+    <style>
+        .container {{
+            width: 1000px;
+            margin: 0 auto;
+            padding: 10px;
+            background: #173529;
+            font-family: Arial, Helvetica, sans-serif;
+            font-size: 14px;
+            color: #fff;
+        }}
+
+        .info span{{
+            color: #b3c3bc;
+        }}
+
+        .chart svg {{
+            background: #fff;
+            margin-bottom: 30px;
+        }}
+
+        .chart text {{
+            fill: #666;
+            font-size: 12px;
+        }}
+
+        .chart rect {{
+            fill: #00ca5a;
+        }}
+
+        .chart .grid {{
+            stroke: #cad9ea;
+        }}
+
+        .chart .axis {{
+            stroke: #666;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>Swanling Latency Distribution</h1>
+
+        <div class="info">
+            <p>During: <span>{start_time} - {end_time}</span></p>
+            <p>Target Host: <span>{host}</span></p>
+        </div>
+
+        <div class="charts-container">
+        {charts}
+        </div>
+
+    </div>
+</body>
+</html>"#,
+        start_time = start_time,
+        end_time = end_time,
+        host = host,
+        charts = charts,
+    )
+}
+
+/// Escape the characters that are not allowed in XML attributes.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Build a JUnit XML report, with a single testsuite containing each testcase.
+pub fn build_junit_xml(suite: &str, time: usize, test_cases: &[JunitTestCase]) -> String {
+    let failures = test_cases
+        .iter()
+        .filter(|test_case| test_case.failure.is_some())
+        .count();
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" time=\"{}\">\n",
+        xml_escape(suite),
+        test_cases.len(),
+        failures,
+        time
+    );
+    for test_case in test_cases {
+        xml.push_str(&format!(
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+            xml_escape(&test_case.classname),
+            xml_escape(&test_case.name),
+            test_case.time
+        ));
+        match &test_case.failure {
+            Some(failure) => xml.push_str(&format!(
+                ">\n      <failure message=\"{}\"/>\n    </testcase>\n",
+                xml_escape(failure)
+            )),
+            None => xml.push_str("/>\n"),
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
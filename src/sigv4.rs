@@ -0,0 +1,340 @@
+//! Optionally signs requests with AWS Signature Version 4.
+//!
+//! When the `--aws-region` run-time option is set, each request a
+//! [`SwanlingUser`](../swanling/struct.SwanlingUser.html) makes is signed right before it
+//! is sent, so load tests can target IAM protected AWS endpoints such as API Gateway or
+//! S3. The credentials are read from `--aws-access-key-id`, `--aws-secret-access-key` and
+//! `--aws-session-token`, or from the standard `AWS_ACCESS_KEY_ID`,
+//! `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN` environment variables.
+
+use chrono::prelude::*;
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::Request;
+use sha2::{Digest, Sha256};
+
+use crate::SwanlingConfiguration;
+
+/// The signing algorithm, included in the string to sign and the Authorization header.
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// The payload hash used when the body is streamed, so can't be hashed before it's sent.
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Environment variable holding the access key id, if not set with `--aws-access-key-id`.
+pub(crate) const ACCESS_KEY_ID_ENV: &str = "AWS_ACCESS_KEY_ID";
+
+/// Environment variable holding the secret access key, if not set with
+/// `--aws-secret-access-key`.
+pub(crate) const SECRET_ACCESS_KEY_ENV: &str = "AWS_SECRET_ACCESS_KEY";
+
+/// Environment variable holding the session token, if not set with `--aws-session-token`.
+pub(crate) const SESSION_TOKEN_ENV: &str = "AWS_SESSION_TOKEN";
+
+/// Response header in which AWS returns the type of error that rejected a request.
+pub(crate) const ERROR_TYPE_HEADER: &str = "x-amzn-errortype";
+
+/// Error types AWS returns when a request is rejected because of its signature, its
+/// credentials, or because the clock of the load test is skewed.
+const SIGNING_ERROR_TYPES: [&str; 7] = [
+    "IncompleteSignatureException",
+    "InvalidSignatureException",
+    "SignatureDoesNotMatch",
+    "UnrecognizedClientException",
+    "InvalidClientTokenId",
+    "ExpiredTokenException",
+    "RequestTimeTooSkewed",
+];
+
+/// The region, service and credentials requests are signed with.
+pub(crate) struct SigV4<'a> {
+    region: &'a str,
+    service: &'a str,
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    session_token: &'a str,
+}
+impl<'a> SigV4<'a> {
+    /// Returns `None` unless requests are to be signed.
+    pub(crate) fn from_config(config: &'a SwanlingConfiguration) -> Option<Self> {
+        if config.aws_region.is_empty() {
+            return None;
+        }
+        Some(SigV4 {
+            region: &config.aws_region,
+            service: &config.aws_service,
+            access_key_id: &config.aws_access_key_id,
+            secret_access_key: &config.aws_secret_access_key,
+            session_token: &config.aws_session_token,
+        })
+    }
+
+    /// Sign the request as of `now`, adding the `X-Amz-Date` and `Authorization` headers
+    /// (and `X-Amz-Security-Token` and `X-Amz-Content-Sha256` when needed).
+    pub(crate) fn sign(&self, request: &mut Request, now: DateTime<Utc>) -> Result<(), String> {
+        if self.access_key_id.is_empty() || self.secret_access_key.is_empty() {
+            return Err("no AWS credentials".to_string());
+        }
+        let host = match request.url().host_str() {
+            Some(host) => match request.url().port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            },
+            None => return Err(format!("no host in {}", request.url())),
+        };
+
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let payload_hash = match request.body() {
+            Some(body) => match body.as_bytes() {
+                Some(bytes) => hex_sha256(bytes),
+                None => UNSIGNED_PAYLOAD.to_string(),
+            },
+            None => hex_sha256(b""),
+        };
+
+        // Headers added by the signature are signed along with the host.
+        let mut added_headers = vec![("x-amz-date", amz_date.to_string())];
+        if !self.session_token.is_empty() {
+            added_headers.push(("x-amz-security-token", self.session_token.to_string()));
+        }
+        // S3 requires the payload hash as a header.
+        if self.service == "s3" {
+            added_headers.push(("x-amz-content-sha256", payload_hash.to_string()));
+        }
+        let mut signed_headers = vec![("host", host)];
+        signed_headers.extend(added_headers.iter().cloned());
+        signed_headers.sort();
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            request.method().as_str(),
+            canonical_uri(request.url().path(), self.service != "s3"),
+            canonical_query(request.url()),
+            signed_headers
+                .iter()
+                .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+                .collect::<String>(),
+            signed_header_names(&signed_headers),
+            payload_hash
+        );
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            ALGORITHM,
+            amz_date,
+            scope,
+            hex_sha256(canonical_request.as_bytes())
+        );
+
+        let signing_key = [self.region, self.service, "aws4_request"].iter().fold(
+            hmac_sha256(
+                format!("AWS4{}", self.secret_access_key).as_bytes(),
+                date.as_bytes(),
+            ),
+            |key, part| hmac_sha256(&key, part.as_bytes()),
+        );
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            ALGORITHM,
+            self.access_key_id,
+            scope,
+            signed_header_names(&signed_headers),
+            signature
+        );
+
+        for (name, value) in added_headers {
+            let value = HeaderValue::from_str(&value).map_err(|e| e.to_string())?;
+            request
+                .headers_mut()
+                .insert(HeaderName::from_static(name), value);
+        }
+        let authorization = HeaderValue::from_str(&authorization).map_err(|e| e.to_string())?;
+        request.headers_mut().insert(AUTHORIZATION, authorization);
+
+        Ok(())
+    }
+}
+
+/// Returns the error type of a response that AWS rejected because of how it was signed,
+/// from the value of its `X-Amzn-ErrorType` header.
+pub(crate) fn signing_error_type(error_type: &str) -> Option<&str> {
+    // The error type can be followed by a colon and a link to its documentation.
+    let error_type = error_type.split(':').next().unwrap_or_default().trim();
+    if SIGNING_ERROR_TYPES.contains(&error_type) {
+        Some(error_type)
+    } else {
+        None
+    }
+}
+
+// The path, with each segment URI encoded. Services other than S3 encode it twice.
+fn canonical_uri(path: &str, double_encode: bool) -> String {
+    if path.is_empty() {
+        return "/".to_string();
+    }
+    path.split('/')
+        .map(|segment| {
+            let encoded = uri_encode(&percent_decode(segment), true);
+            if double_encode {
+                uri_encode(&encoded, true)
+            } else {
+                encoded
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+// The query parameters, URI encoded and sorted by name then value.
+fn canonical_query(url: &url::Url) -> String {
+    let mut parameters: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| (uri_encode(&name, true), uri_encode(&value, true)))
+        .collect();
+    parameters.sort();
+    parameters
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+fn signed_header_names(headers: &[(&str, String)]) -> String {
+    headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<&str>>()
+        .join(";")
+}
+
+// Percent encode everything except unreserved characters, as AWS requires.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let digits = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+            if let Ok(byte) = u8::from_str_radix(digits, 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex_sha256(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use reqwest::Method;
+
+    fn signer<'a>(service: &'a str, session_token: &'a str) -> SigV4<'a> {
+        SigV4 {
+            region: "us-east-1",
+            service,
+            access_key_id: "AKIDEXAMPLE",
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            session_token,
+        }
+    }
+
+    #[test]
+    fn get_vanilla() {
+        // The get-vanilla case from the AWS Signature Version 4 test suite.
+        let mut request = Request::new(
+            Method::GET,
+            url::Url::parse("https://example.amazonaws.com/").unwrap(),
+        );
+        let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+        signer("service", "").sign(&mut request, now).unwrap();
+
+        assert_eq!(request.headers()["x-amz-date"], "20150830T123600Z");
+        assert_eq!(
+            request.headers()[AUTHORIZATION],
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn signed_headers() {
+        let mut request = Request::new(
+            Method::GET,
+            url::Url::parse("http://bucket.example.com:8080/a%20b?z=1&a=2").unwrap(),
+        );
+        let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+        signer("s3", "token").sign(&mut request, now).unwrap();
+
+        assert_eq!(request.headers()["x-amz-security-token"], "token");
+        assert_eq!(request.headers()["x-amz-content-sha256"], hex_sha256(b""));
+        assert!(request.headers()[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token,"));
+
+        // Missing credentials are a signing failure.
+        let mut unsigned = signer("s3", "");
+        unsigned.secret_access_key = "";
+        assert!(unsigned.sign(&mut request, now).is_err());
+    }
+
+    #[test]
+    fn canonical_components() {
+        assert_eq!(canonical_uri("", true), "/");
+        assert_eq!(canonical_uri("/a%20b/c", false), "/a%20b/c");
+        assert_eq!(canonical_uri("/a%20b/c", true), "/a%2520b/c");
+        assert_eq!(
+            canonical_query(&url::Url::parse("http://example.com/?z=1&a=b+c&a=a").unwrap()),
+            "a=a&a=b%20c&z=1"
+        );
+        assert_eq!(uri_encode("a/b~*", false), "a/b~%2A");
+    }
+
+    #[test]
+    fn signing_error_types() {
+        assert_eq!(
+            signing_error_type(
+                "InvalidSignatureException:http://internal.amazon.com/coral/com.amazon.coral.service/"
+            ),
+            Some("InvalidSignatureException")
+        );
+        assert_eq!(
+            signing_error_type("RequestTimeTooSkewed"),
+            Some("RequestTimeTooSkewed")
+        );
+        assert_eq!(signing_error_type("AccessDeniedException"), None);
+    }
+}
@@ -66,6 +66,26 @@
 //! let mut foo_tasks = taskset!("FooTasks").set_wait_time(0, 3).unwrap();
 //! let mut bar_tasks = taskset!("BarTasks").set_wait_time(5, 10).unwrap();
 //! ```
+//!
+//! ### User Classes
+//!
+//! Each task set defines a class of users: its weight controls how many users of the
+//! class are launched, and its tasks, wait time, host and headers are used by all of
+//! them. In the following example, 80% of users browse anonymously while 20% are logged
+//! in, sending an `Authorization` header with every request:
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! fn main() -> Result<(), SwanlingError> {
+//!     let mut anonymous_tasks = taskset!("Anonymous").set_weight(8)?;
+//!     let mut logged_in_tasks = taskset!("LoggedIn")
+//!         .set_weight(2)?
+//!         .set_header("Authorization", "Bearer secret-token")?;
+//!
+//!     Ok(())
+//! }
+//! ```
 //! ## Creating Tasks
 //!
 //! A [`SwanlingTask`](./struct.SwanlingTask.html) must include a pointer to a function which
@@ -214,6 +234,28 @@
 //! }
 //! ```
 //!
+//! ### Task Wait Time
+//!
+//! Tasks can override the wait time of their task set, pausing for a random number of seconds
+//! inclusively between their own low and high wait times after they complete. Tasks without
+//! their own wait time use the wait time of their task set. In the following example, users
+//! sleep 10 to 30 seconds after loading an article, and 0 to 3 seconds after other tasks.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut foo_tasks = taskset!("FooTasks")
+//!     .set_wait_time(0, 3).unwrap()
+//!     .register_task(task!(article_task_function).set_wait_time(10, 30).unwrap());
+//!
+//! /// A very simple task that loads an article.
+//! async fn article_task_function(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let _swanling = user.get("/article/").await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! ## Controlling User
 //!
 //! When Swanling starts, it creates one or more [`SwanlingUser`](./struct.SwanlingUser.html)s,
@@ -268,6 +310,88 @@
 //! }
 //! ```
 //!
+//! ### Correlation
+//!
+//! Values returned by the server, such as a session token or the id of a newly created
+//! item, can be extracted from a response with
+//! [`extract`](./struct.SwanlingUser.html#method.extract) and read back in later tasks
+//! with [`get_session_value`](./struct.SwanlingUser.html#method.get_session_value).
+//! Values are stored per user. If a value can't be extracted the task fails.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut task = task!(login);
+//!
+//! /// Log in, then use the returned token in a later request.
+//! async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let swanling = user.post("/login", "user=foo&pass=bar").await?;
+//!     user.extract(
+//!         swanling,
+//!         &[
+//!             ("token", SwanlingExtractor::Json("/token".to_string())),
+//!             ("session", SwanlingExtractor::Header("x-session".to_string())),
+//!         ],
+//!     )
+//!     .await?;
+//!
+//!     let token = user.get_session_value("token").await.unwrap_or_default();
+//!     let _swanling = user.get(&format!("/account?token={}", token)).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Response Validation
+//!
+//! By default any response with a 2xx status code is a success. To also confirm that a
+//! JSON response has the expected shape, deserialize it into your own type with
+//! [`deserialize`](./struct.SwanlingUser.html#method.deserialize). If it doesn't match,
+//! the request is marked as a failure and the task fails.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use swanling::prelude::*;
+//!
+//! #[derive(Deserialize)]
+//! struct Status {
+//!     healthy: bool,
+//! }
+//!
+//! let mut task = task!(check_status);
+//!
+//! async fn check_status(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let swanling = user.get("/status").await?;
+//!     let _status: Status = user.deserialize(swanling).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Some servers signal a degraded response with a header while still returning a 2xx
+//! status code. Declare which headers make a response a failure, or only tag it in a
+//! custom counter, with [`check_headers`](./struct.SwanlingUser.html#method.check_headers).
+//! The response isn't consumed, so its body can still be validated afterward.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut task = task!(check_status);
+//!
+//! async fn check_status(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let mut swanling = user.get("/status").await?;
+//!     user.check_headers(
+//!         &mut swanling,
+//!         &[(
+//!             SwanlingHeaderCheck::Present("x-degraded".to_string()),
+//!             SwanlingHeaderOutcome::Fail("degraded".to_string()),
+//!         )],
+//!     )?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! ## License
 //!
 //! Copyright 2020 Jeremy Andrews
@@ -284,26 +408,108 @@
 //! See the License for the specific language governing permissions and
 //! limitations under the License.
 
+use chrono::Utc;
+use futures::TryStreamExt;
 use http::method::Method;
-use reqwest::{header, Client, ClientBuilder, RequestBuilder, Response};
+use hyper::client::connect::dns::Name;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::redirect;
+use reqwest::{
+    header, Body, Certificate, Client, ClientBuilder, Identity, RequestBuilder, Response,
+};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ops::{Bound, RangeBounds};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::{future::Future, pin::Pin, time::Instant};
-use tokio::sync::{Mutex, RwLock};
+use tokio::io::AsyncRead;
+use tokio::sync::{Mutex, RwLock, Semaphore};
+use tokio_util::io::ReaderStream;
 use url::Url;
 
 use crate::logger::SwanlingLog;
 use crate::metrics::{
-    SwanlingCoordinatedOmissionMitigation, SwanlingMetric, SwanlingRequestMetric,
+    SwanlingCoordinatedOmissionMitigation, SwanlingCustomMetric, SwanlingMetric,
+    SwanlingRequestMetric,
 };
+use crate::sigv4::{self, SigV4};
+use crate::util;
 use crate::{SwanlingConfiguration, SwanlingError, WeightedSwanlingTasks};
 
 /// By default Swanling sets the following User-Agent header when making requests.
 static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// Prefix of the error recorded when a request fails while negotiating a TLS connection.
+pub const TLS_HANDSHAKE_ERROR: &str = "TLS handshake failed";
+
+/// Prefix of the error recorded when a request fails because a connection couldn't be
+/// made, for example because it was refused.
+pub const CONNECTION_ERROR: &str = "Connection failed";
+
+/// Prefix of the error recorded when a request times out.
+pub const TIMEOUT_ERROR: &str = "Request timed out";
+
+/// Prefix of the error recorded when a request fails because an OAuth2 access token
+/// could not be obtained.
+pub const OAUTH_TOKEN_ERROR: &str = "OAuth token request failed";
+
+/// Prefix of the error recorded when a request can't be signed with AWS SigV4, or AWS
+/// rejects its signature, for example because the clock of the load test is skewed.
+pub const SIGV4_ERROR: &str = "AWS SigV4 signing failed";
+
+/// Prefix of the error recorded when a response body doesn't deserialize into the
+/// expected type.
+pub const INVALID_RESPONSE_ERROR: &str = "invalid response body";
+
+/// Prefix of the error recorded when a GraphQL response includes errors, even if its
+/// status code was 2xx.
+pub const GRAPHQL_ERROR: &str = "GraphQL error";
+
+/// Prefix of the error recorded when the size of a response body falls outside the range
+/// set with [`expect_size`](./trait.SwanlingRequestExpectSize.html#tymethod.expect_size).
+pub const RESPONSE_SIZE_ERROR: &str = "unexpected response size";
+
+/// Prefix of the error recorded when the content type of a response differs from the one
+/// set with [`expect_content_type`](./trait.SwanlingRequestExpectContentType.html#tymethod.expect_content_type).
+pub const CONTENT_TYPE_ERROR: &str = "content-type mismatch";
+
+/// Label recording the region that served each request when `--region-header` is set.
+pub const REGION_LABEL: &str = "region";
+
+/// OAuth2 access tokens are refreshed this many seconds before they expire.
+const OAUTH_REFRESH_MARGIN: u64 = 30;
+
+/// At most this many bytes of a failed response body are captured in the debug log
+/// when `--capture-failure-bodies` is enabled.
+pub const CAPTURE_FAILURE_BODY_LIMIT: usize = 64 * 1024;
+
+/// Labels are carried from the request builder to Swanling in headers starting with this
+/// prefix, which are removed before the request is made.
+const LABEL_HEADER_PREFIX: &str = "x-swanling-label-";
+
+/// The expected response size is carried from the request builder to Swanling in this
+/// header, which is removed before the request is made.
+const EXPECT_SIZE_HEADER: &str = "x-swanling-expect-size";
+
+/// The expected response content type is carried from the request builder to Swanling in
+/// this header, which is removed before the request is made.
+const EXPECT_CONTENT_TYPE_HEADER: &str = "x-swanling-expect-content-type";
+
+/// The status codes expected in response are carried from the request builder to Swanling
+/// in this header, which is removed before the request is made.
+const EXPECT_STATUS_HEADER: &str = "x-swanling-expect-status";
+
+/// How much a request counts toward the request mix is carried from the request builder
+/// to Swanling in this header, which is removed before the request is made.
+const MIX_WEIGHT_HEADER: &str = "x-swanling-mix-weight";
+
 /// `task!(foo)` expands to `SwanlingTask::new(foo)`, but also does some boxing to work around a limitation in the compiler.
 #[macro_export]
 macro_rules! task {
@@ -322,6 +528,31 @@ macro_rules! taskset {
     };
 }
 
+/// Controls what a [`SwanlingUser`](./struct.SwanlingUser.html) runs after the current
+/// task, set with
+/// [`SwanlingUser::set_task_control`](./struct.SwanlingUser.html#method.set_task_control).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwanlingTaskControl {
+    /// Run the next task as scheduled (default).
+    Continue,
+    /// Skip the rest of the tasks, starting over with the first. During `on_start` or
+    /// `on_stop` tasks, skip the rest of them.
+    SkipRest,
+    /// Stop the user after running its `on_stop` tasks. During `on_start` tasks, the user
+    /// doesn't run any normal tasks.
+    AbortUser,
+}
+impl SwanlingTaskControl {
+    // Convert a value stored with `as usize` back into a SwanlingTaskControl.
+    fn from_usize(value: usize) -> Self {
+        match value {
+            1 => SwanlingTaskControl::SkipRest,
+            2 => SwanlingTaskControl::AbortUser,
+            _ => SwanlingTaskControl::Continue,
+        }
+    }
+}
+
 /// Swanling tasks return a result, which is empty on success, or contains a
 /// [`SwanlingTaskError`](./enum.SwanlingTaskError.html) on error.
 pub type SwanlingTaskResult = Result<(), SwanlingTaskError>;
@@ -362,6 +593,38 @@ pub enum SwanlingTaskError {
         /// The unrecognized HTTP request method.
         method: Method,
     },
+    /// Failed to extract a value from a response.
+    ExtractionFailed {
+        /// Wraps a [`SwanlingExtractError`](./struct.SwanlingExtractError.html).
+        source: SwanlingExtractError,
+    },
+    /// Failed to deserialize a response body into the expected type.
+    DeserializeFailed {
+        /// Wraps a [`SwanlingDeserializeError`](./struct.SwanlingDeserializeError.html).
+        source: SwanlingDeserializeError,
+    },
+    /// A response header check marked the request as a failure.
+    HeaderCheckFailed {
+        /// Wraps a [`SwanlingHeaderError`](./struct.SwanlingHeaderError.html).
+        source: SwanlingHeaderError,
+    },
+    /// Failed to obtain an OAuth2 access token, so the request was not made.
+    OAuthTokenFailed {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that was not made.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// Failed to sign the request with AWS SigV4, so the request was not made.
+    SigningFailed {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that was not made.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// No value was published with
+    /// [`set_shared_value`](./struct.SwanlingUser.html#method.set_shared_value) before
+    /// the timeout elapsed.
+    SharedValueTimeout {
+        /// The key of the value that wasn't published.
+        key: String,
+    },
 }
 /// Implement a helper to provide a text description of all possible types of errors.
 impl SwanlingTaskError {
@@ -376,6 +639,14 @@ impl SwanlingTaskError {
             SwanlingTaskError::MetricsFailed { .. } => "failed to send metrics to parent thread",
             SwanlingTaskError::LoggerFailed { .. } => "failed to send log message to logger thread",
             SwanlingTaskError::InvalidMethod { .. } => "unrecognized HTTP request method",
+            SwanlingTaskError::ExtractionFailed { .. } => "failed to extract value from response",
+            SwanlingTaskError::DeserializeFailed { .. } => "failed to deserialize response body",
+            SwanlingTaskError::HeaderCheckFailed { .. } => "response header check failed",
+            SwanlingTaskError::OAuthTokenFailed { .. } => "failed to obtain OAuth access token",
+            SwanlingTaskError::SigningFailed { .. } => "failed to sign request with AWS SigV4",
+            SwanlingTaskError::SharedValueTimeout { .. } => {
+                "timed out waiting for shared value to be published"
+            }
         }
     }
 }
@@ -400,6 +671,27 @@ impl fmt::Display for SwanlingTaskError {
             SwanlingTaskError::LoggerFailed { ref source } => {
                 write!(f, "SwanlingTaskError: {} ({})", self.describe(), source)
             }
+            SwanlingTaskError::ExtractionFailed { ref source } => {
+                write!(f, "SwanlingTaskError: {} ({})", self.describe(), source)
+            }
+            SwanlingTaskError::DeserializeFailed { ref source } => {
+                write!(f, "SwanlingTaskError: {} ({})", self.describe(), source)
+            }
+            SwanlingTaskError::HeaderCheckFailed { ref source } => {
+                write!(f, "SwanlingTaskError: {} ({})", self.describe(), source)
+            }
+            SwanlingTaskError::OAuthTokenFailed { ref raw_request }
+            | SwanlingTaskError::SigningFailed { ref raw_request } => {
+                write!(
+                    f,
+                    "SwanlingTaskError: {} ({})",
+                    self.describe(),
+                    raw_request.error
+                )
+            }
+            SwanlingTaskError::SharedValueTimeout { ref key } => {
+                write!(f, "SwanlingTaskError: {} ({})", self.describe(), key)
+            }
             _ => write!(f, "SwanlingTaskError: {}", self.describe()),
         }
     }
@@ -414,6 +706,9 @@ impl std::error::Error for SwanlingTaskError {
             SwanlingTaskError::RequestCanceled { ref source } => Some(source),
             SwanlingTaskError::MetricsFailed { ref source } => Some(source),
             SwanlingTaskError::LoggerFailed { ref source } => Some(source),
+            SwanlingTaskError::ExtractionFailed { ref source } => Some(source),
+            SwanlingTaskError::DeserializeFailed { ref source } => Some(source),
+            SwanlingTaskError::HeaderCheckFailed { ref source } => Some(source),
             _ => None,
         }
     }
@@ -458,6 +753,27 @@ impl From<flume::SendError<Option<SwanlingLog>>> for SwanlingTaskError {
     }
 }
 
+/// Attempt to extract a value from a response failed.
+impl From<SwanlingExtractError> for SwanlingTaskError {
+    fn from(source: SwanlingExtractError) -> SwanlingTaskError {
+        SwanlingTaskError::ExtractionFailed { source }
+    }
+}
+
+/// Attempt to deserialize a response body failed.
+impl From<SwanlingDeserializeError> for SwanlingTaskError {
+    fn from(source: SwanlingDeserializeError) -> SwanlingTaskError {
+        SwanlingTaskError::DeserializeFailed { source }
+    }
+}
+
+/// A response header check failed.
+impl From<SwanlingHeaderError> for SwanlingTaskError {
+    fn from(source: SwanlingHeaderError) -> SwanlingTaskError {
+        SwanlingTaskError::HeaderCheckFailed { source }
+    }
+}
+
 /// An individual task set.
 #[derive(Clone, Hash)]
 pub struct SwanlingTaskSet {
@@ -488,6 +804,14 @@ pub struct SwanlingTaskSet {
     pub weighted_on_stop_tasks: WeightedSwanlingTasks,
     /// An optional default host to run this `SwanlingTaskSet` against.
     pub host: Option<String>,
+    /// Headers sent with every request made by users running this `SwanlingTaskSet`.
+    pub headers: Vec<(header::HeaderName, header::HeaderValue)>,
+    /// The paths of the client certificate and private key presented by users running
+    /// this `SwanlingTaskSet`, in place of `--client-cert` and `--client-key`.
+    pub client_identity: Option<(String, String)>,
+    /// The paths of certificate authorities trusted by users running this
+    /// `SwanlingTaskSet`, in addition to `--ca-cert`.
+    pub ca_certs: Vec<String>,
 }
 impl SwanlingTaskSet {
     /// Creates a new [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html). Once created, a
@@ -514,6 +838,9 @@ impl SwanlingTaskSet {
             weighted_on_start_tasks: Vec::new(),
             weighted_on_stop_tasks: Vec::new(),
             host: None,
+            headers: Vec::new(),
+            client_identity: None,
+            ca_certs: Vec::new(),
         }
     }
 
@@ -589,6 +916,116 @@ impl SwanlingTaskSet {
         self
     }
 
+    /// Set a header sent with every request made by users running this task set, for
+    /// example to authenticate one class of users. Headers set on an individual request
+    /// take precedence. Setting the same header more than once replaces its value.
+    ///
+    /// Environment variables referenced as `${NAME}` in the value are expanded, so secrets
+    /// don't need to appear in the load test, failing if a referenced variable is not set.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut example_tasks = taskset!("ExampleTasks")
+    ///         .set_header("Authorization", "Bearer secret-token")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_header(mut self, name: &str, value: &str) -> Result<Self, SwanlingError> {
+        // The value isn't logged, it may be a secret.
+        trace!("{} set_header: {}", self.name, name);
+        let header_name = header::HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            SwanlingError::InvalidOption {
+                option: "set_header".to_string(),
+                value: name.to_string(),
+                detail: "Invalid header name.".to_string(),
+            }
+        })?;
+        let expanded_value =
+            util::expand_env_vars(value).map_err(|detail| SwanlingError::InvalidOption {
+                option: "set_header".to_string(),
+                value: value.to_string(),
+                detail,
+            })?;
+        let header_value = header::HeaderValue::from_str(&expanded_value).map_err(|_| {
+            SwanlingError::InvalidOption {
+                option: "set_header".to_string(),
+                value: value.to_string(),
+                detail: format!("Invalid value for the {} header.", name),
+            }
+        })?;
+        self.headers
+            .retain(|(existing, _)| *existing != header_name);
+        self.headers.push((header_name, header_value));
+
+        Ok(self)
+    }
+
+    /// Set the PEM-encoded client certificate and private key presented by users running
+    /// this task set when the server requests one, so different classes of users can
+    /// authenticate with mutual TLS as different clients. This replaces `--client-cert`
+    /// and `--client-key` for the class. TLS handshake failures are recorded with the
+    /// certificate that was presented, so they can be told apart in the error summary.
+    ///
+    /// The files are read when the task set is configured, and again by each user, so
+    /// in a Regatta they must also be present on every Worker.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut example_tasks = taskset!("PartnerA")
+    ///         .set_client_identity("partner-a.pem", "partner-a-key.pem")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_client_identity(mut self, cert: &str, key: &str) -> Result<Self, SwanlingError> {
+        trace!("{} set_client_identity: {} {}", self.name, cert, key);
+        let invalid = |value: &str, detail: String| SwanlingError::InvalidOption {
+            option: "set_client_identity".to_string(),
+            value: value.to_string(),
+            detail,
+        };
+        let cert_pem = std::fs::read(cert)
+            .map_err(|e| invalid(cert, format!("Unable to read client certificate: {}", e)))?;
+        let key_pem = std::fs::read(key)
+            .map_err(|e| invalid(key, format!("Unable to read private key: {}", e)))?;
+        client_identity(&cert_pem, &key_pem)
+            .map_err(|e| invalid(cert, format!("Invalid client identity: {}", e)))?;
+        self.client_identity = Some((cert.to_string(), key.to_string()));
+
+        Ok(self)
+    }
+
+    /// Trust the PEM-encoded certificate authorities in a file for users running this
+    /// task set, in addition to any trusted with `--ca-cert`. The file can be a bundle of
+    /// several certificates, and can be added more than once to trust several files.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut example_tasks = taskset!("PartnerA").add_ca_cert("partner-a-ca.pem")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn add_ca_cert(mut self, ca_cert: &str) -> Result<Self, SwanlingError> {
+        trace!("{} add_ca_cert: {}", self.name, ca_cert);
+        read_ca_certs("add_ca_cert", ca_cert)?;
+        if !self.ca_certs.iter().any(|existing| existing == ca_cert) {
+            self.ca_certs.push(ca_cert.to_string());
+        }
+
+        Ok(self)
+    }
+
     /// Configure a task_set to to pause after running each task. The length of the pause will be randomly
     /// selected from `min_weight` to `max_wait` inclusively.  For example, if `min_wait` is `0` and
     /// `max_weight` is `2`, the user will randomly sleep for 0, 1 or 2 seconds after each task completes.
@@ -634,12 +1071,58 @@ impl SwanlingTaskSet {
 /// worker processes.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SwanlingUserCommand {
-    /// Tell worker process to pause load test.
+    /// Tell user thread or worker process to pause load test.
     Wait,
-    /// Tell worker process to start load test.
+    /// Tell user thread or worker process to start load test.
     Run,
     /// Tell user thread or worker process to exit.
     Exit,
+    /// Tell worker process to apply configuration changes while the load test keeps
+    /// running.
+    Reconfigure(SwanlingReconfiguration),
+    /// Send worker process all values published with
+    /// [`SwanlingUser::set_shared_value`](./struct.SwanlingUser.html#method.set_shared_value).
+    SharedValues(BTreeMap<String, String>),
+    /// Tell worker process to send the most recent lines of its `--request-log` to the
+    /// manager.
+    SendRequestLog,
+}
+
+/// Changes to the configuration of a running load test, sent from the manager to all
+/// worker processes with
+/// [`SwanlingUserCommand::Reconfigure`](./enum.SwanlingUserCommand.html#variant.Reconfigure).
+/// Only options that can be changed without restarting the load test are included, and
+/// options set to `None` are left unchanged, as are options missing from the message.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SwanlingReconfiguration {
+    /// The maximum requests per second, only changed if the throttle was enabled with
+    /// `--throttle-requests` when the load test started.
+    pub throttle_requests: Option<usize>,
+    /// Whether users pause instead of starting their next task.
+    pub paused: Option<bool>,
+    /// How many users to run, launching or stopping users as needed. In a Regatta this is
+    /// the number of users across all Workers, each running its share.
+    pub users: Option<usize>,
+    /// Whether to discard all metrics not yet pushed to the manager, sent after the
+    /// `reset-metrics` Controller command reset the manager's metrics. Only sent to each
+    /// worker once, so it isn't merged.
+    pub reset_metrics: bool,
+}
+impl SwanlingReconfiguration {
+    /// Apply changes from a newer reconfiguration on top of this one.
+    #[cfg(feature = "gaggle")]
+    pub(crate) fn merge(&mut self, newer: &SwanlingReconfiguration) {
+        if newer.throttle_requests.is_some() {
+            self.throttle_requests = newer.throttle_requests;
+        }
+        if newer.paused.is_some() {
+            self.paused = newer.paused;
+        }
+        if newer.users.is_some() {
+            self.users = newer.users;
+        }
+    }
 }
 
 /// Supported HTTP methods.
@@ -683,94 +1166,686 @@ pub fn swanling_method_from_method(method: Method) -> Result<SwanlingMethod, Swa
     })
 }
 
-/// The response to a SwanlingRequest
-#[derive(Debug)]
-pub struct SwanlingResponse {
-    /// The request that this is a response to.
-    pub request: SwanlingRequestMetric,
-    /// The response.
-    pub response: Result<Response, reqwest::Error>,
+/// Build the JSON body of a GraphQL request, made up of the operation name, the query
+/// document and its variables.
+///
+/// # Example
+/// ```rust
+/// use swanling::swanling::graphql_body;
+///
+/// let body = graphql_body(
+///     "GetItem",
+///     "query GetItem($id: ID!) { item(id: $id) { name } }",
+///     serde_json::json!({ "id": 1 }),
+/// );
+/// assert_eq!(body["operationName"], "GetItem");
+/// ```
+pub fn graphql_body(
+    operation_name: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> serde_json::Value {
+    serde_json::json!({
+        "operationName": operation_name,
+        "query": query,
+        "variables": variables,
+    })
 }
-impl SwanlingResponse {
-    pub fn new(request: SwanlingRequestMetric, response: Result<Response, reqwest::Error>) -> Self {
-        SwanlingResponse { request, response }
+
+/// Labels requests with arbitrary key-value pairs, so their metrics can be grouped
+/// across request names with the `--group-by` run-time option.
+///
+/// Labels are recorded in the
+/// [`labels`](../metrics/struct.SwanlingRequestMetric.html#structfield.labels) of each
+/// request metric. Keys are case insensitive and recorded in lower case, and must be
+/// valid in an HTTP header name. If the same key is set more than once, the last value
+/// is recorded. Labels are never sent to the server.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// let mut task = task!(get_function);
+///
+/// /// A simple task that makes a GET request labeled with the region it targets.
+/// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let request_builder = user
+///         .swanling_get("/path/to/foo")
+///         .await?
+///         .label("region", "eu")
+///         .label("tier", "premium");
+///     let _swanling = user.swanling_send(request_builder, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub trait SwanlingRequestLabel {
+    /// Label the request with a value for the key.
+    fn label(self, key: &str, value: &str) -> Self;
+}
+impl SwanlingRequestLabel for RequestBuilder {
+    fn label(self, key: &str, value: &str) -> Self {
+        self.header(format!("{}{}", LABEL_HEADER_PREFIX, key), value)
     }
 }
 
-/// Object created by [`log_debug()`](struct.SwanlingUser.html#method.log_debug) and written
-/// to log to assist in debugging.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct SwanlingDebug {
-    /// String to identify the source of the log message.
-    pub tag: String,
-    /// Optional request made.
-    pub request: Option<SwanlingRequestMetric>,
-    /// Optional headers returned by server.
-    pub header: Option<String>,
-    /// Optional body text returned by server.
-    pub body: Option<String>,
-}
-impl SwanlingDebug {
-    fn new(
-        tag: &str,
-        request: Option<&SwanlingRequestMetric>,
-        header: Option<&header::HeaderMap>,
-        body: Option<&str>,
-    ) -> Self {
-        SwanlingDebug {
-            // Convert tag from &str to string.
-            tag: tag.to_string(),
-            // If request is defined, clone it.
-            request: request.cloned(),
-            // If header is defined, convert it to a string.
-            header: header.map(|h| format!("{:?}", h)),
-            // If header is defined, convert from &str to string.
-            body: body.map(|b| b.to_string()),
+/// Remove the headers carrying labels from a request, returning the labels.
+fn take_labels(headers: &mut header::HeaderMap) -> BTreeMap<String, String> {
+    let names: Vec<header::HeaderName> = headers
+        .keys()
+        .filter(|name| name.as_str().starts_with(LABEL_HEADER_PREFIX))
+        .cloned()
+        .collect();
+    let mut labels = BTreeMap::new();
+    for name in names {
+        if let Some(value) = headers.get_all(&name).iter().next_back() {
+            labels.insert(
+                name.as_str()[LABEL_HEADER_PREFIX.len()..].to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            );
         }
+        headers.remove(&name);
     }
+    labels
 }
 
-/// The elements needed to build an individual user state on a Regatta Worker.
-#[derive(Debug, Clone)]
-pub struct GaggleUser {
-    /// An index into the internal [`SwanlingAttack`](../struct.SwanlingAttack.html)`.task_sets`
-    /// vector, indicating which [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html) is running.
-    pub task_sets_index: usize,
-    /// The base URL to prepend to all relative paths.
-    pub base_url: Arc<RwLock<Url>>,
-    /// Minimum amount of time to sleep after running a task.
-    pub min_wait: usize,
-    /// Maximum amount of time to sleep after running a task.
-    pub max_wait: usize,
-    /// A local copy of the global SwanlingConfiguration.
-    pub config: SwanlingConfiguration,
-    /// Load test hash.
-    pub load_test_hash: u64,
+/// Extract the region from the value of the configured `--region-header`.
+///
+/// CDNs append the code of the point of presence that served the request to an identifier:
+/// `CF-Ray` ends with `-SJC`, and `X-Served-By` lists each cache that served the request,
+/// the last ending with `-SJC`. Any other header is expected to contain only the region.
+fn region_from_header(name: &str, value: &str) -> String {
+    let value = match name {
+        "x-served-by" => value.rsplit(',').next().unwrap_or(value),
+        _ => value,
+    };
+    let region = match name {
+        "cf-ray" | "x-served-by" => value.rsplit('-').next().unwrap_or(value),
+        _ => value,
+    };
+    region.trim().to_string()
 }
-impl GaggleUser {
-    /// Create a new user state.
-    pub fn new(
-        task_sets_index: usize,
-        base_url: Url,
-        min_wait: usize,
-        max_wait: usize,
-        configuration: &SwanlingConfiguration,
-        load_test_hash: u64,
-    ) -> Self {
-        trace!("new gaggle user");
-        GaggleUser {
-            task_sets_index,
-            base_url: Arc::new(RwLock::new(base_url)),
-            min_wait,
-            max_wait,
-            config: configuration.clone(),
-            load_test_hash,
-        }
+
+/// Fails requests whose response body is smaller or larger than expected, even if the
+/// status code was 2xx, catching truncated responses or error pages served in place of
+/// data.
+///
+/// The size of the body is measured in bytes after it is decompressed. Requests outside
+/// the range are recorded as failed in the request metrics and error summary, with an
+/// error starting with [`RESPONSE_SIZE_ERROR`]. The body is read to measure it, and is
+/// still available to the task in the response.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// let mut task = task!(get_function);
+///
+/// /// A simple task that expects a response of at least 1 KB and less than 100 KB.
+/// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let request_builder = user
+///         .swanling_get("/path/to/foo")
+///         .await?
+///         .expect_size(1_024..100 * 1_024);
+///     let _swanling = user.swanling_send(request_builder, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub trait SwanlingRequestExpectSize {
+    /// Expect the response body size in bytes to be within the range.
+    fn expect_size<R: RangeBounds<usize>>(self, range: R) -> Self;
+}
+impl SwanlingRequestExpectSize for RequestBuilder {
+    fn expect_size<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let expected_size = SwanlingExpectedSize::from_range(range);
+        self.header(EXPECT_SIZE_HEADER, expected_size.to_header())
     }
 }
 
-/// Used internally by Coordinated Omission Mitigation, tracks the cadence between when the same request
-/// is made as Swanling loops through a SwanlingTaskSet.
+/// Fails requests whose response has a different content type than expected, even if the
+/// status code was 2xx, catching error pages served in place of data without reading the
+/// body.
+///
+/// Only the media type of the `Content-Type` response header is compared, ignoring case
+/// and parameters such as `charset`. Responses without a `Content-Type` header don't
+/// match. Requests with a different content type are recorded as failed in the request
+/// metrics and error summary, with an error starting with [`CONTENT_TYPE_ERROR`].
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// let mut task = task!(get_function);
+///
+/// /// A simple task that expects a JSON response.
+/// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let request_builder = user
+///         .swanling_get("/path/to/foo")
+///         .await?
+///         .expect_content_type("application/json");
+///     let _swanling = user.swanling_send(request_builder, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub trait SwanlingRequestExpectContentType {
+    /// Expect the response to have this content type, for example `application/json`.
+    fn expect_content_type(self, content_type: &str) -> Self;
+}
+impl SwanlingRequestExpectContentType for RequestBuilder {
+    fn expect_content_type(self, content_type: &str) -> Self {
+        self.header(EXPECT_CONTENT_TYPE_HEADER, media_type(content_type))
+    }
+}
+
+/// Defines which status codes a request succeeds with, instead of any 2xx status code, so
+/// endpoints that correctly respond with something else, such as a `404` from an existence
+/// check or a `302` to a login page, aren't counted as failing.
+///
+/// Responses with any other status code, including 2xx status codes not in the list, are
+/// recorded as failed in the request metrics and error summary.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// let mut task = task!(get_function);
+///
+/// /// A simple task checking if a document exists, which it may not.
+/// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let request_builder = user
+///         .swanling_get("/path/to/foo")
+///         .await?
+///         .expect_status(&[200, 404]);
+///     let _swanling = user.swanling_send(request_builder, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub trait SwanlingRequestExpectStatus {
+    /// Expect the response to have one of these status codes.
+    fn expect_status(self, status_codes: &[u16]) -> Self;
+}
+impl SwanlingRequestExpectStatus for RequestBuilder {
+    fn expect_status(self, status_codes: &[u16]) -> Self {
+        self.header(
+            EXPECT_STATUS_HEADER,
+            status_codes
+                .iter()
+                .map(|status_code| status_code.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+    }
+}
+
+/// Remove the header carrying the expected status codes from a request, returning them.
+/// Returns `None` if the default status codes are expected.
+fn take_expected_status(headers: &mut header::HeaderMap) -> Option<Vec<u16>> {
+    let value = headers.remove(EXPECT_STATUS_HEADER)?;
+    Some(
+        value
+            .to_str()
+            .ok()?
+            .split(',')
+            .filter_map(|status_code| status_code.parse().ok())
+            .collect(),
+    )
+}
+
+/// Defines how much a request counts toward the request mix, the share of all requests
+/// each request makes up in the REQUEST MIX table of the final report. By default every
+/// request counts once.
+///
+/// A weight of `0` still records the request in all other metrics, but leaves it out of
+/// the mix, for example a health check made before the primary operation of a task. A
+/// higher weight counts each request as that many, for example a request that stands for
+/// several operations on the server.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// let mut task = task!(get_function);
+///
+/// /// A simple task checking the server is healthy before loading the cart.
+/// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let request_builder = user.swanling_get("/health").await?.mix_weight(0);
+///     let _swanling = user.swanling_send(request_builder, None).await?;
+///     let _swanling = user.get("/cart").await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub trait SwanlingRequestMixWeight {
+    /// Count the request this many times toward the request mix, or not at all if `0`.
+    fn mix_weight(self, weight: usize) -> Self;
+}
+impl SwanlingRequestMixWeight for RequestBuilder {
+    fn mix_weight(self, weight: usize) -> Self {
+        self.header(MIX_WEIGHT_HEADER, weight)
+    }
+}
+
+/// Remove the header carrying the mix weight from a request, returning it. Returns `1` if
+/// no weight was set.
+fn take_mix_weight(headers: &mut header::HeaderMap) -> usize {
+    headers
+        .remove(MIX_WEIGHT_HEADER)
+        .and_then(|value| value.to_str().ok()?.parse().ok())
+        .unwrap_or(1)
+}
+
+/// The media type of a content type, without parameters and in lower case.
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+/// The range of response body sizes in bytes expected by a request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SwanlingExpectedSize {
+    /// The smallest expected size.
+    min: usize,
+    /// The size all responses are expected to be smaller than, if any.
+    max: Option<usize>,
+}
+impl SwanlingExpectedSize {
+    /// Convert any range to an inclusive minimum and exclusive maximum.
+    fn from_range<R: RangeBounds<usize>>(range: R) -> Self {
+        let min = match range.start_bound() {
+            Bound::Included(min) => *min,
+            Bound::Excluded(min) => min.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let max = match range.end_bound() {
+            Bound::Included(max) => Some(max.saturating_add(1)),
+            Bound::Excluded(max) => Some(*max),
+            Bound::Unbounded => None,
+        };
+        SwanlingExpectedSize { min, max }
+    }
+
+    /// Encode the range to carry it in a header.
+    fn to_header(self) -> String {
+        match self.max {
+            Some(max) => format!("{}-{}", self.min, max),
+            None => format!("{}-", self.min),
+        }
+    }
+
+    /// Remove the header carrying the expected size from a request, returning the range.
+    /// Returns `None` if no size is expected.
+    fn take(headers: &mut header::HeaderMap) -> Option<Self> {
+        let value = headers.remove(EXPECT_SIZE_HEADER)?;
+        let (min, max) = value.to_str().ok()?.split_once('-')?;
+        Some(SwanlingExpectedSize {
+            min: min.parse().ok()?,
+            max: if max.is_empty() {
+                None
+            } else {
+                Some(max.parse().ok()?)
+            },
+        })
+    }
+
+    /// Returns true if a response body of this many bytes is within the range.
+    fn contains(&self, size: usize) -> bool {
+        match self.max {
+            Some(max) => size >= self.min && size < max,
+            None => size >= self.min,
+        }
+    }
+}
+
+/// Display the range as it's written in Rust, for example `100..1000`.
+impl fmt::Display for SwanlingExpectedSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.max {
+            Some(max) => write!(f, "{}..{}", self.min, max),
+            None => write!(f, "{}..", self.min),
+        }
+    }
+}
+
+/// Rebuild a response from its status code, HTTP version, headers and an already read
+/// body. The rebuilt response no longer knows its URL.
+fn rebuild_response<T: Into<Body>>(
+    status: reqwest::StatusCode,
+    version: reqwest::Version,
+    headers: header::HeaderMap,
+    body: T,
+) -> Response {
+    let mut rebuilt = http::Response::new(body);
+    *rebuilt.status_mut() = status;
+    *rebuilt.version_mut() = version;
+    *rebuilt.headers_mut() = headers;
+    Response::from(rebuilt)
+}
+
+/// Build a client [`Identity`](https://docs.rs/reqwest/*/reqwest/struct.Identity.html) from a
+/// PEM-encoded certificate and private key.
+#[cfg(feature = "rustls-tls")]
+fn client_identity(cert: &[u8], key: &[u8]) -> Result<Identity, reqwest::Error> {
+    // Rustls expects the certificate and private key in a single PEM buffer.
+    let mut pem = cert.to_vec();
+    pem.extend_from_slice(key);
+    Identity::from_pem(&pem)
+}
+
+/// Build a client [`Identity`](https://docs.rs/reqwest/*/reqwest/struct.Identity.html) from a
+/// PEM-encoded certificate and PKCS #8 private key.
+#[cfg(not(feature = "rustls-tls"))]
+fn client_identity(cert: &[u8], key: &[u8]) -> Result<Identity, reqwest::Error> {
+    Identity::from_pkcs8_pem(cert, key)
+}
+
+/// Read the PEM-encoded certificates in a certificate authority file, which must contain at
+/// least one. Errors are reported against the given option.
+fn read_ca_certs(option: &str, ca_cert: &str) -> Result<Vec<Certificate>, SwanlingError> {
+    let pem = std::fs::read(ca_cert).map_err(|e| SwanlingError::InvalidOption {
+        option: option.to_string(),
+        value: ca_cert.to_string(),
+        detail: format!("Unable to read certificate authorities: {}", e),
+    })?;
+    let certificates = Certificate::from_pem_bundle(&pem)?;
+    if certificates.is_empty() {
+        return Err(SwanlingError::InvalidOption {
+            option: option.to_string(),
+            value: ca_cert.to_string(),
+            detail: "No PEM-encoded certificates found.".to_string(),
+        });
+    }
+    Ok(certificates)
+}
+
+/// Determine whether a request failed while negotiating a TLS connection, for example
+/// because the server rejected the client certificate or presented an untrusted one.
+fn is_tls_handshake_error(error: &reqwest::Error) -> bool {
+    if !error.is_connect() {
+        return false;
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(e) = source {
+        if is_tls_error(e) {
+            return true;
+        }
+        // An I/O error doesn't return the error it wraps as its source.
+        if let Some(inner) = e
+            .downcast_ref::<std::io::Error>()
+            .and_then(|io_error| io_error.get_ref())
+        {
+            if is_tls_error(inner) {
+                return true;
+            }
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Determine whether an error was raised by the TLS implementation the client is built
+/// with.
+fn is_tls_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    #[cfg(feature = "native-tls")]
+    if error.is::<native_tls::Error>() {
+        return true;
+    }
+    #[cfg(feature = "reqwest-rustls")]
+    if error.is::<reqwest_rustls::Error>() {
+        return true;
+    }
+    false
+}
+
+/// Describe a failed TLS handshake, naming the client certificate of the user's class if
+/// one was presented so failures of each identity are counted separately.
+fn tls_handshake_error(client_identity: Option<&str>, error: &reqwest::Error) -> String {
+    match client_identity {
+        Some(cert) => format!(
+            "{} (client identity {}): {}",
+            TLS_HANDSHAKE_ERROR,
+            cert,
+            error_reason(error)
+        ),
+        None => format!("{}: {}", TLS_HANDSHAKE_ERROR, error_reason(error)),
+    }
+}
+
+/// The underlying reason a request failed, such as the rustls or OpenSSL error behind a
+/// failed TLS handshake, which is otherwise hidden behind a generic description.
+fn error_reason(error: &reqwest::Error) -> String {
+    let mut reason = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(e) = source {
+        reason = e.to_string();
+        source = e.source();
+    }
+    reason
+}
+
+/// The response to a SwanlingRequest
+#[derive(Debug)]
+pub struct SwanlingResponse {
+    /// The request that this is a response to.
+    pub request: SwanlingRequestMetric,
+    /// The response.
+    pub response: Result<Response, reqwest::Error>,
+}
+impl SwanlingResponse {
+    pub fn new(request: SwanlingRequestMetric, response: Result<Response, reqwest::Error>) -> Self {
+        SwanlingResponse { request, response }
+    }
+}
+
+/// Describes where to find a value in a response, used by
+/// [`extract()`](./struct.SwanlingUser.html#method.extract) to correlate values
+/// between requests.
+#[derive(Clone, Debug)]
+pub enum SwanlingExtractor {
+    /// The value of the named response header.
+    Header(String),
+    /// The value at a [JSON Pointer](https://tools.ietf.org/html/rfc6901), such as
+    /// `/data/token`, in a JSON response body. Strings are stored without quotes, other
+    /// JSON values are stored as serialized JSON.
+    Json(String),
+    /// The first capture group of a regular expression matched against the response
+    /// body, or the entire match if the expression has no capture groups.
+    Regex(String),
+}
+impl fmt::Display for SwanlingExtractor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingExtractor::Header(name) => write!(f, "header {}", name),
+            SwanlingExtractor::Json(pointer) => write!(f, "json {}", pointer),
+            SwanlingExtractor::Regex(expression) => write!(f, "regex {}", expression),
+        }
+    }
+}
+
+/// Returned by [`extract()`](./struct.SwanlingUser.html#method.extract) when a value
+/// can't be extracted from a response.
+#[derive(Debug)]
+pub struct SwanlingExtractError {
+    /// The name the value would have been stored as.
+    pub name: String,
+    /// Why the value could not be extracted.
+    pub detail: String,
+}
+impl fmt::Display for SwanlingExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unable to extract {}: {}", self.name, self.detail)
+    }
+}
+impl std::error::Error for SwanlingExtractError {}
+
+/// Returned by [`deserialize()`](./struct.SwanlingUser.html#method.deserialize) when a
+/// response body can't be deserialized into the expected type.
+#[derive(Debug)]
+pub struct SwanlingDeserializeError {
+    /// The name of the request the response belongs to.
+    pub name: String,
+    /// Why the response body could not be deserialized.
+    pub detail: String,
+}
+impl fmt::Display for SwanlingDeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unable to deserialize {}: {}", self.name, self.detail)
+    }
+}
+impl std::error::Error for SwanlingDeserializeError {}
+
+/// A condition on a response header, checked by
+/// [`check_headers()`](./struct.SwanlingUser.html#method.check_headers). Header names
+/// are not case sensitive, values are.
+#[derive(Clone, Debug)]
+pub enum SwanlingHeaderCheck {
+    /// Matches if the named header is present.
+    Present(String),
+    /// Matches if the named header is absent.
+    Absent(String),
+    /// Matches if the named header (first) is present with this value (second).
+    Equals(String, String),
+}
+impl SwanlingHeaderCheck {
+    fn matches(&self, headers: &header::HeaderMap) -> bool {
+        match self {
+            SwanlingHeaderCheck::Present(name) => headers.contains_key(name.as_str()),
+            SwanlingHeaderCheck::Absent(name) => !headers.contains_key(name.as_str()),
+            SwanlingHeaderCheck::Equals(name, value) => headers
+                .get_all(name.as_str())
+                .iter()
+                .any(|v| matches!(v.to_str(), Ok(v) if v == value)),
+        }
+    }
+}
+impl fmt::Display for SwanlingHeaderCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingHeaderCheck::Present(name) => write!(f, "header {} present", name),
+            SwanlingHeaderCheck::Absent(name) => write!(f, "header {} absent", name),
+            SwanlingHeaderCheck::Equals(name, value) => write!(f, "header {}: {}", name, value),
+        }
+    }
+}
+
+/// Returned by [`check_headers()`](./struct.SwanlingUser.html#method.check_headers)
+/// when a header check marks a request as a failure.
+#[derive(Debug)]
+pub struct SwanlingHeaderError {
+    /// The name of the request the response belongs to.
+    pub name: String,
+    /// The tag of the check that failed the request.
+    pub tag: String,
+}
+impl fmt::Display for SwanlingHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} failed header check: {}", self.name, self.tag)
+    }
+}
+impl std::error::Error for SwanlingHeaderError {}
+
+/// What happens when a [`SwanlingHeaderCheck`](./enum.SwanlingHeaderCheck.html)
+/// matches a response.
+#[derive(Clone, Debug)]
+pub enum SwanlingHeaderOutcome {
+    /// Mark the request as a failure, collecting this tag into the errors summary.
+    Fail(String),
+    /// Leave the request a success, but increment the custom counter with this name.
+    Tag(String),
+}
+
+/// Object created by [`log_debug()`](struct.SwanlingUser.html#method.log_debug) and written
+/// to log to assist in debugging.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SwanlingDebug {
+    /// String to identify the source of the log message.
+    pub tag: String,
+    /// Optional request made.
+    pub request: Option<SwanlingRequestMetric>,
+    /// Optional headers returned by server.
+    pub header: Option<String>,
+    /// Optional body text returned by server.
+    pub body: Option<String>,
+    /// Optional structured fields logged by
+    /// [`log_debug_fields()`](struct.SwanlingUser.html#method.log_debug_fields).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<serde_json::Value>,
+}
+impl SwanlingDebug {
+    fn new(
+        tag: &str,
+        request: Option<&SwanlingRequestMetric>,
+        header: Option<&header::HeaderMap>,
+        body: Option<&str>,
+    ) -> Self {
+        SwanlingDebug {
+            // Convert tag from &str to string.
+            tag: tag.to_string(),
+            // If request is defined, clone it.
+            request: request.cloned(),
+            // If header is defined, convert it to a string.
+            header: header.map(|h| format!("{:?}", h)),
+            // If header is defined, convert from &str to string.
+            body: body.map(|b| b.to_string()),
+            fields: None,
+        }
+    }
+}
+
+/// The elements needed to build an individual user state on a Regatta Worker.
+#[derive(Debug, Clone)]
+pub struct GaggleUser {
+    /// An index into the internal [`SwanlingAttack`](../struct.SwanlingAttack.html)`.task_sets`
+    /// vector, indicating which [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html) is running.
+    pub task_sets_index: usize,
+    /// The base URL to prepend to all relative paths.
+    pub base_url: Arc<RwLock<Url>>,
+    /// Minimum amount of time to sleep after running a task.
+    pub min_wait: usize,
+    /// Maximum amount of time to sleep after running a task.
+    pub max_wait: usize,
+    /// A local copy of the global SwanlingConfiguration.
+    pub config: SwanlingConfiguration,
+    /// Load test hash.
+    pub load_test_hash: u64,
+    /// The User-Agent allocated to this user, if `--user-agents` is configured.
+    pub user_agent: Option<String>,
+    /// The name of the [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html), or class of
+    /// users, this user belongs to.
+    pub user_class: String,
+}
+impl GaggleUser {
+    /// Create a new user state.
+    pub fn new(
+        task_sets_index: usize,
+        base_url: Url,
+        min_wait: usize,
+        max_wait: usize,
+        configuration: &SwanlingConfiguration,
+        load_test_hash: u64,
+    ) -> Self {
+        trace!("new gaggle user");
+        GaggleUser {
+            task_sets_index,
+            base_url: Arc::new(RwLock::new(base_url)),
+            min_wait,
+            max_wait,
+            config: configuration.clone(),
+            load_test_hash,
+            user_agent: None,
+            user_class: String::new(),
+        }
+    }
+}
+
+/// Used internally by Coordinated Omission Mitigation, tracks the cadence between when the same request
+/// is made as Swanling loops through a SwanlingTaskSet.
 #[derive(Debug, Clone)]
 struct SwanlingRequestCadence {
     /// The last time this SwanlingUser lopped through its SwanlingTasks.
@@ -815,6 +1890,482 @@ impl SwanlingRequestCadence {
     }
 }
 
+/// An OAuth2 access token obtained with a client-credentials grant, cached per user.
+#[derive(Debug, Clone)]
+struct SwanlingOAuthToken {
+    /// The access token sent as a bearer token with each request.
+    access_token: String,
+    /// When to request a new token, or `None` if the token doesn't expire.
+    refresh_at: Option<Instant>,
+}
+
+/// The cookies and session values of a user, saved and reloaded with `--session-file`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(crate) struct SwanlingSession {
+    /// Cookies sent to the base URL, each as `name=value`.
+    pub(crate) cookies: Vec<String>,
+    /// Values stored with [`set_session_value`](./struct.SwanlingUser.html#method.set_session_value).
+    pub(crate) session_data: HashMap<String, String>,
+}
+
+/// A closure customizing the client builder of every user, set with
+/// [`SwanlingAttack::set_client_customizer`](../struct.SwanlingAttack.html#method.set_client_customizer).
+#[derive(Clone)]
+pub(crate) struct SwanlingClientCustomizer(
+    pub(crate) Arc<dyn Fn(ClientBuilder) -> ClientBuilder + Send + Sync>,
+);
+impl fmt::Debug for SwanlingClientCustomizer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SwanlingClientCustomizer")
+    }
+}
+
+/// A closure naming requests from their path, set with
+/// [`SwanlingAttack::set_request_namer`](../struct.SwanlingAttack.html#method.set_request_namer).
+#[derive(Clone)]
+pub(crate) struct SwanlingRequestNamer(pub(crate) Arc<dyn Fn(&str) -> String + Send + Sync>);
+impl fmt::Debug for SwanlingRequestNamer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SwanlingRequestNamer")
+    }
+}
+
+/// Decides which [`SwanlingTask`](./struct.SwanlingTask.html) a
+/// [`SwanlingUser`](./struct.SwanlingUser.html) runs next, registered with
+/// [`SwanlingAttack::set_task_scheduler`](../struct.SwanlingAttack.html#method.set_task_scheduler).
+///
+/// Each user gets its own scheduler, so it can keep state between tasks, for example to
+/// model a user journey as a state machine. The built-in
+/// [`SwanlingScheduler`](../enum.SwanlingScheduler.html) variants implement this trait,
+/// running the tasks of the user in the order they were allocated.
+pub trait SwanlingTaskScheduler: Send {
+    /// Returns the index of the next task to run, in the `tasks` of the
+    /// [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html) the user is running.
+    ///
+    /// Returning `None` ends the current pass through the tasks, and the user starts over,
+    /// calling `next_task` again. If it returns `None` before running any task in a pass,
+    /// the user has nothing left to do: it runs its `on_stop` tasks and exits.
+    fn next_task(&mut self, user: &SwanlingUser, task_set: &SwanlingTaskSet) -> Option<usize>;
+
+    /// Invoked when a task ends the current pass early with
+    /// [`SwanlingTaskControl::SkipRest`](./enum.SwanlingTaskControl.html#variant.SkipRest),
+    /// before the user starts over. Does nothing by default.
+    fn skip_rest(&mut self, _user: &SwanlingUser) {}
+}
+
+/// Creates the [`SwanlingTaskScheduler`](./trait.SwanlingTaskScheduler.html) of a user
+/// running the given [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html).
+type SwanlingTaskSchedulerFn =
+    dyn Fn(&SwanlingTaskSet) -> Box<dyn SwanlingTaskScheduler> + Send + Sync;
+
+/// A closure creating the [`SwanlingTaskScheduler`](./trait.SwanlingTaskScheduler.html) of
+/// each user, set with
+/// [`SwanlingAttack::set_task_scheduler`](../struct.SwanlingAttack.html#method.set_task_scheduler).
+#[derive(Clone)]
+pub(crate) struct SwanlingTaskSchedulerFactory(pub(crate) Arc<SwanlingTaskSchedulerFn>);
+impl fmt::Debug for SwanlingTaskSchedulerFactory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SwanlingTaskSchedulerFactory")
+    }
+}
+
+/// A [`SwanlingTaskScheduler`](./trait.SwanlingTaskScheduler.html) walking a Markov chain
+/// of tasks, modeling how real users navigate based on what they see.
+///
+/// Each session starts with a normal task randomly selected in proportion to the task
+/// weights. After each task, the next task is randomly selected from the weighted
+/// transitions declared with
+/// [`SwanlingTask::set_transition`](./struct.SwanlingTask.html#method.set_transition). The
+/// session ends on an exit transition, declared with
+/// [`SwanlingTask::set_exit_transition`](./struct.SwanlingTask.html#method.set_exit_transition),
+/// or after a task without transitions, and the user starts a new session. Each session
+/// counts as one iteration for `--iterations-per-user`.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// fn main() -> Result<(), SwanlingError> {
+///     SwanlingAttack::initialize()?
+///         .register_taskset(taskset!("Shopper")
+///             .register_task(task!(home).set_name("home")
+///                 .set_transition("search", 4)?
+///                 .set_transition("home", 3)?
+///                 .set_exit_transition(3)?
+///             )
+///             .register_task(task!(search).set_name("search").set_weight(1)?
+///                 .set_transition("home", 1)?
+///             )
+///         )
+///         // Each user walks the chain the same way every time the load test runs.
+///         .set_task_scheduler(|task_set| {
+///             Box::new(SwanlingMarkovScheduler::new(task_set).set_seed(42))
+///         });
+///
+///     Ok(())
+/// }
+///
+/// async fn home(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let _swanling = user.get("/").await?;
+///
+///     Ok(())
+/// }
+///
+/// async fn search(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let _swanling = user.get("/search?q=swan").await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SwanlingMarkovScheduler {
+    /// The weighted normal tasks a session can start with.
+    entries: Vec<(usize, usize)>,
+    /// The weighted transitions from each task, by task index. `None` ends the session.
+    transitions: Vec<Vec<(Option<usize>, usize)>>,
+    /// The task the user ran last in the current session.
+    current: Option<usize>,
+    /// Makes the walk of each user reproducible, combined with the user's index.
+    seed: Option<u64>,
+    /// Random number generator, created when the user starts.
+    rng: Option<StdRng>,
+}
+impl SwanlingMarkovScheduler {
+    /// Create a scheduler walking the transitions of the tasks in the given task set.
+    /// Transitions to task names that aren't normal tasks in the task set are ignored.
+    pub fn new(task_set: &SwanlingTaskSet) -> Self {
+        let is_normal = |task: &SwanlingTask| !task.on_start && !task.on_stop;
+        let entries = task_set
+            .tasks
+            .iter()
+            .filter(|task| is_normal(task))
+            .map(|task| (task.tasks_index, task.weight))
+            .collect();
+        let transitions = task_set
+            .tasks
+            .iter()
+            .map(|task| {
+                task.transitions
+                    .iter()
+                    .filter_map(|(name, weight)| match name {
+                        Some(name) => task_set
+                            .tasks
+                            .iter()
+                            .find(|to| is_normal(to) && &to.name == name)
+                            .map(|to| (Some(to.tasks_index), *weight)),
+                        None => Some((None, *weight)),
+                    })
+                    .collect()
+            })
+            .collect();
+        SwanlingMarkovScheduler {
+            entries,
+            transitions,
+            current: None,
+            seed: None,
+            rng: None,
+        }
+    }
+
+    /// Walk the chain the same way every time, each user following its own path derived
+    /// from the seed.
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+impl SwanlingTaskScheduler for SwanlingMarkovScheduler {
+    fn next_task(&mut self, user: &SwanlingUser, _task_set: &SwanlingTaskSet) -> Option<usize> {
+        let seed = self.seed;
+        let rng = self.rng.get_or_insert_with(|| match seed {
+            Some(seed) => {
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                (seed, user.weighted_users_index).hash(&mut hasher);
+                StdRng::seed_from_u64(hasher.finish())
+            }
+            None => StdRng::from_entropy(),
+        });
+        self.current = match self.current {
+            // Start a new session.
+            None => choose_weighted(rng, &self.entries),
+            // Follow a transition from the last task, ending the session on an exit.
+            Some(current) => choose_weighted(rng, &self.transitions[current]).flatten(),
+        };
+        self.current
+    }
+
+    fn skip_rest(&mut self, _user: &SwanlingUser) {
+        self.current = None;
+    }
+}
+
+// Randomly select one of the values, in proportion to their weights.
+fn choose_weighted<T: Copy>(rng: &mut StdRng, weighted: &[(T, usize)]) -> Option<T> {
+    let total: usize = weighted.iter().map(|(_, weight)| weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut selected = rng.gen_range(0..total);
+    for (value, weight) in weighted {
+        if selected < *weight {
+            return Some(*value);
+        }
+        selected -= weight;
+    }
+    None
+}
+
+/// Counts the passes through their tasks run by all users, reported when limited with
+/// the `--iterations-per-user` run-time option.
+#[derive(Debug, Default)]
+pub(crate) struct SwanlingIterationCounters {
+    /// Passes users completed.
+    pub(crate) completed: AtomicUsize,
+    /// Passes users were still running when they stopped.
+    pub(crate) in_progress: AtomicUsize,
+}
+
+/// Values shared by all users of a load test, published with
+/// [`SwanlingUser::set_shared_value`](./struct.SwanlingUser.html#method.set_shared_value).
+/// In a Regatta, values published on a Worker are sent to the Manager, which sends all
+/// values to every Worker.
+#[derive(Debug, Default)]
+pub(crate) struct SwanlingSharedValues {
+    /// All values published so far, with a version incremented each time one changes.
+    values: std::sync::Mutex<(usize, BTreeMap<String, String>)>,
+    /// Values published on this Worker not yet sent to the Manager.
+    unsent: std::sync::Mutex<Vec<(String, String)>>,
+    /// How many users are waiting for a value to be published.
+    waiting: AtomicUsize,
+    /// Wakes users waiting for a value to be published.
+    published: tokio::sync::Notify,
+}
+impl SwanlingSharedValues {
+    /// Get a copy of a published value.
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        self.values.lock().unwrap().1.get(key).cloned()
+    }
+
+    /// Publish a value, to also be sent to the Manager if `is_worker`.
+    pub(crate) fn publish(&self, key: &str, value: &str, is_worker: bool) {
+        if is_worker {
+            self.unsent
+                .lock()
+                .unwrap()
+                .push((key.to_string(), value.to_string()));
+        }
+        self.merge(vec![(key.to_string(), value.to_string())]);
+    }
+
+    /// Store values published elsewhere, waking any users waiting for them.
+    pub(crate) fn merge<I: IntoIterator<Item = (String, String)>>(&self, values: I) {
+        let mut changed = false;
+        {
+            let mut shared = self.values.lock().unwrap();
+            for (key, value) in values {
+                if shared.1.get(&key) != Some(&value) {
+                    shared.1.insert(key, value);
+                    changed = true;
+                }
+            }
+            if changed {
+                shared.0 += 1;
+            }
+        }
+        if changed {
+            self.published.notify_waiters();
+        }
+    }
+
+    /// The version of the values, incremented each time one changes.
+    #[cfg(feature = "gaggle")]
+    pub(crate) fn version(&self) -> usize {
+        self.values.lock().unwrap().0
+    }
+
+    /// The version of the values, incremented each time one changes, and a copy of them.
+    #[cfg(feature = "gaggle")]
+    pub(crate) fn snapshot(&self) -> (usize, BTreeMap<String, String>) {
+        self.values.lock().unwrap().clone()
+    }
+
+    /// Take the values published on this Worker not yet sent to the Manager.
+    #[cfg(feature = "gaggle")]
+    pub(crate) fn take_unsent(&self) -> Vec<(String, String)> {
+        std::mem::take(&mut *self.unsent.lock().unwrap())
+    }
+
+    /// Whether a Worker needs to exchange values with the Manager, because values were
+    /// published or users are waiting for one.
+    pub(crate) fn needs_sync(&self) -> bool {
+        self.waiting.load(Ordering::SeqCst) > 0 || !self.unsent.lock().unwrap().is_empty()
+    }
+
+    /// Wait up to `timeout` for a value to be published.
+    async fn wait_for(&self, key: &str, timeout: std::time::Duration) -> Option<String> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        self.waiting.fetch_add(1, Ordering::SeqCst);
+        let value = loop {
+            // Register for a notification before checking, so none is missed.
+            let published = self.published.notified();
+            if let Some(value) = self.get(key) {
+                break Some(value);
+            }
+            if tokio::time::timeout_at(deadline, published).await.is_err() {
+                break self.get(key);
+            }
+        };
+        self.waiting.fetch_sub(1, Ordering::SeqCst);
+        value
+    }
+}
+
+/// How many redirects are followed if `--max-redirects` isn't set, the same as the
+/// default policy of the client.
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Build a policy following up to `max_redirects` redirects, storing how many were
+/// followed by the latest request in `redirects`.
+fn redirect_policy(max_redirects: usize, redirects: Arc<AtomicUsize>) -> redirect::Policy {
+    redirect::Policy::custom(move |attempt| {
+        // Each URL already requested was redirected once.
+        let hops = attempt.previous().len();
+        if hops > max_redirects {
+            attempt.error("too many redirects")
+        } else {
+            redirects.store(hops, Ordering::SeqCst);
+            attempt.follow()
+        }
+    })
+}
+
+/// How many milliseconds the client spent resolving host names during the latest request,
+/// or `None` if no host name was resolved, for example because a connection was reused.
+type SwanlingDnsTime = Arc<std::sync::Mutex<Option<u64>>>;
+
+/// Resolves host names with the system resolver, like the default resolver of the client,
+/// adding how long each lookup took to `dns_time`.
+struct SwanlingDnsResolver {
+    dns_time: SwanlingDnsTime,
+}
+impl Resolve for SwanlingDnsResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let dns_time = self.dns_time.clone();
+        Box::pin(async move {
+            let started = Instant::now();
+            let addrs: Vec<std::net::SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            let elapsed = started.elapsed().as_millis() as u64;
+            let mut dns_time = dns_time.lock().unwrap();
+            *dns_time = Some(dns_time.unwrap_or(0) + elapsed);
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Build a client configured like a user's for requests made outside of the load test,
+/// such as the `--health-check-url` checked before it starts.
+pub(crate) fn build_standalone_client(
+    configuration: &SwanlingConfiguration,
+) -> Result<Client, SwanlingError> {
+    build_client(
+        configuration,
+        APP_USER_AGENT,
+        None,
+        &Arc::new(Jar::default()),
+        &Arc::new(AtomicUsize::new(0)),
+        &SwanlingDnsTime::default(),
+        None,
+    )
+}
+
+/// Build the client used by a user to make requests, sending the given User-Agent and
+/// the headers and certificates of the user's class, storing cookies in the given jar,
+/// counting redirects followed in `redirects` and timing host name lookups in `dns_time`.
+fn build_client(
+    configuration: &SwanlingConfiguration,
+    user_agent: &str,
+    task_set: Option<&SwanlingTaskSet>,
+    cookie_jar: &Arc<Jar>,
+    redirects: &Arc<AtomicUsize>,
+    dns_time: &SwanlingDnsTime,
+    customizer: Option<&SwanlingClientCustomizer>,
+) -> Result<Client, SwanlingError> {
+    let mut builder = Client::builder()
+        .user_agent(user_agent)
+        .cookie_provider(cookie_jar.clone())
+        .dns_resolver(Arc::new(SwanlingDnsResolver {
+            dns_time: dns_time.clone(),
+        }));
+    // Send the headers of the user's class with every request.
+    if let Some(task_set) = task_set.filter(|task_set| !task_set.headers.is_empty()) {
+        builder = builder.default_headers(task_set.headers.iter().cloned().collect());
+    }
+    // Follow redirects up to the configured limit, or not at all if the limit is 0.
+    builder = match configuration.max_redirects {
+        Some(0) => builder.redirect(redirect::Policy::none()),
+        max_redirects => builder.redirect(redirect_policy(
+            max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS),
+            redirects.clone(),
+        )),
+    };
+    // Optionally present a client certificate when the server requests one, preferring
+    // the certificate of the user's class.
+    if let Some((cert, key)) = task_set.and_then(|task_set| task_set.client_identity.as_ref()) {
+        let cert = std::fs::read(cert)?;
+        let key = std::fs::read(key)?;
+        builder = builder.identity(client_identity(&cert, &key)?);
+    } else if !configuration.client_cert.is_empty() {
+        let cert = std::fs::read(&configuration.client_cert)?;
+        let key = std::fs::read(&configuration.client_key)?;
+        builder = builder.identity(client_identity(&cert, &key)?);
+    }
+    // Optionally trust custom certificate authorities, for example to load test a
+    // server with a self-signed certificate.
+    if !configuration.ca_cert.is_empty() {
+        for certificate in read_ca_certs("--ca-cert", &configuration.ca_cert)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    // Also trust the certificate authorities of the user's class.
+    for ca_cert in task_set.map_or(&[][..], |task_set| &task_set.ca_certs) {
+        for certificate in read_ca_certs("add_ca_cert", ca_cert)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    // Optionally don't verify certificates at all, only for test environments.
+    if configuration.insecure {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    // Optionally limit how many idle connections are kept open between requests.
+    if let Some(pool_size) = configuration.pool_size {
+        builder = builder.pool_max_idle_per_host(pool_size);
+    }
+    // Optionally close idle connections sooner or later than reqwest's default of 90 seconds.
+    if let Some(pool_idle_timeout) = configuration.pool_idle_timeout {
+        builder =
+            builder.pool_idle_timeout(std::time::Duration::from_secs(pool_idle_timeout as u64));
+    }
+    // Optionally send TCP keep-alive probes on connections that are idle this long.
+    if let Some(keepalive_idle_timeout) = configuration.keepalive_idle_timeout {
+        builder = builder.tcp_keepalive(std::time::Duration::from_secs(
+            keepalive_idle_timeout as u64,
+        ));
+    }
+    // Optionally resolve host names to specific addresses, bypassing DNS. The request
+    // keeps the original host name, so virtual hosting and TLS SNI are unaffected.
+    for mapping in &configuration.resolve {
+        if let Some((host, address)) = util::parse_resolve(mapping) {
+            builder = builder.resolve(&host, address);
+        }
+    }
+    // Finally apply any customization from the load test, which can override the above.
+    if let Some(customizer) = customizer {
+        builder = (customizer.0)(builder);
+    }
+    Ok(builder.build()?)
+}
+
 /// An individual user state, repeatedly running all [`SwanlingTask`](./struct.SwanlingTask.html)s
 /// in a specific [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html).
 #[derive(Debug, Clone)]
@@ -840,6 +2391,8 @@ pub struct SwanlingUser {
     pub logger: Option<flume::Sender<Option<SwanlingLog>>>,
     /// Channel to throttle.
     pub throttle: Option<flume::Sender<bool>>,
+    /// Caps how many connections all users can use at once, if `--max-connections` is set.
+    pub(crate) connections: Option<Arc<Semaphore>>,
     /// Normal tasks are optionally throttled,
     /// [`test_start`](../struct.SwanlingAttack.html#method.test_start) and
     /// [`test_stop`](../struct.SwanlingAttack.html#method.test_stop) tasks are not.
@@ -862,6 +2415,44 @@ pub struct SwanlingUser {
     request_cadence: Arc<RwLock<SwanlingRequestCadence>>,
     /// Tracks how much time is spent sleeping during a loop through all tasks.
     pub(crate) slept: Arc<AtomicU64>,
+    /// Tracks how much time is spent thinking during the current task.
+    thought: Arc<AtomicU64>,
+    /// Values extracted from responses, available to later tasks run by this user.
+    session_data: Arc<RwLock<HashMap<String, String>>>,
+    /// Cookies stored by this user's client, kept when the client is rebuilt.
+    cookie_jar: Arc<Jar>,
+    /// The cached OAuth2 access token, if `--oauth-token-url` is configured.
+    oauth_token: Arc<Mutex<Option<SwanlingOAuthToken>>>,
+    /// Values shared by all users of the load test.
+    pub(crate) shared_values: Arc<SwanlingSharedValues>,
+    /// Counts metric samples dropped because the parent's buffer was full, if bounded with
+    /// `--worker-metrics-buffer`.
+    pub(crate) dropped_metrics: Arc<AtomicUsize>,
+    /// Counts the iterations run by all users.
+    pub(crate) iterations: Arc<SwanlingIterationCounters>,
+    /// The User-Agent allocated to this user, if `--user-agents` is configured.
+    pub(crate) user_agent: Option<String>,
+    /// An optional closure naming requests that aren't otherwise named.
+    pub(crate) request_namer: Option<SwanlingRequestNamer>,
+    /// The name of the [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html), or class of
+    /// users, this user belongs to.
+    pub user_class: String,
+    /// The client certificate presented by users of this user's class, if set with
+    /// [`SwanlingTaskSet::set_client_identity`](./struct.SwanlingTaskSet.html#method.set_client_identity).
+    pub(crate) client_identity: Option<String>,
+    /// How many redirects the client followed during the latest request.
+    redirects: Arc<AtomicUsize>,
+    /// How long the client spent resolving host names during the latest request.
+    dns_time: SwanlingDnsTime,
+    /// How many requests this user has considered for the `--request-log-sample`.
+    sampled_requests: Arc<AtomicU64>,
+    /// What to run after the current task, set with `set_task_control`.
+    task_control: Arc<AtomicUsize>,
+    /// How many requests in a row have failed, for `--user-max-consecutive-failures`.
+    consecutive_failures: Arc<AtomicUsize>,
+    /// The name and response time of each request made by the current task, for the
+    /// `--task-profile`.
+    task_request_times: Arc<std::sync::Mutex<Vec<(String, u64)>>>,
 }
 impl SwanlingUser {
     /// Create a new user state.
@@ -874,10 +2465,18 @@ impl SwanlingUser {
         load_test_hash: u64,
     ) -> Result<Self, SwanlingError> {
         trace!("new SwanlingUser");
-        let client = Client::builder()
-            .user_agent(APP_USER_AGENT)
-            .cookie_store(true)
-            .build()?;
+        let cookie_jar = Arc::new(Jar::default());
+        let redirects = Arc::new(AtomicUsize::new(0));
+        let dns_time = SwanlingDnsTime::default();
+        let client = build_client(
+            configuration,
+            APP_USER_AGENT,
+            None,
+            &cookie_jar,
+            &redirects,
+            &dns_time,
+            None,
+        )?;
 
         Ok(SwanlingUser {
             started: Instant::now(),
@@ -890,6 +2489,7 @@ impl SwanlingUser {
             config: configuration.clone(),
             logger: None,
             throttle: None,
+            connections: None,
             is_throttled: true,
             channel_to_parent: None,
             // A value of max_value() indicates this user isn't fully initialized yet.
@@ -900,9 +2500,67 @@ impl SwanlingUser {
             load_test_hash,
             request_cadence: Arc::new(RwLock::new(SwanlingRequestCadence::new())),
             slept: Arc::new(AtomicU64::new(0)),
+            thought: Arc::new(AtomicU64::new(0)),
+            session_data: Arc::new(RwLock::new(HashMap::new())),
+            cookie_jar,
+            oauth_token: Arc::new(Mutex::new(None)),
+            shared_values: Arc::new(SwanlingSharedValues::default()),
+            dropped_metrics: Arc::new(AtomicUsize::new(0)),
+            iterations: Arc::new(SwanlingIterationCounters::default()),
+            user_agent: None,
+            request_namer: None,
+            user_class: String::new(),
+            client_identity: None,
+            redirects,
+            dns_time,
+            sampled_requests: Arc::new(AtomicU64::new(0)),
+            task_control: Arc::new(AtomicUsize::new(SwanlingTaskControl::Continue as usize)),
+            consecutive_failures: Arc::new(AtomicUsize::new(0)),
+            task_request_times: Arc::new(std::sync::Mutex::new(Vec::new())),
         })
     }
 
+    /// Replace the client with one sending the User-Agent allocated to this user and the
+    /// headers and certificates of its class, and customized by the load test. The client
+    /// is not rebuilt if none of these are set.
+    pub(crate) fn configure_client(
+        &mut self,
+        user_agent: Option<&str>,
+        task_set: Option<&SwanlingTaskSet>,
+        customizer: Option<&SwanlingClientCustomizer>,
+    ) -> Result<(), SwanlingError> {
+        let class_client = match task_set {
+            Some(task_set) => {
+                !task_set.headers.is_empty()
+                    || task_set.client_identity.is_some()
+                    || !task_set.ca_certs.is_empty()
+            }
+            None => false,
+        };
+        if let Some(task_set) = task_set {
+            self.user_class = task_set.name.to_string();
+            self.client_identity = task_set
+                .client_identity
+                .as_ref()
+                .map(|(cert, _)| cert.to_string());
+        }
+        if user_agent.is_none() && !class_client && customizer.is_none() {
+            return Ok(());
+        }
+        let client = build_client(
+            &self.config,
+            user_agent.unwrap_or(APP_USER_AGENT),
+            task_set,
+            &self.cookie_jar,
+            &self.redirects,
+            &self.dns_time,
+            customizer,
+        )?;
+        self.client = Arc::new(Mutex::new(client));
+        self.user_agent = user_agent.map(|user_agent| user_agent.to_string());
+        Ok(())
+    }
+
     /// Create a new single-use user.
     pub fn single(
         base_url: Url,
@@ -927,6 +2585,8 @@ impl SwanlingUser {
     /// current task set)
     ///  3. [`SwanlingDefault::Host`](../enum.SwanlingDefault.html#variant.Host) (default host
     /// defined for the current load test)
+    ///
+    /// If `--base-path` is set, it is prepended to all relative paths, after the host.
     pub async fn build_url(&self, path: &str) -> Result<String, SwanlingTaskError> {
         // If URL includes a host, simply use it.
         if let Ok(parsed_path) = Url::parse(path) {
@@ -935,10 +2595,30 @@ impl SwanlingUser {
             }
         }
 
+        // Optionally prefix the path with the `--base-path`.
+        if !self.config.base_path.is_empty() {
+            let prefixed_path =
+                format!("{}/{}", self.config.base_path, path.trim_start_matches('/'));
+            return Ok(self.base_url.read().await.join(&prefixed_path)?.to_string());
+        }
+
         // Otherwise use the `base_url`.
         Ok(self.base_url.read().await.join(path)?.to_string())
     }
 
+    /// Remove the `--base-path` from the start of a request path, so requests are named
+    /// by the path as written in the load test.
+    fn strip_base_path<'a>(&self, path: &'a str) -> &'a str {
+        if self.config.base_path.is_empty() {
+            return path;
+        }
+        match path.strip_prefix(self.config.base_path.as_str()) {
+            Some("") => "/",
+            Some(stripped) if stripped.starts_with('/') => stripped,
+            _ => path,
+        }
+    }
+
     /// A helper to make a `GET` request of a path and collect relevant metrics.
     /// Automatically prepends the correct host.
     ///
@@ -1076,6 +2756,185 @@ impl SwanlingUser {
             .await?)
     }
 
+    /// A helper to make a GraphQL request and collect relevant metrics. Automatically
+    /// prepends the correct host.
+    ///
+    /// The query and its variables are `POST`ed to `path` as a JSON body built with
+    /// [`graphql_body`](./fn.graphql_body.html). GraphQL APIs typically route every
+    /// operation through the same path, so the request is named after the operation,
+    /// for example `POST GetItem`, while the URL stays unchanged.
+    ///
+    /// GraphQL servers report many errors in an `errors` array of a response with a 2xx
+    /// status code. If the response includes any errors, the request is marked as a
+    /// failure with [`set_failure`](./struct.SwanlingUser.html#method.set_failure),
+    /// collecting the message of the first error into the errors summary prefixed with
+    /// `GraphQL error`. Checking for errors consumes the response body, so an equivalent
+    /// response is returned with the same status code, headers and body, which no longer
+    /// knows its URL.
+    ///
+    /// Calls to `graphql()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(graphql_function);
+    ///
+    /// /// A simple task that makes a GraphQL query.
+    /// async fn graphql_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user
+    ///         .graphql(
+    ///             "/graphql",
+    ///             "GetItem",
+    ///             "query GetItem($id: ID!) { item(id: $id) { name } }",
+    ///             serde_json::json!({ "id": 1 }),
+    ///         )
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn graphql(
+        &self,
+        path: &str,
+        operation_name: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder =
+            self.swanling_post(path)
+                .await?
+                .json(&graphql_body(operation_name, query, variables));
+
+        let mut swanling = self
+            .swanling_send(request_builder, Some(operation_name))
+            .await?;
+
+        // Only check responses that are otherwise successful.
+        if !swanling.request.success {
+            return Ok(swanling);
+        }
+        let response = match swanling.response {
+            Ok(r) => r,
+            Err(e) => {
+                swanling.response = Err(e);
+                return Ok(swanling);
+            }
+        };
+
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let body = match response.bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                swanling.response = Err(e);
+                return Ok(swanling);
+            }
+        };
+
+        let first_error = serde_json::from_slice::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| json.get("errors")?.as_array()?.first().cloned());
+        if let Some(error) = first_error {
+            let message = match error.get("message") {
+                Some(serde_json::Value::String(message)) => message.to_string(),
+                _ => error.to_string(),
+            };
+            // Always returns an error, the failed request is still returned to the task.
+            let _ = self.set_failure(
+                &format!("{}: {}", GRAPHQL_ERROR, message),
+                &mut swanling.request,
+                Some(&headers),
+                Some(&String::from_utf8_lossy(&body)),
+            );
+        }
+
+        swanling.response = Ok(rebuild_response(status, version, headers, body));
+        Ok(swanling)
+    }
+
+    /// A helper to make a `POST` request of a path with a streaming body and collect
+    /// relevant metrics. Automatically prepends the correct host.
+    ///
+    /// The body is read from any [`AsyncRead`](https://docs.rs/tokio/*/tokio/io/trait.AsyncRead.html)
+    /// source, such as a [`tokio::fs::File`](https://docs.rs/tokio/*/tokio/fs/struct.File.html),
+    /// and uploaded in chunks as it is read. The body is never buffered in memory, so large
+    /// uploads don't limit how many users can run at once. Each chunk sent is counted in the
+    /// `bytes_sent` metric.
+    ///
+    /// Calls to `post_stream()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(post_stream_function);
+    ///
+    /// /// A simple task that uploads a file without loading it into memory.
+    /// async fn post_stream_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     if let Ok(file) = tokio::fs::File::open("large-upload.bin").await {
+    ///         let _swanling = user.post_stream("/upload", file).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn post_stream<R>(
+        &self,
+        path: &str,
+        body: R,
+    ) -> Result<SwanlingResponse, SwanlingTaskError>
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        let request_builder = self.swanling_post(path).await?;
+
+        self.swanling_send_stream(request_builder, body, None).await
+    }
+
+    /// A helper to make a named `POST` request of a path with a streaming body and collect
+    /// relevant metrics. Automatically prepends the correct host. Naming a request only
+    /// affects collected metrics.
+    ///
+    /// Calls to `post_stream_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(post_stream_function);
+    ///
+    /// /// A simple task that uploads a file without loading it into memory.
+    /// async fn post_stream_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     if let Ok(file) = tokio::fs::File::open("large-upload.bin").await {
+    ///         let _swanling = user.post_stream_named("/upload", "upload", file).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn post_stream_named<R>(
+        &self,
+        path: &str,
+        request_name: &str,
+        body: R,
+    ) -> Result<SwanlingResponse, SwanlingTaskError>
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        let request_builder = self.swanling_post(path).await?;
+
+        self.swanling_send_stream(request_builder, body, Some(request_name))
+            .await
+    }
+
     /// A helper to make a `HEAD` request of a path and collect relevant metrics.
     /// Automatically prepends the correct host.
     ///
@@ -1413,17 +3272,78 @@ impl SwanlingUser {
         &self,
         request_builder: RequestBuilder,
         request_name: Option<&str>,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        self.send_request(request_builder, request_name, None).await
+    }
+
+    /// Adds a streaming body to a
+    /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+    /// object, then executes the request the same as
+    /// [`swanling_send`](./struct.SwanlingUser.html#method.swanling_send).
+    ///
+    /// The body is read from any [`AsyncRead`](https://docs.rs/tokio/*/tokio/io/trait.AsyncRead.html)
+    /// source and uploaded in chunks as it is read, so memory use stays flat regardless of
+    /// the size of the body. The number of bytes sent is counted as each chunk is uploaded,
+    /// and recorded in the `bytes_sent` metric. To stream a generated body, wrap a
+    /// [`Stream`](https://docs.rs/futures/*/futures/stream/trait.Stream.html) of chunks in a
+    /// [`tokio_util::io::StreamReader`](https://docs.rs/tokio-util/*/tokio_util/io/struct.StreamReader.html).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(put_function);
+    ///
+    /// /// A simple task that uploads a file with a PUT request.
+    /// async fn put_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     if let Ok(file) = tokio::fs::File::open("large-upload.bin").await {
+    ///         let request_builder = user.swanling_put("/upload").await?;
+    ///         let _swanling = user.swanling_send_stream(request_builder, file, None).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn swanling_send_stream<R>(
+        &self,
+        request_builder: RequestBuilder,
+        body: R,
+        request_name: Option<&str>,
+    ) -> Result<SwanlingResponse, SwanlingTaskError>
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        // Count each chunk as it is sent, without buffering the body.
+        let bytes_sent = Arc::new(AtomicU64::new(0));
+        let counter = bytes_sent.clone();
+        let stream = ReaderStream::new(body).inspect_ok(move |chunk| {
+            counter.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        });
+        let request_builder = request_builder.body(Body::wrap_stream(stream));
+
+        self.send_request(request_builder, request_name, Some(bytes_sent))
+            .await
+    }
+
+    // Execute a request, capturing metrics. If the request has a streaming body,
+    // `streamed_bytes` counts how many bytes of it have been sent.
+    async fn send_request(
+        &self,
+        request_builder: RequestBuilder,
+        request_name: Option<&str>,
+        streamed_bytes: Option<Arc<AtomicU64>>,
     ) -> Result<SwanlingResponse, SwanlingTaskError> {
         // If throttle-requests is enabled...
+        let throttle_started = Instant::now();
         if self.is_throttled && self.throttle.is_some() {
             // ...wait until there's room to add a token to the throttle channel before proceeding.
             debug!("SwanlingUser: waiting on throttle");
             // Will result in SwanlingTaskError::RequestCanceled if this fails.
             self.throttle.clone().unwrap().send_async(true).await?;
         };
+        let throttle_wait = throttle_started.elapsed().as_millis() as u64;
 
-        let started = Instant::now();
-        let request = request_builder.build()?;
+        let mut request = request_builder.build()?;
 
         // String version of request path.
         let path = match Url::parse(&request.url().to_string()) {
@@ -1434,7 +3354,7 @@ impl SwanlingUser {
             }
         };
         let method = swanling_method_from_method(request.method().clone())?;
-        let request_name = self.get_request_name(&path, request_name);
+        let request_name = self.get_request_name(self.strip_base_path(&path), request_name);
 
         // Record information about the request.
         let mut request_metric = SwanlingRequestMetric::new(
@@ -1444,23 +3364,166 @@ impl SwanlingUser {
             self.started.elapsed().as_millis(),
             self.weighted_users_index,
         );
+        request_metric.throttle_wait = throttle_wait;
+        request_metric.labels = take_labels(request.headers_mut());
+        let expected_size = SwanlingExpectedSize::take(request.headers_mut());
+        let expected_content_type = request
+            .headers_mut()
+            .remove(EXPECT_CONTENT_TYPE_HEADER)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string());
+        let expected_status = take_expected_status(request.headers_mut());
+        request_metric.mix_weight = take_mix_weight(request.headers_mut());
+
+        // Optionally authenticate with an OAuth2 access token, unless the request already
+        // sets its own Authorization header.
+        if !request.headers().contains_key(header::AUTHORIZATION) {
+            match self.oauth_access_token().await {
+                Ok(Some(token)) => {
+                    if let Ok(value) = header::HeaderValue::from_str(&format!("Bearer {}", token)) {
+                        request.headers_mut().insert(header::AUTHORIZATION, value);
+                    }
+                }
+                Ok(None) => (),
+                Err(e) => {
+                    warn!("{:?}: {}", &path, e);
+                    // Track token failures as their own category of error.
+                    request_metric.success = false;
+                    request_metric.set_status_code(None);
+                    request_metric.error = format!("{}: {}", OAUTH_TOKEN_ERROR, e);
+                    if !self.config.no_metrics {
+                        self.send_request_metric_to_parent(request_metric.clone())
+                            .map_err(|error| *error)?;
+                    }
+                    return Err(SwanlingTaskError::OAuthTokenFailed {
+                        raw_request: request_metric,
+                    });
+                }
+            }
+        }
+
+        // Optionally sign the request with AWS SigV4, unless the request already sets its
+        // own Authorization header.
+        if !request.headers().contains_key(header::AUTHORIZATION) {
+            if let Some(signer) = SigV4::from_config(&self.config) {
+                if let Err(e) = signer.sign(&mut request, Utc::now()) {
+                    warn!("{:?}: {}", &path, e);
+                    // Track signing failures as their own category of error.
+                    request_metric.success = false;
+                    request_metric.set_status_code(None);
+                    request_metric.error = format!("{}: {}", SIGV4_ERROR, e);
+                    if !self.config.no_metrics {
+                        self.send_request_metric_to_parent(request_metric.clone())
+                            .map_err(|error| *error)?;
+                    }
+                    return Err(SwanlingTaskError::SigningFailed {
+                        raw_request: request_metric,
+                    });
+                }
+            }
+        }
+
+        // The size of a buffered body is known before the request is made.
+        let buffered_bytes = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map_or(0, |bytes| bytes.len() as u64);
+
+        // If max-connections is enabled, wait until a connection is available. Requests
+        // queue here rather than opening more connections than allowed.
+        let _connection = match self.connections.as_ref() {
+            Some(connections) => connections.acquire().await.ok(),
+            None => None,
+        };
 
-        // Make the actual request.
-        let response = self.client.lock().await.execute(request).await;
+        // Make the actual request. The client is locked for the whole request, so the
+        // redirects it counts and the host names it resolves belong to this request.
+        let started = Instant::now();
+        let response = {
+            let client = self.client.lock().await;
+            self.redirects.store(0, Ordering::SeqCst);
+            *self.dns_time.lock().unwrap() = None;
+            let response = client.execute(request).await;
+            request_metric.redirects = self.redirects.load(Ordering::SeqCst) as u64;
+            request_metric.dns_time = self.dns_time.lock().unwrap().take();
+            response
+        };
         request_metric.set_response_time(started.elapsed().as_millis());
+        request_metric.bytes_sent = match streamed_bytes {
+            Some(bytes_sent) => bytes_sent.load(Ordering::Relaxed),
+            None => buffered_bytes,
+        };
 
         match &response {
             Ok(r) => {
                 let status_code = r.status();
                 debug!("{:?}: status_code {}", &path, status_code);
                 // @TODO: match/handle all is_foo() https://docs.rs/http/0.2.1/http/status/struct.StatusCode.html
-                if !status_code.is_success() {
+                // A redirect is the expected response if following redirects is disabled.
+                let redirect_expected =
+                    status_code.is_redirection() && self.config.max_redirects == Some(0);
+                // Unless the request defines which status codes it expects.
+                let status_expected = match expected_status.as_ref() {
+                    Some(expected_status) => expected_status.contains(&status_code.as_u16()),
+                    None => status_code.is_success() || redirect_expected,
+                };
+                if !status_expected {
                     request_metric.success = false;
                     request_metric.error = format!("{}: {}", status_code, &path);
+                    // Responses rejecting the AWS SigV4 signature are signing failures.
+                    if !self.config.aws_region.is_empty() {
+                        if let Some(error_type) = r
+                            .headers()
+                            .get(sigv4::ERROR_TYPE_HEADER)
+                            .and_then(|value| value.to_str().ok())
+                            .and_then(sigv4::signing_error_type)
+                        {
+                            request_metric.error = format!(
+                                "{}: {} {}: {}",
+                                SIGV4_ERROR, status_code, error_type, &path
+                            );
+                        }
+                    }
                 }
                 request_metric.set_status_code(Some(status_code));
                 request_metric.set_final_url(r.url().as_str());
 
+                // Optionally fail the request if the response has an unexpected content type.
+                if let Some(expected_content_type) = expected_content_type {
+                    let content_type = r
+                        .headers()
+                        .get(header::CONTENT_TYPE)
+                        .map(|value| media_type(&String::from_utf8_lossy(value.as_bytes())));
+                    if request_metric.success
+                        && content_type.as_ref() != Some(&expected_content_type)
+                    {
+                        let content_type = content_type.unwrap_or_else(|| "none".to_string());
+                        warn!(
+                            "{:?}: content type {}, expected {}",
+                            &path, content_type, expected_content_type
+                        );
+                        request_metric.success = false;
+                        request_metric.error = format!(
+                            "{} ({}, expected {}): {}",
+                            CONTENT_TYPE_ERROR, content_type, expected_content_type, &path
+                        );
+                    }
+                }
+
+                // Optionally label the request with the region that served it.
+                if !self.config.region_header.is_empty() {
+                    if let Some(value) = r.headers().get(self.config.region_header.as_str()) {
+                        let region = region_from_header(
+                            &self.config.region_header,
+                            &String::from_utf8_lossy(value.as_bytes()),
+                        );
+                        if !region.is_empty() {
+                            request_metric
+                                .labels
+                                .insert(REGION_LABEL.to_string(), region);
+                        }
+                    }
+                }
+
                 // Load test user was redirected.
                 if self.config.sticky_follow && request_metric.url != request_metric.final_url {
                     let base_url = self.base_url.read().await.to_string();
@@ -1484,8 +3547,58 @@ impl SwanlingUser {
                 warn!("{:?}: {}", &path, e);
                 request_metric.success = false;
                 request_metric.set_status_code(None);
-                request_metric.error = e.to_string();
+                // Track TLS handshake failures, connection failures and timeouts as their
+                // own kinds of error, as they point at different problems.
+                request_metric.error = if is_tls_handshake_error(e) {
+                    tls_handshake_error(self.client_identity.as_deref(), e)
+                } else if e.is_timeout() {
+                    format!("{}: {}", TIMEOUT_ERROR, error_reason(e))
+                } else if e.is_connect() {
+                    format!("{}: {}", CONNECTION_ERROR, error_reason(e))
+                } else {
+                    e.to_string()
+                };
+            }
+        };
+
+        // If a response size is expected, read the body to measure it. Reading the body
+        // consumes the response, so an equivalent response is rebuilt to return to the task.
+        let response = match (expected_size, response) {
+            (Some(expected_size), Ok(r)) if request_metric.success => {
+                let status = r.status();
+                let version = r.version();
+                let headers = r.headers().clone();
+                match r.bytes().await {
+                    Ok(bytes) => {
+                        if !expected_size.contains(bytes.len()) {
+                            warn!(
+                                "{:?}: response of {} bytes, expected {}",
+                                &path,
+                                bytes.len(),
+                                expected_size
+                            );
+                            request_metric.success = false;
+                            request_metric.error =
+                                format!("{} ({}): {}", RESPONSE_SIZE_ERROR, expected_size, &path);
+                        }
+                        Ok(rebuild_response(status, version, headers, bytes))
+                    }
+                    Err(e) => {
+                        warn!("{:?}: {}", &path, e);
+                        request_metric.success = false;
+                        request_metric.error = e.to_string();
+                        Err(e)
+                    }
+                }
             }
+            (_, response) => response,
+        };
+
+        // If enabled, write the details and body of a failed request to the debug log.
+        let response = if self.config.capture_failure_bodies && !request_metric.success {
+            self.capture_failure_body(&request_metric, response).await?
+        } else {
+            response
         };
 
         // If enabled, track the cadence between each time the same request is made while
@@ -1496,13 +3609,133 @@ impl SwanlingUser {
             .coordinated_omission_mitigation(&request_metric)
             .await?;
 
+        // If enabled, emit a structured event for the request, within the span of the
+        // user and task that made it.
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            request = %request_metric.name,
+            method = %request_metric.method,
+            url = %request_metric.final_url,
+            status_code = request_metric.status_code,
+            response_time = request_metric.response_time,
+            success = request_metric.success,
+            "request"
+        );
+
         // Send a copy of the raw request object to the parent process if
         // we're tracking metrics.
         if !self.config.no_metrics {
-            self.send_request_metric_to_parent(request_metric.clone())?;
+            self.send_request_metric_to_parent(request_metric.clone())
+                .map_err(|error| *error)?;
+        }
+
+        Ok(SwanlingResponse::new(request_metric, response))
+    }
+
+    // Write the details of a failed request to the debug log, with up to
+    // CAPTURE_FAILURE_BODY_LIMIT bytes of the response body. Reading the body consumes the
+    // response, so an equivalent response is rebuilt from the status code, headers and
+    // body to return to the task. The rebuilt response no longer knows its URL, which
+    // remains available as `final_url` in the request metric.
+    async fn capture_failure_body(
+        &self,
+        request_metric: &SwanlingRequestMetric,
+        response: Result<Response, reqwest::Error>,
+    ) -> Result<Result<Response, reqwest::Error>, SwanlingTaskError> {
+        let response = match response {
+            Ok(r) => r,
+            Err(e) => {
+                // There's no response body, only capture the details of the request.
+                self.log_debug(&request_metric.error, Some(request_metric), None, None)?;
+                return Ok(Err(e));
+            }
+        };
+
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let bytes = match response.bytes().await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.log_debug(
+                    &request_metric.error,
+                    Some(request_metric),
+                    Some(&headers),
+                    None,
+                )?;
+                return Ok(Err(e));
+            }
+        };
+        let captured = &bytes[..bytes.len().min(CAPTURE_FAILURE_BODY_LIMIT)];
+        self.log_debug(
+            &request_metric.error,
+            Some(request_metric),
+            Some(&headers),
+            Some(&String::from_utf8_lossy(captured)),
+        )?;
+
+        Ok(Ok(rebuild_response(status, version, headers, bytes)))
+    }
+
+    // Return the OAuth2 access token for this user, performing a client-credentials grant
+    // if there's no cached token or the cached token is about to expire. Returns `None` if
+    // OAuth2 is not configured.
+    async fn oauth_access_token(&self) -> Result<Option<String>, String> {
+        if self.config.oauth_token_url.is_empty() {
+            return Ok(None);
+        }
+
+        let mut cached_token = self.oauth_token.lock().await;
+        if let Some(token) = cached_token.as_ref() {
+            let expired =
+                matches!(token.refresh_at, Some(refresh_at) if Instant::now() >= refresh_at);
+            if !expired {
+                return Ok(Some(token.access_token.to_string()));
+            }
         }
 
-        Ok(SwanlingResponse::new(request_metric, response))
+        // Token requests bypass the throttle and are not included in request metrics.
+        let response = self
+            .client
+            .lock()
+            .await
+            .post(&self.config.oauth_token_url)
+            .basic_auth(
+                &self.config.oauth_client_id,
+                Some(&self.config.oauth_client_secret),
+            )
+            .form(&[("grant_type", "client_credentials")])
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status_code = response.status();
+        if !status_code.is_success() {
+            return Err(format!("{}: {}", status_code, &self.config.oauth_token_url));
+        }
+        let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        let access_token = match body.get("access_token").and_then(|t| t.as_str()) {
+            Some(access_token) => access_token.to_string(),
+            None => return Err("response has no access_token".to_string()),
+        };
+        // Refresh the token shortly before it expires. Tokens without an expiry are
+        // reused for the rest of the load test.
+        let refresh_at = body
+            .get("expires_in")
+            .and_then(|e| e.as_u64())
+            .map(|expires_in| {
+                let margin = OAUTH_REFRESH_MARGIN.min(expires_in / 2);
+                Instant::now() + std::time::Duration::from_secs(expires_in - margin)
+            });
+        debug!(
+            "user {} obtained OAuth access token",
+            self.weighted_users_index + 1
+        );
+        *cached_token = Some(SwanlingOAuthToken {
+            access_token: access_token.to_string(),
+            refresh_at,
+        });
+
+        Ok(Some(access_token))
     }
 
     /// Tracks the time it takes for the current SwanlingUser to loop through all SwanlingTasks
@@ -1648,7 +3881,8 @@ impl SwanlingUser {
                 // Record data points specific to coordinated_omission.
                 coordinated_omission_request_metric.user_cadence = request_cadence.user_cadence;
                 // Send the coordinated omission mitigation generated metrics to the parent.
-                self.send_request_metric_to_parent(coordinated_omission_request_metric)?;
+                self.send_request_metric_to_parent(coordinated_omission_request_metric)
+                    .map_err(|error| *error)?;
             }
             Ok(request_cadence.user_cadence)
         } else {
@@ -1657,25 +3891,82 @@ impl SwanlingUser {
         }
     }
 
+    // The error is boxed, as the request metric it may contain is large.
     fn send_request_metric_to_parent(
         &self,
         request_metric: SwanlingRequestMetric,
-    ) -> SwanlingTaskResult {
-        // If requests-file is enabled, send a copy of the raw request to the logger thread.
-        if !self.config.request_log.is_empty() {
+    ) -> Result<(), Box<SwanlingTaskError>> {
+        // Count failed requests in a row, a success starts over.
+        if request_metric.success {
+            self.consecutive_failures.store(0, Ordering::SeqCst);
+        } else {
+            self.consecutive_failures.fetch_add(1, Ordering::SeqCst);
+        }
+
+        // Attribute the time of actual requests to the current task for the task profile.
+        if !self.config.task_profile.is_empty() && request_metric.coordinated_omission_elapsed == 0
+        {
+            self.task_request_times.lock().unwrap().push((
+                format!("{} {}", request_metric.method, request_metric.name),
+                request_metric.response_time,
+            ));
+        }
+
+        // If requests-file is enabled, send a copy of the raw request to the logger thread,
+        // unless limited to other request names with --log-only or not sampled with
+        // --request-log-sample, and not slow.
+        if !self.config.request_log.is_empty()
+            && self.config.log_request(&request_metric)
+            && self.sample_request(&request_metric)
+        {
             if let Some(logger) = self.logger.as_ref() {
-                logger.send(Some(SwanlingLog::Request(request_metric.clone())))?;
+                logger
+                    .send(Some(SwanlingLog::Request(request_metric.clone())))
+                    .map_err(SwanlingTaskError::from)?;
             }
         }
 
+        if let Some(metric) = self.send_metric_to_parent(SwanlingMetric::Request(request_metric)) {
+            return Err(SwanlingTaskError::from(flume::SendError(metric)).into());
+        }
+
+        Ok(())
+    }
+
+    /// Send a metric to the parent. If the parent's buffer is full the metric is dropped and
+    /// counted rather than waiting, so a saturated Worker doesn't slow down the load test.
+    /// Returns the metric if the parent has gone away.
+    pub(crate) fn send_metric_to_parent(&self, metric: SwanlingMetric) -> Option<SwanlingMetric> {
         // Parent is not defined when running
         // [`test_start`](../struct.SwanlingAttack.html#method.test_start),
         // [`test_stop`](../struct.SwanlingAttack.html#method.test_stop), and during testing.
-        if let Some(parent) = self.channel_to_parent.clone() {
-            parent.send(SwanlingMetric::Request(request_metric))?;
+        if let Some(parent) = self.channel_to_parent.as_ref() {
+            match parent.try_send(metric) {
+                Ok(()) => (),
+                Err(flume::TrySendError::Full(_)) => {
+                    self.dropped_metrics.fetch_add(1, Ordering::SeqCst);
+                }
+                Err(flume::TrySendError::Disconnected(metric)) => return Some(metric),
+            }
         }
 
-        Ok(())
+        None
+    }
+
+    /// Returns true if this request is part of the `--request-log-sample`, which is all
+    /// requests if not sampling. Requests slower than `--slow-threshold` are always sampled.
+    fn sample_request(&self, request_metric: &SwanlingRequestMetric) -> bool {
+        match self.config.request_log_sample {
+            Some(rate) if !request_metric.is_slow(self.config.slow_threshold) => {
+                util::sample_request(
+                    rate,
+                    self.config.request_log_seed.map(|seed| seed as u64),
+                    self.weighted_users_index,
+                    self.sampled_requests.fetch_add(1, Ordering::SeqCst),
+                )
+            }
+            _ => true,
+        }
     }
 
     /// If `request_name` is set, unwrap and use this. Otherwise, if the SwanlingTask has a name
@@ -1690,6 +3981,9 @@ impl SwanlingUser {
                 let position = self.position.load(Ordering::SeqCst);
                 if !self.weighted_tasks.is_empty() && !self.weighted_tasks[position].1.is_empty() {
                     self.weighted_tasks[position].1.clone()
+                } else if let Some(request_namer) = self.request_namer.as_ref() {
+                    // Otherwise name the path with the request namer, if set.
+                    (request_namer.0)(path)
                 } else {
                     // Otherwise return a copy of the the path.
                     path.to_string()
@@ -1698,6 +3992,527 @@ impl SwanlingUser {
         }
     }
 
+    /// Extract values from a response, storing them for use by later tasks.
+    ///
+    /// Each extractor is paired with a name, and the extracted value is stored under
+    /// that name in this user's session where it can be read with
+    /// [`get_session_value`](./struct.SwanlingUser.html#method.get_session_value),
+    /// for example to reuse a CSRF token or an id returned when creating a resource.
+    /// Values are only stored if every extraction succeeds. If any extraction fails, a
+    /// [`SwanlingExtractError`](./struct.SwanlingExtractError.html) is returned which
+    /// converts into a
+    /// [`SwanlingTaskError::ExtractionFailed`](./enum.SwanlingTaskError.html#variant.ExtractionFailed),
+    /// so returning it from the task function records a task failure.
+    ///
+    /// The response body is consumed if any extractor needs it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(create_then_view);
+    ///
+    /// /// Create an item, then view it using the id returned by the server.
+    /// async fn create_then_view(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.post("/item", "name=swanling").await?;
+    ///     user.extract(swanling, &[("id", SwanlingExtractor::Json("/item/id".to_string()))])
+    ///         .await?;
+    ///
+    ///     if let Some(id) = user.get_session_value("id").await {
+    ///         let _swanling = user.get(&format!("/item/{}", id)).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn extract(
+        &self,
+        swanling: SwanlingResponse,
+        extractors: &[(&str, SwanlingExtractor)],
+    ) -> Result<(), SwanlingExtractError> {
+        let response = match swanling.response {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(SwanlingExtractError {
+                    name: extractors
+                        .first()
+                        .map(|(name, _)| name.to_string())
+                        .unwrap_or_default(),
+                    detail: format!("request failed: {}", e),
+                })
+            }
+        };
+
+        // Collect header values before the body is consumed.
+        let mut values = Vec::with_capacity(extractors.len());
+        let mut needs_body = false;
+        for (name, extractor) in extractors {
+            match extractor {
+                SwanlingExtractor::Header(header_name) => {
+                    let value = response
+                        .headers()
+                        .get(header_name.as_str())
+                        .and_then(|v| v.to_str().ok())
+                        .ok_or_else(|| SwanlingExtractError {
+                            name: name.to_string(),
+                            detail: format!("{} not found", extractor),
+                        })?;
+                    values.push(Some(value.to_string()));
+                }
+                _ => {
+                    needs_body = true;
+                    values.push(None);
+                }
+            }
+        }
+
+        if needs_body {
+            let body = response.text().await.map_err(|e| SwanlingExtractError {
+                name: extractors[0].0.to_string(),
+                detail: format!("failed to read response body: {}", e),
+            })?;
+            // Only parse the body as JSON once, and only if needed.
+            let mut json: Option<serde_json::Value> = None;
+            for ((name, extractor), value) in extractors.iter().zip(values.iter_mut()) {
+                let error = |detail: String| SwanlingExtractError {
+                    name: name.to_string(),
+                    detail,
+                };
+                match extractor {
+                    SwanlingExtractor::Header(_) => (),
+                    SwanlingExtractor::Json(pointer) => {
+                        if json.is_none() {
+                            json = Some(serde_json::from_str(&body).map_err(|e| {
+                                error(format!("response body is not valid JSON: {}", e))
+                            })?);
+                        }
+                        let found = json
+                            .as_ref()
+                            .and_then(|j| j.pointer(pointer))
+                            .ok_or_else(|| error(format!("{} not found", extractor)))?;
+                        *value = Some(match found {
+                            serde_json::Value::String(string) => string.to_string(),
+                            other => other.to_string(),
+                        });
+                    }
+                    SwanlingExtractor::Regex(expression) => {
+                        let re = regex::Regex::new(expression)
+                            .map_err(|e| error(format!("invalid {}: {}", extractor, e)))?;
+                        let captures = re
+                            .captures(&body)
+                            .ok_or_else(|| error(format!("{} not matched", extractor)))?;
+                        let found = captures.get(1).or_else(|| captures.get(0));
+                        *value = found.map(|m| m.as_str().to_string());
+                    }
+                }
+            }
+        }
+
+        let mut session_data = self.session_data.write().await;
+        for ((name, _), value) in extractors.iter().zip(values) {
+            session_data.insert(name.to_string(), value.unwrap_or_default());
+        }
+
+        Ok(())
+    }
+
+    /// Store a value in this user's session, making it available to later tasks.
+    ///
+    /// Values are stored per user, and are typically set by
+    /// [`extract`](./struct.SwanlingUser.html#method.extract).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(set_language);
+    ///
+    /// async fn set_language(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     user.set_session_value("language", "en").await;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn set_session_value(&self, name: &str, value: &str) {
+        self.session_data
+            .write()
+            .await
+            .insert(name.to_string(), value.to_string());
+    }
+
+    /// Get a copy of a value previously stored in this user's session, or `None` if
+    /// no value has been stored with this name.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(view_profile);
+    ///
+    /// async fn view_profile(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     if let Some(user_id) = user.get_session_value("user_id").await {
+    ///         let _swanling = user.get(&format!("/user/{}", user_id)).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_session_value(&self, name: &str) -> Option<String> {
+        self.session_data.read().await.get(name).cloned()
+    }
+
+    /// Publish a value shared by all users of the load test, for example an access token
+    /// or the ID of a resource created once by
+    /// [`test_start`](../struct.SwanlingAttack.html#method.test_start). In a Regatta, the
+    /// value is sent to every Worker through the Manager. Publishing the same key again
+    /// replaces its value.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(create_fixture);
+    ///
+    /// async fn create_fixture(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     user.set_shared_value("fixture_id", "42");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_shared_value(&self, key: &str, value: &str) {
+        self.shared_values.publish(key, value, self.config.worker);
+    }
+
+    /// Get a copy of a value published with
+    /// [`set_shared_value`](./struct.SwanlingUser.html#method.set_shared_value), waiting
+    /// up to `timeout` for it to be published. Returns
+    /// [`SwanlingTaskError::SharedValueTimeout`](./enum.SwanlingTaskError.html#variant.SharedValueTimeout)
+    /// if it isn't published in time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(view_fixture);
+    ///
+    /// async fn view_fixture(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let fixture_id = user
+    ///         .get_shared_value("fixture_id", Duration::from_secs(10))
+    ///         .await?;
+    ///     let _swanling = user.get(&format!("/fixture/{}", fixture_id)).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_shared_value(
+        &self,
+        key: &str,
+        timeout: std::time::Duration,
+    ) -> Result<String, SwanlingTaskError> {
+        self.shared_values
+            .wait_for(key, timeout)
+            .await
+            .ok_or_else(|| SwanlingTaskError::SharedValueTimeout {
+                key: key.to_string(),
+            })
+    }
+
+    /// Decide what this user runs after the current task, for example to skip the rest of a
+    /// scenario when logging in fails rather than making requests that can only fail. Applies
+    /// only to the task that sets it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(login);
+    ///
+    /// async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.post("/login", "name=foo&pass=bar").await?;
+    ///     if !swanling.request.success {
+    ///         // Don't run the tasks that need a logged in user.
+    ///         user.set_task_control(SwanlingTaskControl::SkipRest);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_task_control(&self, task_control: SwanlingTaskControl) {
+        self.task_control
+            .store(task_control as usize, Ordering::SeqCst);
+    }
+
+    /// Pause for a random think time from `min_think` to `max_think` inclusively, for
+    /// example to model a user reading the page returned by one request before making the
+    /// next. Think time isn't included in the response time of any request. It is included
+    /// in the time of the task, unless the `--exclude-think-time` flag is set. If
+    /// `max_think` is less than `min_think`, the user thinks for `min_think`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(comment);
+    ///
+    /// async fn comment(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/article").await?;
+    ///     // Read the article before commenting on it.
+    ///     user.think_time(Duration::from_secs(5), Duration::from_secs(15)).await;
+    ///     let _swanling = user.post("/article/comment", "text=foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn think_time(&self, min_think: std::time::Duration, max_think: std::time::Duration) {
+        let think_time = if max_think > min_think {
+            rand::thread_rng().gen_range(min_think..=max_think)
+        } else {
+            min_think
+        };
+        tokio::time::sleep(think_time).await;
+
+        let thought = think_time.as_millis() as u64;
+        // Time spent thinking is not a delay for Coordinated Omission Mitigation.
+        self.slept.fetch_add(thought, Ordering::SeqCst);
+        self.thought.fetch_add(thought, Ordering::SeqCst);
+    }
+
+    // Take how many milliseconds this user spent thinking since this was last called.
+    pub(crate) fn take_think_time(&self) -> u64 {
+        self.thought.swap(0, Ordering::SeqCst)
+    }
+
+    // How many of this user's requests have failed in a row.
+    pub(crate) fn consecutive_failures(&self) -> usize {
+        self.consecutive_failures.load(Ordering::SeqCst)
+    }
+
+    // Take the name and response time of each request made since this was last called.
+    pub(crate) fn take_task_request_times(&self) -> Vec<(String, u64)> {
+        std::mem::take(&mut *self.task_request_times.lock().unwrap())
+    }
+
+    // Get what to run after the current task, resetting it for the next task.
+    pub(crate) fn take_task_control(&self) -> SwanlingTaskControl {
+        SwanlingTaskControl::from_usize(
+            self.task_control
+                .swap(SwanlingTaskControl::Continue as usize, Ordering::SeqCst),
+        )
+    }
+
+    /// Open this user's connection to its base URL with a `HEAD` request, if
+    /// `--prewarm-connections` is enabled, so the time spent connecting and negotiating
+    /// TLS isn't included in the response time of its first request. No metrics are
+    /// recorded, and failures are only logged.
+    pub(crate) async fn prewarm_connection(&self) {
+        let base_url = self.base_url.read().await.to_string();
+
+        // Prewarming a connection counts against --max-connections like any request.
+        let _connection = match self.connections.as_ref() {
+            Some(connections) => connections.acquire().await.ok(),
+            None => None,
+        };
+
+        let started = Instant::now();
+        let client = self.client.lock().await;
+        match client.head(&base_url).send().await {
+            Ok(_) => debug!(
+                "prewarmed connection to {} in {} ms",
+                base_url,
+                started.elapsed().as_millis()
+            ),
+            Err(e) => warn!("failed to prewarm connection to {}: {}", base_url, e),
+        }
+    }
+
+    /// Copy the cookies sent to the base URL and the values stored in this user's
+    /// session, to be saved with `--session-file`.
+    pub(crate) async fn save_session(&self) -> SwanlingSession {
+        let base_url = self.base_url.read().await.clone();
+        let cookies = self
+            .cookie_jar
+            .cookies(&base_url)
+            .and_then(|cookies| cookies.to_str().ok().map(|cookies| cookies.to_string()))
+            .map(|cookies| cookies.split("; ").map(|c| c.to_string()).collect())
+            .unwrap_or_default();
+        SwanlingSession {
+            cookies,
+            session_data: self.session_data.read().await.clone(),
+        }
+    }
+
+    /// Restore cookies and session values saved by an earlier load test with
+    /// `--session-file`.
+    pub(crate) async fn restore_session(&self, session: &SwanlingSession) {
+        let base_url = self.base_url.read().await.clone();
+        for cookie in &session.cookies {
+            self.cookie_jar.add_cookie_str(cookie, &base_url);
+        }
+        self.session_data
+            .write()
+            .await
+            .extend(session.session_data.clone());
+    }
+
+    /// Deserialize a JSON response body into the expected type.
+    ///
+    /// Asserts that a response not only has a 2xx status code, but also has the expected
+    /// shape, catching servers that return truncated or garbled bodies under load. If the
+    /// body can't be deserialized the request is marked as a failure with
+    /// [`set_failure`](./struct.SwanlingUser.html#method.set_failure), so the error is
+    /// collected into the errors summary, and a
+    /// [`SwanlingDeserializeError`](./struct.SwanlingDeserializeError.html) is returned
+    /// which converts into a
+    /// [`SwanlingTaskError::DeserializeFailed`](./enum.SwanlingTaskError.html#variant.DeserializeFailed).
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use swanling::prelude::*;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Item {
+    ///     id: usize,
+    ///     name: String,
+    /// }
+    ///
+    /// let mut task = task!(view_item);
+    ///
+    /// /// Load an item, failing if the response isn't a valid item.
+    /// async fn view_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.get("/item/1").await?;
+    ///     let item: Item = user.deserialize(swanling).await?;
+    ///
+    ///     if item.id == 1 {
+    ///         let _swanling = user.get(&format!("/search?q={}", item.name)).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn deserialize<T: DeserializeOwned>(
+        &self,
+        swanling: SwanlingResponse,
+    ) -> Result<T, SwanlingDeserializeError> {
+        let mut request = swanling.request;
+        let deserialize_error = |detail: String| SwanlingDeserializeError {
+            name: request.name.to_string(),
+            detail,
+        };
+        let response = match swanling.response {
+            Ok(r) => r,
+            Err(e) => return Err(deserialize_error(format!("request failed: {}", e))),
+        };
+
+        let headers = response.headers().clone();
+        let (tag, detail, body) = match response.text().await {
+            Ok(body) => match serde_json::from_str(&body) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    let detail = e.to_string();
+                    // Leave out the position so errors of the same kind are counted together.
+                    let reason = detail.split(" at line ").next().unwrap_or_default();
+                    let tag = format!("{}: {}", INVALID_RESPONSE_ERROR, reason);
+                    (tag, detail, Some(body))
+                }
+            },
+            Err(e) => (
+                format!("{}: failed to read body", INVALID_RESPONSE_ERROR),
+                e.to_string(),
+                None,
+            ),
+        };
+        let error = deserialize_error(detail);
+
+        // Always returns an error, which is replaced with the more specific one.
+        let _ = self.set_failure(&tag, &mut request, Some(&headers), body.as_deref());
+
+        Err(error)
+    }
+
+    /// Check the headers of a response, failing or tagging responses that succeeded
+    /// according to their status code but signal a problem in a header, for example
+    /// `X-Served-From: fallback`.
+    ///
+    /// Each [`SwanlingHeaderCheck`](./enum.SwanlingHeaderCheck.html) is paired with a
+    /// [`SwanlingHeaderOutcome`](./enum.SwanlingHeaderOutcome.html). Every check is
+    /// applied: matching `Tag` outcomes increment the named custom counter, and if any
+    /// `Fail` outcome matches, the request is marked as a failure with
+    /// [`set_failure`](./struct.SwanlingUser.html#method.set_failure) using the tag of
+    /// the first one, and a [`SwanlingHeaderError`](./struct.SwanlingHeaderError.html)
+    /// is returned which converts into a
+    /// [`SwanlingTaskError::HeaderCheckFailed`](./enum.SwanlingTaskError.html#variant.HeaderCheckFailed).
+    /// Responses to requests that failed to send are not checked.
+    ///
+    /// The response is not consumed, so the body can still be validated afterward, for
+    /// example with [`deserialize`](./struct.SwanlingUser.html#method.deserialize). A
+    /// request that already failed is only counted once in the errors summary.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(loadtest_index_page);
+    ///
+    /// async fn loadtest_index_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let mut swanling = user.get("/").await?;
+    ///     user.check_headers(
+    ///         &mut swanling,
+    ///         &[
+    ///             (
+    ///                 SwanlingHeaderCheck::Equals("x-served-from".to_string(), "fallback".to_string()),
+    ///                 SwanlingHeaderOutcome::Fail("served from fallback".to_string()),
+    ///             ),
+    ///             (
+    ///                 SwanlingHeaderCheck::Absent("x-cache".to_string()),
+    ///                 SwanlingHeaderOutcome::Tag("uncached".to_string()),
+    ///             ),
+    ///         ],
+    ///     )?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn check_headers(
+        &self,
+        swanling: &mut SwanlingResponse,
+        checks: &[(SwanlingHeaderCheck, SwanlingHeaderOutcome)],
+    ) -> Result<(), SwanlingHeaderError> {
+        let headers = match &swanling.response {
+            Ok(response) => response.headers(),
+            Err(_) => return Ok(()),
+        };
+
+        let mut failure = None;
+        for (check, outcome) in checks {
+            if !check.matches(headers) {
+                continue;
+            }
+            match outcome {
+                SwanlingHeaderOutcome::Fail(tag) => {
+                    if failure.is_none() {
+                        failure = Some(tag);
+                    }
+                }
+                SwanlingHeaderOutcome::Tag(name) => self.increment_counter(name, 1),
+            }
+        }
+
+        match failure {
+            Some(tag) => {
+                // Always returns an error, which is replaced with the more specific one.
+                let _ = self.set_failure(tag, &mut swanling.request, Some(headers), None);
+                Err(SwanlingHeaderError {
+                    name: swanling.request.name.to_string(),
+                    tag: tag.to_string(),
+                })
+            }
+            None => Ok(()),
+        }
+    }
+
     /// Manually mark a request as a success.
     ///
     /// By default, Swanling will consider any response with a 2xx status code as a success.
@@ -1732,7 +4547,8 @@ impl SwanlingUser {
         if !request.success {
             request.success = true;
             request.update = true;
-            self.send_request_metric_to_parent(request.clone())?;
+            self.send_request_metric_to_parent(request.clone())
+                .map_err(|error| *error)?;
         }
 
         Ok(())
@@ -1805,17 +4621,96 @@ impl SwanlingUser {
             request.success = false;
             request.update = true;
             request.error = tag.to_string();
-            self.send_request_metric_to_parent(request.clone())?;
+            self.send_request_metric_to_parent(request.clone())
+                .map_err(|error| *error)?;
         }
         // Write failure to log, converting `&mut request` to `&request` as needed by `log_debug()`.
         self.log_debug(tag, Some(&*request), headers, body)?;
 
-        // Print log to stdout if `-v` is enabled.
-        info!("set_failure: {}", tag);
+        // Print log to stdout if `-v` is enabled.
+        info!("set_failure: {}", tag);
+
+        Err(SwanlingTaskError::RequestFailed {
+            raw_request: request.clone(),
+        })
+    }
+
+    /// Increment a named custom counter.
+    ///
+    /// Custom counters make it possible to count things Swanling doesn't otherwise know
+    /// about, such as cache hits reported in a response header. Counters with the same
+    /// name are summed together, across all
+    /// [`SwanlingUser`](./struct.SwanlingUser.html) threads and all Workers, and displayed
+    /// in the custom metrics table when the load test finishes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(loadtest_index_page);
+    ///
+    /// async fn loadtest_index_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.get("/").await?;
+    ///
+    ///     if let Ok(response) = &swanling.response {
+    ///         // Count how many pages were served from the cache.
+    ///         if let Some(cache) = response.headers().get("x-cache") {
+    ///             if cache == "HIT" {
+    ///                 user.increment_counter("cache hits", 1);
+    ///             }
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn increment_counter(&self, name: &str, value: usize) {
+        self.send_custom_metric_to_parent(SwanlingCustomMetric::Counter {
+            name: name.to_string(),
+            value,
+        })
+    }
+
+    /// Record an observation of a named custom gauge.
+    ///
+    /// Custom gauges track the minimum, maximum and mean of all values observed with the
+    /// same name, across all [`SwanlingUser`](./struct.SwanlingUser.html) threads and all
+    /// Workers, and are displayed in the custom metrics table when the load test finishes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(loadtest_cart);
+    ///
+    /// async fn loadtest_cart(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.get("/cart").await?;
+    ///
+    ///     if let Ok(response) = swanling.response {
+    ///         if let Ok(text) = response.text().await {
+    ///             // Track how many items are in the cart.
+    ///             user.observe_gauge("cart size", text.matches("cart-item").count() as f64);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn observe_gauge(&self, name: &str, value: f64) {
+        self.send_custom_metric_to_parent(SwanlingCustomMetric::Gauge {
+            name: name.to_string(),
+            value,
+        })
+    }
+
+    fn send_custom_metric_to_parent(&self, custom_metric: SwanlingCustomMetric) {
+        // Custom metrics are not collected if all metrics are disabled.
+        if self.config.no_metrics {
+            return;
+        }
 
-        Err(SwanlingTaskError::RequestFailed {
-            raw_request: request.clone(),
-        })
+        // Best effort metrics.
+        self.send_metric_to_parent(SwanlingMetric::Custom(custom_metric));
     }
 
     /// Write to [`debug_file`](../struct.SwanlingConfiguration.html#structfield.debug_file)
@@ -1920,6 +4815,60 @@ impl SwanlingUser {
         Ok(())
     }
 
+    /// Write a structured record to
+    /// [`debug_file`](../struct.SwanlingConfiguration.html#structfield.debug_file) if
+    /// enabled, for example a value correlated from a response or a decision made by the
+    /// task, so it can be read alongside the entries Swanling writes itself.
+    ///
+    /// Like [`log_debug`](#method.log_debug), each entry includes a tag and optionally the
+    /// request it relates to. The `fields` can be any value that serializes with `serde`,
+    /// and are written in the configured `--debug-format`: as a `fields` object in JSON,
+    /// as a JSON string in CSV, and as Rust's debug output in raw format. Fields that can't
+    /// be serialized are left out of the entry with a warning.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let mut task = task!(place_order);
+    ///
+    /// async fn place_order(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.post("/order", "item=42").await?;
+    ///
+    ///     // Record which order the request created, and why it was placed.
+    ///     user.log_debug_fields(
+    ///         "placed order",
+    ///         Some(&swanling.request),
+    ///         &json!({ "order_id": 1234, "reason": "cart full" }),
+    ///     )?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn log_debug_fields<T: Serialize>(
+        &self,
+        tag: &str,
+        request: Option<&SwanlingRequestMetric>,
+        fields: &T,
+    ) -> SwanlingTaskResult {
+        if !self.config.debug_log.is_empty() {
+            if let Some(logger) = self.logger.clone() {
+                let mut debug = SwanlingDebug::new(tag, request, None, None);
+                debug.fields = match serde_json::to_value(fields) {
+                    Ok(fields) => Some(fields),
+                    Err(e) => {
+                        warn!("unable to serialize debug fields for {}: {}", tag, e);
+                        None
+                    }
+                };
+                logger.send(Some(SwanlingLog::Debug(debug)))?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Manually build a
     /// [`reqwest::Client`](https://docs.rs/reqwest/*/reqwest/struct.Client.html).
     ///
@@ -2153,6 +5102,13 @@ pub struct SwanlingTask {
     pub on_start: bool,
     /// A flag indicating that this task runs when the user stops.
     pub on_stop: bool,
+    /// An optional minimum and maximum number of seconds to pause after this task runs,
+    /// used instead of the [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html) wait time.
+    pub wait_time: Option<(usize, usize)>,
+    /// Weighted transitions to the tasks that can run after this task, followed by a
+    /// [`SwanlingMarkovScheduler`](./struct.SwanlingMarkovScheduler.html). A transition to
+    /// `None` ends the session.
+    pub transitions: Vec<(Option<String>, usize)>,
     /// A required function that is executed each time this task runs.
     pub function: SwanlingTaskFunction,
 }
@@ -2166,6 +5122,8 @@ impl SwanlingTask {
             sequence: 0,
             on_start: false,
             on_stop: false,
+            wait_time: None,
+            transitions: Vec::new(),
             function,
         }
     }
@@ -2290,6 +5248,137 @@ impl SwanlingTask {
         Ok(self)
     }
 
+    /// Configure a task to pause for its own length of time after it runs, instead of using the
+    /// wait time of its [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html). The length of the
+    /// pause will be randomly selected from `min_wait` to `max_wait` inclusively. Tasks that don't
+    /// set a wait time fall back to the wait time of their task set.
+    ///
+    /// For example, a user may spend several seconds reading an article, but quickly click
+    /// through to the next page of search results.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     taskset!("ExampleTasks")
+    ///         .set_wait_time(0, 2)?
+    ///         .register_task(task!(read_article).set_wait_time(10, 30)?)
+    ///         .register_task(task!(next_page));
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn read_article(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/article").await?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn next_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/search?page=2").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_wait_time(
+        mut self,
+        min_wait: usize,
+        max_wait: usize,
+    ) -> Result<Self, SwanlingError> {
+        trace!(
+            "{} [{}] set_wait_time: min: {} max: {}",
+            self.name,
+            self.tasks_index,
+            min_wait,
+            max_wait
+        );
+        if min_wait > max_wait {
+            return Err(SwanlingError::InvalidWaitTime {
+                min_wait,
+                max_wait,
+                detail:
+                    "The min_wait option can not be set to a larger value than the max_wait option."
+                        .to_string(),
+            });
+        }
+        self.wait_time = Some((min_wait, max_wait));
+
+        Ok(self)
+    }
+
+    /// Declare that the task with the given name can run after this task, when users are
+    /// scheduled with a [`SwanlingMarkovScheduler`](./struct.SwanlingMarkovScheduler.html).
+    /// The next task is randomly selected from all transitions of this task, in proportion
+    /// to their `weight`.
+    ///
+    /// The named task must be a normal task in the same
+    /// [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html), or the load test won't start.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     // From the home page, 40% of users search, 30% browse and 30% leave.
+    ///     task!(home)
+    ///         .set_name("home")
+    ///         .set_transition("search", 4)?
+    ///         .set_transition("browse", 3)?
+    ///         .set_exit_transition(3)?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn home(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_transition(mut self, task_name: &str, weight: usize) -> Result<Self, SwanlingError> {
+        trace!(
+            "{} [{}] set_transition: {} ({})",
+            self.name,
+            self.tasks_index,
+            task_name,
+            weight
+        );
+        if weight == 0 {
+            return Err(SwanlingError::InvalidWeight {
+                weight,
+                detail: "Weight must be set to at least 1.".to_string(),
+            });
+        }
+        self.transitions.push((Some(task_name.to_string()), weight));
+
+        Ok(self)
+    }
+
+    /// Declare that the session can end after this task, when users are scheduled with a
+    /// [`SwanlingMarkovScheduler`](./struct.SwanlingMarkovScheduler.html). Ending the session
+    /// is selected in proportion to its `weight`, compared to the weights set with
+    /// [`SwanlingTask::set_transition`](./struct.SwanlingTask.html#method.set_transition).
+    ///
+    /// A task without any transitions always ends the session.
+    pub fn set_exit_transition(mut self, weight: usize) -> Result<Self, SwanlingError> {
+        trace!(
+            "{} [{}] set_exit_transition: {}",
+            self.name,
+            self.tasks_index,
+            weight
+        );
+        if weight == 0 {
+            return Err(SwanlingError::InvalidWeight {
+                weight,
+                detail: "Weight must be set to at least 1.".to_string(),
+            });
+        }
+        self.transitions.push((None, weight));
+
+        Ok(self)
+    }
+
     /// Defines the sequence value of an individual tasks. Tasks are run in order of their sequence value,
     /// so a task with a sequence value of 1 will run before a task with a sequence value of 2. Tasks with
     /// no sequence value (or a sequence value of 0) will run last, after all tasks with positive sequence
@@ -2384,6 +5473,8 @@ impl Hash for SwanlingTask {
         self.sequence.hash(state);
         self.on_start.hash(state);
         self.on_stop.hash(state);
+        self.wait_time.hash(state);
+        self.transitions.hash(state);
     }
 }
 
@@ -2406,6 +5497,38 @@ mod tests {
         SwanlingUser::single(base_url, &configuration)
     }
 
+    #[test]
+    fn client_tls() {
+        let base_url = get_base_url(Some("https://example.com".to_string()), None, None).unwrap();
+
+        // Users can be configured not to verify certificates.
+        let configuration = SwanlingConfiguration::parse_args_default(&["--insecure"]).unwrap();
+        assert!(configuration.insecure);
+        assert!(SwanlingUser::single(base_url.clone(), &configuration).is_ok());
+
+        // A CA certificate file without any certificates is rejected.
+        let ca_cert = "client-tls-empty-ca.pem";
+        std::fs::write(ca_cert, "").unwrap();
+        let configuration =
+            SwanlingConfiguration::parse_args_default(&["--ca-cert", ca_cert]).unwrap();
+        let user = SwanlingUser::single(base_url, &configuration);
+
+        // So is one trusted by a class of users, or one that can't be read.
+        assert!(SwanlingTaskSet::new("PartnerA")
+            .add_ca_cert(ca_cert)
+            .is_err());
+        std::fs::remove_file(ca_cert).unwrap();
+        assert!(user.is_err());
+        assert!(SwanlingTaskSet::new("PartnerA")
+            .add_ca_cert(ca_cert)
+            .is_err());
+
+        // A class of users can't present a client identity that can't be read.
+        let task_set = SwanlingTaskSet::new("PartnerA")
+            .set_client_identity("client-tls-missing.pem", "client-tls-missing-key.pem");
+        assert!(task_set.is_err());
+    }
+
     #[test]
     fn swanling_task_set() {
         // Simplistic test task functions.
@@ -2505,6 +5628,41 @@ mod tests {
         task_set = task_set.set_wait_time(3, 9).unwrap();
         assert_eq!(task_set.min_wait, 3);
         assert_eq!(task_set.max_wait, 9);
+
+        // Headers only affect the headers field.
+        assert!(task_set.headers.is_empty());
+        task_set = task_set.set_header("Authorization", "Bearer foo").unwrap();
+        task_set = task_set.set_header("X-Class", "logged-in").unwrap();
+        assert_eq!(task_set.headers.len(), 2);
+        assert_eq!(task_set.headers[0].0, "authorization");
+        assert_eq!(task_set.headers[0].1, "Bearer foo");
+        assert_eq!(task_set.min_wait, 3);
+        assert_eq!(task_set.weight, 5);
+
+        // Setting a header again replaces its value.
+        task_set = task_set.set_header("authorization", "Bearer bar").unwrap();
+        assert_eq!(task_set.headers.len(), 2);
+        assert_eq!(task_set.headers[1].0, "authorization");
+        assert_eq!(task_set.headers[1].1, "Bearer bar");
+
+        // Environment variables are expanded in values.
+        std::env::set_var("SWANLING_TEST_HEADER_TOKEN", "baz");
+        task_set = task_set
+            .set_header("authorization", "Bearer ${SWANLING_TEST_HEADER_TOKEN}")
+            .unwrap();
+        assert_eq!(task_set.headers[1].1, "Bearer baz");
+
+        // Invalid header names and values are rejected.
+        assert!(task_set.clone().set_header("Bad Name", "foo").is_err());
+        assert!(task_set.clone().set_header("X-Class", "foo\nbar").is_err());
+
+        // Values referencing a variable that isn't set are rejected, naming the variable.
+        match task_set.set_header("X-Class", "${SWANLING_TEST_HEADER_UNSET}") {
+            Err(SwanlingError::InvalidOption { detail, .. }) => {
+                assert!(detail.contains("SWANLING_TEST_HEADER_UNSET"))
+            }
+            _ => panic!("expected an invalid option error"),
+        }
     }
 
     #[test]
@@ -2585,6 +5743,20 @@ mod tests {
         // Sequence field can be changed multiple times.
         task = task.set_sequence(8);
         assert_eq!(task.sequence, 8);
+
+        // Setting wait time doesn't change anything else.
+        assert_eq!(task.wait_time, None);
+        task = task.set_wait_time(1, 3).unwrap();
+        assert_eq!(task.wait_time, Some((1, 3)));
+        assert_eq!(task.sequence, 8);
+        assert_eq!(task.weight, 3);
+
+        // Minimum and maximum wait time can be the same.
+        task = task.set_wait_time(2, 2).unwrap();
+        assert_eq!(task.wait_time, Some((2, 2)));
+
+        // Minimum wait time can't be larger than maximum wait time.
+        assert!(task.set_wait_time(3, 1).is_err());
     }
 
     #[tokio::test]
@@ -2637,6 +5809,26 @@ mod tests {
         let url = user2.build_url("https://example.com/foo").await.unwrap();
         assert_eq!(url, "https://example.com/foo");
 
+        // Create a third user, this time setting a base path.
+        let mut base_path_configuration = configuration.clone();
+        base_path_configuration.base_path = "/api/v2".to_string();
+        let base_url = get_base_url(Some(HOST.to_string()), None, None).unwrap();
+        let user3 = SwanlingUser::new(0, base_url, 0, 0, &base_path_configuration, 0).unwrap();
+
+        // Confirm the base path is prepended to relative paths, but not to other hosts.
+        let url = user3.build_url("/foo").await.unwrap();
+        assert_eq!(url, "http://example.com/api/v2/foo");
+        let url = user3.build_url("bar/").await.unwrap();
+        assert_eq!(url, "http://example.com/api/v2/bar/");
+        let url = user3.build_url("https://example.com/foo").await.unwrap();
+        assert_eq!(url, "https://example.com/foo");
+
+        // Confirm the base path is removed when naming requests.
+        assert_eq!(user3.strip_base_path("/api/v2/foo"), "/foo");
+        assert_eq!(user3.strip_base_path("/api/v2"), "/");
+        assert_eq!(user3.strip_base_path("/api/v20/foo"), "/api/v20/foo");
+        assert_eq!(user3.strip_base_path("/foo"), "/foo");
+
         // Recreate user2.
         let server = MockServer::start();
         let user2 = setup_user(&server).await.unwrap();
@@ -2759,4 +5951,274 @@ mod tests {
         assert_eq!(swanling.request.status_code, 200);
         comment.assert_hits(1);
     }
+
+    #[tokio::test]
+    async fn expect_size() {
+        let server = MockServer::start();
+
+        let user = setup_user(&server).await.unwrap();
+
+        // Set up a mock http server endpoint returning a 3 byte body.
+        const INDEX_PATH: &str = "/";
+        let index = server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200).body("foo");
+        });
+
+        // A response within the range succeeds, and its body is still available.
+        let request_builder = user
+            .swanling_get(INDEX_PATH)
+            .await
+            .unwrap()
+            .expect_size(1..10);
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(swanling.request.success);
+        assert_eq!(swanling.response.unwrap().text().await.unwrap(), "foo");
+
+        // Inclusive and unbounded ranges are supported.
+        let request_builder = user
+            .swanling_get(INDEX_PATH)
+            .await
+            .unwrap()
+            .expect_size(..=3);
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(swanling.request.success);
+
+        // A response smaller than expected fails.
+        let request_builder = user
+            .swanling_get(INDEX_PATH)
+            .await
+            .unwrap()
+            .expect_size(10..);
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(!swanling.request.success);
+        assert_eq!(swanling.request.status_code, 200);
+        assert!(swanling.request.error.starts_with(RESPONSE_SIZE_ERROR));
+        assert_eq!(swanling.response.unwrap().text().await.unwrap(), "foo");
+
+        // A response larger than expected fails.
+        let request_builder = user
+            .swanling_get(INDEX_PATH)
+            .await
+            .unwrap()
+            .expect_size(..3);
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(!swanling.request.success);
+        index.assert_hits(4);
+    }
+
+    #[tokio::test]
+    async fn expect_content_type() {
+        let server = MockServer::start();
+
+        let user = setup_user(&server).await.unwrap();
+
+        // Set up mock http server endpoints returning JSON, and an HTML error page.
+        const JSON_PATH: &str = "/json";
+        const HTML_PATH: &str = "/html";
+        let json = server.mock(|when, then| {
+            when.method(GET).path(JSON_PATH);
+            then.status(200)
+                .header("content-type", "application/json; charset=utf-8")
+                .body("{}");
+        });
+        let html = server.mock(|when, then| {
+            when.method(GET).path(HTML_PATH);
+            then.status(200)
+                .header("content-type", "text/html")
+                .body("<html></html>");
+        });
+
+        // The media type is compared, ignoring case and parameters.
+        let request_builder = user
+            .swanling_get(JSON_PATH)
+            .await
+            .unwrap()
+            .expect_content_type("Application/JSON");
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(swanling.request.success);
+
+        // A different content type fails, even with a 2xx status code.
+        let request_builder = user
+            .swanling_get(HTML_PATH)
+            .await
+            .unwrap()
+            .expect_content_type("application/json");
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(!swanling.request.success);
+        assert_eq!(swanling.request.status_code, 200);
+        assert_eq!(
+            swanling.request.error,
+            "content-type mismatch (text/html, expected application/json): /html"
+        );
+        assert_eq!(
+            swanling.response.unwrap().text().await.unwrap(),
+            "<html></html>"
+        );
+        json.assert_hits(1);
+        html.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn expect_status() {
+        let server = MockServer::start();
+
+        let user = setup_user(&server).await.unwrap();
+
+        // Set up mock http server endpoints returning a 404, and a 200.
+        const MISSING_PATH: &str = "/missing";
+        const INDEX_PATH: &str = "/";
+        let missing = server.mock(|when, then| {
+            when.method(GET).path(MISSING_PATH);
+            then.status(404);
+        });
+        let index = server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        });
+
+        // By default a 404 fails.
+        let swanling = user.get(MISSING_PATH).await.unwrap();
+        assert!(!swanling.request.success);
+
+        // An expected 404 succeeds.
+        let request_builder = user
+            .swanling_get(MISSING_PATH)
+            .await
+            .unwrap()
+            .expect_status(&[200, 404]);
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(swanling.request.success);
+        assert_eq!(swanling.request.status_code, 404);
+        assert!(swanling.request.error.is_empty());
+
+        // A 200 fails if it's not expected.
+        let request_builder = user
+            .swanling_get(INDEX_PATH)
+            .await
+            .unwrap()
+            .expect_status(&[201]);
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert!(!swanling.request.success);
+        assert_eq!(swanling.request.error, "200 OK: /");
+        missing.assert_hits(2);
+        index.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn mix_weight() {
+        let server = MockServer::start();
+
+        let user = setup_user(&server).await.unwrap();
+
+        // Set up a mock http server endpoint.
+        const INDEX_PATH: &str = "/";
+        let index = server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header_exists(MIX_WEIGHT_HEADER);
+            then.status(500);
+        });
+        let _ = server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        });
+
+        // By default a request counts once.
+        let swanling = user.get(INDEX_PATH).await.unwrap();
+        assert_eq!(swanling.request.mix_weight, 1);
+
+        // The weight is recorded, and isn't sent to the server.
+        let request_builder = user.swanling_get(INDEX_PATH).await.unwrap().mix_weight(0);
+        let swanling = user.swanling_send(request_builder, None).await.unwrap();
+        assert_eq!(swanling.request.mix_weight, 0);
+        assert!(swanling.request.success);
+        index.assert_hits(0);
+    }
+
+    #[test]
+    fn send_metric_to_parent() {
+        let configuration = SwanlingConfiguration::parse_args_default(&EMPTY_ARGS).unwrap();
+        let base_url = get_base_url(Some("http://example.com/".to_string()), None, None).unwrap();
+        let mut user = SwanlingUser::new(0, base_url, 0, 0, &configuration, 0).unwrap();
+
+        // Metrics that don't fit in a bounded buffer are dropped and counted.
+        let (parent, metrics) = flume::bounded(1);
+        user.channel_to_parent = Some(parent);
+        let metric = SwanlingCustomMetric::Counter {
+            name: "orders".to_string(),
+            value: 1,
+        };
+        for _ in 0..3 {
+            assert!(user
+                .send_metric_to_parent(SwanlingMetric::Custom(metric.clone()))
+                .is_none());
+        }
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(user.dropped_metrics.load(Ordering::SeqCst), 2);
+
+        // Sending fails if the parent went away.
+        drop(metrics);
+        assert!(user
+            .send_metric_to_parent(SwanlingMetric::Custom(metric))
+            .is_some());
+    }
+
+    #[test]
+    fn region_header() {
+        // The point of presence is extracted from known CDN headers.
+        assert_eq!(region_from_header("cf-ray", "6d1f4f1b8c7a1234-SJC"), "SJC");
+        assert_eq!(
+            region_from_header(
+                "x-served-by",
+                "cache-iad-kiad7000025-IAD, cache-sjc10043-SJC"
+            ),
+            "SJC"
+        );
+        // Other headers are used as is.
+        assert_eq!(region_from_header("x-amz-cf-pop", " LHR62-C2 "), "LHR62-C2");
+    }
+
+    #[tokio::test]
+    async fn shared_values() {
+        let configuration = SwanlingConfiguration::parse_args_default(&EMPTY_ARGS).unwrap();
+        let base_url = get_base_url(Some("http://example.com/".to_string()), None, None).unwrap();
+        let mut user = SwanlingUser::new(0, base_url.clone(), 0, 0, &configuration, 0).unwrap();
+        let mut other_user = SwanlingUser::new(0, base_url, 0, 0, &configuration, 0).unwrap();
+        let shared_values = Arc::new(SwanlingSharedValues::default());
+        user.shared_values = shared_values.clone();
+        other_user.shared_values = shared_values.clone();
+
+        // Values that are never published time out.
+        let timeout = std::time::Duration::from_millis(50);
+        match other_user.get_shared_value("token", timeout).await {
+            Err(SwanlingTaskError::SharedValueTimeout { key }) => assert_eq!(key, "token"),
+            other => panic!("expected SharedValueTimeout, got {:?}", other),
+        }
+        assert!(!shared_values.needs_sync());
+
+        // Waiting users receive the value once another user publishes it.
+        let waiting = tokio::spawn(async move {
+            other_user
+                .get_shared_value("token", std::time::Duration::from_secs(5))
+                .await
+                .unwrap()
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(shared_values.needs_sync());
+        user.set_shared_value("token", "secret");
+        assert_eq!(waiting.await.unwrap(), "secret");
+        assert!(!shared_values.needs_sync());
+
+        // Published values are immediately available, and can be replaced.
+        user.set_shared_value("token", "other");
+        assert_eq!(
+            user.get_shared_value("token", timeout).await.unwrap(),
+            "other"
+        );
+
+        // Values published elsewhere are merged in.
+        shared_values.merge(vec![("fixture_id".to_string(), "42".to_string())]);
+        assert_eq!(shared_values.get("fixture_id"), Some("42".to_string()));
+    }
 }
@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use tokio::time;
 
 use crate::util;
@@ -9,33 +11,16 @@ use crate::util;
 /// implementation of the leaky bucket algorithm as a queue: instead of leaking the
 /// overflow we asynchronously block. More information on the leaky bucket algorithm
 /// can be found at: https://en.wikipedia.org/wiki/Leaky_bucket
+///
+/// The parent can change how many requests are allowed per second while the load test
+/// runs by storing a new value in `throttle_requests`.
 pub async fn throttle_main(
-    throttle_requests: usize,
+    throttle_requests: Arc<AtomicUsize>,
     throttle_receiver: flume::Receiver<bool>,
     parent_receiver: flume::Receiver<bool>,
 ) {
-    // Use microseconds to allow configurations up to 1,000,000 requests per second.
-    let mut sleep_duration = time::Duration::from_micros(1_000_000 / throttle_requests as u64);
-    let tokens_per_duration;
-
-    let ten_milliseconds = time::Duration::from_millis(10);
-    debug!(
-        "sleep_duration: {:?} ten_milliseconds: {:?}",
-        sleep_duration, ten_milliseconds
-    );
-
-    // Keep sleep_duration at least ~10ms as `delay_for` has millisecond granularity.
-    if sleep_duration < ten_milliseconds {
-        tokens_per_duration = (ten_milliseconds.as_nanos() / sleep_duration.as_nanos()) as u32;
-        sleep_duration *= tokens_per_duration;
-    } else {
-        tokens_per_duration = 1;
-    }
-
-    info!(
-        "throttle allowing {} request(s) every {:?}",
-        tokens_per_duration, sleep_duration
-    );
+    let mut current_throttle_requests = throttle_requests.load(Ordering::SeqCst);
+    let (mut sleep_duration, mut tokens_per_duration) = throttle_rate(current_throttle_requests);
 
     // One or more token gets removed from the throttle_receiver bucket at regular
     // intervals. The throttle_drift variable tracks how much time is spent on
@@ -58,6 +43,16 @@ pub async fn throttle_main(
             break;
         }
 
+        // Adjust the rate if the parent changed it.
+        let new_throttle_requests = throttle_requests.load(Ordering::SeqCst);
+        if new_throttle_requests != current_throttle_requests {
+            current_throttle_requests = new_throttle_requests;
+            let (new_sleep_duration, new_tokens_per_duration) =
+                throttle_rate(current_throttle_requests);
+            sleep_duration = new_sleep_duration;
+            tokens_per_duration = new_tokens_per_duration;
+        }
+
         // Remove tokens from the channel, freeing spots for request to be made.
         for token in 0..tokens_per_duration {
             // If the channel is empty, we will get an error, so stop trying to remove tokens.
@@ -68,3 +63,32 @@ pub async fn throttle_main(
         }
     }
 }
+
+// Determine how long to sleep, and how many tokens to remove from the channel each time,
+// to allow the configured number of requests per second.
+fn throttle_rate(throttle_requests: usize) -> (time::Duration, u32) {
+    // Use microseconds to allow configurations up to 1,000,000 requests per second.
+    let mut sleep_duration = time::Duration::from_micros(1_000_000 / throttle_requests as u64);
+    let tokens_per_duration;
+
+    let ten_milliseconds = time::Duration::from_millis(10);
+    debug!(
+        "sleep_duration: {:?} ten_milliseconds: {:?}",
+        sleep_duration, ten_milliseconds
+    );
+
+    // Keep sleep_duration at least ~10ms as `delay_for` has millisecond granularity.
+    if sleep_duration < ten_milliseconds {
+        tokens_per_duration = (ten_milliseconds.as_nanos() / sleep_duration.as_nanos()) as u32;
+        sleep_duration *= tokens_per_duration;
+    } else {
+        tokens_per_duration = 1;
+    }
+
+    info!(
+        "throttle allowing {} request(s) every {:?}",
+        tokens_per_duration, sleep_duration
+    );
+
+    (sleep_duration, tokens_per_duration)
+}
@@ -5,11 +5,15 @@ use std::time;
 use crate::get_worker_id;
 use crate::logger::SwanlingLog;
 use crate::metrics::{SwanlingMetric, SwanlingTaskMetric};
-use crate::swanling::{SwanlingTaskFunction, SwanlingTaskSet, SwanlingUser, SwanlingUserCommand};
+use crate::swanling::{
+    SwanlingTaskControl, SwanlingTaskFunction, SwanlingTaskScheduler, SwanlingTaskSet,
+    SwanlingUser, SwanlingUserCommand,
+};
 
 pub(crate) async fn user_main(
     thread_number: usize,
     thread_task_set: SwanlingTaskSet,
+    mut thread_scheduler: Box<dyn SwanlingTaskScheduler>,
     thread_user: SwanlingUser,
     thread_receiver: flume::Receiver<SwanlingUserCommand>,
     worker: bool,
@@ -28,7 +32,13 @@ pub(crate) async fn user_main(
         );
     }
 
+    // Optionally open the user's connection before it runs any tasks.
+    if thread_user.config.prewarm_connections {
+        thread_user.prewarm_connection().await;
+    }
+
     // User is starting, first invoke the weighted on_start tasks.
+    let mut aborted = false;
     if !thread_user.weighted_on_start_tasks.is_empty() {
         // Tasks are already weighted and scheduled, execute each in order.
         for (thread_task_index, thread_task_name) in &thread_user.weighted_on_start_tasks {
@@ -42,92 +52,181 @@ pub(crate) async fn user_main(
             let _todo =
                 invoke_task_function(function, &thread_user, *thread_task_index, thread_task_name)
                     .await;
+            // The task can skip the rest of the on_start tasks, or all normal tasks.
+            match thread_user.take_task_control() {
+                SwanlingTaskControl::Continue => (),
+                SwanlingTaskControl::SkipRest => break,
+                SwanlingTaskControl::AbortUser => {
+                    aborted = true;
+                    break;
+                }
+            }
         }
     }
 
-    // If normal tasks are defined, loop launching tasks until parent tells us to stop.
-    if !thread_user.weighted_tasks.is_empty() {
-        let mut position;
-        'launch_tasks: loop {
-            // Start at the first task in thread_user.weighted_tasks.
-            position = 0;
-            thread_user.position.store(position, Ordering::SeqCst);
+    // Loop launching the tasks chosen by the scheduler until parent tells us to stop.
+    let mut position;
+    let mut paused = false;
+    // Count passes through the tasks, tracking whether one has started but not finished.
+    let mut iterations = 0;
+    let mut in_iteration = false;
+    'launch_tasks: loop {
+        // Start at the first task in thread_user.weighted_tasks.
+        position = 0;
+        thread_user.position.store(position, Ordering::SeqCst);
+
+        // An on_start task stopped the user before it ran any normal tasks.
+        if aborted {
+            break;
+        }
+
+        // Retire the user once it has run all of its iterations.
+        if let Some(iterations_per_user) = thread_user.config.iterations_per_user {
+            if iterations >= iterations_per_user {
+                debug!("user {} completed {} iterations", thread_number, iterations);
+                break;
+            }
+        }
+
+        // Stop launching tasks if the scheduler has nothing for the user to do.
+        let mut next_task = thread_scheduler.next_task(&thread_user, &thread_task_set);
+        if next_task.is_none() {
+            break;
+        }
 
-            // Tracks the time it takes to loop through all SwanlingTasks when Coordinated Omission
-            // Mitigation is enabled.
-            thread_user.update_request_cadence(thread_number).await;
+        // Tracks the time it takes to loop through all SwanlingTasks when Coordinated Omission
+        // Mitigation is enabled.
+        thread_user.update_request_cadence(thread_number).await;
 
-            for (thread_task_index, thread_task_name) in &thread_user.weighted_tasks {
-                // Determine which task we're going to run next.
-                let function = &thread_task_set.tasks[*thread_task_index].function;
+        while let Some(thread_task_index) = next_task {
+            let thread_task_name = match thread_task_set.tasks.get(thread_task_index) {
+                Some(thread_task) => &thread_task.name,
+                None => {
+                    warn!(
+                        "user {} scheduled task {} which doesn't exist in {}",
+                        thread_number, thread_task_index, thread_task_set.name
+                    );
+                    break 'launch_tasks;
+                }
+            };
+
+            // Don't start the next task while the load test is paused.
+            let pause_timer = time::Instant::now();
+            loop {
+                if received_exit(&thread_receiver, &mut paused) {
+                    break 'launch_tasks;
+                }
+                if !paused {
+                    break;
+                }
+                tokio::time::sleep(time::Duration::from_millis(100)).await;
+            }
+            // Time spent paused is not a delay for Coordinated Omission Mitigation.
+            thread_user.slept.fetch_add(
+                (time::Instant::now() - pause_timer).as_millis() as u64,
+                Ordering::SeqCst,
+            );
+
+            // Determine which task we're going to run next.
+            let function = &thread_task_set.tasks[thread_task_index].function;
+            debug!(
+                "launching on_start {} task from {}",
+                thread_task_name, thread_task_set.name
+            );
+            // Invoke the task function.
+            in_iteration = true;
+            let _todo =
+                invoke_task_function(function, &thread_user, thread_task_index, thread_task_name)
+                    .await;
+
+            // The task can stop the user right away, or skip the rest of the tasks after
+            // the usual wait.
+            let task_control = thread_user.take_task_control();
+            if task_control == SwanlingTaskControl::AbortUser {
                 debug!(
-                    "launching on_start {} task from {}",
-                    thread_task_name, thread_task_set.name
+                    "user {} aborted by task {}",
+                    thread_number, thread_task_name
                 );
-                // Invoke the task function.
-                let _todo = invoke_task_function(
-                    function,
-                    &thread_user,
-                    *thread_task_index,
-                    thread_task_name,
-                )
-                .await;
-
-                // Prepare to sleep for a random value from min_wait to max_wait.
-                let wait_time = if thread_user.max_wait > 0 {
-                    rand::thread_rng().gen_range(thread_user.min_wait..thread_user.max_wait)
-                } else {
-                    0
-                };
-
-                // Counter to track how long we've slept, waking regularly to check for messages.
-                let mut slept: usize = 0;
-
-                // Wake every second to check if the parent thread has told us to exit.
-                let mut in_sleep_loop = true;
-                // Track the time slept for Coordinated Omission Mitigation.
-                let sleep_timer = time::Instant::now();
-                while in_sleep_loop {
-                    let mut message = thread_receiver.try_recv();
-                    while message.is_ok() {
-                        match message.unwrap() {
-                            // Time to exit, break out of launch_tasks loop.
-                            SwanlingUserCommand::Exit => {
-                                break 'launch_tasks;
-                            }
-                            command => {
-                                debug!("ignoring unexpected SwanlingUserCommand: {:?}", command);
-                            }
-                        }
-                        message = thread_receiver.try_recv();
-                    }
-                    if thread_user.max_wait > 0 {
-                        let sleep_duration = time::Duration::from_secs(1);
-                        debug!(
-                            "user {} from {} sleeping {:?} second...",
-                            thread_number, thread_task_set.name, sleep_duration
-                        );
-                        tokio::time::sleep(sleep_duration).await;
-                        slept += 1;
-                        if slept > wait_time {
-                            in_sleep_loop = false;
-                        }
-                    } else {
+                break 'launch_tasks;
+            }
+
+            // Retire the user once too many of its requests have failed in a row.
+            if let Some(max_failures) = thread_user.config.user_max_consecutive_failures {
+                if thread_user.consecutive_failures() >= max_failures {
+                    info!(
+                        "retiring user {} from {} after {} consecutive failed requests",
+                        thread_number, thread_task_set.name, max_failures
+                    );
+                    break 'launch_tasks;
+                }
+            }
+
+            // Tasks can override the wait time of their task set.
+            let (min_wait, max_wait) = thread_task_set.tasks[thread_task_index]
+                .wait_time
+                .unwrap_or((thread_user.min_wait, thread_user.max_wait));
+
+            // Prepare to sleep for a random value from min_wait to max_wait.
+            let wait_time = random_wait_time(min_wait, max_wait);
+
+            // Counter to track how long we've slept, waking regularly to check for messages.
+            let mut slept: usize = 0;
+
+            // Wake every second to check if the parent thread has told us to exit.
+            let mut in_sleep_loop = true;
+            // Track the time slept for Coordinated Omission Mitigation.
+            let sleep_timer = time::Instant::now();
+            while in_sleep_loop {
+                // Time to exit, break out of launch_tasks loop.
+                if received_exit(&thread_receiver, &mut paused) {
+                    break 'launch_tasks;
+                }
+                if max_wait > 0 {
+                    let sleep_duration = time::Duration::from_secs(1);
+                    debug!(
+                        "user {} from {} sleeping {:?} second...",
+                        thread_number, thread_task_set.name, sleep_duration
+                    );
+                    tokio::time::sleep(sleep_duration).await;
+                    slept += 1;
+                    if slept > wait_time {
                         in_sleep_loop = false;
                     }
+                } else {
+                    in_sleep_loop = false;
                 }
-                // Track how much time the SwanlingUser sleeps during this loop through all SwanlingTasks,
-                // used by Coordinated Omission Mitigation.
-                thread_user.slept.fetch_add(
-                    (time::Instant::now() - sleep_timer).as_millis() as u64,
-                    Ordering::SeqCst,
-                );
+            }
+            // Track how much time the SwanlingUser sleeps during this loop through all SwanlingTasks,
+            // used by Coordinated Omission Mitigation.
+            thread_user.slept.fetch_add(
+                (time::Instant::now() - sleep_timer).as_millis() as u64,
+                Ordering::SeqCst,
+            );
 
-                // Move to the next task in thread_user.weighted_tasks.
-                position += 1;
-                thread_user.position.store(position, Ordering::SeqCst);
+            // Skipping the rest of the tasks ends the pass early, it still counts as an
+            // iteration.
+            if task_control == SwanlingTaskControl::SkipRest {
+                thread_scheduler.skip_rest(&thread_user);
+                complete_iteration(&thread_user, &mut iterations, &mut in_iteration);
+                continue 'launch_tasks;
             }
+
+            // Move to the next task chosen by the scheduler.
+            position += 1;
+            thread_user.position.store(position, Ordering::SeqCst);
+            next_task = thread_scheduler.next_task(&thread_user, &thread_task_set);
         }
+
+        // The scheduler has no more tasks for this pass.
+        complete_iteration(&thread_user, &mut iterations, &mut in_iteration);
+    }
+
+    // The user stopped part way through a pass.
+    if in_iteration {
+        thread_user
+            .iterations
+            .in_progress
+            .fetch_add(1, Ordering::SeqCst);
     }
 
     // User is exiting, first invoke the weighted on_stop tasks.
@@ -144,6 +243,10 @@ pub(crate) async fn user_main(
             let _todo =
                 invoke_task_function(function, &thread_user, *thread_task_index, thread_task_name)
                     .await;
+            // The task can skip the rest of the on_stop tasks.
+            if thread_user.take_task_control() != SwanlingTaskControl::Continue {
+                break;
+            }
         }
     }
 
@@ -163,6 +266,44 @@ pub(crate) async fn user_main(
     }
 }
 
+// Process all messages from the parent thread, tracking whether the load test is paused.
+// Returns true if the user has been told to exit.
+fn received_exit(
+    thread_receiver: &flume::Receiver<SwanlingUserCommand>,
+    paused: &mut bool,
+) -> bool {
+    while let Ok(command) = thread_receiver.try_recv() {
+        match command {
+            SwanlingUserCommand::Exit => return true,
+            SwanlingUserCommand::Wait => *paused = true,
+            SwanlingUserCommand::Run => *paused = false,
+            command => {
+                debug!("ignoring unexpected SwanlingUserCommand: {:?}", command);
+            }
+        }
+    }
+    false
+}
+
+// Count a completed pass through the tasks.
+fn complete_iteration(thread_user: &SwanlingUser, iterations: &mut usize, in_iteration: &mut bool) {
+    *iterations += 1;
+    *in_iteration = false;
+    thread_user
+        .iterations
+        .completed
+        .fetch_add(1, Ordering::SeqCst);
+}
+
+// Randomly select how many seconds to sleep, from min_wait to max_wait inclusively.
+fn random_wait_time(min_wait: usize, max_wait: usize) -> usize {
+    if max_wait > 0 {
+        rand::thread_rng().gen_range(min_wait..=max_wait)
+    } else {
+        0
+    }
+}
+
 // Invoke the task function, collecting task metrics.
 async fn invoke_task_function(
     function: &SwanlingTaskFunction,
@@ -170,6 +311,10 @@ async fn invoke_task_function(
     thread_task_index: usize,
     thread_task_name: &str,
 ) -> Result<(), flume::SendError<Option<SwanlingLog>>> {
+    // Only attribute requests made by this task to it in the task profile.
+    if !thread_user.config.task_profile.is_empty() {
+        thread_user.take_task_request_times();
+    }
     let started = time::Instant::now();
     let mut raw_task = SwanlingTaskMetric::new(
         thread_user.started.elapsed().as_millis(),
@@ -178,8 +323,26 @@ async fn invoke_task_function(
         thread_task_name.to_string(),
         thread_user.weighted_users_index,
     );
+    // If enabled, trace each task iteration in its own span.
+    #[cfg(feature = "tracing")]
+    let success = tracing::Instrument::instrument(
+        function(thread_user),
+        tracing::info_span!("task", task = thread_task_name),
+    )
+    .await
+    .is_ok();
+    #[cfg(not(feature = "tracing"))]
     let success = function(&thread_user).await.is_ok();
-    raw_task.set_time(started.elapsed().as_millis(), success);
+    // Think time is part of the task, unless excluded.
+    let mut task_time = started.elapsed().as_millis();
+    let think_time = thread_user.take_think_time();
+    if thread_user.config.exclude_think_time {
+        task_time = task_time.saturating_sub(think_time as u128);
+    }
+    raw_task.set_time(task_time, success);
+    if !thread_user.config.task_profile.is_empty() {
+        raw_task.request_times = thread_user.take_task_request_times();
+    }
 
     // Exit if all metrics or task metrics are disabled.
     if thread_user.config.no_metrics || thread_user.config.no_task_metrics {
@@ -193,11 +356,8 @@ async fn invoke_task_function(
         }
     }
 
-    // Otherwise send metrics to parent.
-    if let Some(parent) = thread_user.channel_to_parent.clone() {
-        // Best effort metrics.
-        let _ = parent.send(SwanlingMetric::Task(raw_task));
-    }
+    // Otherwise send metrics to parent, best effort.
+    thread_user.send_metric_to_parent(SwanlingMetric::Task(raw_task));
 
     Ok(())
 }
@@ -1,16 +1,31 @@
 //! Utility functions used by Swanling, and available when writing load tests.
 
+use lazy_static::lazy_static;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use regex::Regex;
 use std::cmp::{max, min};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time;
 use url::Url;
 
 use crate::SwanlingError;
 
+lazy_static! {
+    /// The flag set when ctrl-c is caught. The handler can only be set once per process,
+    /// so each load test replaces the flag it sets.
+    static ref CTRLC_CANCELED: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+}
+
 /// Parse a string representing a time span and return the number of seconds.
 ///
 /// Can be specified as an integer, indicating seconds. Or can use integers
@@ -397,11 +412,313 @@ pub fn is_valid_host(host: &str) -> Result<bool, SwanlingError> {
     Ok(true)
 }
 
+/// Parse a curl-style `HOST:PORT:ADDR` mapping, returning the host name and the
+/// socket address it should resolve to.
+///
+/// IPv6 addresses can optionally be wrapped in square brackets.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// // Map a host name to an IPv4 address.
+/// let (host, address) = util::parse_resolve("api.example.com:443:10.0.0.42").unwrap();
+/// assert_eq!(host, "api.example.com");
+/// assert_eq!(address.to_string(), "10.0.0.42:443");
+///
+/// // Map a host name to an IPv6 address.
+/// assert!(util::parse_resolve("api.example.com:80:[::1]").is_some());
+///
+/// // A port is required.
+/// assert!(util::parse_resolve("api.example.com:10.0.0.42").is_none());
+/// ```
+pub fn parse_resolve(mapping: &str) -> Option<(String, SocketAddr)> {
+    let mut parts = mapping.splitn(3, ':');
+    let host = parts.next()?;
+    let port: u16 = parts.next()?.parse().ok()?;
+    let address = parts.next()?.trim_start_matches('[').trim_end_matches(']');
+    let ip: IpAddr = address.parse().ok()?;
+    if host.is_empty() {
+        return None;
+    }
+    Some((host.to_string(), SocketAddr::new(ip, port)))
+}
+
+/// Parse a `NAME:WORKER` pin, returning the name of the task set and the id of the Worker
+/// it is pinned to. Workers are numbered from 1 in the order they connect to the Manager.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// // Pin a task set to the second Worker.
+/// let (task_set, worker) = util::parse_pin_task_set("WriteHeavy:2").unwrap();
+/// assert_eq!(task_set, "WriteHeavy");
+/// assert_eq!(worker, 2);
+///
+/// // The Worker is a number starting from 1.
+/// assert!(util::parse_pin_task_set("WriteHeavy:0").is_none());
+/// assert!(util::parse_pin_task_set("WriteHeavy").is_none());
+/// ```
+pub fn parse_pin_task_set(pin: &str) -> Option<(String, usize)> {
+    let mut parts = pin.rsplitn(2, ':');
+    let worker: usize = parts.next()?.trim().parse().ok()?;
+    let task_set = parts.next()?.trim();
+    if task_set.is_empty() || worker == 0 {
+        return None;
+    }
+    Some((task_set.to_string(), worker))
+}
+
+/// Expand environment variables referenced as `${NAME}` in a value.
+///
+/// Returns an explanation of the error if a referenced variable is not set, or if a
+/// reference is not closed.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// std::env::set_var("SWANLING_EXAMPLE_HOST", "example.com");
+/// assert_eq!(
+///     util::expand_env_vars("https://${SWANLING_EXAMPLE_HOST}/").unwrap(),
+///     "https://example.com/"
+/// );
+///
+/// // Values without references are unchanged.
+/// assert_eq!(util::expand_env_vars("http://localhost/").unwrap(), "http://localhost/");
+///
+/// // Referencing a variable that isn't set is an error.
+/// assert!(util::expand_env_vars("${SWANLING_EXAMPLE_UNSET}").is_err());
+/// ```
+pub fn expand_env_vars(value: &str) -> Result<String, String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut remaining = value;
+    while let Some(start) = remaining.find("${") {
+        expanded.push_str(&remaining[..start]);
+        let reference = &remaining[start + 2..];
+        let end = match reference.find('}') {
+            Some(e) => e,
+            None => return Err("Missing closing } for ${.".to_string()),
+        };
+        let name = &reference[..end];
+        if name.is_empty() {
+            return Err("Environment variable name can not be empty.".to_string());
+        }
+        match std::env::var(name) {
+            Ok(v) => expanded.push_str(&v),
+            Err(_) => return Err(format!("Environment variable {} is not set.", name)),
+        }
+        remaining = &reference[end + 1..];
+    }
+    expanded.push_str(remaining);
+
+    Ok(expanded)
+}
+
+/// Build a weighted list of User-Agents from the contents of a `--user-agents` file.
+///
+/// Each line holds a User-Agent, optionally preceded by an integer weight and a space.
+/// Lines without a weight have a weight of 1, and empty lines and lines starting with `#`
+/// are ignored. User-Agents are allocated round robin, so each appears in the returned
+/// list as many times as its weight, spread out as evenly as possible.
+///
+/// Returns an explanation of the error if a weight is 0, or if there are no User-Agents.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// let user_agents = util::weight_user_agents("# Browsers and bots.\n3 Mozilla/5.0\nbot/1.0").unwrap();
+/// assert_eq!(user_agents, vec!["Mozilla/5.0", "bot/1.0", "Mozilla/5.0", "Mozilla/5.0"]);
+///
+/// // A weight of 0 is an error.
+/// assert!(util::weight_user_agents("0 Mozilla/5.0").is_err());
+/// ```
+pub fn weight_user_agents(contents: &str) -> Result<Vec<String>, String> {
+    let mut user_agents = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (weight, user_agent) = match line.split_once(' ') {
+            Some((weight, user_agent)) => match weight.parse::<usize>() {
+                Ok(weight) => (weight, user_agent.trim()),
+                Err(_) => (1, line),
+            },
+            None => (1, line),
+        };
+        if weight == 0 {
+            return Err(format!("Weight of {} must be greater than 0.", user_agent));
+        }
+        user_agents.push((weight, user_agent));
+    }
+    if user_agents.is_empty() {
+        return Err("No User-Agents found.".to_string());
+    }
+
+    // Allocate User-Agents round robin until every weight is used up.
+    let mut weighted_user_agents = Vec::new();
+    let rounds = user_agents.iter().map(|(weight, _)| *weight).max().unwrap();
+    for round in 0..rounds {
+        for (weight, user_agent) in &user_agents {
+            if round < *weight {
+                weighted_user_agents.push(user_agent.to_string());
+            }
+        }
+    }
+    Ok(weighted_user_agents)
+}
+
+/// Return a random number of milliseconds from 0 up to and including `window`, used to
+/// stagger Workers connecting to the Manager.
+///
+/// The delay is the same every time it's calculated with the same seed.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// let delay = util::startup_jitter(500, None);
+/// assert!(delay <= 500);
+///
+/// // The same seed always returns the same delay.
+/// assert_eq!(util::startup_jitter(500, Some(42)), util::startup_jitter(500, Some(42)));
+/// ```
+pub fn startup_jitter(window: usize, seed: Option<u64>) -> u64 {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    rng.gen_range(0..=window as u64)
+}
+
+/// Return true if a request is one of the `rate` fraction of requests that are sampled,
+/// used to limit how many requests are written to the request log.
+///
+/// With a seed, the same requests are sampled every time they're identified by the same
+/// `user` and `request` number.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// // A rate of 1.0 samples every request, and a rate of 0.0 none.
+/// assert!(util::sample_request(1.0, None, 0, 1));
+/// assert!(!util::sample_request(0.0, None, 0, 1));
+///
+/// // The same seed always samples the same requests.
+/// assert_eq!(
+///     util::sample_request(0.5, Some(42), 3, 7),
+///     util::sample_request(0.5, Some(42), 3, 7)
+/// );
+/// ```
+pub fn sample_request(rate: f32, seed: Option<u64>, user: usize, request: u64) -> bool {
+    let sample: f32 = match seed {
+        Some(seed) => {
+            let mut hasher = DefaultHasher::new();
+            (seed, user, request).hash(&mut hasher);
+            StdRng::seed_from_u64(hasher.finish()).gen()
+        }
+        None => rand::thread_rng().gen(),
+    };
+    sample < rate
+}
+
+/// Return the maximum number of file descriptors this process can have open, or
+/// `None` if there is no limit or it can't be determined.
+///
+/// Every connection a load test opens uses a file descriptor.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// if let Some(limit) = util::open_file_limit() {
+///     println!("can open up to {} files and connections", limit);
+/// }
+/// ```
+#[cfg(unix)]
+pub fn open_file_limit() -> Option<usize> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // getrlimit only writes to the struct it's passed.
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0
+        || limit.rlim_cur == libc::RLIM_INFINITY
+    {
+        return None;
+    }
+    limit.rlim_cur.try_into().ok()
+}
+
+/// Return the maximum number of file descriptors this process can have open, or
+/// `None` if there is no limit or it can't be determined.
+#[cfg(not(unix))]
+pub fn open_file_limit() -> Option<usize> {
+    None
+}
+
+/// Return up to the given number of lines from the end of a file, oldest first.
+///
+/// The file is read backwards from its end, so only the tail of a large file, such as
+/// a request log, is loaded into memory.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// if let Ok(lines) = util::tail_lines("requests.log", 10) {
+///     for line in lines {
+///         println!("{}", line);
+///     }
+/// }
+/// ```
+pub fn tail_lines(path: &str, lines: usize) -> io::Result<Vec<String>> {
+    const BLOCK_SIZE: u64 = 64 * 1024;
+
+    let mut file = File::open(path)?;
+    let mut position = file.seek(SeekFrom::End(0))?;
+    let mut tail = Vec::new();
+    let mut newlines = 0;
+    // Read one block at a time, stopping once the start of the oldest line is found.
+    while position > 0 && newlines <= lines {
+        let block = position.min(BLOCK_SIZE);
+        position -= block;
+        file.seek(SeekFrom::Start(position))?;
+        let mut buffer = vec![0; block as usize];
+        file.read_exact(&mut buffer)?;
+        newlines += buffer.iter().filter(|&&byte| byte == b'\n').count();
+        buffer.append(&mut tail);
+        tail = buffer;
+    }
+
+    // With more newlines than lines requested, a partially read first line is skipped.
+    let text = String::from_utf8_lossy(&tail);
+    let all: Vec<&str> = text.lines().collect();
+    Ok(all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect())
+}
+
 // Internal helper to configure the control-c handler. Shutdown cleanly on the first
 // ctrl-c. Exit abruptly on the second ctrl-c.
 pub(crate) fn setup_ctrlc_handler(canceled: &Arc<AtomicBool>) {
-    let caught_ctrlc = canceled.clone();
+    let mut ctrlc_canceled = CTRLC_CANCELED.lock().unwrap();
+    let handler_set = ctrlc_canceled.is_some();
+    *ctrlc_canceled = Some(canceled.clone());
+    // The handler set by an earlier load test in this process sets the new flag.
+    if handler_set {
+        return;
+    }
+
     match ctrlc::set_handler(move || {
+        let caught_ctrlc = match CTRLC_CANCELED.lock().unwrap().as_ref() {
+            Some(caught_ctrlc) => caught_ctrlc.clone(),
+            None => return,
+        };
         // We've caught a ctrl-c, determine if it's the first time or an additional time.
         if caught_ctrlc.load(Ordering::SeqCst) {
             warn!("caught another ctrl-c, exiting immediately...");
@@ -418,6 +735,14 @@ pub(crate) fn setup_ctrlc_handler(canceled: &Arc<AtomicBool>) {
     }
 }
 
+/// Returns true if ctrl-c was caught during the most recent load test in this process.
+pub(crate) fn ctrlc_caught() -> bool {
+    match CTRLC_CANCELED.lock().unwrap().as_ref() {
+        Some(canceled) => canceled.load(Ordering::SeqCst),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -571,6 +896,86 @@ mod tests {
         assert!((get_hatch_rate(Some("1.1.1".to_string())) - 1.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn resolve() {
+        let (host, address) = parse_resolve("example.com:443:10.0.0.42").unwrap();
+        assert_eq!(host, "example.com");
+        assert_eq!(address, SocketAddr::from(([10, 0, 0, 42], 443)));
+        let (host, address) = parse_resolve("example.com:8080:[::1]").unwrap();
+        assert_eq!(host, "example.com");
+        assert_eq!(address.to_string(), "[::1]:8080");
+        assert!(parse_resolve("example.com:8080:::1").is_some());
+        assert!(parse_resolve("example.com:10.0.0.42").is_none());
+        assert!(parse_resolve("example.com:https:10.0.0.42").is_none());
+        assert!(parse_resolve("example.com:443:example.org").is_none());
+        assert!(parse_resolve(":443:10.0.0.42").is_none());
+    }
+
+    #[test]
+    fn env_vars() {
+        std::env::set_var("SWANLING_TEST_HOST", "example.com");
+        std::env::set_var("SWANLING_TEST_PORT", "8080");
+        std::env::remove_var("SWANLING_TEST_UNSET");
+        assert_eq!(expand_env_vars("example.com").unwrap(), "example.com");
+        assert_eq!(
+            expand_env_vars("${SWANLING_TEST_HOST}").unwrap(),
+            "example.com"
+        );
+        assert_eq!(
+            expand_env_vars("http://${SWANLING_TEST_HOST}:${SWANLING_TEST_PORT}/").unwrap(),
+            "http://example.com:8080/"
+        );
+        // Only braced references are expanded.
+        assert_eq!(
+            expand_env_vars("$SWANLING_TEST_HOST").unwrap(),
+            "$SWANLING_TEST_HOST"
+        );
+        assert!(expand_env_vars("${SWANLING_TEST_UNSET}").is_err());
+        assert!(expand_env_vars("${SWANLING_TEST_HOST").is_err());
+        assert!(expand_env_vars("${}").is_err());
+    }
+
+    #[test]
+    fn user_agents() {
+        assert_eq!(weight_user_agents("bot/1.0").unwrap(), vec!["bot/1.0"]);
+        // User-Agents can contain spaces.
+        assert_eq!(
+            weight_user_agents("2 Mozilla/5.0 (X11; Linux x86_64)\n\n# comment\nbot/1.0").unwrap(),
+            vec![
+                "Mozilla/5.0 (X11; Linux x86_64)",
+                "bot/1.0",
+                "Mozilla/5.0 (X11; Linux x86_64)"
+            ]
+        );
+        // Allocated round robin.
+        assert_eq!(
+            weight_user_agents("1 a\n3 b\n2 c").unwrap(),
+            vec!["a", "b", "c", "b", "c", "b"]
+        );
+        assert!(weight_user_agents("0 bot/1.0").is_err());
+        assert!(weight_user_agents("").is_err());
+        assert!(weight_user_agents("# comment").is_err());
+    }
+
+    #[test]
+    fn tail() {
+        let path = std::env::temp_dir().join(format!("swanling-tail-{}.log", std::process::id()));
+        // Long lines, so the tail spans several blocks.
+        let contents: Vec<String> = (0..200)
+            .map(|i| format!("{} {}", i, "x".repeat(1_000)))
+            .collect();
+        std::fs::write(&path, contents.join("\n") + "\n").unwrap();
+        let path_str = path.to_str().unwrap();
+
+        assert_eq!(tail_lines(path_str, 10).unwrap(), contents[190..].to_vec());
+        assert_eq!(tail_lines(path_str, 150).unwrap(), contents[50..].to_vec());
+        assert_eq!(tail_lines(path_str, 1_000).unwrap(), contents);
+        assert!(tail_lines(path_str, 0).unwrap().is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(tail_lines(path_str, 10).is_err());
+    }
+
     #[test]
     fn valid_host() {
         assert!(is_valid_host("http://example.com").is_ok());
@@ -1,17 +1,26 @@
-use gumdrop::Options;
+use lazy_static::lazy_static;
 use nng::*;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_cbor::Value;
+use std::collections::BTreeMap;
 use std::io::BufWriter;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::{thread, time};
 use url::Url;
 
-const EMPTY_ARGS: Vec<&str> = vec![];
-
-use crate::manager::SwanlingUserInitializer;
-use crate::metrics::{SwanlingErrorMetrics, SwanlingRequestMetrics, SwanlingTaskMetrics};
+use crate::manager::{SwanlingUserInitializer, SwanlingWorkerInitializer};
+use crate::metrics::{
+    SwanlingCustomMetricAggregate, SwanlingCustomMetrics, SwanlingErrorMetrics, SwanlingIterations,
+    SwanlingRequestMetrics, SwanlingTaskMetrics,
+};
 use crate::swanling::{SwanlingUser, SwanlingUserCommand};
-use crate::{get_worker_id, AttackMode, SwanlingAttack, SwanlingConfiguration, WORKER_ID};
+use crate::util;
+use crate::{
+    configure_gaggle_socket, gaggle_address, get_worker_id, AttackMode, SwanlingAttack,
+    SwanlingConfiguration, WORKER_ID,
+};
 
 /// Workers send GaggleMetrics to the Manager process to be aggregated together.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,10 +29,97 @@ pub enum GaggleMetrics {
     WorkerInit(u64),
     /// Swanling request metrics.
     Requests(SwanlingRequestMetrics),
+    /// Swanling request metrics grouped by the value of the `--group-by` label.
+    Groups(SwanlingRequestMetrics),
     /// Swanling task metrics.
     Tasks(SwanlingTaskMetrics),
     /// Swanling error metrics.
     Errors(SwanlingErrorMetrics),
+    /// Swanling custom counters and gauges.
+    Custom(SwanlingCustomMetrics),
+    /// Acknowledges a SwanlingUserCommand::Reconfigure, describing each change applied.
+    Reconfigured(Vec<String>),
+    /// The most recent lines of the Worker's request log, sent when requested with
+    /// SwanlingUserCommand::SendRequestLog. The name is empty if the Worker isn't
+    /// logging requests.
+    RequestLog {
+        /// The file name of the request log.
+        name: String,
+        /// The last lines of the request log, oldest first.
+        lines: Vec<String>,
+    },
+    /// Values published with SwanlingUser::set_shared_value since the last push.
+    SharedValues(Vec<(String, String)>),
+    /// How many metric samples were dropped since the last push because the
+    /// `--worker-metrics-buffer` was full.
+    Dropped(usize),
+    /// How many iterations the Worker's users ran, if limited with
+    /// `--iterations-per-user`. Sent with the final metrics.
+    Iterations(SwanlingIterations),
+    /// The Worker's system clock when the metrics were pushed, in milliseconds since the
+    /// Unix epoch, and how many milliseconds the Manager took to answer the previous push.
+    /// The Manager uses these to estimate how far the Worker's clock is from its own.
+    Clock {
+        /// When the metrics were pushed, according to the Worker's clock.
+        sent: u64,
+        /// How long the previous push took to be answered, in milliseconds.
+        round_trip: u64,
+    },
+}
+
+/// How many lines from the end of its request log a Worker sends the Manager.
+pub(crate) const REQUEST_LOG_TAIL_LINES: usize = 10_000;
+
+// How many milliseconds the Manager took to answer the last push of metrics.
+lazy_static! {
+    static ref ROUND_TRIP: AtomicUsize = AtomicUsize::new(0);
+}
+
+// How many Workers this process has started, so Workers sharing a process are spread
+// across the Manager's sockets too.
+lazy_static! {
+    static ref WORKERS_STARTED: AtomicUsize = AtomicUsize::new(0);
+}
+
+/// Build a GaggleMetrics::Clock with the Worker's current system clock, sent first with
+/// each push of metrics so the Manager can place them on its own timeline.
+pub(crate) fn clock_metric() -> GaggleMetrics {
+    let sent = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+    GaggleMetrics::Clock {
+        sent,
+        round_trip: ROUND_TRIP.load(Ordering::Relaxed) as u64,
+    }
+}
+
+/// Build a GaggleMetrics::RequestLog with the last lines of the Worker's request log,
+/// sent to the Manager when it asks for them.
+pub(crate) fn request_log_metric(request_log: &str) -> GaggleMetrics {
+    if request_log.is_empty() {
+        return GaggleMetrics::RequestLog {
+            name: String::new(),
+            lines: Vec::new(),
+        };
+    }
+    // Lines still buffered by the logger aren't in the file yet, so the very latest
+    // requests may be missing.
+    let lines = util::tail_lines(request_log, REQUEST_LOG_TAIL_LINES).unwrap_or_else(|e| {
+        warn!(
+            "[{}] failed to read request log {}: {}",
+            get_worker_id(),
+            request_log,
+            e
+        );
+        Vec::new()
+    });
+    let name = std::path::Path::new(request_log)
+        .file_name()
+        .map_or(request_log.to_string(), |name| {
+            name.to_string_lossy().to_string()
+        });
+    GaggleMetrics::RequestLog { name, lines }
 }
 
 // If pipe closes unexpectedly, panic.
@@ -49,11 +145,141 @@ pub fn register_shutdown_pipe_handler(manager: &Socket) {
         .expect("failed to set up new pipe handler");
 }
 
+// Sleep for the given duration, waking regularly to stop early if canceled. Returns true
+// if canceled.
+fn sleep_unless_canceled(duration: time::Duration, canceled: &AtomicBool) -> bool {
+    let started = time::Instant::now();
+    while !canceled.load(Ordering::SeqCst) {
+        let remaining = duration.saturating_sub(started.elapsed());
+        if remaining == time::Duration::from_millis(0) {
+            return false;
+        }
+        thread::sleep(remaining.min(time::Duration::from_millis(100)));
+    }
+    true
+}
+
+// Exit cleanly when canceled before connecting to the manager, as there's nothing to
+// shut down yet.
+fn exit_before_connecting(address: &str) -> ! {
+    info!(
+        "canceled while waiting to connect to manager at {}, exiting",
+        address
+    );
+    std::process::exit(0);
+}
+
+// Decode the initializer sent by the manager. Fields added or removed in another version
+// of the load test are tolerated by serde defaults. If it still doesn't decode, each user
+// is decoded on its own, skipping users that don't parse, and each remaining field is
+// decoded on its own to name exactly what didn't parse.
+pub(crate) fn decode_initializer(
+    msg: &[u8],
+) -> std::result::Result<SwanlingWorkerInitializer, String> {
+    let error = match serde_cbor::from_slice::<SwanlingWorkerInitializer>(msg) {
+        Ok(initializer) => return Ok(initializer),
+        Err(error) => error,
+    };
+    let mut fields = match serde_cbor::from_slice(msg) {
+        Ok(Value::Map(fields)) => fields,
+        _ => {
+            return Err(format!(
+                "message of {} bytes isn't an initializer: {}",
+                msg.len(),
+                error
+            ))
+        }
+    };
+
+    let mut users = Vec::new();
+    let mut sent_users = 0;
+    if let Some(Value::Array(values)) = fields.remove(&Value::Text("users".to_string())) {
+        sent_users = values.len();
+        for (index, value) in values.into_iter().enumerate() {
+            match serde_cbor::value::from_value::<SwanlingUserInitializer>(value) {
+                Ok(user) => users.push(user),
+                Err(e) => error!(
+                    "skipping user {} from manager that didn't parse: {}",
+                    index + 1,
+                    e
+                ),
+            }
+        }
+    }
+
+    let mut problems = Vec::new();
+    for (key, value) in &fields {
+        let field = match key {
+            Value::Text(field) => field.as_str(),
+            _ => continue,
+        };
+        match value {
+            // Name the options of the configuration that don't parse.
+            Value::Map(options) if field == "config" => {
+                problems.extend(
+                    undecodable_fields::<SwanlingConfiguration>(options)
+                        .into_iter()
+                        .map(|problem| format!("config.{}", problem)),
+                );
+            }
+            _ => problems.extend(undecodable_fields::<SwanlingWorkerInitializer>(
+                &[(key.clone(), value.clone())].iter().cloned().collect(),
+            )),
+        }
+    }
+    if !problems.is_empty() {
+        return Err(format!(
+            "unable to decode initializer from manager: {}",
+            problems.join(", ")
+        ));
+    }
+
+    let mut initializer: SwanlingWorkerInitializer =
+        serde_cbor::value::from_value(Value::Map(fields))
+            .map_err(|e| format!("unable to decode initializer from manager: {}", e))?;
+    warn!(
+        "decoded {} of {} users in the initializer from manager",
+        users.len(),
+        sent_users
+    );
+    initializer.users = users;
+    Ok(initializer)
+}
+
+// Decode each field of a map on its own, returning the name of each that doesn't parse
+// and why. Relies on every other field of T having a serde default.
+fn undecodable_fields<T: DeserializeOwned>(fields: &BTreeMap<Value, Value>) -> Vec<String> {
+    fields
+        .iter()
+        .filter_map(|(key, value)| {
+            let field: BTreeMap<Value, Value> =
+                [(key.clone(), value.clone())].iter().cloned().collect();
+            serde_cbor::value::from_value::<T>(Value::Map(field))
+                .err()
+                .map(|e| match key {
+                    Value::Text(name) => format!("{} ({})", name, e),
+                    _ => format!("{:?} ({})", key, e),
+                })
+        })
+        .collect()
+}
+
 pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAttack {
-    // Creates a TCP address.
-    let address = format!(
-        "tcp://{}:{}",
-        swanling_attack.configuration.manager_host, swanling_attack.configuration.manager_port
+    // When the Manager shards Workers across multiple sockets, each listening on the next
+    // port after the last, pick one by process id and how many Workers this process has
+    // already started to spread Workers between them. This relies on --manager-sockets
+    // being set to the same value on the Manager and all Workers, which is confirmed once
+    // the Manager sends its configuration.
+    let manager_sockets = swanling_attack.configuration.manager_sockets.unwrap_or(1);
+    let shard = (std::process::id() as usize + WORKERS_STARTED.fetch_add(1, Ordering::SeqCst))
+        % manager_sockets;
+
+    // Creates a TCP, inproc or ipc address.
+    let address = gaggle_address(
+        &swanling_attack.configuration,
+        &swanling_attack.configuration.manager_host,
+        swanling_attack.configuration.manager_port,
+        shard,
     );
     info!("worker connecting to manager at {}", &address);
 
@@ -62,13 +288,39 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
         .map_err(|error| eprintln!("{:?} address({})", error, address))
         .expect("failed to create socket");
 
+    // Apply any configured socket options.
+    configure_gaggle_socket(&manager, &swanling_attack.configuration)
+        .map_err(|error| eprintln!("{:?}", error))
+        .expect("failed to configure socket");
+
     manager
         .pipe_notify(pipe_closed)
         .map_err(|error| eprintln!("{:?}", error))
         .expect("failed to set up pipe handler");
 
-    // Pause 1/10 of a second in case we're blocking on a cargo lock.
-    thread::sleep(time::Duration::from_millis(100));
+    // Catch ctrl-c and termination signals, so a Worker that can't reach the manager can
+    // still be stopped cleanly.
+    let canceled = Arc::new(AtomicBool::new(false));
+    util::setup_ctrlc_handler(&canceled);
+
+    // Pause 1/10 of a second in case we're blocking on a cargo lock, plus an optional
+    // random delay so many Workers starting at once don't all connect at the same time.
+    let jitter = match swanling_attack.configuration.worker_startup_jitter {
+        Some(window) => util::startup_jitter(
+            window,
+            swanling_attack
+                .configuration
+                .worker_startup_seed
+                .map(|seed| seed as u64),
+        ),
+        None => 0,
+    };
+    if jitter > 0 {
+        info!("delaying {} ms before connecting to manager", jitter);
+    }
+    if sleep_unless_canceled(time::Duration::from_millis(100 + jitter), &canceled) {
+        exit_before_connecting(&address);
+    }
     // Connect to manager.
     let mut retries = 0;
     loop {
@@ -76,6 +328,13 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
             Ok(_) => break,
             Err(e) => {
                 if retries >= 5 {
+                    // The socket picked may not exist if the Manager listens on fewer.
+                    if manager_sockets > 1 {
+                        panic!(
+                            "failed to communicate with manager at {}: {}, confirm --manager-sockets is set to {} on the manager too.",
+                            &address, e, manager_sockets
+                        );
+                    }
                     panic!("failed to communicate with manager at {}: {}.", &address, e);
                 }
                 debug!("failed to communicate with manager at {}: {}.", &address, e);
@@ -84,23 +343,29 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
                     "sleeping {:?} milliseconds waiting for manager...",
                     sleep_duration
                 );
-                thread::sleep(sleep_duration);
+                if sleep_unless_canceled(sleep_duration, &canceled) {
+                    exit_before_connecting(&address);
+                }
                 retries += 1;
             }
         }
     }
 
+    // The largest message the Manager accepts, as validated with the configuration.
+    let max_msg_size = swanling_attack
+        .configuration
+        .gaggle_max_msg_size
+        .unwrap_or(0);
+
     // Send manager the hash of the load test we are ready to run.
     push_metrics_to_manager(
         &manager,
         vec![GaggleMetrics::WorkerInit(swanling_attack.metrics.hash)],
         false,
+        max_msg_size,
     );
 
-    let mut config: SwanlingConfiguration = SwanlingConfiguration::parse_args_default(&EMPTY_ARGS)
-        .expect("failed to generate default configuration");
     let mut weighted_users: Vec<SwanlingUser> = Vec::new();
-    let mut run_time: usize = 0;
 
     // Wait for the manager to send user parameters.
     info!("waiting for instructions from manager");
@@ -109,13 +374,13 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
         .map_err(|error| eprintln!("{:?}", error))
         .expect("error receiving manager message");
 
-    let initializers: Vec<SwanlingUserInitializer> = match serde_cbor::from_reader(msg.as_slice()) {
+    let initializer = match decode_initializer(msg.as_slice()) {
         Ok(i) => i,
-        Err(_) => {
+        Err(initializer_error) => {
             let command: SwanlingUserCommand = match serde_cbor::from_reader(msg.as_slice()) {
                 Ok(c) => c,
-                Err(e) => {
-                    panic!("invalid message received: {}", e);
+                Err(_) => {
+                    panic!("invalid message received: {}", initializer_error);
                 }
             };
             match command {
@@ -129,38 +394,59 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
         }
     };
 
-    let mut worker_id: usize = 0;
+    // The configuration, run_time, stop_time and cooldown are shared by all users on this
+    // worker.
+    let config = initializer.config;
+    let run_time = initializer.run_time;
+    let stop_time = initializer.stop_time;
+    let cooldown = initializer.cooldown;
+
+    // Workers picking their socket with a different number than the Manager listens on
+    // aren't spread across all of them, and may pick one that doesn't exist.
+    if config.manager_sockets.unwrap_or(1) != manager_sockets {
+        panic!(
+            "--manager-sockets is set to {} but the manager listens on {} sockets, it must be set to the same value on the manager and all workers.",
+            manager_sockets,
+            config.manager_sockets.unwrap_or(1)
+        );
+    }
+
     // Allocate a state for each user that will be spawned.
     info!("initializing user states...");
-    for initializer in initializers {
-        if worker_id == 0 {
-            worker_id = initializer.worker_id;
-        }
-        let user = SwanlingUser::new(
-            initializer.task_sets_index,
-            Url::parse(&initializer.base_url).unwrap(),
-            initializer.min_wait,
-            initializer.max_wait,
-            &initializer.config,
+    for user_initializer in initializer.users {
+        let mut user = SwanlingUser::new(
+            user_initializer.task_sets_index,
+            Url::parse(&user_initializer.base_url).unwrap(),
+            user_initializer.min_wait,
+            user_initializer.max_wait,
+            &config,
             swanling_attack.metrics.hash,
         )
         .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
         .expect("failed to create socket");
+        // Configure the client with the User-Agent allocated by the Manager, the headers
+        // of the user's class, and any customization set by the load test.
+        let task_set = swanling_attack
+            .task_sets
+            .iter()
+            .find(|task_set| task_set.name == user_initializer.user_class);
+        user.configure_client(
+            user_initializer.user_agent.as_deref(),
+            task_set,
+            swanling_attack.client_customizer.as_ref(),
+        )
+        .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
+        .expect("failed to configure client");
 
-        // The initializer.config and run_time are the same for all users, only copy it
-        // one time.
-        if weighted_users.is_empty() {
-            config = initializer.config;
-            run_time = initializer.run_time;
-        }
         weighted_users.push(user);
     }
-    WORKER_ID.store(worker_id, Ordering::Relaxed);
+    WORKER_ID.store(initializer.worker_id, Ordering::Relaxed);
     info!(
         "[{}] initialized {} user states",
         get_worker_id(),
         weighted_users.len()
     );
+    swanling_attack.check_open_file_limit(weighted_users.len(), config.pool_size);
 
     info!("[{}] waiting for go-ahead from manager", get_worker_id());
 
@@ -171,28 +457,31 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
             &manager,
             vec![GaggleMetrics::WorkerInit(swanling_attack.metrics.hash)],
             false,
+            max_msg_size,
         );
         let msg = manager
             .recv()
             .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
             .expect("error receiving manager message");
 
-        let command: SwanlingUserCommand = serde_cbor::from_reader(msg.as_slice())
-            .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
-            .expect("invalid message received");
-
-        match command {
+        match decode_command(msg.as_slice()) {
             // Break out of loop and start the load test.
-            SwanlingUserCommand::Run => break,
+            Some(SwanlingUserCommand::Run) => break,
+            // Store values published by test_start() before the load test started, then
+            // loop again to get the go-ahead.
+            Some(SwanlingUserCommand::SharedValues(values)) => {
+                swanling_attack.shared_values.merge(values);
+            }
             // Exit worker process immediately.
-            SwanlingUserCommand::Exit => {
+            Some(SwanlingUserCommand::Exit) => {
                 warn!(
                     "[{}] received SwanlingUserCommand::Exit command from manager",
                     get_worker_id()
                 );
                 std::process::exit(0);
             }
-            // Sleep and then loop again.
+            // Sleep and then loop again, including after a command this Worker doesn't
+            // understand.
             _ => {
                 let sleep_duration = time::Duration::from_secs(1);
                 debug!(
@@ -216,9 +505,19 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
 
     worker_swanling_attack.started = Some(time::Instant::now());
     worker_swanling_attack.task_sets = swanling_attack.task_sets.clone();
+    // Users on the Worker run tasks with the same custom task scheduler, if any.
+    worker_swanling_attack.task_scheduler = swanling_attack.task_scheduler.clone();
+    // Requests on the Worker are named with the same request namer, if any.
+    worker_swanling_attack.request_namer = swanling_attack.request_namer.clone();
     // Use the run_time from the Manager so Worker can shut down in a timely manner.
     worker_swanling_attack.run_time = run_time;
+    // Use the stop_time from the Manager so all Workers stop their users together.
+    worker_swanling_attack.stop_time = stop_time;
+    // Use the cooldown from the Manager so all Workers keep collecting metrics together.
+    worker_swanling_attack.cooldown = cooldown;
     worker_swanling_attack.weighted_users = weighted_users;
+    // Users on the Worker start with the values already received from the Manager.
+    worker_swanling_attack.shared_values = swanling_attack.shared_values.clone();
     // This is a Worker instance, not a Manager instance.
     worker_swanling_attack.configuration.manager = false;
     worker_swanling_attack.configuration.worker = true;
@@ -228,6 +527,12 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
     // The request_format option is configured on the Worker.
     worker_swanling_attack.configuration.request_format =
         swanling_attack.configuration.request_format.clone();
+    // The request_log_sample option is configured on the Worker.
+    worker_swanling_attack.configuration.request_log_sample =
+        swanling_attack.configuration.request_log_sample;
+    // The request_log_seed option is configured on the Worker.
+    worker_swanling_attack.configuration.request_log_seed =
+        swanling_attack.configuration.request_log_seed;
     // The task_log option is configured on the Worker.
     worker_swanling_attack.configuration.task_log =
         swanling_attack.configuration.task_log.to_string();
@@ -246,9 +551,21 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
     // The debug_format option is configured on the Worker.
     worker_swanling_attack.configuration.debug_format =
         swanling_attack.configuration.debug_format.clone();
+    // The capture_failure_bodies option is configured on the Worker.
+    worker_swanling_attack.configuration.capture_failure_bodies =
+        swanling_attack.configuration.capture_failure_bodies;
     // The throttle_requests option is set on the Worker.
     worker_swanling_attack.configuration.throttle_requests =
         swanling_attack.configuration.throttle_requests;
+    // The max_connections option is set on the Worker.
+    worker_swanling_attack.configuration.max_connections =
+        swanling_attack.configuration.max_connections;
+    // The worker_metrics_interval option is set on the Worker.
+    worker_swanling_attack.configuration.worker_metrics_interval =
+        swanling_attack.configuration.worker_metrics_interval;
+    // The worker_metrics_buffer option is set on the Worker.
+    worker_swanling_attack.configuration.worker_metrics_buffer =
+        swanling_attack.configuration.worker_metrics_buffer;
     worker_swanling_attack.attack_mode = AttackMode::Worker;
     worker_swanling_attack.defaults = swanling_attack.defaults.clone();
 
@@ -259,19 +576,180 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
         .expect("failed to launch SwanlingAttack")
 }
 
-// Push metrics to manager.
+// Push metrics to manager. If a response is requested, returns the command the manager
+// replied with. Batches larger than the manager accepts, as set by --gaggle-max-msg-size
+// (0 for no limit), are split and pushed in parts.
 pub fn push_metrics_to_manager(
     manager: &Socket,
     metrics: Vec<GaggleMetrics>,
     get_response: bool,
-) -> bool {
+    max_msg_size: usize,
+) -> Option<SwanlingUserCommand> {
     debug!("[{}] pushing metrics to manager", get_worker_id(),);
+    let mut batches = split_metrics(metrics, max_msg_size);
+    let last_batch = batches.pop().unwrap_or_default();
+
+    // The reply to each part must be received before sending the next. Keep the first
+    // reply that isn't routine, so the caller still acts on it.
+    let mut command = None;
+    for batch in batches {
+        match send_metrics_to_manager(manager, batch, true) {
+            Some(SwanlingUserCommand::Run) | Some(SwanlingUserCommand::Wait) | None => (),
+            reply => command = command.or(reply),
+        }
+    }
+    let reply = send_metrics_to_manager(manager, last_batch, get_response);
+    match reply {
+        Some(SwanlingUserCommand::Run) | Some(SwanlingUserCommand::Wait) | None => {
+            command.or(reply)
+        }
+        _ => reply,
+    }
+}
+
+/// Describes a batch of metrics too large for the manager to receive, and how to fix it.
+pub fn oversized_metrics_error(size: usize, max_msg_size: usize) -> String {
+    format!(
+        "metrics batch of {} bytes exceeds the configured max of {}; raise --gaggle-max-msg-size or lower --worker-metrics-interval",
+        size, max_msg_size
+    )
+}
+
+// Split a batch of metrics into parts no larger than max_msg_size bytes once serialized,
+// where 0 is unlimited. Metrics are split by request, task, error or custom metric, as the
+// manager adds them together. A single entry too large to send on its own is dropped with
+// an error, and counted as dropped so the loss shows in the metrics.
+pub(crate) fn split_metrics(
+    mut metrics: Vec<GaggleMetrics>,
+    max_msg_size: usize,
+) -> Vec<Vec<GaggleMetrics>> {
+    let size = serde_cbor::to_vec(&metrics)
+        .map(|message| message.len())
+        .unwrap_or(0);
+    if max_msg_size == 0 || size <= max_msg_size {
+        return vec![metrics];
+    }
+
+    if metrics.len() > 1 {
+        let second_half = metrics.split_off(metrics.len() / 2);
+        let mut batches = split_metrics(metrics, max_msg_size);
+        batches.extend(split_metrics(second_half, max_msg_size));
+        return batches;
+    }
+
+    let halves = match metrics.pop() {
+        Some(GaggleMetrics::Requests(requests)) if requests.len() > 1 => {
+            let len = requests.len();
+            let (first_half, second_half) = split_in_half(requests, len);
+            (
+                GaggleMetrics::Requests(first_half),
+                GaggleMetrics::Requests(second_half),
+            )
+        }
+        Some(GaggleMetrics::Groups(groups)) if groups.len() > 1 => {
+            let len = groups.len();
+            let (first_half, second_half) = split_in_half(groups, len);
+            (
+                GaggleMetrics::Groups(first_half),
+                GaggleMetrics::Groups(second_half),
+            )
+        }
+        Some(GaggleMetrics::Tasks(tasks)) if tasks.len() > 1 => {
+            let len = tasks.len();
+            let (first_half, second_half) = split_in_half(tasks, len);
+            (
+                GaggleMetrics::Tasks(first_half),
+                GaggleMetrics::Tasks(second_half),
+            )
+        }
+        // Each task carries its own index, so the tasks of one task set can be sent apart.
+        Some(GaggleMetrics::Tasks(mut tasks)) if tasks.first().map_or(0, Vec::len) > 1 => {
+            let task_set = tasks.remove(0);
+            let len = task_set.len();
+            let (first_half, second_half) = split_in_half(task_set, len);
+            (
+                GaggleMetrics::Tasks(vec![first_half]),
+                GaggleMetrics::Tasks(vec![second_half]),
+            )
+        }
+        Some(GaggleMetrics::Errors(errors)) if errors.len() > 1 => {
+            let len = errors.len();
+            let (first_half, second_half) = split_in_half(errors, len);
+            (
+                GaggleMetrics::Errors(first_half),
+                GaggleMetrics::Errors(second_half),
+            )
+        }
+        Some(GaggleMetrics::Custom(custom)) if custom.len() > 1 => {
+            let len = custom.len();
+            let (first_half, second_half) = split_in_half(custom, len);
+            (
+                GaggleMetrics::Custom(first_half),
+                GaggleMetrics::Custom(second_half),
+            )
+        }
+        metric => {
+            error!(
+                "[{}] {}",
+                get_worker_id(),
+                oversized_metrics_error(size, max_msg_size)
+            );
+            let dropped = metric.as_ref().map_or(0, samples);
+            if dropped > 0 {
+                return vec![vec![GaggleMetrics::Dropped(dropped)]];
+            }
+            return vec![Vec::new()];
+        }
+    };
+    split_metrics(vec![halves.0, halves.1], max_msg_size)
+}
+
+// Split metrics of len entries into two halves.
+fn split_in_half<T, E>(metrics: T, len: usize) -> (T, T)
+where
+    T: IntoIterator<Item = E> + std::iter::FromIterator<E>,
+{
+    let mut entries = metrics.into_iter();
+    let first_half = entries.by_ref().take(len / 2).collect();
+    let second_half = entries.collect();
+    (first_half, second_half)
+}
+
+// How many samples a metric aggregates, counted as dropped if it's too large to send. A
+// custom counter only holds the total of its increments, so counts as one.
+fn samples(metric: &GaggleMetrics) -> usize {
+    match metric {
+        GaggleMetrics::Requests(requests) | GaggleMetrics::Groups(requests) => requests
+            .values()
+            .map(|request| request.raw_data.counter)
+            .sum(),
+        GaggleMetrics::Tasks(tasks) => tasks.iter().flatten().map(|task| task.counter).sum(),
+        GaggleMetrics::Errors(errors) => errors.values().map(|error| error.occurrences).sum(),
+        GaggleMetrics::Custom(custom) => custom
+            .values()
+            .map(|aggregate| match aggregate {
+                SwanlingCustomMetricAggregate::Counter(_) => 1,
+                SwanlingCustomMetricAggregate::Gauge { observations, .. } => *observations,
+            })
+            .sum(),
+        _ => 0,
+    }
+}
+
+// Send one batch of metrics to the manager. If a response is requested, returns the
+// command the manager replied with.
+fn send_metrics_to_manager(
+    manager: &Socket,
+    metrics: Vec<GaggleMetrics>,
+    get_response: bool,
+) -> Option<SwanlingUserCommand> {
     let mut message = BufWriter::new(Message::new());
 
     serde_cbor::to_writer(&mut message, &metrics)
         .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
         .expect("failed to serialize GaggleMetrics");
 
+    let pushed = time::Instant::now();
     manager
         .try_send(
             message
@@ -287,10 +765,9 @@ pub fn push_metrics_to_manager(
             .recv()
             .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
             .expect("error receiving manager message");
+        ROUND_TRIP.store(pushed.elapsed().as_millis() as usize, Ordering::Relaxed);
 
-        let command: SwanlingUserCommand = serde_cbor::from_reader(msg.as_slice())
-            .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
-            .expect("invalid message");
+        let command = decode_command(msg.as_slice())?;
 
         if command == SwanlingUserCommand::Exit {
             info!(
@@ -299,8 +776,25 @@ pub fn push_metrics_to_manager(
             );
             // Shutting down, register shutdown pipe handler.
             register_shutdown_pipe_handler(manager);
-            return false;
+        }
+        return Some(command);
+    }
+    None
+}
+
+// Decode a command from the manager, logging and ignoring commands that don't parse, for
+// example commands added in a newer version of Swanling.
+fn decode_command(msg: &[u8]) -> Option<SwanlingUserCommand> {
+    match serde_cbor::from_slice(msg) {
+        Ok(command) => Some(command),
+        Err(e) => {
+            error!(
+                "[{}] ignoring command of {} bytes from manager that didn't parse: {}",
+                get_worker_id(),
+                msg.len(),
+                e
+            );
+            None
         }
     }
-    true
 }
@@ -0,0 +1,148 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const FAST_PATH: &str = "/fast";
+const SLOW_PATH: &str = "/slow";
+
+// Indexes to the above paths.
+const FAST_KEY: usize = 0;
+const SLOW_KEY: usize = 1;
+
+// How long the slow path takes to respond, in milliseconds.
+const SLOW_DELAY: u64 = 100;
+
+// Load test configuration, long enough for the adaptive throttle to adjust twice.
+const USERS: usize = 2;
+const RUN_TIME: usize = 7;
+const THROTTLE_REQUESTS: usize = 4;
+
+// Test task.
+pub async fn get_fast(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(FAST_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_slow(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SLOW_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up FAST_PATH, store in vector at FAST_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(FAST_PATH);
+            then.status(200);
+        }),
+        // Next set up SLOW_PATH, store in vector at SLOW_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_millis(SLOW_DELAY));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, target_p95: usize) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let throttle_requests = THROTTLE_REQUESTS.to_string();
+    let target_p95 = target_p95.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--throttle-requests",
+            &throttle_requests,
+            "--adaptive-target-p95",
+            &target_p95,
+        ],
+    )
+}
+
+#[test]
+// Confirm the throttle is raised while responses stay within the target.
+fn test_adaptive_within_target() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, 1000),
+            &taskset!("LoadTest").register_task(task!(get_fast)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[FAST_KEY].hits() > 0);
+    assert_eq!(mock_endpoints[SLOW_KEY].hits(), 0);
+
+    // Every adjustment raised the throttle.
+    let adaptive = swanling_metrics.adaptive.unwrap();
+    assert_eq!(adaptive.target_p95, 1000);
+    assert!(!adaptive.steps.is_empty());
+    assert_eq!(adaptive.steps[0].throttle_requests, THROTTLE_REQUESTS);
+    for step in adaptive.steps.windows(2) {
+        assert!(step[1].throttle_requests > step[0].throttle_requests);
+    }
+    assert!(adaptive.steps.iter().all(|step| step.within_bounds));
+
+    // A safe rate was found.
+    let max_safe_rps = adaptive.max_safe_rps.unwrap();
+    assert!(max_safe_rps > 0.0);
+}
+
+#[test]
+// Confirm the throttle is lowered while responses are slower than the target.
+fn test_adaptive_exceeds_target() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, 10),
+            &taskset!("LoadTest").register_task(task!(get_slow)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert_eq!(mock_endpoints[FAST_KEY].hits(), 0);
+    assert!(mock_endpoints[SLOW_KEY].hits() > 0);
+
+    // Every adjustment lowered the throttle.
+    let adaptive = swanling_metrics.adaptive.unwrap();
+    assert!(!adaptive.steps.is_empty());
+    assert_eq!(adaptive.steps[0].throttle_requests, THROTTLE_REQUESTS);
+    for step in adaptive.steps.windows(2) {
+        assert!(step[1].throttle_requests < step[0].throttle_requests);
+    }
+    assert!(adaptive.steps.iter().all(|step| !step.within_bounds));
+
+    // No safe rate was found.
+    assert!(adaptive.max_safe_rps.is_none());
+}
@@ -0,0 +1,144 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const EXPECT_WORKERS: usize = 2;
+const USERS: usize = 4;
+const RUN_TIME: usize = 4;
+const AGGREGATE_WINDOW: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    worker: Option<bool>,
+    manager: Option<usize>,
+) -> SwanlingConfiguration {
+    if let Some(expect_workers) = manager {
+        common::build_configuration(
+            server,
+            vec![
+                "--manager",
+                "--expect-workers",
+                &expect_workers.to_string(),
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--aggregate-window",
+                &AGGREGATE_WINDOW.to_string(),
+                "--no-reset-metrics",
+            ],
+        )
+    } else if worker.is_some() {
+        common::build_configuration(server, vec!["--worker"])
+    } else {
+        common::build_configuration(
+            server,
+            vec![
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--aggregate-window",
+                &AGGREGATE_WINDOW.to_string(),
+            ],
+        )
+    }
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(get_index))
+}
+
+#[test]
+// Confirm --aggregate-window is rejected without --manager.
+fn test_aggregate_window_standalone() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration.
+    let configuration = common_build_configuration(&server, None, None);
+
+    // Confirm the load test refuses to start.
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(get_tasks());
+    assert!(swanling_attack.execute().is_err());
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+// Confirm the Manager aggregates metrics into windows in addition to lifetime
+// totals, in Regatta mode.
+fn test_aggregate_window_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each worker has the same identical configuration.
+    let worker_configuration = common_build_configuration(&server, Some(true), None);
+
+    // Build the load test for the Workers.
+    let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
+
+    // Workers launched in own threads, store thread handles.
+    let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
+
+    // Build Manager configuration.
+    let manager_configuration = common_build_configuration(&server, None, Some(EXPECT_WORKERS));
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(manager_configuration, &get_tasks(), None, None),
+        Some(worker_handles),
+    );
+
+    // The load test ran long enough to aggregate several windows.
+    assert!(swanling_metrics.windows.len() > 1);
+    assert!(swanling_metrics.last_complete_window().is_some());
+
+    // The windows add up to the lifetime totals.
+    let lifetime = swanling_metrics.requests.get("GET /").unwrap();
+    let windowed: usize = swanling_metrics
+        .windows
+        .iter()
+        .filter_map(|window| window.requests.get("GET /"))
+        .map(|request| request.success_count)
+        .sum();
+    assert_eq!(windowed, lifetime.success_count);
+    assert_eq!(lifetime.success_count, mock_endpoints[INDEX_KEY].hits());
+}
@@ -0,0 +1,124 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// The base path prefixed to all requests, and the prefixed paths the server receives.
+const BASE_PATH: &str = "/api/v2/";
+const PREFIXED_INDEX_PATH: &str = "/api/v2/";
+const PREFIXED_ABOUT_PATH: &str = "/api/v2/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+const PREFIXED_INDEX_KEY: usize = 2;
+const PREFIXED_ABOUT_KEY: usize = 3;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+        // Next set up PREFIXED_INDEX_PATH, store in vector at PREFIXED_INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(PREFIXED_INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up PREFIXED_ABOUT_PATH, store in vector at PREFIXED_ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(PREFIXED_ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--base-path",
+            BASE_PATH,
+        ],
+    )
+}
+
+#[test]
+// Confirm the base path is prepended to all requests, which are named without it.
+fn test_base_path() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(get_index))
+                .register_task(task!(get_about)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Only the prefixed paths were requested.
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), 0);
+    assert_eq!(mock_endpoints[ABOUT_KEY].hits(), 0);
+    assert!(mock_endpoints[PREFIXED_INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[PREFIXED_ABOUT_KEY].hits() > 0);
+
+    // Requests are named by the path as written in the load test.
+    assert_eq!(swanling_metrics.requests.len(), 2);
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    let about_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", ABOUT_PATH))
+        .unwrap();
+    assert_eq!(index_metrics.fail_count, 0);
+    assert_eq!(about_metrics.fail_count, 0);
+}
@@ -0,0 +1,112 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// File the baseline report is written to.
+const BASELINE: &str = "baseline-test.json";
+
+// How long the index responds in the slower load test, in milliseconds.
+const SLOW_DELAY: u64 = 100;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint, optionally delayed.
+fn setup_mock_server_endpoints(server: &MockServer, delay: u64) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200).delay(Duration::from_millis(delay));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration.extend(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Run a load test against an index that responds after the delay.
+fn run_load_test(delay: u64, custom: Vec<&str>) -> Result<SwanlingMetrics, SwanlingError> {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server, delay);
+
+    let result = common::build_load_test(
+        common_build_configuration(&server, custom),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    )
+    .execute();
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    result
+}
+
+#[test]
+// Confirm a slower load test regresses from the baseline.
+fn test_baseline() {
+    // Store the metrics of a fast load test as the baseline.
+    let baseline = run_load_test(0, vec![]).unwrap();
+    std::fs::write(BASELINE, serde_json::to_string(&baseline).unwrap())
+        .expect("failed to write baseline");
+
+    // The same load test doesn't regress with a generous threshold.
+    let metrics = run_load_test(
+        0,
+        vec!["--baseline", BASELINE, "--regression-threshold", "1000"],
+    )
+    .unwrap();
+    let comparison = metrics.comparison.unwrap();
+    assert!(!comparison.regressed);
+    assert_eq!(comparison.requests.len(), 1);
+
+    // A slower load test regresses.
+    match run_load_test(SLOW_DELAY, vec!["--baseline", BASELINE]) {
+        Err(SwanlingError::Regression {
+            regressed, metrics, ..
+        }) => {
+            assert!(regressed.contains(&format!("GET {}", INDEX_PATH)));
+            let comparison = metrics.comparison.unwrap();
+            assert!(comparison.regressed);
+            assert!(comparison.requests[0].response_time_change.unwrap() > 10.0);
+        }
+        _ => panic!("expected a regression"),
+    }
+
+    // Cleanup from test.
+    common::cleanup_files(vec![BASELINE]);
+}
@@ -0,0 +1,135 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::swanling::CAPTURE_FAILURE_BODY_LIMIT;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Bodies returned by the above paths.
+const INDEX_BODY: &str = "index page";
+const ERROR_BODY: &str = "database connection refused";
+
+// File the debug log is written to.
+const DEBUG_LOG: &str = "capture-failure-bodies-debug.log";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// How many times a task still received the full failed response body.
+static ERROR_BODIES_RECEIVED: AtomicUsize = AtomicUsize::new(0);
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(ERROR_PATH).await?;
+    if let Ok(response) = swanling.response {
+        if response.text().await.unwrap_or_default() == error_body() {
+            ERROR_BODIES_RECEIVED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    Ok(())
+}
+
+// The failed response body is larger than the capture limit.
+fn error_body() -> String {
+    format!("{}{}", ERROR_BODY, "x".repeat(CAPTURE_FAILURE_BODY_LIMIT))
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200).body(INDEX_BODY);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500).body(error_body());
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--debug-log",
+            DEBUG_LOG,
+            "--capture-failure-bodies",
+        ],
+    )
+}
+
+#[test]
+// Confirm only the bodies of failed requests are written to the debug log.
+fn test_capture_failure_bodies() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(get_index))
+                .register_task(task!(get_error)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    let error_hits = mock_endpoints[ERROR_KEY].hits();
+    assert!(error_hits > 0);
+
+    // Tasks still received the complete body of each failed response.
+    assert_eq!(ERROR_BODIES_RECEIVED.load(Ordering::SeqCst), error_hits);
+
+    // Each failed request was written to the debug log, with a truncated body.
+    let debug_log = std::fs::read_to_string(DEBUG_LOG).expect("failed to read debug log");
+    let lines: Vec<&str> = debug_log.lines().collect();
+    assert_eq!(lines.len(), error_hits);
+    for line in lines {
+        let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(entry["tag"].as_str().unwrap().starts_with("500"));
+        assert_eq!(entry["request"]["status_code"], 500);
+        let body = entry["body"].as_str().unwrap();
+        assert!(body.starts_with(ERROR_BODY));
+        assert_eq!(body.len(), CAPTURE_FAILURE_BODY_LIMIT);
+    }
+    assert!(!debug_log.contains(INDEX_BODY));
+
+    // Cleanup from test.
+    common::cleanup_files(vec![DEBUG_LOG]);
+}
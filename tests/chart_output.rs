@@ -0,0 +1,132 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const FAST_PATH: &str = "/fast";
+const SLOW_PATH: &str = "/slow";
+
+// Indexes to the above paths.
+const FAST_KEY: usize = 0;
+const SLOW_KEY: usize = 1;
+
+// How long the slow path takes to respond, in milliseconds.
+const SLOW_DELAY: u64 = 200;
+
+// Directory the charts are written to.
+const CHART_OUTPUT: &str = "chart-output-test";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_fast(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(FAST_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_slow(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SLOW_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up FAST_PATH, store in vector at FAST_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(FAST_PATH);
+            then.status(200);
+        }),
+        // Next set up SLOW_PATH, store in vector at SLOW_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_millis(SLOW_DELAY));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--chart-output",
+            CHART_OUTPUT,
+        ],
+    )
+}
+
+#[test]
+// Confirm latency distribution charts are written for each request.
+fn test_chart_output() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(get_fast))
+                .register_task(task!(get_slow)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[FAST_KEY].hits() > 0);
+    assert!(mock_endpoints[SLOW_KEY].hits() > 0);
+
+    let read_chart = |file_name: &str| {
+        std::fs::read_to_string(std::path::Path::new(CHART_OUTPUT).join(file_name))
+            .expect("failed to read chart")
+    };
+
+    // The html report charts each request, and all requests together.
+    let html = read_chart("latency.html");
+    assert_eq!(html.matches("<svg").count(), 3);
+    assert!(html.contains(&format!("GET {}", FAST_PATH)));
+    assert!(html.contains(&format!("GET {}", SLOW_PATH)));
+    assert!(html.contains("Aggregated"));
+
+    // The Gnuplot data has a block for each chart, and slow responses are recorded.
+    let data = read_chart("latency.dat");
+    let blocks: Vec<&str> = data.split("\n\n\n").collect();
+    assert_eq!(blocks.len(), 3);
+    assert!(blocks[0].starts_with(&format!("# GET {}", FAST_PATH)));
+    assert!(blocks[1].starts_with(&format!("# GET {}", SLOW_PATH)));
+    assert!(blocks[2].starts_with("# Aggregated"));
+    let slowest: usize = blocks[1]
+        .lines()
+        .last()
+        .and_then(|line| line.split(' ').next())
+        .and_then(|time| time.parse().ok())
+        .unwrap();
+    assert!(slowest >= SLOW_DELAY as usize);
+
+    // The Gnuplot script plots each block.
+    let script = read_chart("latency.gnuplot");
+    assert!(script.contains("'latency.dat' index 2 using 1:3 with impulses title 'Aggregated'"));
+
+    // Cleanup from test.
+    std::fs::remove_dir_all(CHART_OUTPUT).expect("failed to remove chart output");
+}
@@ -0,0 +1,111 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const SETUP_PATH: &str = "/setup";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const SETUP_KEY: usize = 1;
+
+// The header added by the client customizer.
+const CUSTOM_HEADER: &str = "x-custom-header";
+const CUSTOM_VALUE: &str = "custom value";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn setup(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SETUP_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints, only responding to requests
+// with the custom header.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header(CUSTOM_HEADER, CUSTOM_VALUE);
+            then.status(200);
+        }),
+        // Next set up SETUP_PATH, store in vector at SETUP_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(SETUP_PATH)
+                .header(CUSTOM_HEADER, CUSTOM_VALUE);
+            then.status(200);
+        }),
+    ]
+}
+
+#[test]
+// Confirm the client of every user is customized, including the test_start user.
+fn test_client_customizer() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            Some(&task!(setup)),
+            None,
+        )
+        .set_client_customizer(|builder| {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(
+                CUSTOM_HEADER,
+                reqwest::header::HeaderValue::from_static(CUSTOM_VALUE),
+            );
+            builder.default_headers(headers)
+        }),
+        None,
+    );
+
+    // The test_start user sent the custom header.
+    assert_eq!(mock_endpoints[SETUP_KEY].hits(), 1);
+
+    // All requests by the load test users sent the custom header.
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(index_hits > 0);
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.fail_count, 0);
+    assert_eq!(request_metrics.success_count, index_hits);
+}
@@ -498,6 +498,95 @@ fn run_standalone_test(test_type: TestType) {
                         }
                     }
                 }
+                SwanlingControllerCommand::ResetMetrics => {
+                    match test_state.step {
+                        // Reset the running metrics.
+                        0 => {
+                            make_request(&mut test_state, "reset-metrics\r\n");
+                        }
+                        // Confirm the running metrics were reset.
+                        _ => {
+                            assert!(response.starts_with("metrics reset"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Throttle => {
+                    match test_state.step {
+                        // Try to configure an invalid throttle.
+                        0 => {
+                            make_request(&mut test_state, "throttle abc\r\n");
+                        }
+                        // Confirm the invalid throttle is not recognized.
+                        1 => {
+                            assert!(response.starts_with("unrecognized command"));
+
+                            // Try to configure the throttle of an unthrottled load test.
+                            make_request(&mut test_state, "throttle 5\r\n");
+                        }
+                        // Confirm the throttle can not be enabled on a running load test.
+                        _ => {
+                            assert!(response.starts_with("failed to configure throttle"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::RequestLog => {
+                    match test_state.step {
+                        // Try to request the log of an invalid worker.
+                        0 => {
+                            make_request(&mut test_state, "request-log abc\r\n");
+                        }
+                        // Confirm the invalid worker is not recognized.
+                        1 => {
+                            assert!(response.starts_with("unrecognized command"));
+
+                            // Try to request the log of a worker without a manager.
+                            make_request(&mut test_state, "request-log 1\r\n");
+                        }
+                        // Confirm only a manager can request the log of a worker.
+                        _ => {
+                            assert!(response.starts_with("failed to request log"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Pause => {
+                    match test_state.step {
+                        // Pause the running load test.
+                        0 => {
+                            make_request(&mut test_state, "pause\r\n");
+                        }
+                        // Confirm the load test was paused.
+                        _ => {
+                            assert!(response.starts_with("load test paused"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Resume => {
+                    match test_state.step {
+                        // Resume the paused load test.
+                        0 => {
+                            make_request(&mut test_state, "resume\r\n");
+                        }
+                        // Confirm the load test was resumed.
+                        _ => {
+                            assert!(response.starts_with("load test resumed"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
                 SwanlingControllerCommand::Start => {
                     match test_state.step {
                         // Try to stop an idle load test.
@@ -508,11 +597,18 @@ fn run_standalone_test(test_type: TestType) {
                         1 => {
                             assert!(response.starts_with("load test not running"));
 
+                            // Try to reset the metrics of an idle load test.
+                            make_request(&mut test_state, "reset-metrics\r\n");
+                        }
+                        // Confirm the metrics of an idle load test can not be reset.
+                        2 => {
+                            assert!(response.starts_with("load test not running"));
+
                             // Send the start request.
                             make_request(&mut test_state, "start\r\n");
                         }
                         // Confirm an idle load test can be started.
-                        2 => {
+                        3 => {
                             assert!(response.starts_with("load test started"));
 
                             // Send the start request again.
@@ -614,6 +710,11 @@ fn update_state(test_state: Option<TestState>, test_type: &TestType) -> TestStat
         SwanlingControllerCommand::ConfigJson,
         SwanlingControllerCommand::Metrics,
         SwanlingControllerCommand::MetricsJson,
+        SwanlingControllerCommand::ResetMetrics,
+        SwanlingControllerCommand::Throttle,
+        SwanlingControllerCommand::RequestLog,
+        SwanlingControllerCommand::Pause,
+        SwanlingControllerCommand::Resume,
         SwanlingControllerCommand::Stop,
         SwanlingControllerCommand::Shutdown,
     ];
@@ -0,0 +1,100 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::{Duration, Instant};
+
+mod common;
+
+use swanling::metrics::SwanlingCooldownWindow;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 1;
+const COOLDOWN: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY. Responses are slow, so
+        // requests are still in flight when the users are told to stop.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200).delay(Duration::from_millis(600));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let cooldown = COOLDOWN.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--cooldown",
+            &cooldown,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm metrics are still collected during the --cooldown after users are stopped.
+fn test_cooldown() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let started = Instant::now();
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // The load test kept running through the cooldown.
+    assert!(started.elapsed() >= Duration::from_secs((RUN_TIME + COOLDOWN) as u64));
+
+    // The cooldown isn't included in the duration, but is recorded separately.
+    assert_eq!(swanling_metrics.duration, RUN_TIME);
+    assert_eq!(
+        swanling_metrics.cooldown,
+        Some(SwanlingCooldownWindow {
+            started: RUN_TIME,
+            stopped: RUN_TIME + COOLDOWN,
+        })
+    );
+
+    // Requests that finished during the cooldown were counted.
+    let index_metrics = swanling_metrics.requests.get("GET /").unwrap();
+    assert_eq!(
+        index_metrics.success_count,
+        mock_endpoints[INDEX_KEY].hits()
+    );
+}
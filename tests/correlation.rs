@@ -0,0 +1,170 @@
+use httpmock::{Method::GET, Method::POST, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const ACCOUNT_PATH: &str = "/account";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const ACCOUNT_KEY: usize = 1;
+
+// Values returned by the server when logging in.
+const TOKEN: &str = "3f8a1c";
+const SESSION: &str = "session-42";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn login_then_view_account(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.post(LOGIN_PATH, "user=foo&pass=bar").await?;
+    user.extract(
+        swanling,
+        &[
+            ("token", SwanlingExtractor::Json("/auth/token".to_string())),
+            (
+                "session",
+                SwanlingExtractor::Header("x-session".to_string()),
+            ),
+            (
+                "expires",
+                SwanlingExtractor::Regex(r#""expires":\s*(\d+)"#.to_string()),
+            ),
+        ],
+    )
+    .await?;
+
+    let token = user.get_session_value("token").await.unwrap_or_default();
+    let session = user.get_session_value("session").await.unwrap_or_default();
+    let _swanling = user
+        .get(&format!(
+            "{}?token={}&session={}",
+            ACCOUNT_PATH, token, session
+        ))
+        .await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn login_with_missing_value(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.post(LOGIN_PATH, "user=foo&pass=bar").await?;
+    user.extract(
+        swanling,
+        &[("token", SwanlingExtractor::Json("/missing".to_string()))],
+    )
+    .await?;
+
+    // Not reached, the extraction above always fails.
+    let _swanling = user.get(ACCOUNT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(POST).path(LOGIN_PATH);
+            then.status(200).header("x-session", SESSION).body(format!(
+                r#"{{"auth": {{"token": "{}", "expires": 3600}}}}"#,
+                TOKEN
+            ));
+        }),
+        // Next set up ACCOUNT_PATH, store in vector at ACCOUNT_KEY. Only match requests
+        // that include the values extracted from the login response.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(ACCOUNT_PATH)
+                .query_param("token", TOKEN)
+                .query_param("session", SESSION);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm values extracted from one response can be used in a later request.
+fn test_correlation() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(login_then_view_account)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Every login was followed by a request using the extracted values.
+    assert!(mock_endpoints[LOGIN_KEY].hits() > 0);
+    assert_eq!(
+        mock_endpoints[LOGIN_KEY].hits(),
+        mock_endpoints[ACCOUNT_KEY].hits()
+    );
+
+    // No tasks failed.
+    let task_metrics = &swanling_metrics.tasks[0][0];
+    assert!(task_metrics.success_count > 0);
+    assert_eq!(task_metrics.fail_count, 0);
+}
+
+#[test]
+// Confirm a failed extraction is counted as a task failure.
+fn test_correlation_failed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(login_with_missing_value)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // The task stopped after the failed extraction.
+    assert!(mock_endpoints[LOGIN_KEY].hits() > 0);
+    assert_eq!(mock_endpoints[ACCOUNT_KEY].hits(), 0);
+
+    // Every task failed.
+    let task_metrics = &swanling_metrics.tasks[0][0];
+    assert!(task_metrics.fail_count > 0);
+    assert_eq!(task_metrics.success_count, 0);
+}
@@ -0,0 +1,169 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::metrics::SwanlingCustomMetricAggregate;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+const EXPECT_WORKERS: usize = 2;
+
+// Names of the custom metrics recorded by the test task.
+const COUNTER_NAME: &str = "index loads";
+const GAUGE_NAME: &str = "index gauge";
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    user.increment_counter(COUNTER_NAME, 1);
+    // Alternate between observing 1.0 and 3.0.
+    user.observe_gauge(GAUGE_NAME, (user.weighted_users_index % 2 * 2 + 1) as f64);
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    worker: Option<bool>,
+    manager: Option<usize>,
+) -> SwanlingConfiguration {
+    if let Some(expect_workers) = manager {
+        common::build_configuration(
+            &server,
+            vec![
+                "--manager",
+                "--expect-workers",
+                &expect_workers.to_string(),
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--no-reset-metrics",
+            ],
+        )
+    } else if worker.is_some() {
+        common::build_configuration(&server, vec!["--worker"])
+    } else {
+        common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--no-reset-metrics",
+            ],
+        )
+    }
+}
+
+// Helper to confirm all variations generate appropriate results.
+fn validate_test(swanling_metrics: &SwanlingMetrics, mock_endpoints: &[MockRef]) {
+    // Confirm that the counter was incremented once per request.
+    assert_eq!(
+        swanling_metrics.custom[COUNTER_NAME],
+        SwanlingCustomMetricAggregate::Counter(mock_endpoints[INDEX_KEY].hits())
+    );
+
+    // Confirm that the gauge was observed once per request, within the expected range.
+    if let SwanlingCustomMetricAggregate::Gauge {
+        observations,
+        minimum,
+        maximum,
+        ..
+    } = swanling_metrics.custom[GAUGE_NAME]
+    {
+        assert_eq!(observations, mock_endpoints[INDEX_KEY].hits());
+        assert!(minimum >= 1.0);
+        assert!(maximum <= 3.0);
+        assert!(minimum <= maximum);
+    } else {
+        panic!("{} is not a gauge", GAUGE_NAME);
+    }
+    let mean = swanling_metrics.custom[GAUGE_NAME].mean().unwrap();
+    assert!((1.0..=3.0).contains(&mean));
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(get_index))
+}
+
+#[test]
+// Confirm custom counters and gauges are aggregated.
+fn test_custom_metrics() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common_build_configuration(&server, None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm that the custom metrics were aggregated.
+    validate_test(&swanling_metrics, &mock_endpoints);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+// Confirm custom counters and gauges are aggregated across Workers, in Regatta mode.
+fn test_custom_metrics_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each worker has the same identical configuration.
+    let worker_configuration = common_build_configuration(&server, Some(true), None);
+
+    // Build the load test for the Workers.
+    let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
+
+    // Workers launched in own threads, store thread handles.
+    let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
+
+    // Build Manager configuration.
+    let manager_configuration = common_build_configuration(&server, None, Some(EXPECT_WORKERS));
+
+    // Build the load test for the Manager.
+    let manager_swanling_attack =
+        common::build_load_test(manager_configuration, &get_tasks(), None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(manager_swanling_attack, Some(worker_handles));
+
+    // Confirm that the custom metrics were aggregated across all Workers.
+    validate_test(&swanling_metrics, &mock_endpoints);
+}
@@ -0,0 +1,117 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use serde_json::json;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const ORDER_PATH: &str = "/order";
+
+// Indexes to the above paths.
+const ORDER_KEY: usize = 0;
+
+// Debug logs written during the tests.
+const DEBUG_LOG_JSON: &str = "debug-fields.json";
+const DEBUG_LOG_CSV: &str = "debug-fields.csv";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task, logging the order each request placed.
+pub async fn place_order(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(ORDER_PATH).await?;
+    user.log_debug_fields(
+        "placed order",
+        Some(&swanling.request),
+        &json!({ "order_id": 1234, "user": user.weighted_users_index }),
+    )?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up ORDER_PATH, store in vector at ORDER_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ORDER_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Run a load test logging structured fields to the debug log.
+fn run_load_test(server: &MockServer, custom: &[&str]) {
+    let _swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(server, custom),
+            &taskset!("LoadTest").register_task(task!(place_order)),
+            None,
+            None,
+        ),
+        None,
+    );
+}
+
+#[test]
+// Confirm structured fields logged by tasks are written in the configured debug format.
+fn test_debug_fields() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each JSON entry includes the fields as an object, with the request they relate to.
+    run_load_test(&server, &["--debug-log", DEBUG_LOG_JSON]);
+    let hits = mock_endpoints[ORDER_KEY].hits();
+    assert!(hits > 0);
+    let log = std::fs::read_to_string(DEBUG_LOG_JSON).expect("failed to read debug log");
+    let entries: Vec<serde_json::Value> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(entries.len(), hits);
+    for entry in &entries {
+        assert_eq!(entry["tag"], "placed order");
+        assert_eq!(entry["fields"]["order_id"], 1234);
+        assert!(entry["fields"]["user"].as_u64().unwrap() < USERS as u64);
+        assert!(entry["request"]["url"]
+            .as_str()
+            .unwrap()
+            .ends_with(ORDER_PATH));
+    }
+
+    // Each CSV row includes the fields as a JSON string.
+    run_load_test(
+        &server,
+        &["--debug-log", DEBUG_LOG_CSV, "--debug-format", "csv"],
+    );
+    let log = std::fs::read_to_string(DEBUG_LOG_CSV).expect("failed to read debug log");
+    let mut lines = log.lines();
+    assert_eq!(lines.next(), Some("tag,request,header,body,fields"));
+    let row = lines.next().unwrap();
+    assert!(row.starts_with("\"placed order\""));
+    assert!(row.contains("order_id"));
+
+    // Cleanup from test.
+    common::cleanup_files(vec![DEBUG_LOG_JSON, DEBUG_LOG_CSV]);
+}
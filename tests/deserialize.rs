@@ -0,0 +1,161 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use serde::Deserialize;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::swanling::INVALID_RESPONSE_ERROR;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const ITEM_PATH: &str = "/item";
+const GARBLED_PATH: &str = "/garbled";
+
+// Indexes to the above paths.
+const ITEM_KEY: usize = 0;
+const GARBLED_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// The expected shape of the responses.
+#[derive(Deserialize)]
+struct Item {
+    id: usize,
+    name: String,
+}
+
+// Test task.
+pub async fn get_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(ITEM_PATH).await?;
+    let item: Item = user.deserialize(swanling).await?;
+    assert_eq!(item.id, 1);
+    assert_eq!(item.name, "swanling");
+    Ok(())
+}
+
+// Test task.
+pub async fn get_garbled(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(GARBLED_PATH).await?;
+    let _item: Item = user.deserialize(swanling).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up ITEM_PATH, store in vector at ITEM_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ITEM_PATH);
+            then.status(200)
+                .body(r#"{"id": 1, "name": "swanling", "tags": []}"#);
+        }),
+        // Next set up GARBLED_PATH, store in vector at GARBLED_KEY. Return a success
+        // status code with a truncated body.
+        server.mock(|when, then| {
+            when.method(GET).path(GARBLED_PATH);
+            then.status(200).body(r#"{"id": 1, "na"#);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm responses with the expected shape are deserialized.
+fn test_deserialize() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_item)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[ITEM_KEY].hits() > 0);
+    assert_eq!(mock_endpoints[GARBLED_KEY].hits(), 0);
+
+    // No requests or tasks failed.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", ITEM_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.fail_count, 0);
+    assert!(swanling_metrics.errors.is_empty());
+    let task_metrics = &swanling_metrics.tasks[0][0];
+    assert!(task_metrics.success_count > 0);
+    assert_eq!(task_metrics.fail_count, 0);
+}
+
+#[test]
+// Confirm a response that doesn't have the expected shape fails the request and the
+// task, and is collected into the errors summary.
+fn test_deserialize_failed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_garbled)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[GARBLED_KEY].hits() > 0);
+
+    // Every request failed despite the success status code.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", GARBLED_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.success_count, 0);
+    assert_eq!(
+        request_metrics.fail_count,
+        mock_endpoints[GARBLED_KEY].hits()
+    );
+
+    // All failures were collected as the same error.
+    assert_eq!(swanling_metrics.errors.len(), 1);
+    for error in swanling_metrics.errors.values() {
+        assert!(error.error.starts_with(INVALID_RESPONSE_ERROR));
+        assert_eq!(error.occurrences, request_metrics.fail_count);
+    }
+
+    // Every task failed.
+    let task_metrics = &swanling_metrics.tasks[0][0];
+    assert!(task_metrics.fail_count > 0);
+    assert_eq!(task_metrics.success_count, 0);
+}
@@ -0,0 +1,123 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests, load testing the given host.
+fn common_build_configuration(server: &MockServer, host: &str) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--host",
+            host,
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+// Run a load test against the host, returning the metrics.
+fn run_load_test(server: &MockServer, host: &str) -> SwanlingMetrics {
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(server, host),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Confirm the time spent resolving a host name is tracked separately, and only for
+// requests that opened a new connection.
+fn test_dns_time() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = run_load_test(&server, &format!("http://localhost:{}", server.port()));
+
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(request_metrics.success_count > USERS);
+    assert_eq!(
+        mock_endpoints[INDEX_KEY].hits(),
+        request_metrics.success_count
+    );
+
+    // Each user resolved the host name when it opened its connection, then reused it.
+    let dns_time = request_metrics.dns_time_data.as_ref().unwrap();
+    assert!(dns_time.counter >= USERS);
+    assert!(dns_time.counter < request_metrics.success_count);
+
+    // The DNS resolution table is included in the report.
+    assert!(swanling_metrics
+        .to_string()
+        .contains("=== DNS RESOLUTION ==="));
+}
+
+#[test]
+// Confirm no DNS time is tracked when the host is an IP address.
+fn test_no_dns_time() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = run_load_test(&server, &format!("http://127.0.0.1:{}", server.port()));
+
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(request_metrics.success_count > 0);
+    assert!(request_metrics.dns_time_data.is_none());
+    assert!(!swanling_metrics
+        .to_string()
+        .contains("=== DNS RESOLUTION ==="));
+}
@@ -3,6 +3,7 @@ use serial_test::serial;
 
 mod common;
 
+use swanling::metrics::SwanlingErrorKind;
 use swanling::prelude::*;
 use swanling::swanling::SwanlingMethod;
 use swanling::SwanlingConfiguration;
@@ -10,6 +11,7 @@ use swanling::SwanlingConfiguration;
 // Paths used in load tests performed during these tests.
 const INDEX_PATH: &str = "/";
 const A_404_PATH: &str = "/404";
+const SLOW_PATH: &str = "/slow";
 
 // Indexes to the above paths.
 const INDEX_KEY: usize = 0;
@@ -18,6 +20,10 @@ const A_404_KEY: usize = 1;
 // Load test configuration.
 const EXPECT_WORKERS: usize = 2;
 
+// How long requests to SLOW_PATH wait for a response, and how long it takes to respond.
+const SLOW_TIMEOUT: u64 = 100;
+const SLOW_DELAY: u64 = 1_000;
+
 // There are multiple test variations in this file.
 #[derive(Clone)]
 enum TestType {
@@ -39,6 +45,16 @@ pub async fn get_404_path(user: &SwanlingUser) -> SwanlingTaskResult {
     Ok(())
 }
 
+// Test task, timing out before the server responds.
+pub async fn get_slow_path(user: &SwanlingUser) -> SwanlingTaskResult {
+    let request_builder = user
+        .swanling_get(SLOW_PATH)
+        .await?
+        .timeout(std::time::Duration::from_millis(SLOW_TIMEOUT));
+    let _swanling = user.swanling_send(request_builder, None).await?;
+    Ok(())
+}
+
 // All tests in this file run against common endpoints.
 fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
     vec![
@@ -127,6 +143,8 @@ fn validate_error(
                 assert!(error.1.name == A_404_PATH);
                 // The error was captured the number of times we requested the 404 path.
                 assert!(error.1.occurrences == a_404_metrics.fail_count);
+                // An unexpected response isn't a connection level failure.
+                assert!(error.1.kind == SwanlingErrorKind::Other);
             }
         }
         TestType::NoErrorSummary => {
@@ -261,3 +279,79 @@ fn test_no_error_summary() {
 fn test_no_error_summary_gaggle() {
     run_gaggle_test(TestType::NoErrorSummary);
 }
+
+#[test]
+// Confirm requests that time out are recorded as a distinct kind of error.
+fn test_error_kind_timeout() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // The server responds more slowly than requests wait.
+    let slow = server.mock(|when, then| {
+        when.method(GET).path(SLOW_PATH);
+        then.status(200)
+            .delay(std::time::Duration::from_millis(SLOW_DELAY));
+    });
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, &mut vec![]),
+            &taskset!("LoadTest").register_task(task!(get_slow_path)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Every request timed out.
+    assert!(slow.hits() > 0);
+    assert_eq!(swanling_metrics.errors.len(), 1);
+    for error in swanling_metrics.errors.values() {
+        assert_eq!(error.kind, SwanlingErrorKind::Timeout);
+        assert!(error.error.starts_with("Request timed out: "));
+    }
+
+    // The report counts failures by kind.
+    let report = swanling_metrics.to_string();
+    assert!(report.contains("Request timed out\n"));
+}
+
+#[test]
+// Confirm requests that can't connect are recorded as a distinct kind of error.
+fn test_error_kind_connection() {
+    // Start the mock server, only used to build the configuration.
+    let server = MockServer::start();
+
+    // Find a port nothing is listening on.
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let host = format!("http://127.0.0.1:{}", port);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, &mut vec!["--host", &host]),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Every request failed to connect.
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(index_metrics.success_count, 0);
+    assert_eq!(swanling_metrics.errors.len(), 1);
+    for error in swanling_metrics.errors.values() {
+        assert_eq!(error.kind, SwanlingErrorKind::Connection);
+        assert!(error.error.starts_with("Connection failed: "));
+        assert_eq!(error.occurrences, index_metrics.fail_count);
+    }
+}
@@ -0,0 +1,154 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+const MISSING_PATH: &str = "/missing";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+const MISSING_KEY: usize = 2;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_missing(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(MISSING_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(503);
+        }),
+        // Next set up MISSING_PATH, store in vector at MISSING_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(MISSING_PATH);
+            then.status(404);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, error_tail: usize) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let error_tail = error_tail.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--error-tail",
+            &error_tail,
+        ],
+    )
+}
+
+#[test]
+// Confirm the most recent distinct errors are tracked while the load test runs.
+fn test_error_tail() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, 2),
+            &taskset!("LoadTest")
+                .register_task(task!(get_index))
+                .register_task(task!(get_error))
+                .register_task(task!(get_missing)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+    assert!(mock_endpoints[MISSING_KEY].hits() > 0);
+
+    // Both distinct errors are in the tail, successful requests are not.
+    assert_eq!(swanling_metrics.error_tail.len(), 2);
+    let mut errors: Vec<&str> = swanling_metrics
+        .error_tail
+        .iter()
+        .map(|recent_error| recent_error.error.as_str())
+        .collect();
+    errors.sort_unstable();
+    assert_eq!(
+        errors,
+        vec!["404 Not Found: /missing", "503 Service Unavailable: /error"]
+    );
+    for recent_error in &swanling_metrics.error_tail {
+        assert!(recent_error.occurrences > 0);
+        assert!(recent_error.last_seen <= RUN_TIME);
+    }
+}
+
+#[test]
+// Confirm only the configured number of distinct errors are kept.
+fn test_error_tail_size() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, 1),
+            &taskset!("LoadTest")
+                .register_task(task!(get_error))
+                .register_task(task!(get_missing)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+    assert!(mock_endpoints[MISSING_KEY].hits() > 0);
+
+    // Only the most recent error is kept, although both are in the error summary.
+    assert_eq!(swanling_metrics.error_tail.len(), 1);
+    assert_eq!(swanling_metrics.errors.len(), 2);
+}
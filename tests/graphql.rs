@@ -0,0 +1,143 @@
+use httpmock::{Method::POST, MockRef, MockServer};
+use serde_json::json;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::swanling::{graphql_body, GRAPHQL_ERROR};
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const GRAPHQL_PATH: &str = "/graphql";
+
+// Indexes to the above paths.
+const ITEM_KEY: usize = 0;
+const CART_KEY: usize = 1;
+
+// GraphQL operations performed during these tests.
+const ITEM_OPERATION: &str = "GetItem";
+const ITEM_QUERY: &str = "query GetItem($id: ID!) { item(id: $id) { name } }";
+const CART_OPERATION: &str = "GetCart";
+const CART_QUERY: &str = "query GetCart { cart { items } }";
+
+// The error returned by the cart operation.
+const CART_ERROR: &str = "cart service unavailable";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user
+        .graphql(GRAPHQL_PATH, ITEM_OPERATION, ITEM_QUERY, json!({ "id": 1 }))
+        .await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_cart(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user
+        .graphql(GRAPHQL_PATH, CART_OPERATION, CART_QUERY, json!({}))
+        .await?;
+    Ok(())
+}
+
+// All tests in this file run against a common GraphQL endpoint, responding to each
+// operation with a 200 status code.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up ITEM_OPERATION, store in vector at ITEM_KEY.
+        server.mock(|when, then| {
+            when.method(POST).path(GRAPHQL_PATH).json_body(graphql_body(
+                ITEM_OPERATION,
+                ITEM_QUERY,
+                json!({ "id": 1 }),
+            ));
+            then.status(200)
+                .json_body(json!({ "data": { "item": { "name": "swanling" } } }));
+        }),
+        // Next set up CART_OPERATION, store in vector at CART_KEY.
+        server.mock(|when, then| {
+            when.method(POST).path(GRAPHQL_PATH).json_body(graphql_body(
+                CART_OPERATION,
+                CART_QUERY,
+                json!({}),
+            ));
+            then.status(200).json_body(json!({
+                "data": null,
+                "errors": [{ "message": CART_ERROR }],
+            }));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm GraphQL requests are named after their operation, and GraphQL errors are
+// counted as failures.
+fn test_graphql() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(get_item))
+                .register_task(task!(get_cart)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let item_hits = mock_endpoints[ITEM_KEY].hits();
+    let cart_hits = mock_endpoints[CART_KEY].hits();
+    assert!(item_hits > 0);
+    assert!(cart_hits > 0);
+
+    // Requests are named after the operation, not the path.
+    assert!(!swanling_metrics
+        .requests
+        .contains_key(&format!("POST {}", GRAPHQL_PATH)));
+    let item_metrics = swanling_metrics
+        .requests
+        .get(&format!("POST {}", ITEM_OPERATION))
+        .unwrap();
+    assert_eq!(item_metrics.success_count, item_hits);
+    assert_eq!(item_metrics.fail_count, 0);
+
+    // Responses with GraphQL errors failed, even though their status code was 200.
+    let cart_metrics = swanling_metrics
+        .requests
+        .get(&format!("POST {}", CART_OPERATION))
+        .unwrap();
+    assert_eq!(cart_metrics.success_count, 0);
+    assert_eq!(cart_metrics.fail_count, cart_hits);
+    assert!(swanling_metrics
+        .errors
+        .values()
+        .any(|error| error.error == format!("{}: {}", GRAPHQL_ERROR, CART_ERROR)));
+}
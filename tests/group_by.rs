@@ -0,0 +1,216 @@
+use httpmock::{HttpMockRequest, Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// The label requests are grouped by, and its values.
+const LABEL: &str = "region";
+const EU: &str = "eu";
+const US: &str = "us";
+
+// The header identifying the region that served each request, and the regions serving
+// each path.
+const REGION_HEADER: &str = "CF-Ray";
+const SJC: &str = "SJC";
+const IAD: &str = "IAD";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task, requesting the index from both regions.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    for region in &[EU, US] {
+        let request_builder = user
+            .swanling_get(INDEX_PATH)
+            .await?
+            .label(LABEL, region)
+            .label("tier", "premium");
+        let _swanling = user.swanling_send(request_builder, None).await?;
+    }
+    Ok(())
+}
+
+// Test task, making requests without the label.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let request_builder = user.swanling_get(ABOUT_PATH).await?.label("tier", "free");
+    let _swanling = user.swanling_send(request_builder, None).await?;
+    Ok(())
+}
+
+// Test task, requesting both paths without labels.
+pub async fn get_pages(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// Labels are never sent to the server.
+fn without_labels(request: &HttpMockRequest) -> bool {
+    !request.headers.as_ref().is_some_and(|headers| {
+        headers
+            .iter()
+            .any(|(name, _)| name.to_lowercase().starts_with("x-swanling-label-"))
+    })
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH).matches(without_labels);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH).matches(without_labels);
+            then.status(200);
+        }),
+    ]
+}
+
+// Set up endpoints served from different regions.
+fn setup_mock_server_region_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200)
+                .header(REGION_HEADER, &format!("6d1f4f1b8c7a1234-{}", SJC));
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200)
+                .header(REGION_HEADER, &format!("6d1f4f1b8c7a5678-{}", IAD));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--group-by",
+            LABEL,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm requests are grouped by the value of their label, without sending it.
+fn test_group_by() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(get_index))
+                .register_task(task!(get_about)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    let about_hits = mock_endpoints[ABOUT_KEY].hits();
+    assert!(index_hits > 0);
+    assert!(about_hits > 0);
+
+    // No request failed, so no labels were sent to the server.
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(index_metrics.fail_count, 0);
+    assert_eq!(index_metrics.raw_data.counter, index_hits);
+
+    // Each region is grouped, requests without the label are not.
+    assert_eq!(swanling_metrics.groups.len(), 2);
+    let eu = swanling_metrics.groups.get(EU).unwrap();
+    let us = swanling_metrics.groups.get(US).unwrap();
+    assert_eq!(eu.raw_data.counter + us.raw_data.counter, index_hits);
+    assert!(eu.raw_data.counter >= us.raw_data.counter);
+    assert!(eu.raw_data.counter - us.raw_data.counter <= USERS);
+    assert_eq!(eu.fail_count + us.fail_count, 0);
+}
+
+#[test]
+// Confirm requests are grouped by the region in a response header.
+fn test_region_header() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_region_endpoints(&server);
+
+    // Group requests by the region serving them, instead of by a label.
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--region-header",
+            REGION_HEADER,
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_pages)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    let about_hits = mock_endpoints[ABOUT_KEY].hits();
+    assert!(index_hits > 0);
+    assert!(about_hits > 0);
+
+    // Requests are grouped by the region that served them.
+    assert_eq!(swanling_metrics.groups.len(), 2);
+    let sjc = swanling_metrics.groups.get(SJC).unwrap();
+    let iad = swanling_metrics.groups.get(IAD).unwrap();
+    assert_eq!(sjc.raw_data.counter, index_hits);
+    assert_eq!(iad.raw_data.counter, about_hits);
+    assert_eq!(sjc.fail_count + iad.fail_count, 0);
+}
@@ -0,0 +1,197 @@
+use httpmock::{
+    Method::{GET, POST},
+    MockRef, MockServer,
+};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const LOGIN_PATH: &str = "/login";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const LOGIN_KEY: usize = 1;
+
+// The recorded header and body, which must be replayed.
+const ACCEPT_HEADER: &str = "text/html";
+const LOGIN_BODY: &str = "name=swan";
+
+// Files the HAR recordings are written to.
+const HAR_FILE: &str = "har-test.har";
+const HAR_TIMING_FILE: &str = "har-timing-test.har";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// All tests in this file run against common endpoints, only responding to requests
+// replayed with the recorded header and body.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("accept", ACCEPT_HEADER);
+            then.status(200);
+        }),
+        // Next set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(POST).path(LOGIN_PATH).body(LOGIN_BODY);
+            then.status(200);
+        }),
+    ]
+}
+
+// Write a HAR recording of a request for the index, a data: URL, a request to another
+// host, and then logging in the given number of milliseconds after the first request.
+fn write_har_file(server: &MockServer, har_file: &str, login_delay: usize) {
+    std::fs::write(
+        har_file,
+        format!(
+            r#"{{"log": {{"entries": [
+                {{
+                    "startedDateTime": "2021-06-01T10:00:00.000Z",
+                    "request": {{
+                        "method": "GET",
+                        "url": "{}",
+                        "headers": [{{"name": "Accept", "value": "{}"}}]
+                    }}
+                }},
+                {{
+                    "startedDateTime": "2021-06-01T10:00:00.000Z",
+                    "request": {{"method": "GET", "url": "data:image/gif;base64,R0lGODlh"}}
+                }},
+                {{
+                    "startedDateTime": "2021-06-01T10:00:00.000Z",
+                    "request": {{"method": "GET", "url": "https://analytics.example.com/"}}
+                }},
+                {{
+                    "startedDateTime": "2021-06-01T10:00:{:02}.{:03}Z",
+                    "request": {{
+                        "method": "POST",
+                        "url": "{}",
+                        "postData": {{"mimeType": "text/plain", "text": "{}"}}
+                    }}
+                }}
+            ]}}}}"#,
+            server.url(INDEX_PATH),
+            ACCEPT_HEADER,
+            login_delay / 1_000,
+            login_delay % 1_000,
+            server.url(LOGIN_PATH),
+            LOGIN_BODY,
+        ),
+    )
+    .expect("failed to write HAR file");
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    har_file: &str,
+    har_timing: bool,
+) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--har",
+        har_file,
+        "--no-reset-metrics",
+    ];
+    if har_timing {
+        configuration.push("--har-timing");
+    }
+    common::build_configuration(server, configuration)
+}
+
+#[test]
+// Confirm the requests recorded in a HAR file are replayed, skipping requests that
+// aren't HTTP requests to the recorded host.
+fn test_har() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    write_har_file(&server, HAR_FILE, 0);
+
+    // Run the Swanling Attack, without defining any task sets.
+    let swanling_metrics = common::run_load_test(
+        SwanlingAttack::initialize_with_config(common_build_configuration(
+            &server, HAR_FILE, false,
+        ))
+        .unwrap(),
+        None,
+    );
+
+    // Both requests were replayed, once each per pass.
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    let login_hits = mock_endpoints[LOGIN_KEY].hits();
+    assert!(index_hits > 0);
+    assert!(login_hits > 0);
+    assert!(index_hits - login_hits <= USERS);
+
+    // No other requests were made.
+    assert_eq!(swanling_metrics.requests.len(), 2);
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(index_metrics.fail_count, 0);
+    assert_eq!(index_metrics.success_count, index_hits);
+    let login_metrics = swanling_metrics
+        .requests
+        .get(&format!("POST {}", LOGIN_PATH))
+        .unwrap();
+    assert_eq!(login_metrics.fail_count, 0);
+    assert_eq!(login_metrics.success_count, login_hits);
+
+    // Cleanup from test.
+    common::cleanup_files(vec![HAR_FILE]);
+}
+
+#[test]
+// Confirm --har-timing waits between requests as long as they were recorded.
+fn test_har_timing() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Logging in was recorded most of the load test after loading the index.
+    write_har_file(&server, HAR_TIMING_FILE, 1_500);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        SwanlingAttack::initialize_with_config(common_build_configuration(
+            &server,
+            HAR_TIMING_FILE,
+            true,
+        ))
+        .unwrap(),
+        None,
+    );
+
+    // Each user waited before logging in, so made at most two passes through the
+    // requests, finishing the last after the load test stopped.
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    let login_hits = mock_endpoints[LOGIN_KEY].hits();
+    assert!((USERS..=USERS * 2).contains(&index_hits));
+    assert!((USERS..=USERS * 2).contains(&login_hits));
+
+    // Cleanup from test.
+    common::cleanup_files(vec![HAR_TIMING_FILE]);
+}
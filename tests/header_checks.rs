@@ -0,0 +1,219 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use serde::Deserialize;
+
+mod common;
+
+use swanling::metrics::SwanlingCustomMetricAggregate;
+use swanling::prelude::*;
+use swanling::swanling::INVALID_RESPONSE_ERROR;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const PRIMARY_PATH: &str = "/primary";
+const FALLBACK_PATH: &str = "/fallback";
+
+// Indexes to the above paths.
+const PRIMARY_KEY: usize = 0;
+const FALLBACK_KEY: usize = 1;
+
+// The error and custom counter recorded by the header checks.
+const FALLBACK_ERROR: &str = "served from fallback";
+const UNCACHED_COUNTER: &str = "uncached";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// The expected shape of the responses.
+#[derive(Deserialize)]
+struct Item {
+    id: usize,
+}
+
+// The header checks applied by all test tasks.
+fn header_checks() -> Vec<(SwanlingHeaderCheck, SwanlingHeaderOutcome)> {
+    vec![
+        (
+            SwanlingHeaderCheck::Equals("x-served-from".to_string(), "fallback".to_string()),
+            SwanlingHeaderOutcome::Fail(FALLBACK_ERROR.to_string()),
+        ),
+        (
+            SwanlingHeaderCheck::Absent("x-cache".to_string()),
+            SwanlingHeaderOutcome::Tag(UNCACHED_COUNTER.to_string()),
+        ),
+    ]
+}
+
+// Test task.
+pub async fn get_primary(user: &SwanlingUser) -> SwanlingTaskResult {
+    let mut swanling = user.get(PRIMARY_PATH).await?;
+    user.check_headers(&mut swanling, &header_checks())?;
+    let item: Item = user.deserialize(swanling).await?;
+    assert_eq!(item.id, 1);
+    Ok(())
+}
+
+// Test task.
+pub async fn get_fallback(user: &SwanlingUser) -> SwanlingTaskResult {
+    let mut swanling = user.get(FALLBACK_PATH).await?;
+    user.check_headers(&mut swanling, &header_checks())?;
+    let _item: Item = user.deserialize(swanling).await?;
+    Ok(())
+}
+
+// Test task, only tags responses then validates the body.
+pub async fn get_fallback_tagged(user: &SwanlingUser) -> SwanlingTaskResult {
+    let mut swanling = user.get(FALLBACK_PATH).await?;
+    user.check_headers(&mut swanling, &header_checks()[1..])?;
+    let _item: Item = user.deserialize(swanling).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up PRIMARY_PATH, store in vector at PRIMARY_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(PRIMARY_PATH);
+            then.status(200)
+                .header("x-served-from", "primary")
+                .body(r#"{"id": 1}"#);
+        }),
+        // Next set up FALLBACK_PATH, store in vector at FALLBACK_KEY. Return a success
+        // status code, but flag the response as degraded and leave the body incomplete.
+        server.mock(|when, then| {
+            when.method(GET).path(FALLBACK_PATH);
+            then.status(200)
+                .header("x-served-from", "fallback")
+                .body(r#"{"id""#);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+// Run a load test against a new mock server, returning how many requests the server
+// received on each path and the final metrics.
+fn run_load_test(task: SwanlingTask) -> (Vec<usize>, SwanlingMetrics) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let hits = mock_endpoints
+        .iter()
+        .map(|endpoint| endpoint.hits())
+        .collect();
+    (hits, swanling_metrics)
+}
+
+#[test]
+// Confirm responses passing all checks succeed, and matching tags are counted.
+fn test_header_checks() {
+    let (hits, swanling_metrics) = run_load_test(task!(get_primary));
+
+    assert!(hits[PRIMARY_KEY] > 0);
+    assert_eq!(hits[FALLBACK_KEY], 0);
+
+    // No requests or tasks failed.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", PRIMARY_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.fail_count, 0);
+    assert!(swanling_metrics.errors.is_empty());
+    assert_eq!(swanling_metrics.tasks[0][0].fail_count, 0);
+
+    // Every response was tagged as uncached.
+    assert_eq!(
+        swanling_metrics.custom[UNCACHED_COUNTER],
+        SwanlingCustomMetricAggregate::Counter(hits[PRIMARY_KEY])
+    );
+}
+
+#[test]
+// Confirm a header check fails a request despite the success status code.
+fn test_header_checks_failed() {
+    let (hits, swanling_metrics) = run_load_test(task!(get_fallback));
+
+    assert!(hits[FALLBACK_KEY] > 0);
+
+    // Every request failed.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", FALLBACK_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.success_count, 0);
+    assert_eq!(request_metrics.fail_count, hits[FALLBACK_KEY]);
+
+    // All failures were collected as the header error, the body was never checked.
+    assert_eq!(swanling_metrics.errors.len(), 1);
+    for error in swanling_metrics.errors.values() {
+        assert_eq!(error.error, FALLBACK_ERROR);
+        assert_eq!(error.occurrences, request_metrics.fail_count);
+    }
+
+    // Every task failed.
+    assert!(swanling_metrics.tasks[0][0].fail_count > 0);
+    assert_eq!(swanling_metrics.tasks[0][0].success_count, 0);
+
+    // Every check was applied, so failed responses were also tagged.
+    assert_eq!(
+        swanling_metrics.custom[UNCACHED_COUNTER],
+        SwanlingCustomMetricAggregate::Counter(hits[FALLBACK_KEY])
+    );
+}
+
+#[test]
+// Confirm tagged responses can still fail body validation.
+fn test_header_checks_tagged() {
+    let (hits, swanling_metrics) = run_load_test(task!(get_fallback_tagged));
+
+    assert!(hits[FALLBACK_KEY] > 0);
+
+    // Every request failed body validation.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", FALLBACK_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.success_count, 0);
+    assert_eq!(request_metrics.fail_count, hits[FALLBACK_KEY]);
+    assert_eq!(swanling_metrics.errors.len(), 1);
+    for error in swanling_metrics.errors.values() {
+        assert!(error.error.starts_with(INVALID_RESPONSE_ERROR));
+    }
+
+    // Every response was also tagged as uncached.
+    assert_eq!(
+        swanling_metrics.custom[UNCACHED_COUNTER],
+        SwanlingCustomMetricAggregate::Counter(hits[FALLBACK_KEY])
+    );
+}
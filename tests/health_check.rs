@@ -0,0 +1,179 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const HEALTH_PATH: &str = "/health";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const HEALTH_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints, the health check responding with
+// the given status code.
+fn setup_mock_server_endpoints(server: &MockServer, health_status: u16) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up HEALTH_PATH, store in vector at HEALTH_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(HEALTH_PATH);
+            then.status(health_status);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Run the load test, returning its result.
+fn run_load_test(server: &MockServer, custom: &[&str]) -> Result<SwanlingMetrics, SwanlingError> {
+    SwanlingAttack::initialize_with_config(common_build_configuration(server, custom))
+        .unwrap()
+        .register_taskset(taskset!("LoadTest").register_task(task!(get_index)))
+        .execute()
+}
+
+#[test]
+// Confirm the load test starts once the health check passes.
+fn test_health_check() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server, 200);
+
+    // Run the Swanling Attack.
+    let health_check_url = server.url(HEALTH_PATH);
+    let swanling_metrics =
+        run_load_test(&server, &["--health-check-url", &health_check_url]).unwrap();
+
+    // The health check ran once, and isn't included in the metrics.
+    assert_eq!(mock_endpoints[HEALTH_KEY].hits(), 1);
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(!swanling_metrics
+        .requests
+        .contains_key(&format!("GET {}", HEALTH_PATH)));
+}
+
+#[test]
+// Confirm the load test refuses to start when the health check fails.
+fn test_health_check_failed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server, 503);
+
+    // Run the Swanling Attack.
+    let health_check_url = server.url(HEALTH_PATH);
+    match run_load_test(&server, &["--health-check-url", &health_check_url]) {
+        Err(SwanlingError::HealthCheckFailed { url, detail }) => {
+            assert_eq!(url, health_check_url);
+            assert!(detail.contains("503"));
+        }
+        _ => panic!("load test started despite the failed health check"),
+    }
+
+    // No requests were made against the target.
+    assert_eq!(mock_endpoints[HEALTH_KEY].hits(), 1);
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), 0);
+}
+
+#[test]
+// Confirm the health check can expect a specific status code.
+fn test_health_check_expect() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server, 204);
+
+    // The expected status code passes.
+    let health_check_url = server.url(HEALTH_PATH);
+    let _swanling_metrics = run_load_test(
+        &server,
+        &[
+            "--health-check-url",
+            &health_check_url,
+            "--health-check-expect",
+            "204",
+        ],
+    )
+    .unwrap();
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(index_hits > 0);
+
+    // Any other status code fails, even a success.
+    let result = run_load_test(
+        &server,
+        &[
+            "--health-check-url",
+            &health_check_url,
+            "--health-check-expect",
+            "200",
+        ],
+    );
+    assert!(matches!(
+        result,
+        Err(SwanlingError::HealthCheckFailed { .. })
+    ));
+    assert_eq!(mock_endpoints[HEALTH_KEY].hits(), 2);
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), index_hits);
+}
+
+#[test]
+// Confirm the load test refuses to start with an invalid health check.
+fn test_health_check_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // An expected status code without a URL.
+    assert!(run_load_test(&server, &["--health-check-expect", "200"]).is_err());
+
+    // A URL that isn't a full URL.
+    assert!(run_load_test(&server, &["--health-check-url", HEALTH_PATH]).is_err());
+
+    // An expected status code that isn't a status code.
+    let health_check_url = server.url(HEALTH_PATH);
+    assert!(run_load_test(
+        &server,
+        &[
+            "--health-check-url",
+            &health_check_url,
+            "--health-check-expect",
+            "1000",
+        ],
+    )
+    .is_err());
+}
@@ -0,0 +1,110 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(get_index))
+}
+
+#[test]
+// Confirm environment variables referenced in --host are expanded.
+fn test_host_env() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Configure the host through environment variables.
+    std::env::set_var("SWANLING_TEST_HOST_ENV_PORT", server.port().to_string());
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--host", "http://127.0.0.1:${SWANLING_TEST_HOST_ENV_PORT}"],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm requests were made to the expanded host.
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+}
+
+#[test]
+// Confirm referencing an unset environment variable in --host is an error.
+fn test_host_env_unset() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Reference an environment variable that isn't set.
+    std::env::remove_var("SWANLING_TEST_HOST_ENV_UNSET");
+    let configuration =
+        common::build_configuration(&server, vec!["--host", "${SWANLING_TEST_HOST_ENV_UNSET}"]);
+
+    // Confirm the load test refuses to start, explaining which variable isn't set.
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(get_tasks());
+    match swanling_attack.execute() {
+        Err(SwanlingError::InvalidOption { option, detail, .. }) => {
+            assert_eq!(option, "--host");
+            assert!(detail.contains("SWANLING_TEST_HOST_ENV_UNSET"));
+        }
+        other => panic!("expected InvalidOption, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+// Confirm environment variables referenced in a task set's host are expanded.
+fn test_host_env_task_set() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Configure the task set's host through environment variables, without --host.
+    std::env::set_var(
+        "SWANLING_TEST_HOST_ENV_TASK_SET_PORT",
+        server.port().to_string(),
+    );
+    let configuration = common::build_configuration(&server, vec!["--host", ""]);
+    let task_set = get_tasks().set_host("http://127.0.0.1:${SWANLING_TEST_HOST_ENV_TASK_SET_PORT}");
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &task_set, None, None),
+        None,
+    );
+
+    // Confirm requests were made to the expanded host.
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+}
@@ -0,0 +1,139 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 5;
+
+// How many seconds users wait between requests, longer than the idle timeout.
+const WAIT_TIME: usize = 2;
+const POOL_IDLE_TIMEOUT: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests, load testing the server by host name
+// so each new connection resolves it.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let host = format!("http://localhost:{}", server.port());
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--host",
+        &host,
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Run a load test with users pausing between requests, returning the metrics.
+fn run_load_test(server: &MockServer, custom: &[&str]) -> SwanlingMetrics {
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(server, custom),
+            &taskset!("LoadTest")
+                .set_wait_time(WAIT_TIME, WAIT_TIME)
+                .unwrap()
+                .register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Confirm idle connections are closed after the pool idle timeout, so the next request
+// opens a new connection.
+fn test_pool_idle_timeout() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // By default each user reuses its connection.
+    let swanling_metrics = run_load_test(&server, &[]);
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(request_metrics.success_count > USERS);
+    assert_eq!(
+        mock_endpoints[INDEX_KEY].hits(),
+        request_metrics.success_count
+    );
+    let dns_time = request_metrics.dns_time_data.as_ref().unwrap();
+    assert_eq!(dns_time.counter, USERS);
+
+    // With a shorter timeout than the wait, each request opens a new connection.
+    let pool_idle_timeout = POOL_IDLE_TIMEOUT.to_string();
+    let swanling_metrics = run_load_test(
+        &server,
+        &[
+            "--pool-idle-timeout",
+            &pool_idle_timeout,
+            "--keepalive-idle-timeout",
+            "30",
+        ],
+    );
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(request_metrics.success_count > USERS);
+    let dns_time = request_metrics.dns_time_data.as_ref().unwrap();
+    assert_eq!(dns_time.counter, request_metrics.success_count);
+}
+
+#[test]
+// Confirm the load test refuses to start with unusable timeouts.
+fn test_idle_timeout_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for custom in &[
+        vec!["--pool-idle-timeout", "0"],
+        vec!["--keepalive-idle-timeout", "0"],
+        vec!["--pool-idle-timeout", "10", "--pool-size", "0"],
+    ] {
+        let swanling_attack = common::build_load_test(
+            common_build_configuration(&server, custom),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
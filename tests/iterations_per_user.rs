@@ -0,0 +1,151 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::metrics::SwanlingIterations;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 3;
+const ITERATIONS: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    run_time: usize,
+    iterations: usize,
+) -> SwanlingConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &run_time.to_string(),
+            "--iterations-per-user",
+            &iterations.to_string(),
+        ],
+    )
+}
+
+#[test]
+// Confirm each user stops after running all of its iterations, ending the load test
+// before the run time expires.
+fn test_iterations_per_user() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let run_time = 30;
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, run_time, ITERATIONS),
+            &taskset!("LoadTest")
+                .register_task(task!(get_index).set_sequence(1))
+                .register_task(task!(get_about).set_sequence(2)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Each user ran every task once per iteration.
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), USERS * ITERATIONS);
+    assert_eq!(mock_endpoints[ABOUT_KEY].hits(), USERS * ITERATIONS);
+    assert_eq!(
+        swanling_metrics.iterations,
+        Some(SwanlingIterations {
+            per_user: ITERATIONS,
+            completed: USERS * ITERATIONS,
+            in_progress: 0,
+        })
+    );
+
+    // The load test stopped once all users were done.
+    assert!(swanling_metrics.duration < run_time);
+}
+
+#[test]
+// Confirm iterations users are still running when the load test stops are counted as in
+// progress, not completed.
+fn test_iterations_per_user_in_progress() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack. Waiting after each task makes an iteration take longer
+    // than the load test runs.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, 2, ITERATIONS),
+            &taskset!("LoadTest")
+                .set_wait_time(2, 2)
+                .unwrap()
+                .register_task(task!(get_index).set_sequence(1))
+                .register_task(task!(get_about).set_sequence(2)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Each user started, but didn't complete, its first iteration.
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), USERS);
+    assert_eq!(mock_endpoints[ABOUT_KEY].hits(), 0);
+    assert_eq!(
+        swanling_metrics.iterations,
+        Some(SwanlingIterations {
+            per_user: ITERATIONS,
+            completed: 0,
+            in_progress: USERS,
+        })
+    );
+
+    // The report notes both completed and in progress iterations.
+    let report = swanling_metrics.to_string();
+    assert!(report.contains("=== ITERATIONS ==="));
+}
@@ -0,0 +1,159 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(503);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    junit_xml: &str,
+    slas: &[&str],
+) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--junit-xml",
+        junit_xml,
+    ];
+    for sla in slas {
+        configuration.push("--sla");
+        configuration.push(*sla);
+    }
+    common::build_configuration(server, configuration)
+}
+
+// Helper to confirm the load test ran against both endpoints.
+fn validate_test(mock_endpoints: &[MockRef]) {
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest")
+        .register_task(task!(get_index))
+        .register_task(task!(get_error))
+}
+
+#[test]
+// Confirm each request is a testcase, failing if any of its requests failed.
+fn test_junit_xml_requests() {
+    const JUNIT_XML: &str = "junit-xml-requests.xml";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, JUNIT_XML, &[]),
+            &get_tasks(),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    validate_test(&mock_endpoints);
+
+    let xml = std::fs::read_to_string(JUNIT_XML).expect("failed to read JUnit XML report");
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(xml.contains("<testsuite name=\"swanling\" tests=\"2\" failures=\"1\""));
+    assert!(xml.contains("<testcase classname=\"requests\" name=\"GET /\" time=\""));
+    assert!(xml.contains("<testcase classname=\"requests\" name=\"GET /error\" time=\""));
+    // Every request to ERROR_PATH failed.
+    assert_eq!(xml.matches("<failure message=").count(), 1);
+    assert!(xml.contains(" requests failed (100.00%)\"/>"));
+
+    // Cleanup from test.
+    common::cleanup_files(vec![JUNIT_XML]);
+}
+
+#[test]
+// Confirm each service level agreement is a testcase, and the report is written even if
+// an agreement is breached.
+fn test_junit_xml_slas() {
+    const JUNIT_XML: &str = "junit-xml-slas.xml";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack, allowing no errors on /error.
+    let swanling_attack = common::build_load_test(
+        common_build_configuration(&server, JUNIT_XML, &["/:p95=10000", "/error:errors=0"]),
+        &get_tasks(),
+        None,
+        None,
+    );
+    match swanling_attack.execute() {
+        Err(SwanlingError::SlaBreach { .. }) => (),
+        other => panic!("expected SlaBreach, got {:?}", other.map(|_| ())),
+    }
+
+    validate_test(&mock_endpoints);
+
+    let xml = std::fs::read_to_string(JUNIT_XML).expect("failed to read JUnit XML report");
+    assert!(xml.contains("<testsuite name=\"swanling\" tests=\"2\" failures=\"1\""));
+    assert!(
+        xml.contains("<testcase classname=\"sla\" name=\"/: p95 &lt;= 10000 ms\" time=\"0.000\"/>")
+    );
+    assert!(xml.contains("<testcase classname=\"sla\" name=\"/error: errors &lt;= 0%\""));
+    assert!(xml.contains("<failure message=\"observed 100.00%, breaching errors &lt;= 0%\"/>"));
+
+    // Cleanup from test.
+    common::cleanup_files(vec![JUNIT_XML]);
+}
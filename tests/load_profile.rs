@@ -0,0 +1,99 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// File the load profile is written to.
+const LOAD_PROFILE_FILE: &str = "load-profile-test.csv";
+
+// Load test configuration. The profile grows from the configured users to the peak, and
+// then shrinks again until the run time expires.
+const USERS: usize = 2;
+const PEAK_USERS: usize = 6;
+const LOAD_PROFILE: &str = "# offset,users\n0,2\n2,6\n4,1\n";
+const RUN_TIME: usize = 4;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--load-profile",
+            LOAD_PROFILE_FILE,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm users are launched and stopped to trace the load profile.
+fn test_load_profile() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    std::fs::write(LOAD_PROFILE_FILE, LOAD_PROFILE).expect("failed to write load profile");
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Cleanup from test before any assertion can fail.
+    common::cleanup_files(vec![LOAD_PROFILE_FILE]);
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // The load test ran until the run time expired, measured in whole seconds.
+    assert!((RUN_TIME..=RUN_TIME + 1).contains(&swanling_metrics.duration));
+
+    // Users were launched up to the peak, and not again while shrinking.
+    assert_eq!(swanling_metrics.users, PEAK_USERS);
+    let task_set_users: usize = swanling_metrics
+        .task_set_users
+        .iter()
+        .map(|task_set_users| task_set_users.users)
+        .sum();
+    assert_eq!(task_set_users, PEAK_USERS);
+}
@@ -589,3 +589,112 @@ fn test_all_logs_raw() {
 fn test_all_logs_raw_gaggle() {
     run_gaggle_test(TestType::All, "raw");
 }
+
+#[test]
+// Limit the requests log to a single request name, while collecting metrics for all.
+fn test_requests_logs_log_only() {
+    let request_log = "log-only-request-log.json";
+
+    let server = MockServer::start();
+
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--request-log",
+            request_log,
+            "--request-format",
+            "json",
+            "--log-only",
+            ERROR_PATH,
+            "--users",
+            "4",
+            "--hatch-rate",
+            "4",
+            "--run-time",
+            "2",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    // Metrics are collected for all requests.
+    assert!(swanling_metrics
+        .requests
+        .contains_key(&format!("GET {}", INDEX_PATH)));
+    assert!(swanling_metrics
+        .requests
+        .contains_key(&format!("GET {}", ERROR_PATH)));
+
+    // Only the listed request is logged.
+    let logged = std::fs::read_to_string(request_log).expect("failed to read request log");
+    assert!(logged.lines().count() > 0);
+    for line in logged.lines() {
+        assert!(line.contains(&format!(r#""name":"{}""#, ERROR_PATH)));
+    }
+
+    common::cleanup_files(vec![request_log]);
+}
+
+#[test]
+// Log a sample of requests, while collecting metrics for all.
+fn test_requests_logs_sample() {
+    let request_log = "sample-request-log.json";
+
+    let server = MockServer::start();
+
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--request-log",
+            request_log,
+            "--request-format",
+            "json",
+            "--request-log-sample",
+            "0.1",
+            "--request-log-seed",
+            "42",
+            "--users",
+            "4",
+            "--hatch-rate",
+            "4",
+            "--run-time",
+            "2",
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    let hits = mock_endpoints[INDEX_KEY].hits() + mock_endpoints[ERROR_KEY].hits();
+    assert!(hits > 0);
+
+    // Metrics are collected for all requests.
+    let requests: usize = swanling_metrics
+        .requests
+        .values()
+        .map(|request| request.success_count + request.fail_count)
+        .sum();
+    assert_eq!(requests, hits);
+
+    // Only a fraction of requests are logged.
+    let logged = std::fs::read_to_string(request_log).expect("failed to read request log");
+    assert!(logged.lines().count() > 0);
+    assert!(logged.lines().count() < hits / 2);
+
+    common::cleanup_files(vec![request_log]);
+}
@@ -0,0 +1,149 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const HOME_PATH: &str = "/";
+const SEARCH_PATH: &str = "/search";
+
+// Indexes to the above paths.
+const HOME_KEY: usize = 0;
+const SEARCH_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 4;
+const SESSIONS: usize = 3;
+const RUN_TIME: usize = 10;
+const SEED: u64 = 42;
+
+// Test task.
+pub async fn get_home(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(HOME_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_search(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SEARCH_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up HOME_PATH, store in vector at HOME_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(HOME_PATH);
+            then.status(200);
+        }),
+        // Next set up SEARCH_PATH, store in vector at SEARCH_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SEARCH_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests, each user running a fixed number of
+// sessions.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--iterations-per-user",
+            &SESSIONS.to_string(),
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+// A session starts on the home page or the search page. The home page always leads to
+// the search page, which ends the session.
+fn get_taskset(search_transition: &str) -> SwanlingTaskSet {
+    taskset!("Navigate")
+        .register_task(
+            task!(get_home)
+                .set_name("home")
+                .set_weight(3)
+                .unwrap()
+                .set_transition(search_transition, 1)
+                .unwrap(),
+        )
+        .register_task(
+            task!(get_search)
+                .set_name("search")
+                .set_exit_transition(1)
+                .unwrap(),
+        )
+}
+
+// Run a load test walking the Markov chain, returning how many times each page loaded.
+fn run_load_test(seed: Option<u64>) -> (usize, usize) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_attack =
+        SwanlingAttack::initialize_with_config(common_build_configuration(&server))
+            .unwrap()
+            .register_taskset(get_taskset("search"))
+            .set_task_scheduler(move |task_set| {
+                let scheduler = SwanlingMarkovScheduler::new(task_set);
+                match seed {
+                    Some(seed) => Box::new(scheduler.set_seed(seed)),
+                    None => Box::new(scheduler),
+                }
+            });
+
+    // Run the Swanling Attack.
+    common::run_load_test(swanling_attack, None);
+
+    (
+        mock_endpoints[HOME_KEY].hits(),
+        mock_endpoints[SEARCH_KEY].hits(),
+    )
+}
+
+#[test]
+// Confirm users follow the transitions between tasks, one session per iteration.
+fn test_markov() {
+    let (home, search) = run_load_test(None);
+
+    // Every session ends on the search page, some of them after the home page.
+    assert_eq!(search, USERS * SESSIONS);
+    assert!(home <= search);
+}
+
+#[test]
+// Confirm users walk the chain the same way every time with a seed.
+fn test_markov_seed() {
+    let (home, search) = run_load_test(Some(SEED));
+    assert_eq!(search, USERS * SESSIONS);
+
+    assert_eq!(run_load_test(Some(SEED)), (home, search));
+}
+
+#[test]
+// Confirm the load test refuses to start with a transition to a task that doesn't exist.
+fn test_markov_invalid_transition() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let swanling_attack =
+        SwanlingAttack::initialize_with_config(common_build_configuration(&server))
+            .unwrap()
+            .register_taskset(get_taskset("missing"))
+            .set_task_scheduler(|task_set| Box::new(SwanlingMarkovScheduler::new(task_set)));
+    assert!(swanling_attack.execute().is_err());
+}
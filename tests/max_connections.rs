@@ -0,0 +1,100 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// How long the server takes to respond, in milliseconds.
+const RESPONSE_DELAY: u64 = 250;
+
+// Load test configuration.
+const USERS: usize = 4;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200)
+                .delay(Duration::from_millis(RESPONSE_DELAY));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration.extend(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Run a load test against a new mock server, returning how many requests the server
+// received and the final metrics.
+fn run_load_test(custom: Vec<&str>) -> (usize, SwanlingMetrics) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, custom),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    (mock_endpoints[INDEX_KEY].hits(), swanling_metrics)
+}
+
+#[test]
+// Confirm --max-connections limits how many requests are made at once.
+fn test_max_connections() {
+    // Without a cap, all users make requests at the same time.
+    let (uncapped_hits, _) = run_load_test(vec![]);
+
+    // With a single connection, users take turns.
+    let (capped_hits, swanling_metrics) =
+        run_load_test(vec!["--max-connections", "1", "--pool-size", "0"]);
+
+    // Requests were made, but far fewer than without the cap.
+    assert!(capped_hits > 0);
+    assert!(capped_hits * 2 < uncapped_hits);
+
+    // Waiting for a connection is not included in response times.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.fail_count, 0);
+    assert!(request_metrics.raw_data.maximum_time < RESPONSE_DELAY as usize * 2);
+}
@@ -0,0 +1,163 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::metrics::SwanlingRequestMetricAggregate;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const REDIRECT_PATH: &str = "/redirect";
+const REDIRECT2_PATH: &str = "/redirect2";
+const REDIRECT3_PATH: &str = "/redirect3";
+const ABOUT_PATH: &str = "/about.php";
+
+// Indexes to the above paths.
+const REDIRECT_KEY: usize = 0;
+const REDIRECT2_KEY: usize = 1;
+const REDIRECT3_KEY: usize = 2;
+const ABOUT_KEY: usize = 3;
+
+// How many redirects are followed to load ABOUT_PATH from REDIRECT_PATH.
+const REDIRECTS: usize = 3;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_redirect(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(REDIRECT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up REDIRECT_PATH, store in vector at REDIRECT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(REDIRECT_PATH);
+            then.status(301).header("Location", REDIRECT2_PATH);
+        }),
+        // Next set up REDIRECT2_PATH, store in vector at REDIRECT2_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(REDIRECT2_PATH);
+            then.status(302).header("Location", REDIRECT3_PATH);
+        }),
+        // Next set up REDIRECT3_PATH, store in vector at REDIRECT3_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(REDIRECT3_PATH);
+            then.status(303).header("Location", ABOUT_PATH);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    max_redirects: Option<usize>,
+) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    let max_redirects = max_redirects.map(|max_redirects| max_redirects.to_string());
+    if let Some(max_redirects) = max_redirects.as_ref() {
+        configuration.push("--max-redirects");
+        configuration.push(max_redirects);
+    }
+    common::build_configuration(server, configuration)
+}
+
+// Helper to run the load test, returning the metrics of the only request made.
+fn run_load_test(
+    server: &MockServer,
+    max_redirects: Option<usize>,
+) -> SwanlingRequestMetricAggregate {
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(server, max_redirects),
+            &taskset!("LoadTest").register_task(task!(get_redirect)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Redirects are recorded under the name of the original request.
+    assert_eq!(swanling_metrics.requests.len(), 1);
+    swanling_metrics
+        .requests
+        .get(&format!("GET {}", REDIRECT_PATH))
+        .unwrap()
+        .clone()
+}
+
+#[test]
+// Confirm redirects are followed by default, and each hop is counted.
+fn test_max_redirects_default() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let request = run_load_test(&server, None);
+
+    assert!(mock_endpoints[REDIRECT_KEY].hits() > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+    assert_eq!(request.fail_count, 0);
+    assert_eq!(request.redirect_count, request.success_count * REDIRECTS);
+}
+
+#[test]
+// Confirm requests fail if they are redirected more than --max-redirects times.
+fn test_max_redirects_exceeded() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let request = run_load_test(&server, Some(1));
+
+    // Only the first redirect was followed.
+    assert!(mock_endpoints[REDIRECT2_KEY].hits() > 0);
+    assert_eq!(mock_endpoints[REDIRECT3_KEY].hits(), 0);
+    assert_eq!(mock_endpoints[ABOUT_KEY].hits(), 0);
+    assert_eq!(request.success_count, 0);
+    assert!(request.fail_count > 0);
+}
+
+#[test]
+// Confirm redirects are not followed with --max-redirects 0, and the redirect is not
+// a failure.
+fn test_max_redirects_disabled() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let request = run_load_test(&server, Some(0));
+
+    assert!(mock_endpoints[REDIRECT_KEY].hits() > 0);
+    assert_eq!(mock_endpoints[REDIRECT2_KEY].hits(), 0);
+    assert_eq!(request.fail_count, 0);
+    assert!(request.success_count > 0);
+    assert_eq!(request.redirect_count, 0);
+}
@@ -0,0 +1,120 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod common;
+
+use swanling::metrics::METRICS_OVERFLOW;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const ITEM_PATH: &str = "/item/";
+
+// Indexes to the above paths.
+const ITEM_KEY: usize = 0;
+
+// How many request names are tracked by name.
+const MAX_REQUEST_NAMES: usize = 5;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// How many requests have been made by all users.
+static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+// Test task, loading a different item each time without naming the request.
+pub async fn get_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    let item = REQUESTS.fetch_add(1, Ordering::SeqCst);
+    let _swanling = user.get(&format!("{}{}", ITEM_PATH, item)).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up ITEM_PATH, store in vector at ITEM_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path_contains(ITEM_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+#[test]
+// Confirm request names beyond the limit are combined into a single entry.
+fn test_max_request_names() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let max_request_names = MAX_REQUEST_NAMES.to_string();
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, &["--max-request-names", &max_request_names]),
+            &taskset!("LoadTest").register_task(task!(get_item)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let hits = mock_endpoints[ITEM_KEY].hits();
+    assert!(hits > MAX_REQUEST_NAMES);
+
+    // Only the first names are tracked on their own, and every request is still counted.
+    assert_eq!(swanling_metrics.requests.len(), MAX_REQUEST_NAMES + 1);
+    let overflow = swanling_metrics
+        .requests
+        .get(&format!("GET {}", METRICS_OVERFLOW))
+        .unwrap();
+    assert_eq!(overflow.path, METRICS_OVERFLOW);
+    assert_eq!(
+        swanling_metrics
+            .requests
+            .values()
+            .map(|request| request.success_count)
+            .sum::<usize>(),
+        hits
+    );
+}
+
+#[test]
+// Confirm the load test refuses to start with an unusable limit.
+fn test_max_request_names_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for custom in &[
+        vec!["--max-request-names", "0"],
+        vec!["--max-request-names", "10", "--no-metrics"],
+    ] {
+        let swanling_attack = common::build_load_test(
+            common_build_configuration(&server, custom),
+            &taskset!("LoadTest").register_task(task!(get_item)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const MAX_REQUESTS: usize = 20;
+const USERS: usize = 2;
+const RUN_TIME: usize = 30;
+const EXPECT_WORKERS: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    worker: Option<bool>,
+    manager: Option<usize>,
+) -> SwanlingConfiguration {
+    if let Some(expect_workers) = manager {
+        common::build_configuration(
+            &server,
+            vec![
+                "--manager",
+                "--expect-workers",
+                &expect_workers.to_string(),
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--max-requests",
+                &MAX_REQUESTS.to_string(),
+            ],
+        )
+    } else if worker.is_some() {
+        common::build_configuration(&server, vec!["--worker"])
+    } else {
+        common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--max-requests",
+                &MAX_REQUESTS.to_string(),
+                // Count every request in the final metrics.
+                "--no-reset-metrics",
+            ],
+        )
+    }
+}
+
+// Helper to confirm all variations generate appropriate results.
+fn validate_test(mock_endpoints: &[MockRef], started: std::time::Instant) {
+    // Confirm that at least the maximum number of requests were made.
+    assert!(mock_endpoints[INDEX_KEY].hits() >= MAX_REQUESTS);
+
+    // Confirm that the load test stopped before the run time expired.
+    assert!(started.elapsed().as_secs() < RUN_TIME as u64);
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(get_index))
+}
+
+#[test]
+// Confirm the load test stops after making the configured maximum number of requests.
+fn test_max_requests() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common_build_configuration(&server, None, None);
+
+    // Run the Swanling Attack.
+    let started = std::time::Instant::now();
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm that the load test stopped after making the maximum number of requests.
+    validate_test(&mock_endpoints, started);
+
+    // Confirm that all requests were recorded.
+    let recorded: usize = swanling_metrics
+        .requests
+        .values()
+        .map(|request| request.success_count + request.fail_count)
+        .sum();
+    assert!(recorded >= MAX_REQUESTS);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+// Confirm the load test stops after making the configured maximum number of requests,
+// in Regatta mode.
+fn test_max_requests_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each worker has the same identical configuration.
+    let worker_configuration = common_build_configuration(&server, Some(true), None);
+
+    // Build the load test for the Workers.
+    let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
+
+    // Workers launched in own threads, store thread handles.
+    let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
+
+    // Build Manager configuration.
+    let manager_configuration = common_build_configuration(&server, None, Some(EXPECT_WORKERS));
+
+    // Build the load test for the Manager.
+    let manager_swanling_attack =
+        common::build_load_test(manager_configuration, &get_tasks(), None, None);
+
+    // Run the Swanling Attack.
+    let started = std::time::Instant::now();
+    common::run_load_test(manager_swanling_attack, Some(worker_handles));
+
+    // Confirm that the load test stopped after making the maximum number of requests.
+    validate_test(&mock_endpoints, started);
+}
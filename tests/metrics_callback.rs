@@ -0,0 +1,151 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+use swanling::metrics::SwanlingControlAction;
+use swanling::prelude::*;
+use swanling::swanling::SwanlingReconfiguration;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 10;
+const INTERVAL: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    run_time: usize,
+    throttle_requests: Option<usize>,
+) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = run_time.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    let throttle_requests = throttle_requests.map(|t| t.to_string());
+    if let Some(throttle_requests) = throttle_requests.as_ref() {
+        configuration.push("--throttle-requests");
+        configuration.push(throttle_requests);
+    }
+    common::build_configuration(server, configuration)
+}
+
+#[test]
+// Confirm the callback can abort the load test before the run time expires.
+fn test_metrics_callback_abort() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Abort once the metrics include requests.
+    let calls = Arc::new(AtomicUsize::new(0));
+    let callback_calls = calls.clone();
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, RUN_TIME, None),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        )
+        .set_metrics_callback(INTERVAL, move |metrics| {
+            callback_calls.fetch_add(1, Ordering::SeqCst);
+            if metrics.requests.is_empty() {
+                SwanlingControlAction::Continue
+            } else {
+                SwanlingControlAction::Abort
+            }
+        })
+        .unwrap(),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(calls.load(Ordering::SeqCst) > 0);
+    assert!(swanling_metrics.duration < RUN_TIME);
+}
+
+#[test]
+// Confirm the callback can adjust the running load test.
+fn test_metrics_callback_adjust() {
+    const RUN_TIME: usize = 4;
+    const THROTTLE_REQUESTS: usize = 1;
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Raise the throttle the first time the callback is invoked.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, RUN_TIME, Some(THROTTLE_REQUESTS)),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        )
+        .set_metrics_callback(INTERVAL, |_| {
+            SwanlingControlAction::Adjust(SwanlingReconfiguration {
+                throttle_requests: Some(100),
+                ..Default::default()
+            })
+        })
+        .unwrap(),
+        None,
+    );
+
+    // Far more requests were made than the initial throttle allows.
+    let hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(hits > THROTTLE_REQUESTS * RUN_TIME * 5);
+    assert_eq!(swanling_metrics.duration, RUN_TIME);
+}
+
+#[test]
+// Confirm the callback interval must be at least one second.
+fn test_metrics_callback_interval() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let result = common::build_load_test(
+        common_build_configuration(&server, RUN_TIME, None),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    )
+    .set_metrics_callback(0, |_| SwanlingControlAction::Continue);
+
+    assert!(result.is_err());
+}
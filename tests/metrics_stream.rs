@@ -0,0 +1,106 @@
+use futures::executor::block_on_stream;
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 10;
+const INTERVAL: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+        ],
+    )
+}
+
+#[test]
+// Confirm snapshots are streamed while the load test runs, and it can be stopped through
+// the stream.
+fn test_metrics_stream() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let mut swanling_attack = common::build_load_test(
+        common_build_configuration(&server),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    let snapshots = swanling_attack.metrics_stream(INTERVAL).unwrap();
+    let shutdown = snapshots.shutdown_handle();
+
+    // Run the Swanling Attack on its own thread.
+    let load_test = std::thread::spawn(move || common::run_load_test(swanling_attack, None));
+
+    // Stop the load test once a snapshot includes requests. The stream ends once it has
+    // stopped.
+    let mut received = 0;
+    for snapshot in block_on_stream(snapshots) {
+        received += 1;
+        if !snapshot.requests.is_empty() {
+            shutdown.shutdown();
+        }
+    }
+    let swanling_metrics = load_test.join().unwrap();
+
+    assert!(received > 0);
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(swanling_metrics.duration < RUN_TIME);
+}
+
+#[test]
+// Confirm the stream interval must be at least one second.
+fn test_metrics_stream_interval() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mut swanling_attack = common::build_load_test(
+        common_build_configuration(&server),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+
+    assert!(swanling_attack.metrics_stream(0).is_err());
+}
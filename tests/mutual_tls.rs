@@ -0,0 +1,162 @@
+use httpmock::MockServer;
+use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode, SslVersion};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+use swanling::metrics::SwanlingErrorKind;
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Certificates signed by a test certificate authority, valid for 100 years. The server
+// certificate is for localhost and 127.0.0.1.
+const CA_CERT: &str = "tests/fixtures/tls/ca.pem";
+const SERVER_CERT: &str = "tests/fixtures/tls/server.pem";
+const SERVER_KEY: &str = "tests/fixtures/tls/server.key";
+const CLIENT_CERT: &str = "tests/fixtures/tls/client.pem";
+const CLIENT_KEY: &str = "tests/fixtures/tls/client.key";
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Start a server that only completes the TLS handshake with clients presenting a
+// certificate signed by the test certificate authority, returning its port and how many
+// requests it has answered.
+fn start_mutual_tls_server() -> (u16, Arc<AtomicUsize>) {
+    let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+    acceptor
+        .set_private_key_file(SERVER_KEY, SslFiletype::PEM)
+        .unwrap();
+    acceptor.set_certificate_chain_file(SERVER_CERT).unwrap();
+    acceptor.set_ca_file(CA_CERT).unwrap();
+    acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    // With TLS 1.3 the client finishes its side of the handshake before the server checks
+    // the client certificate, so a rejected certificate would only fail the first read.
+    acceptor
+        .set_max_proto_version(Some(SslVersion::TLS1_2))
+        .unwrap();
+    let acceptor = Arc::new(acceptor.build());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let hits = Arc::new(AtomicUsize::new(0));
+    let server_hits = hits.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let acceptor = acceptor.clone();
+            let hits = server_hits.clone();
+            std::thread::spawn(move || {
+                // Handshakes that fail are simply dropped.
+                if let Ok(mut stream) = acceptor.accept(stream) {
+                    // Answer each request on the connection until the client closes it.
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    while let Ok(read) = stream.read(&mut buffer) {
+                        if read == 0 {
+                            break;
+                        }
+                        request.extend_from_slice(&buffer[..read]);
+                        while let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                            request.drain(..end + 4);
+                            hits.fetch_add(1, Ordering::SeqCst);
+                            if stream
+                                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                                .is_err()
+                            {
+                                return;
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    (port, hits)
+}
+
+// Run a load test against the mutual TLS server, returning the metrics and how many
+// requests the server answered.
+fn run_mutual_tls_test(custom: Vec<&str>) -> (SwanlingMetrics, usize) {
+    // The mock server isn't loaded, the configuration points at the mutual TLS server.
+    let server = MockServer::start();
+    let (port, hits) = start_mutual_tls_server();
+    let host = format!("https://localhost:{}", port);
+    let mut configuration = vec!["--host", &host, "--no-reset-metrics"];
+    configuration.extend(custom);
+
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common::build_configuration(&server, configuration),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let hits = hits.load(Ordering::SeqCst);
+    (swanling_metrics, hits)
+}
+
+#[test]
+// Confirm requests succeed when the client presents a certificate the server trusts.
+fn test_mutual_tls() {
+    let (swanling_metrics, hits) = run_mutual_tls_test(vec![
+        "--ca-cert",
+        CA_CERT,
+        "--client-cert",
+        CLIENT_CERT,
+        "--client-key",
+        CLIENT_KEY,
+    ]);
+
+    assert!(hits > 0);
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(index_metrics.success_count, hits);
+    assert_eq!(index_metrics.fail_count, 0);
+    assert!(swanling_metrics.errors.is_empty());
+}
+
+#[test]
+// Confirm requests without a client certificate fail the TLS handshake, and are recorded
+// as that kind of error.
+fn test_mutual_tls_without_client_cert() {
+    let (swanling_metrics, hits) = run_mutual_tls_test(vec!["--ca-cert", CA_CERT]);
+
+    assert_eq!(hits, 0);
+    assert!(!swanling_metrics.errors.is_empty());
+    for error in swanling_metrics.errors.values() {
+        assert_eq!(error.kind, SwanlingErrorKind::TlsHandshake);
+    }
+}
+
+#[test]
+// Confirm requests to a server whose certificate isn't trusted fail the TLS handshake,
+// and are recorded as that kind of error.
+fn test_mutual_tls_untrusted_server() {
+    // Without the test certificate authority, the server certificate isn't trusted.
+    let (swanling_metrics, hits) = run_mutual_tls_test(vec![
+        "--client-cert",
+        CLIENT_CERT,
+        "--client-key",
+        CLIENT_KEY,
+    ]);
+
+    assert_eq!(hits, 0);
+    assert!(!swanling_metrics.errors.is_empty());
+    for error in swanling_metrics.errors.values() {
+        assert_eq!(error.kind, SwanlingErrorKind::TlsHandshake);
+    }
+}
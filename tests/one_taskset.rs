@@ -18,6 +18,9 @@ const ABOUT_KEY: usize = 1;
 // Load test configuration.
 const EXPECT_WORKERS: usize = 2;
 
+// The Unix domain socket the Manager and Workers communicate over with the ipc transport.
+const IPC_PATH: &str = "one-taskset-gaggle.ipc";
+
 // There are multiple test variations in this file.
 #[derive(Clone)]
 enum TestType {
@@ -190,16 +193,31 @@ fn run_standalone_test(test_type: TestType) {
     );
 }
 
-// Helper to run all standalone tests.
-fn run_gaggle_test(test_type: TestType) {
+// Helper to run all gaggle tests, with the Manager and Workers communicating over the
+// given transport and number of Manager sockets.
+fn run_gaggle_test(test_type: TestType, transport: &str, manager_sockets: usize) {
     // Start the mock server.
     let server = MockServer::start();
 
     // Setup the endpoints needed for this test on the mock server.
     let mock_endpoints = setup_mock_server_endpoints(&server);
 
+    // The ipc transport also needs the Unix domain socket to communicate over.
+    let manager_sockets = manager_sockets.to_string();
+    let mut transport_options = vec![
+        "--gaggle-transport",
+        transport,
+        "--manager-sockets",
+        &manager_sockets,
+    ];
+    if transport == "ipc" {
+        transport_options.extend(vec!["--gaggle-ipc", IPC_PATH]);
+    }
+
     // Each worker has the same identical configuration.
-    let worker_configuration = common::build_configuration(&server, vec!["--worker"]);
+    let mut worker_options = vec!["--worker"];
+    worker_options.extend(transport_options.iter());
+    let worker_configuration = common::build_configuration(&server, worker_options);
 
     // Build the load test for the Workers.
     let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
@@ -208,20 +226,15 @@ fn run_gaggle_test(test_type: TestType) {
     let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
 
     // Build common configuration elements, adding Manager Regatta flags.
+    let expect_workers = EXPECT_WORKERS.to_string();
+    let mut manager_options = vec!["--manager", "--expect-workers", &expect_workers];
+    manager_options.extend(transport_options.iter());
     let manager_configuration = match test_type {
-        TestType::NoResetMetrics => common_build_configuration(
-            &server,
-            &mut vec![
-                "--manager",
-                "--expect-workers",
-                &EXPECT_WORKERS.to_string(),
-                "--no-reset-metrics",
-            ],
-        ),
-        TestType::ResetMetrics => common_build_configuration(
-            &server,
-            &mut vec!["--manager", "--expect-workers", &EXPECT_WORKERS.to_string()],
-        ),
+        TestType::NoResetMetrics => {
+            manager_options.push("--no-reset-metrics");
+            common_build_configuration(&server, &mut manager_options)
+        }
+        TestType::ResetMetrics => common_build_configuration(&server, &mut manager_options),
     };
 
     // Build the load test for the Manager.
@@ -251,7 +264,34 @@ fn test_one_taskset() {
 #[serial]
 // Test a single task set with multiple weighted tasks, in Regatta mode.
 fn test_one_taskset_gaggle() {
-    run_gaggle_test(TestType::NoResetMetrics);
+    run_gaggle_test(TestType::NoResetMetrics, "tcp", 1);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+#[serial]
+// Test a single task set with multiple weighted tasks, in Regatta mode with the Manager
+// and Workers communicating in the same process.
+fn test_one_taskset_gaggle_inproc() {
+    run_gaggle_test(TestType::NoResetMetrics, "inproc", 1);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+#[serial]
+// Test a single task set with multiple weighted tasks, in Regatta mode with the Manager
+// and Workers communicating over a Unix domain socket.
+fn test_one_taskset_gaggle_ipc() {
+    run_gaggle_test(TestType::NoResetMetrics, "ipc", 1);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+#[serial]
+// Test a single task set with multiple weighted tasks, in Regatta mode with the Manager
+// listening on two sockets and the Workers spread across both of them.
+fn test_one_taskset_gaggle_manager_sockets() {
+    run_gaggle_test(TestType::NoResetMetrics, "tcp", 2);
 }
 
 #[test]
@@ -268,6 +308,6 @@ fn test_one_taskset_reset_metrics() {
 // Test a single task set with multiple weighted tasks, enable --no-reset-metrics
 // in Regatta mode.
 fn test_one_taskset_reset_metrics_gaggle() {
-    run_gaggle_test(TestType::ResetMetrics);
+    run_gaggle_test(TestType::ResetMetrics, "tcp", 1);
 }
 */
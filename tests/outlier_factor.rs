@@ -0,0 +1,140 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+mod common;
+
+use swanling::metrics::OUTLIER_MIN_REQUESTS;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const SLOW_PATH: &str = "/slow";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const SLOW_KEY: usize = 1;
+
+// How long the slow path takes to respond, in milliseconds, and how often it's requested
+// once enough requests have been made to find outliers.
+const SLOW_DELAY: u64 = 500;
+const SLOW_EVERY: usize = 50;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 3;
+
+// How many requests have been made by all users.
+static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+// Test task, occasionally loading the slow path under the same request name.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let request = REQUESTS.fetch_add(1, Ordering::SeqCst);
+    let path = if request > OUTLIER_MIN_REQUESTS * 2 && request.is_multiple_of(SLOW_EVERY) {
+        SLOW_PATH
+    } else {
+        INDEX_PATH
+    };
+    let _swanling = user.get_named(path, INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up SLOW_PATH, store in vector at SLOW_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_millis(SLOW_DELAY));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+#[test]
+// Confirm requests far slower than others with the same name are reported as outliers.
+fn test_outlier_factor() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, &["--outlier-factor", "3"]),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let slow_hits = mock_endpoints[SLOW_KEY].hits();
+    assert!(mock_endpoints[INDEX_KEY].hits() > OUTLIER_MIN_REQUESTS);
+    assert!(slow_hits > 0);
+
+    // Every slow request is an outlier, and the slowest are kept with their full URL.
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.outlier_count >= slow_hits);
+    let outlier_request = &index_metrics.outlier_requests[0];
+    assert!(outlier_request.url.ends_with(SLOW_PATH));
+    assert!(outlier_request.response_time >= SLOW_DELAY);
+    assert!(outlier_request.threshold < SLOW_DELAY);
+
+    // The outliers are included in the final report.
+    let report = swanling_metrics.to_string();
+    assert!(report.contains("OUTLIERS (over 3 standard deviations slower than average)"));
+    assert!(report.contains(&outlier_request.url));
+}
+
+#[test]
+// Confirm the load test refuses to start without a usable factor.
+fn test_outlier_factor_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // The factor must be greater than 0.
+    let swanling_attack = common::build_load_test(
+        common_build_configuration(&server, &["--outlier-factor", "0"]),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+
+    // Outliers can't be found without metrics.
+    let swanling_attack = common::build_load_test(
+        common_build_configuration(&server, &["--outlier-factor", "3", "--no-metrics"]),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
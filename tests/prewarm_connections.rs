@@ -0,0 +1,115 @@
+use httpmock::{
+    Method::{GET, HEAD},
+    MockRef, MockServer,
+};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const PREWARM_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const PREWARM_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up PREWARM_PATH, store in vector at PREWARM_KEY.
+        server.mock(|when, then| {
+            when.method(HEAD).path(PREWARM_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Run the load test, returning the metrics.
+fn run_load_test(server: &MockServer, custom: &[&str]) -> SwanlingMetrics {
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(server, custom),
+            &taskset!("LoadTest").register_task(task!(get_about)),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Confirm each user opens its connection before running tasks, without recording it.
+fn test_prewarm_connections() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = run_load_test(&server, &["--prewarm-connections"]);
+
+    // Each user prewarmed its connection once.
+    assert_eq!(mock_endpoints[PREWARM_KEY].hits(), USERS);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+
+    // Only the requests made by tasks are recorded.
+    assert_eq!(swanling_metrics.requests.len(), 1);
+    let about_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", ABOUT_PATH))
+        .unwrap();
+    assert_eq!(
+        about_metrics.success_count,
+        mock_endpoints[ABOUT_KEY].hits()
+    );
+}
+
+#[test]
+// Confirm connections aren't prewarmed by default.
+fn test_no_prewarm_connections() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _swanling_metrics = run_load_test(&server, &[]);
+
+    assert_eq!(mock_endpoints[PREWARM_KEY].hits(), 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+}
@@ -0,0 +1,152 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Load test configuration. The ramp measures the error rate every 5 seconds, adding users
+// while it stays within the threshold.
+const USERS: usize = 2;
+const RUN_TIME: usize = 30;
+const ERROR_THRESHOLD: usize = 10;
+
+// Test task, the target keeps up with the configured users.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task, the target breaks once the ramp launches more users.
+pub async fn get_overloaded(user: &SwanlingUser) -> SwanlingTaskResult {
+    if user.weighted_users_index < USERS {
+        let _swanling = user.get(INDEX_PATH).await?;
+    } else {
+        let _swanling = user.get(ERROR_PATH).await?;
+    }
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(503);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, run_time: usize) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = run_time.to_string();
+    let error_threshold = ERROR_THRESHOLD.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--ramp-to-failure",
+            "--error-threshold",
+            &error_threshold,
+        ],
+    )
+}
+
+#[test]
+// Confirm the load test stops once the error rate exceeds the threshold, and reports
+// the breaking point.
+fn test_ramp_to_failure() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, RUN_TIME),
+            &taskset!("LoadTest").register_task(task!(get_overloaded)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    // The load test stopped before the run time expired.
+    assert!(swanling_metrics.duration < RUN_TIME);
+
+    // The configured users stayed within the threshold, the ramped users did not.
+    let ramp = swanling_metrics.ramp_to_failure.unwrap();
+    assert_eq!(ramp.error_threshold, ERROR_THRESHOLD);
+    assert_eq!(ramp.steps[0].users, USERS);
+    assert!(!ramp.steps[0].over_threshold);
+    assert!(ramp.steps.iter().skip(1).all(|step| step.over_threshold));
+
+    // The breaking point is the first interval that exceeded the threshold.
+    let breaking_point = ramp.breaking_point.unwrap();
+    assert_eq!(breaking_point.users, USERS * 2);
+    assert!(breaking_point.error_rate > ERROR_THRESHOLD as f32);
+    assert_eq!(swanling_metrics.users, USERS * 2);
+}
+
+#[test]
+// Confirm users are added each interval while the error rate stays within the threshold.
+fn test_ramp_to_failure_within_threshold() {
+    const RUN_TIME: usize = 12;
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, RUN_TIME),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert_eq!(mock_endpoints[ERROR_KEY].hits(), 0);
+
+    // The load test ran until the run time expired.
+    assert_eq!(swanling_metrics.duration, RUN_TIME);
+
+    // More users were launched after each interval.
+    let ramp = swanling_metrics.ramp_to_failure.unwrap();
+    assert_eq!(ramp.steps.len(), 2);
+    assert_eq!(ramp.steps[0].users, USERS);
+    assert_eq!(ramp.steps[1].users, USERS * 2);
+    assert!(ramp.steps.iter().all(|step| !step.over_threshold));
+    assert!(ramp.breaking_point.is_none());
+    assert_eq!(swanling_metrics.users, USERS * 3);
+}
@@ -0,0 +1,117 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    report_file: &str,
+    report_json: &str,
+) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--report-file",
+            report_file,
+            "--report-json",
+            report_json,
+        ],
+    )
+}
+
+#[test]
+// Confirm the html and JSON reports are both written from the same final metrics.
+fn test_report_json() {
+    const REPORT_FILE: &str = "report-json.html";
+    const REPORT_JSON: &str = "report-json.json";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, REPORT_FILE, REPORT_JSON),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    let html = std::fs::read_to_string(REPORT_FILE).expect("failed to read html report");
+    assert!(html.contains("<title>Swanling Attack Report</title>"));
+
+    // The JSON report is the serialized final metrics.
+    let json = std::fs::read_to_string(REPORT_JSON).expect("failed to read JSON report");
+    let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let success_count = report["requests"]["GET /"]["success_count"]
+        .as_u64()
+        .unwrap();
+    assert_eq!(
+        success_count as usize,
+        swanling_metrics.requests["GET /"].success_count
+    );
+
+    // Cleanup from test.
+    common::cleanup_files(vec![REPORT_FILE, REPORT_JSON]);
+}
+
+#[test]
+// Confirm there's no JSON report without metrics.
+fn test_report_json_no_metrics() {
+    const REPORT_JSON: &str = "report-json-no-metrics.json";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration =
+        common::build_configuration(&server, vec!["--report-json", REPORT_JSON, "--no-metrics"]);
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(taskset!("LoadTest").register_task(task!(get_index)));
+    assert!(swanling_attack.execute().is_err());
+}
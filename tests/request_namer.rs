@@ -0,0 +1,126 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const USER_ONE_PATH: &str = "/users/1";
+const USER_TWO_PATH: &str = "/users/2";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const USER_ONE_KEY: usize = 0;
+const USER_TWO_KEY: usize = 1;
+const ABOUT_KEY: usize = 2;
+
+// The name requests for users are recorded under, and the name given to requests for
+// the about page.
+const USER_NAME: &str = "/users/:id";
+const ABOUT_NAME: &str = "about";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task, requesting both users and the about page.
+pub async fn get_pages(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(USER_ONE_PATH).await?;
+    let _swanling = user.get(USER_TWO_PATH).await?;
+    let _swanling = user.get_named(ABOUT_PATH, ABOUT_NAME).await?;
+    Ok(())
+}
+
+// Replace numeric path segments with :id.
+fn name_request(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+                ":id"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up USER_ONE_PATH, store in vector at USER_ONE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(USER_ONE_PATH);
+            then.status(200);
+        }),
+        // Next set up USER_TWO_PATH, store in vector at USER_TWO_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(USER_TWO_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm requests are named by the request namer, unless otherwise named.
+fn test_request_namer() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_pages)),
+            None,
+            None,
+        )
+        .set_request_namer(name_request),
+        None,
+    );
+
+    let user_hits = mock_endpoints[USER_ONE_KEY].hits() + mock_endpoints[USER_TWO_KEY].hits();
+    let about_hits = mock_endpoints[ABOUT_KEY].hits();
+    assert!(user_hits > 0);
+    assert!(about_hits > 0);
+
+    // Requests for both users are recorded under one name.
+    assert_eq!(swanling_metrics.requests.len(), 2);
+    let user_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", USER_NAME))
+        .unwrap();
+    assert_eq!(user_metrics.success_count, user_hits);
+
+    // Named requests keep their name.
+    let about_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", ABOUT_NAME))
+        .unwrap();
+    assert_eq!(about_metrics.success_count, about_hits);
+}
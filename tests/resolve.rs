@@ -0,0 +1,211 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// A host name that can't be resolved by DNS.
+const HOST_NAME: &str = "swanling.invalid";
+
+// Load test configuration.
+const EXPECT_WORKERS: usize = 2;
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY. Only match requests that
+        // preserved the original host name in the Host header.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("host", &format!("{}:{}", HOST_NAME, server.port()));
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    worker: Option<bool>,
+    manager: Option<usize>,
+) -> SwanlingConfiguration {
+    let host = format!("http://{}:{}", HOST_NAME, server.port());
+    let resolve = format!("{}:{}:127.0.0.1", HOST_NAME, server.port());
+    if let Some(expect_workers) = manager {
+        common::build_configuration(
+            &server,
+            vec![
+                "--manager",
+                "--expect-workers",
+                &expect_workers.to_string(),
+                "--host",
+                &host,
+                "--resolve",
+                &resolve,
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+            ],
+        )
+    } else if worker.is_some() {
+        common::build_configuration(&server, vec!["--worker"])
+    } else {
+        common::build_configuration(
+            &server,
+            vec![
+                "--host",
+                &host,
+                "--resolve",
+                &resolve,
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+            ],
+        )
+    }
+}
+
+// Helper to confirm all variations generate appropriate results.
+fn validate_test(swanling_metrics: &SwanlingMetrics, mock_endpoints: &[MockRef]) {
+    // Confirm that requests for the unresolvable host name reached the mock server.
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // Confirm that no requests failed.
+    assert!(swanling_metrics
+        .requests
+        .values()
+        .all(|request| request.fail_count == 0));
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(get_index))
+}
+
+#[test]
+// Confirm requests for a host name are sent to the address configured with --resolve.
+fn test_resolve() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common_build_configuration(&server, None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm that the host name was resolved to the mock server.
+    validate_test(&swanling_metrics, &mock_endpoints);
+}
+
+#[test]
+// Confirm an invalid --resolve mapping is rejected.
+fn test_resolve_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration with a mapping that is missing the port.
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--resolve", &format!("{}:127.0.0.1", HOST_NAME)],
+    );
+
+    // Confirm the load test refuses to start.
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(get_tasks());
+    assert!(swanling_attack.execute().is_err());
+}
+
+#[test]
+// Confirm mapping the same host name to different ports or addresses is rejected, as
+// connections always use the port from the request URL.
+fn test_resolve_conflicting() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration mapping the host name on two ports.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--resolve",
+            &format!("{}:80:127.0.0.1", HOST_NAME),
+            "--resolve",
+            &format!("{}:443:127.0.0.2", HOST_NAME),
+        ],
+    );
+
+    // Confirm the load test refuses to start.
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(get_tasks());
+    match swanling_attack.execute() {
+        Err(SwanlingError::InvalidOption { option, value, .. }) => {
+            assert_eq!(option, "--resolve");
+            assert_eq!(value, format!("{}:443:127.0.0.2", HOST_NAME));
+        }
+        _ => panic!("conflicting --resolve mappings were accepted"),
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+// Confirm host names are resolved to the address configured on the Manager, in
+// Regatta mode.
+fn test_resolve_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each worker has the same identical configuration.
+    let worker_configuration = common_build_configuration(&server, Some(true), None);
+
+    // Build the load test for the Workers.
+    let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
+
+    // Workers launched in own threads, store thread handles.
+    let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
+
+    // Build Manager configuration.
+    let manager_configuration = common_build_configuration(&server, None, Some(EXPECT_WORKERS));
+
+    // Build the load test for the Manager.
+    let manager_swanling_attack =
+        common::build_load_test(manager_configuration, &get_tasks(), None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(manager_swanling_attack, Some(worker_handles));
+
+    // Confirm that the host name was resolved to the mock server.
+    validate_test(&swanling_metrics, &mock_endpoints);
+}
@@ -218,6 +218,32 @@ fn validate_test(test_type: &TestType, scheduler: &SwanlingScheduler, mock_endpo
     mock_endpoints[STOP_ONE_KEY].assert_hits(1);
 }
 
+// Helper to confirm the users allocated to each task set are reported.
+fn validate_task_set_users(
+    test_type: &TestType,
+    swanling_metrics: &SwanlingMetrics,
+    mock_endpoints: &[MockRef],
+) {
+    let task_set_users = &swanling_metrics.task_set_users;
+    match test_type {
+        TestType::TaskSets => {
+            // Each user of a task set loads its endpoint one time.
+            assert_eq!(task_set_users.len(), 2);
+            assert_eq!(task_set_users[0].name, "TaskSetOne");
+            assert_eq!(task_set_users[0].weight, USERS);
+            assert_eq!(task_set_users[0].users, mock_endpoints[ONE_KEY].hits());
+            assert_eq!(task_set_users[1].name, "TaskSetTwo");
+            assert_eq!(task_set_users[1].weight, USERS + 1);
+            assert_eq!(task_set_users[1].users, mock_endpoints[TWO_KEY].hits());
+        }
+        TestType::Tasks => {
+            // All users run the only task set.
+            assert_eq!(task_set_users.len(), 1);
+            assert_eq!(task_set_users[0].users, USERS);
+        }
+    }
+}
+
 // Returns the appropriate taskset, start_task and stop_task needed to build these tests.
 fn get_tasksets() -> (SwanlingTaskSet, SwanlingTaskSet, SwanlingTask, SwanlingTask) {
     (
@@ -290,10 +316,11 @@ fn run_standalone_test(test_type: &TestType, scheduler: &SwanlingScheduler) {
     }
 
     // Run the Swanling Attack.
-    common::run_load_test(swanling_attack, None);
+    let swanling_metrics = common::run_load_test(swanling_attack, None);
 
     // Confirm the load test ran correctly.
     validate_test(test_type, &scheduler, &mock_endpoints);
+    validate_task_set_users(test_type, &swanling_metrics, &mock_endpoints);
 }
 
 // Helper to run all gaggle tests.
@@ -370,10 +397,11 @@ fn run_gaggle_test(test_type: &TestType, scheduler: &SwanlingScheduler) {
     }
 
     // Run the Swanling Attack.
-    common::run_load_test(manager_swanling_attack, Some(worker_handles));
+    let swanling_metrics = common::run_load_test(manager_swanling_attack, Some(worker_handles));
 
     // Confirm the load test ran correctly.
     validate_test(test_type, &scheduler, &mock_endpoints);
+    validate_task_set_users(test_type, &swanling_metrics, &mock_endpoints);
 }
 
 #[test]
@@ -447,3 +475,61 @@ fn test_random_taskset_gaggle() {
 fn test_random_tasks() {
     run_standalone_test(&TestType::Tasks, &SwanlingScheduler::Random);
 }
+
+// A custom task scheduler that runs the `three` task three times per pass, stopping the
+// user after two passes.
+struct ThreeTimes {
+    passes: usize,
+    runs: usize,
+}
+impl SwanlingTaskScheduler for ThreeTimes {
+    fn next_task(&mut self, _user: &SwanlingUser, _task_set: &SwanlingTaskSet) -> Option<usize> {
+        if self.runs == 3 {
+            // End the pass, the next one starts over.
+            self.runs = 0;
+            self.passes += 1;
+            None
+        } else if self.passes == 2 {
+            // Nothing left to do, the user stops.
+            None
+        } else {
+            self.runs += 1;
+            // The `three` task is the first task registered by get_tasks().
+            Some(0)
+        }
+    }
+}
+
+#[test]
+// Load test with a custom task scheduler deciding which task each user runs next.
+fn test_custom_task_scheduler() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the mock endpoints needed for this test.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build common configuration.
+    let configuration = common_build_configuration(&server, None, None);
+
+    // Get the taskset, start and stop tasks to build a load test.
+    let (taskset1, start_task, stop_task) = get_tasks();
+    let swanling_attack = crate::SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(taskset1)
+        .test_start(start_task)
+        .test_stop(stop_task)
+        .set_task_scheduler(|_task_set| Box::new(ThreeTimes { passes: 0, runs: 0 }));
+
+    // Run the Swanling Attack.
+    common::run_load_test(swanling_attack, None);
+
+    // Only the scheduled task ran, three times in each of two passes per user.
+    assert_eq!(mock_endpoints[ONE_KEY].hits(), 0);
+    assert_eq!(mock_endpoints[TWO_KEY].hits(), 0);
+    assert_eq!(mock_endpoints[THREE_KEY].hits(), USERS * 6);
+
+    // Start and stop tasks still run one time.
+    assert_eq!(mock_endpoints[START_ONE_KEY].hits(), 1);
+    assert_eq!(mock_endpoints[STOP_ONE_KEY].hits(), 1);
+}
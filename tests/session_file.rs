@@ -0,0 +1,132 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const INDEX_KEY: usize = 1;
+
+// The session cookie set when logging in.
+const SESSION_COOKIE: &str = "session=swanling";
+
+// File the user sessions are saved to.
+const SESSION_FILE: &str = "session-file-test.json";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task, logging in only if the user doesn't already have a session.
+pub async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    if user.get_session_value("logged_in").await.is_none() {
+        let _swanling = user.get(LOGIN_PATH).await?;
+        user.set_session_value("logged_in", "true").await;
+    }
+    Ok(())
+}
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints, the index only responding to
+// logged in users.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200)
+                .header("set-cookie", &format!("{}; Path=/", SESSION_COOKIE));
+        }),
+        // Next set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("cookie", SESSION_COOKIE);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--session-file",
+            SESSION_FILE,
+        ],
+    )
+}
+
+// Run a load test that logs in each user before requesting the index.
+fn run_load_test(server: &MockServer) -> SwanlingMetrics {
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(server),
+            &taskset!("LoadTest")
+                .register_task(task!(login).set_on_start())
+                .register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Confirm user sessions are saved at the end of a load test, and reloaded by the next.
+fn test_session_file() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each user logs in during the first load test.
+    run_load_test(&server);
+    assert_eq!(mock_endpoints[LOGIN_KEY].hits(), USERS);
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(index_hits > 0);
+
+    // The session of each user was saved.
+    let session_file = std::fs::read_to_string(SESSION_FILE).expect("failed to read sessions");
+    let sessions: serde_json::Value = serde_json::from_str(&session_file).unwrap();
+    let users = sessions["users"].as_array().unwrap();
+    assert_eq!(users.len(), USERS);
+    for user in users {
+        assert_eq!(user["cookies"][0], SESSION_COOKIE);
+        assert_eq!(user["session_data"]["logged_in"], "true");
+    }
+
+    // Users reuse their saved sessions in the next load test, without logging in again.
+    let swanling_metrics = run_load_test(&server);
+    assert_eq!(mock_endpoints[LOGIN_KEY].hits(), USERS);
+    assert!(mock_endpoints[INDEX_KEY].hits() > index_hits);
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.fail_count, 0);
+
+    // Cleanup from test.
+    common::cleanup_files(vec![SESSION_FILE]);
+}
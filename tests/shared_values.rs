@@ -0,0 +1,208 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const FIXTURE_PATH: &str = "/fixture/42";
+
+// Indexes to the above paths.
+const FIXTURE_KEY: usize = 0;
+
+// The shared value published once, and read by all users.
+const FIXTURE_ID_KEY: &str = "fixture_id";
+const FIXTURE_ID: &str = "42";
+
+// Load test configuration.
+const EXPECT_WORKERS: usize = 2;
+const USERS: &str = "4";
+
+// How long users wait for the fixture to be published, in seconds.
+const TIMEOUT: u64 = 5;
+
+// There are multiple test variations in this file.
+#[derive(Clone)]
+enum TestType {
+    // The value is published by test_start().
+    Setup,
+    // The value is published by a task run by one user.
+    Task,
+}
+
+// Test task, publishing the fixture.
+pub async fn publish_fixture(user: &SwanlingUser) -> SwanlingTaskResult {
+    user.set_shared_value(FIXTURE_ID_KEY, FIXTURE_ID);
+    Ok(())
+}
+
+// Test task, loading the published fixture.
+pub async fn get_fixture(user: &SwanlingUser) -> SwanlingTaskResult {
+    let fixture_id = user
+        .get_shared_value(FIXTURE_ID_KEY, Duration::from_secs(TIMEOUT))
+        .await?;
+    let _swanling = user.get(&format!("/fixture/{}", fixture_id)).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up FIXTURE_PATH, store in vector at FIXTURE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(FIXTURE_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    worker: Option<bool>,
+    manager: Option<usize>,
+) -> SwanlingConfiguration {
+    if let Some(expect_workers) = manager {
+        common::build_configuration(
+            &server,
+            vec![
+                "--manager",
+                "--expect-workers",
+                &expect_workers.to_string(),
+                "--users",
+                USERS,
+                "--hatch-rate",
+                USERS,
+                "--run-time",
+                "3",
+            ],
+        )
+    } else if worker.is_some() {
+        common::build_configuration(&server, vec!["--worker"])
+    } else {
+        common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                USERS,
+                "--hatch-rate",
+                USERS,
+                "--run-time",
+                "3",
+                "--no-reset-metrics",
+            ],
+        )
+    }
+}
+
+// Helper to confirm all variations generate appropriate results.
+fn validate_test(mock_endpoints: &[MockRef]) {
+    // Users only request the fixture once it has been published.
+    assert!(mock_endpoints[FIXTURE_KEY].hits() > 0);
+}
+
+// Build an appropriate SwanlingAttack object for test type, using supplied configuration.
+fn build_swanling_attack(
+    test_type: &TestType,
+    configuration: SwanlingConfiguration,
+) -> SwanlingAttack {
+    let taskset = taskset!("LoadTest").register_task(task!(get_fixture));
+    match test_type {
+        TestType::Setup => {
+            common::build_load_test(configuration, &taskset, Some(&task!(publish_fixture)), None)
+        }
+        TestType::Task => common::build_load_test(configuration, &taskset, None, None)
+            .register_taskset(
+                taskset!("Setup")
+                    .register_task(task!(publish_fixture).set_on_start())
+                    .register_task(task!(get_fixture)),
+            ),
+    }
+}
+
+// Helper to run all standalone tests.
+fn run_standalone_test(test_type: TestType) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the mock endpoints needed for this test.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build common configuration.
+    let configuration = common_build_configuration(&server, None, None);
+
+    // Use configuration to generate the load test.
+    let swanling_attack = build_swanling_attack(&test_type, configuration);
+
+    // Run the load test.
+    let swanling_metrics = common::run_load_test(swanling_attack, None);
+
+    // Confirm the load test ran correctly.
+    validate_test(&mock_endpoints);
+
+    // No user timed out waiting for the fixture.
+    for task_metrics in swanling_metrics.tasks.iter().flatten() {
+        assert_eq!(task_metrics.fail_count, 0);
+    }
+}
+
+// Helper to run all gaggle tests.
+fn run_gaggle_test(test_type: TestType) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the mock endpoints needed for this test.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build common configuration.
+    let worker_configuration = common_build_configuration(&server, Some(true), None);
+
+    // Use Worker configuration to generate the load test.
+    let swanling_attack = build_swanling_attack(&test_type, worker_configuration);
+
+    // Workers launched in own threads, store thread handles.
+    let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
+
+    // Build Manager configuration.
+    let manager_configuration = common_build_configuration(&server, None, Some(EXPECT_WORKERS));
+
+    // Use Manager configuration to generate the load test.
+    let swanling_attack = build_swanling_attack(&test_type, manager_configuration);
+
+    // Run the load test.
+    common::run_load_test(swanling_attack, Some(worker_handles));
+
+    // Confirm the load test ran correctly.
+    validate_test(&mock_endpoints);
+}
+
+#[test]
+// Confirm a value published by test_start() is shared with all users.
+fn test_shared_values_setup() {
+    run_standalone_test(TestType::Setup);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+#[serial]
+// Confirm a value published by test_start() is shared with all users, in Regatta mode.
+fn test_shared_values_setup_gaggle() {
+    run_gaggle_test(TestType::Setup);
+}
+
+#[test]
+// Confirm a value published by one user is shared with all users.
+fn test_shared_values_task() {
+    run_standalone_test(TestType::Task);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+#[serial]
+// Confirm a value published by one user is shared with all users, in Regatta mode.
+fn test_shared_values_task_gaggle() {
+    run_gaggle_test(TestType::Task);
+}
@@ -0,0 +1,91 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 10;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm the load test can be stopped from an async task with a shutdown handle, still
+// returning the metrics it collected.
+fn test_shutdown_handle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_attack = common::build_load_test(
+        common_build_configuration(&server),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    let shutdown = swanling_attack.shutdown_handle();
+
+    // Run the Swanling Attack from an async program, stopping it once requests have been
+    // made.
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let swanling_metrics = rt.block_on(async {
+        let load_test =
+            tokio::task::spawn_blocking(move || common::run_load_test(swanling_attack, None));
+        while mock_endpoints[INDEX_KEY].hits() == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        shutdown.shutdown();
+        load_test.await.unwrap()
+    });
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(swanling_metrics.duration < RUN_TIME);
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(request_metrics.success_count > 0);
+}
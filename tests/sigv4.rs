@@ -0,0 +1,177 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::metrics::SwanlingErrorKind;
+use swanling::prelude::*;
+use swanling::swanling::SIGV4_ERROR;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// AWS region, service and credentials requests are signed with.
+const REGION: &str = "us-east-1";
+const SERVICE: &str = "execute-api";
+const ACCESS_KEY_ID: &str = "AKIDEXAMPLE";
+const SECRET_ACCESS_KEY: &str = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint. Only signed requests match, and
+// are answered with the provided status code and AWS error type.
+fn setup_mock_server_endpoints<'a>(
+    server: &'a MockServer,
+    status: u16,
+    error_type: Option<&str>,
+) -> Vec<MockRef<'a>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header_exists("authorization")
+                .header_exists("x-amz-date");
+            let then = then.status(status);
+            if let Some(error_type) = error_type {
+                then.header("x-amzn-errortype", error_type);
+            }
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--aws-region",
+            REGION,
+            "--aws-service",
+            SERVICE,
+            "--aws-access-key-id",
+            ACCESS_KEY_ID,
+            "--aws-secret-access-key",
+            SECRET_ACCESS_KEY,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm every request is signed with AWS SigV4.
+fn test_sigv4() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server, 200, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Every request was signed.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(request_metrics.success_count > 0);
+    assert_eq!(request_metrics.fail_count, 0);
+    assert_eq!(
+        mock_endpoints[INDEX_KEY].hits(),
+        request_metrics.success_count
+    );
+}
+
+#[test]
+// Confirm requests AWS rejects because of their signature are tracked as their own
+// category of error.
+fn test_sigv4_rejected() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints =
+        setup_mock_server_endpoints(&server, 403, Some("InvalidSignatureException"));
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Every request failed.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(request_metrics.fail_count > 0);
+    assert_eq!(request_metrics.success_count, 0);
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), request_metrics.fail_count);
+
+    // The failures were captured as a signing error.
+    assert_eq!(swanling_metrics.errors.len(), 1);
+    for error in swanling_metrics.errors.values() {
+        assert!(error.error.starts_with(SIGV4_ERROR));
+        assert_eq!(error.kind, SwanlingErrorKind::Signing);
+        assert_eq!(error.occurrences, request_metrics.fail_count);
+    }
+}
+
+#[test]
+// Confirm the load test refuses to start without a service or credentials.
+fn test_sigv4_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // A region without a service.
+    let configuration = common::build_configuration(&server, vec!["--aws-region", REGION]);
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(taskset!("LoadTest").register_task(task!(get_index)));
+    assert!(swanling_attack.execute().is_err());
+
+    // A region and service without credentials.
+    std::env::remove_var("AWS_ACCESS_KEY_ID");
+    std::env::remove_var("AWS_SECRET_ACCESS_KEY");
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--aws-region", REGION, "--aws-service", SERVICE],
+    );
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(taskset!("LoadTest").register_task(task!(get_index)));
+    assert!(swanling_attack.execute().is_err());
+}
@@ -0,0 +1,220 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Load test configuration.
+const EXPECT_WORKERS: usize = 2;
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(503);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    slas: &[&str],
+    worker: Option<bool>,
+    manager: Option<usize>,
+) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    for sla in slas {
+        configuration.push("--sla");
+        configuration.push(*sla);
+    }
+
+    if let Some(expect_workers) = manager {
+        let expect_workers = expect_workers.to_string();
+        let mut manager_configuration = vec!["--manager", "--expect-workers", &expect_workers];
+        manager_configuration.extend(configuration);
+        common::build_configuration(&server, manager_configuration)
+    } else if worker.is_some() {
+        common::build_configuration(&server, vec!["--worker"])
+    } else {
+        common::build_configuration(&server, configuration)
+    }
+}
+
+// Helper to confirm the load test ran against both endpoints.
+fn validate_test(mock_endpoints: &[MockRef]) {
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest")
+        .register_task(task!(get_index))
+        .register_task(task!(get_error))
+}
+
+#[test]
+// Confirm the load test succeeds when all service level agreements are met.
+fn test_sla_passed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration, allowing generous response times and errors on /error.
+    let configuration = common_build_configuration(
+        &server,
+        &[
+            "/:p95=10000",
+            "/:errors=0",
+            "/error:max=10000",
+            "/error:errors=100",
+        ],
+        None,
+        None,
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    validate_test(&mock_endpoints);
+
+    // Confirm all agreements were checked and met.
+    assert_eq!(swanling_metrics.slas.len(), 4);
+    assert!(swanling_metrics.slas.iter().all(|sla| sla.passed));
+    assert_eq!(swanling_metrics.slas[3].observed, Some(100.0));
+}
+
+#[test]
+// Confirm the load test returns an error when a service level agreement is breached.
+fn test_sla_breached() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration, allowing no errors on /error.
+    let configuration =
+        common_build_configuration(&server, &["/:p95=10000", "/error:errors=0"], None, None);
+
+    // Run the Swanling Attack.
+    let swanling_attack = common::build_load_test(configuration, &get_tasks(), None, None);
+    match swanling_attack.execute() {
+        Err(SwanlingError::SlaBreach {
+            breached, metrics, ..
+        }) => {
+            // Only the agreement for /error was breached.
+            assert_eq!(breached, vec!["/error: errors <= 0%".to_string()]);
+
+            // The final metrics are returned with the error.
+            assert_eq!(metrics.slas.len(), 2);
+            let error_request = metrics.requests.get("GET /error").unwrap();
+            assert_eq!(error_request.fail_count, mock_endpoints[ERROR_KEY].hits());
+        }
+        other => panic!("expected SlaBreach, got {:?}", other.map(|_| ())),
+    }
+
+    validate_test(&mock_endpoints);
+}
+
+#[test]
+// Confirm an invalid service level agreement is rejected.
+fn test_sla_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration with an agreement that is missing a threshold.
+    let configuration = common::build_configuration(&server, vec!["--sla", "/error"]);
+
+    // Confirm the load test refuses to start.
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(get_tasks());
+    assert!(swanling_attack.execute().is_err());
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+// Confirm service level agreements are checked against metrics merged from all
+// Workers, in Regatta mode.
+fn test_sla_breached_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each worker has the same identical configuration.
+    let worker_configuration = common_build_configuration(&server, &[], Some(true), None);
+
+    // Build the load test for the Workers.
+    let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
+
+    // Workers launched in own threads, store thread handles.
+    let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
+
+    // Build Manager configuration, allowing no errors on /error.
+    let manager_configuration =
+        common_build_configuration(&server, &["/error:errors=0"], None, Some(EXPECT_WORKERS));
+
+    // Run the Swanling Attack.
+    let manager_swanling_attack =
+        common::build_load_test(manager_configuration, &get_tasks(), None, None);
+    assert!(matches!(
+        manager_swanling_attack.execute(),
+        Err(SwanlingError::SlaBreach { .. })
+    ));
+
+    // Wait for the Workers to exit.
+    for worker in worker_handles {
+        let _ = worker.join();
+    }
+
+    validate_test(&mock_endpoints);
+}
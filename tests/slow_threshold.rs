@@ -0,0 +1,159 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::metrics::SLOW_REQUEST_SAMPLES;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const FAST_PATH: &str = "/fast";
+const SLOW_PATH: &str = "/slow";
+
+// The query sent with slow requests, which is included in the slow request examples.
+const SLOW_QUERY: &str = "q=swanling";
+
+// Indexes to the above paths.
+const FAST_KEY: usize = 0;
+const SLOW_KEY: usize = 1;
+
+// How long the slow path takes to respond, and the slow threshold, in milliseconds.
+const SLOW_DELAY: u64 = 200;
+const SLOW_THRESHOLD: usize = 100;
+
+// Request log written during the test.
+const REQUEST_LOG: &str = "slow-threshold-request-log.json";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_fast(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(FAST_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_slow(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user
+        .get_named(&format!("{}?{}", SLOW_PATH, SLOW_QUERY), SLOW_PATH)
+        .await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up FAST_PATH, store in vector at FAST_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(FAST_PATH);
+            then.status(200);
+        }),
+        // Next set up SLOW_PATH, store in vector at SLOW_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_millis(SLOW_DELAY));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let slow_threshold = SLOW_THRESHOLD.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--slow-threshold",
+            &slow_threshold,
+            "--request-log",
+            REQUEST_LOG,
+            "--request-format",
+            "json",
+            "--log-only",
+            FAST_PATH,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm requests slower than the threshold are counted, sampled and always logged.
+fn test_slow_threshold() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(get_fast))
+                .register_task(task!(get_slow)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let fast_hits = mock_endpoints[FAST_KEY].hits();
+    let slow_hits = mock_endpoints[SLOW_KEY].hits();
+    assert!(fast_hits > 0);
+    assert!(slow_hits > 0);
+
+    // Fast requests are never slow.
+    let fast_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", FAST_PATH))
+        .unwrap();
+    assert_eq!(fast_metrics.slow_count, 0);
+    assert!(fast_metrics.slow_requests.is_empty());
+
+    // Every slow request is counted, and the slowest are kept with their full URL.
+    let slow_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", SLOW_PATH))
+        .unwrap();
+    assert_eq!(slow_metrics.slow_count, slow_hits);
+    assert_eq!(
+        slow_metrics.slow_requests.len(),
+        slow_hits.min(SLOW_REQUEST_SAMPLES)
+    );
+    for slow_request in &slow_metrics.slow_requests {
+        assert!(slow_request
+            .url
+            .ends_with(&format!("{}?{}", SLOW_PATH, SLOW_QUERY)));
+        assert!(slow_request.response_time > SLOW_THRESHOLD as u64);
+    }
+    assert!(slow_metrics
+        .slow_requests
+        .windows(2)
+        .all(|pair| pair[0].response_time >= pair[1].response_time));
+
+    // Slow requests are logged even though --log-only is limited to the fast path.
+    let logged = std::fs::read_to_string(REQUEST_LOG).expect("failed to read request log");
+    let fast_name = format!(r#""name":"{}""#, FAST_PATH);
+    let slow_name = format!(r#""name":"{}""#, SLOW_PATH);
+    assert_eq!(logged.matches(&fast_name).count(), fast_hits);
+    assert_eq!(logged.matches(&slow_name).count(), slow_hits);
+
+    // The slow requests are included in the final report.
+    let report = swanling_metrics.to_string();
+    assert!(report.contains(&format!("SLOW REQUESTS (over {} ms)", SLOW_THRESHOLD)));
+    assert!(report.contains(&format!("{}?{}", SLOW_PATH, SLOW_QUERY)));
+
+    // Cleanup from test.
+    common::cleanup_files(vec![REQUEST_LOG]);
+}
@@ -0,0 +1,161 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about";
+const ERROR_PATH: &str = "/error";
+const LOGOUT_PATH: &str = "/logout";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const INDEX_KEY: usize = 1;
+const ABOUT_KEY: usize = 2;
+const ERROR_KEY: usize = 3;
+const LOGOUT_KEY: usize = 4;
+
+// Test task.
+pub async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGIN_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn logout(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200);
+        }),
+        // Next set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(503);
+        }),
+        // Last set up LOGOUT_PATH, store in vector at LOGOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Returns a task set logging in, loading pages and logging out. Tasks are registered out
+// of order, and the normal tasks are weighted.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest")
+        .register_task(task!(logout).set_on_stop())
+        .register_task(task!(get_about).set_name("about").set_weight(5).unwrap())
+        .register_task(task!(get_index).set_name("index").set_sequence(1))
+        .register_task(task!(login).set_on_start())
+}
+
+#[test]
+// Confirm a smoke test runs each task once, in order, without generating load.
+fn test_smoke() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = common::build_load_test(
+        common::build_configuration(&server, vec!["--smoke", "--users", "10"]),
+        &get_tasks(),
+        None,
+        None,
+    )
+    .execute()
+    .unwrap();
+
+    // Each task ran exactly once.
+    assert_eq!(mock_endpoints[LOGIN_KEY].hits(), 1);
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), 1);
+    assert_eq!(mock_endpoints[ABOUT_KEY].hits(), 1);
+    assert_eq!(mock_endpoints[LOGOUT_KEY].hits(), 1);
+    assert_eq!(mock_endpoints[ERROR_KEY].hits(), 0);
+
+    // Tasks ran in the order a user runs them, and all passed.
+    let tasks: Vec<&str> = swanling_metrics
+        .smoke
+        .iter()
+        .map(|result| result.task_name.as_str())
+        .collect();
+    assert_eq!(tasks, vec!["", "index", "about", ""]);
+    assert_eq!(swanling_metrics.smoke[0].task_index, 3);
+    assert_eq!(swanling_metrics.smoke[3].task_index, 0);
+    assert!(swanling_metrics.smoke.iter().all(|result| result.passed));
+}
+
+#[test]
+// Confirm a smoke test fails if a request made by a task fails.
+fn test_smoke_failed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let result = common::build_load_test(
+        common::build_configuration(&server, vec!["--smoke"]),
+        &get_tasks().register_task(task!(get_error).set_name("error")),
+        None,
+        None,
+    )
+    .execute();
+
+    // The remaining tasks still ran after the failure.
+    assert_eq!(mock_endpoints[ERROR_KEY].hits(), 1);
+    assert_eq!(mock_endpoints[LOGOUT_KEY].hits(), 1);
+
+    match result {
+        Err(SwanlingError::SmokeFailed {
+            failed, metrics, ..
+        }) => {
+            assert_eq!(failed, vec!["LoadTest: 5: error".to_string()]);
+            let error = metrics.smoke.iter().find(|r| !r.passed).unwrap();
+            assert!(error.detail.as_ref().unwrap().contains("GET error"));
+            assert_eq!(metrics.smoke.iter().filter(|r| r.passed).count(), 4);
+        }
+        _ => panic!("expected the smoke test to fail"),
+    }
+}
@@ -0,0 +1,116 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 8;
+const SPIKE: &str = "3x,1s,every=2s";
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, spike: &str) -> SwanlingConfiguration {
+    common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--spike",
+            spike,
+        ],
+    )
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(get_index))
+}
+
+#[test]
+// Confirm a spike launches additional users, and is recorded in the metrics.
+fn test_spike() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common_build_configuration(&server, SPIKE);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // The configured users are not counted twice.
+    assert_eq!(swanling_metrics.users, USERS);
+
+    // Spikes are due every two seconds, so at least two start during the load test.
+    assert!(swanling_metrics.spikes.len() >= 2);
+
+    // Confirm the first spike started after two seconds.
+    assert!(swanling_metrics.spikes[0].started >= 2);
+
+    // Each spike launches twice the configured number of users. Every spike but the last
+    // is over before the next starts, stopping at least one second later. The last may
+    // still be running when the load test stops.
+    for spike in &swanling_metrics.spikes {
+        assert_eq!(spike.users, USERS * 2);
+    }
+    let (last, closed) = swanling_metrics.spikes.split_last().unwrap();
+    for spike in closed {
+        assert!(spike.stopped.unwrap() > spike.started);
+    }
+    if let Some(stopped) = last.stopped {
+        assert!(stopped > last.started);
+    }
+}
+
+#[test]
+// Confirm an invalid spike is rejected.
+fn test_spike_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration with a spike that lasts longer than the interval.
+    let configuration = common_build_configuration(&server, "3x,5s,every=2s");
+
+    // Confirm the load test refuses to start.
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(get_tasks());
+    assert!(swanling_attack.execute().is_err());
+}
@@ -0,0 +1,102 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 4;
+const RUN_TIME: usize = 1;
+const STOP_TIME: usize = 2;
+
+// When each user last made a request.
+static LAST_REQUEST: Mutex<[Option<Instant>; USERS]> = Mutex::new([None; USERS]);
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    LAST_REQUEST.lock().unwrap()[user.weighted_users_index] = Some(Instant::now());
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let stop_time = STOP_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--stop-time",
+            &stop_time,
+        ],
+    )
+}
+
+#[test]
+// Confirm users are stopped gradually over --stop-time rather than all at once.
+fn test_stop_time() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let started = Instant::now();
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // The load test ran for the run time plus most of the stop time.
+    let stop_user_in = Duration::from_millis((STOP_TIME * 1_000 / USERS) as u64);
+    assert!(started.elapsed() >= Duration::from_secs(RUN_TIME as u64) + stop_user_in * 3);
+
+    // Users kept making requests until they were stopped one at a time. On a busy host
+    // a user may not finish a request before it is stopped, so only compare the others.
+    let last_requests: Vec<Instant> = LAST_REQUEST
+        .lock()
+        .unwrap()
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+    assert!(last_requests.len() > 1);
+    let first_stopped = last_requests.iter().min().unwrap();
+    let last_stopped = last_requests.iter().max().unwrap();
+    assert!(*last_stopped - *first_stopped >= stop_user_in * 2);
+}
@@ -0,0 +1,161 @@
+use httpmock::{Method::POST, MockRef, MockServer};
+use std::io::Cursor;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const UPLOAD_PATH: &str = "/upload";
+
+// Indexes to the above paths.
+const UPLOAD_KEY: usize = 0;
+
+// Size of the streamed request body, large enough to be sent in several chunks.
+const BODY_SIZE: usize = 64 * 1024;
+
+// Load test configuration.
+const EXPECT_WORKERS: usize = 2;
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn post_upload(user: &SwanlingUser) -> SwanlingTaskResult {
+    let body = Cursor::new(vec![b'x'; BODY_SIZE]);
+    let _swanling = user.post_stream(UPLOAD_PATH, body).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up UPLOAD_PATH, store in vector at UPLOAD_KEY. Only match requests
+        // that uploaded the entire body.
+        server.mock(|when, then| {
+            when.method(POST)
+                .path(UPLOAD_PATH)
+                .body("x".repeat(BODY_SIZE));
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    worker: Option<bool>,
+    manager: Option<usize>,
+) -> SwanlingConfiguration {
+    if let Some(expect_workers) = manager {
+        common::build_configuration(
+            &server,
+            vec![
+                "--manager",
+                "--expect-workers",
+                &expect_workers.to_string(),
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--no-reset-metrics",
+            ],
+        )
+    } else if worker.is_some() {
+        common::build_configuration(&server, vec!["--worker"])
+    } else {
+        common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+                "--no-reset-metrics",
+            ],
+        )
+    }
+}
+
+// Helper to confirm all variations generate appropriate results.
+fn validate_test(swanling_metrics: &SwanlingMetrics, mock_endpoints: &[MockRef]) {
+    // Confirm that the entire body was uploaded.
+    assert!(mock_endpoints[UPLOAD_KEY].hits() > 0);
+
+    let request = swanling_metrics
+        .requests
+        .values()
+        .next()
+        .expect("no requests recorded");
+
+    // Confirm that no requests failed.
+    assert_eq!(request.fail_count, 0);
+    assert_eq!(request.success_count, mock_endpoints[UPLOAD_KEY].hits());
+
+    // Confirm that every byte streamed was counted.
+    assert_eq!(request.bytes_sent, request.success_count * BODY_SIZE);
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(post_upload))
+}
+
+#[test]
+// Confirm streamed request bodies are uploaded and counted in bytes_sent.
+fn test_stream_body() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common_build_configuration(&server, None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm that the body was streamed and counted.
+    validate_test(&swanling_metrics, &mock_endpoints);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+// Confirm streamed request bodies are counted in bytes_sent, in Regatta mode.
+fn test_stream_body_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each worker has the same identical configuration.
+    let worker_configuration = common_build_configuration(&server, Some(true), None);
+
+    // Build the load test for the Workers.
+    let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
+
+    // Workers launched in own threads, store thread handles.
+    let worker_handles = common::launch_gaggle_workers(swanling_attack, EXPECT_WORKERS);
+
+    // Build Manager configuration.
+    let manager_configuration = common_build_configuration(&server, None, Some(EXPECT_WORKERS));
+
+    // Build the load test for the Manager.
+    let manager_swanling_attack =
+        common::build_load_test(manager_configuration, &get_tasks(), None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(manager_swanling_attack, Some(worker_handles));
+
+    // Confirm that the body was streamed and counted.
+    validate_test(&swanling_metrics, &mock_endpoints);
+}
@@ -0,0 +1,180 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Names of the attacks run by the suite.
+const INDEX_ATTACK: &str = "index";
+const ABOUT_ATTACK: &str = "about";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm a suite runs each attack in sequence, keeping the metrics of each apart.
+fn test_suite() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run both attacks, one after the other.
+    let suite_metrics = SwanlingSuite::new()
+        .register_attack(
+            INDEX_ATTACK,
+            common::build_load_test(
+                common_build_configuration(&server),
+                &taskset!("LoadTest").register_task(task!(get_index)),
+                None,
+                None,
+            ),
+        )
+        .register_attack(
+            ABOUT_ATTACK,
+            common::build_load_test(
+                common_build_configuration(&server),
+                &taskset!("LoadTest").register_task(task!(get_about)),
+                None,
+                None,
+            ),
+        )
+        .execute()
+        .unwrap();
+
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    let about_hits = mock_endpoints[ABOUT_KEY].hits();
+    assert!(index_hits > 0);
+    assert!(about_hits > 0);
+
+    // The attacks ran in the order they were registered.
+    let names: Vec<&str> = suite_metrics
+        .attacks
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(names, vec![INDEX_ATTACK, ABOUT_ATTACK]);
+
+    // Each attack only includes its own requests.
+    let index_metrics = suite_metrics.get(INDEX_ATTACK).unwrap();
+    assert_eq!(index_metrics.requests.len(), 1);
+    assert_eq!(
+        index_metrics
+            .requests
+            .get(&format!("GET {}", INDEX_PATH))
+            .unwrap()
+            .success_count,
+        index_hits
+    );
+    let about_metrics = suite_metrics.get(ABOUT_ATTACK).unwrap();
+    assert_eq!(about_metrics.requests.len(), 1);
+    assert_eq!(
+        about_metrics
+            .requests
+            .get(&format!("GET {}", ABOUT_PATH))
+            .unwrap()
+            .success_count,
+        about_hits
+    );
+
+    // Each attack is displayed in its own section, followed by a summary.
+    let report = suite_metrics.to_string();
+    assert!(report.contains(&format!("=== ATTACK: {} ===", INDEX_ATTACK)));
+    assert!(report.contains(&format!("=== ATTACK: {} ===", ABOUT_ATTACK)));
+    assert!(report.contains("=== SUITE ==="));
+}
+
+#[test]
+// Confirm a suite won't run attacks with the same name.
+fn test_suite_duplicate_name() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let result = SwanlingSuite::new()
+        .register_attack(
+            INDEX_ATTACK,
+            common::build_load_test(
+                common_build_configuration(&server),
+                &taskset!("LoadTest").register_task(task!(get_index)),
+                None,
+                None,
+            ),
+        )
+        .register_attack(
+            INDEX_ATTACK,
+            common::build_load_test(
+                common_build_configuration(&server),
+                &taskset!("LoadTest").register_task(task!(get_about)),
+                None,
+                None,
+            ),
+        )
+        .execute();
+
+    match result {
+        Err(SwanlingError::InvalidOption { value, .. }) => assert_eq!(value, INDEX_ATTACK),
+        _ => panic!("expected an invalid option"),
+    }
+
+    // Neither attack ran.
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), 0);
+    assert_eq!(mock_endpoints[ABOUT_KEY].hits(), 0);
+}
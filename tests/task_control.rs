@@ -0,0 +1,179 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const CHECKOUT_PATH: &str = "/checkout";
+const LOGOUT_PATH: &str = "/logout";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const CHECKOUT_KEY: usize = 1;
+const LOGOUT_KEY: usize = 2;
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 2;
+
+// Test task, skips the rest of the tasks if logging in fails.
+pub async fn login_or_skip(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(LOGIN_PATH).await?;
+    if !swanling.request.success {
+        user.set_task_control(SwanlingTaskControl::SkipRest);
+    }
+    Ok(())
+}
+
+// Test task, stops the user if logging in fails.
+pub async fn login_or_abort(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(LOGIN_PATH).await?;
+    if !swanling.request.success {
+        user.set_task_control(SwanlingTaskControl::AbortUser);
+    }
+    Ok(())
+}
+
+// Test task, only works for logged in users.
+pub async fn checkout(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(CHECKOUT_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn logout(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY. Logging in always fails.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(401);
+        }),
+        // Next set up CHECKOUT_PATH, store in vector at CHECKOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(CHECKOUT_PATH);
+            then.status(200);
+        }),
+        // Next set up LOGOUT_PATH, store in vector at LOGOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    )
+}
+
+#[test]
+// Confirm a task can skip the rest of the tasks, starting over with the first.
+fn test_task_control_skip_rest() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(login_or_skip).set_sequence(1))
+                .register_task(task!(checkout).set_sequence(2))
+                .register_task(task!(logout).set_on_stop()),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Users kept trying to log in, but never checked out.
+    assert!(mock_endpoints[LOGIN_KEY].hits() > USERS);
+    assert_eq!(mock_endpoints[CHECKOUT_KEY].hits(), 0);
+
+    // The on_stop tasks still ran.
+    assert_eq!(mock_endpoints[LOGOUT_KEY].hits(), USERS);
+}
+
+#[test]
+// Confirm a task can stop the user, which still runs its on_stop tasks.
+fn test_task_control_abort_user() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(login_or_abort).set_sequence(1))
+                .register_task(task!(checkout).set_sequence(2))
+                .register_task(task!(logout).set_on_stop()),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Each user tried to log in once, then stopped.
+    assert_eq!(mock_endpoints[LOGIN_KEY].hits(), USERS);
+    assert_eq!(mock_endpoints[CHECKOUT_KEY].hits(), 0);
+
+    // The on_stop tasks still ran.
+    assert_eq!(mock_endpoints[LOGOUT_KEY].hits(), USERS);
+}
+
+#[test]
+// Confirm an on_start task can stop the user before it runs any normal tasks.
+fn test_task_control_abort_user_on_start() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest")
+                .register_task(task!(login_or_abort).set_on_start())
+                .register_task(task!(checkout))
+                .register_task(task!(logout).set_on_stop()),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Each user tried to log in once, and never ran a normal task.
+    assert_eq!(mock_endpoints[LOGIN_KEY].hits(), USERS);
+    assert_eq!(mock_endpoints[CHECKOUT_KEY].hits(), 0);
+
+    // The on_stop tasks still ran.
+    assert_eq!(mock_endpoints[LOGOUT_KEY].hits(), USERS);
+}
@@ -0,0 +1,128 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// How long the about page takes to respond, in milliseconds.
+const ABOUT_DELAY: u64 = 50;
+
+// Task profile written during the test.
+const TASK_PROFILE: &str = "task-profile.folded";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task, making two different requests. Requests are named after the task unless
+// they're given their own names.
+pub async fn get_pages(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get_named(INDEX_PATH, "index").await?;
+    let _swanling = user.get_named(ABOUT_PATH, "about").await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200).delay(Duration::from_millis(ABOUT_DELAY));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+#[test]
+// Confirm the time spent in each task and request is written as a folded stack profile.
+fn test_task_profile() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, &["--task-profile", TASK_PROFILE]),
+            &taskset!("LoadTest").register_task(task!(get_pages).set_name("pages")),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    let about_hits = mock_endpoints[ABOUT_KEY].hits();
+    assert!(about_hits > 0);
+
+    // The task metrics include the time spent in each request the task made.
+    let task = &swanling_metrics.tasks[0][0];
+    let index_time = task.request_times["GET index"];
+    let about_time = task.request_times["GET about"];
+    assert!(about_time >= about_hits * ABOUT_DELAY as usize);
+    assert!(index_time + about_time <= task.total_time + task.counter);
+
+    // Each request is written below the task and task set that made it.
+    let profile = std::fs::read_to_string(TASK_PROFILE).expect("failed to read task profile");
+    assert!(profile.contains(&format!("1: LoadTest;1: pages;GET about {}\n", about_time)));
+    for line in profile.lines() {
+        assert!(line.starts_with("1: LoadTest;1: pages"));
+        let (_, value) = line.rsplit_once(' ').unwrap();
+        assert!(value.parse::<usize>().unwrap() > 0);
+    }
+
+    // Cleanup from test.
+    common::cleanup_files(vec![TASK_PROFILE]);
+}
+
+#[test]
+// Confirm the load test refuses to start without task metrics to profile.
+fn test_task_profile_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let swanling_attack = common::build_load_test(
+        common_build_configuration(
+            &server,
+            &["--task-profile", TASK_PROFILE, "--no-task-metrics"],
+        ),
+        &taskset!("LoadTest").register_task(task!(get_pages)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
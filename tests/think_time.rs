@@ -0,0 +1,125 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// How long users think between requests, in milliseconds.
+const THINK_TIME: u64 = 500;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task, thinking after loading the index before loading the about page.
+pub async fn get_pages(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    let think_time = Duration::from_millis(THINK_TIME);
+    user.think_time(think_time, think_time).await;
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Run a load test with users thinking between requests, returning the metrics.
+fn run_load_test(server: &MockServer, custom: &[&str]) -> SwanlingMetrics {
+    common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(server, custom),
+            &taskset!("LoadTest").register_task(task!(get_pages)),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Confirm think time paces requests within a task without adding to their response time.
+fn test_think_time() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Think time is included in the task time by default.
+    let swanling_metrics = run_load_test(&server, &[]);
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(index_hits > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+    // Each user thinks for the whole think time before every request for the about page,
+    // so can't have made more than one more request for the index.
+    let iterations = RUN_TIME * 1_000 / THINK_TIME as usize + 1;
+    assert!(index_hits <= USERS * iterations);
+
+    let task = &swanling_metrics.tasks[0][0];
+    assert!(task.counter > 0);
+    assert!(task.min_time >= THINK_TIME as usize);
+    for request in swanling_metrics.requests.values() {
+        assert!(request.raw_data.maximum_time < THINK_TIME as usize);
+    }
+
+    // Think time can be excluded from the task time.
+    let swanling_metrics = run_load_test(&server, &["--exclude-think-time"]);
+    let task = &swanling_metrics.tasks[0][0];
+    assert!(task.counter > 0);
+    assert!(task.max_time < THINK_TIME as usize);
+}
+
+#[test]
+// Confirm the load test refuses to exclude think time from task metrics it doesn't track.
+fn test_think_time_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let swanling_attack = common::build_load_test(
+        common_build_configuration(&server, &["--exclude-think-time", "--no-task-metrics"]),
+        &taskset!("LoadTest").register_task(task!(get_pages)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
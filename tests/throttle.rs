@@ -324,3 +324,132 @@ fn test_throttle_gaggle() {
         Some(test1_lines),
     );
 }
+
+#[test]
+// Confirm time spent waiting in the throttle is tracked separately from response times,
+// and only when the throttle is enabled.
+fn test_throttle_wait() {
+    // Multiple tests run together, so set a unique name.
+    let request_log = "wait-".to_string() + REQUEST_LOG;
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common_build_configuration(
+        &server,
+        &request_log,
+        THROTTLE_REQUESTS,
+        USERS,
+        RUN_TIME,
+        None,
+        None,
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+
+    // Users spend most of their time waiting in the throttle, not on the fast mock server.
+    for request in swanling_metrics.requests.values() {
+        let throttle_wait = request.throttle_wait_data.as_ref().unwrap();
+        assert_eq!(throttle_wait.counter, request.raw_data.counter);
+        assert!(throttle_wait.total_time > request.raw_data.total_time);
+    }
+    assert!(swanling_metrics.to_string().contains("THROTTLE WAIT"));
+
+    // Cleanup log file.
+    std::fs::remove_file(&request_log).expect("failed to delete metrics log file");
+
+    // Without the throttle, no throttle wait is collected.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            "1",
+        ],
+    );
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+    assert!(swanling_metrics
+        .requests
+        .values()
+        .all(|request| request.throttle_wait_data.is_none()));
+    assert!(!swanling_metrics.to_string().contains("THROTTLE WAIT"));
+}
+
+#[test]
+// Confirm a load test that falls short of the throttled rate because the target is slow
+// reports the target as the bottleneck.
+fn test_throttle_efficiency() {
+    // How long the slow page takes to respond, in milliseconds.
+    const SLOW_PATH: &str = "/slow";
+    const SLOW_DELAY: u64 = 100;
+
+    // Test task.
+    pub async fn get_slow(user: &SwanlingUser) -> SwanlingTaskResult {
+        let _swanling = user.get(SLOW_PATH).await?;
+        Ok(())
+    }
+
+    // Start the mock server.
+    let server = MockServer::start();
+    let slow = server.mock(|when, then| {
+        when.method(GET).path(SLOW_PATH);
+        then.status(200)
+            .delay(std::time::Duration::from_millis(SLOW_DELAY));
+    });
+
+    // The users can make at most 50 requests per second, far fewer than the throttle allows.
+    let throttle_requests = 200;
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--throttle-requests",
+            &throttle_requests.to_string(),
+        ],
+    );
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_slow)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(slow.hits() > 0);
+
+    let efficiency = swanling_metrics.throttle_efficiency().unwrap();
+    assert_eq!(efficiency.intended, throttle_requests);
+    assert!(efficiency.achieved <= (USERS * 1_000) as f32 / SLOW_DELAY as f32);
+    assert!(efficiency.efficiency < swanling::metrics::THROTTLE_EFFICIENCY_SHORTFALL);
+    assert_eq!(
+        efficiency.bottleneck,
+        Some(swanling::metrics::SwanlingBottleneck::Target)
+    );
+
+    let report = swanling_metrics.to_string();
+    assert!(report.contains("THROTTLE EFFICIENCY"));
+    assert!(report.contains("Bottleneck: target"));
+}
@@ -0,0 +1,97 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 3;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    )
+}
+
+#[test]
+// Confirm requests are counted in each second of the load test, and summarized as the
+// stability of the throughput.
+fn test_throughput_stability() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let mut swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Metrics were reset after all users started, so the whole timeline is steady state.
+    assert_eq!(swanling_metrics.steady_state_started, 0);
+
+    // Every request counted since the metrics were reset is on the timeline.
+    let index_metrics = swanling_metrics.requests.get("GET /").unwrap();
+    assert_eq!(
+        swanling_metrics.throughput.iter().sum::<usize>(),
+        index_metrics.success_count
+    );
+    assert!(mock_endpoints[INDEX_KEY].hits() >= index_metrics.success_count);
+
+    // Throughput stability is measured over the duration of the load test.
+    let stability = swanling_metrics.throughput_stability().unwrap();
+    assert_eq!(stability.seconds, RUN_TIME);
+    assert!(stability.mean > 0.0);
+    assert!(stability.min <= stability.max);
+
+    // It's included in the final metrics and the html report.
+    swanling_metrics.set_display_metrics(true);
+    swanling_metrics.set_final_metrics(true);
+    assert!(swanling_metrics
+        .to_string()
+        .contains("=== THROUGHPUT STABILITY ==="));
+    assert!(swanling_metrics
+        .html_report("http://example.com")
+        .contains("Throughput Stability"));
+}
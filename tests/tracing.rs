@@ -0,0 +1,165 @@
+// Structured tracing is only available with the tracing feature enabled.
+#![cfg(feature = "tracing")]
+
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Collects the fields of a span or event as "name=value" strings.
+struct FieldCollector(Vec<String>);
+
+impl Visit for FieldCollector {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+}
+
+// The name and fields of a span.
+type RecordedSpan = (String, Vec<String>);
+
+// A minimal subscriber recording the name and fields of every span and event.
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    next_id: Arc<AtomicU64>,
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    events: Arc<Mutex<Vec<Vec<String>>>>,
+}
+
+impl Subscriber for RecordingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = FieldCollector(Vec::new());
+        span.record(&mut fields);
+        self.spans
+            .lock()
+            .unwrap()
+            .push((span.metadata().name().to_string(), fields.0));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = FieldCollector(Vec::new());
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+// Confirm users, tasks and requests are traced with identifying fields.
+fn test_tracing() {
+    let subscriber = RecordingSubscriber::default();
+    tracing::subscriber::set_global_default(subscriber.clone()).expect("failed to set subscriber");
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index).set_name("index")),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(hits > 0);
+
+    // A span was created for each user, identifying the user and its task set.
+    let spans = subscriber.spans.lock().unwrap();
+    let user_spans: Vec<&Vec<String>> = spans
+        .iter()
+        .filter(|(name, _)| name == "user")
+        .map(|(_, fields)| fields)
+        .collect();
+    assert_eq!(user_spans.len(), USERS);
+    for user in 1..=USERS {
+        assert!(user_spans
+            .iter()
+            .any(|fields| fields.contains(&format!("user={}", user))));
+    }
+    assert!(user_spans
+        .iter()
+        .all(|fields| fields.contains(&"task_set=LoadTest".to_string())));
+
+    // A span was created for each task iteration.
+    let task_spans = spans.iter().filter(|(name, _)| name == "task").count();
+    assert!(task_spans >= hits);
+    assert!(spans
+        .iter()
+        .filter(|(name, _)| name == "task")
+        .all(|(_, fields)| fields.contains(&"task=\"index\"".to_string())));
+
+    // An event was emitted for each request.
+    let events = subscriber.events.lock().unwrap();
+    let request_events = events
+        .iter()
+        .filter(|fields| fields.contains(&"request=index".to_string()))
+        .count();
+    assert_eq!(request_events, hits);
+}
@@ -0,0 +1,119 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the User-Agents below.
+const BROWSER_KEY: usize = 0;
+const BOT_KEY: usize = 1;
+
+// The weighted User-Agents to allocate.
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64)";
+const BOT_USER_AGENT: &str = "bot/1.0";
+
+// File the weighted User-Agents are written to.
+const USER_AGENTS: &str = "user-agents-test.txt";
+
+// Load test configuration.
+const USERS: usize = 4;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints, only responding to requests
+// with an expected User-Agent.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up BROWSER_USER_AGENT, store in vector at BROWSER_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("user-agent", BROWSER_USER_AGENT);
+            then.status(200);
+        }),
+        // Next set up BOT_USER_AGENT, store in vector at BOT_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("user-agent", BOT_USER_AGENT);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--user-agents",
+            USER_AGENTS,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm users are allocated User-Agents from the weighted list.
+fn test_user_agents() {
+    // Three of every four users send the browser User-Agent.
+    std::fs::write(
+        USER_AGENTS,
+        format!(
+            "# Browsers and bots.\n3 {}\n{}\n",
+            BROWSER_USER_AGENT, BOT_USER_AGENT
+        ),
+    )
+    .expect("failed to write user agents");
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Both User-Agents were sent, the browser by more users.
+    let browser_hits = mock_endpoints[BROWSER_KEY].hits();
+    let bot_hits = mock_endpoints[BOT_KEY].hits();
+    assert!(bot_hits > 0);
+    assert!(browser_hits > bot_hits * 2);
+
+    // All requests sent one of the listed User-Agents.
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.fail_count, 0);
+    assert_eq!(request_metrics.success_count, browser_hits + bot_hits);
+
+    // Cleanup from test.
+    common::cleanup_files(vec![USER_AGENTS]);
+}
@@ -0,0 +1,139 @@
+use httpmock::{HttpMockRequest, Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const ACCOUNT_PATH: &str = "/account";
+
+// Indexes to the classes of users below.
+const ANONYMOUS_KEY: usize = 0;
+const LOGGED_IN_KEY: usize = 1;
+
+// The classes of users, and the token only logged in users send.
+const ANONYMOUS: &str = "Anonymous";
+const LOGGED_IN: &str = "LoggedIn";
+const TOKEN: &str = "Bearer secret-token";
+
+// Load test configuration, four of every five users are anonymous.
+const USERS: usize = 5;
+const RUN_TIME: usize = 2;
+
+// Test task, naming the request after the class of the user making it.
+pub async fn get_account(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get_named(ACCOUNT_PATH, &user.user_class).await?;
+    Ok(())
+}
+
+// Anonymous users send no Authorization header.
+fn without_authorization(request: &HttpMockRequest) -> bool {
+    !request.headers.as_ref().is_some_and(|headers| {
+        headers
+            .iter()
+            .any(|(name, _)| name.to_lowercase() == "authorization")
+    })
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up ACCOUNT_PATH for anonymous users, store in vector at ANONYMOUS_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(ACCOUNT_PATH)
+                .matches(without_authorization);
+            then.status(200);
+        }),
+        // Next set up ACCOUNT_PATH for logged in users, store in vector at LOGGED_IN_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(ACCOUNT_PATH)
+                .header("authorization", TOKEN);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &users,
+            "--hatch-rate",
+            &users,
+            "--run-time",
+            &run_time,
+            "--no-reset-metrics",
+        ],
+    )
+}
+
+#[test]
+// Confirm users are launched by the weight of their class, and send its headers.
+fn test_user_classes() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            &taskset!(ANONYMOUS)
+                .set_weight(4)
+                .unwrap()
+                .register_task(task!(get_account)),
+            None,
+            None,
+        )
+        .register_taskset(
+            taskset!(LOGGED_IN)
+                .set_weight(1)
+                .unwrap()
+                .set_header("Authorization", TOKEN)
+                .unwrap()
+                .register_task(task!(get_account)),
+        ),
+        None,
+    );
+
+    // Users were allocated to each class by weight.
+    assert_eq!(
+        swanling_metrics.task_set_users[ANONYMOUS_KEY].name,
+        ANONYMOUS
+    );
+    assert_eq!(swanling_metrics.task_set_users[ANONYMOUS_KEY].users, 4);
+    assert_eq!(
+        swanling_metrics.task_set_users[LOGGED_IN_KEY].name,
+        LOGGED_IN
+    );
+    assert_eq!(swanling_metrics.task_set_users[LOGGED_IN_KEY].users, 1);
+
+    // Only logged in users sent the Authorization header.
+    let anonymous_hits = mock_endpoints[ANONYMOUS_KEY].hits();
+    let logged_in_hits = mock_endpoints[LOGGED_IN_KEY].hits();
+    assert!(logged_in_hits > 0);
+    assert!(anonymous_hits > logged_in_hits * 2);
+
+    // Each user knows its class.
+    let anonymous_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", ANONYMOUS))
+        .unwrap();
+    assert_eq!(anonymous_metrics.fail_count, 0);
+    assert_eq!(anonymous_metrics.success_count, anonymous_hits);
+    let logged_in_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", LOGGED_IN))
+        .unwrap();
+    assert_eq!(logged_in_metrics.fail_count, 0);
+    assert_eq!(logged_in_metrics.success_count, logged_in_hits);
+}
@@ -0,0 +1,152 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 3;
+const MAX_FAILURES: usize = 3;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints. The index always fails, the about
+// page always succeeds.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(500);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: &[&str]) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(custom);
+    common::build_configuration(server, configuration)
+}
+
+#[test]
+// Confirm users retire after failing the configured number of requests in a row.
+fn test_user_max_consecutive_failures() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let max_failures = MAX_FAILURES.to_string();
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(
+                &server,
+                &["--user-max-consecutive-failures", &max_failures],
+            ),
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Each user stopped after failing exactly the configured number of requests.
+    assert_eq!(mock_endpoints[INDEX_KEY].hits(), USERS * MAX_FAILURES);
+    let request_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert_eq!(request_metrics.fail_count, USERS * MAX_FAILURES);
+}
+
+#[test]
+// Confirm a successful request starts counting consecutive failures over.
+fn test_user_max_consecutive_failures_reset() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack, each user alternating between a failing and a successful
+    // request so it never fails twice in a row.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, &["--user-max-consecutive-failures", "2"]),
+            &taskset!("LoadTest")
+                .register_task(task!(get_index))
+                .register_task(task!(get_about)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // The users kept running for the whole load test.
+    assert!(mock_endpoints[INDEX_KEY].hits() > USERS * MAX_FAILURES);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > USERS * MAX_FAILURES);
+    assert!(swanling_metrics.duration >= RUN_TIME);
+}
+
+#[test]
+// Confirm the load test refuses to start without a usable limit.
+fn test_user_max_consecutive_failures_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // The limit must be at least 1.
+    let configuration =
+        common_build_configuration(&server, &["--user-max-consecutive-failures", "0"]);
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(taskset!("LoadTest").register_task(task!(get_index)));
+    assert!(swanling_attack.execute().is_err());
+
+    // Failures can't be counted without metrics.
+    let configuration = common_build_configuration(
+        &server,
+        &["--user-max-consecutive-failures", "1", "--no-metrics"],
+    );
+    let swanling_attack = SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(taskset!("LoadTest").register_task(task!(get_index)));
+    assert!(swanling_attack.execute().is_err());
+}
@@ -0,0 +1,659 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Validate the configuration built from these options, returning how many requests the
+// mock server received and the result.
+fn validate_config(custom: Vec<&str>) -> (usize, Result<SwanlingMetrics, SwanlingError>) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let mut configuration = vec!["--validate-config"];
+    configuration.extend(custom);
+    let result = common::build_load_test(
+        common::build_configuration(&server, configuration),
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    )
+    .execute();
+
+    (mock_endpoints[INDEX_KEY].hits(), result)
+}
+
+#[test]
+// Confirm a valid configuration is accepted without running the load test.
+fn test_validate_config() {
+    let (hits, result) = validate_config(vec![]);
+
+    assert!(result.is_ok());
+    assert_eq!(hits, 0);
+}
+
+#[test]
+// Confirm all problems with an invalid configuration are reported together.
+fn test_validate_config_invalid() {
+    let (hits, result) = validate_config(vec![
+        "--throttle-requests",
+        "2000000",
+        "--expect-workers",
+        "2",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 2);
+            assert!(problems[0].starts_with("--expect-workers = 2:"));
+            assert!(problems[1].starts_with("--throttle-requests = 2000000:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --gaggle-transport is only accepted in a Regatta.
+fn test_validate_config_gaggle_transport() {
+    let (hits, result) = validate_config(vec!["--gaggle-transport", "inproc"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--gaggle-transport = inproc:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --worker-metrics-interval is only accepted on a Worker.
+fn test_validate_config_worker_metrics_interval() {
+    let (hits, result) = validate_config(vec!["--worker-metrics-interval", "500"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--worker-metrics-interval = 500:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --worker-metrics-buffer is only accepted on a Worker.
+fn test_validate_config_worker_metrics_buffer() {
+    let (hits, result) = validate_config(vec!["--worker-metrics-buffer", "10000"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--worker-metrics-buffer = 10000:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --capture-failure-bodies requires the debug log.
+fn test_validate_config_capture_failure_bodies() {
+    let (hits, result) = validate_config(vec!["--capture-failure-bodies"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--capture-failure-bodies = true:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --baseline must be readable, and --regression-threshold requires it.
+fn test_validate_config_baseline() {
+    let (hits, result) = validate_config(vec![
+        "--baseline",
+        "missing-baseline.json",
+        "--regression-threshold",
+        "5",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--baseline = missing-baseline.json:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec!["--regression-threshold", "5"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--regression-threshold = 5:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm the Regatta socket options are only accepted in a Regatta.
+fn test_validate_config_gaggle_socket_options() {
+    let (hits, result) = validate_config(vec![
+        "--gaggle-send-buffer",
+        "64",
+        "--gaggle-recv-timeout",
+        "30000",
+        "--gaggle-max-msg-size",
+        "4194304",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 3);
+            assert!(problems[0].starts_with("--gaggle-send-buffer = 64:"));
+            assert!(problems[1].starts_with("--gaggle-recv-timeout = 30000:"));
+            assert!(problems[2].starts_with("--gaggle-max-msg-size = 4194304:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --smoke can't run in a Regatta.
+fn test_validate_config_smoke() {
+    let (hits, result) = validate_config(vec!["--smoke", "--manager", "--expect-workers", "1"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--smoke = true:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --session-file can't be used in a Regatta.
+fn test_validate_config_session_file() {
+    let (hits, result) = validate_config(vec![
+        "--session-file",
+        "sessions.json",
+        "--manager",
+        "--expect-workers",
+        "1",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--session-file = sessions.json:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --manager-sockets is only accepted in a Regatta, and must be in range.
+fn test_validate_config_manager_sockets() {
+    let (hits, result) = validate_config(vec!["--manager-sockets", "4"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--manager-sockets = 4:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec![
+        "--manager-sockets",
+        "0",
+        "--manager",
+        "--expect-workers",
+        "1",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--manager-sockets = 0:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --worker-startup-jitter is only accepted on a Worker, and required by its seed.
+fn test_validate_config_worker_startup_jitter() {
+    let (hits, result) = validate_config(vec![
+        "--worker-startup-jitter",
+        "500",
+        "--worker-startup-seed",
+        "42",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--worker-startup-jitter = 500:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec!["--worker-startup-seed", "42"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--worker-startup-seed = 42:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --adaptive-target-p95 requires a throttle to start from.
+fn test_validate_config_adaptive_target_p95() {
+    let (hits, result) = validate_config(vec!["--adaptive-target-p95", "250"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--adaptive-target-p95 = 250:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --group-by requires metrics.
+fn test_validate_config_group_by() {
+    let (hits, result) = validate_config(vec!["--group-by", "region", "--no-metrics"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            // The --co-mitigation set by the tests also requires metrics.
+            assert_eq!(problems.len(), 2);
+            assert!(problems[0].starts_with("--co-mitigation = "));
+            assert!(problems[1].starts_with("--group-by = region:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --junit-xml can't be written by a smoke test.
+fn test_validate_config_junit_xml() {
+    let (hits, result) = validate_config(vec!["--junit-xml", "junit.xml", "--smoke"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--junit-xml = junit.xml:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --base-path must be a path.
+fn test_validate_config_base_path() {
+    let (hits, result) = validate_config(vec!["--base-path", "http://example.com/api"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--base-path = http://example.com/api:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --max-redirects can't disable redirects that --sticky-follow follows.
+fn test_validate_config_max_redirects() {
+    let (hits, result) = validate_config(vec!["--max-redirects", "0", "--sticky-follow"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--max-redirects = 0:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --error-tail must keep at least one error.
+fn test_validate_config_error_tail() {
+    let (hits, result) = validate_config(vec!["--error-tail", "0"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--error-tail = 0:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --request-log-sample is a fraction of the request log, and required by its seed.
+fn test_validate_config_request_log_sample() {
+    let (hits, result) = validate_config(vec![
+        "--request-log",
+        "validate-sample-request-log.json",
+        "--request-log-sample",
+        "1.5",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--request-log-sample = 1.5:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec!["--request-log-sample", "0.5"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--request-log-sample = 0.5:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec!["--request-log-seed", "42"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--request-log-seed = 42:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --ramp-to-failure can't be combined with --spike, and is required by its threshold.
+fn test_validate_config_ramp_to_failure() {
+    let (hits, result) = validate_config(vec!["--ramp-to-failure", "--spike", "2x,10s,every=60s"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--ramp-to-failure = true:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec!["--error-threshold", "5"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--error-threshold = 5:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --gaggle-ipc is only accepted in a Regatta.
+fn test_validate_config_gaggle_ipc() {
+    let (hits, result) = validate_config(vec!["--gaggle-ipc", "swanling.ipc"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--gaggle-ipc = swanling.ipc:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --region-header can't group requests by another label.
+fn test_validate_config_region_header() {
+    let (hits, result) = validate_config(vec!["--region-header", "cf-ray", "--group-by", "tier"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--region-header = cf-ray:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --prewarm-connections requires a connection pool.
+fn test_validate_config_prewarm_connections() {
+    let (hits, result) = validate_config(vec!["--prewarm-connections", "--pool-size", "0"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--prewarm-connections = true:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm InfluxDB line protocol is only written by a Manager aggregating windows, to a
+// valid address.
+fn test_validate_config_influx() {
+    let (hits, result) = validate_config(vec!["--influx-file", "influx.txt"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--influx-file = influx.txt:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec![
+        "--influx-file",
+        "influx.txt",
+        "--manager",
+        "--expect-workers",
+        "1",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--influx-file = influx.txt:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec![
+        "--influx-udp",
+        "telegraf",
+        "--manager",
+        "--expect-workers",
+        "1",
+        "--aggregate-window",
+        "10",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--influx-udp = telegraf:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm task sets are only pinned by a Manager, to Workers that are expected.
+fn test_validate_config_pin_task_set() {
+    let (hits, result) = validate_config(vec!["--pin-task-set", "LoadTest:1"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--pin-task-set = LoadTest:1:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    for pin in &["LoadTest", "LoadTest:3", "Missing:1"] {
+        let (hits, result) = validate_config(vec![
+            "--pin-task-set",
+            pin,
+            "--manager",
+            "--expect-workers",
+            "2",
+            "--users",
+            "2",
+        ]);
+
+        assert_eq!(hits, 0);
+        match result {
+            Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+                assert_eq!(problems.len(), 1);
+                assert!(problems[0].starts_with(&format!("--pin-task-set = {}:", pin)));
+            }
+            _ => panic!("expected an invalid configuration"),
+        }
+    }
+}
+
+#[test]
+// Confirm --iterations-per-user must be at least 1.
+fn test_validate_config_iterations_per_user() {
+    let (hits, result) = validate_config(vec!["--iterations-per-user", "0"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--iterations-per-user = 0:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --har must be a readable HAR file, and --har-timing requires --har.
+fn test_validate_config_har() {
+    let (hits, result) = validate_config(vec!["--har", "missing.har"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--har = missing.har:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec!["--har-timing"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--har-timing = true:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}
+
+#[test]
+// Confirm --load-profile must be a readable profile, and can't be combined with --spike.
+fn test_validate_config_load_profile() {
+    let (hits, result) = validate_config(vec!["--load-profile", "missing.csv"]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("--load-profile = missing.csv:"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+
+    let (hits, result) = validate_config(vec![
+        "--load-profile",
+        "missing.csv",
+        "--spike",
+        "2x,10s,every=60s",
+    ]);
+
+    assert_eq!(hits, 0);
+    match result {
+        Err(SwanlingError::InvalidConfiguration { problems, .. }) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].contains("can not be set together with the --spike option"));
+        }
+        _ => panic!("expected an invalid configuration"),
+    }
+}